@@ -0,0 +1,22 @@
+// Benchmarks do parser (cargo bench): o array de 300 eventos que a API devolve com
+// --all, tirado de tests/fixtures/events_300.json (gerado com `generate --events 300`)
+//
+// Cada execução imprime o tempo médio e a diferença para a anterior; os números de
+// referência (antes e depois da otimização do leitor de JSON) estão no README
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use github_activity::parser;
+
+const EVENTS_300: &str = include_str!("../tests/fixtures/events_300.json");
+
+fn parse_300_events(c: &mut Criterion) {
+    c.bench_function("parse_events (300 events)", |b| {
+        b.iter(|| parser::parse_events(black_box(EVENTS_300)).unwrap())
+    });
+}
+
+criterion_group!(benches, parse_300_events);
+criterion_main!(benches);
@@ -0,0 +1,17 @@
+// Bytes quaisquer (convertidos com from_utf8_lossy, como um corpo de resposta qualquer)
+// no parser: Ok ou Err, nunca um panic
+//
+// cargo +nightly fuzz run parse_events
+// A semente (corpus/parse_events) são respostas reais da API, as de tests/fixtures;
+// um crash encontrado vira um caso em tests/fuzz_regressions.rs
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use github_activity::parser;
+
+fuzz_target!(|data: &[u8]| {
+    let text = String::from_utf8_lossy(data);
+    let _ = parser::parse_events(&text);
+});
@@ -1,50 +1,1577 @@
-// Este módulo gerencia a comunicação com a API do GitHub
-// Demonstra como fazer requisições HTTP e integrar módulos em Rust
+//! Este módulo gerencia a comunicação com a API do GitHub
+//! Demonstra como fazer requisições HTTP e integrar módulos em Rust
 
 // CONCEITO: use e crate::
 // 'use' importa itens de outros módulos
 // 'crate::' é o caminho absoluto a partir da raiz do nosso projeto
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Read;
+use std::mem;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use flate2::read::GzDecoder;
+
+use crate::cache::{Cache, CacheEntry};
+use crate::config;
+use crate::date::Timestamp;
 use crate::error::ActivityError;
-use crate::models::GitHubEvent;
-use crate::parser;
+use crate::logging::{self, Level};
+use crate::models::{GitHubEvent, Repository, UserProfile};
+use crate::parser::{self, ParseOutcome, ParseWarning};
 
 // CONCEITO: const
 // Constantes são imutáveis e conhecidas em tempo de compilação
 // Por convenção, usam SCREAMING_SNAKE_CASE
-const GITHUB_API_BASE: &str = "https://api.github.com";
+/// Endereço padrão da API (o do github.com)
+pub const GITHUB_API_BASE: &str = "https://api.github.com";
 
 // GitHub requer um User-Agent header em todas as requisições
-// Isso identifica nossa aplicação
+// Isso identifica nossa aplicação (o padrão de GitHubClientBuilder::user_agent)
 const USER_AGENT: &str = "github-activity-cli/1.0";
 
-// Função principal que busca eventos de um usuário
+/// Versão da API pedida por padrão (header X-GitHub-Api-Version)
+/// Fixar a versão evita que uma mudança no padrão do GitHub altere as respostas
+pub const GITHUB_API_VERSION: &str = "2022-11-28";
+
+// Tipo de mídia recomendado pelo GitHub para a API REST
+const ACCEPT: &str = "application/vnd.github+json";
+
+// Paginação do modo --all
+// A API guarda no máximo 300 eventos por usuário/repositório:
+// por padrão, 3 páginas de 100 eventos cobrem todo o histórico disponível
+pub(crate) const HISTORY_PER_PAGE: usize = 100;
+pub(crate) const HISTORY_EVENTS: usize = 300;
+
+// Com --all, quantas páginas de uma lista de repositórios buscar no máximo (1000 com 100 por página)
+// Diferente dos eventos, essas listas não têm um fim imposto pela API
+const LIST_MAX_PAGES: usize = 10;
+
+/// Maior tamanho de página aceito pela API (--per-page)
+pub const MAX_PER_PAGE: usize = 100;
+
+/// Maior espera antes de repetir uma requisição barrada pelo limite secundário (--wait-on-limit)
+pub const MAX_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// Novas tentativas de um POST de webhook que falhou com 5xx ou sem conexão
+pub const WEBHOOK_RETRIES: u32 = 2;
+
+// A espera antes da primeira nova tentativa; cada uma espera o dobro da anterior
+const WEBHOOK_RETRY_WAIT: Duration = Duration::from_millis(500);
+
+/// Quantos redirecionamentos uma requisição segue antes de desistir (ver send_request)
+pub const MAX_REDIRECTS: usize = 5;
+
+// Headers de resposta úteis para diagnóstico, mostrados com -vv
+// (o limite de requisições também aparece resumido com -v)
+const LOGGED_HEADERS: &[&str] = &[
+    "x-ratelimit-limit",
+    "x-ratelimit-remaining",
+    "x-ratelimit-reset",
+    "etag",
+    "link",
+    "content-type",
+];
+
+/// De quem (ou de onde) buscar a atividade
+/// Regra de ambiguidade: um argumento com barra ("rust-lang/rust") é SEMPRE um repositório,
+/// já que usernames do GitHub não podem conter '/'
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// Os eventos de um usuário
+    User(String),
+    /// Os eventos de um repositório
+    Repo {
+        /// Dono do repositório (usuário ou organização)
+        owner: String,
+        /// Nome do repositório
+        repo: String,
+    },
+}
+
+impl Target {
+    /// Interpreta "torvalds" ou "rust-lang/rust"
+    pub fn parse(arg: &str) -> Target {
+        // CONCEITO: split_once
+        // Divide na PRIMEIRA ocorrência do separador; "a/b/c" vira ("a", "b/c")
+        // e validate_repo rejeita o restante
+        match arg.split_once('/') {
+            Some((owner, repo)) => Target::Repo {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+            },
+            None => Target::User(arg.to_string()),
+        }
+    }
+
+    /// Valida o alvo sem fazer requisições, como as buscas fazem: pelas regras do
+    /// github.com ou, com `skip_validation`, só pelo que a URL exige
+    pub fn validate(&self, skip_validation: bool) -> Result<(), ActivityError> {
+        match (self, skip_validation) {
+            (Target::User(username), false) => validate_username(username),
+            (Target::User(username), true) => validate_path_segment(username),
+            (Target::Repo { owner, repo }, false) => validate_repo(owner, repo),
+            (Target::Repo { owner, repo }, true) => {
+                validate_path_segment(owner)?;
+                validate_path_segment(repo)
+            }
+        }
+    }
+}
+
+// "torvalds" ou "rust-lang/rust", como o usuário digitou
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Target::User(username) => write!(f, "{}", username),
+            Target::Repo { owner, repo } => write!(f, "{}/{}", owner, repo),
+        }
+    }
+}
+
+/// Cliente da API do GitHub: guarda a configuração usada em todas as requisições
+/// (endereço, token, tempo limite...), então cada busca só recebe o que muda entre elas
+///
+/// O padrão é a API pública do github.com, sem autenticação. Para mudar algo, use o builder:
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use github_activity::api::{GitHubClient, Target};
+///
+/// let client = GitHubClient::builder()
+///     .token("ghp_...")
+///     .timeout(Duration::from_secs(10))
+///     .build();
+/// let pages = client.fetch_target_pages(&Target::parse("torvalds"), false)?;
+/// println!("{} events", pages.outcome.events.len());
+/// # Ok::<(), github_activity::ActivityError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct GitHubClient {
+    // Os campos são os de GitHubClientBuilder (veja a documentação de cada método de lá)
+    pub(crate) base_url: String,
+    pub(crate) token: Option<String>,
+    pub(crate) api_version: String,
+    pub(crate) user_agent: String,
+    pub(crate) compression: bool,
+    pub(crate) per_page: Option<usize>,
+    pub(crate) max_events: Option<usize>,
+    pub(crate) max_pages: Option<usize>,
+    pub(crate) wait_on_limit: bool,
+    pub(crate) skip_validation: bool,
+    // Cliente do reqwest, com o mesmo tempo limite (só com a feature "async")
+    #[cfg(feature = "async")]
+    pub(crate) http: reqwest::Client,
+    // CONCEITO: Clone barato
+    // Um Agent guarda o pool de conexões atrás de um Arc: clones (inclusive em outras
+    // threads, em fetch_parallel) compartilham as mesmas conexões já abertas
+    agent: ureq::Agent,
+    // O limite de requisições da última resposta bem-sucedida (ver rate_limit()),
+    // também compartilhado entre os clones
+    last_rate_limit: Arc<Mutex<Option<RateLimitInfo>>>,
+    // Logins renomeados descobertos pelos redirecionamentos: (antigo, novo)
+    renames: Arc<Mutex<Vec<(String, String)>>>,
+    // Respostas guardadas para revalidar com ETag (ver o módulo cache)
+    cache: Option<Cache>,
+    max_age: Option<Duration>,
+    // Logins cujos eventos vieram do cache sem requisição: (login, idade da entrada)
+    cache_hits: Arc<Mutex<Vec<(String, Duration)>>>,
+}
+
+impl Default for GitHubClient {
+    fn default() -> Self {
+        GitHubClient::builder().build()
+    }
+}
+
+/// Monta um [`GitHubClient`]: cada método troca um valor padrão e devolve o builder
+// CONCEITO: Builder pattern
+// Os métodos recebem `self` por valor e o devolvem, então as chamadas se encadeiam:
+// GitHubClient::builder().token(..).timeout(..).build()
+#[derive(Debug, Clone)]
+pub struct GitHubClientBuilder {
+    base_url: String,
+    token: Option<String>,
+    api_version: String,
+    user_agent: String,
+    compression: bool,
+    per_page: Option<usize>,
+    max_events: Option<usize>,
+    max_pages: Option<usize>,
+    wait_on_limit: bool,
+    skip_validation: bool,
+    timeout: Option<Duration>,
+    cache: Option<Cache>,
+    max_age: Option<Duration>,
+}
+
+impl Default for GitHubClientBuilder {
+    fn default() -> Self {
+        GitHubClientBuilder {
+            base_url: GITHUB_API_BASE.to_string(),
+            token: None,
+            api_version: GITHUB_API_VERSION.to_string(),
+            user_agent: USER_AGENT.to_string(),
+            compression: true,
+            per_page: None,
+            max_events: None,
+            max_pages: None,
+            wait_on_limit: false,
+            skip_validation: false,
+            timeout: None,
+            cache: None,
+            max_age: None,
+        }
+    }
+}
+
+impl GitHubClientBuilder {
+    /// Endereço base (GitHub Enterprise: "https://host/api/v3"); a barra final é opcional
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Token de acesso; aumenta o limite de requisições de 60 para 5000 por hora
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Valor do header X-GitHub-Api-Version (padrão: [`GITHUB_API_VERSION`])
+    pub fn api_version(mut self, api_version: impl Into<String>) -> Self {
+        self.api_version = api_version.into();
+        self
+    }
+
+    /// Valor do header User-Agent, que o GitHub exige em toda requisição
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Pede respostas comprimidas com gzip (padrão: true; --no-compression desliga)
+    pub fn compression(mut self, compression: bool) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Eventos por página, de 1 a [`MAX_PER_PAGE`] (--per-page)
+    /// Sem isso vale o padrão da API: 30 em uma busca simples, 100 com `all`
+    pub fn per_page(mut self, per_page: usize) -> Self {
+        self.per_page = Some(per_page);
+        self
+    }
+
+    /// Para de buscar páginas quando já há esta quantidade de eventos (--max-events)
+    pub fn max_events(mut self, max_events: usize) -> Self {
+        self.max_events = Some(max_events);
+        self
+    }
+
+    /// Máximo de páginas buscadas com `all` (sem isso, as que cobrem todo o histórico)
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = Some(max_pages);
+        self
+    }
+
+    /// Barrado pelo limite secundário, espera o Retry-After (no máximo [`MAX_LIMIT_WAIT`])
+    /// e tenta a requisição mais uma vez (--wait-on-limit). Sem isso, devolve o erro na hora
+    pub fn wait_on_limit(mut self, wait_on_limit: bool) -> Self {
+        self.wait_on_limit = wait_on_limit;
+        self
+    }
+
+    /// Não aplica as regras de username do github.com (--skip-validation), para instâncias
+    /// do GitHub Enterprise com outras regras; só é recusado o que quebraria a URL
+    pub fn skip_validation(mut self, skip_validation: bool) -> Self {
+        self.skip_validation = skip_validation;
+        self
+    }
+
+    /// Tempo limite de cada requisição (conexão + resposta); sem isso, espera indefinidamente
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Guarda a primeira página dos eventos de cada usuário em `cache` e a revalida com
+    /// If-None-Match: um `304 Not Modified` usa o corpo guardado (e não gasta do limite)
+    /// Sem isso (ou com --no-cache), toda busca baixa tudo de novo
+    pub fn cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Com cache, usa uma entrada mais nova que `max_age` sem perguntar nada à API
+    /// (--max-age). Zero (ou sem isso) revalida sempre; uma entrada de idade desconhecida
+    /// (sem o horário da busca, ou com o relógio atrasado) também é revalidada
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Cria o cliente
+    /// Os valores só são validados na primeira busca, antes de qualquer requisição
+    pub fn build(self) -> GitHubClient {
+        // Os redirecionamentos são seguidos à mão, em send_request: assim um usuário
+        // renomeado aparece (em vez de sumir num redirecionamento silencioso)
+        let mut agent = ureq::AgentBuilder::new().redirects(0);
+        if let Some(timeout) = self.timeout {
+            agent = agent.timeout(timeout);
+        }
+
+        #[cfg(feature = "async")]
+        let http = {
+            // Sem redirecionamentos automáticos aqui também: send_request_async segue
+            // cada um com a mesma RedirectChain do cliente síncrono
+            let mut http = reqwest::Client::builder().redirect(reqwest::redirect::Policy::none());
+            if let Some(timeout) = self.timeout {
+                http = http.timeout(timeout);
+            }
+            // Só falha se o TLS não puder ser iniciado; Client::new() teria o mesmo problema
+            http.build().unwrap_or_else(|_| reqwest::Client::new())
+        };
+
+        GitHubClient {
+            base_url: self.base_url,
+            token: self.token,
+            api_version: self.api_version,
+            user_agent: self.user_agent,
+            compression: self.compression,
+            per_page: self.per_page,
+            max_events: self.max_events,
+            max_pages: self.max_pages,
+            wait_on_limit: self.wait_on_limit,
+            skip_validation: self.skip_validation,
+            #[cfg(feature = "async")]
+            http,
+            agent: agent.build(),
+            last_rate_limit: Arc::default(),
+            renames: Arc::default(),
+            cache: self.cache,
+            max_age: self.max_age,
+            cache_hits: Arc::default(),
+        }
+    }
+}
+
+/// Eventos buscados junto com o corpo bruto de cada página, na ordem em que chegaram
+/// Os corpos são o que --record grava em disco (ver o módulo recording)
+#[derive(Debug, Default)]
+pub struct FetchedPages {
+    /// Eventos (e avisos) de todas as páginas, já interpretados
+    pub outcome: ParseOutcome,
+    /// Corpo de cada resposta, exatamente como o GitHub mandou
+    pub bodies: Vec<String>,
+}
+
+/// As páginas de eventos de um alvo, buscadas uma a uma conforme o iterador avança
+/// (criado por [`GitHubClient::event_pages`])
+///
+/// Cada `next()` faz UMA requisição e devolve os eventos daquela página, na ordem da API,
+/// seguindo as mesmas regras de paginação de [`GitHubClient::fetch_target_pages`].
+/// Os eventos que o parser pulou ficam guardados até [`EventPages::take_warnings`].
+/// Depois de um erro o iterador termina
+#[derive(Debug)]
+pub struct EventPages<'a> {
+    client: &'a GitHubClient,
+    url: String,
+    all: bool,
+    // Para traduzir um 404 em UserNotFound (None em um repositório)
+    username: Option<String>,
+    // Próxima página a buscar (1 = a primeira)
+    page: usize,
+    finished: bool,
+    // Eventos já devolvidos, para --max-events
+    delivered: usize,
+    // A última página segundo o Link header (rel="last"), quando a API informa
+    last_page: Option<usize>,
+    // Eventos + avisos das páginas anteriores: os índices dos avisos continuam daqui
+    offset: usize,
+    warnings: Vec<ParseWarning>,
+    bodies: Vec<String>,
+}
+
+// CONCEITO: Implementando Iterator
+// Basta definir o tipo Item e next(); map, take, collect... vêm de graça
+// Aqui cada item é uma página: Ok com os eventos dela, ou o erro que interrompeu a busca
+impl Iterator for EventPages<'_> {
+    type Item = Result<Vec<GitHubEvent>, ActivityError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let page = self.fetch_page();
+        if page.is_err() {
+            self.finished = true;
+        }
+        Some(page.map_err(|e| self.map_error(e)))
+    }
+}
+
+impl EventPages<'_> {
+    /// Os avisos das páginas devolvidas até agora que ainda não foram pegos
+    /// Os índices contam desde o primeiro evento da primeira página
+    pub fn take_warnings(&mut self) -> Vec<ParseWarning> {
+        mem::take(&mut self.warnings)
+    }
+
+    fn fetch_page(&mut self) -> Result<Vec<GitHubEvent>, ActivityError> {
+        let (response, outcome) = self.client.fetch_events(&self.next_url())?;
+        Ok(self.accept(response, outcome))
+    }
+
+    // A paginação fica toda aqui; a requisição em si é de quem chama (fetch_page ou,
+    // com a feature "async", GitHubClient::collect_pages_async), então os dois clientes
+    // param na mesma página e juntam os avisos do mesmo jeito
+    //
+    // Sem `all`, uma única requisição (a primeira página da API)
+    // Com `all`, página por página até uma página vir incompleta,
+    // o histórico acabar ou já haver `max_events` eventos
+    pub(crate) fn next_url(&self) -> String {
+        if self.all {
+            page_url(&self.url, Some(self.client.page_size()), Some(self.page))
+        } else {
+            page_url(&self.url, self.client.per_page, None)
+        }
+    }
+
+    // Já não há o que buscar (a última página chegou)?
+    pub(crate) fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    // No endpoint de usuário, um 404 vira UserNotFound
+    pub(crate) fn map_error(&self, error: ActivityError) -> ActivityError {
+        match &self.username {
+            Some(username) => user_not_found(error, username),
+            None => error,
+        }
+    }
+
+    // Registra a resposta de next_url() e devolve os eventos dela que cabem em max_events
+    pub(crate) fn accept(&mut self, response: ApiResponse, outcome: ParseOutcome) -> Vec<GitHubEvent> {
+        // Eventos pulados também contam: a página veio cheia do servidor
+        let count = outcome.events.len() + outcome.warnings.len();
+
+        let offset = self.offset;
+        self.warnings.extend(outcome.warnings.into_iter().map(|warning| warning.shifted(offset)));
+        self.offset += count;
+        // A última página não traz rel="last": vale o da página anterior
+        self.last_page = response.last_page.or(self.last_page);
+        self.bodies.push(response.body);
+
+        // A última página pode passar do limite: os eventos a mais são descartados
+        let mut events = outcome.events;
+        if let Some(max) = self.client.max_events {
+            events.truncate(max.saturating_sub(self.delivered));
+        }
+        self.delivered += events.len();
+
+        self.finished = self.client.is_last_page(self.all, count, self.page, self.client.history_pages(), self.delivered);
+        self.page += 1;
+
+        events
+    }
+
+    /// Páginas já buscadas
+    pub fn pages_fetched(&self) -> usize {
+        self.page - 1
+    }
+
+    /// Eventos já devolvidos (depois do corte de max_events)
+    pub fn events_fetched(&self) -> usize {
+        self.delivered
+    }
+
+    /// Quantas páginas a busca terá, quando dá para saber: sem `all`, uma;
+    /// com `all`, o `rel="last"` do Link header da primeira página (limitado por
+    /// max_pages e max_events). None antes da primeira página ou sem o header
+    pub fn total_pages(&self) -> Option<usize> {
+        if !self.all {
+            return Some(1);
+        }
+
+        let mut total = self.last_page?.min(self.client.history_pages());
+        if let Some(max) = self.client.max_events {
+            total = total.min(max.div_ceil(self.client.page_size()).max(1));
+        }
+        Some(total)
+    }
+
+    // Junta todas as páginas, com os corpos das respostas (o que fetch_*_pages devolvem)
+    fn collect_all(self) -> Result<FetchedPages, ActivityError> {
+        self.collect_while(|_| true)
+    }
+
+    /// Junta as páginas, com os corpos das respostas, enquanto `keep_going` responder true
+    ///
+    /// A pergunta é feita antes de cada requisição, com o iterador no estado atual
+    /// (ver [`EventPages::pages_fetched`]): serve para mostrar o progresso e para
+    /// parar no meio (ex: depois de um Ctrl-C) sem perder o que as páginas anteriores trouxeram
+    pub fn collect_while(mut self, mut keep_going: impl FnMut(&Self) -> bool) -> Result<FetchedPages, ActivityError> {
+        let mut events = Vec::new();
+        while !self.is_finished() && keep_going(&self) {
+            match self.next() {
+                Some(page) => events.extend(page?),
+                None => break,
+            }
+        }
+
+        Ok(self.into_fetched(events))
+    }
+
+    // Os eventos juntados, com os avisos e os corpos guardados pelo caminho
+    pub(crate) fn into_fetched(self, events: Vec<GitHubEvent>) -> FetchedPages {
+        FetchedPages { outcome: ParseOutcome { events, warnings: self.warnings }, bodies: self.bodies }
+    }
+}
+
+/// Função principal que busca eventos de um usuário (com um [`GitHubClient`] padrão)
 // CONCEITO: Assinatura de função com Result
-// -> Result<Vec<GitHubEvent>, ActivityError> significa:
+// -> Result<ParseOutcome, ActivityError> significa:
 // "Esta função pode retornar Ok(Vec de eventos) ou Err(erro)"
-pub fn fetch_user_events(username: &str) -> Result<Vec<GitHubEvent>, ActivityError> {
-    // Valida o username antes de fazer a requisição
-    // O operador ? propaga o erro se a validação falhar
-    validate_username(username)?;
+pub fn fetch_user_events(username: &str) -> Result<ParseOutcome, ActivityError> {
+    GitHubClient::default().fetch_user_events(username)
+}
 
-    // CONCEITO: format! macro
-    // Cria uma String interpolando valores
-    // {} é substituído pelos argumentos
-    let url = format!("{}/users/{}/events", GITHUB_API_BASE, username);
+/// Busca os eventos de um repositório ("rust-lang/rust")
+/// Usado quando o argumento da linha de comando contém uma barra
+pub fn fetch_repo_events(owner: &str, repo: &str) -> Result<ParseOutcome, ActivityError> {
+    GitHubClient::default().fetch_repo_events(owner, repo)
+}
 
-    // Faz a requisição HTTP
-    let response_text = make_http_request(&url)?;
+/// O perfil público de um usuário (nome, seguidores, repositórios...)
+pub fn fetch_user_profile(username: &str) -> Result<UserProfile, ActivityError> {
+    GitHubClient::default().fetch_user_profile(username)
+}
 
-    // Parseia o JSON usando nosso parser manual
-    // parser::parse_events refere-se à função parse_events do módulo parser
-    let events = parser::parse_events(&response_text)?;
+/// Versões "--all": percorrem todas as páginas do histórico
+pub fn fetch_all_user_events(username: &str) -> Result<ParseOutcome, ActivityError> {
+    GitHubClient::default().fetch_user_pages(username, true).map(|pages| pages.outcome)
+}
 
-    // Retorna os eventos parseados
-    Ok(events)
+/// Todas as páginas de eventos de um repositório (a versão "--all" de fetch_repo_events)
+pub fn fetch_all_repo_events(owner: &str, repo: &str) -> Result<ParseOutcome, ActivityError> {
+    GitHubClient::default().fetch_repo_pages(owner, repo, true).map(|pages| pages.outcome)
 }
 
-// Valida se o username é válido
+impl GitHubClient {
+    /// Um builder com os valores padrão (ver [`GitHubClientBuilder`])
+    pub fn builder() -> GitHubClientBuilder {
+        GitHubClientBuilder::default()
+    }
+
+    /// A primeira página de eventos de um usuário
+    pub fn fetch_user_events(&self, username: &str) -> Result<ParseOutcome, ActivityError> {
+        self.fetch_user_pages(username, false).map(|pages| pages.outcome)
+    }
+
+    /// A primeira página de eventos de um repositório
+    pub fn fetch_repo_events(&self, owner: &str, repo: &str) -> Result<ParseOutcome, ActivityError> {
+        self.fetch_repo_pages(owner, repo, false).map(|pages| pages.outcome)
+    }
+
+    /// O limite de requisições informado pela última resposta bem-sucedida
+    /// (headers X-RateLimit-*); None antes da primeira ou se a API não informou
+    pub fn rate_limit(&self) -> Option<RateLimitInfo> {
+        *self.last_rate_limit.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// O login atual de `login`, se a API redirecionou uma requisição dele para outro
+    /// (o usuário foi renomeado); None se nenhum redirecionamento mudou o login
+    ///
+    /// ```no_run
+    /// # use github_activity::api::GitHubClient;
+    /// let client = GitHubClient::default();
+    /// client.fetch_user_events("old-login")?;
+    /// if let Some(login) = client.renamed_login("old-login") {
+    ///     eprintln!("Note: 'old-login' is now '{}'", login);
+    /// }
+    /// # Ok::<(), github_activity::ActivityError>(())
+    /// ```
+    pub fn renamed_login(&self, login: &str) -> Option<String> {
+        let renames = self.renames.lock().unwrap_or_else(|e| e.into_inner());
+        renames.iter().find(|(old, _)| old.eq_ignore_ascii_case(login)).map(|(_, new)| new.clone())
+    }
+
+    /// A idade da entrada do cache usada no lugar da busca dos eventos de `login`,
+    /// se eles vieram do cache sem requisição nenhuma (ver [`GitHubClientBuilder::max_age`])
+    pub fn cached_age(&self, login: &str) -> Option<Duration> {
+        let hits = self.cache_hits.lock().unwrap_or_else(|e| e.into_inner());
+        hits.iter().find(|(cached, _)| cached.eq_ignore_ascii_case(login)).map(|(_, age)| *age)
+    }
+
+    // Anota a troca de login entre a URL pedida e a que respondeu de fato
+    pub(crate) fn record_rename(&self, requested: &str, answered: &str) {
+        if let (Some(old), Some(new)) = (login_in(requested), login_in(answered)) {
+            if !old.eq_ignore_ascii_case(new) {
+                logging::log(Level::Info, || format!("'{}' was renamed to '{}'", old, new));
+                let mut renames = self.renames.lock().unwrap_or_else(|e| e.into_inner());
+                if !renames.iter().any(|(known, _)| known.eq_ignore_ascii_case(old)) {
+                    renames.push((old.to_string(), new.to_string()));
+                }
+            }
+        }
+    }
+
+    /// O cliente envia um token?
+    pub fn has_token(&self) -> bool {
+        self.token.is_some()
+    }
+
+    /// Uma requisição à raiz da API (`GET /`), só para saber se ela responde
+    ///
+    /// Devolve o status HTTP e quanto a resposta demorou: um status de erro também
+    /// é uma resposta (o DNS, a conexão e o TLS funcionaram); só falhas de rede viram Err
+    pub fn ping(&self) -> Result<(u16, Duration), ActivityError> {
+        self.validate()?;
+
+        let url = join_url(&self.base_url, "");
+        logging::log(Level::Info, || format!("GET {}", url));
+        let started = Instant::now();
+        match self.build_request(&url, Method::Get).call() {
+            Ok(response) => {
+                log_ureq_response(&response, started);
+                Ok((response.status(), started.elapsed()))
+            }
+            Err(ureq::Error::Status(status, response)) => {
+                log_ureq_response(&response, started);
+                Ok((status, started.elapsed()))
+            }
+            Err(e) => Err(ActivityError::from(Box::new(e))),
+        }
+    }
+
+    /// A cota de requisições como a API a informa em `GET /rate_limit`
+    /// (essa consulta não gasta da cota)
+    pub fn fetch_rate_limit(&self) -> Result<RateLimitInfo, ActivityError> {
+        self.validate()?;
+
+        let response = self.make_http_request(&self.rate_limit_url())?;
+        parser::parse_rate_limit(&response.body)
+    }
+
+    // Guarda o limite de uma resposta (também pelo cliente assíncrono)
+    // Uma resposta sem os headers não apaga o que já se sabia
+    pub(crate) fn record_rate_limit(&self, rate_limit: Option<RateLimitInfo>) {
+        if rate_limit.is_some() {
+            *self.last_rate_limit.lock().unwrap_or_else(|e| e.into_inner()) = rate_limit;
+        }
+    }
+
+    /// O perfil de um usuário: uma requisição a mais, que também gasta do limite
+    pub fn fetch_user_profile(&self, username: &str) -> Result<UserProfile, ActivityError> {
+        self.check_username(username)?;
+        self.validate()?;
+
+        let response = self.make_http_request(&self.user_url(username)).map_err(|e| user_not_found(e, username))?;
+        parser::parse_user_profile(&response.body)
+    }
+
+    /// O perfil do dono do token (`GET /user`), para descobrir o login de quem está autenticado
+    ///
+    /// Sem token a API responde 401 (um [`ActivityError::ApiError`])
+    pub fn fetch_authenticated_user(&self) -> Result<UserProfile, ActivityError> {
+        self.validate()?;
+
+        let response = self.make_http_request(&self.authenticated_user_url())?;
+        parser::parse_user_profile(&response.body)
+    }
+
+    /// Os repositórios em que o usuário deu estrela (`/users/{username}/starred`),
+    /// da estrela mais recente para a mais antiga
+    ///
+    /// Sem `all`, a primeira página; com `all`, página por página pelas mesmas regras
+    /// dos eventos (per_page, max_events como número de repositórios, max_pages)
+    pub fn fetch_starred(&self, username: &str, all: bool) -> Result<Vec<Repository>, ActivityError> {
+        self.check_username(username)?;
+        self.validate()?;
+
+        self.fetch_list(&self.starred_url(username), all, parser::parse_repositories)
+            .map_err(|e| user_not_found(e, username))
+    }
+
+    /// Os repositórios públicos do usuário (`/users/{username}/repos?sort=updated`),
+    /// do atualizado mais recentemente para o mais antigo, com a mesma paginação de
+    /// [`GitHubClient::fetch_starred`]
+    pub fn fetch_repos(&self, username: &str, all: bool) -> Result<Vec<Repository>, ActivityError> {
+        self.check_username(username)?;
+        self.validate()?;
+
+        self.fetch_list(&self.repos_url(username), all, parser::parse_repositories)
+            .map_err(|e| user_not_found(e, username))
+    }
+
+    /// Eventos de um alvo (usuário ou repositório) mantendo os corpos das respostas
+    pub fn fetch_target_pages(&self, target: &Target, all: bool) -> Result<FetchedPages, ActivityError> {
+        self.event_pages(target, all)?.collect_all()
+    }
+
+    /// As páginas de eventos de um alvo, uma requisição por vez (ver [`EventPages`])
+    /// Serve para mostrar os eventos conforme chegam, sem esperar o histórico inteiro
+    ///
+    /// O alvo e a configuração são validados aqui, antes de qualquer requisição
+    pub fn event_pages(&self, target: &Target, all: bool) -> Result<EventPages<'_>, ActivityError> {
+        let (url, username) = match target {
+            Target::User(username) => {
+                // Valida o username (e a configuração) antes de fazer a requisição
+                // O operador ? propaga o erro se a validação falhar
+                self.check_username(username)?;
+                (self.user_events_url(username), Some(username.clone()))
+            }
+            Target::Repo { owner, repo } => {
+                self.check_repo(owner, repo)?;
+                (self.repo_events_url(owner, repo), None)
+            }
+        };
+        self.validate()?;
+
+        Ok(EventPages {
+            client: self,
+            url,
+            all,
+            username,
+            page: 1,
+            finished: false,
+            delivered: 0,
+            last_page: None,
+            offset: 0,
+            warnings: Vec::new(),
+            bodies: Vec::new(),
+        })
+    }
+
+    /// O corpo da primeira página de eventos de um alvo, como a API mandou (--raw)
+    ///
+    /// Faz a mesma requisição (e as mesmas validações) que [`GitHubClient::event_pages`],
+    /// mas o corpo não passa pelo parser: serve para ver o que o GitHub respondeu
+    pub fn fetch_raw(&self, target: &Target) -> Result<String, ActivityError> {
+        let pages = self.event_pages(target, false)?;
+        let url = page_url(&pages.url, self.per_page, None);
+
+        self.make_http_request(&url).map(|response| response.body).map_err(|e| match &pages.username {
+            Some(username) => user_not_found(e, username),
+            None => e,
+        })
+    }
+
+    /// Busca vários alvos em paralelo, com no máximo `jobs` threads, e devolve os resultados
+    /// na MESMA ordem de `targets`
+    ///
+    /// O erro de um alvo não interrompe os outros. A exceção é o limite de requisições:
+    /// depois de um [`ActivityError::RateLimited`] (ou do limite secundário), os alvos
+    /// que ainda não começaram são pulados (None), porque falhariam do mesmo jeito
+    pub fn fetch_parallel(
+        &self,
+        targets: &[Target],
+        all: bool,
+        jobs: usize,
+    ) -> Vec<Option<Result<FetchedPages, ActivityError>>> {
+        // Cada thread pega o próximo índice livre até a lista acabar:
+        // um alvo lento não segura os outros, e nunca há mais que `jobs` requisições ao mesmo tempo
+        let next = AtomicUsize::new(0);
+        let rate_limited = AtomicBool::new(false);
+        // CONCEITO: Mutex
+        // Só uma thread por vez entra no mapa; lock() devolve um guard que solta a trava ao sair de escopo
+        let results = Mutex::new(BTreeMap::new());
+
+        // CONCEITO: thread::scope
+        // As threads criadas dentro do escopo terminam antes de scope() retornar,
+        // então podem usar referências (targets, self, results) sem Arc nem 'static
+        thread::scope(|scope| {
+            for _ in 0..jobs.clamp(1, targets.len().max(1)) {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let target = match targets.get(index) {
+                        Some(target) => target,
+                        None => break,
+                    };
+                    if rate_limited.load(Ordering::SeqCst) {
+                        continue;
+                    }
+
+                    let result = self.fetch_target_pages(target, all);
+                    if matches!(&result, Err(error) if error.is_rate_limit()) {
+                        rate_limited.store(true, Ordering::SeqCst);
+                    }
+                    // Um panic em outra thread "envenena" o Mutex; os dados continuam válidos
+                    results.lock().unwrap_or_else(|e| e.into_inner()).insert(index, result);
+                });
+            }
+        });
+
+        // Índices sem resultado foram pulados por causa do limite de requisições
+        let mut results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+        (0..targets.len()).map(|index| results.remove(&index)).collect()
+    }
+
+    /// Eventos de um usuário mantendo os corpos das respostas
+    /// `all` percorre todas as páginas do histórico, como --all
+    pub fn fetch_user_pages(&self, username: &str, all: bool) -> Result<FetchedPages, ActivityError> {
+        self.event_pages(&Target::User(username.to_string()), all)?.collect_all()
+    }
+
+    /// Eventos de um repositório mantendo os corpos das respostas
+    pub fn fetch_repo_pages(&self, owner: &str, repo: &str, all: bool) -> Result<FetchedPages, ActivityError> {
+        let target = Target::Repo { owner: owner.to_string(), repo: repo.to_string() };
+        self.event_pages(&target, all)?.collect_all()
+    }
+
+    // Endereços dos endpoints de eventos, a partir do endereço base
+    pub(crate) fn user_events_url(&self, username: &str) -> String {
+        // CONCEITO: format! macro
+        // Cria uma String interpolando valores
+        // {} é substituído pelos argumentos
+        join_url(&self.base_url, &format!("users/{}/events", username))
+    }
+
+    pub(crate) fn repo_events_url(&self, owner: &str, repo: &str) -> String {
+        join_url(&self.base_url, &format!("repos/{}/{}/events", owner, repo))
+    }
+
+    pub(crate) fn starred_url(&self, username: &str) -> String {
+        join_url(&self.base_url, &format!("users/{}/starred", username))
+    }
+
+    // O sort vai na URL: com --all, as páginas seguem a mesma ordem
+    pub(crate) fn repos_url(&self, username: &str) -> String {
+        join_url(&self.base_url, &format!("users/{}/repos?sort=updated", username))
+    }
+
+    pub(crate) fn user_url(&self, username: &str) -> String {
+        join_url(&self.base_url, &format!("users/{}", username))
+    }
+
+    pub(crate) fn authenticated_user_url(&self) -> String {
+        join_url(&self.base_url, "user")
+    }
+
+    pub(crate) fn rate_limit_url(&self) -> String {
+        join_url(&self.base_url, "rate_limit")
+    }
+
+    // Valida o endereço base e a paginação antes de qualquer requisição
+    pub(crate) fn validate(&self) -> Result<(), ActivityError> {
+        config::parse_api_url(&self.base_url)
+            .map_err(|e| ActivityError::InvalidArgument(format!("invalid API base URL: {}", e)))?;
+        if matches!(self.per_page, Some(per_page) if !(1..=MAX_PER_PAGE).contains(&per_page)) {
+            return Err(ActivityError::InvalidArgument(format!(
+                "--per-page must be between 1 and {}",
+                MAX_PER_PAGE
+            )));
+        }
+        if self.max_events == Some(0) {
+            return Err(ActivityError::InvalidArgument("--max-events must be at least 1".to_string()));
+        }
+        if self.max_pages == Some(0) {
+            return Err(ActivityError::InvalidArgument("max_pages must be at least 1".to_string()));
+        }
+        Ok(())
+    }
+
+    // Eventos por página no modo --all
+    pub(crate) fn page_size(&self) -> usize {
+        self.per_page.unwrap_or(HISTORY_PER_PAGE)
+    }
+
+    // Páginas necessárias para cobrir todo o histórico com page_size eventos cada
+    // (ou menos, se max_pages pedir)
+    pub(crate) fn history_pages(&self) -> usize {
+        let pages = HISTORY_EVENTS.div_ceil(self.page_size());
+        match self.max_pages {
+            Some(max_pages) => pages.min(max_pages),
+            None => pages,
+        }
+    }
+
+    // `events` eventos já bastam para --max-events?
+    pub(crate) fn has_enough(&self, events: usize) -> bool {
+        matches!(self.max_events, Some(max) if events >= max)
+    }
+
+    // A regra de parada da paginação, a mesma para eventos e repositórios: sem `all`,
+    // uma página só; com `all`, até uma página vir incompleta (`count` itens, sem contar
+    // o corte de max_events), a página `max_pages` ou `delivered` itens bastarem
+    pub(crate) fn is_last_page(&self, all: bool, count: usize, page: usize, max_pages: usize, delivered: usize) -> bool {
+        !all || count < self.page_size() || page >= max_pages || self.has_enough(delivered)
+    }
+
+    // Parte comum das listas paginadas que não são eventos (repositórios...):
+    // as páginas vão sendo pedidas e interpretadas com `parse` até is_last_page
+    fn fetch_list<T>(
+        &self,
+        url: &str,
+        all: bool,
+        parse: fn(&str) -> Result<Vec<T>, ActivityError>,
+    ) -> Result<Vec<T>, ActivityError> {
+        let max_pages = self.max_pages.unwrap_or(LIST_MAX_PAGES);
+        let mut items = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let page_url = if all {
+                page_url(url, Some(self.page_size()), Some(page))
+            } else {
+                page_url(url, self.per_page, None)
+            };
+            let mut found = parse(&self.make_http_request(&page_url)?.body)?;
+            let count = found.len();
+
+            if let Some(max) = self.max_events {
+                found.truncate(max.saturating_sub(items.len()));
+            }
+            items.extend(found);
+
+            if self.is_last_page(all, count, page, max_pages, items.len()) {
+                return Ok(items);
+            }
+            page += 1;
+        }
+    }
+
+    // Parte comum dos endpoints de eventos: requisição + parsing
+    // Devolve também a resposta original, para quem quiser gravá-la (--record)
+    fn fetch_events(&self, url: &str) -> Result<(ApiResponse, ParseOutcome), ActivityError> {
+        // Faz a requisição HTTP
+        let response = self.make_http_request(url)?;
+
+        // Parseia o JSON usando nosso parser manual
+        // parser::parse_events refere-se à função parse_events do módulo parser
+        let events = parser::parse_events(&response.body)?;
+
+        // Retorna a resposta e os eventos parseados
+        Ok((response, events))
+    }
+
+    // Faz uma requisição HTTP GET e retorna o corpo da resposta (e a última página do Link)
+    // Com wait_on_limit, o limite secundário ganha uma segunda chance (ver limit_wait)
+    //
+    // CONCEITO: Requisição condicional
+    // Com cache, a primeira página dos eventos de um usuário vai com If-None-Match: se o
+    // ETag ainda é o mesmo, a API responde 304 sem corpo e o corpo guardado é usado
+    fn make_http_request(&self, url: &str) -> Result<ApiResponse, ActivityError> {
+        let (login, cached) = match self.cache_lookup(url) {
+            CacheLookup::Skip => return self.make_request(url, Method::Get),
+            CacheLookup::Fresh(response) => return Ok(response),
+            CacheLookup::Revalidate { login, cached } => (login, cached),
+        };
+
+        let response = self.make_request(url, Method::revalidating(cached.as_ref()))?;
+        Ok(self.cache_update(url, login, cached, response))
+    }
+
+    // O que o cache diz de `url` antes da requisição (a mesma regra nos dois clientes)
+    pub(crate) fn cache_lookup<'a>(&self, url: &'a str) -> CacheLookup<'a> {
+        let (cache, login) = match (&self.cache, cache_key(url)) {
+            (Some(cache), Some(login)) => (cache, login),
+            _ => return CacheLookup::Skip,
+        };
+
+        // Uma entrada de outra URL (outro per_page, outro servidor) não serve; o login
+        // pode mudar de caixa, como no GitHub
+        let cached = cache.load(login).filter(|entry| entry.url.eq_ignore_ascii_case(url));
+        let now = Timestamp::now();
+
+        // Fresca o bastante (--max-age): nenhuma requisição
+        if let (Some(entry), Some(max_age)) = (&cached, self.max_age) {
+            if entry.is_fresh(max_age, now) {
+                let age = entry.age(now).unwrap_or_default();
+                logging::log(Level::Info, || format!("using the cached response for '{}' ({:?} old)", login, age));
+                self.cache_hits.lock().unwrap_or_else(|e| e.into_inner()).push((login.to_string(), age));
+                return CacheLookup::Fresh(ApiResponse::cached(entry));
+            }
+        }
+        CacheLookup::Revalidate { login, cached }
+    }
+
+    // Guarda a resposta de `url` no cache (ou a revalidação de `cached`, num 304)
+    // e devolve o que vale como resposta: num 304, o corpo guardado
+    pub(crate) fn cache_update(
+        &self,
+        url: &str,
+        login: &str,
+        cached: Option<CacheEntry>,
+        response: ApiResponse,
+    ) -> ApiResponse {
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return response,
+        };
+        let now = Timestamp::now();
+
+        // Um 304 usa o corpo guardado; a entrada continua a mesma, só que revalidada agora
+        let entry = match cached {
+            Some(entry) if response.not_modified => {
+                logging::log(Level::Info, || format!("HTTP 304: using the cached response for '{}'", login));
+                CacheEntry { fetched_at: Some(now), ..entry }
+            }
+            _ => match &response.etag {
+                Some(etag) => CacheEntry {
+                    url: url.to_string(),
+                    etag: etag.clone(),
+                    last_page: response.last_page,
+                    fetched_at: Some(now),
+                    body: response.body.clone(),
+                },
+                None => return response,
+            },
+        };
+        // O cache é só uma economia: sem ele, a busca continua valendo
+        if let Err(error) = cache.store(login, &entry) {
+            logging::log(Level::Info, || format!("could not update the cache: {}", error));
+        }
+        if response.not_modified {
+            return ApiResponse { body: entry.body, last_page: entry.last_page, ..response };
+        }
+        response
+    }
+
+    // Um POST à própria API (a GraphQL, em graphql.rs): os headers de sempre, token incluído,
+    // e as mesmas regras de erro e de limite de um GET; devolve o corpo da resposta
+    #[cfg(feature = "graphql")]
+    pub(crate) fn post_api(&self, url: &str, body: &str) -> Result<String, ActivityError> {
+        self.make_request(url, Method::Query { body }).map(|response| response.body)
+    }
+
+    fn make_request(&self, url: &str, method: Method) -> Result<ApiResponse, ActivityError> {
+        let response = match self.send_request(url, method) {
+            Err(error) => match self.limit_wait(&error) {
+                Some(wait) => {
+                    thread::sleep(wait);
+                    self.send_request(url, method)
+                }
+                None => Err(error),
+            },
+            ok => ok,
+        }?;
+        self.record_rate_limit(response.rate_limit);
+        Ok(response)
+    }
+
+    // Quanto esperar antes de repetir uma requisição que falhou com `error`
+    // None = não repetir: só o limite secundário, e só com wait_on_limit
+    pub(crate) fn limit_wait(&self, error: &ActivityError) -> Option<Duration> {
+        match error {
+            ActivityError::SecondaryRateLimit { retry_after } if self.wait_on_limit => {
+                let wait = Duration::from_secs(*retry_after).min(MAX_LIMIT_WAIT);
+                logging::log(Level::Info, || {
+                    format!("secondary rate limit: waiting {:?} before retrying", wait)
+                });
+                Some(wait)
+            }
+            _ => None,
+        }
+    }
+
+    // Uma única tentativa: a requisição (seguindo redirecionamentos) + log + leitura do corpo
+    //
+    // CONCEITO: loop com break valor
+    // O loop termina devolvendo a resposta que não é um redirecionamento
+    fn send_request(&self, url: &str, method: Method) -> Result<ApiResponse, ActivityError> {
+        let mut chain = RedirectChain::new(url);
+        let response = loop {
+            // call() devolve Box<ureq::Error>, que o ? converte em ActivityError (ver error.rs)
+            let response = self.call(chain.current(), method)?;
+            if !chain.follow(response.status(), response.header("location"), method)? {
+                break response;
+            }
+        };
+        let current = chain.current().to_string();
+        if current != url {
+            self.record_rename(url, &current);
+        }
+
+        // Os headers precisam ser lidos antes: read_body() consome a resposta
+        let head = ApiResponse::from_head(response.status(), &current, |name| response.header(name))?;
+        Ok(head.with_body(read_body(response)?))
+    }
+
+    // Uma única tentativa de qualquer requisição, com o log: a parte comum do GET
+    // da API e do POST de um webhook
+    // O erro vai numa Box: ureq::Error é grande demais para viajar dentro de um Result
+    fn call(&self, url: &str, method: Method) -> Result<ureq::Response, Box<ureq::Error>> {
+        // CONCEITO: ureq - Cliente HTTP simples
+        // build_request() cria a requisição já com os headers
+        // .call() (ou .send_string(), com um corpo) executa a requisição
+        //
+        // O endereço de um webhook é um segredo: no log, só o host
+        let shown = match method {
+            Method::Get | Method::Conditional { .. } | Method::Query { .. } => url.to_string(),
+            Method::Post { .. } => logging::redact_url(url),
+        };
+        logging::log(Level::Info, || format!("{} {}", method.name(), shown));
+
+        let started = Instant::now();
+        let request = self.build_request(url, method);
+        // CONCEITO: map_err para converter erros
+        // map_err transforma um tipo de erro em outro: aqui, ureq::Error em Box<ureq::Error>
+        let result = match method {
+            Method::Get | Method::Conditional { .. } => request.call(),
+            Method::Post { body, .. } | Method::Query { body } => request.send_string(body),
+        }
+        .map_err(Box::new);
+
+        // Respostas de erro (404, 403...) também interessam ao diagnóstico
+        match result.as_ref().map_err(|e| e.as_ref()) {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => log_ureq_response(response, started),
+            Err(ureq::Error::Transport(transport)) => match method {
+                Method::Get | Method::Conditional { .. } | Method::Query { .. } => {
+                    logging::log(Level::Info, || format!("request failed: {}", transport))
+                }
+                // A descrição do ureq inclui o endereço: só o tipo da falha
+                Method::Post { .. } => {
+                    logging::log(Level::Info, || format!("request to {} failed: {}", shown, transport.kind()))
+                }
+            },
+        }
+        result
+    }
+
+    // A requisição de um endpoint, com os headers de request_headers
+    // Todo acesso à API passa por aqui, então nenhum endpoint esquece um header
+    // Um POST vai para fora da API (um webhook): só User-Agent e Content-Type, nunca o token
+    fn build_request(&self, url: &str, method: Method) -> ureq::Request {
+        // agent.get() reaproveita as conexões do Agent; .set() adiciona cada header
+        let mut request = match method {
+            Method::Get | Method::Conditional { .. } => self.agent.get(url),
+            Method::Query { .. } | Method::Post { .. } => self.agent.post(url),
+        };
+        for (name, value) in self.headers_for(method) {
+            logging::log(Level::Debug, || format!("> {}: {}", name, logging::redact(name, &value)));
+            request = request.set(name, &value);
+        }
+        request
+    }
+
+    /// Envia `body` (JSON) com um POST para `url`, um endereço fora da API (--post-webhook)
+    ///
+    /// Só User-Agent e Content-Type vão junto: nenhum header da API, nem o token.
+    /// Uma resposta 5xx ou uma falha de conexão ganha até [`WEBHOOK_RETRIES`] novas
+    /// tentativas, com uma espera que dobra a cada uma; um 4xx é definitivo.
+    /// O erro (e o log) mostra só o host de `url`, nunca o caminho
+    pub fn post_webhook(&self, url: &str, body: &str) -> Result<(), ActivityError> {
+        let method = Method::Post { content_type: "application/json", body };
+        let mut wait = WEBHOOK_RETRY_WAIT;
+        let mut attempt = 0;
+
+        loop {
+            let error = match self.call(url, method) {
+                // Os redirecionamentos não são seguidos (ver build): o corpo não chegou a lugar nenhum
+                Ok(response) if (300..400).contains(&response.status()) => {
+                    return Err(ActivityError::WebhookError {
+                        url: logging::redact_url(url),
+                        reason: format!("HTTP {} (redirects are not followed)", response.status()),
+                    });
+                }
+                Ok(_) => return Ok(()),
+                Err(error) => error,
+            };
+            let (reason, retry) = match *error {
+                ureq::Error::Status(status, response) => {
+                    // Slack e companhia explicam o erro no corpo ("invalid_payload")
+                    let text = read_body(response).unwrap_or_default();
+                    let first_line = text.lines().next().unwrap_or("").trim();
+                    let detail: String = first_line.chars().filter(|c| !c.is_control()).take(200).collect();
+                    let reason = if detail.is_empty() {
+                        format!("HTTP {}", status)
+                    } else {
+                        format!("HTTP {}: {}", status, detail)
+                    };
+                    (reason, status >= 500)
+                }
+                ureq::Error::Transport(transport) => {
+                    // Sem o Display do ureq, que inclui o endereço
+                    let reason = match std::error::Error::source(&transport) {
+                        Some(source) => format!("{}: {}", transport.kind(), source),
+                        None => transport.kind().to_string(),
+                    };
+                    (reason, true)
+                }
+            };
+
+            if !retry || attempt == WEBHOOK_RETRIES {
+                return Err(ActivityError::WebhookError { url: logging::redact_url(url), reason });
+            }
+            attempt += 1;
+            logging::log(Level::Info, || format!("webhook failed ({}): retrying in {:?}", reason, wait));
+            thread::sleep(wait);
+            wait *= 2;
+        }
+    }
+
+    // Headers enviados em toda requisição (também pelo cliente assíncrono)
+    // User-Agent é obrigatório para o GitHub; Accept e X-GitHub-Api-Version fixam o formato
+    // das respostas; com um token, também Authorization; com `compression`, pedimos gzip
+    // (o corpo é descomprimido em decode_body)
+    // Os headers de cada tipo de requisição (também os do cliente assíncrono)
+    pub(crate) fn headers_for(&self, method: Method) -> Vec<(&'static str, String)> {
+        match method {
+            Method::Get => self.request_headers(),
+            Method::Conditional { etag } => {
+                let mut headers = self.request_headers();
+                headers.push(("If-None-Match", etag.to_string()));
+                headers
+            }
+            Method::Query { .. } => {
+                let mut headers = self.request_headers();
+                headers.push(("Content-Type", "application/json".to_string()));
+                headers
+            }
+            Method::Post { content_type, .. } => {
+                vec![("User-Agent", self.user_agent.clone()), ("Content-Type", content_type.to_string())]
+            }
+        }
+    }
+
+    pub(crate) fn request_headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = vec![
+            ("User-Agent", self.user_agent.clone()),
+            ("Accept", ACCEPT.to_string()),
+            ("X-GitHub-Api-Version", self.api_version.clone()),
+        ];
+        if self.compression {
+            headers.push(("Accept-Encoding", "gzip".to_string()));
+        }
+        if let Some(token) = &self.token {
+            headers.push(("Authorization", format!("Bearer {}", token)));
+        }
+        headers
+    }
+}
+
+// No endpoint de usuário, 404 significa que o usuário não existe
+// (um usuário sem atividade recebe 200 com uma lista vazia)
+// Outros erros passam sem alteração
+pub(crate) fn user_not_found(error: ActivityError, username: &str) -> ActivityError {
+    match error {
+        ActivityError::ApiError { status: 404, .. } => {
+            ActivityError::UserNotFound(username.to_string())
+        }
+        other => other,
+    }
+}
+
+// Os endereços visitados por uma requisição que segue redirecionamentos à mão,
+// a mesma regra nos dois clientes (ureq aqui, reqwest em async_api)
+pub(crate) struct RedirectChain {
+    visited: Vec<String>,
+}
+
+impl RedirectChain {
+    pub(crate) fn new(url: &str) -> Self {
+        RedirectChain { visited: vec![url.to_string()] }
+    }
+
+    // O endereço a pedir agora (o último da cadeia)
+    pub(crate) fn current(&self) -> &str {
+        self.visited.last().map_or("", String::as_str)
+    }
+
+    // Recebe o status e o Location da resposta de current(): true se ela é um
+    // redirecionamento a seguir, e então current() já é o próximo endereço
+    pub(crate) fn follow(&mut self, status: u16, location: Option<&str>, method: Method) -> Result<bool, ActivityError> {
+        let current = self.current();
+        let next = match redirect_target(current, status, location)? {
+            Some(next) => next,
+            None => return Ok(false),
+        };
+
+        // Um POST redirecionado teria que ser reenviado: só o GET segue adiante
+        if !method.is_get() {
+            return Err(network_error(format!("the API redirected a {} to {}", method.name(), next)));
+        }
+        if self.visited.contains(&next) || self.visited.len() > MAX_REDIRECTS {
+            return Err(network_error(format!("redirect loop: {} -> {}", current, next)));
+        }
+        logging::log(Level::Info, || format!("HTTP {}: following the redirect to {}", status, next));
+        self.visited.push(next);
+        Ok(true)
+    }
+}
+
+// O próximo endereço, se a resposta for um redirecionamento (301, 302, 303, 307 ou 308)
+//
+// Location pode ser absoluto ("https://api.github.com/users/new") ou só o caminho
+// ("/users/new"). Um redirecionamento para outro host é recusado: o token (e as
+// próximas páginas) iriam para um servidor que não é a API configurada
+fn redirect_target(current: &str, status: u16, location: Option<&str>) -> Result<Option<String>, ActivityError> {
+    if !matches!(status, 301 | 302 | 303 | 307 | 308) {
+        return Ok(None);
+    }
+
+    let location = location
+        .map(str::trim)
+        .filter(|location| !location.is_empty())
+        .ok_or_else(|| network_error(format!("HTTP {} redirect without a Location header", status)))?;
+    let next = if location.contains("://") {
+        location.to_string()
+    } else if location.starts_with('/') && !location.starts_with("//") {
+        format!("{}{}", origin(current), location)
+    } else {
+        return Err(network_error(format!("unsupported redirect to '{}'", location)));
+    };
+
+    if !origin(&next).eq_ignore_ascii_case(origin(current)) {
+        return Err(network_error(format!(
+            "refusing to follow a redirect from {} to another host ({})",
+            origin(current),
+            origin(&next)
+        )));
+    }
+    Ok(Some(next))
+}
+
+/// O endereço das páginas (não da API) do GitHub que responde em `api_url`, para os
+/// links dos eventos (ver models::set_web_base):
+/// - "https://api.github.com" -> "https://github.com"
+/// - GitHub Enterprise Server, "https://ghe.example.com/api/v3" -> "https://ghe.example.com"
+/// - GitHub Enterprise Cloud, "https://api.acme.ghe.com" -> "https://acme.ghe.com"
+///
+/// Qualquer outro endereço (um proxy, um servidor de testes) fica como está, sem a "/" final
+pub fn web_base_url(api_url: &str) -> String {
+    let url = api_url.trim_end_matches('/');
+    if let Some(server) = url.strip_suffix("/api/v3") {
+        return server.to_string();
+    }
+    let host_start = url.find("://").map_or(0, |start| start + 3);
+    match url[host_start..].strip_prefix("api.") {
+        Some(host) => format!("{}{}", &url[..host_start], host),
+        None => url.to_string(),
+    }
+}
+
+// Esquema, host e porta de uma URL: "https://api.github.com:443/users" -> "https://api.github.com:443"
+fn origin(url: &str) -> &str {
+    match url.find("://") {
+        Some(start) => {
+            let host_start = start + 3;
+            let end = url[host_start..].find(['/', '?', '#']).map_or(url.len(), |end| host_start + end);
+            &url[..end]
+        }
+        None => "",
+    }
+}
+
+// O login de uma URL de usuário: ".../users/torvalds/events?page=2" -> "torvalds"
+fn login_in(url: &str) -> Option<&str> {
+    let path = &url[origin(url).len()..];
+    let rest = path.split_once("/users/")?.1;
+    rest.split(['/', '?', '#']).next().filter(|login| !login.is_empty())
+}
+
+// O login de uma URL que vai para o cache: só a primeira página de /users/{login}/events
+fn cache_key(url: &str) -> Option<&str> {
+    let login = login_in(url)?;
+    let (address, query) = url.split_once('?').unwrap_or((url, ""));
+    let first_page = query.split('&').all(|pair| pair.strip_prefix("page=").is_none_or(|page| page == "1"));
+    let events_path = address.ends_with(&format!("/users/{}/events", login));
+    (first_page && events_path).then_some(login)
+}
+
+fn network_error(msg: String) -> ActivityError {
+    ActivityError::NetworkError { msg, source: None }
+}
+
+// O que uma requisição envia: o GET de sempre (ou condicional, com o ETag do cache),
+// um POST com um corpo para fora da API (--post-webhook) ou um POST de uma consulta
+// JSON para a própria API (--backend graphql)
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Method<'a> {
+    Get,
+    Conditional { etag: &'a str },
+    Post { content_type: &'a str, body: &'a str },
+    // Só a feature "graphql" constrói esta variante
+    #[cfg_attr(not(feature = "graphql"), allow(dead_code))]
+    Query { body: &'a str },
+}
+
+impl<'a> Method<'a> {
+    // O GET de uma URL que pode estar no cache: condicional quando há um ETag guardado
+    pub(crate) fn revalidating(cached: Option<&'a CacheEntry>) -> Method<'a> {
+        match cached {
+            Some(entry) => Method::Conditional { etag: &entry.etag },
+            None => Method::Get,
+        }
+    }
+
+    fn is_get(&self) -> bool {
+        matches!(self, Method::Get | Method::Conditional { .. })
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Method::Get | Method::Conditional { .. } => "GET",
+            Method::Post { .. } | Method::Query { .. } => "POST",
+        }
+    }
+}
+
+// Uma resposta bem-sucedida: o corpo já como texto e, quando os headers
+// informam, o número da última página (Link, rel="last"), o limite de requisições
+// e o ETag; not_modified marca um 304 (corpo vazio) a uma requisição condicional
+pub(crate) struct ApiResponse {
+    pub(crate) body: String,
+    last_page: Option<usize>,
+    pub(crate) rate_limit: Option<RateLimitInfo>,
+    etag: Option<String>,
+    not_modified: bool,
+}
+
+// O que o cache responde antes de uma requisição (ver GitHubClient::cache_lookup)
+pub(crate) enum CacheLookup<'a> {
+    // A URL não vai para o cache (ou não há cache)
+    Skip,
+    // Uma entrada nova o bastante para --max-age: nenhuma requisição
+    Fresh(ApiResponse),
+    // Requisição (condicional, se houver entrada) e depois cache_update
+    Revalidate { login: &'a str, cached: Option<CacheEntry> },
+}
+
+impl ApiResponse {
+    // Tudo que os headers de um sucesso (ou de um 304) de `current` informam; o corpo,
+    // que cada cliente HTTP lê do seu jeito, fica vazio
+    //
+    // Um sucesso com Content-Length: 0 não traz nada para interpretar (um 304 nunca traz):
+    // melhor dizer isso já do que deixar o parser reclamar do array que não veio
+    pub(crate) fn from_head<'h>(
+        status: u16,
+        current: &str,
+        header: impl Fn(&str) -> Option<&'h str>,
+    ) -> Result<ApiResponse, ActivityError> {
+        let not_modified = status == 304;
+        if !not_modified && header("content-length").map(str::trim) == Some("0") {
+            logging::log(Level::Info, || format!("HTTP {}: {} came back with Content-Length: 0", status, current));
+            return Err(parser::empty_response());
+        }
+
+        Ok(ApiResponse {
+            body: String::new(),
+            last_page: header("link").and_then(last_page_from_link),
+            rate_limit: RateLimitInfo::from_headers(&header),
+            etag: header("etag").map(str::to_string),
+            not_modified,
+        })
+    }
+
+    // A resposta de from_head com o corpo já lido
+    pub(crate) fn with_body(self, body: String) -> ApiResponse {
+        ApiResponse { body, ..self }
+    }
+
+    // Uma entrada do cache no lugar de uma resposta (sem os headers de limite)
+    fn cached(entry: &CacheEntry) -> ApiResponse {
+        ApiResponse {
+            body: entry.body.clone(),
+            last_page: entry.last_page,
+            rate_limit: None,
+            etag: Some(entry.etag.clone()),
+            not_modified: false,
+        }
+    }
+}
+
+/// O limite de requisições por hora, como os headers X-RateLimit-* de uma resposta informam
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// Requisições permitidas por hora (60 sem token, 5000 com)
+    pub limit: u64,
+    /// Quantas ainda restam
+    pub remaining: u64,
+    /// Quando a contagem recomeça (x-ratelimit-reset, em segundos desde a época Unix)
+    pub reset_at: Option<Timestamp>,
+}
+
+impl RateLimitInfo {
+    /// Lê os headers com `header` (closure, para servir a qualquer cliente HTTP)
+    /// None sem x-ratelimit-limit e x-ratelimit-remaining válidos
+    pub fn from_headers<'a>(header: impl Fn(&str) -> Option<&'a str>) -> Option<RateLimitInfo> {
+        let number = |name: &str| header(name).and_then(|value| value.trim().parse::<u64>().ok());
+        Some(RateLimitInfo {
+            limit: number("x-ratelimit-limit")?,
+            remaining: number("x-ratelimit-remaining")?,
+            reset_at: number("x-ratelimit-reset").map(|seconds| Timestamp::from_epoch_seconds(seconds as i64)),
+        })
+    }
+}
+
+// O número da última página de um Link header, como o GitHub manda:
+// <https://api.github.com/user/1/events?page=2>; rel="next", <...?page=3>; rel="last"
+pub(crate) fn last_page_from_link(link: &str) -> Option<usize> {
+    let last = link.split(',').find(|part| part.contains("rel=\"last\""))?;
+    let url = last.split_once('<')?.1.split_once('>')?.0;
+    let query = url.split_once('?')?.1;
+    query.split('&').find_map(|pair| pair.strip_prefix("page=")?.parse().ok())
+}
+
+// URL de uma página de uma lista (de eventos ou de repositórios): per_page (se houver)
+// e o número da página (no modo --all)
+pub(crate) fn page_url(url: &str, per_page: Option<usize>, page: Option<usize>) -> String {
+    let mut params = Vec::new();
+    if let Some(per_page) = per_page {
+        params.push(("per_page", per_page.to_string()));
+    }
+    if let Some(page) = page {
+        params.push(("page", page.to_string()));
+    }
+    with_query(url, &params)
+}
+
+// Acrescenta parâmetros à query string: o primeiro depois de "?", os outros depois de "&"
+// (uma URL que já tem "?" só recebe "&")
+// Os valores aqui são sempre números, então não precisam de percent-encoding
+pub(crate) fn with_query(url: &str, params: &[(&str, String)]) -> String {
+    let mut url = url.to_string();
+    for (name, value) in params {
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(name);
+        url.push('=');
+        url.push_str(value);
+    }
+    url
+}
+
+// Junta base e caminho com exatamente uma barra entre eles
+// O caminho da base é preservado: "https://host/api/v3/" + "users" -> "https://host/api/v3/users"
+pub(crate) fn join_url(base: &str, path: &str) -> String {
+    format!("{}/{}", base.trim_end_matches('/'), path.trim_start_matches('/'))
+}
+
+impl GitHubClient {
+    /// Valida um username como as buscas deste cliente fazem: pelas regras do
+    /// github.com ou, com `skip_validation`, só pelo que a URL exige
+    pub fn check_username(&self, username: &str) -> Result<(), ActivityError> {
+        if self.skip_validation {
+            validate_path_segment(username)
+        } else {
+            validate_username(username)
+        }
+    }
+
+    // O mesmo para owner/repo
+    pub(crate) fn check_repo(&self, owner: &str, repo: &str) -> Result<(), ActivityError> {
+        if self.skip_validation {
+            validate_path_segment(owner)?;
+            validate_path_segment(repo)
+        } else {
+            validate_repo(owner, repo)
+        }
+    }
+}
+
+// GitHub usernames têm no máximo 39 caracteres
+const MAX_USERNAME_LENGTH: usize = 39;
+
+// Valida se o username segue as regras do GitHub, antes de gastar uma requisição
 // Em Rust, Result<(), E> significa "sucesso sem valor" ou erro
-fn validate_username(username: &str) -> Result<(), ActivityError> {
+// A mensagem diz qual regra foi quebrada: "user_name: the character '_' is not allowed..."
+pub(crate) fn validate_username(username: &str) -> Result<(), ActivityError> {
     // CONCEITO: is_empty() em strings
     // Verifica se a string tem comprimento 0
     if username.is_empty() {
@@ -53,53 +1580,203 @@ fn validate_username(username: &str) -> Result<(), ActivityError> {
         ));
     }
 
-    // Validações básicas de username do GitHub
-    // Username não pode conter espaços ou caracteres especiais
-    if username.contains(' ') {
-        return Err(ActivityError::InvalidUsername(
-            "Username cannot contain spaces".to_string(),
-        ));
+    match username_violation(username) {
+        Some(reason) => Err(ActivityError::InvalidUsername(format!("{}: {}", username, reason))),
+        // Tudo certo!
+        None => Ok(()),
     }
+}
 
-    // GitHub usernames têm no máximo 39 caracteres
-    if username.len() > 39 {
-        return Err(ActivityError::InvalidUsername(
-            "Username is too long (max 39 characters)".to_string(),
+// A primeira regra de username que `username` (não vazio) quebra, ou None
+// Só letras e dígitos ASCII e hífens isolados, sem hífen nas pontas, de 1 a 39 caracteres
+fn username_violation(username: &str) -> Option<String> {
+    // CONCEITO: Iterator::find
+    // Devolve o primeiro item que satisfaz a condição (None se nenhum satisfizer)
+    // Letras acentuadas também são recusadas: o GitHub só aceita ASCII
+    if let Some(c) = username.chars().find(|c| !c.is_ascii_alphanumeric() && *c != '-') {
+        return Some(format!(
+            "the character '{}' is not allowed (only ASCII letters, digits and single hyphens)",
+            c
         ));
     }
 
-    // Tudo certo!
+    // Daqui em diante só há ASCII, então len() (em bytes) é o número de caracteres
+    if username.len() > MAX_USERNAME_LENGTH {
+        return Some(format!("too long ({} characters, max {})", username.len(), MAX_USERNAME_LENGTH));
+    }
+    if username.starts_with('-') {
+        return Some("cannot start with a hyphen".to_string());
+    }
+    if username.ends_with('-') {
+        return Some("cannot end with a hyphen".to_string());
+    }
+    if username.contains("--") {
+        return Some("cannot contain consecutive hyphens".to_string());
+    }
+
+    None
+}
+
+// Com skip_validation (GitHub Enterprise pode ter outras regras) só é recusado
+// o que quebraria a URL: um nome vazio, espaços e os separadores '/', '?' e '#'
+fn validate_path_segment(name: &str) -> Result<(), ActivityError> {
+    if name.is_empty() {
+        return Err(ActivityError::InvalidUsername("Name cannot be empty".to_string()));
+    }
+    if let Some(c) = name.chars().find(|c| c.is_whitespace() || matches!(c, '/' | '?' | '#')) {
+        return Err(ActivityError::InvalidUsername(format!(
+            "{}: the character '{}' cannot be part of a URL path",
+            name,
+            c.escape_default()
+        )));
+    }
     Ok(())
 }
 
-// Faz uma requisição HTTP GET e retorna o corpo da resposta como String
-fn make_http_request(url: &str) -> Result<String, ActivityError> {
-    // CONCEITO: ureq - Cliente HTTP simples
-    // ureq::get() cria uma requisição GET
-    // .set() adiciona headers
-    // .call() executa a requisição
-    //
-    // O tipo de retorno de .call() é Result<Response, Error>
-    // Usamos ? para propagar erros automaticamente
-    let response = ureq::get(url)
-        .set("User-Agent", USER_AGENT)  // Header obrigatório para GitHub
-        .call()
-        // CONCEITO: map_err para converter erros
-        // ureq retorna ureq::Error, mas nossa função espera ActivityError
-        // map_err transforma um tipo de erro em outro
-        .map_err(|e| {
-            // Box::new é necessário porque ureg::Error não implementa From
-            ActivityError::from(Box::new(e))
+// Valida "owner/repo" antes de consultar /repos/{owner}/{repo}/events
+// O owner segue as regras de username; nomes de repositório vão até 100 caracteres
+pub(crate) fn validate_repo(owner: &str, repo: &str) -> Result<(), ActivityError> {
+    if owner.is_empty() {
+        return Err(ActivityError::InvalidUsername(format!("/{}: repository owner cannot be empty", repo)));
+    }
+    if let Some(reason) = username_violation(owner) {
+        return Err(ActivityError::InvalidUsername(format!(
+            "{}/{}: invalid repository owner ({})",
+            owner, repo, reason
+        )));
+    }
+
+    if repo.is_empty() {
+        return Err(ActivityError::InvalidUsername(format!(
+            "{}/: repository name cannot be empty",
+            owner
+        )));
+    }
+
+    if repo.len() > 100 {
+        return Err(ActivityError::InvalidUsername(format!(
+            "{}/{}: repository name is too long (max 100 characters)",
+            owner, repo
+        )));
+    }
+
+    // Uma segunda barra (ex: "a/b/c") nunca forma um repositório válido
+    if repo.contains('/') {
+        return Err(ActivityError::InvalidUsername(format!(
+            "{}/{}: expected exactly 'owner/repo'",
+            owner, repo
+        )));
+    }
+
+    // O GitHub só aceita letras e dígitos ASCII, '.', '-' e '_' no nome: qualquer outro
+    // caractere ('?', '#', '%', espaço, ESC...) mudaria a URL de /repos/{owner}/{repo}/events
+    if let Some(c) = repo.chars().find(|c| !c.is_ascii_alphanumeric() && !matches!(c, '.' | '-' | '_')) {
+        return Err(ActivityError::InvalidUsername(format!(
+            "{}/{}: the character '{}' is not allowed in a repository name \
+             (only ASCII letters, digits, '.', '-' and '_')",
+            owner,
+            repo.escape_default(),
+            c.escape_default()
+        )));
+    }
+
+    // "." e ".." são segmentos de caminho: /repos/a/../events sairia do repositório
+    if repo == "." || repo == ".." {
+        return Err(ActivityError::InvalidUsername(format!("{}/{}: is not a repository name", owner, repo)));
+    }
+
+    Ok(())
+}
+
+/// Lê o corpo de uma resposta do ureq, descomprimindo se preciso (ver decode_body)
+pub(crate) fn read_body(response: ureq::Response) -> Result<String, ActivityError> {
+    // O header precisa ser copiado antes: into_reader() consome a resposta
+    let encoding = response.header("content-encoding").map(str::to_string);
+
+    // CONCEITO: into_reader()
+    // Dá acesso aos bytes crus do corpo (io::Read), sem interpretá-los como texto
+    let mut raw = Vec::new();
+    response
+        .into_reader()
+        .read_to_end(&mut raw)
+        .map_err(|e| ActivityError::ParseError {
+            message: format!("Failed to read response: {}", e),
+            event_index: None,
+            snippet: String::new(),
+            source: Some(Box::new(e)),  // Guarda o io::Error original (ver Error::source)
         })?;
 
-    // CONCEITO: into_string()
-    // Converte o corpo da resposta HTTP em String
-    // Pode falhar se o corpo não for UTF-8 válido
-    let body = response
-        .into_string()
-        .map_err(|e| ActivityError::ParseError(format!("Failed to read response: {}", e)))?;
+    decode_body(encoding.as_deref(), raw)
+}
+
+/// Transforma o corpo cru em texto conforme o header Content-Encoding
+/// gzip é descomprimido; sem header (ou "identity") o corpo já é o texto
+pub(crate) fn decode_body(encoding: Option<&str>, raw: Vec<u8>) -> Result<String, ActivityError> {
+    let received = raw.len();
+    let encoding = encoding.map(|value| value.trim().to_ascii_lowercase()).unwrap_or_default();
+
+    let bytes = match encoding.as_str() {
+        "" | "identity" => raw,
+        "gzip" | "x-gzip" => {
+            // CONCEITO: Adaptadores de io::Read
+            // GzDecoder "embrulha" outro Read e entrega os bytes já descomprimidos
+            let mut decompressed = Vec::new();
+            GzDecoder::new(raw.as_slice())
+                .read_to_end(&mut decompressed)
+                .map_err(|e| ActivityError::ParseError {
+                    message: format!("Failed to decompress the response (content-encoding: gzip): {}", e),
+                    event_index: None,
+                    snippet: String::new(),
+                    source: Some(Box::new(e)),
+                })?;
+            decompressed
+        }
+        other => {
+            return Err(ActivityError::parse(format!(
+                "Unsupported response content-encoding '{}'",
+                other
+            )));
+        }
+    };
+
+    logging::log(Level::Info, || match encoding.as_str() {
+        "" | "identity" => format!("read {} bytes", received),
+        _ => format!("read {} bytes ({}, {} decompressed)", received, encoding, bytes.len()),
+    });
 
-    Ok(body)
+    // CONCEITO: String::from_utf8
+    // Converte bytes em String, verificando se são UTF-8 válido
+    String::from_utf8(bytes).map_err(|e| ActivityError::ParseError {
+        message: format!("Failed to read response: {}", e),
+        event_index: None,
+        snippet: String::new(),
+        source: Some(Box::new(e)),
+    })
+}
+
+fn log_ureq_response(response: &ureq::Response, started: Instant) {
+    log_response(response.status(), response.status_text(), started, |name| response.header(name));
+}
+
+// Status, tempo de resposta e limite de requisições (-v); headers escolhidos (-vv)
+// Recebe a leitura de headers como closure para servir a qualquer cliente HTTP
+pub(crate) fn log_response<'a>(
+    status: u16,
+    reason: &str,
+    started: Instant,
+    header: impl Fn(&str) -> Option<&'a str>,
+) {
+    logging::log(Level::Info, || format!("{} {} in {:.0?}", status, reason, started.elapsed()));
+
+    if let (Some(remaining), Some(limit)) = (header("x-ratelimit-remaining"), header("x-ratelimit-limit")) {
+        logging::log(Level::Info, || format!("rate limit: {}/{} requests left", remaining, limit));
+    }
+
+    for name in LOGGED_HEADERS {
+        if let Some(value) = header(name) {
+            logging::log(Level::Debug, || format!("< {}: {}", name, value));
+        }
+    }
 }
 
 // TESTES (opcional, mas boa prática)
@@ -115,13 +1792,306 @@ mod tests {
         assert!(validate_username("torvalds").is_ok());
         assert!(validate_username("github").is_ok());
         assert!(validate_username("user-name").is_ok());
-        assert!(validate_username("user_name").is_ok());
+        assert!(validate_username("a-b-c").is_ok());
+        assert!(validate_username("CaioMS2000").is_ok());
+        assert!(validate_username("42").is_ok());
+        // Um caractere só, e o máximo de 39
+        assert!(validate_username("a").is_ok());
+        assert!(validate_username(&"a".repeat(39)).is_ok());
     }
 
     #[test]
     fn test_validate_username_invalid() {
-        assert!(validate_username("").is_err());
-        assert!(validate_username("user name").is_err());
-        assert!(validate_username(&"a".repeat(40)).is_err());
+        let reason = |username: &str| match validate_username(username) {
+            Err(ActivityError::InvalidUsername(message)) => message,
+            other => panic!("{:?}: expected InvalidUsername, got {:?}", username, other),
+        };
+
+        assert_eq!(reason(""), "Username cannot be empty");
+        assert_eq!(reason(&"a".repeat(40)), format!("{}: too long (40 characters, max 39)", "a".repeat(40)));
+        assert_eq!(reason("-leading-dash"), "-leading-dash: cannot start with a hyphen");
+        assert_eq!(reason("trailing-"), "trailing-: cannot end with a hyphen");
+        assert_eq!(reason("-"), "-: cannot start with a hyphen");
+        assert_eq!(reason("double--hyphen"), "double--hyphen: cannot contain consecutive hyphens");
+        assert!(reason("user_name").contains("the character '_' is not allowed"));
+        assert!(reason("user..name").contains("the character '.' is not allowed"));
+        assert!(reason("user name").contains("the character ' ' is not allowed"));
+        // Letras fora do ASCII, mesmo sendo letras
+        assert!(reason("josé").contains("the character 'é' is not allowed"));
+        assert!(reason("Ünïcode").contains("the character 'Ü' is not allowed"));
+        assert!(reason("用户").contains("is not allowed"));
+    }
+
+    #[test]
+    fn test_validate_repo_owner_explains_the_rule() {
+        assert!(validate_repo("rust-lang", "rust").is_ok());
+        assert!(validate_repo("rust-lang", "rust_analyzer.v2").is_ok());
+        assert_eq!(
+            validate_repo("rust--lang", "rust").unwrap_err().to_string(),
+            "Invalid username: 'rust--lang/rust: invalid repository owner (cannot contain consecutive hyphens)'"
+        );
+        assert!(validate_repo("", "rust").is_err());
+    }
+
+    #[test]
+    fn test_skip_validation_only_checks_the_url() {
+        let strict = GitHubClient::default();
+        let relaxed = GitHubClient::builder().skip_validation(true).build();
+
+        assert!(strict.check_username("ent_user.name").is_err());
+        assert!(relaxed.check_username("ent_user.name").is_ok());
+        assert!(relaxed.check_repo("ent_org", "repo").is_ok());
+
+        // O que quebraria a URL continua recusado
+        assert!(relaxed.check_username("").is_err());
+        assert!(relaxed.check_username("a b").is_err());
+        assert!(relaxed.check_username("a?b").is_err());
+        assert!(relaxed.check_repo("org", "a#b").is_err());
+
+        // Target::validate segue as mesmas regras, sem um cliente
+        assert!(Target::parse("ent_user.name").validate(false).is_err());
+        assert!(Target::parse("ent_user.name").validate(true).is_ok());
+        assert!(Target::parse("ent_org/repo").validate(true).is_ok());
+        assert!(Target::parse("org/a#b").validate(true).is_err());
+    }
+
+    #[test]
+    fn test_user_not_found_maps_only_404() {
+        let not_found = ActivityError::ApiError {
+            status: 404,
+            message: "Not Found".to_string(),
+            documentation_url: None,
+        };
+        match user_not_found(not_found, "torvlads") {
+            ActivityError::UserNotFound(username) => assert_eq!(username, "torvlads"),
+            other => panic!("expected UserNotFound, got {:?}", other),
+        }
+
+        let server_error = ActivityError::ApiError {
+            status: 500,
+            message: String::new(),
+            documentation_url: None,
+        };
+        assert!(matches!(
+            user_not_found(server_error, "torvalds"),
+            ActivityError::ApiError { status: 500, .. }
+        ));
+    }
+
+    #[test]
+    fn test_with_query_joins_params() {
+        let url = "https://api.github.com/users/torvalds/events";
+        assert_eq!(with_query(url, &[]), url);
+        assert_eq!(page_url(url, Some(50), None), format!("{}?per_page=50", url));
+        assert_eq!(page_url(url, Some(50), Some(2)), format!("{}?per_page=50&page=2", url));
+        assert_eq!(page_url(url, None, Some(3)), format!("{}?page=3", url));
+        // Uma URL que já tem query string continua com "&"
+        assert_eq!(
+            with_query("http://localhost/events?since=x", &[("page", "2".to_string())]),
+            "http://localhost/events?since=x&page=2"
+        );
+    }
+
+    #[test]
+    fn test_history_pages_follow_the_page_size() {
+        assert_eq!(GitHubClient::default().history_pages(), 3);
+        assert_eq!(GitHubClient::builder().per_page(30).build().history_pages(), 10);
+        assert_eq!(GitHubClient::builder().per_page(7).build().history_pages(), 43);
+        assert_eq!(GitHubClient::builder().per_page(7).max_pages(5).build().history_pages(), 5);
+    }
+
+    #[test]
+    fn test_validate_paging() {
+        assert!(GitHubClient::default().validate().is_ok());
+        assert!(GitHubClient::builder().per_page(1).max_events(1).build().validate().is_ok());
+        assert!(GitHubClient::builder().per_page(100).build().validate().is_ok());
+        assert!(GitHubClient::builder().per_page(0).build().validate().is_err());
+        assert!(GitHubClient::builder().per_page(101).build().validate().is_err());
+        assert!(GitHubClient::builder().max_events(0).build().validate().is_err());
+        assert!(GitHubClient::builder().max_pages(0).build().validate().is_err());
+
+        // Inválido nem chega a fazer requisição (a URL base nem existe)
+        let client = GitHubClient::builder().base_url("http://invalid.invalid").per_page(500).build();
+        assert!(matches!(
+            client.fetch_user_pages("torvalds", false),
+            Err(ActivityError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_events_urls() {
+        let github = GitHubClient::default();
+        assert_eq!(github.user_events_url("torvalds"), "https://api.github.com/users/torvalds/events");
+        assert_eq!(github.user_url("torvalds"), "https://api.github.com/users/torvalds");
+        assert_eq!(github.authenticated_user_url(), "https://api.github.com/user");
+        assert_eq!(
+            github.repo_events_url("rust-lang", "rust"),
+            "https://api.github.com/repos/rust-lang/rust/events"
+        );
+
+        // GitHub Enterprise: o prefixo /api/v3 fica, com ou sem barra final
+        for base_url in ["https://github.example.com/api/v3", "https://github.example.com/api/v3/"] {
+            let enterprise = GitHubClient::builder().base_url(base_url).build();
+            assert_eq!(
+                enterprise.user_events_url("octocat"),
+                "https://github.example.com/api/v3/users/octocat/events"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_from_headers() {
+        let headers = [("x-ratelimit-limit", "60"), ("x-ratelimit-remaining", " 54 "), ("x-ratelimit-reset", "1714584720")];
+        let header = |name: &str| headers.iter().find(|(key, _)| *key == name).map(|(_, value)| *value);
+        assert_eq!(
+            RateLimitInfo::from_headers(header),
+            Some(RateLimitInfo {
+                limit: 60,
+                remaining: 54,
+                reset_at: Some(Timestamp::from_epoch_seconds(1714584720)),
+            })
+        );
+
+        // Sem o reset ainda dá para mostrar o restante; sem o restante, não há o que mostrar
+        let partial = RateLimitInfo::from_headers(|name| (name != "x-ratelimit-reset").then_some("5"));
+        assert_eq!(partial.map(|info| info.reset_at), Some(None));
+        assert_eq!(RateLimitInfo::from_headers(|name| (name == "x-ratelimit-limit").then_some("60")), None);
+        assert_eq!(RateLimitInfo::from_headers(|_| Some("many")), None);
+    }
+
+    #[test]
+    fn test_web_base_url() {
+        assert_eq!(web_base_url(GITHUB_API_BASE), "https://github.com");
+        assert_eq!(web_base_url("https://api.github.com/"), "https://github.com");
+        assert_eq!(web_base_url("https://ghe.example.com/api/v3"), "https://ghe.example.com");
+        assert_eq!(web_base_url("https://ghe.example.com/api/v3/"), "https://ghe.example.com");
+        assert_eq!(web_base_url("https://api.acme.ghe.com"), "https://acme.ghe.com");
+        assert_eq!(web_base_url("http://127.0.0.1:8080"), "http://127.0.0.1:8080");
+        assert_eq!(web_base_url("https://proxy.example.com/github/"), "https://proxy.example.com/github");
+    }
+
+    #[test]
+    fn test_origin_and_login_in() {
+        assert_eq!(origin("https://api.github.com/users/a"), "https://api.github.com");
+        assert_eq!(origin("http://127.0.0.1:8080?x=1"), "http://127.0.0.1:8080");
+        assert_eq!(origin("/users/a"), "");
+
+        assert_eq!(login_in("https://api.github.com/users/torvalds/events?page=2"), Some("torvalds"));
+        assert_eq!(cache_key("https://api.github.com/users/torvalds/events?per_page=50"), Some("torvalds"));
+        assert_eq!(cache_key("https://api.github.com/users/torvalds/events?per_page=100&page=1"), Some("torvalds"));
+        assert_eq!(cache_key("https://api.github.com/users/torvalds/events?per_page=100&page=2"), None);
+        assert_eq!(cache_key("https://api.github.com/users/torvalds/starred"), None);
+        assert_eq!(cache_key("https://api.github.com/users/torvalds"), None);
+        assert_eq!(login_in("https://ghe.example.com/api/v3/users/octocat"), Some("octocat"));
+        assert_eq!(login_in("https://api.github.com/repos/a/b/events"), None);
+        assert_eq!(login_in("https://api.github.com/users/"), None);
+    }
+
+    #[test]
+    fn test_last_page_from_link() {
+        let link = "<https://api.github.com/user/1/events?per_page=100&page=2>; rel=\"next\", \
+                    <https://api.github.com/user/1/events?per_page=100&page=3>; rel=\"last\"";
+        assert_eq!(last_page_from_link(link), Some(3));
+        // Na última página só há "prev" e "first"
+        assert_eq!(last_page_from_link("<https://api.github.com/x?page=1>; rel=\"first\""), None);
+        assert_eq!(last_page_from_link("<https://api.github.com/x?page=last>; rel=\"last\""), None);
+        assert_eq!(last_page_from_link(""), None);
+    }
+
+    #[test]
+    fn test_base_url_scheme_is_validated_before_requesting() {
+        for base_url in ["ftp://github.example.com", "github.example.com/api/v3", ""] {
+            let client = GitHubClient::builder().base_url(base_url).build();
+            let error = client.fetch_user_pages("torvalds", false).unwrap_err();
+            assert!(matches!(error, ActivityError::InvalidArgument(_)), "{:?}", error);
+            assert!(error.to_string().contains("invalid API base URL"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_decode_body() {
+        assert_eq!(decode_body(None, b"[]".to_vec()).unwrap(), "[]");
+        assert_eq!(decode_body(Some("identity"), b"[]".to_vec()).unwrap(), "[]");
+
+        let error = decode_body(Some("br"), b"[]".to_vec()).unwrap_err();
+        assert!(error.to_string().contains("content-encoding 'br'"), "{}", error);
+        let error = decode_body(Some("gzip"), b"not gzip".to_vec()).unwrap_err();
+        assert!(error.to_string().contains("content-encoding: gzip"), "{}", error);
+    }
+
+    #[test]
+    fn test_request_headers() {
+        let plain = GitHubClient::builder().compression(false);
+        assert_eq!(
+            plain.clone().build().request_headers(),
+            vec![
+                ("User-Agent", USER_AGENT.to_string()),
+                ("Accept", "application/vnd.github+json".to_string()),
+                ("X-GitHub-Api-Version", "2022-11-28".to_string()),
+            ]
+        );
+        assert!(GitHubClient::default().request_headers().contains(&("Accept-Encoding", "gzip".to_string())));
+
+        let headers = plain.clone().token("gho_secret").build().request_headers();
+        assert_eq!(headers[3], ("Authorization", "Bearer gho_secret".to_string()));
+        // O que vai para o log nunca contém o token
+        for (name, value) in &headers {
+            assert!(!logging::redact(name, value).contains("gho_secret"));
+        }
+    }
+
+    #[test]
+    fn test_builder_overrides_the_defaults() {
+        let client = GitHubClient::builder()
+            .base_url("https://github.example.com/api/v3")
+            .user_agent("my-dashboard/2.0")
+            .api_version("2026-03-10")
+            .timeout(Duration::from_secs(5))
+            .build();
+
+        assert_eq!(client.base_url, "https://github.example.com/api/v3");
+        let headers = client.request_headers();
+        assert_eq!(headers[0], ("User-Agent", "my-dashboard/2.0".to_string()));
+        assert_eq!(headers[2], ("X-GitHub-Api-Version", "2026-03-10".to_string()));
+    }
+
+    #[test]
+    fn test_validate_repo_valid() {
+        assert!(validate_repo("rust-lang", "rust").is_ok());
+        assert!(validate_repo("torvalds", "linux").is_ok());
+        assert!(validate_repo("user", &"r".repeat(100)).is_ok());
+    }
+
+    #[test]
+    fn test_validate_repo_invalid() {
+        assert!(validate_repo("", "rust").is_err());
+        assert!(validate_repo("rust-lang", "").is_err());
+        assert!(validate_repo("rust-lang", "a/b").is_err());
+        assert!(validate_repo(&"o".repeat(40), "rust").is_err());
+        assert!(validate_repo("user", &"r".repeat(101)).is_err());
+    }
+
+    #[test]
+    fn test_validate_repo_rejects_what_would_change_the_url() {
+        let reason = |repo: &str| validate_repo("a", repo).unwrap_err().to_string();
+
+        assert_eq!(
+            reason("b?x=1"),
+            "Invalid username: 'a/b?x=1: the character '?' is not allowed in a repository name \
+             (only ASCII letters, digits, '.', '-' and '_')'"
+        );
+        assert!(reason("b#frag").contains("the character '#'"));
+        assert!(reason("b%2F..%2F..%2Fusers").contains("the character '%'"));
+        assert!(reason("my repo").contains("the character ' '"));
+        // O ESC aparece escapado na mensagem, não cru no terminal
+        let escape = reason("b\u{1b}[2J");
+        assert!(escape.contains("the character '\\u{1b}'") && !escape.contains('\u{1b}'), "{}", escape);
+        assert!(reason(".").contains("a/.: is not a repository name"));
+        assert!(reason("..").contains("a/..: is not a repository name"));
+
+        // Pontos no meio ou nas pontas continuam valendo (".github", "rust.vim")
+        for repo in [".github", "rust.vim", "...", "a-b_c.d"] {
+            assert!(validate_repo("a", repo).is_ok(), "{}", repo);
+        }
     }
 }
@@ -0,0 +1,308 @@
+//! Versão assíncrona do cliente da API (feature `async`)
+//!
+//! Os mesmos endpoints de [`crate::api`], mas com o reqwest no lugar do ureq: enquanto uma
+//! resposta não chega, a thread pode cuidar de outras requisições. É o que permite
+//! buscar vários usuários ao mesmo tempo com [`fetch_concurrently`].
+//! Os métodos `*_async` ficam no mesmo [`GitHubClient`] do cliente síncrono:
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), github_activity::ActivityError> {
+//! let outcome = github_activity::fetch_user_events_async("torvalds").await?;
+//! println!("{} events", outcome.events.len());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Compile com `cargo build --features async`. Sem a feature, este módulo nem existe
+//! e o programa continua usando só o cliente síncrono.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use tokio::sync::Semaphore;
+
+use crate::api::{self, ApiResponse, CacheLookup, EventPages, FetchedPages, GitHubClient, Method, RedirectChain, Target};
+use crate::error::ActivityError;
+use crate::logging::{self, Level};
+use crate::parser::{self, ParseOutcome};
+
+/// Busca os eventos de um usuário, como [`crate::fetch_user_events`]
+// CONCEITO: async fn
+// Chamar uma async fn não executa nada: ela devolve uma Future,
+// que só anda quando alguém faz .await nela (ou a entrega a um runtime, como o tokio)
+pub async fn fetch_user_events_async(username: &str) -> Result<ParseOutcome, ActivityError> {
+    GitHubClient::default()
+        .fetch_user_pages_async(username, false)
+        .await
+        .map(|pages| pages.outcome)
+}
+
+// CONCEITO: Vários blocos impl
+// Um tipo pode ter impls em mais de um módulo da crate; este só existe com a feature "async"
+//
+// Só o transporte é outro: a paginação (EventPages), o cache com --max-age, os
+// redirecionamentos e a leitura dos headers são os mesmos do cliente síncrono
+impl GitHubClient {
+    /// Eventos de um usuário mantendo os corpos das respostas, como [`GitHubClient::fetch_user_pages`]
+    pub async fn fetch_user_pages_async(&self, username: &str, all: bool) -> Result<FetchedPages, ActivityError> {
+        self.collect_pages_async(self.event_pages(&Target::User(username.to_string()), all)?).await
+    }
+
+    /// Eventos de um repositório mantendo os corpos das respostas, como [`GitHubClient::fetch_repo_pages`]
+    pub async fn fetch_repo_pages_async(
+        &self,
+        owner: &str,
+        repo: &str,
+        all: bool,
+    ) -> Result<FetchedPages, ActivityError> {
+        let target = Target::Repo { owner: owner.to_string(), repo: repo.to_string() };
+        self.collect_pages_async(self.event_pages(&target, all)?).await
+    }
+
+    // EventPages::collect_while com .await em cada requisição
+    async fn collect_pages_async(&self, mut pages: EventPages<'_>) -> Result<FetchedPages, ActivityError> {
+        let mut events = Vec::new();
+        while !pages.is_finished() {
+            let url = pages.next_url();
+            let (response, outcome) = self.fetch_events_async(&url).await.map_err(|e| pages.map_error(e))?;
+            events.extend(pages.accept(response, outcome));
+        }
+        Ok(pages.into_fetched(events))
+    }
+
+    async fn fetch_events_async(&self, url: &str) -> Result<(ApiResponse, ParseOutcome), ActivityError> {
+        let response = self.make_http_request_async(url).await?;
+        let events = parser::parse_events(&response.body)?;
+        Ok((response, events))
+    }
+
+    // make_http_request: a primeira página de um usuário pode vir do cache
+    // (sem requisição, com --max-age) ou ser revalidada com If-None-Match
+    async fn make_http_request_async(&self, url: &str) -> Result<ApiResponse, ActivityError> {
+        let (login, cached) = match self.cache_lookup(url) {
+            CacheLookup::Skip => return self.make_request_async(url, Method::Get).await,
+            CacheLookup::Fresh(response) => return Ok(response),
+            CacheLookup::Revalidate { login, cached } => (login, cached),
+        };
+
+        let response = self.make_request_async(url, Method::revalidating(cached.as_ref())).await?;
+        Ok(self.cache_update(url, login, cached, response))
+    }
+
+    // Com wait_on_limit, a mesma segunda chance do cliente síncrono
+    // (tokio::time::sleep não bloqueia a thread: as outras tarefas continuam andando)
+    async fn make_request_async(&self, url: &str, method: Method<'_>) -> Result<ApiResponse, ActivityError> {
+        let response = match self.send_request_async(url, method).await {
+            Err(error) => match self.limit_wait(&error) {
+                Some(wait) => {
+                    tokio::time::sleep(wait).await;
+                    self.send_request_async(url, method).await
+                }
+                None => Err(error),
+            },
+            ok => ok,
+        }?;
+        self.record_rate_limit(response.rate_limit);
+        Ok(response)
+    }
+
+    // GET com os mesmos headers (e o mesmo log) do cliente síncrono
+    // Os redirecionamentos seguem a mesma RedirectChain: outro host é recusado
+    // e um login renomeado fica anotado (ver GitHubClient::renamed_login)
+    async fn send_request_async(&self, url: &str, method: Method<'_>) -> Result<ApiResponse, ActivityError> {
+        let mut chain = RedirectChain::new(url);
+        let response = loop {
+            let response = self.call_async(chain.current(), method).await?;
+            let location = response.headers().get("location").and_then(|value| value.to_str().ok());
+            if !chain.follow(response.status().as_u16(), location, method)? {
+                break response;
+            }
+        };
+        if chain.current() != url {
+            self.record_rename(url, chain.current());
+        }
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+        // Diferente do ureq, o reqwest não transforma status de erro em Err:
+        // a classificação (404, limite de requisições...) é a mesma do cliente síncrono
+        // O reqwest está sem a feature "gzip": a descompressão é a mesma de api::decode_body
+        // (um 304 não é "success" para o reqwest, mas é a resposta esperada do cache)
+        if !status.is_success() && status != reqwest::StatusCode::NOT_MODIFIED {
+            let raw = response.bytes().await?;
+            let body = api::decode_body(header("content-encoding"), raw.to_vec()).unwrap_or_default();
+            return Err(ActivityError::from_status(
+                status.as_u16(),
+                header("x-ratelimit-remaining"),
+                header("x-ratelimit-reset"),
+                header("retry-after"),
+                &body,
+            ));
+        }
+
+        let head = ApiResponse::from_head(status.as_u16(), chain.current(), header)?;
+        let raw = response.bytes().await?;
+        Ok(head.with_body(api::decode_body(header("content-encoding"), raw.to_vec())?))
+    }
+
+    // Uma única requisição, sem seguir nada: a requisição, o log e o status
+    async fn call_async(&self, url: &str, method: Method<'_>) -> Result<reqwest::Response, ActivityError> {
+        logging::log(Level::Info, || format!("GET {}", url));
+
+        let mut request = self.http.get(url);
+        for (name, value) in self.headers_for(method) {
+            logging::log(Level::Debug, || format!("> {}: {}", name, logging::redact(name, &value)));
+            request = request.header(name, value);
+        }
+
+        let started = Instant::now();
+        let response = request.send().await.inspect_err(|e| {
+            logging::log(Level::Info, || format!("request failed: {}", e));
+        })?;
+
+        let status = response.status();
+        let header = |name: &str| response.headers().get(name).and_then(|value| value.to_str().ok());
+        api::log_response(status.as_u16(), status.canonical_reason().unwrap_or(""), started, header);
+        Ok(response)
+    }
+}
+
+/// Executa as buscas com no máximo `max_in_flight` ao mesmo tempo
+/// e devolve os resultados na MESMA ordem de `jobs`, depois que todas terminarem
+///
+/// O erro de uma busca não cancela as outras. A exceção é o limite de requisições:
+/// depois de um [`ActivityError::RateLimited`] (ou do limite secundário), as buscas
+/// que ainda não começaram são puladas (None), porque falhariam do mesmo jeito
+///
+/// Precisa rodar dentro de um runtime do tokio
+pub async fn fetch_concurrently<F>(jobs: Vec<F>, max_in_flight: usize) -> Vec<Option<Result<FetchedPages, ActivityError>>>
+where
+    F: Future<Output = Result<FetchedPages, ActivityError>> + Send + 'static,
+{
+    // CONCEITO: Semaphore
+    // Um contador de "vagas": cada busca pega uma antes de começar e devolve ao terminar
+    // (quando _permit sai de escopo), então nunca há mais que max_in_flight rodando
+    let permits = Arc::new(Semaphore::new(max_in_flight.max(1)));
+    let rate_limited = Arc::new(AtomicBool::new(false));
+
+    // CONCEITO: tokio::spawn
+    // Cada busca vira uma tarefa independente; o JoinHandle devolve o resultado dela
+    let handles: Vec<_> = jobs
+        .into_iter()
+        .map(|job| {
+            let permits = Arc::clone(&permits);
+            let rate_limited = Arc::clone(&rate_limited);
+            tokio::spawn(async move {
+                // O semáforo nunca é fechado, então acquire não falha
+                let _permit = permits.acquire_owned().await.ok()?;
+                if rate_limited.load(Ordering::SeqCst) {
+                    return None;
+                }
+
+                let result = job.await;
+                if matches!(&result, Err(error) if error.is_rate_limit()) {
+                    rate_limited.store(true, Ordering::SeqCst);
+                }
+                Some(result)
+            })
+        })
+        .collect();
+
+    // Aguardar na ordem de criação mantém a ordem original dos resultados
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        // Um panic em uma tarefa vira o erro só daquela busca
+        results.push(handle.await.unwrap_or_else(|e| {
+            Some(Err(ActivityError::NetworkError {
+                msg: format!("fetch task failed: {}", e),
+                source: None,
+            }))
+        }));
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    fn block_on<T>(future: impl Future<Output = T>) -> T {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(4)
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    fn pages(count: usize) -> FetchedPages {
+        FetchedPages { bodies: vec![String::new(); count], ..FetchedPages::default() }
+    }
+
+    #[test]
+    fn test_results_keep_the_original_order() {
+        let jobs: Vec<_> = (1..=6)
+            .map(|count| async move {
+                // Os primeiros demoram mais: terminam por último
+                for _ in 0..(6 - count) * 10 {
+                    tokio::task::yield_now().await;
+                }
+                Ok(pages(count))
+            })
+            .collect();
+
+        let counts: Vec<usize> = block_on(fetch_concurrently(jobs, 4))
+            .into_iter()
+            .map(|result| result.unwrap().unwrap().bodies.len())
+            .collect();
+        assert_eq!(counts, vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_in_flight_is_bounded() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let jobs: Vec<_> = (0..10)
+            .map(|_| {
+                let (running, peak) = (Arc::clone(&running), Arc::clone(&peak));
+                async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    for _ in 0..20 {
+                        tokio::task::yield_now().await;
+                    }
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok(pages(1))
+                }
+            })
+            .collect();
+
+        let results = block_on(fetch_concurrently(jobs, 3));
+        assert_eq!(results.len(), 10);
+        assert!(peak.load(Ordering::SeqCst) <= 3, "peak {}", peak.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_errors_do_not_cancel_others_but_rate_limits_do() {
+        let job = |result: Result<FetchedPages, ActivityError>| async move { result };
+        let results = block_on(fetch_concurrently(
+            vec![
+                job(Err(ActivityError::UserNotFound("torvlads".to_string()))),
+                job(Ok(pages(1))),
+                job(Err(ActivityError::RateLimited { reset_at: None })),
+                job(Ok(pages(1))),
+            ],
+            1,
+        ));
+
+        assert!(matches!(results[0], Some(Err(ActivityError::UserNotFound(_)))));
+        assert!(matches!(results[1], Some(Ok(_))));
+        assert!(matches!(results[2], Some(Err(ActivityError::RateLimited { .. }))));
+        assert!(results[3].is_none());
+    }
+}
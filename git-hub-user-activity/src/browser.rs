@@ -0,0 +1,184 @@
+//! Este módulo abre um evento da listagem no navegador (--open N)
+//!
+//! Cada sistema tem um programa que abre links no navegador padrão: `xdg-open` no
+//! Linux (e outros Unix), `open` no macOS e o `start` do cmd no Windows.
+//! O programa é só iniciado, sem esperar: alguns `xdg-open` ficam presos até o
+//! navegador fechar
+//!
+//! Quem abre o link é um [`Opener`]: os testes usam um que só anota a URL
+
+use std::io;
+use std::process::{Command, Stdio};
+
+use github_activity::error::ActivityError;
+use github_activity::models::GitHubEvent;
+
+// CONCEITO: Traits como ponto de extensão
+// open_event() não sabe (nem precisa saber) se um processo será iniciado;
+// o programa passa um SystemOpener e os testes, um que só guarda as URLs
+pub trait Opener {
+    // O nome do programa, para as mensagens de erro
+    fn name(&self) -> &str;
+    fn open(&mut self, url: &str) -> io::Result<()>;
+}
+
+// O abridor do sistema: o programa (e os argumentos antes da URL) de cada plataforma
+pub struct SystemOpener {
+    program: &'static str,
+    args: &'static [&'static str],
+}
+
+impl SystemOpener {
+    pub fn new() -> SystemOpener {
+        if cfg!(target_os = "macos") {
+            SystemOpener { program: "open", args: &[] }
+        } else if cfg!(windows) {
+            // `start` é um comando interno do cmd; o "" vazio é o título da janela,
+            // senão uma URL entre aspas seria tomada como título
+            SystemOpener { program: "cmd", args: &["/C", "start", ""] }
+        } else {
+            SystemOpener { program: "xdg-open", args: &[] }
+        }
+    }
+}
+
+impl Opener for SystemOpener {
+    fn name(&self) -> &str {
+        self.program
+    }
+
+    fn open(&mut self, url: &str) -> io::Result<()> {
+        Command::new(self.program)
+            .args(self.args)
+            .arg(url)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map(|_| ())
+    }
+}
+
+// Abre o evento de número `number` (contado a partir de 1, como em --numbered)
+// e devolve a URL aberta
+pub fn open_event(
+    events: &[GitHubEvent],
+    number: usize,
+    opener: &mut impl Opener,
+) -> Result<String, ActivityError> {
+    let event = match number.checked_sub(1).and_then(|index| events.get(index)) {
+        Some(event) => event,
+        None => {
+            let listed = match events.len() {
+                0 => "no events were listed".to_string(),
+                1 => "only 1 event was listed".to_string(),
+                count => format!("only {} events were listed (use --numbered to see the numbers)", count),
+            };
+            return Err(ActivityError::InvalidArgument(format!("--open {}: {}", number, listed)));
+        }
+    };
+
+    let url = match event.html_url() {
+        Some(url) => url,
+        None => {
+            return Err(ActivityError::InvalidArgument(format!(
+                "--open {}: the event's repository '{}' has no owner, so it has no page to open",
+                number, event.repo_name
+            )))
+        }
+    };
+    opener.open(&url).map_err(|e| {
+        // NotFound: o programa não existe (ex: um servidor sem ambiente gráfico)
+        let reason = match e.kind() {
+            io::ErrorKind::NotFound => format!("'{}' was not found", opener.name()),
+            _ => format!("'{}' failed: {}", opener.name(), e),
+        };
+        ActivityError::BrowserError { url: url.clone(), reason }
+    })?;
+
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use github_activity::models::{EventKind, EventPayload};
+
+    // Guarda as URLs em vez de abri-las; com `missing`, finge que o programa não existe
+    #[derive(Default)]
+    struct FakeOpener {
+        opened: Vec<String>,
+        missing: bool,
+    }
+
+    impl Opener for FakeOpener {
+        fn name(&self) -> &str {
+            "fake-open"
+        }
+
+        fn open(&mut self, url: &str) -> io::Result<()> {
+            if self.missing {
+                return Err(io::ErrorKind::NotFound.into());
+            }
+            self.opened.push(url.to_string());
+            Ok(())
+        }
+    }
+
+    fn events() -> Vec<GitHubEvent> {
+        vec![
+            GitHubEvent::new(EventKind::Watch, "a/b".to_string(), EventPayload::WatchEvent, None),
+            GitHubEvent::new(
+                EventKind::Issues,
+                "a/b".to_string(),
+                EventPayload::IssuesEvent { action: "opened".to_string(), number: Some(42), title: None },
+                None,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_opens_the_url_of_the_numbered_event() {
+        let mut opener = FakeOpener::default();
+
+        assert_eq!(open_event(&events(), 2, &mut opener).unwrap(), "https://github.com/a/b/issues/42");
+        assert_eq!(open_event(&events(), 1, &mut opener).unwrap(), "https://github.com/a/b");
+        assert_eq!(opener.opened, vec!["https://github.com/a/b/issues/42", "https://github.com/a/b"]);
+    }
+
+    #[test]
+    fn test_out_of_range_numbers() {
+        let mut opener = FakeOpener::default();
+
+        let error = open_event(&events(), 3, &mut opener).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid argument: --open 3: only 2 events were listed (use --numbered to see the numbers)"
+        );
+        assert!(open_event(&events(), 0, &mut opener).is_err());
+        assert!(open_event(&[], 1, &mut opener).unwrap_err().to_string().contains("no events were listed"));
+        assert!(opener.opened.is_empty());
+    }
+
+    #[test]
+    fn test_missing_opener() {
+        let mut opener = FakeOpener { missing: true, ..Default::default() };
+
+        let error = open_event(&events(), 1, &mut opener).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Could not open https://github.com/a/b in the browser: 'fake-open' was not found"
+        );
+        assert_eq!(error.exit_code(), github_activity::error::EXIT_BROWSER);
+    }
+
+    #[test]
+    fn test_a_repository_without_owner_is_not_opened() {
+        let mut opener = FakeOpener::default();
+        let event = GitHubEvent::new(EventKind::Watch, "linux".to_string(), EventPayload::WatchEvent, None);
+
+        let error = open_event(&[event], 1, &mut opener).unwrap_err();
+        assert!(error.to_string().contains("repository 'linux' has no owner"), "{}", error);
+        assert!(opener.opened.is_empty());
+    }
+}
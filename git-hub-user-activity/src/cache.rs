@@ -0,0 +1,438 @@
+//! Este módulo guarda a última resposta do feed de cada usuário, para revalidá-la com ETag
+//!
+//! O GitHub manda um `ETag` em cada resposta. Na próxima busca, o cliente (ver
+//! [`crate::api::GitHubClient`]) envia `If-None-Match` com ele; se nada mudou, a API
+//! responde `304 Not Modified` sem corpo, e o corpo guardado aqui é usado no lugar.
+//! Um 304 não gasta do limite de requisições.
+//!
+//! Cada usuário tem um arquivo (só a primeira página dos eventos é guardada):
+//!
+//! ```text
+//! ~/.cache/github-activity/responses/events-torvalds.json
+//! {"url":"https://api.github.com/users/torvalds/events","etag":"W/\"a1b2\"","last_page":3,
+//!  "fetched_at":1717243200,"body":"[...]"}
+//! ```
+//!
+//! Com --max-age, uma entrada buscada (ou revalidada) há menos tempo que isso é usada
+//! sem requisição nenhuma (ver [`CacheEntry::is_fresh`]).
+//!
+//! --cache-info lista as entradas e --clear-cache as apaga. As duas só olham para
+//! arquivos com o nome que este módulo cria (`events-<username>.json`): qualquer outra
+//! coisa no diretório fica intacta, e um arquivo que não pode ser lido ou apagado vira
+//! um problema no relatório, sem interromper os outros
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::api;
+use crate::date::Timestamp;
+use crate::error::ActivityError;
+use crate::json::{self, Json};
+use crate::output::AtomicFile;
+use crate::parser::{extract_number_value, extract_string_value};
+use crate::seen;
+
+// Subdiretório das respostas, dentro do diretório do programa no cache do usuário
+const RESPONSES_SUBDIR: &str = "responses";
+// O nome de cada entrada: events-<username>.json
+const FILE_PREFIX: &str = "events-";
+const FILE_SUFFIX: &str = ".json";
+
+/// Uma resposta guardada: o endereço pedido, o ETag e o corpo que a API mandou
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntry {
+    /// A URL exata da requisição (com per_page): outra URL não reaproveita a entrada
+    pub url: String,
+    /// O header ETag da resposta, enviado de volta em If-None-Match
+    pub etag: String,
+    /// A última página informada pelo header Link, se havia um
+    pub last_page: Option<usize>,
+    /// Quando a resposta foi buscada ou revalidada pela última vez
+    /// (None numa entrada antiga, sem o campo: nunca é considerada fresca)
+    pub fetched_at: Option<Timestamp>,
+    /// O corpo da resposta (o array de eventos)
+    pub body: String,
+}
+
+impl CacheEntry {
+    /// Há quanto tempo a entrada foi buscada, visto de `now`
+    /// None sem fetched_at ou com `now` antes dele (o relógio voltou para trás)
+    pub fn age(&self, now: Timestamp) -> Option<Duration> {
+        let seconds = now.epoch_seconds() - self.fetched_at?.epoch_seconds();
+        u64::try_from(seconds).ok().map(Duration::from_secs)
+    }
+
+    /// A entrada pode ser usada sem perguntar à API: tem menos de `max_age`
+    /// Uma idade desconhecida conta como velha, e `max_age` zero nunca é fresca
+    pub fn is_fresh(&self, max_age: Duration, now: Timestamp) -> bool {
+        self.age(now).is_some_and(|age| age < max_age)
+    }
+
+    // Um objeto JSON numa linha só (o corpo vai como string, com escapes)
+    fn to_json(&self) -> String {
+        Json::object([
+            ("url", self.url.as_str().into()),
+            ("etag", self.etag.as_str().into()),
+            ("last_page", self.last_page.into()),
+            ("fetched_at", self.fetched_at.map(|at| at.epoch_seconds()).into()),
+            ("body", self.body.as_str().into()),
+        ])
+        .to_string()
+    }
+
+    // None para qualquer arquivo estranho: uma entrada ilegível é só uma entrada a menos
+    fn parse(text: &str) -> Option<CacheEntry> {
+        let value = json::parse(text).ok()?;
+        Some(CacheEntry {
+            url: extract_string_value(&value, "url").ok()??,
+            etag: extract_string_value(&value, "etag").ok()??,
+            last_page: extract_number_value(&value, "last_page"),
+            fetched_at: extract_number_value(&value, "fetched_at").map(Timestamp::from_epoch_seconds),
+            body: extract_string_value(&value, "body").ok()??,
+        })
+    }
+}
+
+/// Uma entrada no diretório, como --cache-info mostra
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryInfo {
+    /// O username da entrada (tirado do nome do arquivo)
+    pub username: String,
+    /// Tamanho do arquivo, em bytes
+    pub size: u64,
+    /// Quando o arquivo foi escrito pela última vez (None se o sistema não informa)
+    pub modified: Option<Timestamp>,
+}
+
+/// O conteúdo do diretório de cache
+#[derive(Debug, Default)]
+pub struct CacheInfo {
+    /// As entradas, em ordem alfabética de username
+    pub entries: Vec<EntryInfo>,
+    /// Arquivos do cache que não puderam ser examinados, e o motivo
+    pub problems: Vec<(PathBuf, io::Error)>,
+}
+
+impl CacheInfo {
+    /// A soma dos tamanhos das entradas
+    pub fn total_size(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.size).sum()
+    }
+}
+
+/// O que --clear-cache apagou (e o que não conseguiu apagar)
+#[derive(Debug, Default)]
+pub struct ClearReport {
+    /// As entradas apagadas
+    pub removed: Vec<EntryInfo>,
+    /// Os usernames pedidos que não tinham entrada
+    pub missing: Vec<String>,
+    /// Arquivos que não puderam ser apagados, e o motivo
+    pub failed: Vec<(PathBuf, io::Error)>,
+}
+
+/// O diretório de respostas guardadas
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    /// Um cache em `dir` (criado na primeira escrita)
+    pub fn new(dir: impl Into<PathBuf>) -> Cache {
+        Cache { dir: dir.into() }
+    }
+
+    /// O cache no lugar padrão ("~/.cache/github-activity/responses" no Linux),
+    /// ou None se o sistema não informa um diretório de cache
+    pub fn default_location() -> Option<Cache> {
+        seen::app_cache_dir().map(|dir| Cache::new(dir.join(RESPONSES_SUBDIR)))
+    }
+
+    /// O diretório das entradas
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// A entrada de `username`, se houver uma legível
+    pub fn load(&self, username: &str) -> Option<CacheEntry> {
+        let text = fs::read_to_string(self.entry_path(username)).ok()?;
+        CacheEntry::parse(&text)
+    }
+
+    /// Guarda (ou substitui) a entrada de `username`
+    /// A escrita é atômica: uma execução ao mesmo tempo lê a entrada antiga ou a nova
+    pub fn store(&self, username: &str, entry: &CacheEntry) -> Result<(), ActivityError> {
+        fs::create_dir_all(&self.dir).map_err(|e| ActivityError::io(self.dir.display().to_string(), e))?;
+        let path = self.entry_path(username);
+        let mut file = AtomicFile::create(&path)?;
+        file.write_all(entry.to_json().as_bytes())
+            .map_err(|e| ActivityError::io(path.display().to_string(), e))?;
+        file.commit()
+    }
+
+    /// As entradas do diretório (um diretório que ainda não existe está vazio)
+    pub fn info(&self) -> Result<CacheInfo, ActivityError> {
+        let mut info = CacheInfo::default();
+        for (username, path) in self.entry_files()? {
+            match entry_info(username, &path) {
+                Ok(entry) => info.entries.push(entry),
+                Err(error) => info.problems.push((path, error)),
+            }
+        }
+        Ok(info)
+    }
+
+    /// Apaga as entradas de `usernames` (todas, se a lista for vazia)
+    /// Um arquivo que não pode ser apagado fica em [`ClearReport::failed`] e os outros continuam
+    pub fn clear(&self, usernames: &[String]) -> Result<ClearReport, ActivityError> {
+        let mut report = ClearReport::default();
+        let files = self.entry_files()?;
+
+        let wanted = |username: &str| usernames.is_empty() || usernames.iter().any(|name| name.eq_ignore_ascii_case(username));
+        for (username, path) in files.iter().filter(|(username, _)| wanted(username)) {
+            // O tamanho é só para o relatório: sem ele, a entrada ainda é apagada
+            let entry = entry_info(username.clone(), path)
+                .unwrap_or(EntryInfo { username: username.clone(), size: 0, modified: None });
+            match fs::remove_file(path) {
+                Ok(()) => report.removed.push(entry),
+                Err(error) => report.failed.push((path.clone(), error)),
+            }
+        }
+
+        report.missing = usernames
+            .iter()
+            .filter(|name| !files.iter().any(|(username, _)| username.eq_ignore_ascii_case(name)))
+            .cloned()
+            .collect();
+        Ok(report)
+    }
+
+    fn entry_path(&self, username: &str) -> PathBuf {
+        self.dir.join(format!("{}{}{}", FILE_PREFIX, username.to_lowercase(), FILE_SUFFIX))
+    }
+
+    // Os arquivos de entrada do diretório, em ordem de username
+    // Só arquivos comuns com o nome que entry_path cria: links, subdiretórios, temporários
+    // (".events-x.json.tmp-123") e arquivos de outros programas são ignorados
+    fn entry_files(&self) -> Result<Vec<(String, PathBuf)>, ActivityError> {
+        let dir_error = |e| ActivityError::io(self.dir.display().to_string(), e);
+        let listing = match fs::read_dir(&self.dir) {
+            Ok(listing) => listing,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(dir_error(error)),
+        };
+
+        let mut files = Vec::new();
+        for item in listing {
+            let item = item.map_err(dir_error)?;
+            let name = item.file_name();
+            let username = match name.to_str().and_then(username_from_file) {
+                Some(username) => username.to_string(),
+                None => continue,
+            };
+            // file_type() não segue links simbólicos
+            if item.file_type().map(|kind| kind.is_file()).unwrap_or(false) {
+                files.push((username, item.path()));
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+}
+
+// "events-torvalds.json" -> "torvalds"; None para qualquer outro nome
+// O username precisa ser válido e estar em minúsculas, como entry_path escreve
+fn username_from_file(name: &str) -> Option<&str> {
+    let username = name.strip_prefix(FILE_PREFIX)?.strip_suffix(FILE_SUFFIX)?;
+    let valid = api::validate_username(username).is_ok() && !username.bytes().any(|b| b.is_ascii_uppercase());
+    valid.then_some(username)
+}
+
+fn entry_info(username: String, path: &Path) -> io::Result<EntryInfo> {
+    let metadata = fs::symlink_metadata(path)?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|age| Timestamp::from_epoch_seconds(age.as_secs() as i64));
+    Ok(EntryInfo { username, size: metadata.len(), modified })
+}
+
+/// Tamanho legível: "812 B", "4.1 KB", "2.3 MB" (múltiplos de 1024)
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    // Um diretório só deste teste (os testes rodam em paralelo)
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("github-activity-cache-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn entry(body: &str) -> CacheEntry {
+        CacheEntry {
+            url: "https://api.github.com/users/torvalds/events".to_string(),
+            etag: "W/\"abc\"".to_string(),
+            last_page: Some(3),
+            fetched_at: Some(Timestamp::from_epoch_seconds(1_717_243_200)),
+            body: body.to_string(),
+        }
+    }
+
+    // Um diretório com duas entradas e tudo que NÃO é uma entrada
+    fn mixed_dir(name: &str) -> (Cache, PathBuf) {
+        let dir = test_dir(name);
+        let cache = Cache::new(&dir);
+        cache.store("torvalds", &entry("[1]")).unwrap();
+        cache.store("Octocat", &entry("[]")).unwrap();
+        for unrelated in ["notes.txt", "events-.json", "events-Bad.json", "events-a b.json", ".events-x.json.tmp-1", "last-seen"] {
+            fs::write(dir.join(unrelated), "keep me").unwrap();
+        }
+        fs::create_dir(dir.join("events-folder.json")).unwrap();
+        (cache, dir)
+    }
+
+    fn unrelated_files_are_intact(dir: &Path) {
+        for unrelated in ["notes.txt", "events-.json", "events-Bad.json", "events-a b.json", ".events-x.json.tmp-1", "last-seen"] {
+            assert_eq!(fs::read_to_string(dir.join(unrelated)).unwrap(), "keep me", "{}", unrelated);
+        }
+        assert!(dir.join("events-folder.json").is_dir());
+    }
+
+    #[test]
+    fn test_store_and_load() {
+        let dir = test_dir("store");
+        let cache = Cache::new(dir.join("nested"));
+        let stored = entry("[{\"id\": \"1\", \"note\": \"line\\nbreak é\"}]");
+
+        cache.store("Torvalds", &stored).unwrap();
+        assert_eq!(cache.load("torvalds"), Some(stored.clone()));
+        assert_eq!(cache.load("TORVALDS"), Some(stored));
+        assert_eq!(cache.load("octocat"), None);
+
+        fs::write(cache.dir().join("events-broken.json"), "{\"url\": 1}").unwrap();
+        assert_eq!(cache.load("broken"), None);
+
+        // Uma entrada sem fetched_at ainda serve para revalidar, só nunca é fresca
+        fs::write(cache.dir().join("events-old.json"), r#"{"url":"u","etag":"e","last_page":null,"body":"[]"}"#).unwrap();
+        assert_eq!(cache.load("old").unwrap().fetched_at, None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_freshness() {
+        let fetched = entry("[]");
+        let at = |seconds: i64| Timestamp::from_epoch_seconds(1_717_243_200 + seconds);
+        let five_minutes = Duration::from_secs(300);
+
+        assert_eq!(fetched.age(at(120)), Some(Duration::from_secs(120)));
+        assert!(fetched.is_fresh(five_minutes, at(0)));
+        assert!(fetched.is_fresh(five_minutes, at(299)));
+        assert!(!fetched.is_fresh(five_minutes, at(300)));
+        // --max-age 0 sempre revalida
+        assert!(!fetched.is_fresh(Duration::ZERO, at(0)));
+        // O relógio voltou para trás: a idade é desconhecida
+        assert_eq!(fetched.age(at(-1)), None);
+        assert!(!fetched.is_fresh(five_minutes, at(-1)));
+
+        let unknown = CacheEntry { fetched_at: None, ..entry("[]") };
+        assert!(!unknown.is_fresh(five_minutes, at(0)));
+    }
+
+    #[test]
+    fn test_info_lists_only_cache_files() {
+        let (cache, dir) = mixed_dir("info");
+
+        let info = cache.info().unwrap();
+        let names: Vec<&str> = info.entries.iter().map(|entry| entry.username.as_str()).collect();
+        assert_eq!(names, vec!["octocat", "torvalds"]);
+        assert!(info.problems.is_empty());
+        assert_eq!(info.total_size(), fs::metadata(dir.join("events-octocat.json")).unwrap().len() * 2 + 1);
+        assert!(info.entries.iter().all(|entry| entry.modified.is_some()));
+
+        unrelated_files_are_intact(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_clear_some_or_all() {
+        let (cache, dir) = mixed_dir("clear");
+
+        let report = cache.clear(&["TORVALDS".to_string(), "nobody".to_string()]).unwrap();
+        let removed: Vec<&str> = report.removed.iter().map(|entry| entry.username.as_str()).collect();
+        assert_eq!(removed, vec!["torvalds"]);
+        assert_eq!(report.missing, vec!["nobody".to_string()]);
+        assert!(report.failed.is_empty());
+        assert!(cache.load("octocat").is_some());
+
+        let report = cache.clear(&[]).unwrap();
+        assert_eq!(report.removed.len(), 1);
+        assert!(cache.info().unwrap().entries.is_empty());
+
+        unrelated_files_are_intact(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_a_missing_directory_is_empty() {
+        let cache = Cache::new(test_dir("missing").join("never-created"));
+        assert!(cache.info().unwrap().entries.is_empty());
+        assert!(cache.clear(&[]).unwrap().removed.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_permission_errors_are_per_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (cache, dir) = mixed_dir("permissions");
+        // Sem permissão de escrita no diretório, nenhum arquivo pode ser apagado
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o555)).unwrap();
+        let report = cache.clear(&[]);
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let report = report.unwrap();
+        // Como root, a permissão não impede nada: o teste só confere que nada foi abortado
+        assert_eq!(report.removed.len() + report.failed.len(), 2);
+        unrelated_files_are_intact(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_username_from_file() {
+        assert_eq!(username_from_file("events-torvalds.json"), Some("torvalds"));
+        assert_eq!(username_from_file("events-a-b.json"), Some("a-b"));
+        assert_eq!(username_from_file("events-Torvalds.json"), None);
+        assert_eq!(username_from_file("events-.json"), None);
+        assert_eq!(username_from_file("torvalds.json"), None);
+        assert_eq!(username_from_file("events-torvalds.json.tmp-1"), None);
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(4198), "4.1 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    }
+}
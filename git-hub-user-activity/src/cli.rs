@@ -0,0 +1,2513 @@
+//! Este módulo interpreta os argumentos da linha de comando
+//! Não usamos crates como 'clap': assim como o JSON, o parsing é feito "na unha"
+//!
+//! Todas as flags ficam registradas em tabelas (FlagSpec) que alimentam o --help,
+//! para que a documentação nunca fique desatualizada em relação ao código
+
+use std::str::FromStr;
+
+use github_activity::api;
+use github_activity::config::{self, Config};
+use github_activity::credentials::TokenSource;
+use github_activity::date::Timestamp;
+use github_activity::display::{GroupBy, OutputFormat, Template};
+use github_activity::error::{ActivityError, EXIT_CODES};
+use github_activity::generator::{self, GenerateOptions};
+use github_activity::i18n::Lang;
+use github_activity::models::{EventCategory, EventKind};
+use github_activity::stats::{RepoSort, SortOrder};
+
+use crate::completions::Shell;
+use crate::input;
+
+// Descrição de uma flag para o texto de ajuda (e para os scripts de --completions)
+pub struct FlagSpec {
+    pub name: &'static str,
+    // Nome do valor esperado, ex: Some("N"); valores fixos vêm separados por '|',
+    // ex: Some("repo|type"), e PATH é um caminho de arquivo
+    pub value: Option<&'static str>,
+    pub help: &'static str,
+}
+
+// Flags do modo padrão (listar atividade de um usuário)
+pub const FLAGS: &[FlagSpec] = &[
+    FlagSpec {
+        name: "--all",
+        value: None,
+        help: "Fetch the full history GitHub keeps (up to 300 events, 3 requests)",
+    },
+    FlagSpec {
+        name: "--from-file",
+        value: Some("PATH"),
+        help: "Read an events JSON array from PATH ('-' for stdin) instead of the API",
+    },
+    FlagSpec {
+        name: "--record",
+        value: Some("PATH"),
+        help: "Also save the raw API responses to PATH, with a small metadata header",
+    },
+    FlagSpec {
+        name: "--export-sqlite",
+        value: Some("PATH"),
+        help: "Also add the fetched events to the SQLite database PATH (needs the 'sqlite' feature)",
+    },
+    FlagSpec {
+        name: "--log-history",
+        value: None,
+        help: "Also append the fetched events to the local history of <username>",
+    },
+    FlagSpec {
+        name: "--history",
+        value: None,
+        help: "Show the local history of <username> (see --log-history) instead of fetching",
+    },
+    FlagSpec {
+        name: "--since",
+        value: Some("DATE"),
+        help: "With --history or --backend graphql, only events from DATE on (2024-05-01 or an RFC 3339 time)",
+    },
+    FlagSpec {
+        name: "--backend",
+        value: Some("rest|graphql"),
+        help: "Fetch the events feed (rest, default) or the GraphQL contributions (needs 'graphql' and a token)",
+    },
+    FlagSpec {
+        name: "--starred",
+        value: None,
+        help: "List the repositories <username> starred instead of the events",
+    },
+    FlagSpec {
+        name: "--list-repos",
+        value: None,
+        help: "List the public repositories of <username> instead of the events (most recently updated first)",
+    },
+    FlagSpec {
+        name: "--no-forks",
+        value: None,
+        help: "With --list-repos, leave out the repositories that are forks",
+    },
+    FlagSpec {
+        name: "--replay",
+        value: Some("PATH"),
+        help: "Show a file saved by --record (same as --from-file)",
+    },
+    FlagSpec {
+        name: "--output",
+        value: Some("PATH"),
+        help: "Write the output to PATH instead of the terminal (only if the run succeeds)",
+    },
+    FlagSpec {
+        name: "--collapse",
+        value: None,
+        help: "Merge consecutive pushes to the same repository into one line",
+    },
+    FlagSpec {
+        name: "--quiet",
+        value: None,
+        help: "Print only the events (or the summary, JSON...): no progress line, header or trailing blank line",
+    },
+    FlagSpec {
+        name: "--count-only",
+        value: None,
+        help: "Print only the number of events after the filters (per group with --group-by: KEY<TAB>COUNT)",
+    },
+    FlagSpec {
+        name: "--type",
+        value: Some("TYPES"),
+        help: "Only events of these types, comma-separated API names (PushEvent,PullRequestEvent)",
+    },
+    FlagSpec {
+        name: "--category",
+        value: Some("code|issues|review|social|release|other"),
+        help: "Only events of these categories, comma-separated (code,social); see below",
+    },
+    FlagSpec {
+        name: "--action",
+        value: Some("ACTIONS"),
+        help: "Only events whose payload action is one of these (opened,closed); events without one are left out",
+    },
+    FlagSpec {
+        name: "--no-bots",
+        value: None,
+        help: "Hide events by bots ([bot] logins and the ignored_actors config key)",
+    },
+    FlagSpec {
+        name: "--ignore-actor",
+        value: Some("LOGIN"),
+        help: "Hide events by LOGIN (repeatable)",
+    },
+    FlagSpec {
+        name: "--no-dedup",
+        value: None,
+        help: "Keep repeated stars and forks of the same repository (by default only the latest is shown)",
+    },
+    FlagSpec {
+        name: "--commits",
+        value: None,
+        help: "List the commit messages of each push (up to 5 per push)",
+    },
+    FlagSpec {
+        name: "--new",
+        value: None,
+        help: "Show only events newer than the last run for this user or repository",
+    },
+    FlagSpec {
+        name: "--notify",
+        value: None,
+        help: "With --new, also show a desktop notification summarizing the new events",
+    },
+    FlagSpec {
+        name: "--post-webhook",
+        value: Some("URL"),
+        help: "Also POST the events (only the new ones with --new) to URL as JSON",
+    },
+    FlagSpec {
+        name: "--slack",
+        value: None,
+        help: "With --post-webhook, send a Slack message ({\"text\": ...}) instead of the events",
+    },
+    FlagSpec {
+        name: "--dry-run",
+        value: None,
+        help: "With --post-webhook, print the body to stderr instead of sending it",
+    },
+    FlagSpec {
+        name: "--reset-seen",
+        value: None,
+        help: "Forget the last-seen event for this user or repository and exit",
+    },
+    FlagSpec {
+        name: "--limit",
+        value: Some("N"),
+        help: "Show at most N events",
+    },
+    FlagSpec {
+        name: "--first-contributions",
+        value: None,
+        help: "Mark events in repos with no older activity in this window",
+    },
+    FlagSpec {
+        name: "--only",
+        value: None,
+        help: "With --first-contributions, show only the marked events",
+    },
+    FlagSpec {
+        name: "--sort",
+        value: Some("newest|oldest|repo|type|stars|updated|name"),
+        help: "Order of the listed events (default: newest first; repo and type keep the time order within each) or, with stars|updated|name, of --list-repos",
+    },
+    FlagSpec {
+        name: "--group-by",
+        value: Some("repo|type"),
+        help: "Group events under a heading per repository or per event type",
+    },
+    FlagSpec {
+        name: "--summary",
+        value: None,
+        help: "Print totals per event type and the most active repositories",
+    },
+    FlagSpec {
+        name: "--histogram",
+        value: None,
+        help: "Chart the number of events per day, in local time",
+    },
+    FlagSpec {
+        name: "--by-week",
+        value: None,
+        help: "Table of events per ISO week (pushes, PRs, issues, other), newest week first",
+    },
+    FlagSpec {
+        name: "--utc",
+        value: None,
+        help: "With --histogram, --by-week or --summary, count days in UTC instead of local time",
+    },
+    FlagSpec {
+        name: "--compare",
+        value: Some("USER"),
+        help: "Compare with another user: totals, types, shared repos, busiest day",
+    },
+    FlagSpec {
+        name: "--repos-only",
+        value: None,
+        help: "List each repository touched, with its event count and latest event type",
+    },
+    FlagSpec {
+        name: "--json",
+        value: None,
+        help: "With --summary, --repos-only, --by-week or --doctor, print the result as JSON",
+    },
+    FlagSpec {
+        name: "--raw",
+        value: None,
+        help: "Print the response body as the API sent it (or the --from-file file), indented",
+    },
+    FlagSpec {
+        name: "--template",
+        value: Some("TEXT"),
+        help: "Print each event as TEXT instead of the usual line (see below)",
+    },
+    FlagSpec {
+        name: "--format",
+        value: Some("text|table|ndjson|csv"),
+        help: "List events as text (default), table (aligned columns) or ndjson (see below); csv is for --by-week",
+    },
+    FlagSpec {
+        name: "--wide",
+        value: None,
+        help: "With --format table, don't shorten long repository names and details",
+    },
+    FlagSpec {
+        name: "--pager",
+        value: None,
+        help: "In a terminal, show long output one screen at a time (space, Enter, q)",
+    },
+    FlagSpec {
+        name: "--no-color",
+        value: None,
+        help: "Disable colors (also disabled by NO_COLOR or when not a terminal)",
+    },
+    FlagSpec {
+        name: "--hyperlinks",
+        value: None,
+        help: "In a terminal, make repositories, issues and PRs clickable links",
+    },
+    FlagSpec {
+        name: "--numbered",
+        value: None,
+        help: "Number the events (1, 2, 3...) instead of starting each line with '-'",
+    },
+    FlagSpec {
+        name: "--open",
+        value: Some("N"),
+        help: "After listing, open event number N on GitHub in the default browser",
+    },
+    FlagSpec {
+        name: "--lang",
+        value: Some("en|pt-BR"),
+        help: "Language of the output (default: from LANG, else en)",
+    },
+    FlagSpec {
+        name: "--emoji",
+        value: None,
+        help: "Start each event with an icon for its type (📦 push, ⭐ star, 🔀 PR...)",
+    },
+    FlagSpec {
+        name: "--no-time",
+        value: None,
+        help: "Don't show relative timestamps like \"(2 hours ago)\"",
+    },
+    FlagSpec {
+        name: "--no-profile",
+        value: None,
+        help: "Don't fetch the user's name and followers for the header (saves a request)",
+    },
+    FlagSpec {
+        name: "--per-page",
+        value: Some("N"),
+        help: "Ask the API for N events per page, 1 to 100 (with --all, default: 100)",
+    },
+    FlagSpec {
+        name: "--max-events",
+        value: Some("N"),
+        help: "With --all, stop fetching pages once N events were fetched",
+    },
+    FlagSpec {
+        name: "--jobs",
+        value: Some("N"),
+        help: "With several usernames, fetch up to N at the same time (default: 4)",
+    },
+    FlagSpec {
+        name: "--token",
+        value: Some("TOKEN"),
+        help: "GitHub access token, for a higher rate limit (also GITHUB_TOKEN)",
+    },
+    FlagSpec {
+        name: "--no-gh-token",
+        value: None,
+        help: "Don't fall back to the token of the gh CLI login",
+    },
+    FlagSpec {
+        name: "--wait-on-limit",
+        value: None,
+        help: "On a secondary rate limit, wait as asked (up to 60s) and retry once",
+    },
+    FlagSpec {
+        name: "--skip-validation",
+        value: None,
+        help: "Don't check usernames against github.com's rules (for Enterprise instances with other rules)",
+    },
+    FlagSpec {
+        name: "--no-compression",
+        value: None,
+        help: "Don't ask the API for gzip-compressed responses",
+    },
+    FlagSpec {
+        name: "--no-cache",
+        value: None,
+        help: "Don't read or update the response cache (always download the events again)",
+    },
+    FlagSpec {
+        name: "--max-age",
+        value: Some("SECONDS"),
+        help: "Use cached events younger than SECONDS without asking the API (0: always revalidate)",
+    },
+    FlagSpec {
+        name: "--api-url",
+        value: Some("URL"),
+        help: "API base URL, e.g. for GitHub Enterprise (default: https://api.github.com)",
+    },
+    FlagSpec {
+        name: "--verbose",
+        value: None,
+        help: "Log requests and parsing to stderr, and explain skipped or unknown events (-v)",
+    },
+    FlagSpec {
+        name: "-vv",
+        value: None,
+        help: "Like --verbose, plus request and response headers (the token is redacted)",
+    },
+    FlagSpec {
+        name: "--strict",
+        value: None,
+        help: "Fail instead of skipping events that could not be parsed",
+    },
+    FlagSpec {
+        name: "--doctor",
+        value: None,
+        help: "Check the connection, the token, the rate limit and the cache directory, then exit",
+    },
+    FlagSpec {
+        name: "--cache-info",
+        value: None,
+        help: "Show where the response cache is, the cached users, their size and age, then exit",
+    },
+    FlagSpec {
+        name: "--clear-cache",
+        value: None,
+        help: "Delete the cached responses (only of the given usernames, if any), then exit",
+    },
+    FlagSpec {
+        name: "--help",
+        value: None,
+        help: "Show this help and exit",
+    },
+];
+
+// Flags do subcomando `generate`
+pub const GENERATE_FLAGS: &[FlagSpec] = &[
+    FlagSpec {
+        name: "--events",
+        value: Some("N"),
+        help: "Number of events to generate (default: 30)",
+    },
+    FlagSpec {
+        name: "--seed",
+        value: Some("N"),
+        help: "Seed for the deterministic generator (default: 42)",
+    },
+    FlagSpec {
+        name: "--kinds",
+        value: Some("LIST"),
+        help: "Comma-separated event kinds, e.g. push,issue,star (default: all)",
+    },
+];
+
+// De quem (ou de onde) buscar a atividade: mora na biblioteca, que também busca
+// vários alvos de uma vez (GitHubClient::fetch_parallel)
+pub use github_activity::api::Target;
+
+// O que a execução escreve além dos eventos (ver Options::verbosity)
+// A saída de dados vai para stdout; o progresso vai sempre para stderr
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Verbosity {
+    pub progress: bool, // "Fetching recent activity for 'x'..." (em stderr)
+    pub header: bool,   // O cabeçalho ("Found N events") e as linhas em branco depois da saída
+    pub notices: bool,  // Os avisos de lista vazia ("No recent activity found...")
+}
+
+// Opções do modo padrão
+// Default: sem alvo e todas as flags desligadas
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    pub target: Option<Target>,     // None só com --from-file sem username
+    pub more_targets: Vec<Target>,  // 2º username em diante: vários usuários de uma vez
+    pub from_file: Option<String>,  // --from-file / --replay PATH ("-" = stdin)
+    pub record: Option<String>,     // --record PATH
+    pub export_sqlite: Option<String>, // --export-sqlite PATH
+    pub log_history: bool,          // --log-history
+    pub history: bool,              // --history: lê o histórico local em vez da API
+    pub since: Option<Timestamp>,   // --since DATE (só com --history ou --backend graphql)
+    pub backend: Backend,           // --backend rest|graphql
+    pub starred: bool,              // --starred: lista repositórios em vez de eventos
+    pub list_repos: bool,           // --list-repos: idem, os repositórios do usuário
+    pub no_forks: bool,             // --no-forks
+    pub repo_sort: Option<RepoSort>, // --sort stars|updated|name (só com --list-repos)
+    pub output: Option<String>,     // --output PATH
+    pub pager: bool,                // --pager
+    pub all: bool,                  // --all: busca todas as páginas
+    pub first_contributions: bool,  // --first-contributions
+    pub only: bool,                 // --only
+    pub no_time: bool,              // --no-time
+    pub no_profile: bool,           // --no-profile
+    pub no_color: bool,             // --no-color
+    pub hyperlinks: bool,           // --hyperlinks
+    pub emoji: bool,                // --emoji
+    pub numbered: bool,             // --numbered
+    pub open: Option<usize>,        // --open N (contado a partir de 1)
+    pub lang: Option<Lang>,         // --lang en|pt-BR (None: decidido pelo LANG)
+    pub group_by: Option<GroupBy>,  // --group-by repo|type
+    pub sort: Option<SortOrder>,    // --sort newest|oldest|repo|type
+    pub template: Option<Template>, // --template TEXT (já interpretado: erros saem antes da busca)
+    pub format: OutputFormat,       // --format text|ndjson|table
+    pub wide: bool,                 // --wide
+    pub collapse: bool,             // --collapse
+    pub no_dedup: bool,             // --no-dedup
+    pub quiet: bool,                // --quiet
+    pub count_only: bool,           // --count-only
+    pub types: Vec<EventKind>,      // --type PushEvent,PullRequestEvent
+    pub categories: Vec<EventCategory>, // --category code,social
+    pub actions: Vec<String>,       // --action opened,closed
+    pub no_bots: bool,              // --no-bots
+    pub ignored_actors: Vec<String>, // --ignore-actor LOGIN (repetível) e, com --no-bots, ignored_actors
+    pub new: bool,                  // --new
+    pub notify: bool,               // --notify
+    pub post_webhook: Option<String>, // --post-webhook URL
+    pub slack: bool,                // --slack
+    pub dry_run: bool,              // --dry-run
+    pub limit: Option<usize>,       // --limit N
+    pub jobs: Option<usize>,        // --jobs N
+    pub per_page: Option<usize>,    // --per-page N
+    pub max_events: Option<usize>,  // --max-events N
+    pub token: Option<String>,      // --token TOKEN
+    pub token_source: Option<TokenSource>, // preenchido por resolve (e pelo fallback do gh)
+    pub no_gh_token: bool,          // --no-gh-token
+    pub api_url: Option<String>,    // --api-url URL
+    pub api_version: Option<String>, // só do ambiente ou do arquivo (api_version)
+    pub no_compression: bool,       // --no-compression
+    pub no_cache: bool,             // --no-cache
+    pub max_age: Option<u64>,       // --max-age SECONDS
+    pub wait_on_limit: bool,        // --wait-on-limit
+    pub skip_validation: bool,      // --skip-validation
+    pub reset_seen: bool,           // --reset-seen
+    pub commits: bool,              // --commits
+    pub summary: bool,              // --summary
+    pub repos_only: bool,           // --repos-only
+    pub histogram: bool,            // --histogram
+    pub by_week: bool,              // --by-week
+    pub utc: bool,                  // --utc
+    pub compare: Option<String>,    // --compare USER
+    pub json: bool,                 // --json
+    pub raw: bool,                  // --raw: o corpo da resposta, indentado, sem virar eventos
+    pub verbose: bool,              // --verbose / -v
+    pub debug: bool,                // -vv (implica verbose)
+    pub strict: bool,               // --strict
+    pub doctor: bool,               // --doctor
+    pub cache_info: bool,           // --cache-info
+    pub clear_cache: bool,          // --clear-cache (os usernames vêm dos posicionais)
+}
+
+// De onde vêm os eventos (--backend)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    // O feed de eventos da API REST (/users/{username}/events)
+    #[default]
+    Rest,
+    // As contribuições da API GraphQL (feature "graphql", ver graphql.rs)
+    GraphQl,
+}
+
+// CONCEITO: Enum como resultado do parsing
+// Cada variante representa "o que o programa deve fazer"
+// O Command é criado uma vez só, no começo do main: o tamanho de Options não importa
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Activity(Options),
+    Generate(GenerateOptions),
+    Completions(Shell),
+    Help,
+}
+
+// Interpreta os argumentos (SEM o nome do programa, ou seja, args[1..])
+// As listas @arquivo e - são lidas do disco e da entrada padrão
+pub fn parse_args(args: &[String]) -> Result<Command, ActivityError> {
+    parse_args_with(args, input::read_list)
+}
+
+// parse_args com quem lê as listas de usernames (os testes passam um que não lê nada)
+fn parse_args_with(
+    args: &[String],
+    read_list: impl FnMut(&str) -> Result<String, ActivityError>,
+) -> Result<Command, ActivityError> {
+    if args.first().map(String::as_str) == Some("generate") {
+        return parse_generate_args(&args[1..]);
+    }
+
+    let mut options = Options::default();
+    let mut positionals = Vec::new();
+    // --sort pode vir antes de --list-repos: o valor só é interpretado depois do laço
+    let mut sort = None;
+    let mut i = 0;
+
+    while i < args.len() {
+        let (flag, inline_value) = split_flag(&args[i]);
+
+        match flag {
+            "-h" | "--help" => return Ok(Command::Help),
+            // Fora das tabelas (e do --help): só interessa a quem instala o programa
+            "--completions" => return Ok(Command::Completions(Shell::parse(take_value(args, &mut i, inline_value)?)?)),
+            "--all" => options.all = switch(flag, inline_value)?,
+            "--collapse" => options.collapse = switch(flag, inline_value)?,
+            "--no-dedup" => options.no_dedup = switch(flag, inline_value)?,
+            "--quiet" => options.quiet = switch(flag, inline_value)?,
+            "--count-only" => options.count_only = switch(flag, inline_value)?,
+            "--type" => options.types = parse_types(take_value(args, &mut i, inline_value)?)?,
+            "--category" => options.categories = parse_categories(take_value(args, &mut i, inline_value)?)?,
+            "--action" => options.actions = parse_actions(take_value(args, &mut i, inline_value)?)?,
+            "--no-bots" => options.no_bots = switch(flag, inline_value)?,
+            "--ignore-actor" => {
+                let login = take_value(args, &mut i, inline_value)?;
+                options.ignored_actors.push(login.to_string());
+            }
+            "--commits" => options.commits = switch(flag, inline_value)?,
+            "--new" => options.new = switch(flag, inline_value)?,
+            "--notify" => options.notify = switch(flag, inline_value)?,
+            "--post-webhook" => {
+                let url = take_value(args, &mut i, inline_value)?;
+                if !url.starts_with("https://") && !url.starts_with("http://") {
+                    return Err(invalid_value(flag, "expects an http:// or https:// URL".to_string()));
+                }
+                options.post_webhook = Some(url.to_string());
+            }
+            "--slack" => options.slack = switch(flag, inline_value)?,
+            "--dry-run" => options.dry_run = switch(flag, inline_value)?,
+            "--reset-seen" => options.reset_seen = switch(flag, inline_value)?,
+            "--first-contributions" => options.first_contributions = switch(flag, inline_value)?,
+            "--only" => options.only = switch(flag, inline_value)?,
+            "--summary" => options.summary = switch(flag, inline_value)?,
+            "--repos-only" => options.repos_only = switch(flag, inline_value)?,
+            "--histogram" => options.histogram = switch(flag, inline_value)?,
+            "--by-week" => options.by_week = switch(flag, inline_value)?,
+            "--utc" => options.utc = switch(flag, inline_value)?,
+            "--json" => options.json = switch(flag, inline_value)?,
+            "--raw" => options.raw = switch(flag, inline_value)?,
+            "--pager" => options.pager = switch(flag, inline_value)?,
+            "--wide" => options.wide = switch(flag, inline_value)?,
+            "-v" | "--verbose" => options.verbose = switch(flag, inline_value)?,
+            "-vv" => {
+                options.debug = switch(flag, inline_value)?;
+                options.verbose = true;
+            }
+            "--strict" => options.strict = switch(flag, inline_value)?,
+            "--doctor" => options.doctor = switch(flag, inline_value)?,
+            "--cache-info" => options.cache_info = switch(flag, inline_value)?,
+            "--clear-cache" => options.clear_cache = switch(flag, inline_value)?,
+            "--no-time" => options.no_time = switch(flag, inline_value)?,
+            "--no-profile" => options.no_profile = switch(flag, inline_value)?,
+            "--no-color" => options.no_color = switch(flag, inline_value)?,
+            "--hyperlinks" => options.hyperlinks = switch(flag, inline_value)?,
+            "--emoji" => options.emoji = switch(flag, inline_value)?,
+            "--numbered" => options.numbered = switch(flag, inline_value)?,
+            "--no-gh-token" => options.no_gh_token = switch(flag, inline_value)?,
+            "--no-compression" => options.no_compression = switch(flag, inline_value)?,
+            "--no-cache" => options.no_cache = switch(flag, inline_value)?,
+            "--wait-on-limit" => options.wait_on_limit = switch(flag, inline_value)?,
+            "--skip-validation" => options.skip_validation = switch(flag, inline_value)?,
+            // --replay é só outro nome: a leitura já entende o formato das gravações
+            "--from-file" | "--replay" => {
+                options.from_file = Some(take_value(args, &mut i, inline_value)?.to_string());
+            }
+            "--record" => {
+                options.record = Some(take_value(args, &mut i, inline_value)?.to_string());
+            }
+            "--export-sqlite" => {
+                options.export_sqlite = Some(take_value(args, &mut i, inline_value)?.to_string());
+            }
+            "--backend" => options.backend = parse_backend(take_value(args, &mut i, inline_value)?)?,
+            "--log-history" => options.log_history = switch(flag, inline_value)?,
+            "--history" => options.history = switch(flag, inline_value)?,
+            "--starred" => options.starred = switch(flag, inline_value)?,
+            "--list-repos" => options.list_repos = switch(flag, inline_value)?,
+            "--no-forks" => options.no_forks = switch(flag, inline_value)?,
+            "--since" => {
+                let value = take_value(args, &mut i, inline_value)?;
+                let since = Timestamp::parse_date(value).ok_or_else(|| {
+                    invalid_value(flag, format!("expects a date like 2024-05-01, got '{}'", value))
+                })?;
+                options.since = Some(since);
+            }
+            "--output" => {
+                options.output = Some(take_value(args, &mut i, inline_value)?.to_string());
+            }
+            "--open" => {
+                let number = parse_number(flag, take_value(args, &mut i, inline_value)?)?;
+                if number == 0 {
+                    return Err(ActivityError::InvalidArgument(
+                        "--open counts events from 1, as shown by --numbered".to_string(),
+                    ));
+                }
+                options.open = Some(number);
+            }
+            "--lang" => {
+                let value = take_value(args, &mut i, inline_value)?;
+                options.lang = Some(Lang::parse(value).map_err(|e| invalid_value(flag, e))?);
+            }
+            "--limit" => {
+                let value = take_value(args, &mut i, inline_value)?;
+                options.limit = Some(config::parse_limit(value).map_err(|e| invalid_value(flag, e))?);
+            }
+            "--jobs" => {
+                let jobs = parse_number(flag, take_value(args, &mut i, inline_value)?)?;
+                if jobs == 0 {
+                    return Err(ActivityError::InvalidArgument("--jobs must be at least 1".to_string()));
+                }
+                options.jobs = Some(jobs);
+            }
+            "--max-age" => options.max_age = Some(parse_number(flag, take_value(args, &mut i, inline_value)?)?),
+            "--per-page" => {
+                let per_page = parse_number(flag, take_value(args, &mut i, inline_value)?)?;
+                if !(1..=api::MAX_PER_PAGE).contains(&per_page) {
+                    return Err(ActivityError::InvalidArgument(format!(
+                        "--per-page must be between 1 and {}",
+                        api::MAX_PER_PAGE
+                    )));
+                }
+                options.per_page = Some(per_page);
+            }
+            "--max-events" => {
+                let max_events = parse_number(flag, take_value(args, &mut i, inline_value)?)?;
+                if max_events == 0 {
+                    return Err(ActivityError::InvalidArgument("--max-events must be at least 1".to_string()));
+                }
+                options.max_events = Some(max_events);
+            }
+            "--token" => options.token = Some(take_value(args, &mut i, inline_value)?.to_string()),
+            "--compare" => {
+                let other = take_value(args, &mut i, inline_value)?;
+                if other.contains('/') {
+                    return Err(ActivityError::InvalidArgument(
+                        "--compare takes a username, not <owner/repo>".to_string(),
+                    ));
+                }
+                options.compare = Some(other.to_string());
+            }
+            "--api-url" => {
+                let value = take_value(args, &mut i, inline_value)?;
+                options.api_url = Some(config::parse_api_url(value).map_err(|e| invalid_value(flag, e))?);
+            }
+            "--group-by" => {
+                options.group_by = Some(parse_group_by(take_value(args, &mut i, inline_value)?)?);
+            }
+            "--sort" => sort = Some(take_value(args, &mut i, inline_value)?),
+            "--format" => options.format = parse_format(take_value(args, &mut i, inline_value)?)?,
+            "--template" => {
+                let value = take_value(args, &mut i, inline_value)?;
+                options.template = Some(Template::parse(value).map_err(|e| invalid_value(flag, e))?);
+            }
+            flag if flag.starts_with('-') && flag.len() > 1 => {
+                return Err(unknown_flag(flag));
+            }
+            _ => positionals.push(args[i].clone()),
+        }
+
+        i += 1;
+    }
+
+    // A lista de repositórios tem as suas ordens; a de eventos, as dela
+    match sort {
+        Some(value) if options.list_repos => options.repo_sort = Some(parse_repo_sort(value)?),
+        Some(value) if parse_repo_sort(value).is_ok() => {
+            return Err(ActivityError::InvalidArgument(format!("--sort {} requires --list-repos", value)));
+        }
+        Some(value) => options.sort = Some(parse_sort(value)?),
+        None => {}
+    }
+
+    // Só --new sabe o que é novo desde a última execução
+    if options.notify && !options.new {
+        return Err(ActivityError::InvalidArgument("--notify requires --new".to_string()));
+    }
+
+    // O formato e o ensaio só fazem sentido com um webhook
+    if (options.slack || options.dry_run) && options.post_webhook.is_none() {
+        return Err(ActivityError::InvalidArgument(
+            "--slack and --dry-run require --post-webhook".to_string(),
+        ));
+    }
+    // O webhook recebe os eventos de um alvo: nem a comparação nem o NDJSON (que não
+    // espera todas as páginas) passam por esse caminho
+    if options.post_webhook.is_some() && (options.compare.is_some() || options.format == OutputFormat::Ndjson) {
+        return Err(ActivityError::InvalidArgument(
+            "--post-webhook cannot be combined with --compare or --format ndjson".to_string(),
+        ));
+    }
+
+    // --only sozinho não teria o que filtrar
+    if options.only && !options.first_contributions {
+        return Err(ActivityError::InvalidArgument(
+            "--only requires --first-contributions".to_string(),
+        ));
+    }
+
+    // Cada uma troca a listagem por outra saída: só dá para mostrar uma delas
+    let outputs = [options.summary, options.repos_only, options.histogram, options.by_week];
+    if outputs.iter().filter(|&&chosen| chosen).count() > 1 {
+        return Err(ActivityError::InvalidArgument(
+            "--summary, --repos-only, --histogram and --by-week cannot be combined".to_string(),
+        ));
+    }
+
+    // O fuso só importa para separar os dias (do histograma, das semanas e das sequências do resumo)
+    if options.utc && !options.histogram && !options.by_week && !options.summary {
+        return Err(ActivityError::InvalidArgument(
+            "--utc requires --histogram, --by-week or --summary".to_string(),
+        ));
+    }
+
+    // Por enquanto só o resumo, a lista de repositórios, as semanas e o --doctor têm uma versão em JSON
+    if options.json && !options.summary && !options.repos_only && !options.by_week && !options.doctor {
+        return Err(ActivityError::InvalidArgument(
+            "--json currently requires --summary, --repos-only, --by-week or --doctor".to_string(),
+        ));
+    }
+
+    // O CSV só existe para a tabela de semanas (os eventos têm --format ndjson)
+    if options.format == OutputFormat::Csv && (!options.by_week || options.json) {
+        return Err(ActivityError::InvalidArgument(
+            "--format csv currently requires --by-week (and cannot be combined with --json)".to_string(),
+        ));
+    }
+
+    // As semanas só têm uma ordem: a do calendário, para um lado ou para o outro
+    if options.by_week && matches!(options.sort, Some(SortOrder::Repo) | Some(SortOrder::Type)) {
+        return Err(ActivityError::InvalidArgument(
+            "--by-week only sorts by 'newest' (the default) or 'oldest'".to_string(),
+        ));
+    }
+
+    // --doctor verifica o ambiente, não um usuário
+    if options.doctor && !positionals.is_empty() {
+        return Err(ActivityError::InvalidArgument(
+            "--doctor takes no <username> or <owner/repo>".to_string(),
+        ));
+    }
+
+    // --cache-info mostra o cache inteiro; --clear-cache aceita usernames para apagar só os deles
+    if options.cache_info && options.clear_cache {
+        return Err(ActivityError::InvalidArgument(
+            "--cache-info and --clear-cache cannot be combined".to_string(),
+        ));
+    }
+    if options.cache_info && !positionals.is_empty() {
+        return Err(ActivityError::InvalidArgument(
+            "--cache-info takes no <username> (use --clear-cache <username> to delete an entry)".to_string(),
+        ));
+    }
+    if options.clear_cache && positionals.iter().any(|arg| arg.contains('/')) {
+        return Err(ActivityError::InvalidArgument(
+            "--clear-cache takes usernames: only the events of users are cached".to_string(),
+        ));
+    }
+
+    // Um arquivo local não tem "próximas páginas" para buscar
+    if options.all && options.from_file.is_some() {
+        return Err(ActivityError::InvalidArgument(
+            "--all cannot be combined with --from-file".to_string(),
+        ));
+    }
+
+    // Gravar exige uma resposta da API; regravar um arquivo não acrescentaria nada
+    if options.record.is_some() && options.from_file.is_some() {
+        return Err(ActivityError::InvalidArgument(
+            "--record cannot be combined with --from-file or --replay".to_string(),
+        ));
+    }
+
+    // O banco recebe os eventos de uma busca na API, como o --record
+    if options.export_sqlite.is_some() {
+        if options.from_file.is_some() || options.compare.is_some() || options.format == OutputFormat::Ndjson {
+            return Err(ActivityError::InvalidArgument(
+                "--export-sqlite cannot be combined with --from-file, --replay, --compare or --format ndjson"
+                    .to_string(),
+            ));
+        }
+        // Sem a feature, a flag continua existindo (no --help e nos autocompletes), mas só explica
+        if !cfg!(feature = "sqlite") {
+            return Err(ActivityError::InvalidArgument(
+                "--export-sqlite needs a build with the 'sqlite' feature (cargo build --features sqlite)"
+                    .to_string(),
+            ));
+        }
+    }
+
+    // --history lê o arquivo que --log-history escreve: os dois juntos não fazem sentido
+    if options.log_history && options.history {
+        return Err(ActivityError::InvalidArgument(
+            "--log-history cannot be combined with --history".to_string(),
+        ));
+    }
+    if options.log_history
+        && (options.from_file.is_some() || options.compare.is_some() || options.format == OutputFormat::Ndjson)
+    {
+        return Err(ActivityError::InvalidArgument(
+            "--log-history cannot be combined with --from-file, --replay, --compare or --format ndjson"
+                .to_string(),
+        ));
+    }
+    if options.history
+        && (options.from_file.is_some()
+            || options.all
+            || options.record.is_some()
+            || options.export_sqlite.is_some()
+            || options.compare.is_some())
+    {
+        return Err(ActivityError::InvalidArgument(
+            "--history reads the local history instead of the API and cannot be combined with \
+             --from-file, --replay, --all, --record, --export-sqlite or --compare"
+                .to_string(),
+        ));
+    }
+    if options.since.is_some() && !options.history && options.backend != Backend::GraphQl {
+        return Err(ActivityError::InvalidArgument(
+            "--since requires --history or --backend graphql".to_string(),
+        ));
+    }
+
+    // @arquivo e - viram os usernames da lista, validados antes de qualquer busca
+    if positionals.iter().any(|arg| input::is_list(arg)) {
+        if options.from_file.as_deref() == Some("-") && positionals.iter().any(|arg| arg == "-") {
+            return Err(ActivityError::InvalidArgument(
+                "--from-file - and a '-' username list cannot both read stdin".to_string(),
+            ));
+        }
+        positionals = input::expand(&positionals, read_list)?;
+        input::validate_all(&positionals, options.skip_validation)?;
+    }
+
+    // Os argumentos posicionais são usernames (ou owner/repo)
+    // Sem nenhum, Options::resolve tenta o default_user da configuração
+    // (e, sem ele, o programa tenta descobrir um; ver discovery.rs)
+    // CONCEITO: split_first
+    // Separa o primeiro elemento do resto: Some((primeiro, resto)) ou None se vazio
+    if let Some((first, rest)) = positionals.split_first() {
+        options.target = Some(Target::parse(first));
+        options.more_targets = rest.iter().map(|target| Target::parse(target)).collect();
+    }
+
+    // --compare junta exatamente dois usuários, buscados da API, em uma saída própria
+    if options.compare.is_some() {
+        if !options.more_targets.is_empty() || matches!(options.target, Some(Target::Repo { .. })) {
+            return Err(ActivityError::InvalidArgument(
+                "--compare needs a single <username> to compare with".to_string(),
+            ));
+        }
+        if options.summary
+            || options.repos_only
+            || options.histogram
+            || options.by_week
+            || options.from_file.is_some()
+            || options.record.is_some()
+        {
+            return Err(ActivityError::InvalidArgument(
+                "--compare cannot be combined with --summary, --repos-only, --histogram, --by-week, --from-file \
+                 or --record"
+                    .to_string(),
+            ));
+        }
+    }
+
+    // NDJSON escreve cada página assim que ela chega: nada que precise de todos os eventos
+    // antes de começar (agrupar, juntar pushes, comparar...) e uma única origem de eventos
+    if options.format == OutputFormat::Ndjson {
+        if options.summary
+            || options.repos_only
+            || options.histogram
+            || options.by_week
+            || options.compare.is_some()
+            || options.json
+            || options.template.is_some()
+            || options.group_by.is_some()
+            || options.sort.is_some()
+            || options.collapse
+            || options.first_contributions
+            || options.new
+            || options.record.is_some()
+            || options.pager
+            || options.emoji
+            || options.numbered
+            || options.open.is_some()
+        {
+            return Err(ActivityError::InvalidArgument(
+                "--format ndjson only combines with --all, --limit, --per-page, --max-events and --from-file"
+                    .to_string(),
+            ));
+        }
+        if !options.more_targets.is_empty() {
+            return Err(ActivityError::InvalidArgument(
+                "--format ndjson accepts a single <username> or <owner/repo>".to_string(),
+            ));
+        }
+    }
+
+    // A tabela substitui a listagem; as outras saídas têm formato próprio
+    if options.format == OutputFormat::Table
+        && (options.summary
+            || options.repos_only
+            || options.histogram
+            || options.by_week
+            || options.compare.is_some()
+            || options.json
+            || options.template.is_some()
+            || options.group_by.is_some())
+    {
+        return Err(ActivityError::InvalidArgument(
+            "--format table cannot be combined with --summary, --repos-only, --histogram, --by-week, --compare, \
+             --json, --template or --group-by"
+                .to_string(),
+        ));
+    }
+    if options.wide && options.format != OutputFormat::Table {
+        return Err(ActivityError::InvalidArgument("--wide requires --format table".to_string()));
+    }
+    // O template escreve a linha inteira: não há onde pôr o ícone (nem o número)
+    if options.emoji && options.template.is_some() {
+        return Err(ActivityError::InvalidArgument("--emoji cannot be combined with --template".to_string()));
+    }
+    if options.numbered && options.template.is_some() {
+        return Err(ActivityError::InvalidArgument("--numbered cannot be combined with --template".to_string()));
+    }
+
+    // --count-only escreve só números: nenhuma outra saída cabe junto
+    if options.count_only {
+        if options.summary
+            || options.repos_only
+            || options.histogram
+            || options.by_week
+            || options.compare.is_some()
+            || options.json
+            || options.template.is_some()
+            || options.format != OutputFormat::Text
+            || options.open.is_some()
+        {
+            return Err(ActivityError::InvalidArgument(
+                "--count-only cannot be combined with --summary, --repos-only, --histogram, --by-week, \
+                 --compare, --json, --template, --format or --open"
+                    .to_string(),
+            ));
+        }
+        if !options.more_targets.is_empty() {
+            return Err(ActivityError::InvalidArgument(
+                "--count-only accepts a single <username> or <owner/repo>".to_string(),
+            ));
+        }
+    }
+
+    // --open N conta os eventos da listagem de UM alvo
+    if options.open.is_some() {
+        if options.summary
+            || options.repos_only
+            || options.histogram
+            || options.by_week
+            || options.compare.is_some()
+            || options.json
+        {
+            return Err(ActivityError::InvalidArgument(
+                "--open needs the list of events and cannot be combined with --summary, --repos-only, \
+                 --histogram, --by-week, --compare or --json"
+                    .to_string(),
+            ));
+        }
+        if !options.more_targets.is_empty() {
+            return Err(ActivityError::InvalidArgument(
+                "--open accepts a single <username> or <owner/repo>".to_string(),
+            ));
+        }
+    }
+
+    // O histórico é um arquivo por username
+    if (options.log_history || options.history)
+        && (!options.more_targets.is_empty() || matches!(options.target, Some(Target::Repo { .. })))
+    {
+        return Err(ActivityError::InvalidArgument(
+            "--log-history and --history accept a single <username>".to_string(),
+        ));
+    }
+
+    // --raw mostra UMA resposta como veio: sem eventos, não há o que filtrar ou formatar
+    if options.raw {
+        let used = [
+            (options.starred, "--starred"),
+            (options.list_repos, "--list-repos"),
+            (options.all, "--all"),
+            (options.backend == Backend::GraphQl, "--backend graphql"),
+        ];
+        let flag = event_flags(&options)
+            .into_iter()
+            .find(|flag| *flag != "--from-file")
+            .or_else(|| used.iter().find(|(on, _)| *on).map(|(_, flag)| *flag));
+        if let Some(flag) = flag {
+            return Err(ActivityError::InvalidArgument(format!("--raw cannot be combined with {}", flag)));
+        }
+        if !options.more_targets.is_empty() {
+            return Err(ActivityError::InvalidArgument(
+                "--raw accepts a single <username> or <owner/repo>".to_string(),
+            ));
+        }
+    }
+
+    // --starred e --list-repos listam repositórios: as opções dos eventos não teriam onde agir
+    if options.starred && options.list_repos {
+        return Err(ActivityError::InvalidArgument(
+            "--starred and --list-repos cannot be combined".to_string(),
+        ));
+    }
+    if options.no_forks && !options.list_repos {
+        return Err(ActivityError::InvalidArgument("--no-forks requires --list-repos".to_string()));
+    }
+    if options.starred || options.list_repos {
+        let mode = if options.starred { "--starred" } else { "--list-repos" };
+        if let Some(flag) = event_flags(&options).first() {
+            return Err(ActivityError::InvalidArgument(format!(
+                "{} lists repositories and cannot be combined with {}",
+                mode, flag
+            )));
+        }
+        if !options.more_targets.is_empty() || matches!(options.target, Some(Target::Repo { .. })) {
+            return Err(ActivityError::InvalidArgument(format!("{} accepts a single <username>", mode)));
+        }
+    }
+
+    // As contribuições vêm de UMA consulta, sem páginas, ids ou respostas no formato do feed
+    if options.backend == Backend::GraphQl {
+        if !cfg!(feature = "graphql") {
+            return Err(ActivityError::InvalidArgument(
+                "--backend graphql needs a build with the 'graphql' feature (cargo build --features graphql)"
+                    .to_string(),
+            ));
+        }
+        let used = [
+            (options.from_file.is_some(), "--from-file"),
+            (options.history, "--history"),
+            (options.starred, "--starred"),
+            (options.list_repos, "--list-repos"),
+            (options.all, "--all"),
+            (options.record.is_some(), "--record"),
+            (options.log_history, "--log-history"),
+            (options.export_sqlite.is_some(), "--export-sqlite"),
+            (options.new, "--new"),
+            (options.compare.is_some(), "--compare"),
+            (options.format == OutputFormat::Ndjson, "--format ndjson"),
+        ];
+        if let Some((_, flag)) = used.iter().find(|(on, _)| *on) {
+            return Err(ActivityError::InvalidArgument(format!(
+                "--backend graphql cannot be combined with {}",
+                flag
+            )));
+        }
+        if !options.more_targets.is_empty() || matches!(options.target, Some(Target::Repo { .. })) {
+            return Err(ActivityError::InvalidArgument(
+                "--backend graphql accepts a single <username>".to_string(),
+            ));
+        }
+    }
+
+    // Um arquivo (ou uma gravação) tem os eventos de UMA busca
+    if !options.more_targets.is_empty()
+        && (options.from_file.is_some() || options.record.is_some() || options.export_sqlite.is_some())
+    {
+        return Err(ActivityError::InvalidArgument(
+            "--from-file, --replay, --record and --export-sqlite accept a single <username> or <owner/repo>"
+                .to_string(),
+        ));
+    }
+
+    Ok(Command::Activity(options))
+}
+
+// As flags ligadas que só fazem sentido para uma lista de eventos
+// (as listas de repositórios, como --starred, recusam todas elas)
+fn event_flags(options: &Options) -> Vec<&'static str> {
+    let used = [
+        (options.from_file.is_some(), "--from-file"),
+        (options.record.is_some(), "--record"),
+        (options.export_sqlite.is_some(), "--export-sqlite"),
+        (options.log_history, "--log-history"),
+        (options.history, "--history"),
+        (options.collapse, "--collapse"),
+        (options.count_only, "--count-only"),
+        (!options.types.is_empty(), "--type"),
+        (!options.categories.is_empty(), "--category"),
+        (!options.actions.is_empty(), "--action"),
+        (options.no_bots, "--no-bots"),
+        (!options.ignored_actors.is_empty(), "--ignore-actor"),
+        (options.no_dedup, "--no-dedup"),
+        (options.commits, "--commits"),
+        (options.first_contributions, "--first-contributions"),
+        (options.new, "--new"),
+        (options.reset_seen, "--reset-seen"),
+        (options.post_webhook.is_some(), "--post-webhook"),
+        (options.summary, "--summary"),
+        (options.repos_only, "--repos-only"),
+        (options.histogram, "--histogram"),
+        (options.by_week, "--by-week"),
+        (options.compare.is_some(), "--compare"),
+        (options.json, "--json"),
+        (options.template.is_some(), "--template"),
+        (options.group_by.is_some(), "--group-by"),
+        (options.sort.is_some(), "--sort"),
+        (options.format != OutputFormat::Text, "--format"),
+        (options.emoji, "--emoji"),
+        (options.numbered, "--numbered"),
+        (options.open.is_some(), "--open"),
+    ];
+    used.iter().filter(|(on, _)| *on).map(|(_, flag)| *flag).collect()
+}
+
+impl Options {
+    /// Todos os alvos, na ordem digitada
+    pub fn targets(&self) -> Vec<&Target> {
+        self.target.iter().chain(&self.more_targets).collect()
+    }
+
+    /// --doctor, --cache-info e --clear-cache não buscam eventos de ninguém:
+    /// dispensam um alvo (e a descoberta de um username)
+    pub fn maintenance(&self) -> bool {
+        self.doctor || self.cache_info || self.clear_cache
+    }
+
+    /// O que escrever além dos eventos
+    /// --quiet desliga tudo; com --json, --template e --count-only a saída já é só o
+    /// que o usuário pediu, e nem o progresso aparece (ele não atrapalharia o stdout,
+    /// mas quem pede uma saída para máquinas não quer conversa)
+    pub fn verbosity(&self) -> Verbosity {
+        let body_only = self.json || self.raw || self.template.is_some() || self.count_only || self.format == OutputFormat::Csv;
+        Verbosity {
+            progress: !self.quiet && !body_only,
+            header: !self.quiet && self.template.is_none(),
+            notices: !self.quiet,
+        }
+    }
+
+    /// Completa as opções com as outras origens de configuração (ver o módulo config)
+    /// e faz as validações que dependem delas
+    ///
+    /// As flags da linha de comando vencem, depois o ambiente, depois o arquivo
+    pub fn resolve(mut self, environment: Config, file: Config) -> Result<Options, ActivityError> {
+        // Quem informou o token, para --verbose (a primeira camada que tiver um)
+        self.token_source = if self.token.is_some() {
+            Some(TokenSource::CommandLine)
+        } else if environment.token.is_some() {
+            Some(TokenSource::Environment)
+        } else if file.token.is_some() {
+            Some(TokenSource::ConfigFile)
+        } else {
+            None
+        };
+
+        let command_line = Config {
+            limit: self.limit,
+            color: self.no_color.then_some(false),
+            token: self.token.take(),
+            api_url: self.api_url.take(),
+            api_version: None,
+            default_user: None,
+            ignored_actors: None,
+        };
+        let config = command_line.or(environment).or(file);
+
+        self.limit = config.limit;
+        self.no_color = config.color == Some(false);
+        self.token = config.token;
+        self.api_url = config.api_url;
+        self.api_version = config.api_version;
+        // A lista da configuração só vale com --no-bots; as de --ignore-actor valem sempre
+        if self.no_bots {
+            self.ignored_actors.extend(config.ignored_actors.unwrap_or_default());
+        }
+
+        // default_user só completa buscas na API; com --from-file o username é opcional
+        if self.target.is_none() && self.from_file.is_none() && !self.maintenance() {
+            self.target = config.default_user.map(|user| Target::parse(&user));
+        }
+
+        if self.new && self.from_file.is_some() {
+            return Err(ActivityError::InvalidArgument(
+                "--new cannot be combined with --from-file or --replay".to_string(),
+            ));
+        }
+
+        Ok(self)
+    }
+
+    /// As validações que dependem do alvo, feitas depois de resolve e da descoberta
+    /// do username (que pode preencher um alvo que ainda faltava)
+    pub fn check_target(self) -> Result<Options, ActivityError> {
+        if self.maintenance() {
+            return Ok(self);
+        }
+
+        if self.target.is_none() && self.from_file.is_none() {
+            return Err(missing_target());
+        }
+
+        // O marcador de "já visto" é por username/repositório e só avança com dados da API
+        if (self.new || self.reset_seen) && self.target.is_none() {
+            return Err(ActivityError::InvalidArgument(
+                "--new and --reset-seen require a <username> or <owner/repo>".to_string(),
+            ));
+        }
+
+        Ok(self)
+    }
+}
+
+fn missing_target() -> ActivityError {
+    ActivityError::InvalidArgument("expected a <username> or <owner/repo>".to_string())
+}
+
+// Interpreta as flags de `generate`
+fn parse_generate_args(args: &[String]) -> Result<Command, ActivityError> {
+    let mut options = GenerateOptions::default();
+    let mut i = 0;
+
+    while i < args.len() {
+        let (flag, inline_value) = split_flag(&args[i]);
+
+        match flag {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--events" => {
+                options.events = parse_number(flag, take_value(args, &mut i, inline_value)?)?;
+            }
+            "--seed" => {
+                options.seed = parse_number(flag, take_value(args, &mut i, inline_value)?)?;
+            }
+            "--kinds" => {
+                options.kinds = generator::parse_kinds(take_value(args, &mut i, inline_value)?)?;
+            }
+            _ => return Err(unknown_flag(flag)),
+        }
+
+        i += 1;
+    }
+
+    Ok(Command::Generate(options))
+}
+
+// Separa "--flag=valor" em ("--flag", Some("valor"))
+fn split_flag(arg: &str) -> (&str, Option<&str>) {
+    match arg.split_once('=') {
+        Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+        _ => (arg, None),
+    }
+}
+
+// Flags booleanas (interruptores) não aceitam "--flag=valor"
+fn switch(flag: &str, inline_value: Option<&str>) -> Result<bool, ActivityError> {
+    match inline_value {
+        Some(_) => Err(ActivityError::InvalidArgument(format!(
+            "{} does not take a value",
+            flag
+        ))),
+        None => Ok(true),
+    }
+}
+
+// Obtém o valor de uma flag: inline (--flag=valor) ou o próximo argumento
+// CONCEITO: &mut usize
+// Recebemos o índice por referência mutável para "consumir" o argumento seguinte
+fn take_value<'a>(
+    args: &'a [String],
+    i: &mut usize,
+    inline_value: Option<&'a str>,
+) -> Result<&'a str, ActivityError> {
+    if let Some(value) = inline_value {
+        return Ok(value);
+    }
+
+    let flag = &args[*i];
+    *i += 1;
+    args.get(*i)
+        .map(String::as_str)
+        .ok_or_else(|| ActivityError::InvalidArgument(format!("{} requires a value", flag)))
+}
+
+fn parse_backend(value: &str) -> Result<Backend, ActivityError> {
+    match value {
+        "rest" => Ok(Backend::Rest),
+        "graphql" => Ok(Backend::GraphQl),
+        _ => Err(ActivityError::InvalidArgument(format!(
+            "--backend expects 'rest' or 'graphql', got '{}'",
+            value
+        ))),
+    }
+}
+
+fn parse_group_by(value: &str) -> Result<GroupBy, ActivityError> {
+    match value {
+        "repo" => Ok(GroupBy::Repo),
+        "type" => Ok(GroupBy::Type),
+        _ => Err(ActivityError::InvalidArgument(format!(
+            "--group-by expects 'repo' or 'type', got '{}'",
+            value
+        ))),
+    }
+}
+
+// Nomes da API separados por vírgula: "PushEvent,PullRequestEvent"
+// Um nome desconhecido é erro: um erro de digitação esconderia todos os eventos
+fn parse_types(value: &str) -> Result<Vec<EventKind>, ActivityError> {
+    value
+        .split(',')
+        .map(str::trim)
+        .map(|name| match name.parse::<EventKind>() {
+            Ok(EventKind::Other(_)) | Err(_) => Err(ActivityError::InvalidArgument(format!(
+                "--type expects event types as the API names them (PushEvent, IssuesEvent...), got '{}'",
+                name
+            ))),
+            Ok(kind) => Ok(kind),
+        })
+        .collect()
+}
+
+// Categorias separadas por vírgula: "code,social"
+fn parse_categories(value: &str) -> Result<Vec<EventCategory>, ActivityError> {
+    value
+        .split(',')
+        .map(str::trim)
+        .map(|name| {
+            EventCategory::from_name(name).ok_or_else(|| {
+                ActivityError::InvalidArgument(format!(
+                    "--category expects code, issues, review, social, release or other, got '{}'",
+                    name
+                ))
+            })
+        })
+        .collect()
+}
+
+// Actions separadas por vírgula: "opened,closed"
+fn parse_actions(value: &str) -> Result<Vec<String>, ActivityError> {
+    let actions: Vec<String> = value.split(',').map(str::trim).map(str::to_string).collect();
+    if actions.iter().any(String::is_empty) {
+        return Err(ActivityError::InvalidArgument(format!(
+            "--action expects actions separated by commas, got '{}'",
+            value
+        )));
+    }
+    Ok(actions)
+}
+
+fn parse_sort(value: &str) -> Result<SortOrder, ActivityError> {
+    match value {
+        "newest" => Ok(SortOrder::Newest),
+        "oldest" => Ok(SortOrder::Oldest),
+        "repo" => Ok(SortOrder::Repo),
+        "type" => Ok(SortOrder::Type),
+        _ => Err(ActivityError::InvalidArgument(format!(
+            "--sort expects 'newest', 'oldest', 'repo' or 'type', got '{}'",
+            value
+        ))),
+    }
+}
+
+fn parse_repo_sort(value: &str) -> Result<RepoSort, ActivityError> {
+    match value {
+        "stars" => Ok(RepoSort::Stars),
+        "updated" => Ok(RepoSort::Updated),
+        "name" => Ok(RepoSort::Name),
+        _ => Err(ActivityError::InvalidArgument(format!(
+            "--sort with --list-repos expects 'stars', 'updated' or 'name', got '{}'",
+            value
+        ))),
+    }
+}
+
+fn parse_format(value: &str) -> Result<OutputFormat, ActivityError> {
+    match value {
+        "text" => Ok(OutputFormat::Text),
+        "ndjson" => Ok(OutputFormat::Ndjson),
+        "table" => Ok(OutputFormat::Table),
+        "csv" => Ok(OutputFormat::Csv),
+        _ => Err(ActivityError::InvalidArgument(format!(
+            "--format expects 'text', 'ndjson', 'table' or 'csv', got '{}'",
+            value
+        ))),
+    }
+}
+
+// CONCEITO: Generics com trait bounds
+// T pode ser qualquer tipo que implemente FromStr (usize, u64, ...)
+fn parse_number<T: FromStr>(flag: &str, value: &str) -> Result<T, ActivityError> {
+    value.parse().map_err(|_| {
+        ActivityError::InvalidArgument(format!("{} expects a number, got '{}'", flag, value))
+    })
+}
+
+// Erro de validação de config.rs, com o nome da flag na frente
+fn invalid_value(flag: &str, message: String) -> ActivityError {
+    ActivityError::InvalidArgument(format!("{} {}", flag, message))
+}
+
+fn unknown_flag(flag: &str) -> ActivityError {
+    ActivityError::InvalidArgument(format!("unknown flag '{}'", flag))
+}
+
+// Monta o texto de ajuda a partir das tabelas de flags
+pub fn usage(program: &str) -> String {
+    let mut text = format!("Usage: {} [OPTIONS] <username | owner/repo | @FILE | ->...\n", program);
+    text.push_str(&format!("       {} --from-file <PATH> [OPTIONS] [username | owner/repo]\n", program));
+    text.push_str(&format!("       {} generate [OPTIONS]\n", program));
+
+    text.push_str("\nWithout a username, the first of these is used: the default_user setting,\n");
+    text.push_str("the owner of the token (asked with GET /user, only when a token is set),\n");
+    text.push_str("'git config github.user', then 'git config user.name' (when it is a valid\n");
+    text.push_str("username). If none of them gives one, this help is printed. A username\n");
+    text.push_str("given on the command line always wins.\n");
+
+    text.push_str("\nAn argument containing a slash (e.g. rust-lang/rust) always selects\n");
+    text.push_str("repository mode and lists the events of that repository, showing who\n");
+    text.push_str("performed each one. Anything else is treated as a username.\n");
+
+    text.push_str("\nSeveral usernames (or repositories) can be given at once; each one is\n");
+    text.push_str("listed in the order typed, and a failure in one does not stop the others.\n");
+    text.push_str("Up to 4 of them (see --jobs) are fetched at the same time, in threads\n");
+    text.push_str("or, in builds with the 'async' feature, as async tasks.\n");
+    text.push_str("@FILE reads a list of them from FILE (one per line; blank lines and '#'\n");
+    text.push_str("comments are ignored) and '-' reads the list from stdin. Lists and names can\n");
+    text.push_str("be mixed; repeated names are listed once, and every name is checked before\n");
+    text.push_str("anything is fetched.\n");
+
+    text.push_str("\nGitHub only returns recent events (at most 300 over the last 90 days),\n");
+    text.push_str("so --first-contributions can only say an event is the first activity\n");
+    text.push_str("in a repository *in this window*. Combine it with --all to look at the\n");
+    text.push_str("full available history instead of the first page.\n");
+
+    text.push_str("\nWith --from-file the events are read from a saved API response (or from\n");
+    text.push_str("stdin with '-') and nothing is fetched; the optional username or\n");
+    text.push_str("owner/repo is only used in the header. --record saves what was fetched\n");
+    text.push_str("(all pages with --all) so it can be shown again with --replay.\n");
+    text.push_str("--raw skips the events entirely and prints the first page as GitHub sent it,\n");
+    text.push_str("indented two spaces per level; with --from-file it indents the file instead.\n");
+    text.push_str("--export-sqlite adds the fetched events to a SQLite database, keyed on the\n");
+    text.push_str("event id: running it again updates the rows already there, so the database\n");
+    text.push_str("keeps growing into a history longer than the 90 days GitHub keeps.\n");
+    text.push_str("--log-history does the same without SQLite, appending one JSON line per\n");
+    text.push_str("new event to a file per username in the data directory\n");
+    text.push_str("(~/.local/share/github-activity/<username>.jsonl on Linux); --history\n");
+    text.push_str("then shows that file, with the usual filters, and --since DATE narrows it.\n");
+    text.push_str("--backend graphql (in builds with the 'graphql' feature, and only with a token)\n");
+    text.push_str("asks the GraphQL API for the contributions of <username> instead: commits per\n");
+    text.push_str("repository and day, issues, pull requests (with titles), reviews and new\n");
+    text.push_str("repositories, from --since DATE (default: one year ago) until now.\n");
+
+    text.push_str("\n--starred lists the repositories <username> starred, newest star first, as\n");
+    text.push_str("'- owner/repo (★ 12,345) — description'. --all, --per-page and --max-events\n");
+    text.push_str("page through the list as they do for events (up to 10 pages by default);\n");
+    text.push_str("the options that filter or reshape events do not apply to it.\n");
+    text.push_str("--list-repos lists the public repositories of <username> as a table (name,\n");
+    text.push_str("stars, language, last update, description), most recently updated first;\n");
+    text.push_str("--sort stars|updated|name reorders them and --no-forks leaves out forks.\n");
+
+    text.push_str("\n--doctor checks, in order, that the API answers, that a token was found\n");
+    text.push_str("(and where), that GitHub accepts it, how much of the rate limit is left and\n");
+    text.push_str("that the cache directory is writable, printing PASS, WARN or FAIL for each.\n");
+    text.push_str("It exits with a non-zero code if any check fails.\n");
+
+    text.push_str("\nThe first page of each user's events is cached (in the user cache directory)\n");
+    text.push_str("with its ETag: the next run asks GitHub whether it changed, and an unchanged\n");
+    text.push_str("page (HTTP 304) is read from the cache without counting against the rate\n");
+    text.push_str("limit. --cache-info lists the cached users with their size and age;\n");
+    text.push_str("--clear-cache [<username>...] deletes their entries (all of them without a\n");
+    text.push_str("username); --no-cache skips the cache for one run.\n");
+    text.push_str("--max-age SECONDS uses a cached page fetched less than SECONDS ago without\n");
+    text.push_str("any request (the header then says '(cached, 2m old)'); an entry of unknown\n");
+    text.push_str("age, or one from the future after the clock went back, is revalidated.\n");
+
+    text.push_str("\nEvery successful run remembers the newest event it fetched for that\n");
+    text.push_str("user or repository (in the user cache directory). --new then shows only\n");
+    text.push_str("what happened since; on the first run it shows everything.\n");
+
+    text.push_str("\n--template prints each event as TEXT (with no header), where\n");
+    text.push_str("{type}, {repo}, {owner}, {name}, {date}, {relative_date}, {detail} (the usual\n");
+    text.push_str("description), {commits} and {action} are filled in for every event; the last\n");
+    text.push_str("two stay empty for events without them. Write {{ and }} for literal braces.\n");
+
+    text.push_str("\n--category groups the event types: code (pushes, branches and tags created\n");
+    text.push_str("or deleted), issues (issues and their comments), review (pull requests,\n");
+    text.push_str("reviews, review and commit comments), social (stars, forks, collaborators),\n");
+    text.push_str("release, and other (wiki edits, repos made public, unknown types).\n");
+
+    text.push_str("\n--format ndjson prints one JSON object per event (id, type, repo, actor,\n");
+    text.push_str("created_at, action, commits, description), writing each page as soon as it\n");
+    text.push_str("arrives. If a later page fails, the error is printed on stderr as a JSON\n");
+    text.push_str("object and the exit code is non-zero; the lines already printed stay valid.\n");
+
+    text.push_str("\n--by-week counts the events of each ISO 8601 week (Monday to Sunday, e.g.\n");
+    text.push_str("2024-W19) in local time (UTC with --utc), split into pushes, pull requests,\n");
+    text.push_str("issues and other events. Weeks are listed newest first (--sort oldest flips\n");
+    text.push_str("them); --json prints them as a JSON array and --format csv as CSV.\n");
+
+    text.push_str("\nWith --json, a failure prints {\"error\": {\"kind\", \"message\", \"exit_code\", ...}}\n");
+    text.push_str("on stdout instead of the document; kind is a stable snake_case name such as\n");
+    text.push_str("rate_limited or user_not_found, and the other fields depend on the error\n");
+    text.push_str("(status, retry_after, reset_at, path...). --format ndjson errors use the\n");
+    text.push_str("same object on stderr.\n");
+
+    text.push_str("\nDefaults for --limit, --no-color, --token and --api-url, plus a default_user\n");
+    text.push_str("used when no username is given, the api_version sent to GitHub and the\n");
+    text.push_str("ignored_actors hidden by --no-bots (comma-separated logins), can be set\n");
+    text.push_str("in ~/.config/github-activity/config\n");
+    text.push_str("(one 'key = value' per line) or in the environment. Command-line flags win\n");
+    text.push_str("over the environment, which wins over the config file:\n");
+    let width = config::ENV_VARS.iter().map(|(variable, _)| variable.len()).max().unwrap_or(0);
+    for (variable, key) in config::ENV_VARS {
+        text.push_str(&format!("  {:<width$} {}\n", variable, key, width = width));
+    }
+    text.push_str("Without any token, the one saved by the gh CLI login is used (see --no-gh-token).\n");
+
+    text.push_str("\nOptions:\n");
+    text.push_str(&format_flags(FLAGS));
+
+    text.push_str("\nGenerate options (synthesize a realistic events JSON array):\n");
+    text.push_str(&format_flags(GENERATE_FLAGS));
+
+    text.push_str("\nExit codes:\n");
+    for (code, description) in EXIT_CODES {
+        text.push_str(&format!("  {:<3} {}\n", code, description));
+    }
+
+    text.push_str("\nExamples:\n");
+    text.push_str(&format!("  {} torvalds\n", program));
+    text.push_str(&format!("  {} github\n", program));
+    text.push_str(&format!("  {} rust-lang/rust\n", program));
+    text.push_str(&format!("  {} octocat @team.txt\n", program));
+    text.push_str(&format!("  {} --from-file events.json torvalds\n", program));
+    text.push_str(&format!("  {} torvalds --all --record torvalds.json\n", program));
+    text.push_str(&format!("  {} torvalds --new\n", program));
+    text.push_str(&format!("  {} torvalds --template \"{{date}} {{type}} {{repo}}\"\n", program));
+    text.push_str(&format!("  {} generate --events 50 --seed 42 --kinds push,issue,star\n", program));
+    text.push_str(&format!("  {} generate | {} --from-file -\n", program, program));
+
+    text
+}
+
+// Formata as flags em duas colunas alinhadas
+fn format_flags(flags: &[FlagSpec]) -> String {
+    let labels: Vec<String> = flags
+        .iter()
+        .map(|flag| match flag.value {
+            Some(value) => format!("{} <{}>", flag.name, value),
+            None => flag.name.to_string(),
+        })
+        .collect();
+    let width = labels.iter().map(String::len).max().unwrap_or(0);
+
+    labels
+        .iter()
+        .zip(flags)
+        .map(|(label, flag)| format!("  {:width$}  {}\n", label, flag.help, width = width))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use github_activity::generator::Kind;
+
+    // Converte &[&str] em Vec<String> para simular env::args()
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    // parse_args seguido de resolve, sem ambiente nem arquivo de configuração
+    fn parse(list: &[String]) -> Result<Command, ActivityError> {
+        match parse_args(list)? {
+            Command::Activity(options) => {
+                options
+                    .resolve(Config::default(), Config::default())
+                    .and_then(Options::check_target)
+                    .map(Command::Activity)
+            }
+            other => Ok(other),
+        }
+    }
+
+    #[test]
+    fn test_parse_username() {
+        assert_eq!(
+            parse_args(&args(&["torvalds"])).unwrap(),
+            Command::Activity(Options {
+                target: Some(Target::User("torvalds".to_string())),
+                ..Options::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_first_contributions_flags() {
+        assert_eq!(
+            parse_args(&args(&["--first-contributions", "torvalds", "--only", "--all"])).unwrap(),
+            Command::Activity(Options {
+                target: Some(Target::User("torvalds".to_string())),
+                all: true,
+                first_contributions: true,
+                only: true,
+                ..Options::default()
+            })
+        );
+        assert!(parse_args(&args(&["torvalds", "--only"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--all=yes"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_group_by() {
+        match parse_args(&args(&["torvalds", "--group-by", "repo"])).unwrap() {
+            Command::Activity(options) => assert_eq!(options.group_by, Some(GroupBy::Repo)),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_args(&args(&["--group-by=type", "torvalds"])).unwrap() {
+            Command::Activity(options) => assert_eq!(options.group_by, Some(GroupBy::Type)),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--group-by", "day"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--group-by"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_no_time() {
+        match parse_args(&args(&["torvalds", "--no-time"])).unwrap() {
+            Command::Activity(options) => assert!(options.no_time),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_collapse() {
+        match parse_args(&args(&["torvalds", "--collapse"])).unwrap() {
+            Command::Activity(options) => assert!(options.collapse),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--collapse=yes"])).is_err());
+
+        match parse_args(&args(&["torvalds", "--no-dedup"])).unwrap() {
+            Command::Activity(options) => assert!(options.no_dedup && !options.collapse),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_commits() {
+        match parse_args(&args(&["torvalds", "--commits"])).unwrap() {
+            Command::Activity(options) => assert!(options.commits),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_args(&args(&["torvalds"])).unwrap() {
+            Command::Activity(options) => assert!(!options.commits),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_summary_flags() {
+        match parse_args(&args(&["torvalds", "--summary", "--json"])).unwrap() {
+            Command::Activity(options) => assert!(options.summary && options.json),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--json"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_template() {
+        match parse_args(&args(&["torvalds", "--template", "{date} {repo}"])).unwrap() {
+            Command::Activity(options) => assert!(options.template.is_some()),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        // Um campo desconhecido é erro de uso, antes de qualquer busca
+        match parse_args(&args(&["torvalds", "--template", "{author}"])) {
+            Err(ActivityError::InvalidArgument(message)) => {
+                assert!(message.starts_with("--template has an unknown placeholder '{author}'"), "{}", message)
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_format() {
+        match parse_args(&args(&["torvalds", "--all", "--format", "ndjson", "--limit", "5"])).unwrap() {
+            Command::Activity(options) => assert_eq!(options.format, OutputFormat::Ndjson),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_args(&args(&["torvalds"])).unwrap() {
+            Command::Activity(options) => assert_eq!(options.format, OutputFormat::Text),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        assert!(parse_args(&args(&["torvalds", "--format", "csv"])).is_err());
+        // Saídas que precisam de todos os eventos antes de começar
+        assert!(parse_args(&args(&["torvalds", "--format", "ndjson", "--summary"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--format", "ndjson", "--group-by", "repo"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "octocat", "--format", "ndjson"])).is_err());
+
+        match parse_args(&args(&["torvalds", "--format=table", "--wide"])).unwrap() {
+            Command::Activity(options) => assert!(options.format == OutputFormat::Table && options.wide),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--wide"])).is_err());
+        match parse_args(&args(&["torvalds", "--format=table", "--emoji"])).unwrap() {
+            Command::Activity(options) => assert!(options.emoji),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--emoji", "--format", "ndjson"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--emoji", "--template", "{repo}"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_numbered_and_open() {
+        match parse_args(&args(&["torvalds", "--numbered", "--open", "3"])).unwrap() {
+            Command::Activity(options) => assert!(options.numbered && options.open == Some(3)),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--open", "0"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--open", "first"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--open", "1", "--summary"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "octocat", "--open", "1"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--numbered", "--format", "ndjson"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--numbered", "--template", "{repo}"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_lang() {
+        match parse_args(&args(&["torvalds", "--lang", "pt-BR"])).unwrap() {
+            Command::Activity(options) => assert_eq!(options.lang, Some(Lang::PtBr)),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_args(&args(&["torvalds"])).unwrap() {
+            Command::Activity(options) => assert_eq!(options.lang, None),
+            other => panic!("unexpected {:?}", other),
+        }
+        let error = parse_args(&args(&["torvalds", "--lang=fr"])).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid argument: --lang expects 'en' or 'pt-BR', got 'fr'");
+        assert!(parse_args(&args(&["torvalds", "--format", "table", "--summary"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_histogram() {
+        match parse_args(&args(&["torvalds", "--histogram", "--utc"])).unwrap() {
+            Command::Activity(options) => assert!(options.histogram && options.utc),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--utc"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--summary", "--utc"])).is_ok());
+        assert!(parse_args(&args(&["torvalds", "--histogram", "--summary"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_by_week() {
+        match parse_args(&args(&["torvalds", "--by-week", "--utc", "--sort", "oldest", "--format", "csv"])).unwrap() {
+            Command::Activity(options) => {
+                assert!(options.by_week && options.utc);
+                assert_eq!(options.sort, Some(SortOrder::Oldest));
+                assert_eq!(options.format, OutputFormat::Csv);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--by-week", "--json"])).is_ok());
+
+        // CSV só para as semanas, e só uma saída por vez
+        assert!(parse_args(&args(&["torvalds", "--format", "csv"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--by-week", "--format", "csv", "--json"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--by-week", "--histogram"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--by-week", "--format", "table"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--by-week", "--sort", "repo"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--by-week", "--count-only"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_compare() {
+        match parse_args(&args(&["alice", "--compare", "bob"])).unwrap() {
+            Command::Activity(options) => assert_eq!(options.compare.as_deref(), Some("bob")),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["alice", "--compare"])).is_err());
+        assert!(parse_args(&args(&["alice", "--compare", "rust-lang/rust"])).is_err());
+        assert!(parse_args(&args(&["rust-lang/rust", "--compare", "bob"])).is_err());
+        assert!(parse_args(&args(&["alice", "carol", "--compare", "bob"])).is_err());
+        assert!(parse_args(&args(&["alice", "--compare", "bob", "--summary"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_repos_only() {
+        match parse_args(&args(&["torvalds", "--repos-only", "--json"])).unwrap() {
+            Command::Activity(options) => assert!(options.repos_only && options.json),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--repos-only", "--summary"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_doctor() {
+        // Sem username: nada de "expected a <username>"
+        match parse(&args(&["--doctor", "--json"])).unwrap() {
+            Command::Activity(options) => assert!(options.doctor && options.json && options.target.is_none()),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["--doctor", "torvalds"])).is_err());
+        assert!(usage("github-activity").contains("--doctor checks, in order,"));
+    }
+
+    #[test]
+    fn test_parse_cache_flags() {
+        match parse(&args(&["--cache-info"])).unwrap() {
+            Command::Activity(options) => assert!(options.cache_info && options.target.is_none()),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse(&args(&["--clear-cache"])).unwrap() {
+            Command::Activity(options) => assert!(options.clear_cache && options.targets().is_empty()),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse(&args(&["--clear-cache", "torvalds", "octocat"])).unwrap() {
+            Command::Activity(options) => assert_eq!(options.targets().len(), 2),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse(&args(&["torvalds", "--no-cache"])).unwrap() {
+            Command::Activity(options) => assert!(options.no_cache),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse(&args(&["torvalds", "--max-age", "300"])).unwrap() {
+            Command::Activity(options) => assert_eq!(options.max_age, Some(300)),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--max-age", "-1"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--max-age", "5m"])).is_err());
+        assert!(parse_args(&args(&["--cache-info", "torvalds"])).is_err());
+        assert!(parse_args(&args(&["--cache-info", "--clear-cache"])).is_err());
+        assert!(parse_args(&args(&["--clear-cache", "rust-lang/rust"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_raw() {
+        match parse(&args(&["torvalds", "--raw"])).unwrap() {
+            Command::Activity(options) => {
+                assert!(options.raw);
+                assert!(!options.verbosity().progress);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse(&args(&["--raw", "--from-file", "events.json"])).unwrap() {
+            Command::Activity(options) => assert!(options.raw && options.target.is_none()),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["rust-lang/rust", "--raw", "--per-page", "5"])).is_ok());
+        for extra in [&["--json"][..], &["--summary"], &["--all"], &["--starred"], &["--type", "PushEvent"], &["octocat"]] {
+            let mut argv = vec!["torvalds", "--raw"];
+            argv.extend_from_slice(extra);
+            assert!(parse_args(&args(&argv)).is_err(), "{:?}", extra);
+        }
+    }
+
+    #[test]
+    fn test_parse_verbose_and_strict() {
+        match parse_args(&args(&["--strict", "torvalds", "--verbose"])).unwrap() {
+            Command::Activity(options) => assert!(options.strict && options.verbose && !options.debug),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_args(&args(&["-v", "torvalds"])).unwrap() {
+            Command::Activity(options) => assert!(options.verbose && !options.debug),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_args(&args(&["-vv", "torvalds"])).unwrap() {
+            Command::Activity(options) => assert!(options.verbose && options.debug),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_slash_selects_repo_mode() {
+        assert_eq!(
+            parse_args(&args(&["rust-lang/rust"])).unwrap(),
+            Command::Activity(Options {
+                target: Some(Target::Repo {
+                    owner: "rust-lang".to_string(),
+                    repo: "rust".to_string(),
+                }),
+                ..Options::default()
+            })
+        );
+        // Segmentos vazios ou barras extras são repassados para a validação da API
+        assert_eq!(
+            Target::parse("a/b/c"),
+            Target::Repo { owner: "a".to_string(), repo: "b/c".to_string() }
+        );
+        assert_eq!(
+            Target::parse("/rust"),
+            Target::Repo { owner: String::new(), repo: "rust".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_parse_from_file() {
+        assert_eq!(
+            parse_args(&args(&["--from-file", "events.json"])).unwrap(),
+            Command::Activity(Options {
+                from_file: Some("events.json".to_string()),
+                ..Options::default()
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--from-file=-", "torvalds"])).unwrap(),
+            Command::Activity(Options {
+                target: Some(Target::User("torvalds".to_string())),
+                from_file: Some("-".to_string()),
+                ..Options::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_output() {
+        assert_eq!(
+            parse_args(&args(&["torvalds", "--output", "report.txt"])).unwrap(),
+            Command::Activity(Options {
+                target: Some(Target::User("torvalds".to_string())),
+                output: Some("report.txt".to_string()),
+                ..Options::default()
+            })
+        );
+        assert!(parse_args(&args(&["torvalds", "--output"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_from_file_errors() {
+        assert!(parse_args(&args(&["--from-file"])).is_err());
+        assert!(parse_args(&args(&["--from-file", "events.json", "a", "b"])).is_err());
+        assert!(parse_args(&args(&["a", "b", "--record", "out.json"])).is_err());
+        assert!(parse_args(&args(&["--from-file", "events.json", "--all"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_new_and_reset_seen() {
+        match parse_args(&args(&["torvalds", "--new"])).unwrap() {
+            Command::Activity(options) => assert!(options.new && !options.reset_seen),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_args(&args(&["--reset-seen", "rust-lang/rust"])).unwrap() {
+            Command::Activity(options) => assert!(options.reset_seen),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse(&args(&["--new"])).is_err());
+        assert!(parse(&args(&["--from-file", "events.json", "--new", "torvalds"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--new=yes"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_post_webhook() {
+        let url = "https://hooks.slack.com/services/T0/B0/x";
+        match parse_args(&args(&["torvalds", "--new", "--post-webhook", url, "--slack", "--dry-run"])).unwrap() {
+            Command::Activity(options) => {
+                assert_eq!(options.post_webhook.as_deref(), Some(url));
+                assert!(options.slack && options.dry_run);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--post-webhook", "hooks.slack.com/x"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--slack"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--dry-run"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--post-webhook", url, "--format", "ndjson"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_notify() {
+        match parse_args(&args(&["torvalds", "--new", "--notify"])).unwrap() {
+            Command::Activity(options) => assert!(options.new && options.notify),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--notify"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_record_and_replay() {
+        assert_eq!(
+            parse_args(&args(&["torvalds", "--all", "--record", "out.json"])).unwrap(),
+            Command::Activity(Options {
+                target: Some(Target::User("torvalds".to_string())),
+                all: true,
+                record: Some("out.json".to_string()),
+                ..Options::default()
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["--replay=out.json"])).unwrap(),
+            parse_args(&args(&["--from-file", "out.json"])).unwrap()
+        );
+        assert!(parse_args(&args(&["--replay", "a.json", "--record", "b.json"])).is_err());
+        assert!(parse(&args(&["--record", "out.json"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_starred() {
+        match parse_args(&args(&["torvalds", "--starred", "--all", "--max-events", "50"])).unwrap() {
+            Command::Activity(options) => {
+                assert!(options.starred && options.all);
+                assert_eq!(options.max_events, Some(50));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        let error = parse_args(&args(&["torvalds", "--starred", "--summary"])).unwrap_err();
+        assert!(error.to_string().contains("cannot be combined with --summary"), "{}", error);
+        assert!(parse_args(&args(&["torvalds", "--starred", "--type", "push"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--starred", "--format", "ndjson"])).is_err());
+        assert!(parse_args(&args(&["rust-lang/rust", "--starred"])).is_err());
+        assert!(parse_args(&args(&["a", "b", "--starred"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_list_repos() {
+        // --sort antes de --list-repos também vale
+        match parse_args(&args(&["torvalds", "--sort", "stars", "--list-repos", "--no-forks"])).unwrap() {
+            Command::Activity(options) => {
+                assert!(options.list_repos && options.no_forks);
+                assert_eq!(options.repo_sort, Some(RepoSort::Stars));
+                assert_eq!(options.sort, None);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        let error = parse_args(&args(&["torvalds", "--sort", "name"])).unwrap_err();
+        assert!(error.to_string().contains("--sort name requires --list-repos"), "{}", error);
+        assert!(parse_args(&args(&["torvalds", "--list-repos", "--sort", "oldest"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--no-forks"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--list-repos", "--starred"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--list-repos", "--json"])).is_err());
+        assert!(parse_args(&args(&["a", "b", "--list-repos"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_history() {
+        assert_eq!(
+            parse_args(&args(&["torvalds", "--history", "--since", "2024-05-01"])).unwrap(),
+            Command::Activity(Options {
+                target: Some(Target::User("torvalds".to_string())),
+                history: true,
+                since: Timestamp::parse_date("2024-05-01"),
+                ..Options::default()
+            })
+        );
+        assert!(parse_args(&args(&["torvalds", "--since", "yesterday", "--history"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--since", "2024-05-01"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--history", "--all"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--history", "--log-history"])).is_err());
+        assert!(parse_args(&args(&["rust-lang/rust", "--log-history"])).is_err());
+        assert!(parse_args(&args(&["a", "b", "--history"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--log-history"])).is_ok());
+    }
+
+    #[test]
+    fn test_parse_export_sqlite() {
+        let parsed = parse_args(&args(&["torvalds", "--export-sqlite", "activity.db"]));
+        if cfg!(feature = "sqlite") {
+            assert_eq!(
+                parsed.unwrap(),
+                Command::Activity(Options {
+                    target: Some(Target::User("torvalds".to_string())),
+                    export_sqlite: Some("activity.db".to_string()),
+                    ..Options::default()
+                })
+            );
+        } else {
+            assert!(parsed.unwrap_err().to_string().contains("'sqlite' feature"));
+        }
+        assert!(parse_args(&args(&["a", "b", "--export-sqlite", "activity.db"])).is_err());
+        assert!(parse_args(&args(&["--from-file", "a.json", "--export-sqlite", "activity.db"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_backend() {
+        let parsed = parse_args(&args(&["torvalds", "--backend", "graphql", "--since", "2024-05-01"]));
+        if cfg!(feature = "graphql") {
+            assert_eq!(
+                parsed.unwrap(),
+                Command::Activity(Options {
+                    target: Some(Target::User("torvalds".to_string())),
+                    backend: Backend::GraphQl,
+                    since: Timestamp::parse_date("2024-05-01"),
+                    ..Options::default()
+                })
+            );
+            assert!(parse_args(&args(&["torvalds", "--backend", "graphql", "--all"])).is_err());
+            assert!(parse_args(&args(&["torvalds", "--backend", "graphql", "--new"])).is_err());
+            assert!(parse_args(&args(&["rust-lang/rust", "--backend", "graphql"])).is_err());
+            assert!(parse_args(&args(&["a", "b", "--backend", "graphql"])).is_err());
+        } else {
+            assert!(parsed.unwrap_err().to_string().contains("'graphql' feature"));
+        }
+        assert_eq!(
+            parse_args(&args(&["torvalds", "--backend=rest"])).unwrap(),
+            Command::Activity(Options { target: Some(Target::User("torvalds".to_string())), ..Options::default() })
+        );
+        assert!(parse_args(&args(&["torvalds", "--backend", "soap"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--backend", "rest", "--since", "2024-05-01"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_config_flags() {
+        assert_eq!(
+            parse_args(&args(&["torvalds", "--limit", "5", "--token=abc", "--api-url", "https://ghe.example.com/api/v3/"]))
+                .unwrap(),
+            Command::Activity(Options {
+                target: Some(Target::User("torvalds".to_string())),
+                limit: Some(5),
+                token: Some("abc".to_string()),
+                api_url: Some("https://ghe.example.com/api/v3".to_string()),
+                ..Options::default()
+            })
+        );
+        assert!(parse_args(&args(&["torvalds", "--limit", "0"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--limit", "many"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--api-url", "ftp://example.com"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_wait_on_limit() {
+        match parse(&args(&["torvalds", "--wait-on-limit"])).unwrap() {
+            Command::Activity(options) => assert!(options.wait_on_limit),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--wait-on-limit=60"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_skip_validation() {
+        match parse(&args(&["torvalds", "--skip-validation"])).unwrap() {
+            Command::Activity(options) => assert!(options.skip_validation),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse(&args(&["torvalds"])).unwrap() {
+            Command::Activity(options) => assert!(!options.skip_validation),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_no_profile() {
+        match parse(&args(&["torvalds", "--no-profile"])).unwrap() {
+            Command::Activity(options) => assert!(options.no_profile),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_no_compression() {
+        match parse(&args(&["torvalds", "--no-compression"])).unwrap() {
+            Command::Activity(options) => assert!(options.no_compression),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--no-compression=yes"])).is_err());
+    }
+
+    #[test]
+    fn test_resolve_fills_in_from_config() {
+        let lower = Config {
+            limit: Some(10),
+            color: Some(false),
+            token: Some("from-config".to_string()),
+            api_url: None,
+            api_version: None,
+            default_user: Some("octocat".to_string()),
+            ignored_actors: Some(vec!["hubot".to_string()]),
+        };
+
+        // Sem username na linha de comando, vale o default_user
+        let options = match parse_args(&args(&["--limit", "3"])).unwrap() {
+            Command::Activity(options) => options.resolve(Config::default(), lower.clone()).unwrap(),
+            other => panic!("unexpected {:?}", other),
+        };
+        assert_eq!(options.target, Some(Target::User("octocat".to_string())));
+        assert_eq!(options.limit, Some(3));
+        assert_eq!(options.token.as_deref(), Some("from-config"));
+        assert!(options.no_color);
+        assert_eq!(options.token_source, Some(TokenSource::ConfigFile));
+
+        // Um username explícito vence; com --from-file o default_user não é usado
+        let options = match parse_args(&args(&["torvalds"])).unwrap() {
+            Command::Activity(options) => options.resolve(Config::default(), lower.clone()).unwrap(),
+            other => panic!("unexpected {:?}", other),
+        };
+        assert_eq!(options.target, Some(Target::User("torvalds".to_string())));
+        let options = match parse_args(&args(&["--from-file", "events.json"])).unwrap() {
+            Command::Activity(options) => options.resolve(Config::default(), lower).unwrap(),
+            other => panic!("unexpected {:?}", other),
+        };
+        assert_eq!(options.target, None);
+    }
+
+    #[test]
+    fn test_verbosity() {
+        let verbosity = |list: &[&str]| match parse_args(&args(list)).unwrap() {
+            Command::Activity(options) => options.verbosity(),
+            other => panic!("unexpected {:?}", other),
+        };
+        let all = Verbosity { progress: true, header: true, notices: true };
+
+        assert_eq!(verbosity(&["torvalds"]), all);
+        assert_eq!(verbosity(&["torvalds", "--quiet"]), Verbosity { progress: false, header: false, notices: false });
+        assert_eq!(verbosity(&["torvalds", "--summary", "--json"]), Verbosity { progress: false, ..all });
+        assert_eq!(verbosity(&["torvalds", "--template", "{repo}"]), Verbosity { progress: false, header: false, notices: true });
+    }
+
+    #[test]
+    fn test_parse_sort() {
+        match parse_args(&args(&["torvalds", "--sort", "oldest"])).unwrap() {
+            Command::Activity(options) => assert_eq!(options.sort, Some(SortOrder::Oldest)),
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_args(&args(&["--sort=repo", "torvalds"])).unwrap() {
+            Command::Activity(options) => assert_eq!(options.sort, Some(SortOrder::Repo)),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--sort", "stars"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--sort", "oldest", "--format", "ndjson"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_count_only() {
+        match parse_args(&args(&["torvalds", "--count-only", "--group-by", "type"])).unwrap() {
+            Command::Activity(options) => {
+                assert!(options.count_only);
+                assert_eq!(options.group_by, Some(GroupBy::Type));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--count-only", "--summary"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--count-only", "--format", "table"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "octocat", "--count-only"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_type_and_action() {
+        match parse_args(&args(&["torvalds", "--type", "PullRequestEvent", "--action", "closed"])).unwrap() {
+            Command::Activity(options) => {
+                assert_eq!(options.types, vec![EventKind::PullRequest]);
+                assert_eq!(options.actions, vec!["closed"]);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_args(&args(&["torvalds", "--type=PushEvent, IssuesEvent"])).unwrap() {
+            Command::Activity(options) => {
+                assert_eq!(options.types, vec![EventKind::Push, EventKind::Issues]);
+                assert!(options.actions.is_empty());
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_args(&args(&["torvalds", "--action", "opened,reopened"])).unwrap() {
+            Command::Activity(options) => assert_eq!(options.actions, vec!["opened", "reopened"]),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        assert!(parse_args(&args(&["torvalds", "--type", "push"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--type", "PushEvent,"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--action", "opened,,closed"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_category() {
+        match parse_args(&args(&["torvalds", "--category", "code, social"])).unwrap() {
+            Command::Activity(options) => {
+                assert_eq!(options.categories, vec![EventCategory::Code, EventCategory::Social])
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--category", "Code"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--category", "code,"])).is_err());
+        assert!(parse_args(&args(&["torvalds", "--starred", "--category", "code"])).is_err());
+    }
+
+    #[test]
+    fn test_resolve_ignored_actors() {
+        let lower = Config { ignored_actors: Some(vec!["hubot".to_string()]), ..Config::default() };
+        let resolve = |list: &[&str]| match parse_args(&args(list)).unwrap() {
+            Command::Activity(options) => options.resolve(Config::default(), lower.clone()).unwrap(),
+            other => panic!("unexpected {:?}", other),
+        };
+
+        // --ignore-actor é repetível e vale sozinho; a lista da configuração só com --no-bots
+        let options = resolve(&["torvalds", "--ignore-actor", "bors", "--ignore-actor=ci-runner"]);
+        assert_eq!(options.ignored_actors, vec!["bors", "ci-runner"]);
+        assert!(!options.no_bots);
+
+        let options = resolve(&["torvalds", "--no-bots", "--ignore-actor", "bors"]);
+        assert_eq!(options.ignored_actors, vec!["bors", "hubot"]);
+        assert!(options.no_bots);
+    }
+
+    #[test]
+    fn test_resolve_records_the_token_source() {
+        let token = |value: &str| Config { token: Some(value.to_string()), ..Config::default() };
+        let resolve = |list: &[&str], environment: Config, file: Config| {
+            match parse_args(&args(list)).unwrap() {
+                Command::Activity(options) => options.resolve(environment, file).unwrap(),
+                other => panic!("unexpected {:?}", other),
+            }
+        };
+
+        let options = resolve(&["torvalds", "--token", "a"], token("b"), token("c"));
+        assert_eq!(options.token.as_deref(), Some("a"));
+        assert_eq!(options.token_source, Some(TokenSource::CommandLine));
+        let options = resolve(&["torvalds"], token("b"), token("c"));
+        assert_eq!(options.token.as_deref(), Some("b"));
+        assert_eq!(options.token_source, Some(TokenSource::Environment));
+        let options = resolve(&["torvalds"], Config::default(), Config::default());
+        assert_eq!((options.token, options.token_source), (None, None));
+
+        assert!(resolve(&["torvalds", "--no-gh-token"], Config::default(), Config::default()).no_gh_token);
+    }
+
+    #[test]
+    fn test_usage_documents_username_discovery() {
+        let text = usage("github-activity");
+        assert!(text.contains("GET /user"));
+        assert!(text.contains("git config github.user"));
+    }
+
+    #[test]
+    fn test_usage_documents_repo_mode() {
+        assert!(usage("github-activity").contains("owner/repo"));
+    }
+
+    #[test]
+    fn test_parse_requires_a_username() {
+        assert!(parse(&args(&[])).is_err());
+        assert!(parse_args(&args(&["--bogus", "a"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_jobs() {
+        match parse_args(&args(&["a", "b", "--jobs", "2"])).unwrap() {
+            Command::Activity(options) => assert_eq!(options.jobs, Some(2)),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["a", "b", "--jobs", "0"])).is_err());
+        assert!(parse_args(&args(&["a", "b", "--jobs", "many"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_paging() {
+        match parse_args(&args(&["torvalds", "--all", "--per-page", "50", "--max-events=120"])).unwrap() {
+            Command::Activity(options) => {
+                assert_eq!((options.per_page, options.max_events), (Some(50), Some(120)));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_args(&args(&["torvalds", "--per-page", "1"])).is_ok());
+        assert!(parse_args(&args(&["torvalds", "--per-page", "100"])).is_ok());
+        for bad in ["0", "101", "lots"] {
+            assert!(parse_args(&args(&["torvalds", "--per-page", bad])).is_err(), "{}", bad);
+        }
+        assert!(parse_args(&args(&["torvalds", "--max-events", "0"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_username_lists() {
+        let read = |arg: &str| match arg {
+            "@team.txt" => Ok("# time\ntorvalds\noctocat\n".to_string()),
+            "-" => Ok("gvanrossum\n".to_string()),
+            _ => Err(ActivityError::io(&arg[1..], std::io::ErrorKind::NotFound.into())),
+        };
+
+        match parse_args_with(&args(&["octocat", "@team.txt", "-", "--limit", "5"]), read).unwrap() {
+            Command::Activity(options) => {
+                assert_eq!(options.target, Some(Target::User("octocat".to_string())));
+                assert_eq!(
+                    options.more_targets,
+                    vec![Target::User("torvalds".to_string()), Target::User("gvanrossum".to_string())]
+                );
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        // Um só nome na lista ainda é um único alvo (vale com --open)
+        assert!(parse_args_with(&args(&["-", "--open", "1"]), read).is_ok());
+        assert!(matches!(
+            parse_args_with(&args(&["@missing.txt"]), read),
+            Err(ActivityError::IoError { .. })
+        ));
+        let stdin_twice = parse_args_with(&args(&["--from-file", "-", "-"]), read).unwrap_err();
+        assert!(stdin_twice.to_string().contains("cannot both read stdin"));
+
+        // Todos os nomes inválidos aparecem no mesmo erro
+        let invalid = |_: &str| Ok("user_name\nocto--cat\n".to_string());
+        let error = parse_args_with(&args(&["@bad.txt"]), invalid).unwrap_err();
+        assert!(error.to_string().contains("2 invalid entries"));
+        assert!(parse_args_with(&args(&["@bad.txt", "--skip-validation"]), invalid).is_ok());
+    }
+
+    #[test]
+    fn test_parse_several_targets() {
+        match parse(&args(&["torvalds", "rust-lang/rust", "octocat"])).unwrap() {
+            Command::Activity(options) => assert_eq!(
+                options.targets(),
+                vec![
+                    &Target::User("torvalds".to_string()),
+                    &Target::Repo { owner: "rust-lang".to_string(), repo: "rust".to_string() },
+                    &Target::User("octocat".to_string()),
+                ]
+            ),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_help() {
+        assert_eq!(parse_args(&args(&["--help"])).unwrap(), Command::Help);
+        assert_eq!(parse_args(&args(&["generate", "-h"])).unwrap(), Command::Help);
+    }
+
+    #[test]
+    fn test_parse_completions() {
+        assert_eq!(parse_args(&args(&["--completions", "fish"])).unwrap(), Command::Completions(Shell::Fish));
+        // Como --help, vale junto de qualquer outra coisa e não aparece no texto de ajuda
+        assert_eq!(parse_args(&args(&["torvalds", "--completions=bash"])).unwrap(), Command::Completions(Shell::Bash));
+        assert!(parse_args(&args(&["--completions", "powershell"])).is_err());
+        assert!(parse_args(&args(&["--completions"])).is_err());
+        assert!(!usage("github-activity").contains("--completions"));
+    }
+
+    #[test]
+    fn test_parse_generate() {
+        let command =
+            parse_args(&args(&["generate", "--events", "50", "--seed=7", "--kinds", "push,star"]))
+                .unwrap();
+        assert_eq!(
+            command,
+            Command::Generate(GenerateOptions {
+                events: 50,
+                seed: 7,
+                kinds: vec![Kind::Push, Kind::Star],
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_generate_errors() {
+        assert!(parse_args(&args(&["generate", "--events"])).is_err());
+        assert!(parse_args(&args(&["generate", "--events", "many"])).is_err());
+        assert!(parse_args(&args(&["generate", "--kinds", "nope"])).is_err());
+    }
+
+    #[test]
+    fn test_usage_lists_every_flag() {
+        let text = usage("github-activity");
+        for flag in FLAGS.iter().chain(GENERATE_FLAGS) {
+            assert!(text.contains(flag.name), "missing {}", flag.name);
+        }
+    }
+
+    #[test]
+    fn test_usage_lists_exit_codes() {
+        let text = usage("github-activity");
+        for (code, description) in EXIT_CODES {
+            assert!(text.contains(&format!("{:<3} {}", code, description)), "missing {}", code);
+        }
+    }
+}
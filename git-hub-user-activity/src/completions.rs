@@ -0,0 +1,313 @@
+//! Este módulo gera os scripts de autocompletar do shell (--completions bash|zsh|fish)
+//!
+//! Os scripts saem das mesmas tabelas de flags do --help (cli::FLAGS e
+//! cli::GENERATE_FLAGS), então uma flag nova aparece neles sem mais nada a fazer.
+//! O nome do valor de cada flag decide o que é completado depois dela:
+//! `repo|type` vira a lista de valores, `PATH` completa arquivos e o resto
+//! (N, TEXT...) fica sem sugestões
+//!
+//! Os testes comparam a saída com os scripts em tests/fixtures/completions
+
+use std::fmt;
+
+use github_activity::error::ActivityError;
+
+use crate::cli::{FlagSpec, FLAGS, GENERATE_FLAGS};
+
+// Descrição do subcomando `generate` no fish, que mostra uma ao lado de cada sugestão
+const GENERATE_HELP: &str = "Synthesize a realistic events JSON array";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    pub fn parse(text: &str) -> Result<Shell, ActivityError> {
+        match text {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(ActivityError::InvalidArgument(format!(
+                "--completions expects 'bash', 'zsh' or 'fish', got '{}'",
+                text
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Shell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Shell::Bash => write!(f, "bash"),
+            Shell::Zsh => write!(f, "zsh"),
+            Shell::Fish => write!(f, "fish"),
+        }
+    }
+}
+
+// O que completar depois de uma flag
+enum Value {
+    None,
+    Choices(Vec<&'static str>),
+    Path,
+    Other,
+}
+
+fn value_of(flag: &FlagSpec) -> Value {
+    match flag.value {
+        None => Value::None,
+        Some("PATH") => Value::Path,
+        Some(value) if value.contains('|') => Value::Choices(value.split('|').collect()),
+        Some(_) => Value::Other,
+    }
+}
+
+// O script de `shell` para o programa chamado `program`
+pub fn script(shell: Shell, program: &str) -> String {
+    match shell {
+        Shell::Bash => bash(program),
+        Shell::Zsh => zsh(program),
+        Shell::Fish => fish(program),
+    }
+}
+
+// Nome da função de completar: "github-activity" vira "_github_activity"
+fn function_name(program: &str) -> String {
+    let name: String = program.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    format!("_{}", name)
+}
+
+// Cabeçalho comum: de onde o script veio e como carregá-lo
+fn header(program: &str, shell: Shell, install: &str) -> String {
+    format!(
+        "# {shell} completion for {program}\n# Generated by `{program} --completions {shell}`; {install}\n",
+        shell = shell,
+        program = program,
+        install = install
+    )
+}
+
+// CONCEITO: Geração de código
+// Cada shell tem sua linguagem; montamos o texto do script linha por linha
+fn bash(program: &str) -> String {
+    let function = function_name(program);
+    let mut text = header(program, Shell::Bash, "load it with:");
+    text.push_str(&format!("#   source <({} --completions bash)\n\n", program));
+    text.push_str(&format!("{}() {{\n", function));
+    text.push_str("    local cur=\"${COMP_WORDS[COMP_CWORD]}\" prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n\n");
+
+    // O valor da flag anterior: uma lista fixa, um arquivo ou nada a sugerir
+    let all_flags: Vec<&FlagSpec> = FLAGS.iter().chain(GENERATE_FLAGS).collect();
+    let mut paths = Vec::new();
+    let mut others = Vec::new();
+    text.push_str("    case \"$prev\" in\n");
+    for flag in &all_flags {
+        match value_of(flag) {
+            Value::None => {}
+            Value::Choices(choices) => {
+                text.push_str(&format!("        {})\n", flag.name));
+                text.push_str(&format!(
+                    "            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n",
+                    choices.join(" ")
+                ));
+                text.push_str("            return\n            ;;\n");
+            }
+            Value::Path => paths.push(flag.name),
+            Value::Other => others.push(flag.name),
+        }
+    }
+    text.push_str(&format!("        {})\n", paths.join("|")));
+    text.push_str("            COMPREPLY=($(compgen -f -- \"$cur\"))\n");
+    text.push_str("            return\n            ;;\n");
+    text.push_str(&format!("        {})\n", others.join("|")));
+    text.push_str("            return\n            ;;\n");
+    text.push_str("    esac\n\n");
+
+    text.push_str("    local flags=(\n");
+    for flag in FLAGS {
+        text.push_str(&format!("        {}\n", flag.name));
+    }
+    text.push_str("    )\n");
+    text.push_str("    local generate_flags=(\n");
+    for flag in GENERATE_FLAGS {
+        text.push_str(&format!("        {}\n", flag.name));
+    }
+    text.push_str("    )\n\n");
+
+    text.push_str("    if [[ $COMP_CWORD -gt 1 && \"${COMP_WORDS[1]}\" == generate ]]; then\n");
+    text.push_str("        COMPREPLY=($(compgen -W \"${generate_flags[*]}\" -- \"$cur\"))\n");
+    text.push_str("    elif [[ \"$cur\" == -* ]]; then\n");
+    text.push_str("        COMPREPLY=($(compgen -W \"${flags[*]}\" -- \"$cur\"))\n");
+    text.push_str("    elif [[ $COMP_CWORD -eq 1 ]]; then\n");
+    text.push_str("        COMPREPLY=($(compgen -W \"generate\" -- \"$cur\"))\n");
+    text.push_str("    fi\n");
+    text.push_str("}\n\n");
+    text.push_str(&format!("complete -F {} {}\n", function, program));
+    text
+}
+
+// Dentro de '...' do zsh: a aspa simples fecha e reabre a string
+// e os colchetes da descrição precisam de barra
+fn zsh_description(help: &str) -> String {
+    help.replace('\'', "'\\''").replace('[', "\\[").replace(']', "\\]")
+}
+
+// Uma especificação de _arguments: '--flag[ajuda]:valor:ação'
+fn zsh_spec(flag: &FlagSpec) -> String {
+    let action = match value_of(flag) {
+        Value::None => return format!("'{}[{}]'", flag.name, zsh_description(flag.help)),
+        Value::Choices(choices) => format!("({})", choices.join(" ")),
+        Value::Path => "_files".to_string(),
+        // Um espaço: o zsh mostra o nome do valor, sem sugestões
+        Value::Other => " ".to_string(),
+    };
+    format!(
+        "'{}[{}]:{}:{}'",
+        flag.name,
+        zsh_description(flag.help),
+        flag.value.unwrap_or_default(),
+        action
+    )
+}
+
+fn zsh(program: &str) -> String {
+    let function = function_name(program);
+    let mut text = format!("#compdef {}\n\n", program);
+    text.push_str(&header(
+        program,
+        Shell::Zsh,
+        &format!("save it as _{} in a\n# directory of $fpath", program),
+    ));
+    text.push('\n');
+    text.push_str(&format!("{}() {{\n", function));
+
+    text.push_str("    if (( CURRENT > 2 )) && [[ ${words[2]} == generate ]]; then\n");
+    text.push_str("        _arguments \\\n");
+    for flag in GENERATE_FLAGS {
+        text.push_str(&format!("            {} \\\n", zsh_spec(flag)));
+    }
+    text.push_str("            '*: :'\n");
+    text.push_str("        return\n");
+    text.push_str("    fi\n\n");
+
+    text.push_str("    _arguments \\\n");
+    for flag in FLAGS {
+        text.push_str(&format!("        {} \\\n", zsh_spec(flag)));
+    }
+    text.push_str("        '1: :(generate)' \\\n");
+    text.push_str("        '*:username or owner/repo: '\n");
+    text.push_str("}\n\n");
+    text.push_str(&format!("{} \"$@\"\n", function));
+    text
+}
+
+// Dentro de '...' do fish, a barra e a aspa simples precisam de barra
+fn fish_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn fish_line(program: &str, condition: &str, flag: &FlagSpec) -> String {
+    // -vv é uma opção "antiga" (um traço, vários caracteres): -o no fish
+    let option = match flag.name.strip_prefix("--") {
+        Some(long) => format!("-l {}", long),
+        None => format!("-o {}", flag.name.trim_start_matches('-')),
+    };
+    let value = match value_of(flag) {
+        Value::None => String::new(),
+        Value::Choices(choices) => format!(" -x -a {}", fish_quote(&choices.join(" "))),
+        Value::Path => " -r -F".to_string(),
+        Value::Other => " -x".to_string(),
+    };
+    format!(
+        "complete -c {} -n {} {}{} -d {}\n",
+        program,
+        fish_quote(condition),
+        option,
+        value,
+        fish_quote(flag.help)
+    )
+}
+
+fn fish(program: &str) -> String {
+    let mut text = header(program, Shell::Fish, "save it as");
+    text.push_str(&format!("# ~/.config/fish/completions/{}.fish\n\n", program));
+
+    // Usernames não têm o que completar: nada de nomes de arquivo por padrão
+    text.push_str(&format!("complete -c {} -f\n", program));
+    text.push_str(&format!(
+        "complete -c {} -n __fish_use_subcommand -a generate -d {}\n\n",
+        program,
+        fish_quote(GENERATE_HELP)
+    ));
+    for flag in FLAGS {
+        text.push_str(&fish_line(program, "not __fish_seen_subcommand_from generate", flag));
+    }
+    text.push('\n');
+    for flag in GENERATE_FLAGS {
+        text.push_str(&fish_line(program, "__fish_seen_subcommand_from generate", flag));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Os scripts esperados ficam em arquivos, para serem lidos (e testados) como scripts
+    // Uma flag nova muda os três: gere-os de novo com
+    //   cargo run -- --completions bash > tests/fixtures/completions/github-activity.bash
+    // (trocando o nome do programa) e confira a diferença
+    #[test]
+    fn test_bash_script() {
+        assert_eq!(
+            script(Shell::Bash, "github-activity"),
+            include_str!("../tests/fixtures/completions/github-activity.bash")
+        );
+    }
+
+    #[test]
+    fn test_zsh_script() {
+        assert_eq!(
+            script(Shell::Zsh, "github-activity"),
+            include_str!("../tests/fixtures/completions/_github-activity")
+        );
+    }
+
+    #[test]
+    fn test_fish_script() {
+        assert_eq!(
+            script(Shell::Fish, "github-activity"),
+            include_str!("../tests/fixtures/completions/github-activity.fish")
+        );
+    }
+
+    #[test]
+    fn test_every_flag_is_completed() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let text = script(shell, "github-activity");
+            for flag in FLAGS.iter().chain(GENERATE_FLAGS) {
+                let name = match shell {
+                    // O fish escreve as flags sem os traços (-l from-file)
+                    Shell::Fish => flag.name.trim_start_matches('-'),
+                    Shell::Bash | Shell::Zsh => flag.name,
+                };
+                assert!(text.contains(name), "{} script is missing {}", shell, flag.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_shell() {
+        assert_eq!(Shell::parse("zsh").unwrap(), Shell::Zsh);
+        assert!(Shell::parse("powershell").is_err());
+    }
+
+    #[test]
+    fn test_function_name() {
+        assert_eq!(function_name("github-activity"), "_github_activity");
+        assert_eq!(function_name("git-hub-user-activity"), "_git_hub_user_activity");
+    }
+}
@@ -0,0 +1,428 @@
+//! Este módulo lê as configurações que valem para toda execução
+//! Elas podem vir de três lugares, e o primeiro que informar um valor vence:
+//!
+//!   1. a linha de comando (--limit, --no-color, --token, --api-url)
+//!   2. variáveis de ambiente (GITHUB_ACTIVITY_LIMIT, NO_COLOR, GITHUB_TOKEN...)
+//!   3. o arquivo ~/.config/github-activity/config
+//!
+//! e, se nenhum informar, vale o padrão embutido no programa.
+//!
+//! O arquivo é um "TOML de bolso": uma chave por linha, no formato `chave = valor`
+//!
+//! ```text
+//! # ~/.config/github-activity/config
+//! default_user = "torvalds"
+//! limit = 10
+//! color = false
+//! token = "ghp_..."
+//! api_url = "https://github.example.com/api/v3"
+//! api_version = "2022-11-28"
+//! ignored_actors = "hubot, ci-runner"
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::ActivityError;
+
+// Subdiretório e nome do arquivo dentro do diretório de configuração
+const CONFIG_SUBDIR: &str = "github-activity";
+const CONFIG_FILE: &str = "config";
+
+/// Chaves aceitas no arquivo, na ordem em que aparecem nas mensagens de erro
+pub const KEYS: &[&str] = &["limit", "color", "token", "api_url", "api_version", "default_user", "ignored_actors"];
+
+/// Variáveis de ambiente lidas por Config::from_env, na mesma ordem de KEYS
+/// NO_COLOR segue a convenção de no-color.org: qualquer valor não vazio desliga as cores
+pub const ENV_VARS: &[(&str, &str)] = &[
+    ("GITHUB_ACTIVITY_LIMIT", "limit"),
+    ("NO_COLOR", "color"),
+    ("GITHUB_TOKEN", "token"),
+    ("GITHUB_API_URL", "api_url"),
+    ("GITHUB_API_VERSION", "api_version"),
+    ("GITHUB_ACTIVITY_USER", "default_user"),
+    ("GITHUB_ACTIVITY_IGNORED_ACTORS", "ignored_actors"),
+];
+
+/// Uma "camada" de configuração: cada campo é None quando esta origem não diz nada
+/// A mesma struct representa a linha de comando, o ambiente e o arquivo
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    /// Máximo de eventos exibidos
+    pub limit: Option<usize>,
+    /// false desliga as cores (true deixa a detecção automática decidir)
+    pub color: Option<bool>,
+    /// Token de acesso enviado à API
+    pub token: Option<String>,
+    /// Endereço base da API, sem a barra final
+    pub api_url: Option<String>,
+    /// Versão da API pedida no header X-GitHub-Api-Version (ex: "2022-11-28")
+    pub api_version: Option<String>,
+    /// Username usado quando a linha de comando não informa nenhum
+    pub default_user: Option<String>,
+    /// Logins escondidos por --no-bots além dos terminados em [bot] (lista separada por vírgulas)
+    pub ignored_actors: Option<Vec<String>>,
+}
+
+impl Config {
+    /// Junta duas camadas: os valores de `self` vencem, os de `lower` preenchem o que faltar
+    /// Ex: cli.or(env).or(file) aplica a precedência completa
+    // CONCEITO: Option::or
+    // Some(a).or(b) == Some(a); None.or(b) == b
+    pub fn or(self, lower: Config) -> Config {
+        Config {
+            limit: self.limit.or(lower.limit),
+            color: self.color.or(lower.color),
+            token: self.token.or(lower.token),
+            api_url: self.api_url.or(lower.api_url),
+            api_version: self.api_version.or(lower.api_version),
+            default_user: self.default_user.or(lower.default_user),
+            ignored_actors: self.ignored_actors.or(lower.ignored_actors),
+        }
+    }
+
+    /// Interpreta o texto do arquivo de configuração
+    /// Erros dizem a linha: "line 3: unknown key 'colour' (expected ...)"
+    pub fn parse(text: &str) -> Result<Config, ActivityError> {
+        let mut config = Config::default();
+        let mut seen_keys: Vec<&str> = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |message: String| {
+                ActivityError::InvalidConfig(format!("line {}: {}", number, message))
+            };
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("expected 'key = value', got '{}'", line)))?;
+            let key = key.trim();
+            let value = unquote(value.trim());
+
+            if !KEYS.contains(&key) {
+                return Err(invalid(format!(
+                    "unknown key '{}' (expected one of: {})",
+                    key,
+                    KEYS.join(", ")
+                )));
+            }
+            if seen_keys.contains(&key) {
+                return Err(invalid(format!("duplicate key '{}'", key)));
+            }
+            seen_keys.push(key);
+
+            if value.is_empty() {
+                return Err(invalid(format!("missing value for '{}'", key)));
+            }
+
+            config.set(key, value).map_err(invalid)?;
+        }
+
+        Ok(config)
+    }
+
+    /// Lê as variáveis de ambiente de ENV_VARS
+    /// Recebe a leitura como parâmetro para poder ser testada sem mexer no ambiente do processo
+    /// Variáveis vazias contam como ausentes
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Result<Config, ActivityError> {
+        let mut config = Config::default();
+
+        for (name, key) in ENV_VARS {
+            let value = match var(name) {
+                Some(value) if !value.is_empty() => value,
+                _ => continue,
+            };
+
+            // NO_COLOR não tem valor "verdadeiro" ou "falso": estar definida já desliga
+            let value = if *key == "color" { "false" } else { value.as_str() };
+
+            config
+                .set(key, value)
+                .map_err(|message| ActivityError::InvalidConfig(format!("{}: {}", name, message)))?;
+        }
+
+        Ok(config)
+    }
+
+    // Valida e guarda o valor de uma chave de KEYS
+    // O erro é só a descrição; quem chama acrescenta a origem (linha do arquivo, variável)
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "limit" => self.limit = Some(parse_limit(value)?),
+            "color" => self.color = Some(parse_bool(value)?),
+            "token" => self.token = Some(value.to_string()),
+            "api_url" => self.api_url = Some(parse_api_url(value)?),
+            "api_version" => self.api_version = Some(parse_api_version(value)?),
+            "default_user" => self.default_user = Some(value.to_string()),
+            "ignored_actors" => self.ignored_actors = Some(parse_actor_list(value)?),
+            _ => return Err(format!("unknown key '{}'", key)),
+        }
+        Ok(())
+    }
+}
+
+/// Valida um limite de eventos (--limit, GITHUB_ACTIVITY_LIMIT, limit =)
+pub fn parse_limit(value: &str) -> Result<usize, String> {
+    match value.parse() {
+        Ok(limit) if limit > 0 => Ok(limit),
+        _ => Err(format!("expected a positive number, got '{}'", value)),
+    }
+}
+
+/// Valida o endereço da API e tira a barra final ("https://x/api/v3/" -> "https://x/api/v3")
+pub fn parse_api_url(value: &str) -> Result<String, String> {
+    if !(value.starts_with("https://") || value.starts_with("http://")) {
+        return Err(format!("expected an http:// or https:// URL, got '{}'", value));
+    }
+    Ok(value.trim_end_matches('/').to_string())
+}
+
+/// Valida uma versão da API: as versões do GitHub são datas, no formato AAAA-MM-DD
+pub fn parse_api_version(value: &str) -> Result<String, String> {
+    let is_date = value.len() == 10
+        && value.char_indices().all(|(i, c)| if i == 4 || i == 7 { c == '-' } else { c.is_ascii_digit() });
+    if !is_date {
+        return Err(format!("expected a date like 2022-11-28, got '{}'", value));
+    }
+    Ok(value.to_string())
+}
+
+/// Valida uma lista de logins separados por vírgula ("hubot, ci-runner")
+pub fn parse_actor_list(value: &str) -> Result<Vec<String>, String> {
+    let logins: Vec<String> = value.split(',').map(str::trim).map(str::to_string).collect();
+    if logins.iter().any(String::is_empty) {
+        return Err(format!("expected logins separated by commas, got '{}'", value));
+    }
+    Ok(logins)
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value {
+        "true" | "yes" | "on" => Ok(true),
+        "false" | "no" | "off" => Ok(false),
+        _ => Err(format!("expected true or false, got '{}'", value)),
+    }
+}
+
+// Tira um par de aspas em volta do valor ("x" ou 'x'), como em TOML
+pub(crate) fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value.strip_prefix(quote).and_then(|rest| rest.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    value
+}
+
+/// Caminho do arquivo de configuração, ou None se não há diretório de configuração conhecido
+pub fn config_path() -> Option<PathBuf> {
+    config_dir(|name| std::env::var_os(name).map(PathBuf::from))
+        .map(|dir| dir.join(CONFIG_SUBDIR).join(CONFIG_FILE))
+}
+
+// $XDG_CONFIG_HOME ou ~/.config, em todos os sistemas (como git e outras ferramentas de terminal)
+// Caminhos relativos são ignorados, como pede a especificação XDG
+pub(crate) fn config_dir(var: impl Fn(&str) -> Option<PathBuf>) -> Option<PathBuf> {
+    let absolute = |name: &str| var(name).filter(|path| path.is_absolute());
+
+    absolute("XDG_CONFIG_HOME").or_else(|| absolute("HOME").map(|home| home.join(".config")))
+}
+
+/// Lê o arquivo de configuração; um arquivo que não existe é uma configuração vazia
+/// Os erros de formato começam pelo caminho: "/home/me/.config/...: line 3: ..."
+pub fn load(path: &Path) -> Result<Config, ActivityError> {
+    match fs::read_to_string(path) {
+        Ok(text) => Config::parse(&text).map_err(|error| match error {
+            ActivityError::InvalidConfig(message) => {
+                ActivityError::InvalidConfig(format!("{}: {}", path.display(), message))
+            }
+            other => other,
+        }),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(error) => Err(ActivityError::io(path.display().to_string(), error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(text: &str) -> Config {
+        Config::parse(text).unwrap()
+    }
+
+    fn env(vars: &'static [(&'static str, &'static str)]) -> Config {
+        Config::from_env(|name| {
+            vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+        })
+        .unwrap()
+    }
+
+    fn cli_limit(limit: usize) -> Config {
+        Config { limit: Some(limit), ..Config::default() }
+    }
+
+    #[test]
+    fn test_parse_all_keys() {
+        let config = file(
+            "# defaults\n\
+             default_user = \"torvalds\"\n\
+             limit=10\n\
+             \n\
+             color = false\n\
+             token = 'ghp_abc'\n\
+             api_url = https://github.example.com/api/v3/\n\
+             api_version = 2026-03-10\n\
+             ignored_actors = \"hubot, ci-runner\"\n",
+        );
+        assert_eq!(
+            config,
+            Config {
+                limit: Some(10),
+                color: Some(false),
+                token: Some("ghp_abc".to_string()),
+                api_url: Some("https://github.example.com/api/v3".to_string()),
+                api_version: Some("2026-03-10".to_string()),
+                default_user: Some("torvalds".to_string()),
+                ignored_actors: Some(vec!["hubot".to_string(), "ci-runner".to_string()]),
+            }
+        );
+        assert_eq!(file(""), Config::default());
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        let cases = [
+            ("limit = 10\ncolour = false", "line 2: unknown key 'colour'"),
+            ("\n\njust some text", "line 3: expected 'key = value', got 'just some text'"),
+            ("limit = ten", "line 1: expected a positive number, got 'ten'"),
+            ("limit = 0", "line 1: expected a positive number, got '0'"),
+            ("color = maybe", "line 1: expected true or false, got 'maybe'"),
+            ("api_url = github.com", "line 1: expected an http:// or https:// URL"),
+            ("api_version = latest", "line 1: expected a date like 2022-11-28, got 'latest'"),
+            ("api_version = 2022-1-28", "line 1: expected a date like 2022-11-28"),
+            ("token =", "line 1: missing value for 'token'"),
+            ("token = \"\"", "line 1: missing value for 'token'"),
+            ("limit = 1\nlimit = 2", "line 2: duplicate key 'limit'"),
+            ("ignored_actors = hubot,,bors", "line 1: expected logins separated by commas, got 'hubot,,bors'"),
+        ];
+
+        for (text, expected) in cases {
+            let error = Config::parse(text).unwrap_err();
+            assert!(matches!(error, ActivityError::InvalidConfig(_)), "{:?}", error);
+            assert!(error.to_string().contains(expected), "{:?}: {}", text, error);
+        }
+    }
+
+    #[test]
+    fn test_from_env() {
+        assert_eq!(
+            env(&[
+                ("GITHUB_ACTIVITY_LIMIT", "5"),
+                ("NO_COLOR", "1"),
+                ("GITHUB_TOKEN", "ghp_env"),
+                ("GITHUB_API_URL", "http://localhost:8080"),
+                ("GITHUB_ACTIVITY_USER", "octocat"),
+                ("GITHUB_ACTIVITY_IGNORED_ACTORS", "hubot"),
+            ]),
+            Config {
+                limit: Some(5),
+                color: Some(false),
+                token: Some("ghp_env".to_string()),
+                api_url: Some("http://localhost:8080".to_string()),
+                api_version: None,
+                default_user: Some("octocat".to_string()),
+                ignored_actors: Some(vec!["hubot".to_string()]),
+            }
+        );
+
+        // Vazias contam como ausentes (NO_COLOR= não desliga as cores)
+        assert_eq!(env(&[("NO_COLOR", ""), ("GITHUB_TOKEN", "")]), Config::default());
+
+        let error = Config::from_env(|name| (name == "GITHUB_ACTIVITY_LIMIT").then(|| "lots".to_string()))
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid configuration: GITHUB_ACTIVITY_LIMIT: expected a positive number, got 'lots'"
+        );
+    }
+
+    #[test]
+    fn test_precedence_cli_over_env_over_file() {
+        let from_file = file("limit = 30");
+        let from_env = env(&[("GITHUB_ACTIVITY_LIMIT", "20")]);
+
+        // As quatro combinações: só o padrão, só o arquivo, arquivo + ambiente, as três
+        assert_eq!(Config::default().or(Config::default()).or(Config::default()).limit, None);
+        assert_eq!(Config::default().or(Config::default()).or(from_file.clone()).limit, Some(30));
+        assert_eq!(Config::default().or(from_env.clone()).or(from_file.clone()).limit, Some(20));
+        assert_eq!(cli_limit(10).or(from_env.clone()).or(from_file.clone()).limit, Some(10));
+
+        // A linha de comando vence o arquivo mesmo sem ambiente
+        assert_eq!(cli_limit(10).or(Config::default()).or(from_file).limit, Some(10));
+        // E o ambiente vence o padrão sem arquivo
+        assert_eq!(Config::default().or(from_env).or(Config::default()).limit, Some(20));
+    }
+
+    #[test]
+    fn test_precedence_is_per_key() {
+        // Cada chave é resolvida separadamente: uma camada pode dar o token e outra o limite
+        let merged = cli_limit(10)
+            .or(env(&[("GITHUB_TOKEN", "ghp_env")]))
+            .or(file("token = ghp_file\ndefault_user = torvalds\ncolor = false"));
+
+        assert_eq!(
+            merged,
+            Config {
+                limit: Some(10),
+                color: Some(false),
+                token: Some("ghp_env".to_string()),
+                api_url: None,
+                api_version: None,
+                default_user: Some("torvalds".to_string()),
+                ignored_actors: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_config_dir() {
+        let vars = |list: &'static [(&'static str, &'static str)]| {
+            move |name: &str| list.iter().find(|(key, _)| *key == name).map(|(_, value)| PathBuf::from(value))
+        };
+
+        assert_eq!(
+            config_dir(vars(&[("XDG_CONFIG_HOME", "/etc/xdg-me"), ("HOME", "/home/me")])),
+            Some(PathBuf::from("/etc/xdg-me"))
+        );
+        if cfg!(unix) {
+            assert_eq!(
+                config_dir(vars(&[("XDG_CONFIG_HOME", "relative"), ("HOME", "/home/me")])),
+                Some(PathBuf::from("/home/me/.config"))
+            );
+        }
+        assert_eq!(config_dir(vars(&[])), None);
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = std::env::temp_dir().join(format!("github-activity-config-{}", std::process::id()));
+        let path = dir.join(CONFIG_FILE);
+
+        // Arquivo inexistente = configuração vazia
+        assert_eq!(load(&path).unwrap(), Config::default());
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "limit = 10\nbogus\n").unwrap();
+        let message = load(&path).unwrap_err().to_string();
+        assert!(message.contains(&format!("{}: line 2:", path.display())), "{}", message);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
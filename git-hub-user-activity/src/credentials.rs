@@ -0,0 +1,175 @@
+//! Este módulo descobre de onde vem o token de acesso à API
+//! Além de --token, GITHUB_TOKEN e do arquivo de configuração (ver o módulo config),
+//! aproveitamos o login da ferramenta oficial `gh`, que guarda o token em
+//! `~/.config/gh/hosts.yml`:
+//!
+//! ```text
+//! github.com:
+//!     user: octocat
+//!     oauth_token: gho_xxxxxxxxxxxx
+//!     git_protocol: https
+//! ```
+//!
+//! Lemos só esse pedaço do YAML, linha por linha, sem um parser de YAML completo.
+//! O fallback nunca é um erro: sem arquivo, sem permissão ou sem token,
+//! as requisições simplesmente seguem sem autenticação
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{config_dir, unquote};
+
+// Host cujo token usamos: o `gh` guarda um bloco por host (github.com, Enterprise...)
+const GH_HOST: &str = "github.com";
+const GH_SUBDIR: &str = "gh";
+const GH_HOSTS_FILE: &str = "hosts.yml";
+
+/// De onde veio o token usado nas requisições
+/// Serve para --verbose dizer qual credencial foi usada sem exibir o token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    /// A flag --token
+    CommandLine,
+    /// A variável de ambiente GITHUB_TOKEN
+    Environment,
+    /// A chave `token` do arquivo de configuração
+    ConfigFile,
+    /// O hosts.yml da ferramenta `gh`, neste caminho
+    GhCli(PathBuf),
+}
+
+impl fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenSource::CommandLine => write!(f, "--token"),
+            TokenSource::Environment => write!(f, "the GITHUB_TOKEN environment variable"),
+            TokenSource::ConfigFile => write!(f, "the config file"),
+            TokenSource::GhCli(path) => write!(f, "the gh CLI login ({})", path.display()),
+        }
+    }
+}
+
+/// Extrai o `oauth_token` do bloco de github.com de um hosts.yml
+///
+/// Versões novas do `gh` repetem o token dentro de `users:`; preferimos o do
+/// próprio bloco do host (o usuário ativo) e só usamos um aninhado se ele faltar
+pub fn parse_gh_hosts(text: &str) -> Option<String> {
+    let mut in_host = false;
+    // (indentação, token) do token menos indentado encontrado no bloco
+    let mut best: Option<(usize, String)> = None;
+
+    for line in text.lines() {
+        let content = line.trim_start();
+        if content.is_empty() || content.starts_with('#') {
+            continue;
+        }
+
+        let indent = line.len() - content.len();
+        if indent == 0 {
+            // Uma linha sem indentação começa o bloco de outro host
+            in_host = unquote(content.trim_end().trim_end_matches(':')) == GH_HOST;
+            continue;
+        }
+        if !in_host {
+            continue;
+        }
+
+        if let Some(value) = content.strip_prefix("oauth_token:") {
+            let token = unquote(value.trim());
+            let shallower = !matches!(&best, Some((best_indent, _)) if *best_indent <= indent);
+            if !token.is_empty() && shallower {
+                best = Some((indent, token.to_string()));
+            }
+        }
+    }
+
+    best.map(|(_, token)| token)
+}
+
+/// Caminho do hosts.yml do `gh`: $GH_CONFIG_DIR, ou o diretório de configuração + "gh"
+pub fn gh_hosts_path() -> Option<PathBuf> {
+    gh_hosts_path_from(|name| std::env::var_os(name).map(PathBuf::from))
+}
+
+// Recebe a leitura de variáveis de ambiente como parâmetro para poder ser testado
+fn gh_hosts_path_from(var: impl Fn(&str) -> Option<PathBuf>) -> Option<PathBuf> {
+    match var("GH_CONFIG_DIR").filter(|dir| dir.is_absolute()) {
+        Some(dir) => Some(dir.join(GH_HOSTS_FILE)),
+        None => config_dir(var).map(|dir| dir.join(GH_SUBDIR).join(GH_HOSTS_FILE)),
+    }
+}
+
+/// Lê o token do `gh` em `path`; qualquer problema com o arquivo é só "sem token"
+pub fn load_gh_token(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().and_then(|text| parse_gh_hosts(&text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_classic_hosts_file() {
+        let text = "github.com:\n    user: octocat\n    oauth_token: gho_abc123\n    git_protocol: https\n";
+        assert_eq!(parse_gh_hosts(text), Some("gho_abc123".to_string()));
+    }
+
+    #[test]
+    fn test_parse_multi_account_hosts_file() {
+        // Formato do gh 2.40+: a conta ativa fica no bloco do host, as outras em users:
+        let text = "github.com:\n\
+                    \x20   users:\n\
+                    \x20       other:\n\
+                    \x20           oauth_token: gho_other\n\
+                    \x20       octocat:\n\
+                    \x20           oauth_token: gho_nested\n\
+                    \x20   git_protocol: https\n\
+                    \x20   user: octocat\n\
+                    \x20   oauth_token: \"gho_active\"\n";
+        assert_eq!(parse_gh_hosts(text), Some("gho_active".to_string()));
+
+        // Sem o token do host, vale o primeiro aninhado
+        let nested_only = "github.com:\n  users:\n    octocat:\n      oauth_token: gho_nested\n";
+        assert_eq!(parse_gh_hosts(nested_only), Some("gho_nested".to_string()));
+    }
+
+    #[test]
+    fn test_only_github_com_counts() {
+        let text = "ghe.example.com:\n    oauth_token: gho_enterprise\n\
+                    github.com:\n    user: octocat\n";
+        assert_eq!(parse_gh_hosts(text), None);
+
+        // Token guardado no keyring do sistema: o arquivo não tem oauth_token
+        assert_eq!(parse_gh_hosts("github.com:\n    oauth_token:\n"), None);
+        assert_eq!(parse_gh_hosts(""), None);
+    }
+
+    #[test]
+    fn test_gh_hosts_path() {
+        let vars = |list: &'static [(&'static str, &'static str)]| {
+            move |name: &str| list.iter().find(|(key, _)| *key == name).map(|(_, value)| PathBuf::from(value))
+        };
+
+        assert_eq!(
+            gh_hosts_path_from(vars(&[("GH_CONFIG_DIR", "/opt/gh"), ("HOME", "/home/me")])),
+            Some(PathBuf::from("/opt/gh/hosts.yml"))
+        );
+        assert_eq!(
+            gh_hosts_path_from(vars(&[("HOME", "/home/me")])),
+            Some(PathBuf::from("/home/me/.config/gh/hosts.yml"))
+        );
+        assert_eq!(gh_hosts_path_from(vars(&[])), None);
+    }
+
+    #[test]
+    fn test_missing_file_is_no_token() {
+        assert_eq!(load_gh_token(Path::new("/nonexistent/gh/hosts.yml")), None);
+    }
+
+    #[test]
+    fn test_source_display() {
+        let source = TokenSource::GhCli(PathBuf::from("/home/me/.config/gh/hosts.yml"));
+        assert_eq!(source.to_string(), "the gh CLI login (/home/me/.config/gh/hosts.yml)");
+    }
+}
@@ -0,0 +1,508 @@
+//! Este módulo lida com datas e horários sem depender de crates como 'chrono'
+//! A API do GitHub usa o formato RFC 3339 ("2024-05-01T12:30:00Z"),
+//! que é simples o suficiente para ser interpretado manualmente
+
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// CONCEITO: Newtype pattern
+// Uma struct com um único campo cria um tipo NOVO a partir de um existente
+// Timestamp é "só" um i64, mas o compilador não deixa confundi-lo com
+// uma contagem de eventos ou um id
+//
+// O valor é a quantidade de segundos desde 1970-01-01T00:00:00Z (época Unix)
+// Derivar Ord permite comparar e ordenar timestamps diretamente
+/// Um instante em UTC, com precisão de segundos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    /// Instante a partir dos segundos desde a época Unix
+    pub fn from_epoch_seconds(seconds: i64) -> Self {
+        Timestamp(seconds)
+    }
+
+    /// Instante atual segundo o relógio do sistema
+    /// Um relógio antes de 1970 (muito improvável) vira a própria época
+    pub fn now() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or(0);
+        Timestamp(seconds)
+    }
+
+    /// Segundos desde a época Unix (negativo antes de 1970)
+    pub fn epoch_seconds(self) -> i64 {
+        self.0
+    }
+
+    /// Apenas a data: "2024-05-01"
+    pub fn date_string(self) -> String {
+        let (year, month, day) = self.date();
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+
+    /// Só a hora, no fuso `offset_seconds` (ver parse_utc_offset): "14:32"
+    pub fn clock_time(self, offset_seconds: i64) -> String {
+        let secs_of_day = (self.0 + offset_seconds).rem_euclid(86_400);
+        format!("{:02}:{:02}", secs_of_day / 3600, (secs_of_day % 3600) / 60)
+    }
+
+    /// Interpreta um timestamp RFC 3339
+    /// Aceita "Z" ou deslocamento (+03:00 / -0300) e frações de segundo (ignoradas)
+    /// Retorna None para qualquer formato inesperado, em vez de "chutar" um valor
+    pub fn parse_rfc3339(text: &str) -> Option<Timestamp> {
+        let text = text.trim();
+
+        // A parte fixa "YYYY-MM-DDTHH:MM:SS" tem sempre 19 bytes ASCII
+        // get() devolve None (em vez de panic) se o texto for curto ou não-ASCII
+        let date = text.get(0..10)?;
+        let separator = text.get(10..11)?;
+        let time = text.get(11..19)?;
+        let mut rest = text.get(19..)?;
+
+        if !matches!(separator, "T" | "t" | " ") {
+            return None;
+        }
+
+        let year: i64 = parse_digits(date.get(0..4)?)?;
+        let month: u32 = parse_digits(date.get(5..7)?)?;
+        let day: u32 = parse_digits(date.get(8..10)?)?;
+        let hour: i64 = parse_digits(time.get(0..2)?)?;
+        let minute: i64 = parse_digits(time.get(3..5)?)?;
+        let second: i64 = parse_digits(time.get(6..8)?)?;
+
+        // get() também aqui: com texto não-ASCII, [4..5] poderia cortar um caractere ao meio
+        if date.get(4..5) != Some("-")
+            || date.get(7..8) != Some("-")
+            || time.get(2..3) != Some(":")
+            || time.get(5..6) != Some(":")
+        {
+            return None;
+        }
+
+        if !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+            || hour > 23
+            || minute > 59
+            || second > 60  // 60 = leap second
+        {
+            return None;
+        }
+
+        // Frações de segundo: ".123456"
+        if let Some(fraction) = rest.strip_prefix('.') {
+            let digits = fraction.chars().take_while(char::is_ascii_digit).count();
+            if digits == 0 {
+                return None;
+            }
+            rest = &fraction[digits..];
+        }
+
+        // Fuso horário: Z ou ±HH:MM (convertido para UTC)
+        let offset_seconds = match rest {
+            "Z" | "z" => 0,
+            _ => parse_utc_offset(rest)?,
+        };
+
+        let days = days_from_civil(year, month, day);
+        let seconds = days * 86_400 + hour * 3600 + minute * 60 + second - offset_seconds;
+
+        Some(Timestamp(seconds))
+    }
+
+    /// Interpreta uma data ("2024-05-01", o começo do dia em UTC)
+    /// ou um timestamp RFC 3339 completo, como parse_rfc3339
+    pub fn parse_date(text: &str) -> Option<Timestamp> {
+        let text = text.trim();
+        // Só a data: reaproveita a validação de parse_rfc3339 com a meia-noite
+        if text.len() == 10 {
+            return Timestamp::parse_rfc3339(&format!("{}T00:00:00Z", text));
+        }
+        Timestamp::parse_rfc3339(text)
+    }
+
+    /// (ano, mês, dia) em UTC
+    pub fn date(self) -> (i64, u32, u32) {
+        civil_from_days(self.0.div_euclid(86_400))
+    }
+
+    /// A semana ISO 8601 deste instante, no fuso `offset_seconds` (ver parse_utc_offset)
+    pub fn iso_week(self, offset_seconds: i64) -> IsoWeek {
+        IsoWeek::from_days((self.0 + offset_seconds).div_euclid(86_400))
+    }
+}
+
+// Formata como RFC 3339 em UTC: "2024-05-01T12:30:00Z"
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (year, month, day) = self.date();
+        let secs_of_day = self.0.rem_euclid(86_400);
+
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year,
+            month,
+            day,
+            secs_of_day / 3600,
+            (secs_of_day % 3600) / 60,
+            secs_of_day % 60
+        )
+    }
+}
+
+/// Interpreta um deslocamento em relação ao UTC, "+03:00" ou "-0300", em segundos
+/// É o formato do fim de um RFC 3339 e da saída de `date +%z`
+pub fn parse_utc_offset(text: &str) -> Option<i64> {
+    let sign = match text.get(0..1)? {
+        "+" => 1,
+        "-" => -1,
+        _ => return None,
+    };
+    let offset = text[1..].replace(':', "");
+    if offset.len() != 4 || !offset.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i64 = parse_digits(&offset[0..2])?;
+    let minutes: i64 = parse_digits(&offset[2..4])?;
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// O caminho inverso de parse_utc_offset, para mostrar ao usuário: "UTC", "UTC-03:00", "UTC+05:30"
+pub fn format_utc_offset(offset_seconds: i64) -> String {
+    if offset_seconds == 0 {
+        return "UTC".to_string();
+    }
+    let sign = if offset_seconds < 0 { '-' } else { '+' };
+    let minutes = offset_seconds.abs() / 60;
+    format!("UTC{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+}
+
+// CONCEITO: Semanas ISO 8601
+// A semana começa na segunda-feira, e a semana 1 é a que contém a primeira
+// quinta-feira do ano. Por isso os primeiros dias de janeiro podem ser da última
+// semana do ano anterior (2021-01-01 é 2020-W53), e os últimos de dezembro, da
+// semana 1 do ano seguinte (2024-12-30 é 2025-W01)
+// O truque: a quinta-feira de uma semana sempre cai no ano ISO dela
+/// Uma semana ISO 8601: "2024-W19"
+/// A ordem derivada (ano, depois semana) é a ordem do calendário
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IsoWeek {
+    /// O ano ISO, que nem sempre é o ano do calendário (ver acima)
+    pub year: i64,
+    /// De 1 a 52, ou 53 nos anos longos
+    pub week: u32,
+}
+
+impl IsoWeek {
+    /// A semana do dia `days` (dias desde 1970-01-01, como em days_from_civil)
+    pub fn from_days(days: i64) -> IsoWeek {
+        let thursday = days - i64::from(weekday(days)) + 4;
+        let (year, _, _) = civil_from_days(thursday);
+        let week = (thursday - days_from_civil(year, 1, 1)) / 7 + 1;
+        IsoWeek { year, week: week as u32 }
+    }
+
+    /// A segunda-feira que abre a semana, em dias desde 1970-01-01
+    pub fn monday(self) -> i64 {
+        // 4 de janeiro está sempre na semana 1
+        let january_4 = days_from_civil(self.year, 1, 4);
+        january_4 - i64::from(weekday(january_4)) + 1 + 7 * (i64::from(self.week) - 1)
+    }
+}
+
+// "2024-W19": a semana sempre com dois dígitos, para que o texto ordene como as datas
+impl fmt::Display for IsoWeek {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-W{:02}", self.year, self.week)
+    }
+}
+
+/// O dia da semana de `days` (dias desde 1970-01-01), como na ISO 8601:
+/// 1 = segunda-feira ... 7 = domingo
+pub fn weekday(days: i64) -> u32 {
+    // 1970-01-01 foi uma quinta-feira (4)
+    ((days + 3).rem_euclid(7) + 1) as u32
+}
+
+/// Quantas semanas o ano ISO `year` tem: 53 nos anos que começam numa quinta-feira
+/// (ou numa quarta, se forem bissextos), 52 nos outros
+pub fn weeks_in_year(year: i64) -> u32 {
+    // A semana de 28 de dezembro é sempre a última do ano
+    IsoWeek::from_days(days_from_civil(year, 12, 28)).week
+}
+
+// Converte uma sequência de dígitos ASCII em número
+// Diferente de str::parse, rejeita sinais ("+1") e strings vazias
+fn parse_digits<T: std::str::FromStr>(digits: &str) -> Option<T> {
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Converte (ano, mês, dia) em dias desde 1970-01-01
+/// Algoritmos "days_from_civil"/"civil_from_days" de Howard Hinnant:
+/// tratam o ano como começando em março, o que empurra o dia extra
+/// dos anos bissextos para o FIM do ano e simplifica as contas
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = i64::from(month);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146_097 + doe - 719_468
+}
+
+/// Converte dias desde 1970-01-01 em (ano, mês, dia)
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_utc() {
+        assert_eq!(
+            Timestamp::parse_rfc3339("1970-01-01T00:00:00Z"),
+            Some(Timestamp::from_epoch_seconds(0))
+        );
+        assert_eq!(
+            Timestamp::parse_rfc3339("2024-06-01T00:00:00Z"),
+            Some(Timestamp::from_epoch_seconds(1_717_200_000))
+        );
+    }
+
+    #[test]
+    fn test_parse_offsets_and_fractions() {
+        let utc = Timestamp::parse_rfc3339("2024-05-01T12:00:00Z").unwrap();
+        assert_eq!(Timestamp::parse_rfc3339("2024-05-01T09:00:00-03:00"), Some(utc));
+        assert_eq!(Timestamp::parse_rfc3339("2024-05-01T14:00:00+0200"), Some(utc));
+        assert_eq!(Timestamp::parse_rfc3339("2024-05-01T12:00:00.123456Z"), Some(utc));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        for text in [
+            "",
+            "yesterday",
+            "2024-05-01",
+            "2024-13-01T00:00:00Z",
+            "2023-02-29T00:00:00Z",
+            "2024-05-01T24:00:00Z",
+            "2024-05-01T12:00:00",
+            "2024-05-01T12:00:00.Z",
+            "2024-05-01T12:00:00+3",
+            "2024-05-01T12:00:00+1é1",
+            "2024/05/01T12:00:00Z",
+            "２０２４-05-01T12:00:00Z",
+        ] {
+            assert_eq!(Timestamp::parse_rfc3339(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_parse_multibyte_anywhere_is_rejected_without_panic() {
+        let valid = "2024-05-01T12:00:00+01:00";
+        // Troca cada posição por um caractere multi-byte
+        for (i, _) in valid.char_indices() {
+            for replacement in ["é", "修", "🦀"] {
+                let text = format!("{}{}{}", &valid[..i], replacement, &valid[i + 1..]);
+                assert_eq!(Timestamp::parse_rfc3339(&text), None, "{}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn test_leap_day() {
+        assert!(Timestamp::parse_rfc3339("2024-02-29T00:00:00Z").is_some());
+        assert!(Timestamp::parse_rfc3339("2000-02-29T00:00:00Z").is_some());
+        assert!(Timestamp::parse_rfc3339("1900-02-29T00:00:00Z").is_none());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for text in ["1970-01-01T00:00:00Z", "2000-02-29T23:59:59Z", "2024-12-31T08:05:03Z"] {
+            assert_eq!(Timestamp::parse_rfc3339(text).unwrap().to_string(), text);
+        }
+    }
+
+    #[test]
+    fn test_date_string() {
+        let timestamp = Timestamp::parse_rfc3339("2024-05-01T23:59:59Z").unwrap();
+        assert_eq!(timestamp.date_string(), "2024-05-01");
+    }
+
+    #[test]
+    fn test_civil_conversions_agree() {
+        for days in -1000..1000 {
+            let (year, month, day) = civil_from_days(days * 37);
+            assert_eq!(days_from_civil(year, month, day), days * 37);
+        }
+    }
+
+    #[test]
+    fn test_utc_offsets() {
+        assert_eq!(parse_utc_offset("-0300"), Some(-3 * 3600));
+        assert_eq!(parse_utc_offset("+05:30"), Some(5 * 3600 + 30 * 60));
+        assert_eq!(parse_utc_offset("0300"), None);
+        assert_eq!(parse_utc_offset("+3"), None);
+
+        assert_eq!(format_utc_offset(0), "UTC");
+        assert_eq!(format_utc_offset(-3 * 3600), "UTC-03:00");
+        assert_eq!(format_utc_offset(5 * 3600 + 30 * 60), "UTC+05:30");
+    }
+
+    #[test]
+    fn test_clock_time() {
+        let timestamp = Timestamp::parse_rfc3339("2024-05-01T17:32:59Z").unwrap();
+        assert_eq!(timestamp.clock_time(0), "17:32");
+        assert_eq!(timestamp.clock_time(-3 * 3600), "14:32");
+        assert_eq!(timestamp.clock_time(5 * 3600 + 30 * 60), "23:02");
+        // O fuso pode virar o dia, para os dois lados
+        assert_eq!(timestamp.clock_time(9 * 3600), "02:32");
+        assert_eq!(Timestamp::from_epoch_seconds(0).clock_time(-3600), "23:00");
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(Timestamp::parse_date("2024-05-01"), Timestamp::parse_rfc3339("2024-05-01T00:00:00Z"));
+        assert_eq!(Timestamp::parse_date("2024-05-01T12:30:00-03:00").unwrap().to_string(), "2024-05-01T15:30:00Z");
+        assert_eq!(Timestamp::parse_date("2024-02-30"), None);
+        assert_eq!(Timestamp::parse_date("yesterday"), None);
+    }
+
+    fn week_of(date: &str) -> String {
+        Timestamp::parse_date(date).unwrap().iso_week(0).to_string()
+    }
+
+    #[test]
+    fn test_weekday() {
+        assert_eq!(weekday(0), 4);
+        assert_eq!(weekday(days_from_civil(2024, 5, 6)), 1);
+        assert_eq!(weekday(days_from_civil(2024, 5, 12)), 7);
+        // Antes de 1970 (dias negativos): 1969-12-29 foi uma segunda-feira
+        assert_eq!(weekday(days_from_civil(1969, 12, 29)), 1);
+        assert_eq!(weekday(-1), 3);
+    }
+
+    #[test]
+    fn test_iso_week_in_the_middle_of_the_year() {
+        assert_eq!(week_of("2024-05-06"), "2024-W19");
+        assert_eq!(week_of("2024-05-12"), "2024-W19");
+        assert_eq!(week_of("2024-05-13"), "2024-W20");
+        // Dois dígitos na semana
+        assert_eq!(week_of("2024-02-29"), "2024-W09");
+    }
+
+    #[test]
+    fn test_iso_week_year_boundaries() {
+        let cases = [
+            // Janeiro ainda na última semana do ano anterior
+            ("2021-01-01", "2020-W53"),
+            ("2021-01-03", "2020-W53"),
+            ("2021-01-04", "2021-W01"),
+            ("2022-01-01", "2021-W52"),
+            ("2022-01-02", "2021-W52"),
+            ("2023-01-01", "2022-W52"),
+            ("2010-01-03", "2009-W53"),
+            ("2005-01-02", "2004-W53"),
+            // O ano começa numa quinta (ou antes): 1º de janeiro já é a semana 1
+            ("2026-01-01", "2026-W01"),
+            ("2020-01-01", "2020-W01"),
+            ("2024-01-01", "2024-W01"),
+            ("1970-01-01", "1970-W01"),
+            // Dezembro já na semana 1 do ano seguinte
+            ("2024-12-30", "2025-W01"),
+            ("2024-12-31", "2025-W01"),
+            ("2008-12-29", "2009-W01"),
+            ("2019-12-30", "2020-W01"),
+            ("2025-12-29", "2026-W01"),
+            // ... ou ainda na semana 52/53 do próprio ano
+            ("2024-12-29", "2024-W52"),
+            ("2020-12-31", "2020-W53"),
+            ("2026-12-31", "2026-W53"),
+            ("2027-01-03", "2026-W53"),
+            // Bissextos e antes de 1970
+            ("2000-02-29", "2000-W09"),
+            ("1969-12-31", "1970-W01"),
+            ("1969-12-28", "1969-W52"),
+        ];
+        for (date, week) in cases {
+            assert_eq!(week_of(date), week, "{}", date);
+        }
+    }
+
+    #[test]
+    fn test_iso_week_uses_the_offset() {
+        // Segunda-feira 01:00 UTC ainda é domingo em UTC-03:00
+        let timestamp = Timestamp::parse_rfc3339("2024-05-13T01:00:00Z").unwrap();
+        assert_eq!(timestamp.iso_week(0).to_string(), "2024-W20");
+        assert_eq!(timestamp.iso_week(-3 * 3600).to_string(), "2024-W19");
+        // E na virada do ano, o ano ISO também muda
+        let timestamp = Timestamp::parse_rfc3339("2024-12-29T22:00:00Z").unwrap();
+        assert_eq!(timestamp.iso_week(0).to_string(), "2024-W52");
+        assert_eq!(timestamp.iso_week(3 * 3600).to_string(), "2025-W01");
+    }
+
+    #[test]
+    fn test_weeks_in_year() {
+        // Anos longos: começam numa quinta, ou numa quarta se forem bissextos
+        for year in [2004, 2009, 2015, 2020, 2026, 2032] {
+            assert_eq!(weeks_in_year(year), 53, "{}", year);
+        }
+        for year in [2019, 2021, 2022, 2023, 2024, 2025, 2100] {
+            assert_eq!(weeks_in_year(year), 52, "{}", year);
+        }
+    }
+
+    #[test]
+    fn test_iso_week_monday_round_trip() {
+        assert_eq!(IsoWeek { year: 2020, week: 53 }.monday(), days_from_civil(2020, 12, 28));
+        assert_eq!(IsoWeek { year: 2025, week: 1 }.monday(), days_from_civil(2024, 12, 30));
+        // Todo dia de vários anos cai na semana que começa na sua segunda-feira
+        let start = days_from_civil(2019, 12, 1);
+        for days in start..start + 3 * 366 {
+            let week = IsoWeek::from_days(days);
+            assert!((0..7).contains(&(days - week.monday())), "{:?}", civil_from_days(days));
+            assert!(week.week >= 1 && week.week <= weeks_in_year(week.year), "{:?}", civil_from_days(days));
+        }
+    }
+
+    #[test]
+    fn test_iso_weeks_order_like_the_calendar() {
+        assert!(IsoWeek { year: 2020, week: 53 } < IsoWeek { year: 2021, week: 1 });
+        assert!(IsoWeek { year: 2024, week: 9 } < IsoWeek { year: 2024, week: 10 });
+    }
+}
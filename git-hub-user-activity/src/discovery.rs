@@ -0,0 +1,186 @@
+//! Este módulo descobre o username quando nenhum é informado
+//!
+//! A primeira resposta vence:
+//! 1. o dono do token, perguntando à API (`GET /user`), se houver um token
+//! 2. `git config github.user`
+//! 3. `git config user.name`
+//!
+//! Um username na linha de comando (ou o default_user da configuração) sempre vence:
+//! a descoberta só acontece sem nenhum. Valores do git que não são usernames válidos
+//! são pulados (user.name costuma ser o nome completo, "Linus Torvalds")
+//!
+//! Quem responde cada pergunta é um [`Lookups`]: os testes usam um que responde
+//! de uma tabela, sem git e sem rede
+
+use std::fmt;
+use std::process::{Command, Stdio};
+
+use github_activity::api::GitHubClient;
+use github_activity::logging;
+
+// As chaves do git, na ordem em que são tentadas
+const GIT_KEYS: [&str; 2] = ["github.user", "user.name"];
+
+// CONCEITO: Traits como ponto de extensão (como o Opener de browser.rs)
+// discover_username() só conhece as perguntas; o programa passa um SystemLookups
+pub trait Lookups {
+    // O login do dono do token; None sem token ou se a API não responder
+    fn authenticated_login(&mut self) -> Option<String>;
+    // O valor de `git config <key>`; None sem git ou sem a chave
+    fn git_config(&mut self, key: &str) -> Option<String>;
+}
+
+// De onde veio o username descoberto, para --verbose
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Token,
+    GitConfig(&'static str),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Token => write!(f, "the owner of the token (GET /user)"),
+            Source::GitConfig(key) => write!(f, "git config {}", key),
+        }
+    }
+}
+
+// O primeiro username encontrado e de onde veio (None se nenhuma origem responder)
+// `valid` decide se um valor do git pode ser usado como username
+pub fn discover_username(lookups: &mut impl Lookups, valid: impl Fn(&str) -> bool) -> Option<(String, Source)> {
+    if let Some(login) = lookups.authenticated_login() {
+        return Some((login, Source::Token));
+    }
+
+    for key in GIT_KEYS {
+        match lookups.git_config(key) {
+            Some(value) if valid(&value) => return Some((value, Source::GitConfig(key))),
+            Some(value) => logging::log(logging::Level::Info, || {
+                format!("ignoring git config {} ({:?}): not a valid username", key, value)
+            }),
+            None => {}
+        }
+    }
+
+    None
+}
+
+// As respostas de verdade: a API (só com um cliente que tenha token) e o programa `git`
+pub struct SystemLookups<'a> {
+    client: Option<&'a GitHubClient>,
+}
+
+impl<'a> SystemLookups<'a> {
+    // `client` é None quando não há token: sem ele, GET /user só responderia 401
+    pub fn new(client: Option<&'a GitHubClient>) -> SystemLookups<'a> {
+        SystemLookups { client }
+    }
+}
+
+impl Lookups for SystemLookups<'_> {
+    fn authenticated_login(&mut self) -> Option<String> {
+        let client = self.client?;
+        match client.fetch_authenticated_user() {
+            Ok(profile) => Some(profile.login),
+            Err(e) => {
+                // Um token inválido ou sem rede não impede as outras origens
+                logging::log(logging::Level::Info, || format!("could not ask GitHub who owns the token: {}", e));
+                None
+            }
+        }
+    }
+
+    fn git_config(&mut self, key: &str) -> Option<String> {
+        // --get devolve só o valor; sem a chave o git sai com código 1
+        let output = Command::new("git")
+            .args(["config", "--get", key])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let value = String::from_utf8(output.stdout).ok()?;
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Responde de uma tabela e anota as perguntas feitas
+    #[derive(Default)]
+    struct FakeLookups {
+        login: Option<String>,
+        git: Vec<(&'static str, &'static str)>,
+        asked: Vec<String>,
+    }
+
+    impl Lookups for FakeLookups {
+        fn authenticated_login(&mut self) -> Option<String> {
+            self.asked.push("GET /user".to_string());
+            self.login.clone()
+        }
+
+        fn git_config(&mut self, key: &str) -> Option<String> {
+            self.asked.push(key.to_string());
+            self.git.iter().find(|(name, _)| *name == key).map(|(_, value)| value.to_string())
+        }
+    }
+
+    fn valid(name: &str) -> bool {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    }
+
+    #[test]
+    fn test_token_owner_comes_first() {
+        let mut lookups = FakeLookups {
+            login: Some("octocat".to_string()),
+            git: vec![("github.user", "torvalds")],
+            ..Default::default()
+        };
+
+        assert_eq!(discover_username(&mut lookups, valid), Some(("octocat".to_string(), Source::Token)));
+        // Com a resposta da API, o git nem é consultado
+        assert_eq!(lookups.asked, vec!["GET /user"]);
+    }
+
+    #[test]
+    fn test_falls_back_to_git_config() {
+        let mut lookups = FakeLookups {
+            git: vec![("github.user", "torvalds"), ("user.name", "linus")],
+            ..Default::default()
+        };
+        assert_eq!(
+            discover_username(&mut lookups, valid),
+            Some(("torvalds".to_string(), Source::GitConfig("github.user")))
+        );
+
+        let mut lookups = FakeLookups { git: vec![("user.name", "linus")], ..Default::default() };
+        assert_eq!(
+            discover_username(&mut lookups, valid),
+            Some(("linus".to_string(), Source::GitConfig("user.name")))
+        );
+        assert_eq!(lookups.asked, vec!["GET /user", "github.user", "user.name"]);
+    }
+
+    #[test]
+    fn test_skips_names_that_are_not_usernames() {
+        let mut lookups = FakeLookups { git: vec![("user.name", "Linus Torvalds")], ..Default::default() };
+        assert_eq!(discover_username(&mut lookups, valid), None);
+
+        let mut lookups = FakeLookups::default();
+        assert_eq!(discover_username(&mut lookups, valid), None);
+    }
+
+    #[test]
+    fn test_source_display() {
+        assert_eq!(Source::GitConfig("github.user").to_string(), "git config github.user");
+        assert_eq!(Source::Token.to_string(), "the owner of the token (GET /user)");
+    }
+}
@@ -1,115 +1,926 @@
-// Este módulo é responsável por formatar e exibir eventos do GitHub
-// Demonstra pattern matching avançado e formatação de strings
+//! Este módulo é responsável por formatar e exibir eventos do GitHub
+//! Demonstra pattern matching avançado e formatação de strings
 
-use crate::models::{EventPayload, GitHubEvent};
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::time::Duration;
 
-// Formata um único evento em uma string legível
+use crate::api::RateLimitInfo;
+use crate::date::Timestamp;
+use crate::error::ActivityError;
+use crate::i18n::{self, Messages};
+use crate::json::{self, Json};
+use crate::models::{
+    ActivitySummary, Actor, CommitSummary, EventCategory, EventKind, EventPayload, GitHubEvent, Repository,
+    SummaryComparison, UserProfile,
+};
+use crate::parser::ParseWarning;
+use crate::stats::{DailyActivity, RepoActivity, WeekActivity};
+
+// Acima disso, "N days ago" deixa de ser útil e mostramos a data
+const RELATIVE_TIME_MAX_DAYS: i64 = 30;
+
+// Títulos de issues e PRs maiores que isso são cortados com "…"
+const TITLE_MAX_CHARS: usize = 60;
+
+// --commits: tamanho máximo da primeira linha de cada mensagem (o limite
+// tradicional do git) e quantos commits mostrar por push
+const COMMIT_TITLE_MAX_CHARS: usize = 72;
+const COMMITS_SHOWN_MAX: usize = 5;
+
+// --verbose: quanto do payload de um evento desconhecido mostrar no fim da linha
+const RAW_PAYLOAD_PREVIEW_CHARS: usize = 120;
+
+/// Marcador das primeiras atividades (--first-contributions)
+/// A frase diz "in this window" de propósito: só vemos os eventos recentes
+pub const FIRST_ACTIVITY_MARKER: &str = "✦ first activity here in this window";
+
+/// Eventos escondidos antes da listagem, contados no cabeçalho:
+/// "Found 5 events (2 duplicate events hidden, 12 bot events hidden)"
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HiddenEvents {
+    /// Estrelas e forks repetidos (ver stats::dedup_stars_and_forks)
+    pub duplicates: usize,
+    /// Eventos de bots e de --ignore-actor (ver stats::remove_actors)
+    pub bots: usize,
+}
+
+/// Opções que mudam a forma de cada linha da listagem
+// CONCEITO: #[derive(Default)]
+// Default cria o valor "vazio": false para bool, conjunto vazio para HashSet
+#[derive(Debug, Default)]
+pub struct DisplayOptions {
+    /// Prefixa cada linha com o login do autor (modo repositório,
+    /// onde cada evento pode ter sido feito por uma pessoa diferente)
+    pub show_actor: bool,
+    /// Índices dos eventos que recebem o FIRST_ACTIVITY_MARKER
+    pub first_activity: HashSet<usize>,
+    /// "Agora" usado para calcular "(2 hours ago)"
+    /// None desliga os horários (--no-time) - e deixa os testes determinísticos
+    pub now: Option<Timestamp>,
+    /// Com ou sem cores ANSI
+    pub style: Style,
+    /// Repositórios, issues e PRs viram links clicáveis (--hyperlinks, ver hyperlink())
+    pub hyperlinks: bool,
+    /// Um ícone de event_icon() na frente de cada evento (--emoji)
+    pub emoji: bool,
+    /// Numera os eventos a partir de 1 no lugar do "- " (--numbered, para usar com --open N)
+    pub numbered: bool,
+    /// Lista os commits de cada push abaixo da linha (--commits)
+    pub show_commits: bool,
+    /// Mostra o começo do payload dos eventos de tipo desconhecido (--verbose)
+    pub verbose: bool,
+    /// Formato escolhido pelo usuário (--template): substitui a linha inteira,
+    /// sem o "- " da lista, o ator ou o "(2 hours ago)"
+    pub template: Option<Template>,
+}
+
+/// Cores usadas na listagem, uma por "família" de evento
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    /// Pushes
+    Green,
+    /// Issues
+    Yellow,
+    /// Pull requests
+    Magenta,
+    /// Stars e forks
+    Cyan,
+    /// Comentários (texto esmaecido)
+    Dim,
+    /// Sem cor (cor padrão do terminal)
+    Default,
+}
+
+impl Color {
+    // Código ANSI SGR que liga a cor
+    fn code(self) -> &'static str {
+        match self {
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Magenta => "\x1b[35m",
+            Color::Cyan => "\x1b[36m",
+            Color::Dim => "\x1b[2m",
+            Color::Default => "",
+        }
+    }
+}
+
+/// Cor de cada tipo de evento: uma por categoria, e comentários apagados
+/// (um comentário importa menos que a issue ou o PR em que foi feito)
+pub fn event_color(payload: &EventPayload) -> Color {
+    if payload.is_comment() {
+        return Color::Dim;
+    }
+    match payload.category() {
+        EventCategory::Code => Color::Green,
+        EventCategory::Issues => Color::Yellow,
+        EventCategory::Review => Color::Magenta,
+        EventCategory::Social => Color::Cyan,
+        EventCategory::Release | EventCategory::Other => Color::Default,
+    }
+}
+
+/// Ícone de cada tipo de evento (--emoji): 📦 push, ⭐ star, 🔀 PR, 🐛 issue...
+// Sem braço "_": uma variante nova em EventPayload sem ícone não compila
+pub fn event_icon(payload: &EventPayload) -> &'static str {
+    match payload {
+        EventPayload::Push { .. } => "📦",
+        EventPayload::IssuesEvent { .. } => "🐛",
+        EventPayload::PullRequestEvent { .. } => "🔀",
+        EventPayload::PullRequestReview { .. } => "👀",
+        EventPayload::WatchEvent => "⭐",
+        EventPayload::ForkEvent { .. } => "🍴",
+        EventPayload::ReleaseEvent { .. } => "🏷️",
+        EventPayload::IssueCommentEvent
+        | EventPayload::PullRequestReviewCommentEvent
+        | EventPayload::CommitCommentEvent => "💬",
+        EventPayload::CreateEvent { .. } => "🌱",
+        EventPayload::DeleteEvent { .. } => "🗑️",
+        EventPayload::Member { .. } => "👥",
+        EventPayload::Public => "🔓",
+        EventPayload::Gollum { .. } => "📝",
+        EventPayload::Unknown { .. } => "📌",
+    }
+}
+
+/// Decide SE o texto será colorido
+/// Manter isso num tipo separado deixa format_event testável: os testes usam Plain
+/// e comparam texto puro, sem sequências de escape
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Style {
+    /// Texto puro, sem escapes
+    #[default]
+    Plain,
+    /// Cores e negrito com sequências ANSI
+    Ansi,
+}
+
+impl Style {
+    /// Regras para ligar as cores, em ordem:
+    ///   1. --no-color desliga
+    ///   2. a variável NO_COLOR (qualquer valor não vazio) desliga - ver no-color.org
+    ///   3. stdout precisa ser um terminal (em pipes e arquivos, nada de escapes)
+    ///
+    /// Recebe tudo por parâmetro para poder ser testada sem mexer no ambiente
+    pub fn detect(no_color_flag: bool, no_color_env: Option<&str>, stdout_is_tty: bool) -> Style {
+        let env_disables = no_color_env.is_some_and(|value| !value.is_empty());
+
+        if no_color_flag || env_disables || !stdout_is_tty {
+            Style::Plain
+        } else {
+            Style::Ansi
+        }
+    }
+
+    /// detect() com os valores reais do processo
+    pub fn from_environment(no_color_flag: bool) -> Style {
+        // CONCEITO: IsTerminal
+        // Trait da stdlib que diz se um handle (stdout, stderr...) é um terminal
+        use std::io::IsTerminal;
+
+        let no_color_env = std::env::var("NO_COLOR").ok();
+        Style::detect(no_color_flag, no_color_env.as_deref(), std::io::stdout().is_terminal())
+    }
+
+    /// Pinta o texto inteiro com a cor e depois reseta
+    pub fn paint(self, text: &str, color: Color) -> String {
+        match (self, color) {
+            (Style::Plain, _) | (_, Color::Default) => text.to_string(),
+            (Style::Ansi, color) => format!("{}{}\x1b[0m", color.code(), text),
+        }
+    }
+
+    /// Nome do repositório em negrito
+    /// O reset (\x1b[0m) desliga também a cor da linha, então religamos a cor em seguida
+    pub fn repo(self, name: &str, line_color: Color) -> String {
+        match self {
+            Style::Plain => name.to_string(),
+            Style::Ansi => format!("\x1b[1m{}\x1b[0m{}", name, line_color.code()),
+        }
+    }
+}
+
+/// Terminais que sabidamente entendem links OSC 8, pelo valor de TERM_PROGRAM
+/// Em outros terminais os links só aparecem com --hyperlinks
+pub const HYPERLINK_TERMINALS: &[&str] = &["iTerm.app", "WezTerm", "vscode", "ghostty"];
+
+/// Decide se a listagem terá links clicáveis, no mesmo espírito de Style::detect:
+/// stdout precisa ser um terminal e, além disso, o usuário pediu (--hyperlinks)
+/// ou o terminal está em HYPERLINK_TERMINALS
+pub fn detect_hyperlinks(flag: bool, term_program: Option<&str>, stdout_is_tty: bool) -> bool {
+    let known_terminal = term_program.is_some_and(|name| HYPERLINK_TERMINALS.contains(&name));
+    stdout_is_tty && (flag || known_terminal)
+}
+
+/// detect_hyperlinks() com os valores reais do processo
+pub fn hyperlinks_from_environment(flag: bool) -> bool {
+    use std::io::IsTerminal;
+
+    let term_program = std::env::var("TERM_PROGRAM").ok();
+    detect_hyperlinks(flag, term_program.as_deref(), std::io::stdout().is_terminal())
+}
+
+/// Transforma `text` em um link para `url` (quando `enabled`), com a sequência OSC 8:
+/// ESC ] 8 ; ; URL ESC \ texto ESC ] 8 ; ; ESC \
+///
+/// O terminal mostra só o texto, clicável; terminais que não conhecem a sequência
+/// a ignoram. Desligado, o texto volta intacto - pipes e testes veem texto puro
+// CONCEITO: Operating System Command (OSC)
+// Como os códigos de cor (ESC [ ... m), mas "ESC ]" abre um comando para o terminal
+// e "ESC \" (String Terminator) o fecha
+pub fn hyperlink(text: &str, url: &str, enabled: bool) -> String {
+    if enabled {
+        format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", sanitize_for_terminal(url), text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Formata um único evento em uma string legível
 // CONCEITO: Referências
 // &GitHubEvent significa que pegamos emprestado (borrow) o evento
 // Não tomamos posse (ownership), apenas lemos
 pub fn format_event(event: &GitHubEvent) -> String {
+    // A versão sem cores é a versão "estilizada" com o estilo Plain
+    format_event_styled(event, Style::Plain)
+}
+
+/// Mesma formatação, com cores ANSI conforme o tipo do evento
+/// e o nome do repositório em negrito (quando o estilo é Ansi)
+pub fn format_event_styled(event: &GitHubEvent, style: Style) -> String {
+    format_event_linked(event, style, false, i18n::messages())
+}
+
+// O corpo de format_event_styled; com `links`, o repositório e a issue/PR/fork são clicáveis
+fn format_event_linked(event: &GitHubEvent, style: Style, links: bool, messages: &dyn Messages) -> String {
+    describe_event(&terminal_safe(event), style, links, messages)
+}
+
+/// Uma cópia do evento com todo texto que veio da rede limpo por sanitize_for_terminal
+///
+/// A fronteira única entre os dados e o terminal: cada função pública que escreve
+/// eventos passa por aqui uma vez, e o resto do módulo usa os campos direto.
+/// Os padrões abaixo listam todos os campos (sem `..`): um campo de texto novo
+/// não compila até alguém decidir como limpá-lo
+fn terminal_safe(event: &GitHubEvent) -> GitHubEvent {
+    let clean = |text: &String| sanitize_for_terminal(text);
+    let clean_option = |text: &Option<String>| text.as_deref().map(sanitize_for_terminal);
+
+    let GitHubEvent { kind, id, repo_name, payload, created_at, actor } = event;
+    let kind = match kind {
+        EventKind::Other(name) => EventKind::Other(clean(name)),
+        known => known.clone(),
+    };
+    let payload = match payload {
+        EventPayload::Push { commit_count, distinct_count, push_count, branch, commits } => EventPayload::Push {
+            commit_count: *commit_count,
+            distinct_count: *distinct_count,
+            push_count: *push_count,
+            branch: clean_option(branch),
+            // Linha por linha: a quebra entre o título e o corpo continua lá para title()
+            commits: commits
+                .iter()
+                .map(|CommitSummary { sha, message }| CommitSummary {
+                    sha: clean(sha),
+                    message: message.lines().map(sanitize_for_terminal).collect::<Vec<_>>().join("\n"),
+                })
+                .collect(),
+        },
+        EventPayload::IssuesEvent { action, number, title } => {
+            EventPayload::IssuesEvent { action: clean(action), number: *number, title: clean_option(title) }
+        }
+        EventPayload::PullRequestEvent { action, number, title, merged } => EventPayload::PullRequestEvent {
+            action: clean(action),
+            number: *number,
+            title: clean_option(title),
+            merged: *merged,
+        },
+        EventPayload::WatchEvent => EventPayload::WatchEvent,
+        EventPayload::ForkEvent { forkee } => EventPayload::ForkEvent { forkee: clean_option(forkee) },
+        EventPayload::CreateEvent { ref_type, ref_name } => {
+            EventPayload::CreateEvent { ref_type: clean(ref_type), ref_name: clean_option(ref_name) }
+        }
+        EventPayload::DeleteEvent { ref_type, ref_name } => {
+            EventPayload::DeleteEvent { ref_type: clean(ref_type), ref_name: clean_option(ref_name) }
+        }
+        EventPayload::ReleaseEvent { action, tag, name, draft } => EventPayload::ReleaseEvent {
+            action: clean(action),
+            tag: clean_option(tag),
+            name: clean_option(name),
+            draft: *draft,
+        },
+        EventPayload::IssueCommentEvent => EventPayload::IssueCommentEvent,
+        EventPayload::PullRequestReviewCommentEvent => EventPayload::PullRequestReviewCommentEvent,
+        EventPayload::CommitCommentEvent => EventPayload::CommitCommentEvent,
+        EventPayload::PullRequestReview { action, state } => {
+            EventPayload::PullRequestReview { action: clean(action), state: clean(state) }
+        }
+        EventPayload::Member { action, member_login } => {
+            EventPayload::Member { action: clean(action), member_login: clean_option(member_login) }
+        }
+        EventPayload::Public => EventPayload::Public,
+        EventPayload::Gollum { pages_edited, first_page, first_page_action } => EventPayload::Gollum {
+            pages_edited: *pages_edited,
+            first_page: clean_option(first_page),
+            first_page_action: clean_option(first_page_action),
+        },
+        EventPayload::Unknown { raw_payload } => EventPayload::Unknown { raw_payload: clean_option(raw_payload) },
+    };
+
+    GitHubEvent {
+        kind,
+        id: *id,
+        repo_name: clean(repo_name),
+        payload,
+        created_at: *created_at,
+        actor: actor.as_ref().map(|Actor { login, id }| Actor { login: clean(login), id: *id }),
+    }
+}
+
+// A frase de um evento já limpo por terminal_safe
+// As frases vêm de `messages` (ver o módulo i18n); aqui só escolhemos qual frase e com que dados
+fn describe_event(event: &GitHubEvent, style: Style, links: bool, messages: &dyn Messages) -> String {
+    let color = event_color(&event.payload);
+    let repo = style.repo(&hyperlink(&event.repo_name, &event.repo_url(), links), color);
+    // O link da issue/PR cobre "issue #42"; o do fork, o repositório criado
+    let item = |text: &str| match event.item_url() {
+        Some(url) => hyperlink(text, &url, links),
+        None => text.to_string(),
+    };
+
     // CONCEITO: Pattern Matching Exaustivo
     // match em Rust DEVE cobrir todos os casos possíveis
     // O compilador garante que não esquecemos nenhuma variante
     // & em &event.payload porque estamos fazendo match em uma referência
-    match &event.payload {
+    let text = match &event.payload {
         // Para variantes com dados, usamos destructuring
         // commit_count é extraído do PayloadEvent::Push
-        EventPayload::Push { commit_count } => {
+        EventPayload::Push { commit_count, distinct_count, push_count, branch, .. } => {
+            // Contamos os commits NOVOS (distinct): um force-push ou uma branch
+            // re-enviada repetem commits que já existiam
             // CONCEITO: Dereferencing com *
-            // commit_count é &usize (referência), *commit_count é usize (valor)
-            // Precisamos do valor para comparar com 1
-            let plural = if *commit_count == 1 { "" } else { "s" };
+            // distinct_count é &usize (referência), *distinct_count é usize (valor)
+            // Nenhum commit novo, mas commits enviados: reescrita do histórico
+            let commits = if *distinct_count == 0 && *commit_count > 0 {
+                messages.force_push()
+            } else {
+                messages.commits(*distinct_count)
+            };
 
-            // CONCEITO: format! macro
-            // Similar ao printf em C ou str.format() em Python
-            format!(
-                "Pushed {} commit{} to {}",
-                commit_count, plural, event.repo_name
-            )
+            // "to main in user/repo" quando sabemos a branch
+            let mut text = messages.pushed(&commits, branch.as_deref(), &repo);
+
+            // Vários pushes agregados pelo --collapse
+            if *push_count > 1 {
+                text.push_str(&messages.across_pushes(*push_count));
+            }
+
+            text
         }
 
-        EventPayload::IssuesEvent { action } => {
-            // Capitaliza a primeira letra da action
-            let formatted_action = capitalize_first(action);
-            format!("{} an issue in {}", formatted_action, event.repo_name)
+        // "Opened issue #42 'Fix crash on startup' in user/repo"
+        EventPayload::IssuesEvent { action, number, title } => {
+            let label = number.map(|number| item(&messages.issue_label(number)));
+            messages.issue(&messages.action(action), label.as_deref(), &format_title(title.as_deref()), &repo)
         }
 
-        EventPayload::PullRequestEvent { action } => {
-            let formatted_action = capitalize_first(action);
-            format!(
-                "{} a pull request in {}",
-                formatted_action, event.repo_name
-            )
+        EventPayload::PullRequestEvent { action, number, title, merged } => {
+            // Merge chega como "closed" + merged: dizer "Closed" seria enganoso
+            let action = if *merged && action == "closed" { "merged" } else { action };
+            let label = number.map(|number| item(&messages.pull_request_label(number)));
+            messages.pull_request(&messages.action(action), label.as_deref(), &format_title(title.as_deref()), &repo)
         }
 
         // Variantes sem dados são simples
-        EventPayload::WatchEvent => {
-            format!("Starred {}", event.repo_name)
+        EventPayload::WatchEvent => messages.starred(&repo),
+
+        // "Forked torvalds/linux to myuser/linux"
+        EventPayload::ForkEvent { forkee } => {
+            let destination = forkee.as_deref().map(item);
+            messages.forked(&repo, destination.as_deref())
         }
 
-        EventPayload::ForkEvent => {
-            format!("Forked {}", event.repo_name)
+        // "Created branch 'feature-x' in user/repo"
+        // Sem o nome (ex: criação do próprio repositório): "Created a repository in user/repo"
+        EventPayload::CreateEvent { ref_type, ref_name } => {
+            messages.created_ref(ref_type, ref_name.as_deref(), &repo)
         }
 
-        EventPayload::CreateEvent { ref_type } => {
-            // Adiciona artigo apropriado (a/an)
-            let article = if ref_type == "branch" { "a" } else { "a" };
-            format!(
-                "Created {} {} in {}",
-                article, ref_type, event.repo_name
-            )
+        EventPayload::DeleteEvent { ref_type, ref_name } => {
+            messages.deleted_ref(ref_type, ref_name.as_deref(), &repo)
         }
 
-        EventPayload::DeleteEvent { ref_type } => {
-            let article = if ref_type == "branch" { "a" } else { "a" };
-            format!(
-                "Deleted {} {} in {}",
-                article, ref_type, event.repo_name
-            )
+        // "Published release v1.4.0 ('Spring cleaning') in user/repo"
+        // Rascunhos ainda não foram publicados, seja qual for a ação: "Drafted release ..."
+        EventPayload::ReleaseEvent { action, tag, name, draft } => {
+            let action = if *draft { "drafted" } else { action };
+
+            // O nome só acrescenta algo se existir e for diferente da tag
+            // (muitas releases usam a própria tag como nome)
+            let title = match (name.as_deref(), tag.as_deref()) {
+                (Some(name), Some(tag)) if !name.is_empty() && name != tag => format!(" ('{}')", name),
+                _ => String::new(),
+            };
+            messages.release(&messages.action(action), tag.as_deref(), &title, &repo)
+        }
+
+        EventPayload::IssueCommentEvent => messages.commented_on_issue(&repo),
+
+        EventPayload::PullRequestReviewCommentEvent => messages.commented_on_pull_request(&repo),
+
+        EventPayload::CommitCommentEvent => messages.commented_on_commit(&repo),
+
+        // "Approved a pull request in user/repo"
+        EventPayload::PullRequestReview { state, .. } => messages.reviewed(state, &repo),
+
+        // "Added 'octocat' as a collaborator to user/repo"
+        EventPayload::Member { action, member_login } => {
+            messages.member(action, member_login.as_deref(), &repo)
+        }
+
+        EventPayload::Public => messages.made_public(&repo),
+
+        // "Created wiki page 'Home' in user/repo" / "Edited 3 wiki pages in user/repo"
+        EventPayload::Gollum { pages_edited, first_page, first_page_action } => messages.wiki(
+            *pages_edited,
+            first_page.as_deref(),
+            first_page_action.as_deref() == Some("created"),
+            &repo,
+        ),
+
+        // Para eventos desconhecidos, mostra o tipo original
+        EventPayload::Unknown { .. } => messages.performed(event.kind.name(), &repo),
+    };
+
+    style.paint(&text, color)
+}
+
+/// Escreve uma lista de eventos em `out`, uma linha por evento
+// CONCEITO: Slices
+// &[GitHubEvent] é uma slice - uma referência a uma sequência de eventos
+// Pode ser um array, parte de um Vec, etc.
+pub fn display_events(out: &mut impl Write, events: &[GitHubEvent], options: &DisplayOptions) -> io::Result<()> {
+    write_event_lines(out, events, options, 0..events.len(), "")
+}
+
+// Escreve as linhas dos eventos cujos índices estão em `indices`
+// Os índices (e não os eventos) são passados para que first_activity,
+// que guarda posições na lista original, continue funcionando nos grupos
+//
+// CONCEITO: impl Trait em argumentos
+// `out: &mut impl Write` aceita stdout, um arquivo ou um Vec<u8> (nos testes)
+fn write_event_lines(
+    out: &mut impl Write,
+    events: &[GitHubEvent],
+    options: &DisplayOptions,
+    indices: impl IntoIterator<Item = usize>,
+    indent: &str,
+) -> io::Result<()> {
+    // Os números são alinhados à direita: " 9." e "10." terminam na mesma coluna
+    let number_width = events.len().to_string().len();
+
+    for index in indices {
+        let event = &terminal_safe(&events[index]);
+
+        if let Some(template) = &options.template {
+            writeln!(out, "{}{}", indent, template.render_safe(event, options.now))?;
+            continue;
         }
 
-        EventPayload::ReleaseEvent { action } => {
-            let formatted_action = capitalize_first(action);
-            format!("{} a release in {}", formatted_action, event.repo_name)
+        let mut line = event_line(event, options);
+        if options.emoji {
+            line = format!("{} {}", event_icon(&event.payload), line);
         }
 
-        EventPayload::IssueCommentEvent => {
-            format!("Commented on an issue in {}", event.repo_name)
+        // Sufixo "(2 hours ago)" quando temos o horário do evento
+        // CONCEITO: if let com tupla
+        // Só entra se AMBOS os Option forem Some
+        if let (Some(now), Some(created_at)) = (options.now, event.created_at) {
+            line.push_str(&format!(" ({})", format_relative_time(created_at, now)));
         }
 
-        EventPayload::PullRequestReviewCommentEvent => {
-            format!("Commented on a pull request in {}", event.repo_name)
+        // Com --verbose, o começo do payload que não soubemos interpretar, esmaecido
+        if options.verbose {
+            if let EventPayload::Unknown { raw_payload: Some(raw) } = &event.payload {
+                let preview = shorten(raw, RAW_PAYLOAD_PREVIEW_CHARS);
+                line.push_str(&format!(" {}", options.style.paint(&preview, Color::Dim)));
+            }
         }
 
-        EventPayload::CommitCommentEvent => {
-            format!("Commented on a commit in {}", event.repo_name)
+        // - é o marcador de lista; com --numbered, a posição do evento na lista inteira
+        // (nos grupos os números pulam, mas continuam valendo para o --open)
+        let marker = if options.numbered {
+            format!("{:>width$}.", index + 1, width = number_width)
+        } else {
+            "-".to_string()
+        };
+        if options.first_activity.contains(&index) {
+            writeln!(out, "{}{} {}  {}", indent, marker, line, FIRST_ACTIVITY_MARKER)?;
+        } else {
+            writeln!(out, "{}{} {}", indent, marker, line)?;
         }
 
-        EventPayload::Unknown => {
-            // Para eventos desconhecidos, mostra o tipo original
-            format!("Performed {} in {}", event.event_type, event.repo_name)
+        if options.show_commits {
+            write_commit_lines(out, event, indent, i18n::messages())?;
         }
     }
+
+    Ok(())
 }
 
-// Exibe uma lista de eventos no terminal
-// CONCEITO: Slices
-// &[GitHubEvent] é uma slice - uma referência a uma sequência de eventos
-// Pode ser um array, parte de um Vec, etc.
-pub fn display_events(events: &[GitHubEvent]) {
-    // CONCEITO: for..in loop
-    // Itera sobre cada elemento da slice
-    // 'event' é automaticamente uma referência (&GitHubEvent)
-    for event in events {
-        // println! imprime com nova linha no final
-        // - é o marcador de lista
-        println!("- {}", format_event(event));
+// Os commits de um push, um por linha, abaixo da linha do evento (--commits)
+// Mostra até COMMITS_SHOWN_MAX e resume o resto em "…and N more" (Messages::more_commits)
+// Outros tipos de evento não escrevem nada; `event` já passou por terminal_safe
+fn write_commit_lines(
+    out: &mut impl Write,
+    event: &GitHubEvent,
+    indent: &str,
+    messages: &dyn Messages,
+) -> io::Result<()> {
+    let (commit_count, commits) = match &event.payload {
+        EventPayload::Push { commit_count, commits, .. } => (*commit_count, commits),
+        _ => return Ok(()),
+    };
+
+    for commit in commits.iter().take(COMMITS_SHOWN_MAX) {
+        writeln!(out, "{}    {}", indent, shorten(commit.title(), COMMIT_TITLE_MAX_CHARS))?;
+    }
+
+    // commit_count pode passar do tamanho da lista (a API manda no máximo 20 commits)
+    let shown = commits.len().min(COMMITS_SHOWN_MAX);
+    let hidden = commit_count.max(commits.len()) - shown;
+    if shown > 0 && hidden > 0 {
+        writeln!(out, "{}    {}", indent, messages.more_commits(hidden))?;
+    }
+
+    Ok(())
+}
+
+/// Critério de agrupamento do --group-by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    /// Um grupo por repositório
+    Repo,
+    /// Um grupo por tipo de evento
+    Type,
+}
+
+impl GroupBy {
+    // Chave do grupo de um evento
+    fn key(self, event: &GitHubEvent) -> &str {
+        match self {
+            GroupBy::Repo => &event.repo_name,
+            GroupBy::Type => event.kind.name(),
+        }
+    }
+}
+
+/// Separa os índices dos eventos em grupos, na ordem em que cada chave aparece pela primeira vez
+/// Dentro de cada grupo a ordem original (cronológica) é mantida
+pub fn group_events(events: &[GitHubEvent], group_by: GroupBy) -> Vec<(&str, Vec<usize>)> {
+    let mut groups: Vec<(&str, Vec<usize>)> = Vec::new();
+
+    for (index, event) in events.iter().enumerate() {
+        let key = group_by.key(event);
+
+        // Busca linear: o número de grupos é pequeno (no máximo algumas dezenas)
+        // e um Vec preserva a ordem de aparição, o que um HashMap não faria
+        match groups.iter_mut().find(|(group_key, _)| *group_key == key) {
+            Some((_, indices)) => indices.push(index),
+            None => groups.push((key, vec![index])),
+        }
+    }
+
+    groups
+}
+
+/// Exibe os eventos agrupados: um título por grupo com a contagem e os eventos indentados
+///
+///   torvalds/linux (2 events)
+///     - Pushed 1 commit to torvalds/linux
+///     - Starred torvalds/linux
+pub fn display_events_grouped(
+    out: &mut impl Write,
+    events: &[GitHubEvent],
+    options: &DisplayOptions,
+    group_by: GroupBy,
+) -> io::Result<()> {
+    // CONCEITO: enumerate() + destructuring de tupla
+    for (position, (key, indices)) in group_events(events, group_by).into_iter().enumerate() {
+        // Linha em branco entre grupos (mas não antes do primeiro)
+        if position > 0 {
+            writeln!(out)?;
+        }
+
+        let plural = if indices.len() == 1 { "" } else { "s" };
+        writeln!(out, "{} ({} event{})", sanitize_for_terminal(key), indices.len(), plural)?;
+        write_event_lines(out, events, options, indices, "  ")?;
+    }
+
+    Ok(())
+}
+
+/// Exibe só as contagens (--count-only), para scripts: o total em uma linha ("12\n")
+/// ou, com --group-by, uma linha "chave<TAB>contagem" por grupo
+/// Nada mais é escrito; sem eventos, o total é "0" e os grupos não têm nenhuma linha
+pub fn display_counts(out: &mut impl Write, events: &[GitHubEvent], group_by: Option<GroupBy>) -> io::Result<()> {
+    match group_by {
+        Some(group_by) => {
+            for (key, indices) in group_events(events, group_by) {
+                writeln!(out, "{}\t{}", sanitize_for_terminal(key), indices.len())?;
+            }
+            Ok(())
+        }
+        None => writeln!(out, "{}", events.len()),
+    }
+}
+
+/// Formata o evento, opcionalmente com o ator na frente: "octocat: Starred user/repo"
+pub fn format_event_line(event: &GitHubEvent, options: &DisplayOptions) -> String {
+    event_line(&terminal_safe(event), options)
+}
+
+// format_event_line para um evento que já passou por terminal_safe
+fn event_line(event: &GitHubEvent, options: &DisplayOptions) -> String {
+    let text = describe_event(event, options.style, options.hyperlinks, i18n::messages());
+
+    // CONCEITO: Match guard
+    // Só entra no braço se o padrão casar E a condição (if) for verdadeira
+    match &event.actor {
+        Some(actor) if options.show_actor => format!("{}: {}", actor.login, text),
+        _ => text,
+    }
+}
+
+/// Um formato de linha escrito pelo usuário (--template), já interpretado:
+/// "{date} {type} {repo}" vira [campo date, texto " ", campo type, texto " ", campo repo]
+///
+/// Campos: {type}, {repo}, {owner}, {name}, {date}, {relative_date}, {detail} (a linha
+/// de format_event), {commits} e {action}. Os dois últimos ficam vazios nos eventos que
+/// não os têm. "{{" e "}}" escrevem chaves literais
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<TemplatePart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    Text(String),
+    Field(Placeholder),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Type,
+    Repo,
+    Owner,
+    Name,
+    Date,
+    RelativeDate,
+    Detail,
+    Commits,
+    Action,
+}
+
+// Os nomes aceitos entre chaves, na ordem em que a mensagem de erro os lista
+const PLACEHOLDERS: &[(&str, Placeholder)] = &[
+    ("type", Placeholder::Type),
+    ("repo", Placeholder::Repo),
+    ("owner", Placeholder::Owner),
+    ("name", Placeholder::Name),
+    ("date", Placeholder::Date),
+    ("relative_date", Placeholder::RelativeDate),
+    ("detail", Placeholder::Detail),
+    ("commits", Placeholder::Commits),
+    ("action", Placeholder::Action),
+];
+
+impl Template {
+    /// Interpreta o texto do template
+    /// O erro já vem no formato das mensagens de flag: "--template <erro>"
+    pub fn parse(text: &str) -> Result<Template, String> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        // CONCEITO: Peekable
+        // peek() olha o próximo caractere sem consumi-lo: é o que separa "{{" de "{type}"
+        let mut chars = text.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    // CONCEITO: by_ref
+                    // Empresta o iterator para take_while sem consumi-lo: o laço continua depois
+                    let mut closed = false;
+                    let name: String = chars
+                        .by_ref()
+                        .take_while(|&c| {
+                            closed = c == '}';
+                            !closed
+                        })
+                        .collect();
+                    if !closed {
+                        return Err("has a '{' without a closing '}' (use '{{' for a literal brace)".to_string());
+                    }
+
+                    let placeholder = match PLACEHOLDERS.iter().find(|(known, _)| *known == name) {
+                        Some((_, placeholder)) => *placeholder,
+                        None => {
+                            let known: Vec<String> =
+                                PLACEHOLDERS.iter().map(|(known, _)| format!("{{{}}}", known)).collect();
+                            return Err(format!(
+                                "has an unknown placeholder '{{{}}}' (expected one of {})",
+                                name,
+                                known.join(", ")
+                            ));
+                        }
+                    };
+
+                    if !literal.is_empty() {
+                        parts.push(TemplatePart::Text(std::mem::take(&mut literal)));
+                    }
+                    parts.push(TemplatePart::Field(placeholder));
+                }
+                '}' => {
+                    return Err("has a '}' without an opening '{' (use '}}' for a literal brace)".to_string());
+                }
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Text(literal));
+        }
+        Ok(Template { parts })
+    }
+
+    /// Preenche o template com os dados do evento
+    /// `now` é o "agora" de {relative_date}; None (--no-time) deixa o campo vazio
+    pub fn render(&self, event: &GitHubEvent, now: Option<Timestamp>) -> String {
+        self.render_safe(&terminal_safe(event), now)
+    }
+
+    // render para um evento que já passou por terminal_safe
+    fn render_safe(&self, event: &GitHubEvent, now: Option<Timestamp>) -> String {
+        let mut line = String::new();
+        for part in &self.parts {
+            match part {
+                TemplatePart::Text(text) => line.push_str(text),
+                TemplatePart::Field(placeholder) => line.push_str(&template_field(*placeholder, event, now)),
+            }
+        }
+        line
+    }
+}
+
+// O valor de um campo do template para um evento ("" quando o evento não o tem)
+fn template_field(placeholder: Placeholder, event: &GitHubEvent, now: Option<Timestamp>) -> String {
+    // "torvalds/linux" -> ("torvalds", "linux"); sem owner, tudo é o nome
+    let (owner, name) = event.owner_and_name().unwrap_or(("", &event.repo_name));
+
+    match placeholder {
+        Placeholder::Type => event.kind.name().to_string(),
+        Placeholder::Repo => event.repo_name.clone(),
+        Placeholder::Owner => owner.to_string(),
+        Placeholder::Name => name.to_string(),
+        Placeholder::Date => event.created_at.map(|created_at| created_at.to_string()).unwrap_or_default(),
+        Placeholder::RelativeDate => match (now, event.created_at) {
+            (Some(now), Some(created_at)) => format_relative_time(created_at, now),
+            _ => String::new(),
+        },
+        Placeholder::Detail => describe_event(event, Style::Plain, false, i18n::messages()),
+        // Os commits novos, os mesmos que a listagem conta
+        Placeholder::Commits => match &event.payload {
+            EventPayload::Push { distinct_count, .. } => distinct_count.to_string(),
+            _ => String::new(),
+        },
+        // A action como a API manda ("opened", "closed"...)
+        Placeholder::Action => event.payload.action().unwrap_or_default().to_string(),
+    }
+}
+
+/// Descreve há quanto tempo o evento aconteceu: "2 hours ago"
+/// Eventos com mais de 30 dias mostram a data ISO ("2024-03-01")
+pub fn format_relative_time(created_at: Timestamp, now: Timestamp) -> String {
+    let delta = now.epoch_seconds() - created_at.epoch_seconds();
+
+    if delta > RELATIVE_TIME_MAX_DAYS * 86_400 {
+        return created_at.date_string();
+    }
+
+    humanize_duration(delta)
+}
+
+/// Converte uma diferença em segundos em texto: "just now", "5 minutes ago"...
+/// Deltas negativos acontecem quando o relógio local está atrasado em relação ao
+/// GitHub (clock skew): o evento parece estar "no futuro", então dizemos "just now"
+pub fn humanize_duration(seconds: i64) -> String {
+    // O plural (e a ordem: "2 hours ago", "há 2 horas") depende do idioma
+    i18n::messages().time_ago(seconds)
+}
+
+/// Limpa um texto que veio da rede (nome de repositório, título, mensagem de commit...)
+/// antes de ele chegar ao terminal
+///
+/// Um título com "\x1b[2J" apagaria a tela, e um com "\x1b]8;;" criaria um link falso:
+/// sequências ESC [ (CSI) e ESC ] (OSC) somem inteiras, e os outros caracteres de
+/// controle C0 e C1 (e o DEL) somem sozinhos. Quebras de linha e tabs viram um espaço,
+/// para que um evento continue ocupando uma linha só
+pub fn sanitize_for_terminal(text: &str) -> String {
+    if !text.chars().any(char::is_control) {
+        return text.to_string();
+    }
+
+    let mut clean = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            // Várias seguidas ("\r\n") também viram um espaço só
+            '\n' | '\r' | '\t' => {
+                while chars.next_if(|next| matches!(next, '\n' | '\r' | '\t')).is_some() {}
+                clean.push(' ');
+            }
+            '\x1b' if chars.next_if_eq(&'[').is_some() => skip_csi(&mut chars),
+            '\x1b' if chars.next_if_eq(&']').is_some() => skip_osc(&mut chars),
+            // As versões de um caractere só (C1) das mesmas sequências
+            '\u{9b}' => skip_csi(&mut chars),
+            '\u{9d}' => skip_osc(&mut chars),
+            c if c.is_control() => {}
+            c => clean.push(c),
+        }
+    }
+    clean
+}
+
+// O resto de uma sequência CSI: parâmetros ("31;1") e a letra final ("m")
+fn skip_csi(chars: &mut std::iter::Peekable<std::str::Chars>) {
+    while chars.next_if(|c| ('\x20'..='\x3f').contains(c)).is_some() {}
+    chars.next_if(|c| ('\x40'..='\x7e').contains(c));
+}
+
+// O resto de uma sequência OSC: tudo até BEL, ESC \ ou ST (U+009C)
+fn skip_osc(chars: &mut std::iter::Peekable<std::str::Chars>) {
+    while let Some(c) = chars.next() {
+        match c {
+            '\x07' | '\u{9c}' => return,
+            '\x1b' => {
+                chars.next_if_eq(&'\\');
+                return;
+            }
+            _ => {}
+        }
+    }
+}
+
+// Título de issue/PR entre aspas, precedido de espaço: " 'Fix crash on startup'"
+// Títulos longos são cortados em TITLE_MAX_CHARS caracteres (contando a reticência)
+// Sem título, não acrescenta nada
+fn format_title(title: Option<&str>) -> String {
+    match title {
+        Some(title) => format!(" '{}'", shorten(title, TITLE_MAX_CHARS)),
+        None => String::new(),
+    }
+}
+
+// Corta o texto em `max` caracteres, contando a reticência "…"
+// Com max 0 não cabe nem a reticência: o resultado é vazio
+fn shorten(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+
+    let short: String = text.chars().take(max - 1).collect();
+    // trim_end evita um espaço solto antes da reticência ("Fix the …")
+    format!("{}…", short.trim_end())
+}
+
+// O verbo de uma revisão de PR, a partir do estado dela (review.state)
+// Estados desconhecidos (ex: "dismissed") caem no genérico "Reviewed"
+pub(crate) fn review_verb(state: &str) -> &'static str {
+    // A comparação ignora maiúsculas: a API GraphQL usa "APPROVED"
+    match state.to_ascii_lowercase().as_str() {
+        "approved" => "Approved",
+        "changes_requested" => "Requested changes on",
+        "commented" => "Commented on",
+        _ => "Reviewed",
     }
 }
 
 // Função auxiliar para capitalizar a primeira letra de uma string
-fn capitalize_first(s: &str) -> String {
+// Só a primeira letra muda, e só quando a maiúscula dela é UM caractere: "ße" continua
+// "ße" (e não "SSe", que pareceria uma sigla), e o resto da string nunca é tocado
+pub(crate) fn capitalize_first(s: &str) -> String {
     // CONCEITO: Iterator chains
     // Rust permite encadear operações em iterators de forma elegante
 
@@ -121,76 +932,2661 @@ fn capitalize_first(s: &str) -> String {
         None => String::new(),  // String vazia
         Some(first) => {
             // CONCEITO: to_uppercase() pode retornar múltiplos chars
-            // (ex: em alemão, ß -> SS)
+            // (ex: em alemão, ß -> SS; o "ŉ" vira "ʼN")
+            // Nesses casos a letra fica como está
+            let upper = first.to_uppercase();
+            if upper.len() != 1 {
+                return s.to_string();
+            }
             // collect::<String>() junta os caracteres
 
             // CONCEITO: chain()
             // Une dois iterators
             // first.to_uppercase() + chars (resto da string)
-            first
-                .to_uppercase()
+            upper
                 .chain(chars)  // Adiciona o resto dos caracteres
                 .collect()     // Coleta tudo em uma String
         }
     }
 }
 
-// Exibe mensagem quando não há eventos
-pub fn display_no_events(username: &str) {
-    println!("No recent activity found for user '{}'", username);
-    println!("This could mean:");
-    println!("  - The user has no public activity in the last 90 days");
-    println!("  - The user doesn't exist");
-    println!("  - The user has made their activity private");
+/// Avisa (em stderr, no main) sobre eventos pulados pelo parser
+/// Sem --verbose, só a contagem; com --verbose, o erro e o trecho de JSON de cada um
+///
+///   warning: skipped 2 unparseable events (run with --verbose for details)
+pub fn display_parse_warnings(
+    out: &mut impl Write,
+    warnings: &[ParseWarning],
+    verbose: bool,
+) -> io::Result<()> {
+    if warnings.is_empty() {
+        return Ok(());
+    }
+
+    let count = warnings.len();
+    let plural = if count == 1 { "" } else { "s" };
+
+    if !verbose {
+        return writeln!(
+            out,
+            "warning: skipped {} unparseable event{} (run with --verbose for details)",
+            count, plural
+        );
+    }
+
+    writeln!(out, "warning: skipped {} unparseable event{}:", count, plural)?;
+    for warning in warnings {
+        match &warning.error {
+            // A mensagem e o trecho do JSON em linhas separadas
+            ActivityError::ParseError { message, snippet, .. } => {
+                // O trecho é o JSON cru da resposta: pode trazer qualquer byte
+                writeln!(out, "  event #{}: {}", warning.index, sanitize_for_terminal(message))?;
+                if !snippet.is_empty() {
+                    writeln!(out, "    near: {}", sanitize_for_terminal(snippet))?;
+                }
+            }
+            other => writeln!(out, "  event #{}: {}", warning.index, sanitize_for_terminal(&other.to_string()))?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Exibe mensagem quando não há eventos
+/// Um usuário inexistente não chega aqui: vira ActivityError::UserNotFound
+pub fn display_no_events(out: &mut impl Write, username: &str) -> io::Result<()> {
+    writeln!(out, "{}", i18n::messages().no_user_events(username))
 }
 
-// Exibe cabeçalho antes da lista de eventos
-pub fn display_header(username: &str, event_count: usize) {
-    println!("\nRecent activity for '{}':", username);
-    println!("Found {} event{}\n", event_count, if event_count == 1 { "" } else { "s" });
+/// Exibe mensagem quando um repositório não tem eventos
+pub fn display_no_repo_events(out: &mut impl Write, full_name: &str) -> io::Result<()> {
+    writeln!(out, "{}", i18n::messages().no_repo_events(full_name))
 }
 
-// TESTES
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::models::EventPayload;
+/// Mensagem de --from-file quando o arquivo traz uma lista vazia
+pub fn display_no_file_events(out: &mut impl Write, source: &str) -> io::Result<()> {
+    writeln!(out, "{}", i18n::messages().no_file_events(source))
+}
 
-    #[test]
-    fn test_capitalize_first() {
-        assert_eq!(capitalize_first("hello"), "Hello");
-        assert_eq!(capitalize_first("world"), "World");
-        assert_eq!(capitalize_first(""), "");
-        assert_eq!(capitalize_first("a"), "A");
+/// Mensagem de --new quando nada aconteceu desde a última execução
+/// `since` é o horário do último evento já visto, se conhecido
+pub fn display_no_new_events(out: &mut impl Write, target: &str, since: Option<Timestamp>) -> io::Result<()> {
+    let since = since.map(|since| since.to_string());
+    writeln!(out, "{}", i18n::messages().no_new_events(target, since.as_deref()))
+}
+
+/// Exibe cabeçalho antes da lista de eventos
+/// Com o perfil do usuário (sem --no-profile), a primeira linha ganha o nome e os números,
+/// e empresa e localização (se houver) vêm logo abaixo:
+///
+///   Recent activity for torvalds (Linus Torvalds) — 180k followers, 4 public repos:
+///     Linux Foundation · Portland, OR
+///
+/// Com `cached_age` (eventos do cache, com --max-age), a contagem ganha a idade deles:
+/// "Found 30 events (cached, 2m old)"
+pub fn display_header(
+    out: &mut impl Write,
+    username: &str,
+    profile: Option<&UserProfile>,
+    event_count: usize,
+    hidden: HiddenEvents,
+    cached_age: Option<Duration>,
+) -> io::Result<()> {
+    match profile {
+        Some(profile) => {
+            writeln!(out, "\n{}", i18n::messages().user_header(&format_profile(profile)))?;
+            // CONCEITO: Iterator::flatten em Options
+            // Pula os None e desembrulha os Some
+            let details: Vec<String> = [&profile.company, &profile.location]
+                .into_iter()
+                .flatten()
+                .map(|detail| sanitize_for_terminal(detail))
+                .collect();
+            if !details.is_empty() {
+                writeln!(out, "  {}", details.join(" · "))?;
+            }
+        }
+        None => writeln!(out, "\n{}", i18n::messages().user_header(&format!("'{}'", username)))?,
     }
+    write_event_count(out, event_count, hidden, cached_age)
+}
 
-    #[test]
-    fn test_format_push_event_single() {
-        let event = GitHubEvent::new(
-            "PushEvent".to_string(),
-            "user/repo".to_string(),
-            EventPayload::Push { commit_count: 1 },
-        );
-        assert_eq!(format_event(&event), "Pushed 1 commit to user/repo");
+/// Resumo de um perfil em uma linha: "torvalds (Linus Torvalds) — 180k followers, 4 public repos"
+/// Sem nome de exibição, fica só o login
+/// Login e nome vêm da API: passam por sanitize_for_terminal
+pub fn format_profile(profile: &UserProfile) -> String {
+    let name = match &profile.name {
+        Some(name) if !name.is_empty() && *name != profile.login => format!(" ({})", sanitize_for_terminal(name)),
+        _ => String::new(),
+    };
+
+    let counts = i18n::messages().profile_counts(
+        &compact_count(profile.followers),
+        profile.followers,
+        &compact_count(profile.public_repos),
+        profile.public_repos,
+    );
+    format!("{}{} — {}", sanitize_for_terminal(&profile.login), name, counts)
+}
+
+// Contagens grandes no estilo do GitHub: 950, 1.2k, 180k, 3.4M
+// Corta (não arredonda) as casas: 1999 vira "1.9k", nunca exagera o número
+fn compact_count(count: u64) -> String {
+    let (unit, suffix) = match count {
+        0..=999 => return count.to_string(),
+        1_000..=999_999 => (1_000, "k"),
+        _ => (1_000_000, "M"),
+    };
+
+    let whole = count / unit;
+    let tenth = count % unit * 10 / unit;
+    // Uma casa decimal só enquanto o número é pequeno ("1.2k", mas "12k")
+    if whole < 10 && tenth > 0 {
+        format!("{}.{}{}", whole, tenth, suffix)
+    } else {
+        format!("{}{}", whole, suffix)
     }
+}
 
-    #[test]
-    fn test_format_push_event_multiple() {
-        let event = GitHubEvent::new(
-            "PushEvent".to_string(),
-            "user/repo".to_string(),
-            EventPayload::Push { commit_count: 3 },
-        );
-        assert_eq!(format_event(&event), "Pushed 3 commits to user/repo");
+/// Um número inteiro com separador de milhar: 12345 vira "12,345"
+/// Diferente de compact_count, nada se perde: é o número exato, só mais fácil de ler
+pub fn format_thousands(count: u64) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        // Uma vírgula antes de cada grupo de três dígitos contados a partir do fim
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
     }
+    formatted
+}
 
-    #[test]
-    fn test_format_watch_event() {
-        let event = GitHubEvent::new(
-            "WatchEvent".to_string(),
-            "torvalds/linux".to_string(),
-            EventPayload::WatchEvent,
-        );
-        assert_eq!(format_event(&event), "Starred torvalds/linux");
+/// Uma linha de --starred: "- owner/repo (★ 12,345) — description"
+/// Sem descrição, a linha termina nas estrelas
+pub fn format_starred_repository(repository: &Repository) -> String {
+    let stars = format!(
+        "- {} (★ {})",
+        sanitize_for_terminal(&repository.full_name),
+        format_thousands(repository.stargazers_count)
+    );
+    match repository.description.as_deref().map(sanitize_for_terminal) {
+        Some(description) if !description.trim().is_empty() => format!("{} — {}", stars, description.trim()),
+        _ => stars,
+    }
+}
+
+/// Cabeçalho de --starred: "Repositories starred by 'torvalds':" e a contagem
+pub fn display_starred_header(out: &mut impl Write, username: &str, count: usize) -> io::Result<()> {
+    let messages = i18n::messages();
+    writeln!(out, "\n{}", messages.starred_header(username))?;
+    writeln!(out, "{}\n", messages.found_repositories(count))
+}
+
+/// Mensagem de --starred quando o usuário não deu estrela em nada
+pub fn display_no_starred(out: &mut impl Write, username: &str) -> io::Result<()> {
+    writeln!(out, "{}", i18n::messages().no_starred(username))
+}
+
+/// Cabeçalho de --list-repos: "Public repositories of 'torvalds':" e a contagem
+pub fn display_repos_header(out: &mut impl Write, username: &str, count: usize) -> io::Result<()> {
+    let messages = i18n::messages();
+    writeln!(out, "\n{}", messages.repos_header(username))?;
+    writeln!(out, "{}\n", messages.found_repositories(count))
+}
+
+/// Mensagem de --list-repos quando não há repositórios (ou só forks, com --no-forks)
+pub fn display_no_repos(out: &mut impl Write, username: &str) -> io::Result<()> {
+    writeln!(out, "{}", i18n::messages().no_repos(username))
+}
+
+// Títulos das colunas de --list-repos
+const REPOS_HEADER: [&str; 5] = ["NAME", "STARS", "LANGUAGE", "UPDATED", "DESCRIPTION"];
+
+/// A tabela de --list-repos, com as colunas alinhadas:
+///
+/// ```text
+///   NAME                 STARS  LANGUAGE  UPDATED     DESCRIPTION
+///   linux              171,234  C         2024-05-20  Linux kernel source tree
+///   subsurface (fork)       12  C++       2023-11-02
+/// ```
+///
+/// As estrelas ficam à direita (alinhadas pelas unidades); linguagem e data que
+/// faltam viram "-". A descrição é a última coluna, sem largura fixa
+pub fn display_repositories(out: &mut impl Write, repositories: &[Repository]) -> io::Result<()> {
+    let rows: Vec<[String; 5]> = repositories.iter().map(repository_cells).collect();
+    let mut widths = REPOS_HEADER.map(display_width);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(display_width(cell));
+        }
+    }
+
+    // Como write_table_row, pela largura na tela: um nome com acento não desalinha nada
+    let line = |cells: [&str; 5]| {
+        let mut line = String::from("  ");
+        for (column, (cell, width)) in cells.iter().zip(widths).enumerate() {
+            let padding = " ".repeat(width.saturating_sub(display_width(cell)));
+            match column {
+                1 => line.push_str(&format!("{}{}  ", padding, cell)),
+                4 => line.push_str(cell),
+                _ => line.push_str(&format!("{}{}  ", cell, padding)),
+            }
+        }
+        line.trim_end().to_string()
+    };
+    writeln!(out, "{}", line(REPOS_HEADER))?;
+    for row in &rows {
+        writeln!(out, "{}", line(row.each_ref().map(String::as_str)))?;
+    }
+    Ok(())
+}
+
+// As células de um repositório; forks ganham " (fork)" depois do nome
+fn repository_cells(repository: &Repository) -> [String; 5] {
+    let name = sanitize_for_terminal(&repository.name);
+    let name = if repository.fork { format!("{} (fork)", name) } else { name };
+    [
+        name,
+        format_thousands(repository.stargazers_count),
+        repository.language.as_deref().map_or_else(|| "-".to_string(), sanitize_for_terminal),
+        repository.updated_at.map_or_else(|| "-".to_string(), Timestamp::date_string),
+        sanitize_for_terminal(repository.description.as_deref().unwrap_or("")).trim().to_string(),
+    ]
+}
+
+/// Cabeçalho do modo repositório
+pub fn display_repo_header(
+    out: &mut impl Write,
+    full_name: &str,
+    event_count: usize,
+    hidden: HiddenEvents,
+) -> io::Result<()> {
+    writeln!(out, "\n{}", i18n::messages().repo_header(full_name))?;
+    write_event_count(out, event_count, hidden, None)
+}
+
+/// Cabeçalho de --from-file quando nenhum username foi informado
+pub fn display_file_header(
+    out: &mut impl Write,
+    source: &str,
+    event_count: usize,
+    hidden: HiddenEvents,
+) -> io::Result<()> {
+    writeln!(out, "\n{}", i18n::messages().file_header(source))?;
+    write_event_count(out, event_count, hidden, None)
+}
+
+/// Rodapé com o limite de requisições: "API rate limit: 54/60 remaining, resets at 14:32"
+/// A hora do reset vai para o fuso `utc_offset` (segundos, ver date::parse_utc_offset);
+/// sem os headers X-RateLimit-*, o limite aparece como "unknown"
+pub fn format_rate_limit(info: Option<&RateLimitInfo>, utc_offset: i64, messages: &dyn Messages) -> String {
+    let resets_at = info.and_then(|info| info.reset_at).map(|reset_at| reset_at.clock_time(utc_offset));
+    messages.rate_limit_footer(info.map(|info| (info.remaining, info.limit)), resets_at.as_deref())
+}
+
+// Última linha de todos os cabeçalhos, seguida de uma linha em branco
+// Os avisos entre parênteses só aparecem se algo foi escondido
+fn write_event_count(
+    out: &mut impl Write,
+    event_count: usize,
+    hidden: HiddenEvents,
+    cached_age: Option<Duration>,
+) -> io::Result<()> {
+    let messages = i18n::messages();
+    let mut notes = Vec::new();
+    if hidden.duplicates > 0 {
+        notes.push(messages.duplicates_hidden(hidden.duplicates));
+    }
+    if hidden.bots > 0 {
+        notes.push(messages.bots_hidden(hidden.bots));
+    }
+
+    let mut line = messages.found_events(event_count);
+    if !notes.is_empty() {
+        line.push_str(&format!(" ({})", notes.join(", ")));
+    }
+    // A idade tem vírgula própria: vai em parênteses separados
+    if let Some(age) = cached_age {
+        line.push_str(&format!(" ({})", messages.served_from_cache(&format_age(age))));
+    }
+    writeln!(out, "{}\n", line)
+}
+
+/// Idade curta, na maior unidade que couber: "45s", "2m", "3h", "2d"
+pub fn format_age(age: Duration) -> String {
+    let seconds = age.as_secs();
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m", seconds / 60),
+        3600..=86_399 => format!("{}h", seconds / 3600),
+        _ => format!("{}d", seconds / 86_400),
+    }
+}
+
+/// Exibe o resumo (--summary) em texto
+///
+///   Summary for 'torvalds':
+///     30 events from 2024-05-01 to 2024-06-01
+///     12 pushes, 3 PRs opened, 5 stars
+///
+///   Most active repositories:
+///     1. torvalds/linux (20 events)
+pub fn display_summary(
+    out: &mut impl Write,
+    target: &str,
+    summary: &ActivitySummary,
+    daily: &DailyActivity,
+) -> io::Result<()> {
+    writeln!(out, "\nSummary for '{}':", target)?;
+
+    let plural = if summary.total_events == 1 { "" } else { "s" };
+    match (summary.first_event, summary.last_event) {
+        (Some(first), Some(last)) => writeln!(
+            out,
+            "  {} event{} from {} to {}",
+            summary.total_events,
+            plural,
+            first.date_string(),
+            last.date_string()
+        )?,
+        _ => writeln!(out, "  {} event{}", summary.total_events, plural)?,
+    }
+
+    // "12 pushes, 3 PRs opened, 5 stars"
+    let breakdown: Vec<String> = summary
+        .by_type
+        .iter()
+        .map(|entry| format!("{} {}", entry.count, sanitize_for_terminal(entry.label())))
+        .collect();
+    if !breakdown.is_empty() {
+        writeln!(out, "  {}", breakdown.join(", "))?;
+    }
+
+    // "Current streak: 3 days, longest: 5 days" e o dia mais movimentado
+    if let (Some((day, count)), Some(average)) = (&daily.busiest_day, daily.average_per_day()) {
+        let days = |count: usize| if count == 1 { "1 day".to_string() } else { format!("{} days", count) };
+        writeln!(
+            out,
+            "  Current streak: {}, longest: {}",
+            days(daily.current_streak),
+            days(daily.longest_streak)
+        )?;
+        let plural = if *count == 1 { "" } else { "s" };
+        writeln!(
+            out,
+            "  Busiest day: {} ({} event{}), {:.1} events per day on average",
+            day, count, plural, average
+        )?;
+    }
+
+    if !summary.top_repos.is_empty() {
+        writeln!(out, "\nMost active repositories:")?;
+        for (position, (repo, count)) in summary.top_repos.iter().enumerate() {
+            let plural = if *count == 1 { "" } else { "s" };
+            writeln!(out, "  {}. {} ({} event{})", position + 1, sanitize_for_terminal(repo), count, plural)?;
+        }
+    }
+
+    // Rodapé: tipos que o parser ainda não conhece
+    // "3 events of unrecognized types (SponsorshipEvent, DiscussionEvent)"
+    if summary.unrecognized_events > 0 {
+        let (events, types) = if summary.unrecognized_events == 1 {
+            ("event", "an unrecognized type")
+        } else {
+            ("events", "unrecognized types")
+        };
+        writeln!(
+            out,
+            "\n{} {} of {} ({})",
+            summary.unrecognized_events,
+            events,
+            types,
+            sanitize_for_terminal(&summary.unrecognized_types.join(", "))
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Largura do terminal, para decidir o layout de --compare
+/// Vem de COLUMNS (exportada por muitos shells); sem ela, os 80 caracteres tradicionais
+pub fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|&columns| columns > 0)
+        .unwrap_or(DEFAULT_TERMINAL_WIDTH)
+}
+
+const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+/// Exibe a comparação de --compare: totais, contagem por tipo e o dia mais movimentado
+/// em duas colunas, seguidos dos repositórios em comum
+/// Se as colunas não cabem em `width` caracteres, cada usuário ganha sua própria seção
+///
+/// ```text
+/// Comparing 'alice' and 'bob':
+///
+///                        alice             bob
+///   events                    2               1
+///   stars                     2               0
+///   forks                     0               1
+///   busiest day  2024-06-01 (2)  2024-05-30 (1)
+///
+/// Shared repositories (1):
+///   a/two
+/// ```
+pub fn display_comparison(
+    out: &mut impl Write,
+    names: (&str, &str),
+    comparison: &SummaryComparison,
+    width: usize,
+) -> io::Result<()> {
+    let (left_name, right_name) = names;
+    writeln!(out, "\nComparing '{}' and '{}':\n", left_name, right_name)?;
+
+    // (rótulo, valor à esquerda, valor à direita)
+    let busiest = |summary: &ActivitySummary| match &summary.busiest_day {
+        Some((day, count)) => format!("{} ({})", day, count),
+        None => "-".to_string(),
+    };
+    let mut rows = vec![(
+        "events".to_string(),
+        comparison.left.total_events.to_string(),
+        comparison.right.total_events.to_string(),
+    )];
+    for row in &comparison.by_type {
+        // Um tipo desconhecido chega com o nome que a API mandou
+        rows.push((sanitize_for_terminal(&row.label), row.left.to_string(), row.right.to_string()));
+    }
+    rows.push(("busiest day".to_string(), busiest(&comparison.left), busiest(&comparison.right)));
+
+    // CONCEITO: chars().count()
+    // Largura em caracteres, não em bytes (um login não tem acentos, mas um rótulo poderia ter)
+    let label_width = rows.iter().map(|(label, _, _)| label.chars().count()).max().unwrap_or(0);
+    let column_width = rows
+        .iter()
+        .flat_map(|(_, left, right)| [left.chars().count(), right.chars().count()])
+        .chain([left_name.chars().count(), right_name.chars().count()])
+        .max()
+        .unwrap_or(0);
+
+    if 2 + label_width + 2 * (2 + column_width) <= width {
+        writeln!(
+            out,
+            "  {:label_width$}  {:>column_width$}  {:>column_width$}",
+            "",
+            left_name,
+            right_name,
+        )?;
+        for (label, left, right) in &rows {
+            writeln!(out, "  {:label_width$}  {:>column_width$}  {:>column_width$}", label, left, right)?;
+        }
+    } else {
+        // Terminal estreito: uma seção por usuário, uma informação por linha
+        for (position, name) in [left_name, right_name].into_iter().enumerate() {
+            if position > 0 {
+                writeln!(out)?;
+            }
+            writeln!(out, "{}:", name)?;
+            for (label, left, right) in &rows {
+                writeln!(out, "  {}: {}", label, if position == 0 { left } else { right })?;
+            }
+        }
+    }
+
+    if comparison.shared_repos.is_empty() {
+        writeln!(out, "\nNo shared repositories")?;
+    } else {
+        writeln!(out, "\nShared repositories ({}):", comparison.shared_repos.len())?;
+        for repo in &comparison.shared_repos {
+            writeln!(out, "  {}", sanitize_for_terminal(repo))?;
+        }
+    }
+
+    Ok(())
+}
+
+// Blocos de 1/8 a 8/8 de caractere: a barra cresce de oitavo em oitavo
+const BAR_BLOCKS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+/// Histograma de --histogram: uma linha por dia, com a data, a contagem e uma barra
+/// proporcional; a maior barra ocupa o que sobra de `width` colunas
+///
+/// ```text
+/// Events per day for 'torvalds' (UTC):
+///   2024-06-01  3 ████████████████████████████████████████████████████████████████
+///   2024-06-02  0
+///   2024-06-03  1 █████████████████████▎
+/// ```
+pub fn display_histogram(
+    out: &mut impl Write,
+    title: &str,
+    days: &[(String, usize)],
+    width: usize,
+) -> io::Result<()> {
+    writeln!(out, "\nEvents per day for {}:", title)?;
+    if days.is_empty() {
+        return writeln!(out, "  (no events with a date)");
+    }
+
+    let max = days.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    let count_width = max.to_string().len();
+    // "  2024-06-01  " + contagem + " " antes da barra
+    let bar_width = width.saturating_sub(2 + 10 + 2 + count_width + 1).max(1);
+
+    for (day, count) in days {
+        write!(out, "  {}  {:>count_width$}", day, count)?;
+        let bar = bar(*count, max, bar_width);
+        if !bar.is_empty() {
+            write!(out, " {}", bar)?;
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+// Barra de `count` em relação a `max`, com no máximo `width` caracteres
+// Qualquer dia com eventos ganha pelo menos 1/8 de caractere, para não sumir
+fn bar(count: usize, max: usize, width: usize) -> String {
+    if count == 0 || max == 0 {
+        return String::new();
+    }
+
+    let eighths = (count * width * 8 / max).max(1);
+    let mut bar = "█".repeat(eighths / 8);
+    let remainder = eighths % 8;
+    if remainder > 0 {
+        bar.push(BAR_BLOCKS[remainder - 1]);
+    }
+    bar
+}
+
+/// Largura máxima da coluna REPO de --format table (sem --wide)
+pub const TABLE_REPO_MAX_WIDTH: usize = 40;
+/// Largura máxima da coluna DETAIL de --format table (sem --wide)
+pub const TABLE_DETAIL_MAX_WIDTH: usize = 60;
+
+/// Os eventos como uma tabela alinhada (--format table), com cabeçalho e separador:
+///
+/// ```text
+/// TYPE        REPO            DETAIL                              WHEN
+/// ----------  --------------  ----------------------------------  -----------
+/// PushEvent   torvalds/linux  Pushed 2 commits to torvalds/linux  2 hours ago
+/// WatchEvent  rust-lang/rust  Starred rust-lang/rust              1 day ago
+/// ```
+///
+/// Cada coluna tem a largura do maior valor; REPO e DETAIL param em
+/// TABLE_REPO_MAX_WIDTH e TABLE_DETAIL_MAX_WIDTH e o que passar é cortado com "…".
+/// Com `wide` (--wide) nada é cortado. A tabela não tem cores: os códigos ANSI
+/// atrapalhariam o alinhamento (e a tabela costuma ir para arquivos e planilhas)
+///
+/// Com `options.emoji`, o ícone do evento vem numa primeira coluna, sem título;
+/// com `options.numbered`, o número do evento vem antes de tudo, na coluna "#"
+pub fn display_table(
+    out: &mut impl Write,
+    events: &[GitHubEvent],
+    options: &DisplayOptions,
+    wide: bool,
+) -> io::Result<()> {
+    let mut header = vec!["TYPE", "REPO", "DETAIL", "WHEN"];
+    let mut caps = vec![None, Some(TABLE_REPO_MAX_WIDTH), Some(TABLE_DETAIL_MAX_WIDTH), None];
+    if options.emoji {
+        header.insert(0, "");
+        caps.insert(0, None);
+    }
+    if options.numbered {
+        header.insert(0, "#");
+        caps.insert(0, None);
+    }
+
+    let rows: Vec<Vec<String>> = events
+        .iter()
+        .map(|event| {
+            let event = &terminal_safe(event);
+            let detail = describe_event(event, Style::Plain, false, i18n::messages());
+            let detail = match (&event.actor, options.show_actor) {
+                (Some(actor), true) => format!("{}: {}", actor.login, detail),
+                _ => detail,
+            };
+            // Sem o "agora" (--no-time), a data absoluta
+            let when = match (options.now, event.created_at) {
+                (Some(now), Some(created_at)) => format_relative_time(created_at, now),
+                (None, Some(created_at)) => created_at.date_string(),
+                (_, None) => String::new(),
+            };
+            let mut row = vec![event.kind.name().to_string(), event.repo_name.clone(), detail, when];
+            if options.emoji {
+                row.insert(0, event_icon(&event.payload).to_string());
+            }
+            row
+        })
+        .enumerate()
+        .map(|(index, mut row)| {
+            if options.numbered {
+                row.insert(0, (index + 1).to_string());
+            }
+            row
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = header.iter().map(|title| display_width(title)).collect();
+    for row in &rows {
+        for (column, cell) in row.iter().enumerate() {
+            widths[column] = widths[column].max(display_width(cell));
+        }
+    }
+    if !wide {
+        for (width, cap) in widths.iter_mut().zip(&caps) {
+            if let Some(cap) = cap {
+                *width = (*width).min(*cap);
+            }
+        }
+    }
+
+    let header: Vec<String> = header.iter().map(|title| title.to_string()).collect();
+    let separator: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+    write_table_row(out, &header, &widths)?;
+    write_table_row(out, &separator, &widths)?;
+    for row in &rows {
+        let cells: Vec<String> = if wide {
+            row.clone()
+        } else {
+            row.iter().zip(&widths).map(|(cell, &width)| truncate_to_width(cell, width)).collect()
+        };
+        write_table_row(out, &cells, &widths)?;
+    }
+
+    Ok(())
+}
+
+// Uma linha da tabela: cada célula completada com espaços até a largura da coluna
+// (pela largura na tela, não pelos bytes), duas colunas de espaço entre elas
+// A última não recebe espaços no fim
+fn write_table_row(out: &mut impl Write, cells: &[String], widths: &[usize]) -> io::Result<()> {
+    let mut line = String::new();
+    for (column, (cell, width)) in cells.iter().zip(widths).enumerate() {
+        line.push_str(cell);
+        if column + 1 < cells.len() {
+            line.push_str(&" ".repeat(width.saturating_sub(display_width(cell)) + 2));
+        }
+    }
+    writeln!(out, "{}", line.trim_end())
+}
+
+/// Quantas colunas o texto ocupa no terminal
+///
+/// Não é o número de bytes (é == 2 bytes) nem de chars: ideogramas CJK e emoji
+/// ocupam DUAS colunas, e marcas combinantes (acentos soltos) ou o zero-width joiner, nenhuma
+pub fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+// Largura de um caractere, pelas faixas principais do East Asian Width (Unicode UAX #11)
+// Uma tabela completa seria enorme; estas faixas cobrem CJK, Hangul, formas de largura
+// cheia e os blocos de emoji, que são o que aparece em nomes de repositórios e títulos
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        // Controles, marcas combinantes, zero-width space/joiner e seletores de variação
+        0x00..=0x1F | 0x7F..=0x9F | 0x0300..=0x036F | 0x200B..=0x200F | 0xFE00..=0xFE0F => 0,
+        0x1100..=0x115F
+        | 0x2B50
+        | 0x2B55
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F680..=0x1F6FF
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+// Corta o texto para caber em `max` colunas, contando a reticência "…" (uma coluna)
+// Como shorten(), mas pela largura na tela: um ideograma nunca fica pela metade,
+// e com max 0 o resultado é vazio
+fn truncate_to_width(text: &str, max: usize) -> String {
+    if display_width(text) <= max {
+        return text.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+
+    let mut short = String::new();
+    let mut width = 0;
+    for c in text.chars() {
+        let char_width = char_width(c);
+        if width + char_width > max.saturating_sub(1) {
+            break;
+        }
+        short.push(c);
+        width += char_width;
+    }
+    format!("{}…", short.trim_end())
+}
+
+/// O resumo como um objeto JSON (--summary --json), em uma linha:
+/// {"total_events":3,"by_type":{"pushes":2,"stars":1},"top_repos":[{"repo":"a/b","events":3}],
+///  "first_event":"2024-05-01T00:00:00Z","last_event":"2024-06-01T00:00:00Z",
+///  "current_streak":2,"longest_streak":4,"busiest_day":{"date":"2024-05-03","events":2},
+///  "average_events_per_day":0.09}
+///
+/// Sem eventos, busiest_day e average_events_per_day são null
+/// As chaves de by_type usam sempre o plural, para que scripts não dependam da contagem
+pub fn format_summary_json(summary: &ActivitySummary, daily: &DailyActivity) -> String {
+    let mut json = Json::from(summary);
+    json.push("current_streak", daily.current_streak.into());
+    json.push("longest_streak", daily.longest_streak.into());
+    json.push(
+        "busiest_day",
+        match &daily.busiest_day {
+            Some((day, count)) => Json::object([("date", day.as_str().into()), ("events", (*count).into())]),
+            None => Json::Null,
+        },
+    );
+    json.push("average_events_per_day", daily.average_per_day().map_or(Json::Null, |average| Json::decimal(average, 2)));
+    json.to_string()
+}
+
+// A parte do resumo que não depende dos dias (total, tipos, repositórios e período);
+// format_summary_json acrescenta as sequências e o dia mais movimentado
+impl From<&ActivitySummary> for Json {
+    fn from(summary: &ActivitySummary) -> Json {
+        let by_type = summary.by_type.iter().map(|entry| (entry.plural.as_str(), entry.count.into()));
+        let top_repos = summary
+            .top_repos
+            .iter()
+            .map(|(repo, count)| Json::object([("repo", repo.as_str().into()), ("events", (*count).into())]));
+
+        Json::object([
+            ("total_events", summary.total_events.into()),
+            ("by_type", Json::object(by_type)),
+            ("top_repos", Json::array(top_repos)),
+            // null quando o período é desconhecido
+            ("first_event", summary.first_event.map(|timestamp| timestamp.to_string()).into()),
+            ("last_event", summary.last_event.map(|timestamp| timestamp.to_string()).into()),
+        ])
+    }
+}
+
+/// Lista os repositórios de --repos-only, com a contagem alinhada à direita
+///
+///   Repositories for 'torvalds' (2):
+///     12  torvalds/linux (latest: PushEvent)
+///      1  torvalds/subsurface (latest: WatchEvent)
+pub fn display_repo_activity(
+    out: &mut impl Write,
+    target: &str,
+    repos: &[RepoActivity],
+) -> io::Result<()> {
+    writeln!(out, "\nRepositories for '{}' ({}):", target, repos.len())?;
+
+    // Largura do maior número, para que os nomes fiquem alinhados
+    let width = repos.iter().map(|repo| repo.events.to_string().len()).max().unwrap_or(1);
+    for repo in repos {
+        writeln!(
+            out,
+            "  {:>width$}  {} (latest: {})",
+            repo.events,
+            sanitize_for_terminal(&repo.repo_name),
+            sanitize_for_terminal(repo.latest_kind.name()),
+            width = width
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Os repositórios de --repos-only como um array JSON, em uma linha, na mesma ordem do texto:
+/// [{"repo":"torvalds/linux","events":12,"latest":"PushEvent"}]
+pub fn format_repo_activity_json(repos: &[RepoActivity]) -> String {
+    let items = repos.iter().map(|repo| {
+        Json::object([
+            ("repo", repo.repo_name.as_str().into()),
+            ("events", repo.events.into()),
+            ("latest", repo.latest_kind.name().into()),
+        ])
+    });
+    Json::array(items).to_string()
+}
+
+// As colunas de --by-week, nas três saídas
+const WEEK_HEADER: [&str; 6] = ["WEEK", "TOTAL", "PUSHES", "PRS", "ISSUES", "OTHER"];
+
+fn week_cells(week: &WeekActivity) -> [String; 6] {
+    [
+        week.week.to_string(),
+        week.total.to_string(),
+        week.pushes.to_string(),
+        week.pull_requests.to_string(),
+        week.issues.to_string(),
+        week.other.to_string(),
+    ]
+}
+
+/// As semanas de --by-week como uma tabela, na ordem recebida:
+///
+/// ```text
+/// Events per week for 'torvalds' (UTC-03:00):
+///   WEEK      TOTAL  PUSHES  PRS  ISSUES  OTHER
+///   2024-W20     12       9    2       0      1
+/// ```
+pub fn display_weeks(out: &mut impl Write, title: &str, weeks: &[WeekActivity]) -> io::Result<()> {
+    writeln!(out, "\nEvents per week for {}:", title)?;
+    if weeks.is_empty() {
+        return writeln!(out, "  (no events with a date)");
+    }
+
+    let rows: Vec<[String; 6]> = weeks.iter().map(week_cells).collect();
+    let mut widths = WEEK_HEADER.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    // A semana à esquerda, os números à direita (alinhados pelas unidades)
+    let line = |cells: [&str; 6]| {
+        let columns: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(column, (cell, width))| match column {
+                0 => format!("{:<width$}", cell, width = width),
+                _ => format!("{:>width$}", cell, width = width),
+            })
+            .collect();
+        format!("  {}", columns.join("  "))
+    };
+    writeln!(out, "{}", line(WEEK_HEADER))?;
+    for row in &rows {
+        writeln!(out, "{}", line(row.each_ref().map(String::as_str)))?;
+    }
+    Ok(())
+}
+
+/// As semanas de --by-week em CSV (--format csv), com uma linha de cabeçalho:
+/// week,total,pushes,pull_requests,issues,other (os nomes das chaves do JSON)
+///
+/// Nenhum campo precisa de aspas: são a semana ("2024-W20") e números
+pub fn format_weeks_csv(weeks: &[WeekActivity]) -> String {
+    let mut csv = String::from("week,total,pushes,pull_requests,issues,other\n");
+    for week in weeks {
+        csv.push_str(&week_cells(week).join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// As semanas de --by-week como um array JSON, em uma linha, na ordem do texto:
+/// [{"week":"2024-W20","total":12,"pushes":9,"pull_requests":2,"issues":0,"other":1}]
+pub fn format_weeks_json(weeks: &[WeekActivity]) -> String {
+    let items = weeks.iter().map(|week| {
+        Json::object([
+            ("week", week.week.to_string().into()),
+            ("total", week.total.into()),
+            ("pushes", week.pushes.into()),
+            ("pull_requests", week.pull_requests.into()),
+            ("issues", week.issues.into()),
+            ("other", week.other.into()),
+        ])
+    });
+    Json::array(items).to_string()
+}
+
+/// Formato da listagem (--format)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// As linhas de sempre, para pessoas
+    #[default]
+    Text,
+    /// Um objeto JSON por linha e por evento (NDJSON), escrito assim que cada página chega
+    Ndjson,
+    /// Uma tabela com colunas alinhadas: TYPE, REPO, DETAIL, WHEN
+    Table,
+    /// Valores separados por vírgula (por enquanto, só para --by-week)
+    Csv,
+}
+
+/// Um evento como um objeto JSON em uma linha (uma linha de --format ndjson):
+/// {"id":1,"type":"PushEvent","repo":"a/b","actor":"octocat","created_at":"2024-05-01T12:00:00Z",
+///  "action":null,"commits":2,"description":"Pushed 2 commits to a/b"}
+///
+/// Todas as chaves aparecem em todo evento; as que o evento não tem ficam null
+pub fn format_event_json(event: &GitHubEvent) -> String {
+    Json::from(event).to_string()
+}
+
+// O objeto de format_event_json (também usado, dentro de um array, pelos webhooks)
+impl From<&GitHubEvent> for Json {
+    fn from(event: &GitHubEvent) -> Json {
+        // Os mesmos valores dos campos de --template, com null no lugar do texto vazio
+        let field = |placeholder| Some(template_field(placeholder, event, None)).filter(|value| !value.is_empty());
+        let commits = match &event.payload {
+            EventPayload::Push { distinct_count, .. } => Json::from(*distinct_count),
+            _ => Json::Null,
+        };
+
+        Json::object([
+            ("id", event.id.into()),
+            ("type", event.kind.name().into()),
+            ("repo", event.repo_name.as_str().into()),
+            ("actor", event.actor.as_ref().map(|actor| actor.login.as_str()).into()),
+            ("created_at", field(Placeholder::Date).into()),
+            ("action", field(Placeholder::Action).into()),
+            ("commits", commits),
+            ("description", format_event(event).into()),
+        ])
+    }
+}
+
+/// O corpo de uma resposta (ou de um arquivo) reformatado para leitura (--raw):
+/// um valor por linha, 2 espaços por nível de aninhamento
+///
+/// Nada passa pelo modelo de eventos: chaves, strings (com os escapes) e números saem
+/// como vieram, e qualquer JSON válido é aceito, não só um array de eventos
+pub fn format_raw_json(body: &str) -> Result<String, ActivityError> {
+    json::pretty(body)
+}
+
+/// Um erro como um objeto JSON em uma linha, para quem lê a saída de --json ou --format ndjson:
+/// {"error":{"kind":"secondary_rate_limit","message":"...","exit_code":6,"retry_after":60}}
+///
+/// `kind` ([`ActivityError::kind`]), `message` (no idioma de --lang) e `exit_code` aparecem
+/// sempre; os outros campos dependem do erro (status, reset_at, retry_after, path...)
+pub fn format_error_json(error: &ActivityError) -> String {
+    Json::object([("error", error.into())]).to_string()
+}
+
+// O objeto de dentro de {"error": ...}; um erro que embrulha outro (vários usernames,
+// --compare) leva o original em "cause"
+impl From<&ActivityError> for Json {
+    fn from(error: &ActivityError) -> Json {
+        let mut json = Json::object([
+            ("kind", error.kind().into()),
+            ("message", error.to_string().into()),
+            ("exit_code", error.exit_code().into()),
+        ]);
+        let details: Vec<(&str, Json)> = match error {
+            ActivityError::IoError { path, .. } => vec![("path", path.as_str().into())],
+            ActivityError::InvalidUsername(username) | ActivityError::UserNotFound(username) => {
+                vec![("username", username.as_str().into())]
+            }
+            ActivityError::ApiError { status, documentation_url, .. } => vec![
+                ("status", u32::from(*status).into()),
+                ("documentation_url", documentation_url.as_deref().into()),
+            ],
+            ActivityError::RateLimited { reset_at } => {
+                vec![("reset_at", reset_at.map(|reset_at| reset_at.to_string()).into())]
+            }
+            ActivityError::SecondaryRateLimit { retry_after } => vec![("retry_after", (*retry_after).into())],
+            ActivityError::ParseError { event_index, snippet, .. } => vec![
+                ("event_index", (*event_index).into()),
+                ("snippet", Some(snippet.as_str()).filter(|snippet| !snippet.is_empty()).into()),
+            ],
+            ActivityError::SomeTargetsFailed { failed, first } => {
+                vec![("failed", Json::array(failed.iter().map(String::as_str))), ("cause", first.as_ref().into())]
+            }
+            ActivityError::CompareFailed { user, source } => {
+                vec![("user", user.as_str().into()), ("cause", source.as_ref().into())]
+            }
+            ActivityError::BrowserError { url, .. } | ActivityError::WebhookError { url, .. } => {
+                vec![("url", url.as_str().into())]
+            }
+            ActivityError::Interrupted { shown } => vec![("shown", (*shown).into())],
+            ActivityError::ChecksFailed { failed, total } => {
+                vec![("failed", (*failed).into()), ("total", (*total).into())]
+            }
+            ActivityError::NetworkError { .. }
+            | ActivityError::InvalidConfig(_)
+            | ActivityError::InvalidArgument(_)
+            | ActivityError::NoEventsFound => Vec::new(),
+        };
+        for (key, value) in details {
+            json.push(key, value);
+        }
+        json
+    }
+}
+
+// TESTES
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Actor, CommitSummary, EventKind, EventPayload};
+
+    #[test]
+    fn test_capitalize_first() {
+        assert_eq!(capitalize_first("hello"), "Hello");
+        assert_eq!(capitalize_first("world"), "World");
+        assert_eq!(capitalize_first(""), "");
+        assert_eq!(capitalize_first("a"), "A");
+    }
+
+    #[test]
+    fn test_format_push_event_single() {
+        let event = GitHubEvent::new(
+            EventKind::Push,
+            "user/repo".to_string(),
+            EventPayload::Push { commit_count: 1, distinct_count: 1, push_count: 1, branch: None, commits: Vec::new() },
+            None,
+        );
+        assert_eq!(format_event(&event), "Pushed 1 commit to user/repo");
+    }
+
+    #[test]
+    fn test_format_push_event_multiple() {
+        let event = GitHubEvent::new(
+            EventKind::Push,
+            "user/repo".to_string(),
+            EventPayload::Push { commit_count: 3, distinct_count: 3, push_count: 1, branch: None, commits: Vec::new() },
+            None,
+        );
+        assert_eq!(format_event(&event), "Pushed 3 commits to user/repo");
+    }
+
+    #[test]
+    fn test_format_collapsed_push_event() {
+        let event = GitHubEvent::new(
+            EventKind::Push,
+            "user/repo".to_string(),
+            EventPayload::Push { commit_count: 17, distinct_count: 17, push_count: 10, branch: None, commits: Vec::new() },
+            None,
+        );
+        assert_eq!(format_event(&event), "Pushed 17 commits to user/repo (across 10 pushes)");
+    }
+
+    // Evento em "user/repo" com o payload dado (o tipo não importa para format_event)
+    fn event_in_repo(payload: EventPayload) -> GitHubEvent {
+        GitHubEvent::new(EventKind::Other("SomeEvent".to_string()), "user/repo".to_string(), payload, None)
+    }
+
+    #[test]
+    fn test_format_push_prefers_distinct_count() {
+        let push = |commit_count, distinct_count| {
+            event_in_repo(EventPayload::Push {
+                commit_count,
+                distinct_count,
+                push_count: 1,
+                branch: None,
+                commits: Vec::new(),
+            })
+        };
+
+        assert_eq!(format_event(&push(5, 2)), "Pushed 2 commits to user/repo");
+        assert_eq!(format_event(&push(3, 1)), "Pushed 1 commit to user/repo");
+        assert_eq!(format_event(&push(2, 0)), "Pushed 0 new commits (force-push) to user/repo");
+        // Push vazio (ex: só moveu a branch): nada foi enviado, não é force-push
+        assert_eq!(format_event(&push(0, 0)), "Pushed 0 commits to user/repo");
+    }
+
+    #[test]
+    fn test_format_fork_destination() {
+        let fork = |forkee: Option<&str>| {
+            format_event(&event_in_repo(EventPayload::ForkEvent { forkee: forkee.map(str::to_string) }))
+        };
+
+        assert_eq!(fork(Some("myuser/repo")), "Forked user/repo to myuser/repo");
+        assert_eq!(fork(None), "Forked user/repo");
+    }
+
+    #[test]
+    fn test_format_release() {
+        let release = |action: &str, tag: Option<&str>, name: Option<&str>, draft| {
+            format_event(&event_in_repo(EventPayload::ReleaseEvent {
+                action: action.to_string(),
+                tag: tag.map(str::to_string),
+                name: name.map(str::to_string),
+                draft,
+            }))
+        };
+
+        assert_eq!(
+            release("published", Some("v1.4.0"), Some("Spring cleaning"), false),
+            "Published release v1.4.0 ('Spring cleaning') in user/repo"
+        );
+        // Nome vazio ou igual à tag: sem parênteses
+        assert_eq!(release("edited", Some("v1.3.2"), Some("v1.3.2"), false), "Edited release v1.3.2 in user/repo");
+        assert_eq!(release("published", Some("v1.0"), Some(""), false), "Published release v1.0 in user/repo");
+        assert_eq!(release("created", Some("v2.0.0-rc.1"), None, true), "Drafted release v2.0.0-rc.1 in user/repo");
+        // Sem a tag, a frase genérica de antes
+        assert_eq!(release("published", None, None, false), "Published a release in user/repo");
+    }
+
+    #[test]
+    fn test_review_verb() {
+        assert_eq!(review_verb("approved"), "Approved");
+        assert_eq!(review_verb("APPROVED"), "Approved");
+        assert_eq!(review_verb("changes_requested"), "Requested changes on");
+        assert_eq!(review_verb("commented"), "Commented on");
+        assert_eq!(review_verb("dismissed"), "Reviewed");
+        assert_eq!(review_verb(""), "Reviewed");
+    }
+
+    #[test]
+    fn test_format_pull_request_review() {
+        let review = |state: &str| {
+            format_event(&event_in_repo(EventPayload::PullRequestReview {
+                action: "created".to_string(),
+                state: state.to_string(),
+            }))
+        };
+
+        assert_eq!(review("approved"), "Approved a pull request in user/repo");
+        assert_eq!(review("changes_requested"), "Requested changes on a pull request in user/repo");
+        assert_eq!(review("pending"), "Reviewed a pull request in user/repo");
+    }
+
+    #[test]
+    fn test_format_member_and_public() {
+        let member = |action: &str, login: Option<&str>| {
+            format_event(&event_in_repo(EventPayload::Member {
+                action: action.to_string(),
+                member_login: login.map(str::to_string),
+            }))
+        };
+
+        assert_eq!(member("added", Some("octocat")), "Added 'octocat' as a collaborator to user/repo");
+        assert_eq!(member("added", None), "Added a collaborator to user/repo");
+        assert_eq!(member("removed", Some("octocat")), "Removed collaborator 'octocat' in user/repo");
+
+        assert_eq!(format_event(&event_in_repo(EventPayload::Public)), "Made user/repo public");
+    }
+
+    #[test]
+    fn test_format_gollum() {
+        let wiki = |pages_edited, first_page: Option<&str>, action: Option<&str>| {
+            format_event(&event_in_repo(EventPayload::Gollum {
+                pages_edited,
+                first_page: first_page.map(str::to_string),
+                first_page_action: action.map(str::to_string),
+            }))
+        };
+
+        assert_eq!(wiki(1, Some("Home"), Some("created")), "Created wiki page 'Home' in user/repo");
+        assert_eq!(wiki(1, Some("Setup"), Some("edited")), "Edited wiki page 'Setup' in user/repo");
+        // Várias páginas: só a contagem, mesmo que a primeira tenha sido criada
+        assert_eq!(wiki(2, Some("Home"), Some("created")), "Edited 2 wiki pages in user/repo");
+        assert_eq!(wiki(0, None, None), "Edited the wiki in user/repo");
+    }
+
+    #[test]
+    fn test_format_push_with_branch() {
+        let event = event_in_repo(EventPayload::Push {
+            commit_count: 3,
+            distinct_count: 3,
+            push_count: 1,
+            branch: Some("main".to_string()),
+            commits: Vec::new(),
+        });
+        assert_eq!(format_event(&event), "Pushed 3 commits to main in user/repo");
+    }
+
+    #[test]
+    fn test_format_issue_and_pr_with_number_and_title() {
+        let issue = event_in_repo(EventPayload::IssuesEvent {
+            action: "opened".to_string(),
+            number: Some(42),
+            title: Some("Fix crash on startup".to_string()),
+        });
+        assert_eq!(format_event(&issue), "Opened issue #42 'Fix crash on startup' in user/repo");
+
+        let pr = event_in_repo(EventPayload::PullRequestEvent {
+            action: "closed".to_string(),
+            number: Some(7),
+            title: None,
+            merged: false,
+        });
+        assert_eq!(format_event(&pr), "Closed pull request #7 in user/repo");
+
+        let bare = event_in_repo(EventPayload::IssuesEvent {
+            action: "reopened".to_string(),
+            number: None,
+            title: Some("ignored without a number".to_string()),
+        });
+        assert_eq!(format_event(&bare), "Reopened an issue in user/repo");
+    }
+
+    #[test]
+    fn test_format_pull_request_merge_states() {
+        let pr = |action: &str, merged: bool| {
+            format_event(&event_in_repo(EventPayload::PullRequestEvent {
+                action: action.to_string(),
+                number: None,
+                title: None,
+                merged,
+            }))
+        };
+
+        assert_eq!(pr("opened", false), "Opened a pull request in user/repo");
+        assert_eq!(pr("closed", false), "Closed a pull request in user/repo");
+        assert_eq!(pr("closed", true), "Merged a pull request in user/repo");
+    }
+
+    // Push com `commit_count` commits, dos quais `listed` vêm no payload
+    fn push_with_commits(commit_count: usize, listed: usize) -> GitHubEvent {
+        let commits = (1..=listed)
+            .map(|n| CommitSummary {
+                sha: format!("{:040}", n),
+                message: format!("Commit {}\n\nBody of commit {}", n, n),
+            })
+            .collect();
+        event_in_repo(EventPayload::Push {
+            commit_count,
+            distinct_count: commit_count,
+            push_count: 1,
+            branch: None,
+            commits,
+        })
+    }
+
+    fn render(events: &[GitHubEvent], options: &DisplayOptions) -> String {
+        let mut out = Vec::new();
+        write_event_lines(&mut out, events, options, 0..events.len(), "").unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_commit_lines_show_first_five_titles() {
+        let options = DisplayOptions { show_commits: true, ..Default::default() };
+
+        assert_eq!(
+            render(&[push_with_commits(7, 7)], &options),
+            "- Pushed 7 commits to user/repo\n\
+             \x20   Commit 1\n\
+             \x20   Commit 2\n\
+             \x20   Commit 3\n\
+             \x20   Commit 4\n\
+             \x20   Commit 5\n\
+             \x20   …and 2 more\n"
+        );
+
+        // Poucos commits: sem o resumo
+        assert_eq!(
+            render(&[push_with_commits(2, 2)], &options),
+            "- Pushed 2 commits to user/repo\n    Commit 1\n    Commit 2\n"
+        );
+    }
+
+    #[test]
+    fn test_commit_lines_count_commits_missing_from_the_payload() {
+        // A API lista no máximo 20 commits, mas size diz quantos foram
+        let options = DisplayOptions { show_commits: true, ..Default::default() };
+        let text = render(&[push_with_commits(25, 20)], &options);
+        assert!(text.ends_with("    …and 20 more\n"), "{}", text);
+
+        // Sem a lista no payload, não há o que mostrar
+        assert_eq!(render(&[push_with_commits(3, 0)], &options), "- Pushed 3 commits to user/repo\n");
+    }
+
+    #[test]
+    fn test_commit_lines_in_portuguese() {
+        let mut out = Vec::new();
+        write_commit_lines(&mut out, &push_with_commits(7, 7), "  ", i18n::Lang::PtBr.messages()).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("      Commit 1\n"), "{}", text);
+        assert!(text.ends_with("      Commit 5\n      …e mais 2\n"), "{}", text);
+    }
+
+    #[test]
+    fn test_commit_lines_truncate_long_titles() {
+        let mut event = push_with_commits(1, 1);
+        if let EventPayload::Push { commits, .. } = &mut event.payload {
+            commits[0].message = format!("{}\nsecond line", "é".repeat(100));
+        }
+
+        let options = DisplayOptions { show_commits: true, ..Default::default() };
+        let text = render(&[event], &options);
+        let commit_line = text.lines().nth(1).unwrap();
+        assert_eq!(commit_line, format!("    {}…", "é".repeat(COMMIT_TITLE_MAX_CHARS - 1)));
+    }
+
+    #[test]
+    fn test_commit_lines_only_with_the_flag() {
+        let text = render(&[push_with_commits(7, 7)], &DisplayOptions::default());
+        assert_eq!(text, "- Pushed 7 commits to user/repo\n");
+    }
+
+    #[test]
+    fn test_verbose_shows_raw_payload_of_unknown_events() {
+        let unknown = |raw: &str| GitHubEvent {
+            kind: EventKind::Other("SponsorshipEvent".to_string()),
+            ..event_in_repo(EventPayload::Unknown { raw_payload: Some(raw.to_string()) })
+        };
+        let verbose = DisplayOptions { verbose: true, ..Default::default() };
+
+        assert_eq!(
+            render(&[unknown(r#"{"action":"created"}"#)], &verbose),
+            "- Performed SponsorshipEvent in user/repo {\"action\":\"created\"}\n"
+        );
+
+        // Payloads longos mostram só o começo
+        let long = format!("{{\"body\":\"{}\"}}", "x".repeat(300));
+        let line = render(&[unknown(&long)], &verbose);
+        assert!(line.ends_with("…\n"), "{}", line);
+        assert_eq!(
+            line.chars().count(),
+            "- Performed SponsorshipEvent in user/repo ".chars().count() + RAW_PAYLOAD_PREVIEW_CHARS + 1
+        );
+
+        // Sem --verbose, a linha de sempre
+        assert_eq!(
+            render(&[unknown("{}")], &DisplayOptions::default()),
+            "- Performed SponsorshipEvent in user/repo\n"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_for_terminal() {
+        assert_eq!(sanitize_for_terminal("Fix crash — 日本 🚀"), "Fix crash — 日本 🚀");
+        assert_eq!(sanitize_for_terminal("\x1b[31mred\x1b[0m title"), "red title");
+        assert_eq!(sanitize_for_terminal("clear\x1b[2J\x1b[1;1Hscreen"), "clearscreen");
+        assert_eq!(sanitize_for_terminal("\x1b]8;;https://evil.example\x1b\\click\x1b]8;;\x07"), "click");
+        assert_eq!(sanitize_for_terminal("\u{9b}31mC1\u{9d}0;title\u{9c}"), "C1");
+        assert_eq!(sanitize_for_terminal("first line\r\n\nsecond\tthird"), "first line second third");
+        assert_eq!(sanitize_for_terminal("bell\x07 nul\0 del\x7f\u{85}"), "bell nul del");
+        // Um ESC solto (ou no fim) some sozinho
+        assert_eq!(sanitize_for_terminal("a\x1bb\x1b"), "ab");
+    }
+
+    #[test]
+    fn test_escape_sequences_from_the_network_do_not_reach_the_output() {
+        let hostile = GitHubEvent::new(
+            EventKind::Issues,
+            "evil/\x1b[31mrepo".to_string(),
+            EventPayload::IssuesEvent {
+                action: "opened\x1b[0m".to_string(),
+                number: Some(1),
+                title: Some("\x1b[31mred\nsecond line".to_string()),
+            },
+            None,
+        );
+        assert_eq!(format_event(&hostile), "Opened issue #1 'red second line' in evil/repo");
+
+        let options = DisplayOptions { show_actor: true, ..Default::default() };
+        let hostile = hostile.with_actor(Some(Actor { login: "mal\x1b[31mlory".to_string(), id: 1 }));
+        let events = vec![hostile];
+        let mut table = Vec::new();
+        display_table(&mut table, &events, &options, true).unwrap();
+        let mut grouped = Vec::new();
+        display_events_grouped(&mut grouped, &events, &options, GroupBy::Repo).unwrap();
+        for out in [table, grouped] {
+            let text = String::from_utf8(out).unwrap();
+            assert!(!text.contains('\x1b'), "{:?}", text);
+            assert!(text.contains("evil/repo") && text.contains("mallory"), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn test_escape_sequences_in_a_branch_name_do_not_reach_the_output() {
+        let push = GitHubEvent::new(
+            EventKind::Push,
+            "o/r".to_string(),
+            EventPayload::Push {
+                commit_count: 1,
+                distinct_count: 1,
+                push_count: 1,
+                branch: Some("x\x1b[2Jevil".to_string()),
+                commits: vec![CommitSummary { sha: "1".to_string(), message: "\x1b]0;pwned\x07Fix\n\nbody".to_string() }],
+            },
+            None,
+        );
+        assert_eq!(format_event(&push), "Pushed 1 commit to xevil in o/r");
+
+        let options = DisplayOptions {
+            show_commits: true,
+            template: Some(Template::parse("{detail}").unwrap()),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        display_events(&mut out, std::slice::from_ref(&push), &options).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "Pushed 1 commit to xevil in o/r\n");
+
+        let options = DisplayOptions { show_commits: true, ..Default::default() };
+        let mut out = Vec::new();
+        display_events(&mut out, &[push], &options).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "- Pushed 1 commit to xevil in o/r\n    Fix\n");
+    }
+
+    #[test]
+    fn test_format_title_truncates_long_titles() {
+        let exact = "x".repeat(TITLE_MAX_CHARS);
+        assert_eq!(format_title(Some(&exact)), format!(" '{}'", exact));
+
+        // Conta caracteres, não bytes
+        let long = "é".repeat(TITLE_MAX_CHARS + 1);
+        let expected = format!(" '{}…'", "é".repeat(TITLE_MAX_CHARS - 1));
+        assert_eq!(format_title(Some(&long)), expected);
+
+        assert_eq!(format_title(None), "");
+    }
+
+    #[test]
+    fn test_format_create_and_delete_with_ref_names() {
+        let branch = event_in_repo(EventPayload::CreateEvent {
+            ref_type: "branch".to_string(),
+            ref_name: Some("feature-x".to_string()),
+        });
+        assert_eq!(format_event(&branch), "Created branch 'feature-x' in user/repo");
+
+        let tag = event_in_repo(EventPayload::CreateEvent {
+            ref_type: "tag".to_string(),
+            ref_name: Some("v1.2.0".to_string()),
+        });
+        assert_eq!(format_event(&tag), "Created tag 'v1.2.0' in user/repo");
+
+        let deleted = event_in_repo(EventPayload::DeleteEvent {
+            ref_type: "branch".to_string(),
+            ref_name: Some("old".to_string()),
+        });
+        assert_eq!(format_event(&deleted), "Deleted branch 'old' in user/repo");
+
+        // Sem o nome do ref, a frase antiga
+        let repository = event_in_repo(EventPayload::CreateEvent {
+            ref_type: "repository".to_string(),
+            ref_name: None,
+        });
+        assert_eq!(format_event(&repository), "Created a repository in user/repo");
+    }
+
+    #[test]
+    fn test_format_watch_event() {
+        let event = GitHubEvent::new(
+            EventKind::Watch,
+            "torvalds/linux".to_string(),
+            EventPayload::WatchEvent,
+            None,
+        );
+        assert_eq!(format_event(&event), "Starred torvalds/linux");
+    }
+
+    #[test]
+    fn test_humanize_duration() {
+        assert_eq!(humanize_duration(0), "just now");
+        assert_eq!(humanize_duration(59), "just now");
+        assert_eq!(humanize_duration(60), "1 minute ago");
+        assert_eq!(humanize_duration(125), "2 minutes ago");
+        assert_eq!(humanize_duration(3600), "1 hour ago");
+        assert_eq!(humanize_duration(7200), "2 hours ago");
+        assert_eq!(humanize_duration(86_400), "1 day ago");
+        assert_eq!(humanize_duration(3 * 86_400 + 5), "3 days ago");
+    }
+
+    #[test]
+    fn test_humanize_duration_future_is_just_now() {
+        assert_eq!(humanize_duration(-30), "just now");
+        assert_eq!(humanize_duration(-86_400), "just now");
+    }
+
+    #[test]
+    fn test_format_relative_time_falls_back_to_date() {
+        let now = Timestamp::parse_rfc3339("2024-06-01T12:00:00Z").unwrap();
+        let recent = Timestamp::parse_rfc3339("2024-06-01T10:00:00Z").unwrap();
+        let month_ago = Timestamp::parse_rfc3339("2024-05-02T12:00:00Z").unwrap();
+        let old = Timestamp::parse_rfc3339("2024-03-01T08:00:00Z").unwrap();
+
+        assert_eq!(format_relative_time(recent, now), "2 hours ago");
+        assert_eq!(format_relative_time(month_ago, now), "30 days ago");
+        assert_eq!(format_relative_time(old, now), "2024-03-01");
+    }
+
+    #[test]
+    fn test_format_event_line_with_actor() {
+        let event = GitHubEvent::new(
+            EventKind::Watch,
+            "rust-lang/rust".to_string(),
+            EventPayload::WatchEvent,
+            None,
+        )
+        .with_actor(Some(Actor { login: "octocat".to_string(), id: 583231 }));
+
+        let with_actor = DisplayOptions { show_actor: true, ..Default::default() };
+        assert_eq!(format_event_line(&event, &with_actor), "octocat: Starred rust-lang/rust");
+        assert_eq!(
+            format_event_line(&event, &DisplayOptions::default()),
+            "Starred rust-lang/rust"
+        );
+    }
+
+    #[test]
+    fn test_format_event_line_without_actor() {
+        let event = GitHubEvent::new(
+            EventKind::Watch,
+            "rust-lang/rust".to_string(),
+            EventPayload::WatchEvent,
+            None,
+        );
+        let with_actor = DisplayOptions { show_actor: true, ..Default::default() };
+        assert_eq!(format_event_line(&event, &with_actor), "Starred rust-lang/rust");
+    }
+
+    // Fixture com eventos intercalados em dois repositórios
+    fn mixed_events() -> Vec<GitHubEvent> {
+        let event = |kind: EventKind, repo: &str, payload: EventPayload| {
+            GitHubEvent::new(kind, repo.to_string(), payload, None)
+        };
+
+        vec![
+            event(EventKind::Push, "a/one", EventPayload::Push { commit_count: 2, distinct_count: 2, push_count: 1, branch: None, commits: Vec::new() }),
+            event(EventKind::Watch, "b/two", EventPayload::WatchEvent),
+            event(EventKind::Push, "b/two", EventPayload::Push { commit_count: 1, distinct_count: 1, push_count: 1, branch: None, commits: Vec::new() }),
+            event(EventKind::Watch, "a/one", EventPayload::WatchEvent),
+        ]
+    }
+
+    #[test]
+    fn test_display_events_grouped_by_repo() {
+        let mut out = Vec::new();
+        display_events_grouped(&mut out, &mixed_events(), &DisplayOptions::default(), GroupBy::Repo)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "a/one (2 events)\n\
+             \x20 - Pushed 2 commits to a/one\n\
+             \x20 - Starred a/one\n\
+             \n\
+             b/two (2 events)\n\
+             \x20 - Starred b/two\n\
+             \x20 - Pushed 1 commit to b/two\n"
+        );
+    }
+
+    #[test]
+    fn test_emoji_prefixes_each_line_also_in_groups() {
+        let options = DisplayOptions { emoji: true, ..Default::default() };
+        let mut out = Vec::new();
+        display_events_grouped(&mut out, &mixed_events(), &options, GroupBy::Repo).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "a/one (2 events)\n\
+             \x20 - 📦 Pushed 2 commits to a/one\n\
+             \x20 - ⭐ Starred a/one\n\
+             \n\
+             b/two (2 events)\n\
+             \x20 - ⭐ Starred b/two\n\
+             \x20 - 📦 Pushed 1 commit to b/two\n"
+        );
+    }
+
+    #[test]
+    fn test_numbered_lines_keep_their_number_in_groups() {
+        let mut events = mixed_events();
+        events.extend(mixed_events().into_iter().cycle().take(6));
+        let options = DisplayOptions { numbered: true, ..Default::default() };
+
+        let mut out = Vec::new();
+        display_events(&mut out, &events[..3], &options).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "1. Pushed 2 commits to a/one\n2. Starred b/two\n3. Pushed 1 commit to b/two\n"
+        );
+
+        // 10 eventos: os números de um dígito ganham um espaço na frente
+        let mut out = Vec::new();
+        display_events_grouped(&mut out, &events, &options, GroupBy::Repo).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("a/one (5 events)\n\x20  1. Pushed 2 commits to a/one\n\x20  4. Starred a/one\n"), "{}", text);
+        assert!(text.contains("\n\x20 10. Starred b/two\n"), "{}", text);
+    }
+
+    #[test]
+    fn test_event_icons() {
+        assert_eq!(event_icon(&EventPayload::WatchEvent), "⭐");
+        assert_eq!(event_icon(&EventPayload::ForkEvent { forkee: None }), "🍴");
+        assert_eq!(event_icon(&EventPayload::CommitCommentEvent), "💬");
+        assert_eq!(event_icon(&EventPayload::Unknown { raw_payload: None }), "📌");
+        // Todos ocupam duas colunas, o que mantém a coluna da tabela alinhada
+        for icon in ["📦", "🐛", "🔀", "👀", "⭐", "🍴", "🏷️", "💬", "🌱", "🗑️", "👥", "🔓", "📝", "📌"] {
+            assert_eq!(display_width(icon), 2, "{}", icon);
+        }
+    }
+
+    #[test]
+    fn test_display_events_grouped_by_type() {
+        let mut out = Vec::new();
+        let events = &mixed_events()[..3];
+        display_events_grouped(&mut out, events, &DisplayOptions::default(), GroupBy::Type).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "PushEvent (2 events)\n\
+             \x20 - Pushed 2 commits to a/one\n\
+             \x20 - Pushed 1 commit to b/two\n\
+             \n\
+             WatchEvent (1 event)\n\
+             \x20 - Starred b/two\n"
+        );
+    }
+
+    #[test]
+    fn test_display_counts() {
+        let counts = |events: &[GitHubEvent], group_by| {
+            let mut out = Vec::new();
+            display_counts(&mut out, events, group_by).unwrap();
+            out
+        };
+        let events = mixed_events();
+
+        assert_eq!(counts(&[], None), b"0\n");
+        assert_eq!(counts(&events[..1], None), b"1\n");
+        assert_eq!(counts(&events, None), b"4\n");
+
+        assert_eq!(counts(&events, Some(GroupBy::Type)), b"PushEvent\t2\nWatchEvent\t2\n");
+        assert_eq!(counts(&events[1..2], Some(GroupBy::Type)), b"WatchEvent\t1\n");
+        assert_eq!(counts(&events, Some(GroupBy::Repo)), b"a/one\t2\nb/two\t2\n");
+        assert_eq!(counts(&[], Some(GroupBy::Type)), b"");
+    }
+
+    #[test]
+    fn test_display_events_writes_one_line_per_event() {
+        let mut out = Vec::new();
+        display_events(&mut out, &mixed_events(), &DisplayOptions::default()).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "- Pushed 2 commits to a/one\n\
+             - Starred b/two\n\
+             - Pushed 1 commit to b/two\n\
+             - Starred a/one\n"
+        );
+    }
+
+    #[test]
+    fn test_display_headers() {
+        let mut out = Vec::new();
+        let nothing = HiddenEvents::default();
+        display_repo_header(&mut out, "rust-lang/rust", 1, nothing).unwrap();
+        display_file_header(&mut out, "events.json", 0, nothing).unwrap();
+        display_header(&mut out, "alice", None, 2, nothing, None).unwrap();
+        display_header(&mut out, "bob", None, 5, HiddenEvents { duplicates: 2, bots: 0 }, None).unwrap();
+        display_repo_header(&mut out, "a/b", 3, HiddenEvents { duplicates: 1, bots: 12 }).unwrap();
+        display_header(&mut out, "carol", None, 4, nothing, Some(Duration::from_secs(150))).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\nRecent activity in repository 'rust-lang/rust':\nFound 1 event\n\n\
+             \nEvents from 'events.json':\nFound 0 events\n\n\
+             \nRecent activity for 'alice':\nFound 2 events\n\n\
+             \nRecent activity for 'bob':\nFound 5 events (2 duplicate events hidden)\n\n\
+             \nRecent activity in repository 'a/b':\nFound 3 events (1 duplicate event hidden, 12 bot events hidden)\n\n\
+             \nRecent activity for 'carol':\nFound 4 events (cached, 2m old)\n\n"
+        );
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(Duration::ZERO), "0s");
+        assert_eq!(format_age(Duration::from_secs(59)), "59s");
+        assert_eq!(format_age(Duration::from_secs(150)), "2m");
+        assert_eq!(format_age(Duration::from_secs(3 * 3600 + 59)), "3h");
+        assert_eq!(format_age(Duration::from_secs(2 * 86_400)), "2d");
+    }
+
+    #[test]
+    fn test_display_header_with_profile() {
+        let profile = UserProfile {
+            login: "torvalds".to_string(),
+            name: Some("Linus Torvalds".to_string()),
+            followers: 180_432,
+            public_repos: 4,
+            company: Some("Linux Foundation".to_string()),
+            location: Some("Portland, OR".to_string()),
+        };
+        let mut out = Vec::new();
+        display_header(&mut out, "torvalds", Some(&profile), 30, HiddenEvents::default(), None).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\nRecent activity for torvalds (Linus Torvalds) — 180k followers, 4 public repos:\n\
+             \x20 Linux Foundation · Portland, OR\n\
+             Found 30 events\n\n"
+        );
+    }
+
+    #[test]
+    fn test_display_no_events_messages() {
+        let mut out = Vec::new();
+        display_no_events(&mut out, "ghost").unwrap();
+        display_no_repo_events(&mut out, "a/b").unwrap();
+        display_no_new_events(&mut out, "a/b", Timestamp::parse_rfc3339("2024-06-01T10:00:00Z")).unwrap();
+        display_no_new_events(&mut out, "a/b", None).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "No recent activity found for user 'ghost'\n\
+             This could mean:\n\
+             \x20 - The user has no public activity in the last 90 days\n\
+             \x20 - The user has made their activity private\n\
+             No recent activity found in repository 'a/b'\n\
+             No new activity for 'a/b' since 2024-06-01T10:00:00Z\n\
+             No new activity for 'a/b' since the last run\n"
+        );
+    }
+
+    #[test]
+    fn test_grouped_keeps_first_activity_markers() {
+        let options = DisplayOptions {
+            first_activity: HashSet::from([3]),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        display_events_grouped(&mut out, &mixed_events(), &options, GroupBy::Repo).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains(&format!("- Starred a/one  {}", FIRST_ACTIVITY_MARKER)));
+        assert_eq!(text.matches(FIRST_ACTIVITY_MARKER).count(), 1);
+    }
+
+    #[test]
+    fn test_style_detect() {
+        assert_eq!(Style::detect(false, None, true), Style::Ansi);
+        assert_eq!(Style::detect(true, None, true), Style::Plain);
+        assert_eq!(Style::detect(false, Some("1"), true), Style::Plain);
+        // NO_COLOR vazio não conta (especificação do no-color.org)
+        assert_eq!(Style::detect(false, Some(""), true), Style::Ansi);
+        assert_eq!(Style::detect(false, None, false), Style::Plain);
+    }
+
+    #[test]
+    fn test_format_event_styled_colors_and_bolds_repo() {
+        let event = GitHubEvent::new(
+            EventKind::Push,
+            "user/repo".to_string(),
+            EventPayload::Push { commit_count: 2, distinct_count: 2, push_count: 1, branch: None, commits: Vec::new() },
+            None,
+        );
+
+        assert_eq!(format_event_styled(&event, Style::Plain), format_event(&event));
+        assert_eq!(
+            format_event_styled(&event, Style::Ansi),
+            "\x1b[32mPushed 2 commits to \x1b[1muser/repo\x1b[0m\x1b[32m\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_format_rate_limit() {
+        let english = i18n::Lang::En.messages();
+        let info = RateLimitInfo {
+            limit: 60,
+            remaining: 54,
+            reset_at: Timestamp::parse_rfc3339("2024-05-01T17:32:00Z"),
+        };
+
+        assert_eq!(
+            format_rate_limit(Some(&info), -3 * 3600, english),
+            "API rate limit: 54/60 remaining, resets at 14:32"
+        );
+        assert_eq!(
+            format_rate_limit(Some(&RateLimitInfo { reset_at: None, ..info }), 0, english),
+            "API rate limit: 54/60 remaining"
+        );
+        assert_eq!(format_rate_limit(None, 0, english), "API rate limit: unknown");
+        assert_eq!(
+            format_rate_limit(Some(&info), 0, i18n::Lang::PtBr.messages()),
+            "Limite da API: 54/60 restantes, renova às 17:32"
+        );
+    }
+
+    #[test]
+    fn test_format_event_in_portuguese() {
+        let in_portuguese = |payload| {
+            format_event_linked(&event_in_repo(payload), Style::Plain, false, i18n::Lang::PtBr.messages())
+        };
+
+        assert_eq!(
+            in_portuguese(EventPayload::Push {
+                commit_count: 3,
+                distinct_count: 3,
+                push_count: 2,
+                branch: Some("main".to_string()),
+                commits: Vec::new(),
+            }),
+            "Enviou 3 commits para main em user/repo (em 2 pushes)"
+        );
+        assert_eq!(in_portuguese(EventPayload::WatchEvent), "Favoritou user/repo");
+        assert_eq!(
+            in_portuguese(EventPayload::IssuesEvent {
+                action: "closed".to_string(),
+                number: Some(42),
+                title: Some("Fix crash".to_string()),
+            }),
+            "Fechou a issue #42 'Fix crash' em user/repo"
+        );
+        assert_eq!(
+            in_portuguese(EventPayload::ReleaseEvent {
+                action: "published".to_string(),
+                tag: Some("v1.0".to_string()),
+                name: None,
+                draft: false,
+            }),
+            "Publicou a release v1.0 em user/repo"
+        );
+        assert_eq!(
+            in_portuguese(EventPayload::Gollum { pages_edited: 1, first_page: None, first_page_action: None }),
+            "Editou 1 página da wiki em user/repo"
+        );
+    }
+
+    #[test]
+    fn test_hyperlink() {
+        assert_eq!(
+            hyperlink("user/repo", "https://github.com/user/repo", true),
+            "\x1b]8;;https://github.com/user/repo\x1b\\user/repo\x1b]8;;\x1b\\"
+        );
+        assert_eq!(hyperlink("user/repo", "https://github.com/user/repo", false), "user/repo");
+    }
+
+    #[test]
+    fn test_detect_hyperlinks() {
+        assert!(detect_hyperlinks(true, None, true));
+        assert!(detect_hyperlinks(false, Some("WezTerm"), true));
+        assert!(!detect_hyperlinks(false, Some("Apple_Terminal"), true));
+        assert!(!detect_hyperlinks(false, None, true));
+        // Em um pipe, nem --hyperlinks liga os links
+        assert!(!detect_hyperlinks(true, Some("WezTerm"), false));
+    }
+
+    #[test]
+    fn test_format_event_line_with_hyperlinks() {
+        let link = |text: &str, url: &str| hyperlink(text, url, true);
+        let issue = event_in_repo(EventPayload::IssuesEvent {
+            action: "opened".to_string(),
+            number: Some(42),
+            title: Some("Fix crash".to_string()),
+        });
+        let options = DisplayOptions { hyperlinks: true, ..Default::default() };
+
+        assert_eq!(
+            format_event_line(&issue, &options),
+            format!(
+                "Opened {} 'Fix crash' in {}",
+                link("issue #42", "https://github.com/user/repo/issues/42"),
+                link("user/repo", "https://github.com/user/repo")
+            )
+        );
+        // Desligado (o padrão), a mesma linha sai sem escapes
+        assert_eq!(format_event_line(&issue, &DisplayOptions::default()), format_event(&issue));
+    }
+
+    #[test]
+    fn test_event_colors() {
+        assert_eq!(event_color(&EventPayload::Push { commit_count: 1, distinct_count: 1, push_count: 1, branch: None, commits: Vec::new() }), Color::Green);
+        assert_eq!(
+            event_color(&EventPayload::IssuesEvent { action: "opened".to_string(), number: None, title: None }),
+            Color::Yellow
+        );
+        assert_eq!(
+            event_color(&EventPayload::PullRequestEvent {
+                action: "opened".to_string(),
+                number: None,
+                title: None,
+                merged: false,
+            }),
+            Color::Magenta
+        );
+        assert_eq!(event_color(&EventPayload::WatchEvent), Color::Cyan);
+        assert_eq!(event_color(&EventPayload::ForkEvent { forkee: None }), Color::Cyan);
+        assert_eq!(event_color(&EventPayload::IssueCommentEvent), Color::Dim);
+        assert_eq!(event_color(&EventPayload::CommitCommentEvent), Color::Dim);
+        assert_eq!(
+            event_color(&EventPayload::DeleteEvent { ref_type: "branch".to_string(), ref_name: None }),
+            Color::Green
+        );
+        assert_eq!(event_color(&EventPayload::Unknown { raw_payload: None }), Color::Default);
+    }
+
+    #[test]
+    fn test_compact_count() {
+        assert_eq!(compact_count(0), "0");
+        assert_eq!(compact_count(999), "999");
+        assert_eq!(compact_count(1_000), "1k");
+        assert_eq!(compact_count(1_250), "1.2k");
+        assert_eq!(compact_count(1_999), "1.9k");
+        assert_eq!(compact_count(12_800), "12k");
+        assert_eq!(compact_count(180_432), "180k");
+        assert_eq!(compact_count(3_400_000), "3.4M");
+    }
+
+    #[test]
+    fn test_format_thousands() {
+        assert_eq!(format_thousands(0), "0");
+        assert_eq!(format_thousands(999), "999");
+        assert_eq!(format_thousands(1_000), "1,000");
+        assert_eq!(format_thousands(12_345), "12,345");
+        assert_eq!(format_thousands(123_456), "123,456");
+        assert_eq!(format_thousands(1_234_567), "1,234,567");
+        assert_eq!(format_thousands(u64::MAX), "18,446,744,073,709,551,615");
+    }
+
+    #[test]
+    fn test_format_starred_repository() {
+        let mut repository = Repository {
+            full_name: "torvalds/linux".to_string(),
+            name: "linux".to_string(),
+            description: Some("Linux kernel source tree ".to_string()),
+            stargazers_count: 180_123,
+            language: Some("C".to_string()),
+            fork: false,
+            updated_at: None,
+        };
+        assert_eq!(format_starred_repository(&repository), "- torvalds/linux (★ 180,123) — Linux kernel source tree");
+
+        repository.description = Some(String::new());
+        assert_eq!(format_starred_repository(&repository), "- torvalds/linux (★ 180,123)");
+        repository.description = None;
+        assert_eq!(format_starred_repository(&repository), "- torvalds/linux (★ 180,123)");
+    }
+
+    #[test]
+    fn test_display_repositories_aligns_columns() {
+        let repositories = vec![
+            Repository {
+                full_name: "torvalds/linux".to_string(),
+                name: "linux".to_string(),
+                description: Some("Linux kernel source tree".to_string()),
+                stargazers_count: 171_234,
+                language: Some("C".to_string()),
+                fork: false,
+                updated_at: Timestamp::parse_rfc3339("2024-05-20T09:12:44Z"),
+            },
+            Repository {
+                full_name: "torvalds/subsurface".to_string(),
+                name: "subsurface".to_string(),
+                description: None,
+                stargazers_count: 12,
+                language: None,
+                fork: true,
+                updated_at: None,
+            },
+        ];
+
+        let mut out = Vec::new();
+        display_repositories(&mut out, &repositories).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "  NAME                 STARS  LANGUAGE  UPDATED     DESCRIPTION\n\
+             \x20 linux              171,234  C         2024-05-20  Linux kernel source tree\n\
+             \x20 subsurface (fork)       12  -         -\n"
+        );
+    }
+
+    #[test]
+    fn test_format_profile() {
+        let mut profile = UserProfile {
+            login: "torvalds".to_string(),
+            name: Some("Linus Torvalds".to_string()),
+            followers: 180_432,
+            public_repos: 4,
+            company: None,
+            location: None,
+        };
+        assert_eq!(format_profile(&profile), "torvalds (Linus Torvalds) — 180k followers, 4 public repos");
+
+        // Sem nome (ou com o nome igual ao login) não repetimos nada entre parênteses
+        profile.name = None;
+        profile.followers = 1;
+        profile.public_repos = 1;
+        assert_eq!(format_profile(&profile), "torvalds — 1 follower, 1 public repo");
+    }
+
+    #[test]
+    fn test_display_header_sanitizes_the_profile() {
+        let profile = UserProfile {
+            login: "tor\x1b[2Jvalds".to_string(),
+            name: Some("\x1b]8;;https://evil.example\x07Linus\x1b]8;;\x07".to_string()),
+            followers: 1,
+            public_repos: 1,
+            company: Some("\x1b[31mLinux Foundation".to_string()),
+            location: Some("Portland,\nOR\x1b[0m".to_string()),
+        };
+        let mut out = Vec::new();
+        display_header(&mut out, "torvalds", Some(&profile), 1, HiddenEvents::default(), None).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\nRecent activity for torvalds (Linus) — 1 follower, 1 public repo:\n\
+             \x20 Linux Foundation · Portland, OR\n\
+             Found 1 event\n\n"
+        );
+    }
+
+    fn summary_fixture() -> ActivitySummary {
+        let events = vec![
+            GitHubEvent::new(
+                EventKind::Push,
+                "user/repo".to_string(),
+                EventPayload::Push { commit_count: 2, distinct_count: 2, push_count: 1, branch: None, commits: Vec::new() },
+                Timestamp::parse_rfc3339("2024-06-01T10:00:00Z"),
+            ),
+            GitHubEvent::new(
+                EventKind::Push,
+                "user/repo".to_string(),
+                EventPayload::Push { commit_count: 1, distinct_count: 1, push_count: 1, branch: None, commits: Vec::new() },
+                Timestamp::parse_rfc3339("2024-05-20T10:00:00Z"),
+            ),
+            GitHubEvent::new(
+                EventKind::Watch,
+                "other/\"quoted\"".to_string(),
+                EventPayload::WatchEvent,
+                Timestamp::parse_rfc3339("2024-05-01T10:00:00Z"),
+            ),
+        ];
+        ActivitySummary::from_events(&events)
+    }
+
+    // As sequências do mesmo período: 3 eventos em 32 dias, sem dois dias seguidos
+    fn daily_fixture() -> DailyActivity {
+        DailyActivity {
+            current_streak: 0,
+            longest_streak: 1,
+            busiest_day: Some(("2024-05-01".to_string(), 1)),
+            events: 3,
+            days: 32,
+        }
+    }
+
+    #[test]
+    fn test_display_summary() {
+        let mut out = Vec::new();
+        display_summary(&mut out, "user", &summary_fixture(), &daily_fixture()).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\nSummary for 'user':\n\
+             \x20 3 events from 2024-05-01 to 2024-06-01\n\
+             \x20 2 pushes, 1 star\n\
+             \x20 Current streak: 0 days, longest: 1 day\n\
+             \x20 Busiest day: 2024-05-01 (1 event), 0.1 events per day on average\n\
+             \nMost active repositories:\n\
+             \x20 1. user/repo (2 events)\n\
+             \x20 2. other/\"quoted\" (1 event)\n"
+        );
+    }
+
+    #[test]
+    fn test_display_summary_notes_unrecognized_types() {
+        let unknown = |name: &str| {
+            GitHubEvent::new(
+                EventKind::Other(name.to_string()),
+                "user/repo".to_string(),
+                EventPayload::Unknown { raw_payload: None },
+                None,
+            )
+        };
+        let events = vec![unknown("SponsorshipEvent"), unknown("DiscussionEvent"), unknown("SponsorshipEvent")];
+
+        let mut out = Vec::new();
+        display_summary(&mut out, "user", &ActivitySummary::from_events(&events), &DailyActivity::default()).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(
+            text.ends_with("\n3 events of unrecognized types (SponsorshipEvent, DiscussionEvent)\n"),
+            "{}",
+            text
+        );
+
+        let mut out = Vec::new();
+        display_summary(&mut out, "user", &ActivitySummary::from_events(&events[..1]), &DailyActivity::default())
+            .unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.ends_with("\n1 event of an unrecognized type (SponsorshipEvent)\n"), "{}", text);
+    }
+
+    #[test]
+    fn test_format_summary_json() {
+        assert_eq!(
+            format_summary_json(&summary_fixture(), &daily_fixture()),
+            "{\"total_events\":3,\"by_type\":{\"pushes\":2,\"stars\":1},\
+             \"top_repos\":[{\"repo\":\"user/repo\",\"events\":2},\
+             {\"repo\":\"other/\\\"quoted\\\"\",\"events\":1}],\
+             \"first_event\":\"2024-05-01T10:00:00Z\",\"last_event\":\"2024-06-01T10:00:00Z\",\
+             \"current_streak\":0,\"longest_streak\":1,\"busiest_day\":{\"date\":\"2024-05-01\",\"events\":1},\
+             \"average_events_per_day\":0.09}"
+        );
+
+        let empty = format_summary_json(&ActivitySummary::from_events(&[]), &DailyActivity::default());
+        assert_eq!(
+            empty,
+            "{\"total_events\":0,\"by_type\":{},\"top_repos\":[],\"first_event\":null,\"last_event\":null,\
+             \"current_streak\":0,\"longest_streak\":0,\"busiest_day\":null,\"average_events_per_day\":null}"
+        );
+    }
+
+    fn comparison_fixture() -> SummaryComparison {
+        let at = |repo: &str, payload: EventPayload, time: &str| {
+            GitHubEvent::new(EventKind::Watch, repo.to_string(), payload, Timestamp::parse_rfc3339(time))
+        };
+        let left = ActivitySummary::from_events(&[
+            at("a/one", EventPayload::WatchEvent, "2024-06-01T10:00:00Z"),
+            at("a/two", EventPayload::WatchEvent, "2024-06-01T09:00:00Z"),
+        ]);
+        let right = ActivitySummary::from_events(&[at("a/two", EventPayload::ForkEvent { forkee: None }, "2024-05-30T08:00:00Z")]);
+        left.compare(right)
+    }
+
+    #[test]
+    fn test_display_comparison_side_by_side() {
+        let mut out = Vec::new();
+        display_comparison(&mut out, ("alice", "bob"), &comparison_fixture(), 80).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\nComparing 'alice' and 'bob':\n\n\
+             \x20                       alice             bob\n\
+             \x20 events                    2               1\n\
+             \x20 stars                     2               0\n\
+             \x20 forks                     0               1\n\
+             \x20 busiest day  2024-06-01 (2)  2024-05-30 (1)\n\
+             \nShared repositories (1):\n\
+             \x20 a/two\n"
+        );
+    }
+
+    #[test]
+    fn test_display_comparison_stacks_when_narrow() {
+        let mut out = Vec::new();
+        display_comparison(&mut out, ("alice", "bob"), &comparison_fixture(), 40).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\nComparing 'alice' and 'bob':\n\n\
+             alice:\n\
+             \x20 events: 2\n\
+             \x20 stars: 2\n\
+             \x20 forks: 0\n\
+             \x20 busiest day: 2024-06-01 (2)\n\
+             \n\
+             bob:\n\
+             \x20 events: 1\n\
+             \x20 stars: 0\n\
+             \x20 forks: 1\n\
+             \x20 busiest day: 2024-05-30 (1)\n\
+             \nShared repositories (1):\n\
+             \x20 a/two\n"
+        );
+    }
+
+    #[test]
+    fn test_display_comparison_sanitizes_shared_repos() {
+        let mut comparison = comparison_fixture();
+        comparison.shared_repos = vec!["a/\x1b]8;;https://evil.example\x1b\\two\x1b]8;;\x1b\\\x1b[2J".to_string()];
+        let mut out = Vec::new();
+        display_comparison(&mut out, ("alice", "bob"), &comparison, 80).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(!text.contains('\x1b'), "{:?}", text);
+        assert!(text.ends_with("\nShared repositories (1):\n  a/two\n"), "{:?}", text);
+    }
+
+    #[test]
+    fn test_display_histogram_at_80_columns() {
+        let days = vec![
+            ("2024-06-01".to_string(), 12),
+            ("2024-06-02".to_string(), 0),
+            ("2024-06-03".to_string(), 5),
+            ("2024-06-04".to_string(), 1),
+        ];
+        let mut out = Vec::new();
+        display_histogram(&mut out, "'torvalds' (UTC)", &days, 80).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[1], "Events per day for 'torvalds' (UTC):");
+        // 80 - "  2024-06-01  12 " = 63 colunas para a maior barra
+        assert_eq!(lines[2], format!("  2024-06-01  12 {}", "█".repeat(63)));
+        // Dia sem eventos: a linha fica, sem barra (e sem espaço sobrando no fim)
+        assert_eq!(lines[3], "  2024-06-02   0");
+        // 5/12 de 63 = 26.25 colunas -> 26 cheias + 2/8
+        assert_eq!(lines[4], format!("  2024-06-03   5 {}▎", "█".repeat(26)));
+        assert_eq!(lines[5], format!("  2024-06-04   1 {}▎", "█".repeat(5)));
+        assert!(lines.iter().all(|line| line.chars().count() <= 80));
+    }
+
+    #[test]
+    fn test_display_histogram_without_dates() {
+        let mut out = Vec::new();
+        display_histogram(&mut out, "'torvalds' (UTC)", &[], 80).unwrap();
+        assert!(String::from_utf8(out).unwrap().ends_with("  (no events with a date)\n"));
+    }
+
+    #[test]
+    fn test_template_mixes_fields_and_text() {
+        let template = Template::parse("{date} [{type}] {owner}/{name}: {action}{commits} - {detail}").unwrap();
+        let event = GitHubEvent::new(
+            EventKind::Issues,
+            "octocat/Hello-World".to_string(),
+            EventPayload::IssuesEvent { action: "opened".to_string(), number: Some(42), title: None },
+            Timestamp::parse_rfc3339("2024-06-01T10:00:00Z"),
+        );
+
+        assert_eq!(
+            template.render(&event, None),
+            "2024-06-01T10:00:00Z [IssuesEvent] octocat/Hello-World: opened - Opened issue #42 in octocat/Hello-World"
+        );
+
+        let now = Timestamp::parse_rfc3339("2024-06-01T12:00:00Z");
+        assert_eq!(Template::parse("{relative_date}").unwrap().render(&event, now), "2 hours ago");
+    }
+
+    #[test]
+    fn test_template_leaves_missing_fields_blank() {
+        let template = Template::parse("{repo}|{action}|{commits}|{date}").unwrap();
+        let star = GitHubEvent::new(EventKind::Watch, "a/b".to_string(), EventPayload::WatchEvent, None);
+        assert_eq!(template.render(&star, None), "a/b|||");
+
+        let push = GitHubEvent::new(
+            EventKind::Push,
+            "a/b".to_string(),
+            EventPayload::Push { commit_count: 3, distinct_count: 2, push_count: 1, branch: None, commits: Vec::new() },
+            None,
+        );
+        assert_eq!(template.render(&push, None), "a/b||2|");
+    }
+
+    #[test]
+    fn test_template_escapes_braces() {
+        let template = Template::parse("{{{repo}}} {{literal}}").unwrap();
+        let star = GitHubEvent::new(EventKind::Watch, "a/b".to_string(), EventPayload::WatchEvent, None);
+        assert_eq!(template.render(&star, None), "{a/b} {literal}");
+    }
+
+    #[test]
+    fn test_template_errors() {
+        let error = Template::parse("{date} {author}").unwrap_err();
+        assert!(error.contains("unknown placeholder '{author}'"), "{}", error);
+        assert!(error.contains("{relative_date}"), "{}", error);
+
+        assert!(Template::parse("{repo").unwrap_err().contains("without a closing"));
+        assert!(Template::parse("repo}").unwrap_err().contains("without an opening"));
+    }
+
+    fn repo_activity_fixture() -> Vec<RepoActivity> {
+        vec![
+            RepoActivity { repo_name: "torvalds/linux".to_string(), events: 12, latest_kind: EventKind::Push },
+            RepoActivity { repo_name: "torvalds/subsurface".to_string(), events: 1, latest_kind: EventKind::Watch },
+        ]
+    }
+
+    #[test]
+    fn test_display_repo_activity() {
+        let mut out = Vec::new();
+        display_repo_activity(&mut out, "torvalds", &repo_activity_fixture()).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\nRepositories for 'torvalds' (2):\n\
+             \x20 12  torvalds/linux (latest: PushEvent)\n\
+             \x20  1  torvalds/subsurface (latest: WatchEvent)\n"
+        );
+    }
+
+    #[test]
+    fn test_display_repo_activity_sanitizes_repo_names() {
+        let mut repos = repo_activity_fixture();
+        repos[0].repo_name = "torvalds/\x1b[31mlinux\x1b]0;pwned\x07".to_string();
+        let mut out = Vec::new();
+        display_repo_activity(&mut out, "torvalds", &repos).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(!text.contains('\x1b') && !text.contains("pwned"), "{:?}", text);
+        assert!(text.contains("  12  torvalds/linux (latest: PushEvent)\n"), "{:?}", text);
+    }
+
+    #[test]
+    fn test_display_width_counts_columns() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("café"), 4);
+        // "e" + acento combinante: um caractere na tela
+        assert_eq!(display_width("cafe\u{301}"), 4);
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("🚀 lab"), 6);
+        assert_eq!(display_width("한글"), 4);
+    }
+
+    #[test]
+    fn test_truncate_to_width_never_splits_a_wide_char() {
+        assert_eq!(truncate_to_width("short", 10), "short");
+        assert_eq!(truncate_to_width("abcdefgh", 5), "abcd…");
+        // 4 colunas para o texto: cabem dois ideogramas, não dois e meio
+        assert_eq!(truncate_to_width("日本語のメモ", 5), "日本…");
+        assert_eq!(display_width(&truncate_to_width("日本語のメモ", 6)), 5);
+    }
+
+    fn table_events() -> Vec<GitHubEvent> {
+        let now = Timestamp::parse_rfc3339("2024-06-01T12:00:00Z");
+        vec![
+            GitHubEvent::new(EventKind::Watch, "dev-42/日本語-notes".to_string(), EventPayload::WatchEvent, now),
+            GitHubEvent::new(
+                EventKind::Push,
+                "a/b".to_string(),
+                EventPayload::Push { commit_count: 1, distinct_count: 1, push_count: 1, branch: None, commits: Vec::new() },
+                Timestamp::parse_rfc3339("2024-05-31T12:00:00Z"),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_display_table_aligns_wide_characters() {
+        let options = DisplayOptions { now: Timestamp::parse_rfc3339("2024-06-01T14:00:00Z"), ..Default::default() };
+        let mut out = Vec::new();
+        display_table(&mut out, &table_events(), &options, false).unwrap();
+
+        // "dev-42/日本語-notes" tem 16 chars mas ocupa 19 colunas, como o separador
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "TYPE        REPO                 DETAIL                       WHEN\n\
+             ----------  -------------------  ---------------------------  -----------\n\
+             WatchEvent  dev-42/日本語-notes  Starred dev-42/日本語-notes  2 hours ago\n\
+             PushEvent   a/b                  Pushed 1 commit to a/b       1 day ago\n"
+        );
+    }
+
+    #[test]
+    fn test_display_table_with_emoji_column() {
+        let options = DisplayOptions { emoji: true, ..Default::default() };
+        let mut out = Vec::new();
+        display_table(&mut out, &table_events(), &options, false).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "    TYPE        REPO                 DETAIL                       WHEN\n\
+             --  ----------  -------------------  ---------------------------  ----------\n\
+             ⭐  WatchEvent  dev-42/日本語-notes  Starred dev-42/日本語-notes  2024-06-01\n\
+             📦  PushEvent   a/b                  Pushed 1 commit to a/b       2024-05-31\n"
+        );
+    }
+
+    #[test]
+    fn test_display_table_numbered() {
+        let options = DisplayOptions { numbered: true, emoji: true, ..Default::default() };
+        let mut out = Vec::new();
+        display_table(&mut out, &table_events(), &options, false).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].starts_with("#      TYPE"), "{}", lines[0]);
+        assert!(lines[2].starts_with("1  ⭐  WatchEvent"), "{}", lines[2]);
+        assert!(lines[3].starts_with("2  📦  PushEvent"), "{}", lines[3]);
+    }
+
+    #[test]
+    fn test_display_table_caps_columns_unless_wide() {
+        let long_repo = format!("owner/{}", "x".repeat(50));
+        let events = vec![GitHubEvent::new(EventKind::Watch, long_repo.clone(), EventPayload::WatchEvent, None)];
+
+        let mut out = Vec::new();
+        display_table(&mut out, &events, &DisplayOptions::default(), false).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let row = text.lines().nth(2).unwrap();
+        assert!(row.contains(&format!("{}…  Starred", &long_repo[..TABLE_REPO_MAX_WIDTH - 1])), "{}", row);
+
+        let mut out = Vec::new();
+        display_table(&mut out, &events, &DisplayOptions::default(), true).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(!text.contains('…'));
+        assert!(text.contains(&format!("Starred {}", long_repo)));
+    }
+
+    #[test]
+    fn test_format_event_json() {
+        let push = GitHubEvent::new(
+            EventKind::Push,
+            "a/b".to_string(),
+            EventPayload::Push { commit_count: 3, distinct_count: 2, push_count: 1, branch: None, commits: Vec::new() },
+            Timestamp::parse_rfc3339("2024-05-01T12:00:00Z"),
+        )
+        .with_id(Some(7))
+        .with_actor(Some(Actor { login: "octocat".to_string(), id: 1 }));
+        assert_eq!(
+            format_event_json(&push),
+            "{\"id\":7,\"type\":\"PushEvent\",\"repo\":\"a/b\",\"actor\":\"octocat\",\
+             \"created_at\":\"2024-05-01T12:00:00Z\",\"action\":null,\"commits\":2,\
+             \"description\":\"Pushed 2 commits to a/b\"}"
+        );
+
+        let issue = GitHubEvent::new(
+            EventKind::Issues,
+            "a/b".to_string(),
+            EventPayload::IssuesEvent { action: "opened".to_string(), number: Some(1), title: Some("\"x\"".to_string()) },
+            None,
+        );
+        assert_eq!(
+            format_event_json(&issue),
+            "{\"id\":null,\"type\":\"IssuesEvent\",\"repo\":\"a/b\",\"actor\":null,\"created_at\":null,\
+             \"action\":\"opened\",\"commits\":null,\"description\":\"Opened issue #1 '\\\"x\\\"' in a/b\"}"
+        );
+    }
+
+    #[test]
+    fn test_format_error_json() {
+        assert_eq!(
+            format_error_json(&ActivityError::UserNotFound("ghost".to_string())),
+            "{\"error\":{\"kind\":\"user_not_found\",\"message\":\"User 'ghost' was not found on GitHub. \
+             Check the spelling of the username\",\"exit_code\":44,\"username\":\"ghost\"}}"
+        );
+        assert_eq!(
+            format_error_json(&ActivityError::SecondaryRateLimit { retry_after: 60 }),
+            format!(
+                "{{\"error\":{{\"kind\":\"secondary_rate_limit\",\"message\":{},\"exit_code\":{},\"retry_after\":60}}}}",
+                Json::from(ActivityError::SecondaryRateLimit { retry_after: 60 }.to_string()),
+                ActivityError::SecondaryRateLimit { retry_after: 60 }.exit_code()
+            )
+        );
+    }
+
+    #[test]
+    fn test_format_error_json_keeps_the_cause() {
+        let error = ActivityError::SomeTargetsFailed {
+            failed: vec!["ghost".to_string()],
+            first: Box::new(ActivityError::ApiError {
+                status: 500,
+                message: "boom".to_string(),
+                documentation_url: None,
+            }),
+        };
+        let json = format_error_json(&error);
+        assert!(json.starts_with("{\"error\":{\"kind\":\"some_targets_failed\""));
+        assert!(json.contains("\"failed\":[\"ghost\"],\"cause\":{\"kind\":\"api_error\""));
+        assert!(json.contains("\"status\":500,\"documentation_url\":null}}}"));
+    }
+
+    #[test]
+    fn test_format_repo_activity_json() {
+        assert_eq!(
+            format_repo_activity_json(&repo_activity_fixture()),
+            r#"[{"repo":"torvalds/linux","events":12,"latest":"PushEvent"},{"repo":"torvalds/subsurface","events":1,"latest":"WatchEvent"}]"#
+        );
+        assert_eq!(format_repo_activity_json(&[]), "[]");
+    }
+
+    fn weeks_fixture() -> Vec<WeekActivity> {
+        let week = |year, week| crate::date::IsoWeek { year, week };
+        vec![
+            WeekActivity { week: week(2025, 1), total: 124, pushes: 100, pull_requests: 20, issues: 3, other: 1 },
+            WeekActivity { week: week(2024, 52), total: 0, pushes: 0, pull_requests: 0, issues: 0, other: 0 },
+            WeekActivity { week: week(2024, 51), total: 3, pushes: 1, pull_requests: 0, issues: 1, other: 1 },
+        ]
+    }
+
+    #[test]
+    fn test_display_weeks() {
+        let mut out = Vec::new();
+        display_weeks(&mut out, "'torvalds' (UTC)", &weeks_fixture()).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\nEvents per week for 'torvalds' (UTC):\n\
+             \x20 WEEK      TOTAL  PUSHES  PRS  ISSUES  OTHER\n\
+             \x20 2025-W01    124     100   20       3      1\n\
+             \x20 2024-W52      0       0    0       0      0\n\
+             \x20 2024-W51      3       1    0       1      1\n"
+        );
+
+        let mut out = Vec::new();
+        display_weeks(&mut out, "'torvalds' (UTC)", &[]).unwrap();
+        assert!(String::from_utf8(out).unwrap().ends_with("(no events with a date)\n"));
+    }
+
+    #[test]
+    fn test_format_weeks_csv_and_json() {
+        assert_eq!(
+            format_weeks_csv(&weeks_fixture()[1..]),
+            "week,total,pushes,pull_requests,issues,other\n2024-W52,0,0,0,0,0\n2024-W51,3,1,0,1,1\n"
+        );
+        assert_eq!(format_weeks_csv(&[]), "week,total,pushes,pull_requests,issues,other\n");
+
+        assert_eq!(
+            format_weeks_json(&weeks_fixture()[2..]),
+            r#"[{"week":"2024-W51","total":3,"pushes":1,"pull_requests":0,"issues":1,"other":1}]"#
+        );
+        assert_eq!(format_weeks_json(&[]), "[]");
+    }
+
+    #[test]
+    fn test_display_parse_warnings() {
+        let outcome = crate::parser::parse_events(r#"[{"type": "WatchEvent"}, {}]"#).unwrap();
+
+        let mut out = Vec::new();
+        display_parse_warnings(&mut out, &outcome.warnings, false).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "warning: skipped 2 unparseable events (run with --verbose for details)\n"
+        );
+
+        let mut out = Vec::new();
+        display_parse_warnings(&mut out, &outcome.warnings[..1], true).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "warning: skipped 1 unparseable event:\n\
+             \x20 event #0: Missing 'repo' field\n\
+             \x20   near: {\"type\": \"WatchEvent\"}\n"
+        );
+
+        let mut out = Vec::new();
+        display_parse_warnings(&mut out, &[], true).unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_display_parse_warnings_sanitizes_the_snippet() {
+        // O byte ESC cru no meio do JSON: o evento é pulado, e o trecho vai para o aviso
+        let outcome = crate::parser::parse_events("[{\"type\": \"WatchEvent\", \"x\": \x1b]0;pwned\x07\x1b[2J}]").unwrap();
+        assert_eq!(outcome.warnings.len(), 1);
+
+        let mut out = Vec::new();
+        display_parse_warnings(&mut out, &outcome.warnings, true).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(!text.contains('\x1b') && !text.contains("pwned"), "{:?}", text);
+        assert!(text.contains("    near: {\"type\": \"WatchEvent\""), "{:?}", text);
+    }
+
+    #[test]
+    fn test_capitalize_first_keeps_letters_without_a_single_capital() {
+        assert_eq!(capitalize_first("écrit"), "Écrit");
+        assert_eq!(capitalize_first("ße"), "ße");
+        assert_eq!(capitalize_first("ŉa"), "ŉa");
+        assert_eq!(capitalize_first("1st"), "1st");
+    }
+
+    #[test]
+    fn test_shorten_and_truncate_with_no_room() {
+        assert_eq!(shorten("abc", 0), "");
+        assert_eq!(shorten("abc", 1), "…");
+        assert_eq!(truncate_to_width("abc", 0), "");
+        assert_eq!(truncate_to_width("日本", 1), "…");
+    }
+
+    // CONCEITO: Testes de propriedade (proptest)
+    // Em vez de exemplos escritos à mão, o proptest gera centenas de entradas e
+    // verifica uma regra que vale para todas; quando uma falha, ele encolhe a
+    // entrada até o menor exemplo que ainda quebra a regra
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        // Pedaços que costumam quebrar cortes por byte ou por char: acentos prontos e
+        // combinantes, ideogramas e emoji de duas colunas, o ß
+        fn tricky_text() -> impl Strategy<Value = String> {
+            let pieces = vec!["a", "Z", " ", "é", "e\u{301}", "ß", "日", "🚀", "한", "x"];
+            prop::collection::vec(prop::sample::select(pieces), 0..24).prop_map(|pieces| pieces.concat())
+        }
+
+        // O texto antes da reticência, que precisa ser um prefixo do original
+        const COMBINING_ACUTE: char = '\u{301}';
+
+        fn kept(result: &str) -> &str {
+            result.strip_suffix('…').unwrap_or(result)
+        }
+
+        // "3 hours ago" -> 10800: a ordem dos textos, em segundos
+        fn humanized_seconds(text: &str) -> i64 {
+            if text == "just now" {
+                return 0;
+            }
+            let mut words = text.split(' ');
+            let amount: i64 = words.next().unwrap().parse().unwrap();
+            let unit = match words.next().unwrap().trim_end_matches('s') {
+                "minute" => 60,
+                "hour" => 3600,
+                "day" => 86_400,
+                other => panic!("unidade inesperada: {}", other),
+            };
+            amount * unit
+        }
+
+        proptest! {
+            #[test]
+            fn capitalize_first_preserves_the_tail(text in any::<String>()) {
+                let result = capitalize_first(&text);
+                prop_assert_eq!(result.chars().count(), text.chars().count());
+                prop_assert!(result.chars().skip(1).eq(text.chars().skip(1)));
+            }
+
+            #[test]
+            fn truncate_to_width_fits_and_keeps_whole_chars(text in tricky_text(), max in 0usize..30) {
+                let result = truncate_to_width(&text, max);
+                prop_assert!(display_width(&result) <= max);
+                prop_assert!(text.starts_with(kept(&result)));
+                // Um acento combinante nunca fica separado da letra que ele acompanha
+                let rest = &text[kept(&result).len()..];
+                prop_assert!(!rest.starts_with(COMBINING_ACUTE));
+            }
+
+            #[test]
+            fn sanitize_for_terminal_leaves_no_control_characters(text in any::<String>()) {
+                let result = sanitize_for_terminal(&text);
+                prop_assert!(!result.chars().any(char::is_control));
+                // Texto sem controles passa intacto
+                let plain: String = text.chars().filter(|c| !c.is_control()).collect();
+                prop_assert_eq!(sanitize_for_terminal(&plain), plain);
+            }
+
+            #[test]
+            fn shorten_fits_and_keeps_whole_chars(text in tricky_text(), max in 0usize..30) {
+                let result = shorten(&text, max);
+                prop_assert!(result.chars().count() <= max);
+                prop_assert!(text.starts_with(kept(&result)));
+            }
+
+            #[test]
+            fn humanize_duration_is_monotonic(a in -100_000i64..10_000_000, b in -100_000i64..10_000_000) {
+                let (earlier, later) = (a.min(b), a.max(b));
+                prop_assert!(humanized_seconds(&humanize_duration(earlier)) <= humanized_seconds(&humanize_duration(later)));
+            }
+        }
     }
 }
@@ -0,0 +1,289 @@
+//! Este módulo faz as verificações de `--doctor`: o que costuma dar errado antes
+//! de qualquer evento aparecer
+//!
+//! Cada verificação é uma função que devolve um [`CheckResult`] (nome, situação e
+//! detalhe), na ordem em que os problemas costumam aparecer:
+//!
+//! 1. a API responde (DNS, conexão e TLS)
+//! 2. há um token, e de onde ele veio
+//! 3. o token é aceito (`GET /user`)
+//! 4. quanto sobra do limite de requisições (`GET /rate_limit`, que não gasta da cota)
+//! 5. o diretório de cache (onde --new guarda o último evento visto) aceita escrita
+//!
+//! ```text
+//! PASS  API reachable    https://api.github.com answered with HTTP 200 in 84ms
+//! WARN  Token            none found: unauthenticated requests are limited to 60 per hour
+//! ```
+//!
+//! Sem token não é uma falha (o programa funciona, só com um limite menor):
+//! isso aparece como WARN. Só FAIL muda o código de saída (ver [`any_failed`])
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use crate::api::{GitHubClient, RateLimitInfo};
+use crate::credentials::TokenSource;
+use crate::error::ActivityError;
+use crate::json::Json;
+
+/// Resultado de uma verificação
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// Tudo certo
+    Pass,
+    /// Funciona, mas com alguma limitação (ex: sem token)
+    Warn,
+    /// Não funciona
+    Fail,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "PASS"),
+            Status::Warn => write!(f, "WARN"),
+            Status::Fail => write!(f, "FAIL"),
+        }
+    }
+}
+
+/// Uma linha do relatório de --doctor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    /// O que foi verificado ("API reachable", "Token"...)
+    pub name: &'static str,
+    /// Se deu certo
+    pub status: Status,
+    /// O porquê, em uma frase
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn new(name: &'static str, status: Status, detail: impl Into<String>) -> CheckResult {
+        CheckResult { name, status, detail: detail.into() }
+    }
+}
+
+/// O que as verificações precisam saber além do cliente
+#[derive(Debug, Clone, Default)]
+pub struct Environment<'a> {
+    /// De onde veio o token (None sem token)
+    pub token_source: Option<&'a TokenSource>,
+    /// O diretório de cache (None quando o sistema não informa um)
+    pub cache_dir: Option<&'a Path>,
+    /// Fuso para a hora do reset do limite, em segundos (ver date::parse_utc_offset)
+    pub utc_offset: i64,
+}
+
+// Nomes usados também quando a verificação é pulada (ver run_checks)
+const AUTHENTICATION: &str = "Authentication";
+const RATE_LIMIT: &str = "Rate limit";
+
+/// Faz todas as verificações, na ordem do relatório
+///
+/// Sem resposta da API, as verificações que dependem dela nem são tentadas:
+/// cada uma esperaria o tempo limite de novo para falhar do mesmo jeito
+pub fn run_checks(client: &GitHubClient, environment: &Environment) -> Vec<CheckResult> {
+    let reachability = check_reachability(client);
+    let reachable = reachability.status != Status::Fail;
+
+    let mut results = vec![reachability, check_token(environment.token_source)];
+    if reachable {
+        results.push(check_authentication(client));
+        results.push(check_rate_limit(client, environment.utc_offset));
+    } else {
+        for name in [AUTHENTICATION, RATE_LIMIT] {
+            results.push(CheckResult::new(name, Status::Fail, "not checked: the API is not reachable"));
+        }
+    }
+    results.push(check_cache_dir(environment.cache_dir));
+    results
+}
+
+/// A API responde? Qualquer status HTTP abaixo de 500 serve: o DNS, a conexão e o TLS funcionaram
+pub fn check_reachability(client: &GitHubClient) -> CheckResult {
+    const NAME: &str = "API reachable";
+    let base_url = client.base_url.trim_end_matches('/');
+
+    match client.ping() {
+        Ok((status, elapsed)) => {
+            let detail = format!("{} answered with HTTP {} in {}ms", base_url, status, elapsed.as_millis());
+            let result = if status < 500 { Status::Pass } else { Status::Fail };
+            CheckResult::new(NAME, result, detail)
+        }
+        // A mensagem de erro já traz o endereço
+        Err(e) => CheckResult::new(NAME, Status::Fail, e.to_string()),
+    }
+}
+
+/// Há um token, e de onde ele veio (nunca o token em si)
+pub fn check_token(source: Option<&TokenSource>) -> CheckResult {
+    const NAME: &str = "Token";
+    match source {
+        Some(source) => CheckResult::new(NAME, Status::Pass, format!("from {}", source)),
+        None => CheckResult::new(
+            NAME,
+            Status::Warn,
+            "none found: unauthenticated requests are limited to 60 per hour (see --token)",
+        ),
+    }
+}
+
+/// O token é aceito pela API? (`GET /user`)
+pub fn check_authentication(client: &GitHubClient) -> CheckResult {
+    if !client.has_token() {
+        return CheckResult::new(AUTHENTICATION, Status::Warn, "skipped: no token");
+    }
+
+    match client.fetch_authenticated_user() {
+        Ok(profile) => CheckResult::new(AUTHENTICATION, Status::Pass, format!("authenticated as {}", profile.login)),
+        Err(e) => CheckResult::new(AUTHENTICATION, Status::Fail, e.to_string()),
+    }
+}
+
+/// Quanto sobra do limite de requisições (`GET /rate_limit`); esgotado é uma falha
+pub fn check_rate_limit(client: &GitHubClient, utc_offset: i64) -> CheckResult {
+    match client.fetch_rate_limit() {
+        Ok(info) => {
+            let status = if info.remaining > 0 { Status::Pass } else { Status::Fail };
+            CheckResult::new(RATE_LIMIT, status, describe_rate_limit(&info, utc_offset))
+        }
+        Err(e) => CheckResult::new(RATE_LIMIT, Status::Fail, e.to_string()),
+    }
+}
+
+// "54/60 requests left, resets at 14:32"
+fn describe_rate_limit(info: &RateLimitInfo, utc_offset: i64) -> String {
+    let left = format!("{}/{} requests left", info.remaining, info.limit);
+    match info.reset_at {
+        Some(reset_at) => format!("{}, resets at {}", left, reset_at.clock_time(utc_offset)),
+        None => left,
+    }
+}
+
+/// O diretório de cache aceita escrita? Cria o diretório (se preciso) e um arquivo
+/// de teste, que é apagado em seguida
+pub fn check_cache_dir(dir: Option<&Path>) -> CheckResult {
+    const NAME: &str = "Cache directory";
+    let dir = match dir {
+        Some(dir) => dir,
+        None => {
+            return CheckResult::new(NAME, Status::Fail, "unknown: set XDG_CACHE_HOME or HOME to an absolute path")
+        }
+    };
+
+    // O pid no nome evita disputar o arquivo com outro --doctor rodando ao mesmo tempo
+    let probe = dir.join(format!(".doctor-{}", process::id()));
+    let written = fs::create_dir_all(dir).and_then(|_| fs::write(&probe, b"ok")).and_then(|_| fs::remove_file(&probe));
+    match written {
+        Ok(()) => CheckResult::new(NAME, Status::Pass, format!("{} is writable", dir.display())),
+        Err(e) => CheckResult::new(
+            NAME,
+            Status::Fail,
+            ActivityError::io(dir.display().to_string(), e).to_string(),
+        ),
+    }
+}
+
+/// Alguma verificação falhou? (é o que decide o código de saída)
+pub fn any_failed(results: &[CheckResult]) -> bool {
+    results.iter().any(|result| result.status == Status::Fail)
+}
+
+/// O relatório em texto: uma linha por verificação, com os nomes alinhados
+///
+/// ```text
+/// PASS  API reachable    https://api.github.com answered with HTTP 200 in 84ms
+/// PASS  Token            from the GITHUB_TOKEN environment variable
+/// ```
+pub fn render(results: &[CheckResult]) -> String {
+    let width = results.iter().map(|result| result.name.len()).max().unwrap_or(0);
+    results
+        .iter()
+        .map(|result| format!("{}  {:<width$}  {}\n", result.status, result.name, result.detail, width = width))
+        .collect()
+}
+
+/// O relatório em JSON (--doctor --json): um array de {"name","status","detail"}
+/// status é "pass", "warn" ou "fail"
+pub fn format_json(results: &[CheckResult]) -> String {
+    let items = results.iter().map(|result| {
+        Json::object([
+            ("name", result.name.into()),
+            ("status", result.status.to_string().to_lowercase().into()),
+            ("detail", result.detail.as_str().into()),
+        ])
+    });
+    Json::array(items).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::Timestamp;
+
+    fn results() -> Vec<CheckResult> {
+        vec![
+            CheckResult::new("API reachable", Status::Pass, "https://api.github.com answered with HTTP 200 in 84ms"),
+            CheckResult::new("Token", Status::Warn, "none found"),
+            CheckResult::new("Cache directory", Status::Fail, "/root/.cache: \"denied\""),
+        ]
+    }
+
+    #[test]
+    fn test_render_aligns_the_names() {
+        assert_eq!(
+            render(&results()),
+            "PASS  API reachable    https://api.github.com answered with HTTP 200 in 84ms\n\
+             WARN  Token            none found\n\
+             FAIL  Cache directory  /root/.cache: \"denied\"\n"
+        );
+        assert_eq!(render(&[]), "");
+    }
+
+    #[test]
+    fn test_format_json() {
+        assert_eq!(
+            format_json(&results()[1..]),
+            r#"[{"name":"Token","status":"warn","detail":"none found"},{"name":"Cache directory","status":"fail","detail":"/root/.cache: \"denied\""}]"#
+        );
+        assert_eq!(format_json(&[]), "[]");
+    }
+
+    #[test]
+    fn test_only_failures_count() {
+        assert!(any_failed(&results()));
+        assert!(!any_failed(&results()[..2]));
+    }
+
+    #[test]
+    fn test_token_source() {
+        assert_eq!(check_token(Some(&TokenSource::Environment)).detail, "from the GITHUB_TOKEN environment variable");
+        assert_eq!(check_token(None).status, Status::Warn);
+    }
+
+    #[test]
+    fn test_describe_rate_limit() {
+        let info = RateLimitInfo {
+            limit: 60,
+            remaining: 54,
+            reset_at: Some(Timestamp::from_epoch_seconds(17 * 3600 + 32 * 60)),
+        };
+        assert_eq!(describe_rate_limit(&info, -3 * 3600), "54/60 requests left, resets at 14:32");
+        assert_eq!(describe_rate_limit(&RateLimitInfo { reset_at: None, ..info }, 0), "54/60 requests left");
+    }
+
+    #[test]
+    fn test_cache_dir() {
+        let dir = std::env::temp_dir().join(format!("github-activity-doctor-{}", process::id()));
+        let result = check_cache_dir(Some(&dir.join("nested")));
+        assert_eq!(result.status, Status::Pass, "{}", result.detail);
+        // O arquivo de teste não fica para trás
+        assert_eq!(fs::read_dir(dir.join("nested")).unwrap().count(), 0);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(check_cache_dir(None).status, Status::Fail);
+    }
+}
@@ -1,33 +1,194 @@
-// Este módulo define tipos de erro customizados para a aplicação
-// Em Rust, é uma prática comum criar tipos de erro específicos para o domínio
-// usando enums, ao invés de usar strings genéricas
+//! Este módulo define tipos de erro customizados para a aplicação
+//! Em Rust, é uma prática comum criar tipos de erro específicos para o domínio
+//! usando enums, ao invés de usar strings genéricas
 
+use std::error::Error;
 use std::fmt;
 
+use crate::date::Timestamp;
+use crate::i18n::{self, Messages};
+use crate::parser;
+
+// Códigos de saída do processo, um por categoria de falha
+// Scripts podem distinguir "usuário não existe" de "sem internet" pelo código
+// (0 continua significando sucesso, inclusive quando não há eventos)
+/// Argumentos, configuração ou username/repositório inválidos
+pub const EXIT_USAGE: i32 = 2;
+/// Falha de conexão, DNS, timeout...
+pub const EXIT_NETWORK: i32 = 3;
+/// A API respondeu com um status de erro
+pub const EXIT_API: i32 = 4;
+/// Resposta que não conseguimos interpretar
+pub const EXIT_PARSE: i32 = 5;
+/// Limite de requisições da API esgotado
+pub const EXIT_RATE_LIMIT: i32 = 6;
+/// O arquivo de --from-file/--replay não pôde ser lido (ou o de --record escrito)
+pub const EXIT_IO: i32 = 7;
+/// O navegador não pôde ser aberto (--open)
+pub const EXIT_BROWSER: i32 = 8;
+/// Alguma verificação de --doctor falhou
+pub const EXIT_DOCTOR: i32 = 9;
+/// Os eventos não puderam ser enviados ao webhook (--post-webhook)
+pub const EXIT_WEBHOOK: i32 = 10;
+/// 404: usuário ou repositório não encontrado
+pub const EXIT_NOT_FOUND: i32 = 44;
+/// Interrompido com Ctrl-C: 128 + SIGINT (2), a convenção dos shells
+pub const EXIT_INTERRUPTED: i32 = 130;
+
+// Espera sugerida quando o limite secundário vem sem Retry-After
+// (a documentação do GitHub pede "pelo menos um minuto")
+const SECONDARY_RATE_LIMIT_WAIT: u64 = 60;
+
+/// Tabela (código, descrição) para o --help, no estilo das tabelas de flags em cli.rs
+pub const EXIT_CODES: &[(i32, &str)] = &[
+    (0, "Success (including when there is no recent activity)"),
+    (EXIT_USAGE, "Invalid arguments, configuration, username or repository"),
+    (EXIT_NETWORK, "Network or transport failure"),
+    (EXIT_API, "GitHub API returned an error status"),
+    (EXIT_PARSE, "The response could not be parsed"),
+    (EXIT_RATE_LIMIT, "GitHub API rate limit exceeded"),
+    (EXIT_IO, "A --from-file/--replay, --record or --output file could not be read or written"),
+    (EXIT_BROWSER, "The browser could not be opened (--open)"),
+    (EXIT_DOCTOR, "At least one --doctor check failed"),
+    (EXIT_WEBHOOK, "The events could not be posted to the --post-webhook URL"),
+    (EXIT_NOT_FOUND, "User or repository not found (HTTP 404)"),
+    (EXIT_INTERRUPTED, "Interrupted with Ctrl-C (the events fetched until then are still shown)"),
+];
+
 // CONCEITO: Enums em Rust
 // Um enum (tipo enumerado) pode ter diferentes "variantes" (variants)
 // Cada variante pode conter dados diferentes, tornando-os muito poderosos
+/// Todas as falhas possíveis do programa; cada categoria tem seu código de saída
 #[derive(Debug)]  // Deriva automaticamente a trait Debug para facilitar impressão durante desenvolvimento
 pub enum ActivityError {
-    // Variante que guarda uma String descrevendo um erro de rede
-    // O tipo String é "owned" (possui os dados), diferente de &str que só empresta
-    NetworkError(String),
+    /// Variante que guarda uma String descrevendo um erro de rede
+    /// O tipo String é "owned" (possui os dados), diferente de &str que só empresta
+    //
+    // CONCEITO: Box<dyn Error>
+    // source guarda o erro original (io::Error, erro do ureq...) que causou este
+    // dyn Error é um "trait object": qualquer tipo que implemente Error
+    // Box coloca o erro no heap, já que cada tipo concreto tem um tamanho diferente
+    // Send + Sync permitem que o erro atravesse threads
+    NetworkError {
+        /// Descrição da falha
+        msg: String,
+        /// O erro original, se houver
+        source: Option<Box<dyn Error + Send + Sync>>,
+    },
 
-    // Variante para usuário inválido
+    /// Falha ao ler ou escrever um arquivo local (--from-file, --record)
+    /// Diferente de NetworkError, guarda o caminho para a mensagem dizer QUAL arquivo
+    IoError {
+        /// Caminho como o usuário digitou ("<stdin>" para a entrada padrão)
+        path: String,
+        /// O erro original (arquivo não existe, sem permissão...)
+        source: std::io::Error,
+    },
+
+    /// Variante para usuário inválido
     InvalidUsername(String),
 
-    // Variante com campos nomeados (similar a uma struct)
-    // Usada quando a API retorna um erro HTTP
+    /// O GitHub respondeu 404 para /users/{username}/events: o usuário não existe
+    /// Guarda o username digitado para a mensagem de erro
+    UserNotFound(String),
+
+    /// Variante com campos nomeados (similar a uma struct)
+    /// Usada quando a API retorna um erro HTTP
     ApiError {
-        status: u16,      // Código HTTP (200, 404, 500, etc.)
-        message: String,  // Mensagem de erro
+        /// Código HTTP (200, 404, 500, etc.)
+        /// 0 quando a mensagem de erro veio no corpo de uma resposta de sucesso
+        status: u16,
+        /// Mensagem de erro (campo "message" do corpo JSON)
+        message: String,
+        /// Link para a documentação que o GitHub costuma mandar junto
+        documentation_url: Option<String>,
+    },
+
+    /// A API recusou a requisição por excesso de chamadas (403/429)
+    /// reset_at é quando o limite volta, se o GitHub informou (header x-ratelimit-reset)
+    RateLimited {
+        /// Quando o limite volta
+        reset_at: Option<Timestamp>,
+    },
+
+    /// Limite "secundário" (contra abusos): muitas requisições em pouco tempo,
+    /// mesmo com a cota por hora sobrando. O GitHub responde 403 (ou 429) com Retry-After
+    SecondaryRateLimit {
+        /// Segundos a esperar antes de tentar de novo (header Retry-After)
+        retry_after: u64,
+    },
+
+    /// Variante para erros no parsing de JSON
+    /// source existe quando o erro veio de outra camada (ex: falha ao ler o corpo)
+    ParseError {
+        /// O que deu errado
+        message: String,
+        /// Posição do evento no array (0 = o mais recente), se o erro for de um evento
+        event_index: Option<usize>,
+        /// Trecho do JSON perto do problema, em uma linha (vazio se não houver)
+        snippet: String,
+        /// O erro original, se houver
+        source: Option<Box<dyn Error + Send + Sync>>,
+    },
+
+    /// Valor inválido no arquivo de configuração ou em uma variável de ambiente
+    /// A mensagem já diz a origem: "/home/me/.config/github-activity/config: line 3: ..."
+    InvalidConfig(String),
+
+    /// Variante para argumentos de linha de comando inválidos
+    /// (flag desconhecida, valor faltando, número mal formatado...)
+    InvalidArgument(String),
+
+    /// Com vários usernames, a busca de alguns falhou
+    /// O erro de cada um já foi exibido junto da sua listagem; este só resume
+    SomeTargetsFailed {
+        /// Os usernames (ou owner/repo) que falharam, na ordem digitada
+        failed: Vec<String>,
+        /// O primeiro erro: decide o código de saída
+        first: Box<ActivityError>,
     },
 
-    // Variante para erros no parsing de JSON
-    ParseError(String),
+    /// --compare: a busca de um dos dois usuários falhou, e sem ela não há comparação
+    CompareFailed {
+        /// O usuário cuja busca falhou
+        user: String,
+        /// O erro da busca: decide o código de saída
+        source: Box<ActivityError>,
+    },
+
+    /// --open: o programa que abre links (xdg-open, open, start) não existe ou falhou
+    BrowserError {
+        /// O endereço que seria aberto, para o usuário abri-lo à mão
+        url: String,
+        /// O que deu errado, ex: "'xdg-open' was not found"
+        reason: String,
+    },
+
+    /// --post-webhook: o webhook não respondeu, ou respondeu com um erro (mesmo depois das novas tentativas)
+    WebhookError {
+        /// O endereço, sem o caminho (ver logging::redact_url): nele costuma estar o segredo
+        url: String,
+        /// O que deu errado, ex: "HTTP 500" ou "Connection Failed"
+        reason: String,
+    },
+
+    /// Ctrl-C no meio da busca: o que já tinha chegado foi mostrado, o resto não
+    Interrupted {
+        /// Quantos eventos foram mostrados
+        shown: usize,
+    },
+
+    /// --doctor: alguma verificação falhou (cada uma já foi mostrada com o motivo)
+    ChecksFailed {
+        /// Quantas falharam
+        failed: usize,
+        /// Quantas foram feitas
+        total: usize,
+    },
 
-    // Variante sem dados associados
-    // Usada quando não há eventos para mostrar
+    /// Variante sem dados associados
+    /// Usada quando não há eventos para mostrar
+    /// (Reservada: hoje uma lista vazia não é tratada como erro)
     NoEventsFound,
 }
 
@@ -38,37 +199,225 @@ impl fmt::Display for ActivityError {
     // Método obrigatório da trait Display
     // &self é uma referência imutável ao próprio objeto (borrowing)
     // f é onde escrevemos o output formatado
+    // O texto vem no idioma escolhido (ver o módulo i18n)
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message(i18n::messages()))
+    }
+}
+
+impl ActivityError {
+    // A mensagem de Display, com as frases de `messages`
+    // Separada de fmt() para que os testes escolham o idioma
+    fn message(&self, messages: &dyn Messages) -> String {
         // CONCEITO: Pattern Matching
         // match é extremamente poderoso em Rust - permite "desconstruir" enums
         // O compilador garante que todos os casos sejam tratados (exhaustive matching)
         match self {
             // Para cada variante, extraímos os dados usando pattern matching
             // msg é uma referência aos dados dentro da variante
-            ActivityError::NetworkError(msg) => {
-                write!(f, "Network error: {}", msg)
+            ActivityError::NetworkError { msg, .. } => messages.network_error(msg),
+            ActivityError::IoError { path, source } => messages.io_error(path, &source.to_string()),
+            ActivityError::InvalidUsername(username) => messages.invalid_username(username),
+            // O corpo JSON do 404 não acrescenta nada, então não é exibido
+            ActivityError::UserNotFound(username) => messages.user_not_found(username),
+            // Aqui desconstruímos os campos nomeados
+            ActivityError::ApiError { status, message, documentation_url } => {
+                let mut text = messages.api_error(*status, message);
+                // A documentação vai numa segunda linha, para não poluir a mensagem
+                if let Some(url) = documentation_url {
+                    text.push_str(&messages.see_documentation(url));
+                }
+                text
             }
-            ActivityError::InvalidUsername(username) => {
-                write!(f, "Invalid username: '{}'", username)
+            ActivityError::RateLimited { reset_at } => {
+                let reset_at = reset_at.map(|reset_at| reset_at.to_string());
+                messages.rate_limited(reset_at.as_deref())
             }
-            // Aqui desconstruímos os campos nomeados
-            ActivityError::ApiError { status, message } => {
-                write!(f, "GitHub API error (status {}): {}", status, message)
+            ActivityError::SecondaryRateLimit { retry_after } => messages.secondary_rate_limit(*retry_after),
+            ActivityError::ParseError { message, event_index, snippet, .. } => {
+                let mut text = messages.parse_failed(*event_index, message);
+                if !snippet.is_empty() {
+                    text.push_str(&messages.near(snippet));
+                }
+                text
             }
-            ActivityError::ParseError(msg) => {
-                write!(f, "Failed to parse response: {}", msg)
+            ActivityError::InvalidArgument(msg) => messages.invalid_argument(msg),
+            ActivityError::InvalidConfig(msg) => messages.invalid_config(msg),
+            ActivityError::SomeTargetsFailed { failed, .. } => messages.targets_failed(&failed.join(", ")),
+            ActivityError::CompareFailed { user, source } => {
+                messages.compare_failed(user, &source.message(messages))
+            }
+            ActivityError::BrowserError { url, reason } => messages.browser_error(url, reason),
+            ActivityError::WebhookError { url, reason } => messages.webhook_error(url, reason),
+            ActivityError::Interrupted { shown } => messages.interrupted(*shown),
+            ActivityError::ChecksFailed { failed, total } => messages.checks_failed(*failed, *total),
+            ActivityError::NoEventsFound => messages.no_events_found(),
+        }
+    }
+
+    /// Atalho para um ParseError sem erro de origem (o caso comum no parser)
+    // CONCEITO: impl Into<String>
+    // Aceita tanto &str quanto String (ou qualquer tipo conversível em String)
+    pub fn parse(message: impl Into<String>) -> Self {
+        ActivityError::ParseError {
+            message: message.into(),
+            event_index: None,
+            snippet: String::new(),
+            source: None,
+        }
+    }
+
+    /// Erro de leitura/escrita de um arquivo local, guardando o caminho
+    pub fn io(path: impl Into<String>, source: std::io::Error) -> Self {
+        ActivityError::IoError { path: path.into(), source }
+    }
+
+    /// Marca em qual evento do array o ParseError aconteceu
+    /// Outros erros voltam sem mudança
+    // CONCEITO: mut self
+    // O método toma posse do erro, altera e devolve - encadeável como um builder
+    pub fn in_event(mut self, index: usize) -> Self {
+        if let ActivityError::ParseError { event_index, .. } = &mut self {
+            *event_index = Some(index);
+        }
+        self
+    }
+
+    /// Preenche o trecho do JSON de um ParseError que ainda não tem um
+    // CONCEITO: FnOnce
+    // O trecho só é calculado se for mesmo usado
+    pub fn near(mut self, make_snippet: impl FnOnce() -> String) -> Self {
+        if let ActivityError::ParseError { snippet, .. } = &mut self {
+            if snippet.is_empty() {
+                *snippet = make_snippet();
             }
-            ActivityError::NoEventsFound => {
-                write!(f, "No recent events found")
+        }
+        self
+    }
+
+    /// Código de saída do processo para este erro (ver EXIT_CODES)
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ActivityError::InvalidUsername(_)
+            | ActivityError::InvalidArgument(_)
+            | ActivityError::InvalidConfig(_) => EXIT_USAGE,
+            ActivityError::NetworkError { .. } => EXIT_NETWORK,
+            ActivityError::IoError { .. } => EXIT_IO,
+            // CONCEITO: Literais em padrões
+            // status: 404 só casa quando o campo vale exatamente 404
+            ActivityError::UserNotFound(_) | ActivityError::ApiError { status: 404, .. } => {
+                EXIT_NOT_FOUND
             }
+            ActivityError::ApiError { .. } => EXIT_API,
+            ActivityError::ParseError { .. } => EXIT_PARSE,
+            ActivityError::RateLimited { .. } | ActivityError::SecondaryRateLimit { .. } => EXIT_RATE_LIMIT,
+            ActivityError::SomeTargetsFailed { first, .. } => first.exit_code(),
+            ActivityError::CompareFailed { source, .. } => source.exit_code(),
+            ActivityError::BrowserError { .. } => EXIT_BROWSER,
+            ActivityError::WebhookError { .. } => EXIT_WEBHOOK,
+            ActivityError::Interrupted { .. } => EXIT_INTERRUPTED,
+            ActivityError::ChecksFailed { .. } => EXIT_DOCTOR,
+            // Não há eventos não é uma falha
+            ActivityError::NoEventsFound => 0,
+        }
+    }
+
+    /// Nome estável da categoria do erro, em snake_case ("rate_limited", "user_not_found"...)
+    ///
+    /// É o campo `kind` do erro em JSON (--json, --format ndjson): scripts decidem por ele,
+    /// não pela mensagem, que muda com o idioma. Um nome publicado não muda mais;
+    /// uma variante nova ganha um nome novo
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ActivityError::NetworkError { .. } => "network_error",
+            ActivityError::IoError { .. } => "io_error",
+            ActivityError::InvalidUsername(_) => "invalid_username",
+            ActivityError::UserNotFound(_) => "user_not_found",
+            ActivityError::ApiError { .. } => "api_error",
+            ActivityError::RateLimited { .. } => "rate_limited",
+            ActivityError::SecondaryRateLimit { .. } => "secondary_rate_limit",
+            ActivityError::ParseError { .. } => "parse_error",
+            ActivityError::InvalidConfig(_) => "invalid_config",
+            ActivityError::InvalidArgument(_) => "invalid_argument",
+            ActivityError::SomeTargetsFailed { .. } => "some_targets_failed",
+            ActivityError::CompareFailed { .. } => "compare_failed",
+            ActivityError::BrowserError { .. } => "browser_error",
+            ActivityError::WebhookError { .. } => "webhook_error",
+            ActivityError::Interrupted { .. } => "interrupted",
+            ActivityError::ChecksFailed { .. } => "checks_failed",
+            ActivityError::NoEventsFound => "no_events_found",
         }
     }
+
+    /// Qualquer um dos dois limites de requisições (o por hora ou o secundário)
+    /// Depois de um deles, as próximas requisições falhariam do mesmo jeito
+    pub fn is_rate_limit(&self) -> bool {
+        matches!(self, ActivityError::RateLimited { .. } | ActivityError::SecondaryRateLimit { .. })
+    }
+
+    /// Classifica uma resposta HTTP de erro
+    /// Recebe os headers já extraídos (em vez do ureq::Response) para ser testável
+    ///
+    /// O GitHub sinaliza limite esgotado com 429, ou com 403 e x-ratelimit-remaining: 0
+    /// (um 403 comum - ex: recurso bloqueado - continua sendo ApiError).
+    /// O limite secundário vem com Retry-After e/ou a mensagem "secondary rate limit"
+    pub fn from_status(
+        status: u16,
+        ratelimit_remaining: Option<&str>,
+        ratelimit_reset: Option<&str>,
+        retry_after: Option<&str>,
+        body: &str,
+    ) -> ActivityError {
+        // {"message": "...", "documentation_url": "..."} vira campos separados
+        let (message, documentation_url) = parser::parse_error_body(body);
+
+        // Com a cota por hora zerada, o limite é o primário, mesmo que venha Retry-After
+        let retry_after = retry_after.and_then(|value| value.trim().parse::<u64>().ok());
+        let secondary = matches!(status, 403 | 429)
+            && ratelimit_remaining != Some("0")
+            && (retry_after.is_some() || message.to_lowercase().contains("secondary rate limit"));
+        if secondary {
+            return ActivityError::SecondaryRateLimit {
+                retry_after: retry_after.unwrap_or(SECONDARY_RATE_LIMIT_WAIT),
+            };
+        }
+
+        let rate_limited = status == 429 || (status == 403 && ratelimit_remaining == Some("0"));
+
+        if rate_limited {
+            // x-ratelimit-reset vem em segundos desde a época Unix
+            let reset_at = ratelimit_reset
+                .and_then(|value| value.trim().parse().ok())
+                .map(Timestamp::from_epoch_seconds);
+            return ActivityError::RateLimited { reset_at };
+        }
+
+        ActivityError::ApiError { status, message, documentation_url }
+    }
 }
 
 // CONCEITO: Trait std::error::Error
 // Esta é a trait padrão para tipos de erro em Rust
 // Implementá-la permite que nosso erro seja compatível com o ecossistema Rust
-impl std::error::Error for ActivityError {}
+//
+// source() devolve o erro que causou este, permitindo percorrer a cadeia
+// (é o que o anyhow usa para imprimir "Caused by: ...")
+impl Error for ActivityError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            // CONCEITO: as_deref + coerção
+            // &Box<dyn Error + Send + Sync> vira &(dyn Error + Send + Sync),
+            // que o compilador converte para &dyn Error (os marcadores extras "somem")
+            ActivityError::NetworkError { source, .. } | ActivityError::ParseError { source, .. } => {
+                source.as_deref().map(|source| source as &(dyn Error + 'static))
+            }
+            ActivityError::IoError { source, .. } => Some(source),
+            ActivityError::SomeTargetsFailed { first, .. } => Some(first.as_ref()),
+            ActivityError::CompareFailed { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 // CONCEITO: Conversão automática de erros com From
 // A trait From permite conversão automática entre tipos
@@ -77,7 +426,11 @@ impl From<std::io::Error> for ActivityError {
     fn from(err: std::io::Error) -> Self {
         // Convertemos um erro de I/O em nosso tipo customizado
         // Self refere-se ao tipo que estamos implementando (ActivityError)
-        ActivityError::NetworkError(err.to_string())
+        // O io::Error original é preservado como source
+        ActivityError::NetworkError {
+            msg: err.to_string(),
+            source: Some(Box::new(err)),
+        }
     }
 }
 
@@ -88,20 +441,384 @@ impl From<Box<ureq::Error>> for ActivityError {
         match *err {
             // Erro de status HTTP (404, 500, etc.)
             ureq::Error::Status(code, response) => {
+                // Os headers precisam ser copiados antes: ler o corpo consome a resposta
+                let remaining = response.header("x-ratelimit-remaining").map(str::to_string);
+                let reset = response.header("x-ratelimit-reset").map(str::to_string);
+                let retry_after = response.header("retry-after").map(str::to_string);
+
                 // Tentamos ler o corpo da resposta para obter a mensagem de erro
-                let message = response
-                    .into_string()
-                    .unwrap_or_else(|_| String::from("Unknown error"));
+                // (um corpo ilegível fica vazio e vira "Unknown error" em parse_error_body)
+                let body = crate::api::read_body(response).unwrap_or_default();
 
-                ActivityError::ApiError {
-                    status: code,
-                    message,
-                }
+                ActivityError::from_status(
+                    code,
+                    remaining.as_deref(),
+                    reset.as_deref(),
+                    retry_after.as_deref(),
+                    &body,
+                )
             }
             // Erro de transporte (sem conexão, timeout, etc.)
             ureq::Error::Transport(transport) => {
-                ActivityError::NetworkError(transport.to_string())
+                ActivityError::NetworkError {
+                    msg: transport.to_string(),
+                    source: Some(Box::new(transport)),
+                }
+            }
+        }
+    }
+}
+
+// Conversão de erros do reqwest (o cliente HTTP da feature "async")
+// Respostas de erro (404, 403...) são tratadas antes, em async_api; aqui chegam
+// só falhas de transporte ou de leitura do corpo
+#[cfg(feature = "async")]
+impl From<reqwest::Error> for ActivityError {
+    fn from(err: reqwest::Error) -> Self {
+        ActivityError::NetworkError {
+            msg: err.to_string(),
+            source: Some(Box::new(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Lang;
+
+    fn api_error(status: u16) -> ActivityError {
+        ActivityError::ApiError { status, message: String::new(), documentation_url: None }
+    }
+
+    #[test]
+    fn test_exit_codes_per_category() {
+        assert_eq!(ActivityError::InvalidArgument("x".to_string()).exit_code(), EXIT_USAGE);
+        assert_eq!(ActivityError::InvalidUsername("x".to_string()).exit_code(), EXIT_USAGE);
+        assert_eq!(ActivityError::InvalidConfig("x".to_string()).exit_code(), EXIT_USAGE);
+        assert_eq!(ActivityError::NetworkError { msg: "x".to_string(), source: None }.exit_code(), EXIT_NETWORK);
+        assert_eq!(api_error(500).exit_code(), EXIT_API);
+        assert_eq!(api_error(404).exit_code(), EXIT_NOT_FOUND);
+        assert_eq!(ActivityError::UserNotFound("x".to_string()).exit_code(), EXIT_NOT_FOUND);
+        assert_eq!(ActivityError::parse("x").exit_code(), EXIT_PARSE);
+        assert_eq!(ActivityError::RateLimited { reset_at: None }.exit_code(), EXIT_RATE_LIMIT);
+        assert_eq!(ActivityError::io("x", std::io::ErrorKind::NotFound.into()).exit_code(), EXIT_IO);
+        let browser = ActivityError::BrowserError { url: "x".to_string(), reason: "x".to_string() };
+        assert_eq!(browser.exit_code(), EXIT_BROWSER);
+        let webhook = ActivityError::WebhookError { url: "x".to_string(), reason: "x".to_string() };
+        assert_eq!(webhook.exit_code(), EXIT_WEBHOOK);
+        assert_eq!(ActivityError::Interrupted { shown: 3 }.exit_code(), EXIT_INTERRUPTED);
+        assert_eq!(ActivityError::ChecksFailed { failed: 1, total: 5 }.exit_code(), EXIT_DOCTOR);
+        assert_eq!(ActivityError::NoEventsFound.exit_code(), 0);
+    }
+
+    #[test]
+    fn test_some_targets_failed_uses_the_first_error() {
+        let error = ActivityError::SomeTargetsFailed {
+            failed: vec!["torvlads".to_string(), "rust-lang/rsut".to_string()],
+            first: Box::new(ActivityError::UserNotFound("torvlads".to_string())),
+        };
+        assert_eq!(error.exit_code(), EXIT_NOT_FOUND);
+        assert_eq!(error.to_string(), "Could not show the activity of torvlads, rust-lang/rsut");
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn test_compare_failed_names_the_user() {
+        let error = ActivityError::CompareFailed {
+            user: "bob".to_string(),
+            source: Box::new(ActivityError::RateLimited { reset_at: None }),
+        };
+        assert_eq!(error.exit_code(), EXIT_RATE_LIMIT);
+        assert!(error.to_string().starts_with("Could not compare: fetching 'bob' failed: "), "{}", error);
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn test_exit_codes_are_documented_and_distinct() {
+        let mut codes: Vec<i32> = EXIT_CODES.iter().map(|(code, _)| *code).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), EXIT_CODES.len());
+    }
+
+    // Um exemplo de cada variante: uma variante nova entra aqui (e ganha um `kind` novo)
+    fn one_of_each() -> Vec<ActivityError> {
+        vec![
+            ActivityError::NetworkError { msg: String::new(), source: None },
+            ActivityError::io("events.json", std::io::Error::from(std::io::ErrorKind::NotFound)),
+            ActivityError::InvalidUsername("a b".to_string()),
+            ActivityError::UserNotFound("ghost".to_string()),
+            api_error(500),
+            ActivityError::RateLimited { reset_at: None },
+            ActivityError::SecondaryRateLimit { retry_after: 60 },
+            ActivityError::parse("bad"),
+            ActivityError::InvalidConfig(String::new()),
+            ActivityError::InvalidArgument(String::new()),
+            ActivityError::SomeTargetsFailed { failed: Vec::new(), first: Box::new(api_error(500)) },
+            ActivityError::CompareFailed { user: String::new(), source: Box::new(api_error(500)) },
+            ActivityError::BrowserError { url: String::new(), reason: String::new() },
+            ActivityError::WebhookError { url: String::new(), reason: String::new() },
+            ActivityError::Interrupted { shown: 0 },
+            ActivityError::ChecksFailed { failed: 1, total: 2 },
+            ActivityError::NoEventsFound,
+        ]
+    }
+
+    #[test]
+    fn test_kinds_are_unique_snake_case() {
+        let errors = one_of_each();
+        let kinds: Vec<&str> = errors.iter().map(ActivityError::kind).collect();
+
+        for kind in &kinds {
+            assert!(!kind.is_empty() && !kind.starts_with('_') && !kind.ends_with('_') && !kind.contains("__"), "{}", kind);
+            assert!(kind.chars().all(|c| c.is_ascii_lowercase() || c == '_'), "{}", kind);
+        }
+        let unique: std::collections::HashSet<&str> = kinds.iter().copied().collect();
+        assert_eq!(unique.len(), kinds.len());
+    }
+
+    #[test]
+    fn test_kind_does_not_follow_the_wrapped_error() {
+        // O status não muda a categoria: 404 continua "api_error" (o exit code é que muda)
+        assert_eq!(api_error(404).kind(), "api_error");
+        let wrapped = ActivityError::SomeTargetsFailed { failed: Vec::new(), first: Box::new(api_error(404)) };
+        assert_eq!(wrapped.kind(), "some_targets_failed");
+    }
+
+    #[test]
+    fn test_from_status_detects_rate_limits() {
+        match ActivityError::from_status(403, Some("0"), Some("1717200000"), None, "") {
+            ActivityError::RateLimited { reset_at } => {
+                assert_eq!(reset_at, Some(Timestamp::from_epoch_seconds(1_717_200_000)));
+            }
+            other => panic!("expected RateLimited, got {:?}", other),
+        }
+
+        assert!(matches!(
+            ActivityError::from_status(429, None, None, None, ""),
+            ActivityError::RateLimited { reset_at: None }
+        ));
+
+        // 403 sem o header de limite é um erro comum de permissão
+        assert!(matches!(
+            ActivityError::from_status(403, Some("42"), None, None, ""),
+            ActivityError::ApiError { status: 403, .. }
+        ));
+    }
+
+    #[test]
+    fn test_from_status_detects_secondary_rate_limits() {
+        let body = r#"{"message": "You have exceeded a secondary rate limit. Please wait a few minutes before you try again."}"#;
+
+        assert!(matches!(
+            ActivityError::from_status(403, Some("4990"), None, Some("30"), body),
+            ActivityError::SecondaryRateLimit { retry_after: 30 }
+        ));
+        // Só a mensagem, sem Retry-After: espera o padrão
+        assert!(matches!(
+            ActivityError::from_status(403, None, None, None, body),
+            ActivityError::SecondaryRateLimit { retry_after: 60 }
+        ));
+        // Só o Retry-After também basta
+        assert!(matches!(
+            ActivityError::from_status(429, None, None, Some("5"), ""),
+            ActivityError::SecondaryRateLimit { retry_after: 5 }
+        ));
+        // Com a cota por hora zerada, o limite é o primário
+        assert!(matches!(
+            ActivityError::from_status(403, Some("0"), None, Some("5"), body),
+            ActivityError::RateLimited { .. }
+        ));
+        // Retry-After num 503 não tem nada a ver com limites
+        assert!(matches!(
+            ActivityError::from_status(503, None, None, Some("5"), ""),
+            ActivityError::ApiError { status: 503, .. }
+        ));
+    }
+
+    #[test]
+    fn test_secondary_rate_limit_message_says_how_long_to_wait() {
+        let error = ActivityError::SecondaryRateLimit { retry_after: 45 };
+        assert_eq!(
+            error.to_string(),
+            "GitHub API secondary rate limit exceeded; wait 45 seconds before trying again \
+             (or use --wait-on-limit)"
+        );
+        assert_eq!(error.exit_code(), EXIT_RATE_LIMIT);
+        assert!(error.is_rate_limit());
+        assert!(ActivityError::SecondaryRateLimit { retry_after: 1 }.to_string().contains("wait 1 second "));
+    }
+
+    #[test]
+    fn test_api_error_from_abuse_detection_body() {
+        let body = r#"{
+  "message": "You have triggered an abuse detection mechanism. Please wait a few minutes before you try again.",
+  "documentation_url": "https://docs.github.com/rest/overview/resources-in-the-rest-api#abuse-rate-limits"
+}"#;
+
+        let error = ActivityError::from_status(403, Some("4999"), None, None, body);
+        assert_eq!(
+            error.to_string(),
+            "GitHub API error (status 403): You have triggered an abuse detection mechanism. \
+             Please wait a few minutes before you try again.\n  \
+             See: https://docs.github.com/rest/overview/resources-in-the-rest-api#abuse-rate-limits"
+        );
+    }
+
+    #[test]
+    fn test_api_error_from_plain_text_proxy_body() {
+        let body = format!("<html><head><title>502 Bad Gateway</title></head>{}</html>", " ".repeat(300));
+
+        match ActivityError::from_status(502, None, None, None, &body) {
+            ActivityError::ApiError { status, message, documentation_url } => {
+                assert_eq!(status, 502);
+                assert!(message.starts_with("<html><head><title>502 Bad Gateway"));
+                assert_eq!(message.chars().count(), 200 + "...".len());
+                assert_eq!(documentation_url, None);
             }
+            other => panic!("expected ApiError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_api_error_without_status_message() {
+        let error = ActivityError::ApiError {
+            status: 0,
+            message: "Not Found".to_string(),
+            documentation_url: None,
+        };
+        assert_eq!(error.to_string(), "GitHub API error: Not Found");
+        assert_eq!(error.exit_code(), EXIT_API);
+    }
+
+    #[test]
+    fn test_parse_error_message_names_event_and_snippet() {
+        let error = ActivityError::parse("Missing 'repo.name' field")
+            .in_event(7)
+            .near(|| r#"{"type":"PushEvent","repo":{}}"#.to_string());
+        assert_eq!(
+            error.to_string(),
+            r#"Failed to parse event #7: Missing 'repo.name' field near: {"type":"PushEvent","repo":{}}"#
+        );
+
+        // near não substitui um trecho que já existe
+        let error = error.near(|| "other".to_string());
+        assert!(error.to_string().ends_with(r#"{"type":"PushEvent","repo":{}}"#));
+
+        // Sem evento e sem trecho: a mensagem de sempre
+        assert_eq!(ActivityError::parse("x").to_string(), "Failed to parse response: x");
+    }
+
+    #[test]
+    fn test_user_not_found_message() {
+        let message = ActivityError::UserNotFound("torvlads".to_string()).to_string();
+        assert_eq!(
+            message,
+            "User 'torvlads' was not found on GitHub. Check the spelling of the username"
+        );
+    }
+
+    #[test]
+    fn test_messages_in_portuguese() {
+        let pt = Lang::PtBr.messages();
+        assert_eq!(
+            ActivityError::UserNotFound("torvlads".to_string()).message(pt),
+            "O usuário 'torvlads' não foi encontrado no GitHub. Confira a grafia do nome de usuário"
+        );
+        let error = ActivityError::CompareFailed {
+            user: "octocat".to_string(),
+            source: Box::new(ActivityError::RateLimited { reset_at: None }),
+        };
+        // A causa também sai no idioma pedido
+        assert_eq!(
+            error.message(pt),
+            "Não foi possível comparar: a busca de 'octocat' falhou: \
+             Limite de requisições da API do GitHub esgotado; tente novamente mais tarde"
+        );
+        // Sem set_lang, Display continua em inglês
+        assert_eq!(error.to_string(), error.message(Lang::En.messages()));
+
+        let interrupted = ActivityError::Interrupted { shown: 1 };
+        assert_eq!(interrupted.message(pt), "Interrompido — mostrando o 1 evento buscado até agora");
+        assert_eq!(interrupted.to_string(), "Interrupted — showing 1 event fetched so far");
+
+        let checks = ActivityError::ChecksFailed { failed: 2, total: 5 };
+        assert_eq!(checks.message(pt), "2 de 5 verificações falharam");
+        assert_eq!(checks.to_string(), "2 of 5 checks failed");
+    }
+
+    #[test]
+    fn test_rate_limited_message() {
+        let error = ActivityError::RateLimited {
+            reset_at: Some(Timestamp::from_epoch_seconds(1_717_200_000)),
+        };
+        assert_eq!(
+            error.to_string(),
+            "GitHub API rate limit exceeded; it resets at 2024-06-01T00:00:00Z"
+        );
+    }
+
+    #[test]
+    fn test_io_error_is_kept_as_source() {
+        let io_error = std::io::Error::new(std::io::ErrorKind::TimedOut, "connection timed out");
+        let error = ActivityError::from(io_error);
+
+        // Display continua igual
+        assert_eq!(error.to_string(), "Network error: connection timed out");
+
+        // CONCEITO: downcast_ref
+        // Recupera o tipo concreto por trás de um &dyn Error
+        let source = error.source().expect("source should be preserved");
+        let io_source = source.downcast_ref::<std::io::Error>().unwrap();
+        assert_eq!(io_source.kind(), std::io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn test_io_error_names_the_path() {
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "No such file or directory");
+        let error = ActivityError::io("events.json", io_error);
+
+        assert_eq!(error.to_string(), "Could not access 'events.json': No such file or directory");
+        let source = error.source().expect("source should be preserved");
+        assert_eq!(
+            source.downcast_ref::<std::io::Error>().unwrap().kind(),
+            std::io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn test_source_chain_walks_to_the_root() {
+        // ParseError -> NetworkError -> io::Error
+        let io_error = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset");
+        let error = ActivityError::ParseError {
+            message: "Failed to read response".to_string(),
+            event_index: None,
+            snippet: String::new(),
+            source: Some(Box::new(ActivityError::from(io_error))),
+        };
+
+        // Percorre error, error.source(), error.source().source()... até um None
+        let mut chain = Vec::new();
+        let mut current: Option<&dyn Error> = Some(&error);
+        while let Some(e) = current {
+            chain.push(e.to_string());
+            current = e.source();
         }
+
+        assert_eq!(
+            chain,
+            vec![
+                "Failed to parse response: Failed to read response",
+                "Network error: connection reset",
+                "connection reset",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_errors_without_cause_have_no_source() {
+        assert!(ActivityError::parse("bad").source().is_none());
+        assert!(ActivityError::InvalidArgument("x".to_string()).source().is_none());
     }
 }
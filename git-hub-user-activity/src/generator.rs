@@ -0,0 +1,553 @@
+//! Este módulo gera dados de teste sintéticos: um array JSON de eventos
+//! com o mesmo formato que a API do GitHub devolve
+//! Serve para fixtures de testes, dados de demonstração e screenshots
+//!
+//! A geração é DETERMINÍSTICA: a mesma seed sempre produz exatamente a mesma saída
+//! Por isso não usamos o relógio do sistema nem crates de números aleatórios
+
+use crate::date::Timestamp;
+use crate::error::ActivityError;
+use crate::models::EventKind;
+
+// Instante de referência dos eventos gerados (2024-06-01T00:00:00Z)
+// Os timestamps são espalhados pelos 90 dias anteriores a este instante
+// Usar uma constante (e não o "agora") mantém a saída reproduzível
+const ANCHOR_EPOCH: i64 = 1_717_200_000;
+const WINDOW_SECS: i64 = 90 * 24 * 60 * 60;
+
+// Primeiro id de evento; os ids crescem junto com o tempo, como na API real
+const BASE_EVENT_ID: u64 = 30_000_000_000;
+
+// Logins dos atores (logins do GitHub são sempre ASCII)
+const ACTORS: &[&str] = &["octocat", "hubot", "mona-lisa", "dev-42", "jose-silva"];
+
+// Nomes de repositórios, incluindo Unicode de propósito
+const REPOS: &[&str] = &[
+    "octocat/hello-world",
+    "rust-lang/rust",
+    "jose-silva/café-api",
+    "dev-42/日本語-notes",
+    "mona-lisa/emoji-🚀-lab",
+    "hubot/ünïcödé",
+];
+
+const BRANCHES: &[&str] = &["main", "feature-x", "fix/parser-crash", "release/2.0", "josé/ação"];
+
+const WIKI_PAGES: &[&str] = &["Home", "Installation", "FAQ", "Guia-de-Contribuição"];
+
+// Textos "maldosos" que o parser precisa aguentar
+// Já estão no formato JSON (com escapes), exatamente como chegam da API:
+// aspas e barras escapadas, \n, \u com acento, pares surrogate de emoji,
+// chaves/colchetes dentro de strings e Unicode cru (CJK, emoji)
+const NASTY_TEXTS: &[&str] = &[
+    r#"Fix crash on startup"#,
+    r#"Handle \"quoted\" paths like C:\\temp\\new"#,
+    r#"First line\n\nSecond paragraph with\ttabs"#,
+    r#"Caf\u00e9 com a\u00e7\u00facar"#,
+    r#"Ship it \ud83d\ude80\ud83c\udf89"#,
+    r#"Braces {inside} [strings] and \"key\": \"value\" pairs"#,
+    r#"修复 Unicode 处理 — naïve résumé ✓"#,
+    r#"Trailing backslash \\"#,
+    r#"Emoji direto 🦀 sem escape"#,
+];
+
+// CONCEITO: Registro de tipos (kind registry)
+// Kind só dá um nome curto (--kinds) a cada models::EventKind: o template do
+// payload é escolhido pelo próprio EventKind (payload, mais abaixo)
+// Kind::of e payload usam match exaustivo sobre EventKind, sem braço "_":
+// um tipo novo em models só compila depois de ganhar um Kind e um template aqui
+/// Um tipo de evento que o gerador sabe produzir (nome curto usado em --kinds)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// "push": PushEvent
+    Push,
+    /// "issue": IssuesEvent
+    Issue,
+    /// "pr": PullRequestEvent
+    PullRequest,
+    /// "star": WatchEvent
+    Star,
+    /// "fork": ForkEvent
+    Fork,
+    /// "create": CreateEvent
+    Create,
+    /// "delete": DeleteEvent
+    Delete,
+    /// "release": ReleaseEvent
+    Release,
+    /// "issue-comment": IssueCommentEvent
+    IssueComment,
+    /// "review-comment": PullRequestReviewCommentEvent
+    ReviewComment,
+    /// "commit-comment": CommitCommentEvent
+    CommitComment,
+    /// "review": PullRequestReviewEvent
+    Review,
+    /// "member": MemberEvent
+    Member,
+    /// "public": PublicEvent
+    Public,
+    /// "wiki": GollumEvent
+    Wiki,
+}
+
+impl Kind {
+    /// Todas as variantes, na ordem usada quando --kinds não é informado
+    pub const ALL: [Kind; 15] = [
+        Kind::Push,
+        Kind::Issue,
+        Kind::PullRequest,
+        Kind::Star,
+        Kind::Fork,
+        Kind::Create,
+        Kind::Delete,
+        Kind::Release,
+        Kind::IssueComment,
+        Kind::ReviewComment,
+        Kind::CommitComment,
+        Kind::Review,
+        Kind::Member,
+        Kind::Public,
+        Kind::Wiki,
+    ];
+
+    /// Nome curto aceito em --kinds
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::Push => "push",
+            Kind::Issue => "issue",
+            Kind::PullRequest => "pr",
+            Kind::Star => "star",
+            Kind::Fork => "fork",
+            Kind::Create => "create",
+            Kind::Delete => "delete",
+            Kind::Release => "release",
+            Kind::IssueComment => "issue-comment",
+            Kind::ReviewComment => "review-comment",
+            Kind::CommitComment => "commit-comment",
+            Kind::Review => "review",
+            Kind::Member => "member",
+            Kind::Public => "public",
+            Kind::Wiki => "wiki",
+        }
+    }
+
+    /// O tipo de evento gerado (o campo "type" é `event_kind().name()`)
+    pub fn event_kind(self) -> EventKind {
+        match self {
+            Kind::Push => EventKind::Push,
+            Kind::Issue => EventKind::Issues,
+            Kind::PullRequest => EventKind::PullRequest,
+            Kind::Star => EventKind::Watch,
+            Kind::Fork => EventKind::Fork,
+            Kind::Create => EventKind::Create,
+            Kind::Delete => EventKind::Delete,
+            Kind::Release => EventKind::Release,
+            Kind::IssueComment => EventKind::IssueComment,
+            Kind::ReviewComment => EventKind::PullRequestReviewComment,
+            Kind::CommitComment => EventKind::CommitComment,
+            Kind::Review => EventKind::PullRequestReview,
+            Kind::Member => EventKind::Member,
+            Kind::Public => EventKind::Public,
+            Kind::Wiki => EventKind::Gollum,
+        }
+    }
+
+    /// O caminho inverso de event_kind: None só para EventKind::Other,
+    /// que não tem schema para gerar
+    pub fn of(kind: &EventKind) -> Option<Kind> {
+        match kind {
+            EventKind::Push => Some(Kind::Push),
+            EventKind::Issues => Some(Kind::Issue),
+            EventKind::PullRequest => Some(Kind::PullRequest),
+            EventKind::PullRequestReview => Some(Kind::Review),
+            EventKind::Watch => Some(Kind::Star),
+            EventKind::Fork => Some(Kind::Fork),
+            EventKind::Create => Some(Kind::Create),
+            EventKind::Delete => Some(Kind::Delete),
+            EventKind::Release => Some(Kind::Release),
+            EventKind::IssueComment => Some(Kind::IssueComment),
+            EventKind::PullRequestReviewComment => Some(Kind::ReviewComment),
+            EventKind::CommitComment => Some(Kind::CommitComment),
+            EventKind::Gollum => Some(Kind::Wiki),
+            EventKind::Member => Some(Kind::Member),
+            EventKind::Public => Some(Kind::Public),
+            EventKind::Other(_) => None,
+        }
+    }
+
+    /// Procura uma variante pelo nome curto
+    // CONCEITO: Iterator::find
+    // Retorna Some(primeiro elemento que satisfaz o predicado) ou None
+    pub fn from_name(name: &str) -> Option<Kind> {
+        Kind::ALL.iter().copied().find(|kind| kind.name() == name)
+    }
+}
+
+// Template do payload de cada tipo, seguindo o schema real da API
+fn payload(kind: &EventKind, rng: &mut Rng, actor: &str, repo: &str) -> String {
+    match kind {
+        EventKind::Push => {
+            let size = 1 + rng.below(5) as usize;
+            let commits: Vec<String> = (0..size)
+                .map(|_| {
+                    let sha = rng.sha();
+                    format!(
+                        r#"{{"sha":"{sha}","author":{{"email":"{actor}@users.noreply.github.com","name":"{actor}"}},"message":"{message}","distinct":true,"url":"https://api.github.com/repos/{repo}/commits/{sha}"}}"#,
+                        message = rng.pick(NASTY_TEXTS),
+                    )
+                })
+                .collect();
+            format!(
+                r#"{{"repository_id":{},"push_id":{},"size":{size},"distinct_size":{size},"ref":"refs/heads/{}","head":"{}","before":"{}","commits":[{}]}}"#,
+                rng.below(900_000_000) + 100_000_000,
+                rng.below(9_000_000_000) + 10_000_000_000,
+                rng.pick(BRANCHES),
+                rng.sha(),
+                rng.sha(),
+                commits.join(","),
+            )
+        }
+        EventKind::Issues => {
+            let action = *rng.pick(&["opened", "closed", "reopened"]);
+            let state = if action == "closed" { "closed" } else { "open" };
+            format!(
+                r#"{{"action":"{action}","issue":{}}}"#,
+                issue_object(rng, actor, repo, state),
+            )
+        }
+        EventKind::PullRequest => {
+            let number = 1 + rng.below(5000);
+            let action = *rng.pick(&["opened", "closed", "reopened"]);
+            let merged = action == "closed" && rng.below(2) == 0;
+            format!(
+                r#"{{"action":"{action}","number":{number},"pull_request":{{"url":"https://api.github.com/repos/{repo}/pulls/{number}","number":{number},"state":"{}","title":"{}","user":{{"login":"{actor}","id":{}}},"body":"{}","draft":false,"merged":{merged}}}}}"#,
+                if action == "closed" { "closed" } else { "open" },
+                rng.pick(NASTY_TEXTS),
+                rng.below(90_000_000) + 1,
+                rng.pick(NASTY_TEXTS),
+            )
+        }
+        EventKind::PullRequestReview => {
+            let number = 1 + rng.below(5000);
+            format!(
+                r#"{{"action":"created","review":{{"id":{},"user":{{"login":"{actor}"}},"body":"{}","state":"{}"}},"pull_request":{{"number":{number},"title":"{}","state":"open"}}}}"#,
+                rng.below(2_000_000_000) + 1,
+                rng.pick(NASTY_TEXTS),
+                rng.pick(&["approved", "changes_requested", "commented"]),
+                rng.pick(NASTY_TEXTS),
+            )
+        }
+        EventKind::Watch => r#"{"action":"started"}"#.to_string(),
+        EventKind::Fork => {
+            let name = repo.split('/').nth(1).unwrap_or(repo);
+            format!(
+                r#"{{"forkee":{{"id":{},"name":"{name}","full_name":"{actor}/{name}","private":false,"owner":{{"login":"{actor}"}},"description":"{}","fork":true}}}}"#,
+                rng.below(900_000_000) + 100_000_000,
+                rng.pick(NASTY_TEXTS),
+            )
+        }
+        EventKind::Create => {
+            let (ref_type, ref_name) = ref_for(rng);
+            format!(
+                r#"{{"ref":"{ref_name}","ref_type":"{ref_type}","master_branch":"main","description":null,"pusher_type":"user"}}"#
+            )
+        }
+        EventKind::Delete => {
+            let (ref_type, ref_name) = ref_for(rng);
+            format!(r#"{{"ref":"{ref_name}","ref_type":"{ref_type}","pusher_type":"user"}}"#)
+        }
+        EventKind::Release => {
+            let tag = format!("v{}.{}.{}", rng.below(4), rng.below(20), rng.below(10));
+            format!(
+                r#"{{"action":"published","release":{{"tag_name":"{tag}","name":"{}","draft":false,"prerelease":{},"body":"{}","author":{{"login":"{actor}"}}}}}}"#,
+                rng.pick(NASTY_TEXTS),
+                rng.below(4) == 0,
+                rng.pick(NASTY_TEXTS),
+            )
+        }
+        EventKind::IssueComment => format!(
+            r#"{{"action":"created","issue":{},"comment":{{"id":{},"body":"{}","user":{{"login":"{actor}"}}}}}}"#,
+            issue_object(rng, actor, repo, "open"),
+            rng.below(2_000_000_000) + 1,
+            rng.pick(NASTY_TEXTS),
+        ),
+        EventKind::PullRequestReviewComment => {
+            let number = 1 + rng.below(5000);
+            format!(
+                r#"{{"action":"created","comment":{{"id":{},"path":"src/main.rs","body":"{}","user":{{"login":"{actor}"}}}},"pull_request":{{"number":{number},"title":"{}","state":"open"}}}}"#,
+                rng.below(2_000_000_000) + 1,
+                rng.pick(NASTY_TEXTS),
+                rng.pick(NASTY_TEXTS),
+            )
+        }
+        EventKind::CommitComment => format!(
+            r#"{{"comment":{{"id":{},"commit_id":"{}","body":"{}","user":{{"login":"{actor}"}}}}}}"#,
+            rng.below(2_000_000_000) + 1,
+            rng.sha(),
+            rng.pick(NASTY_TEXTS),
+        ),
+        EventKind::Gollum => {
+            // Uma edição pode tocar várias páginas de uma vez
+            let pages: Vec<String> = (0..1 + rng.below(3))
+                .map(|_| {
+                    let page = rng.pick(WIKI_PAGES);
+                    format!(
+                        r#"{{"page_name":"{page}","title":"{page}","summary":null,"action":"{}","sha":"{}","html_url":"https://github.com/{repo}/wiki/{page}"}}"#,
+                        rng.pick(&["created", "edited"]),
+                        rng.sha(),
+                    )
+                })
+                .collect();
+            format!(r#"{{"pages":[{}]}}"#, pages.join(","))
+        }
+        EventKind::Member => {
+            let member = rng.pick(ACTORS);
+            format!(
+                r#"{{"action":"added","member":{{"login":"{member}","id":{},"type":"User"}}}}"#,
+                rng.below(90_000_000) + 1,
+            )
+        }
+        // A API manda o payload de PublicEvent sempre vazio
+        EventKind::Public => "{}".to_string(),
+        // Um tipo desconhecido não tem schema; o parser guarda o payload cru
+        EventKind::Other(_) => "{}".to_string(),
+    }
+}
+
+// Objeto "issue" compartilhado por IssuesEvent e IssueCommentEvent
+fn issue_object(rng: &mut Rng, actor: &str, repo: &str, state: &str) -> String {
+    let number = 1 + rng.below(5000);
+    format!(
+        r#"{{"url":"https://api.github.com/repos/{repo}/issues/{number}","number":{number},"title":"{}","user":{{"login":"{actor}"}},"labels":[],"state":"{state}","body":"{}"}}"#,
+        rng.pick(NASTY_TEXTS),
+        rng.pick(NASTY_TEXTS),
+    )
+}
+
+// Escolhe entre branch e tag para Create/DeleteEvent
+fn ref_for(rng: &mut Rng) -> (&'static str, String) {
+    if rng.below(3) == 0 {
+        ("tag", format!("v{}.{}.0", rng.below(4), rng.below(20)))
+    } else {
+        ("branch", rng.pick(BRANCHES).to_string())
+    }
+}
+
+/// Opções do subcomando `generate`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerateOptions {
+    /// Quantos eventos gerar (--events)
+    pub events: usize,
+    /// Semente do gerador (--seed)
+    pub seed: u64,
+    /// Tipos sorteados para cada evento (--kinds)
+    pub kinds: Vec<Kind>,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        GenerateOptions {
+            events: 30,  // O mesmo tamanho de página padrão da API
+            seed: 42,
+            kinds: Kind::ALL.to_vec(),
+        }
+    }
+}
+
+/// Converte "push,issue,star" em `Vec<Kind>`
+pub fn parse_kinds(list: &str) -> Result<Vec<Kind>, ActivityError> {
+    let mut kinds = Vec::new();
+
+    for name in list.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let kind = Kind::from_name(name).ok_or_else(|| {
+            let known: Vec<&str> = Kind::ALL.iter().map(|kind| kind.name()).collect();
+            ActivityError::InvalidArgument(format!(
+                "unknown event kind '{}' (expected one of: {})",
+                name,
+                known.join(", ")
+            ))
+        })?;
+
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+
+    if kinds.is_empty() {
+        return Err(ActivityError::InvalidArgument(
+            "--kinds needs at least one event kind".to_string(),
+        ));
+    }
+
+    Ok(kinds)
+}
+
+/// Gera o array JSON completo, do evento mais novo para o mais antigo (ordem da API)
+pub fn generate_events_json(options: &GenerateOptions) -> String {
+    let mut rng = Rng::new(options.seed);
+
+    // Sorteia os instantes primeiro e ordena: assim ids e datas ficam coerentes
+    let mut offsets: Vec<i64> = (0..options.events)
+        .map(|_| rng.below(WINDOW_SECS as u64) as i64)
+        .collect();
+    offsets.sort_unstable();
+
+    let events: Vec<String> = offsets
+        .iter()
+        .enumerate()
+        .map(|(i, offset)| {
+            // offsets[0] é o menor deslocamento, ou seja, o evento mais recente
+            let id = BASE_EVENT_ID + (options.events - i) as u64 * 1_000 + rng.below(1_000);
+            let created_at = Timestamp::from_epoch_seconds(ANCHOR_EPOCH - offset).to_string();
+            generate_event(&mut rng, &options.kinds, id, &created_at)
+        })
+        .collect();
+
+    format!("[\n{}\n]\n", events.join(",\n"))
+}
+
+// Gera um único evento (um objeto JSON)
+fn generate_event(rng: &mut Rng, kinds: &[Kind], id: u64, created_at: &str) -> String {
+    let kind = rng.pick(kinds).event_kind();
+    let actor = *rng.pick(ACTORS);
+    let repo = *rng.pick(REPOS);
+    let actor_id = rng.below(90_000_000) + 1;
+    let repo_id = rng.below(900_000_000) + 100_000_000;
+    let payload = payload(&kind, rng, actor, repo);
+
+    format!(
+        r#"  {{"id":"{id}","type":"{}","actor":{{"id":{actor_id},"login":"{actor}","display_login":"{actor}","gravatar_id":"","url":"https://api.github.com/users/{actor}","avatar_url":"https://avatars.githubusercontent.com/u/{actor_id}?"}},"repo":{{"id":{repo_id},"name":"{repo}","url":"https://api.github.com/repos/{repo}"}},"payload":{payload},"public":true,"created_at":"{created_at}"}}"#,
+        kind.name(),
+    )
+}
+
+// CONCEITO: PRNG determinístico
+// SplitMix64: pequeno, rápido e com boa distribuição para dados de teste
+// (NÃO serve para criptografia)
+/// Gerador de números pseudoaleatórios determinístico (SplitMix64)
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Gerador que sempre produz a mesma sequência para a mesma seed
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    /// Próximo número da sequência
+    pub fn next_u64(&mut self) -> u64 {
+        // wrapping_* evita panic de overflow em modo debug: aqui o overflow é intencional
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Número em [0, bound)
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// Escolhe um elemento da slice (que não pode estar vazia)
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    // SHA de commit falso: 40 dígitos hexadecimais
+    fn sha(&mut self) -> String {
+        (0..40)
+            .map(|_| char::from_digit(self.below(16) as u32, 16).unwrap_or('0'))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EventPayload;
+    use crate::parser;
+
+    #[test]
+    fn test_same_seed_same_output() {
+        let options = GenerateOptions::default();
+        assert_eq!(generate_events_json(&options), generate_events_json(&options));
+    }
+
+    #[test]
+    fn test_different_seed_different_output() {
+        let a = GenerateOptions { seed: 1, ..GenerateOptions::default() };
+        let b = GenerateOptions { seed: 2, ..GenerateOptions::default() };
+        assert_ne!(generate_events_json(&a), generate_events_json(&b));
+    }
+
+    #[test]
+    fn test_round_trips_through_parser_without_skipping() {
+        for seed in 0..20 {
+            let options = GenerateOptions { events: 50, seed, kinds: Kind::ALL.to_vec() };
+            let json = generate_events_json(&options);
+            let outcome = parser::parse_events(&json).expect("generated JSON must parse");
+            assert!(outcome.warnings.is_empty(), "seed {}: {:?}", seed, outcome.warnings);
+            assert_eq!(outcome.events.len(), 50, "seed {} skipped events", seed);
+        }
+    }
+
+    #[test]
+    fn test_every_event_kind_is_generated_and_round_trips() {
+        // Kind::of é exaustivo sobre EventKind: todo tipo menos Other tem um Kind em ALL
+        for kind in Kind::ALL {
+            let event_kind = kind.event_kind();
+            assert_eq!(Kind::of(&event_kind), Some(kind));
+
+            let options = GenerateOptions { events: 20, seed: 3, kinds: vec![kind] };
+            let outcome = parser::parse_events(&generate_events_json(&options)).unwrap();
+            assert!(outcome.warnings.is_empty(), "{}: {:?}", kind.name(), outcome.warnings);
+            assert_eq!(outcome.events.len(), 20, "{}", kind.name());
+            for event in outcome.events {
+                assert_eq!(event.kind, event_kind);
+                assert!(!matches!(event.payload, EventPayload::Unknown { .. }), "{:?}", event);
+            }
+        }
+        assert_eq!(Kind::of(&EventKind::Other("SponsorshipEvent".to_string())), None);
+    }
+
+    #[test]
+    fn test_only_requested_kinds_are_generated() {
+        let options = GenerateOptions {
+            events: 40,
+            seed: 7,
+            kinds: vec![Kind::Push, Kind::Star],
+        };
+        let events = parser::parse_events(&generate_events_json(&options)).unwrap().events;
+        assert!(events
+            .iter()
+            .all(|event| matches!(event.kind, EventKind::Push | EventKind::Watch)));
+    }
+
+    #[test]
+    fn test_parse_kinds() {
+        assert_eq!(parse_kinds("push, star,push").unwrap(), vec![Kind::Push, Kind::Star]);
+        assert!(parse_kinds("push,bogus").is_err());
+        assert!(parse_kinds("").is_err());
+    }
+
+    #[test]
+    fn test_every_kind_has_a_unique_name() {
+        for kind in Kind::ALL {
+            assert_eq!(Kind::from_name(kind.name()), Some(kind));
+        }
+    }
+
+    #[test]
+    fn test_generated_timestamps_are_parsed_and_newest_first() {
+        let options = GenerateOptions { events: 30, ..GenerateOptions::default() };
+        let events = parser::parse_events(&generate_events_json(&options)).unwrap().events;
+        let times: Vec<Timestamp> = events.iter().map(|e| e.created_at.unwrap()).collect();
+
+        assert!(times.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert!(times[0] <= Timestamp::from_epoch_seconds(ANCHOR_EPOCH));
+        assert!(times[29] >= Timestamp::from_epoch_seconds(ANCHOR_EPOCH - WINDOW_SECS));
+    }
+}
@@ -0,0 +1,348 @@
+//! Este módulo busca as contribuições de um usuário pela API GraphQL do GitHub
+//! (feature `graphql`, flag `--backend graphql`)
+//!
+//! O feed de eventos (REST) só guarda 90 dias e, em alguns formatos, vem sem o título
+//! do PR ou da issue. O `contributionsCollection` da API GraphQL cobre qualquer janela
+//! de até um ano e traz esses dados; cada contribuição vira um [`GitHubEvent`], para que
+//! a listagem, os filtros e o `--summary` funcionem sem saber de onde os eventos vieram:
+//!
+//! | Contribuição                            | Evento                                  |
+//! |-----------------------------------------|-----------------------------------------|
+//! | commits de um dia em um repositório      | `PushEvent` (com a contagem de commits) |
+//! | issue aberta                            | `IssuesEvent` "opened"                  |
+//! | pull request aberto                     | `PullRequestEvent` "opened"             |
+//! | revisão de um pull request              | `PullRequestReviewEvent`                |
+//! | repositório criado                      | `CreateEvent` (repository)              |
+//!
+//! Os eventos não têm id (a API GraphQL não tem um equivalente) e vêm do mais novo
+//! para o mais antigo, como os do feed. A API GraphQL só aceita requisições com token.
+//!
+//! ```no_run
+//! use github_activity::api::GitHubClient;
+//! use github_activity::date::Timestamp;
+//!
+//! let client = GitHubClient::builder().token("ghp_...").build();
+//! let to = Timestamp::now();
+//! let from = Timestamp::from_epoch_seconds(to.epoch_seconds() - 180 * 86_400);
+//! let outcome = client.fetch_contributions("torvalds", from, to)?;
+//! println!("{} contributions", outcome.events.len());
+//! # Ok::<(), github_activity::ActivityError>(())
+//! ```
+
+use std::cmp::Reverse;
+
+use crate::api::{self, GitHubClient};
+use crate::date::Timestamp;
+use crate::error::ActivityError;
+use crate::json::{self, Json, JsonValue};
+use crate::models::{EventKind, EventPayload, GitHubEvent};
+use crate::parser::{
+    self, extract_array_objects, extract_nested_object, extract_number_value, extract_string_value,
+    ParseOutcome, ParseWarning,
+};
+
+/// A consulta enviada: até 100 itens de cada tipo de contribuição na janela `$from`..`$to`
+/// (os commits vêm agrupados por repositório e por dia)
+pub const CONTRIBUTIONS_QUERY: &str = "query($login: String!, $from: DateTime!, $to: DateTime!) { \
+user(login: $login) { contributionsCollection(from: $from, to: $to) { \
+commitContributionsByRepository(maxRepositories: 100) { repository { nameWithOwner } \
+contributions(first: 100) { nodes { commitCount occurredAt } } } \
+issueContributions(first: 100) { nodes { occurredAt issue { number title repository { nameWithOwner } } } } \
+pullRequestContributions(first: 100) { nodes { occurredAt pullRequest { number title repository { nameWithOwner } } } } \
+pullRequestReviewContributions(first: 100) { nodes { occurredAt pullRequestReview { state } \
+pullRequest { number title } repository { nameWithOwner } } } \
+repositoryContributions(first: 100) { nodes { occurredAt repository { nameWithOwner } } } } } }";
+
+impl GitHubClient {
+    /// As contribuições de `username` entre `from` e `to`, como eventos (ver o módulo)
+    ///
+    /// Exige um token: sem ele, falha antes de qualquer requisição. A janela pode ter
+    /// no máximo um ano (a API responde com um erro, que vira [`ActivityError::ApiError`]).
+    /// `max_events`, se configurado, corta a lista; as outras opções de paginação não se aplicam
+    pub fn fetch_contributions(
+        &self,
+        username: &str,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> Result<ParseOutcome, ActivityError> {
+        self.check_username(username)?;
+        self.validate()?;
+        if self.token.is_none() {
+            return Err(ActivityError::InvalidArgument(
+                "the GraphQL API requires a token (--token or GITHUB_TOKEN)".to_string(),
+            ));
+        }
+
+        let body = self
+            .post_api(&self.graphql_url(), &request_body(username, from, to))
+            .map_err(|e| api::user_not_found(e, username))?;
+        let mut outcome = parse_contributions(&body).map_err(|e| api::user_not_found(e, username))?;
+
+        if let Some(max) = self.max_events {
+            outcome.events.truncate(max);
+        }
+        Ok(outcome)
+    }
+
+    pub(crate) fn graphql_url(&self) -> String {
+        api::join_url(&self.base_url, "graphql")
+    }
+}
+
+// O corpo do POST: {"query": "...", "variables": {"login": ..., "from": ..., "to": ...}}
+// As datas vão em RFC 3339 (o Display de Timestamp), o formato do tipo DateTime
+fn request_body(username: &str, from: Timestamp, to: Timestamp) -> String {
+    let variables = Json::object([
+        ("login", username.into()),
+        ("from", from.to_string().into()),
+        ("to", to.to_string().into()),
+    ]);
+    Json::object([("query", CONTRIBUTIONS_QUERY.into()), ("variables", variables)]).to_string()
+}
+
+/// Interpreta a resposta da consulta [`CONTRIBUTIONS_QUERY`]
+///
+/// A API GraphQL responde 200 mesmo quando a consulta falha: o erro vem em
+/// `{"errors": [{"message": ...}]}`, e a mensagem do primeiro vira um
+/// [`ActivityError::ApiError`] (com status 404 se o tipo for `NOT_FOUND`, 0 nos outros casos).
+/// Uma contribuição sem os campos esperados é pulada com um aviso, como um evento do feed
+pub fn parse_contributions(json_text: &str) -> Result<ParseOutcome, ActivityError> {
+    let value = json::parse(parser::response_body(json_text)?)?;
+    if let Some(error) = first_error(&value)? {
+        return Err(error);
+    }
+
+    // CONCEITO: Encadeando Options
+    // Cada and_then desce um nível; qualquer nível faltando dá None no final
+    let collection = value
+        .get("data")
+        .and_then(|data| extract_nested_object(data, "user"))
+        .and_then(|user| extract_nested_object(user, "contributionsCollection"))
+        .ok_or_else(|| ActivityError::parse("Expected data.user.contributionsCollection in the GraphQL response"))?;
+
+    // Cada contribuição, na ordem do documento: um evento ou o motivo de ter sido pulada
+    let mut results = Vec::new();
+    for by_repository in extract_array_objects(collection, "commitContributionsByRepository") {
+        for node in nodes(by_repository, "contributions") {
+            results.push(repository_name(by_repository).and_then(|repo_name| commit_event(node, repo_name)));
+        }
+    }
+    results.extend(nodes(collection, "issueContributions").map(issue_event));
+    results.extend(nodes(collection, "pullRequestContributions").map(pull_request_event));
+    results.extend(nodes(collection, "pullRequestReviewContributions").map(review_event));
+    results.extend(nodes(collection, "repositoryContributions").map(repository_event));
+
+    let mut outcome = ParseOutcome::default();
+    for (index, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(event) => outcome.events.push(event),
+            Err(error) => outcome.warnings.push(ParseWarning { index, error: error.in_event(index) }),
+        }
+    }
+
+    // Do mais novo para o mais antigo, como o feed; sem data, no fim
+    outcome.events.sort_by_key(|event| Reverse(event.created_at));
+    Ok(outcome)
+}
+
+// O primeiro item de "errors", se houver um
+fn first_error(value: &JsonValue) -> Result<Option<ActivityError>, ActivityError> {
+    let error = match extract_array_objects(value, "errors").next() {
+        Some(error) => error,
+        None => return Ok(None),
+    };
+
+    let message = extract_string_value(error, "message")?.unwrap_or_else(|| "Unknown error".to_string());
+    // Um login que não existe: o mesmo 404 do feed, que fetch_contributions transforma em UserNotFound
+    let status = match extract_string_value(error, "type")?.as_deref() {
+        Some("NOT_FOUND") => 404,
+        _ => 0,
+    };
+    Ok(Some(ActivityError::ApiError { status, message, documentation_url: None }))
+}
+
+// Os objetos de {"<key>": {"nodes": [...]}}
+fn nodes<'v, 'a>(json: &'v JsonValue<'a>, key: &str) -> impl Iterator<Item = &'v JsonValue<'a>> {
+    let connection = extract_nested_object(json, key);
+    connection.into_iter().flat_map(|connection| extract_array_objects(connection, "nodes"))
+}
+
+// "repository": {"nameWithOwner": "torvalds/linux"}
+fn repository_name(json: &JsonValue) -> Result<String, ActivityError> {
+    extract_nested_object(json, "repository")
+        .map(|repository| extract_string_value(repository, "nameWithOwner"))
+        .transpose()?
+        .flatten()
+        .ok_or_else(|| ActivityError::parse("Missing repository.nameWithOwner"))
+}
+
+// "occurredAt" (uma data inválida fica sem data, como o created_at de um evento)
+fn occurred_at(node: &JsonValue) -> Result<Option<Timestamp>, ActivityError> {
+    Ok(extract_string_value(node, "occurredAt")?.and_then(|text| Timestamp::parse_rfc3339(&text)))
+}
+
+// O objeto aninhado `key` de uma contribuição (o issue, o pullRequest...)
+fn required<'v, 'a>(node: &'v JsonValue<'a>, key: &str) -> Result<&'v JsonValue<'a>, ActivityError> {
+    extract_nested_object(node, key).ok_or_else(|| ActivityError::parse(format!("Missing '{}' object", key)))
+}
+
+fn commit_event(node: &JsonValue, repo_name: String) -> Result<GitHubEvent, ActivityError> {
+    let commit_count = extract_number_value(node, "commitCount")
+        .ok_or_else(|| ActivityError::parse("Missing 'commitCount' number"))?;
+    let payload = EventPayload::Push {
+        commit_count,
+        distinct_count: commit_count,
+        push_count: 1,
+        branch: None,
+        commits: Vec::new(),
+    };
+    Ok(GitHubEvent::new(EventKind::Push, repo_name, payload, occurred_at(node)?))
+}
+
+fn issue_event(node: &JsonValue) -> Result<GitHubEvent, ActivityError> {
+    let issue = required(node, "issue")?;
+    let payload = EventPayload::IssuesEvent {
+        action: "opened".to_string(),
+        number: extract_number_value(issue, "number"),
+        title: extract_string_value(issue, "title")?,
+    };
+    Ok(GitHubEvent::new(EventKind::Issues, repository_name(issue)?, payload, occurred_at(node)?))
+}
+
+fn pull_request_event(node: &JsonValue) -> Result<GitHubEvent, ActivityError> {
+    let pull_request = required(node, "pullRequest")?;
+    let payload = EventPayload::PullRequestEvent {
+        action: "opened".to_string(),
+        number: extract_number_value(pull_request, "number"),
+        title: extract_string_value(pull_request, "title")?,
+        merged: false,
+    };
+    Ok(GitHubEvent::new(EventKind::PullRequest, repository_name(pull_request)?, payload, occurred_at(node)?))
+}
+
+// O estado vem em maiúsculas ("APPROVED"); o feed usa minúsculas ("approved")
+fn review_event(node: &JsonValue) -> Result<GitHubEvent, ActivityError> {
+    let state = extract_string_value(required(node, "pullRequestReview")?, "state")?
+        .ok_or_else(|| ActivityError::parse("Missing pullRequestReview.state"))?;
+    let payload = EventPayload::PullRequestReview { action: "created".to_string(), state: state.to_lowercase() };
+    Ok(GitHubEvent::new(EventKind::PullRequestReview, repository_name(node)?, payload, occurred_at(node)?))
+}
+
+fn repository_event(node: &JsonValue) -> Result<GitHubEvent, ActivityError> {
+    let payload = EventPayload::CreateEvent { ref_type: "repository".to_string(), ref_name: None };
+    Ok(GitHubEvent::new(EventKind::Create, repository_name(node)?, payload, occurred_at(node)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::format_event;
+
+    const RESPONSE: &str = r#"{"data": {"user": {"contributionsCollection": {
+        "commitContributionsByRepository": [{
+            "repository": {"nameWithOwner": "torvalds/linux"},
+            "contributions": {"nodes": [
+                {"commitCount": 3, "occurredAt": "2024-05-02T00:00:00Z"},
+                {"commitCount": 1, "occurredAt": "2024-04-20T00:00:00Z"}
+            ]}
+        }],
+        "issueContributions": {"nodes": [
+            {"occurredAt": "2024-05-03T10:00:00Z",
+             "issue": {"number": 7, "title": "Crash", "repository": {"nameWithOwner": "a/b"}}}
+        ]},
+        "pullRequestContributions": {"nodes": [
+            {"occurredAt": "2024-05-04T10:00:00Z",
+             "pullRequest": {"number": 12, "title": "Fix \"quotes\"", "repository": {"nameWithOwner": "a/b"}}}
+        ]},
+        "pullRequestReviewContributions": {"nodes": [
+            {"occurredAt": "2024-05-01T10:00:00Z", "pullRequestReview": {"state": "APPROVED"},
+             "pullRequest": {"number": 3, "title": "x"}, "repository": {"nameWithOwner": "c/d"}}
+        ]},
+        "repositoryContributions": {"nodes": [
+            {"occurredAt": "2024-04-01T10:00:00Z", "repository": {"nameWithOwner": "torvalds/new"}}
+        ]}
+    }}}}"#;
+
+    #[test]
+    fn test_parse_contributions() {
+        let outcome = parse_contributions(RESPONSE).unwrap();
+        assert!(outcome.warnings.is_empty());
+
+        let lines: Vec<String> = outcome.events.iter().map(format_event).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "Opened pull request #12 'Fix \"quotes\"' in a/b",
+                "Opened issue #7 'Crash' in a/b",
+                "Pushed 3 commits to torvalds/linux",
+                "Approved a pull request in c/d",
+                "Pushed 1 commit to torvalds/linux",
+                "Created a repository in torvalds/new",
+            ]
+        );
+        assert!(outcome.events.iter().all(|event| event.id.is_none()));
+    }
+
+    #[test]
+    fn test_empty_body_and_bom() {
+        let error = parse_contributions("\u{feff} \n").unwrap_err();
+        assert_eq!(error.to_string(), "Failed to parse response: empty response from server");
+
+        let with_bom = parse_contributions(&format!("\u{feff}{}", RESPONSE)).unwrap();
+        assert_eq!(with_bom.events, parse_contributions(RESPONSE).unwrap().events);
+    }
+
+    #[test]
+    fn test_errors_in_a_200_response() {
+        let error = parse_contributions(
+            r#"{"data": {"user": null}, "errors": [{"type": "NOT_FOUND", "path": ["user"],
+                "message": "Could not resolve to a User with the login of 'nobody'."}]}"#,
+        )
+        .unwrap_err();
+        assert!(matches!(error, ActivityError::ApiError { status: 404, .. }), "{:?}", error);
+
+        let error = parse_contributions(
+            r#"{"errors": [{"message": "The total time spanned by 'from' and 'to' must not exceed 1 year"},
+                {"message": "second"}]}"#,
+        )
+        .unwrap_err();
+        match error {
+            ActivityError::ApiError { status: 0, message, .. } => assert!(message.starts_with("The total time")),
+            other => panic!("expected ApiError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_broken_contributions_become_warnings() {
+        let outcome = parse_contributions(
+            r#"{"data": {"user": {"contributionsCollection": {
+                "issueContributions": {"nodes": [
+                    {"occurredAt": "2024-05-03T10:00:00Z"},
+                    {"occurredAt": "2024-05-02T10:00:00Z", "issue": {"number": 1, "repository": {"nameWithOwner": "a/b"}}}
+                ]}
+            }}}}"#,
+        )
+        .unwrap();
+        assert_eq!(outcome.events.len(), 1);
+        assert_eq!(outcome.warnings.len(), 1);
+        assert_eq!(outcome.warnings[0].index, 0);
+        assert!(outcome.warnings[0].error.to_string().contains("Missing 'issue' object"));
+
+        assert!(parse_contributions(r#"{"data": {}}"#).is_err());
+        assert!(parse_contributions("[]").is_err());
+    }
+
+    #[test]
+    fn test_request_body() {
+        let body = request_body(
+            "octocat",
+            Timestamp::parse_date("2024-01-01").unwrap(),
+            Timestamp::parse_date("2024-06-01").unwrap(),
+        );
+        let value = json::parse(&body).unwrap();
+        let variables = value.get("variables").unwrap();
+        assert_eq!(extract_string_value(variables, "login").unwrap().as_deref(), Some("octocat"));
+        assert_eq!(extract_string_value(variables, "from").unwrap().as_deref(), Some("2024-01-01T00:00:00Z"));
+        assert_eq!(extract_string_value(&value, "query").unwrap().as_deref(), Some(CONTRIBUTIONS_QUERY));
+    }
+}
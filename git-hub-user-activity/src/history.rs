@@ -0,0 +1,314 @@
+//! Este módulo mantém o histórico local de eventos (--log-history e --history)
+//!
+//! Um arquivo JSONL por username, no diretório de dados do usuário
+//! (`~/.local/share/github-activity/torvalds.jsonl` no Linux). Cada linha é um
+//! evento como a API mandou, com um campo a mais, `fetched_at`:
+//!
+//! ```text
+//! {"fetched_at":"2024-05-01T12:00:00Z","id":"38941057321","type":"PushEvent",...}
+//! ```
+//!
+//! O arquivo só cresce: cada execução com --log-history acrescenta os eventos cujo
+//! id ainda não está no fim do arquivo (a API só devolve os 300 mais recentes, então
+//! um evento repetido só pode estar entre as últimas linhas). --history lê tudo de
+//! volta, como se fosse uma resposta da API, sem rede
+//!
+//! Duas execuções ao mesmo tempo não se atrapalham: quem escreve segura uma trava
+//! exclusiva do arquivo, quem lê uma compartilhada. Uma linha que não dá para ler
+//! (ex: uma escrita interrompida no meio) é pulada, e [`History::corrupt_lines`] diz qual
+
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::date::Timestamp;
+use crate::error::ActivityError;
+use crate::models::GitHubEvent;
+use crate::parser;
+
+// Subdiretório dentro do diretório de dados
+const DATA_SUBDIR: &str = "github-activity";
+
+// Quanto do fim do arquivo é lido para saber quais ids já estão lá
+// 300 eventos (o máximo que a API devolve) cabem com folga, mesmo pushes grandes
+const TAIL_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Os eventos lidos do histórico
+#[derive(Debug, Default)]
+pub struct History {
+    /// Do mais novo para o mais antigo, como a API, sem ids repetidos
+    pub events: Vec<GitHubEvent>,
+    /// Números (a partir de 1) das linhas que não deu para ler
+    pub corrupt_lines: Vec<usize>,
+}
+
+/// Caminho do histórico de um username, ou None se não há diretório de dados conhecido
+/// O nome do arquivo é o username em minúsculas: o GitHub não diferencia maiúsculas
+pub fn history_path(username: &str) -> Option<PathBuf> {
+    data_dir(|name| std::env::var_os(name).map(PathBuf::from))
+        .map(|dir| dir.join(DATA_SUBDIR).join(format!("{}.jsonl", username.to_lowercase())))
+}
+
+// Diretório de dados do usuário, seguindo a convenção de cada sistema
+// (o mesmo esquema de seen::cache_dir, com XDG_DATA_HOME no lugar de XDG_CACHE_HOME)
+fn data_dir(var: impl Fn(&str) -> Option<PathBuf>) -> Option<PathBuf> {
+    let absolute = |name: &str| var(name).filter(|path| path.is_absolute());
+
+    if let Some(dir) = absolute("XDG_DATA_HOME") {
+        return Some(dir);
+    }
+
+    if cfg!(windows) {
+        absolute("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        absolute("HOME").map(|home| home.join("Library").join("Application Support"))
+    } else {
+        absolute("HOME").map(|home| home.join(".local").join("share"))
+    }
+}
+
+/// Acrescenta ao histórico em `path` os eventos das páginas brutas `bodies` que
+/// ainda não estão nele, com `fetched_at`, e devolve quantos foram escritos
+///
+/// Os eventos entram do mais antigo para o mais novo: o arquivo fica em ordem cronológica.
+/// Eventos sem id ficam de fora, já que não haveria como reconhecê-los na próxima vez
+pub fn append(path: &Path, bodies: &[String], fetched_at: Timestamp) -> Result<usize, ActivityError> {
+    let io_error = |error| ActivityError::io(path.display().to_string(), error);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    let mut file = OpenOptions::new().read(true).append(true).create(true).open(path).map_err(io_error)?;
+
+    // CONCEITO: Trava de arquivo (File::lock)
+    // A trava é do sistema operacional e vale entre processos: outra execução
+    // espera aqui até esta terminar. Ela é solta quando `file` sai de escopo
+    file.lock().map_err(io_error)?;
+
+    let (mut known, ends_with_newline) = tail_ids(&mut file).map_err(io_error)?;
+    let mut text = String::new();
+    // Uma linha pela metade (escrita interrompida) não pode grudar na próxima
+    if !ends_with_newline {
+        text.push('\n');
+    }
+
+    let mut written = 0;
+    for body in bodies.iter().rev() {
+        for (id, event) in parser::raw_events(body).into_iter().rev() {
+            // insert devolve false se o id já estava lá (no arquivo ou nesta mesma leva)
+            if known.insert(id) {
+                text.push_str(&with_fetched_at(&event.to_string(), fetched_at));
+                text.push('\n');
+                written += 1;
+            }
+        }
+    }
+
+    if written > 0 {
+        // Uma escrita só: com append, ela vai inteira para o fim do arquivo
+        file.write_all(text.as_bytes()).map_err(io_error)?;
+    }
+    Ok(written)
+}
+
+// Os ids das linhas no fim do arquivo e se o arquivo termina com uma quebra de linha
+// (um arquivo vazio conta como terminado)
+fn tail_ids(file: &mut File) -> io::Result<(HashSet<u64>, bool)> {
+    let length = file.metadata()?.len();
+    let start = length.saturating_sub(TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+
+    let text = String::from_utf8_lossy(&bytes);
+    // Começando no meio do arquivo, a primeira linha está cortada
+    let lines = if start > 0 { text.split_once('\n').map_or("", |(_, rest)| rest) } else { &text };
+    let mut ids = HashSet::new();
+    for line in lines.lines() {
+        // Uma linha é um evento: o array de um elemento só reaproveita parser::raw_events
+        ids.extend(parser::raw_events(&format!("[{}]", line)).into_iter().map(|(id, _)| id));
+    }
+
+    Ok((ids, bytes.is_empty() || bytes.ends_with(b"\n")))
+}
+
+// Põe "fetched_at" como primeiro campo do objeto (compacto) do evento
+fn with_fetched_at(object: &str, fetched_at: Timestamp) -> String {
+    let field = format!("\"fetched_at\":\"{}\"", fetched_at);
+    match object.strip_prefix('{') {
+        Some("}") => format!("{{{}}}", field),
+        Some(rest) => format!("{{{},{}", field, rest),
+        None => object.to_string(),
+    }
+}
+
+/// Lê o histórico em `path`, só com os eventos a partir de `since` (se informado)
+///
+/// Um arquivo que não existe é um erro (não há histórico desse username ainda);
+/// linhas ilegíveis são puladas e contadas em [`History::corrupt_lines`]
+pub fn read(path: &Path, since: Option<Timestamp>) -> Result<History, ActivityError> {
+    let io_error = |error| ActivityError::io(path.display().to_string(), error);
+
+    let mut file = File::open(path).map_err(|error: io::Error| match error.kind() {
+        io::ErrorKind::NotFound => io_error(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no history yet: fetch with --log-history first",
+        )),
+        _ => io_error(error),
+    })?;
+    // Espera quem estiver escrevendo terminar
+    file.lock_shared().map_err(io_error)?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).map_err(io_error)?;
+
+    let mut history = History::default();
+    let mut seen = HashSet::new();
+    for (index, line) in String::from_utf8_lossy(&bytes).lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        // Cada linha é um evento como a API manda: o parser de sempre ignora o fetched_at
+        let event = match parser::parse_event(line) {
+            Ok(event) => event,
+            Err(_) => {
+                history.corrupt_lines.push(index + 1);
+                continue;
+            }
+        };
+
+        // Um id repetido (de um arquivo editado à mão, por exemplo) aparece uma vez só
+        if event.id.is_some_and(|id| !seen.insert(id)) {
+            continue;
+        }
+        if since.is_some_and(|since| event.created_at.is_none_or(|created_at| created_at < since)) {
+            continue;
+        }
+        history.events.push(event);
+    }
+
+    // Os ids crescem com o tempo: do maior para o menor é do mais novo para o mais antigo
+    history.events.sort_by_key(|event| std::cmp::Reverse(event.id));
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FETCHED_AT: &str = "2024-05-02T08:00:00Z";
+
+    // Uma página como a API manda, com os ids e horários dados
+    fn page(events: &[(u64, &str)]) -> String {
+        let objects: Vec<String> = events
+            .iter()
+            .map(|(id, created_at)| {
+                format!(
+                    r#"{{"id": "{}", "type": "WatchEvent", "repo": {{"name": "a/b"}},
+                        "payload": {{"action": "started"}}, "created_at": "{}"}}"#,
+                    id, created_at
+                )
+            })
+            .collect();
+        format!("[{}]", objects.join(","))
+    }
+
+    fn temp_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("github-activity-history-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("octocat.jsonl")
+    }
+
+    fn fetched_at() -> Timestamp {
+        Timestamp::parse_rfc3339(FETCHED_AT).unwrap()
+    }
+
+    fn ids(history: &History) -> Vec<u64> {
+        history.events.iter().filter_map(|event| event.id).collect()
+    }
+
+    #[test]
+    fn test_append_writes_one_line_per_event_oldest_first() {
+        let path = temp_file("append");
+        let body = page(&[(3, "2024-05-01T12:00:00Z"), (2, "2024-04-30T12:00:00Z")]);
+        assert_eq!(append(&path, &[body], fetched_at()).unwrap(), 2);
+
+        let text = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with(r#"{"fetched_at":"2024-05-02T08:00:00Z","id":"2","type":"WatchEvent""#), "{}", lines[0]);
+        assert!(lines[1].contains(r#""id":"3""#));
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_append_skips_ids_already_logged() {
+        let path = temp_file("dedup");
+        append(&path, &[page(&[(2, "2024-04-30T12:00:00Z"), (1, "2024-04-29T12:00:00Z")])], fetched_at()).unwrap();
+
+        // A busca seguinte repete o 2 e traz o 3; um id repetido na mesma leva entra uma vez
+        let next = page(&[(3, "2024-05-01T12:00:00Z"), (2, "2024-04-30T12:00:00Z")]);
+        let again = page(&[(3, "2024-05-01T12:00:00Z")]);
+        assert_eq!(append(&path, &[next, again], fetched_at()).unwrap(), 1);
+        assert_eq!(append(&path, &[page(&[(3, "2024-05-01T12:00:00Z")])], fetched_at()).unwrap(), 0);
+
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 3);
+        assert_eq!(ids(&read(&path, None).unwrap()), vec![3, 2, 1]);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_read_with_since() {
+        let path = temp_file("since");
+        let body = page(&[(3, "2024-05-01T12:00:00Z"), (2, "2024-04-30T12:00:00Z"), (1, "2024-04-01T00:00:00Z")]);
+        append(&path, &[body], fetched_at()).unwrap();
+
+        let since = Timestamp::parse_date("2024-04-30");
+        assert_eq!(ids(&read(&path, since).unwrap()), vec![3, 2]);
+        assert_eq!(ids(&read(&path, Timestamp::parse_date("2025-01-01")).unwrap()), Vec::<u64>::new());
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_lines_are_skipped() {
+        let path = temp_file("corrupt");
+        append(&path, &[page(&[(1, "2024-04-29T12:00:00Z")])], fetched_at()).unwrap();
+        // Uma escrita interrompida no meio: sem a quebra de linha no fim
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"fetched_at":"2024-05-02T08:00:00Z","id":"2","ty"#).unwrap();
+        drop(file);
+
+        // A próxima escrita começa numa linha nova, sem consertar (nem estragar) a cortada
+        append(&path, &[page(&[(3, "2024-05-01T12:00:00Z")])], fetched_at()).unwrap();
+
+        let history = read(&path, None).unwrap();
+        assert_eq!(ids(&history), vec![3, 1]);
+        assert_eq!(history.corrupt_lines, vec![2]);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_missing_history_is_an_error() {
+        let error = read(&temp_file("missing"), None).unwrap_err();
+        assert!(error.to_string().contains("--log-history"), "{}", error);
+    }
+
+    #[test]
+    fn test_with_fetched_at() {
+        assert_eq!(with_fetched_at(r#"{"id":"1"}"#, fetched_at()), r#"{"fetched_at":"2024-05-02T08:00:00Z","id":"1"}"#);
+        assert_eq!(with_fetched_at("{}", fetched_at()), r#"{"fetched_at":"2024-05-02T08:00:00Z"}"#);
+    }
+
+    #[test]
+    fn test_data_dir_per_platform() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| PathBuf::from(value))
+        };
+        assert_eq!(data_dir(env(&[("XDG_DATA_HOME", "/data"), ("HOME", "/home/me")])), Some(PathBuf::from("/data")));
+        // Relativo não vale (XDG): cai para o padrão do sistema
+        assert_eq!(data_dir(env(&[("XDG_DATA_HOME", "data")])), None);
+        if cfg!(all(unix, not(target_os = "macos"))) {
+            assert_eq!(data_dir(env(&[("HOME", "/home/me")])), Some(PathBuf::from("/home/me/.local/share")));
+        }
+    }
+}
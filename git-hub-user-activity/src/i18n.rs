@@ -0,0 +1,1103 @@
+//! Este módulo traduz as mensagens que o usuário lê: as linhas dos eventos,
+//! os cabeçalhos, os avisos de "nenhuma atividade" e os erros
+//!
+//! Cada idioma implementa a trait [`Messages`], com um método por mensagem.
+//! Os métodos recebem os números (e não um texto já no plural) porque cada
+//! idioma tem suas regras: "1 follower"/"2 followers", mas "1 repositório público"/
+//! "2 repositórios públicos" - o plural é decidido aqui, não em quem chama.
+//!
+//! O idioma vale para o processo inteiro: o programa escolhe um no começo do main
+//! com [`set_lang`], como faz com o logger. Sem isso, tudo sai em inglês:
+//!
+//! ```
+//! use github_activity::i18n::{self, Lang};
+//!
+//! assert_eq!(Lang::PtBr.messages().commits(3), "3 commits");
+//! assert_eq!(i18n::current(), Lang::En);
+//! ```
+
+use std::sync::OnceLock;
+
+use crate::display::{capitalize_first, review_verb};
+
+/// Idiomas disponíveis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    /// Inglês (o padrão)
+    #[default]
+    En,
+    /// Português do Brasil
+    PtBr,
+}
+
+impl Lang {
+    /// Interpreta o valor de --lang: "en" ou "pt-BR" (também "pt_BR" e "pt", sem diferenciar maiúsculas)
+    pub fn parse(text: &str) -> Result<Lang, String> {
+        match text.to_ascii_lowercase().replace('_', "-").as_str() {
+            "en" | "en-us" | "en-gb" => Ok(Lang::En),
+            "pt" | "pt-br" => Ok(Lang::PtBr),
+            _ => Err(format!("expects 'en' or 'pt-BR', got '{}'", text)),
+        }
+    }
+
+    /// Idioma de um locale POSIX, como os de LANG: "pt_BR.UTF-8" é PtBr
+    /// Locales de outros idiomas (e "C", "POSIX") ficam em inglês
+    pub fn from_locale(locale: &str) -> Lang {
+        // "pt_BR.UTF-8@euro" -> "pt"
+        let language = locale.split(['_', '-', '.', '@']).next().unwrap_or_default();
+        if language.eq_ignore_ascii_case("pt") {
+            Lang::PtBr
+        } else {
+            Lang::En
+        }
+    }
+
+    /// Idioma das variáveis de ambiente, na precedência do POSIX:
+    /// LC_ALL, depois LC_MESSAGES, depois LANG (a primeira não vazia decide)
+    /// Recebe a leitura como parâmetro para poder ser testada sem mexer no ambiente
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Lang {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(var)
+            .find(|value| !value.is_empty())
+            .map(|locale| Lang::from_locale(&locale))
+            .unwrap_or_default()
+    }
+
+    /// As mensagens deste idioma
+    // CONCEITO: &'static dyn Trait
+    // English e Portuguese não têm campos: um valor de cada vive no binário
+    // para sempre, e devolvemos uma referência a ele
+    pub fn messages(self) -> &'static dyn Messages {
+        match self {
+            Lang::En => &English,
+            Lang::PtBr => &Portuguese,
+        }
+    }
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// Escolhe o idioma do processo; só a primeira escolha vale (devolve false se já havia uma)
+pub fn set_lang(lang: Lang) -> bool {
+    LANG.set(lang).is_ok()
+}
+
+/// O idioma escolhido com set_lang(), ou inglês
+pub fn current() -> Lang {
+    LANG.get().copied().unwrap_or_default()
+}
+
+/// Atalho para current().messages()
+pub fn messages() -> &'static dyn Messages {
+    current().messages()
+}
+
+/// Uma mensagem por método
+///
+/// `repo` e os rótulos (`label`) chegam prontos para entrar no texto: podem trazer
+/// negrito ou links (ver display::hyperlink), então não devem ser alterados.
+/// `verb` é o que action() devolveu para a ação do evento
+pub trait Messages {
+    // --- Linhas dos eventos ---
+
+    /// "3 commits", "1 commit"
+    fn commits(&self, count: usize) -> String;
+    /// Um push que não trouxe commits novos
+    fn force_push(&self) -> String;
+    /// "Pushed 3 commits to main in user/repo"
+    fn pushed(&self, commits: &str, branch: Option<&str>, repo: &str) -> String;
+    /// Sufixo dos pushes juntados pelo --collapse: " (across 3 pushes)"
+    fn across_pushes(&self, pushes: usize) -> String;
+    /// Os commits que o --commits não listou: "…and 2 more"
+    fn more_commits(&self, hidden: usize) -> String;
+    /// A ação de uma issue, PR, release ou colaborador como verbo no passado: "opened" -> "Opened"
+    fn action(&self, action: &str) -> String;
+    /// "issue #42"
+    fn issue_label(&self, number: u64) -> String;
+    /// "Opened issue #42 'Fix crash' in user/repo"; sem label, "Opened an issue in user/repo"
+    fn issue(&self, verb: &str, label: Option<&str>, title: &str, repo: &str) -> String;
+    /// "pull request #1347"
+    fn pull_request_label(&self, number: u64) -> String;
+    /// "Merged pull request #1347 'Add docs' in user/repo"
+    fn pull_request(&self, verb: &str, label: Option<&str>, title: &str, repo: &str) -> String;
+    /// "Starred user/repo"
+    fn starred(&self, repo: &str) -> String;
+    /// "Forked torvalds/linux to me/linux"
+    fn forked(&self, repo: &str, destination: Option<&str>) -> String;
+    /// "Created branch 'feature-x' in user/repo"; sem nome, "Created a repository in user/repo"
+    fn created_ref(&self, ref_type: &str, name: Option<&str>, repo: &str) -> String;
+    /// "Deleted tag 'v1.0' in user/repo"
+    fn deleted_ref(&self, ref_type: &str, name: Option<&str>, repo: &str) -> String;
+    /// "Published release v1.4.0 ('Spring cleaning') in user/repo"
+    fn release(&self, verb: &str, tag: Option<&str>, title: &str, repo: &str) -> String;
+    /// "Commented on an issue in user/repo"
+    fn commented_on_issue(&self, repo: &str) -> String;
+    /// "Commented on a pull request in user/repo"
+    fn commented_on_pull_request(&self, repo: &str) -> String;
+    /// "Commented on a commit in user/repo"
+    fn commented_on_commit(&self, repo: &str) -> String;
+    /// "Approved a pull request in user/repo", pelo estado da revisão
+    fn reviewed(&self, state: &str, repo: &str) -> String;
+    /// "Added 'octocat' as a collaborator to user/repo"
+    fn member(&self, action: &str, login: Option<&str>, repo: &str) -> String;
+    /// "Made user/repo public"
+    fn made_public(&self, repo: &str) -> String;
+    /// "Created wiki page 'Home' in user/repo", "Edited 3 wiki pages in user/repo"
+    fn wiki(&self, pages_edited: usize, first_page: Option<&str>, created: bool, repo: &str) -> String;
+    /// Evento de tipo desconhecido: "Performed SponsorshipEvent in user/repo"
+    fn performed(&self, kind: &str, repo: &str) -> String;
+    /// Há quanto tempo: "2 hours ago"; menos de um minuto (ou no futuro), "just now"
+    fn time_ago(&self, seconds: i64) -> String;
+
+    // --- Cabeçalhos e avisos ---
+
+    /// "Recent activity for 'torvalds':" (`who` já vem formatado)
+    fn user_header(&self, who: &str) -> String;
+    /// "180k followers, 4 public repos" (os números já compactados, com as contagens para o plural)
+    fn profile_counts(&self, followers: &str, follower_count: u64, repos: &str, repo_count: u64) -> String;
+    /// "Recent activity in repository 'rust-lang/rust':"
+    fn repo_header(&self, full_name: &str) -> String;
+    /// "Events from 'events.json':"
+    fn file_header(&self, source: &str) -> String;
+    /// "Found 3 events"
+    fn found_events(&self, count: usize) -> String;
+    /// "2 duplicate events hidden" (estrelas e forks repetidos, entre parênteses no cabeçalho)
+    fn duplicates_hidden(&self, count: usize) -> String;
+    /// "12 bot events hidden" (--no-bots e --ignore-actor, também no cabeçalho)
+    fn bots_hidden(&self, count: usize) -> String;
+    /// "cached, 2m old" (eventos vindos do cache com --max-age; `age` já vem formatada)
+    fn served_from_cache(&self, age: &str) -> String;
+    /// As linhas do aviso de usuário sem atividade
+    fn no_user_events(&self, username: &str) -> String;
+    /// "No recent activity found in repository 'rust-lang/rust'"
+    fn no_repo_events(&self, full_name: &str) -> String;
+    /// "No events found in 'events.json'"
+    fn no_file_events(&self, source: &str) -> String;
+    /// "No new activity for 'torvalds' since 2024-06-01T12:00:00Z" (ou "since the last run")
+    fn no_new_events(&self, target: &str, since: Option<&str>) -> String;
+
+    // --- Listas de repositórios ---
+
+    /// "Repositories starred by 'torvalds':"
+    fn starred_header(&self, username: &str) -> String;
+    /// "Found 3 repositories"
+    fn found_repositories(&self, count: usize) -> String;
+    /// "'torvalds' has not starred any repositories"
+    fn no_starred(&self, username: &str) -> String;
+    /// "Public repositories of 'torvalds':"
+    fn repos_header(&self, username: &str) -> String;
+    /// "'torvalds' has no public repositories"
+    fn no_repos(&self, username: &str) -> String;
+    /// "Note: 'old-login' is now 'new-login'" (a API redirecionou o login antigo)
+    fn user_renamed(&self, old: &str, new: &str) -> String;
+
+    // --- Erros (ver ActivityError) ---
+
+    /// A palavra antes da mensagem de erro: "Error"
+    fn error_label(&self) -> String;
+    /// "Network error: ..."
+    fn network_error(&self, message: &str) -> String;
+    /// "Could not access 'events.json': ..."
+    fn io_error(&self, path: &str, cause: &str) -> String;
+    /// "Invalid username: 'a b'"
+    fn invalid_username(&self, username: &str) -> String;
+    /// O usuário não existe (404)
+    fn user_not_found(&self, username: &str) -> String;
+    /// "GitHub API error (status 500): ..."; status 0 quando não houve um status de erro
+    fn api_error(&self, status: u16, message: &str) -> String;
+    /// Segunda linha de um erro da API, com o link da documentação
+    fn see_documentation(&self, url: &str) -> String;
+    /// Limite por hora esgotado; `reset_at` é o horário em que ele se renova, se conhecido
+    fn rate_limited(&self, reset_at: Option<&str>) -> String;
+    /// Limite secundário: quantos segundos esperar
+    fn secondary_rate_limit(&self, retry_after: u64) -> String;
+    /// "Failed to parse event #3: ..." ou, sem o índice, "Failed to parse response: ..."
+    fn parse_failed(&self, event_index: Option<usize>, message: &str) -> String;
+    /// Sufixo com o trecho do JSON: " near: {...}"
+    fn near(&self, snippet: &str) -> String;
+    /// "Invalid argument: ..."
+    fn invalid_argument(&self, message: &str) -> String;
+    /// "Invalid configuration: ..."
+    fn invalid_config(&self, message: &str) -> String;
+    /// "Could not show the activity of torvalds, octocat"
+    fn targets_failed(&self, targets: &str) -> String;
+    /// "Could not compare: fetching 'octocat' failed: ..."
+    fn compare_failed(&self, user: &str, cause: &str) -> String;
+    /// "Could not open https://github.com/a/b in the browser: 'xdg-open' was not found"
+    fn browser_error(&self, url: &str, reason: &str) -> String;
+    /// "Could not post the events to https://hooks.slack.com/…: HTTP 500"
+    fn webhook_error(&self, url: &str, reason: &str) -> String;
+    /// "Interrupted — showing 12 events fetched so far" (Ctrl-C)
+    fn interrupted(&self, shown: usize) -> String;
+    /// "2 of 5 checks failed" (--doctor)
+    fn checks_failed(&self, failed: usize, total: usize) -> String;
+    /// "No recent events found"
+    fn no_events_found(&self) -> String;
+    /// "API rate limit: 54/60 remaining, resets at 14:32" (rodapé depois da saída)
+    /// `remaining` é (restantes, limite); None quando a API não informou
+    fn rate_limit_footer(&self, remaining: Option<(u64, u64)>, resets_at: Option<&str>) -> String;
+}
+
+// Plural por "adicionar s", que serve para quase tudo nos dois idiomas
+fn plural(count: u64) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+// Unidade e quantidade de time_ago(); None para "agora mesmo"
+// CONCEITO: Match com ranges
+// ..=59 casa com qualquer valor até 59, inclusive negativos
+fn elapsed(seconds: i64) -> Option<(i64, Unit)> {
+    match seconds {
+        ..=59 => None,
+        60..=3599 => Some((seconds / 60, Unit::Minute)),
+        3600..=86_399 => Some((seconds / 3600, Unit::Hour)),
+        _ => Some((seconds / 86_400, Unit::Day)),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Minute,
+    Hour,
+    Day,
+}
+
+/// Mensagens em inglês
+#[derive(Debug, Clone, Copy)]
+pub struct English;
+
+impl Messages for English {
+    fn commits(&self, count: usize) -> String {
+        format!("{} commit{}", count, plural(count as u64))
+    }
+
+    fn force_push(&self) -> String {
+        "0 new commits (force-push)".to_string()
+    }
+
+    fn pushed(&self, commits: &str, branch: Option<&str>, repo: &str) -> String {
+        match branch {
+            Some(branch) => format!("Pushed {} to {} in {}", commits, branch, repo),
+            None => format!("Pushed {} to {}", commits, repo),
+        }
+    }
+
+    fn across_pushes(&self, pushes: usize) -> String {
+        format!(" (across {} pushes)", pushes)
+    }
+
+    fn more_commits(&self, hidden: usize) -> String {
+        format!("…and {} more", hidden)
+    }
+
+    fn action(&self, action: &str) -> String {
+        capitalize_first(action)
+    }
+
+    fn issue_label(&self, number: u64) -> String {
+        format!("issue #{}", number)
+    }
+
+    fn issue(&self, verb: &str, label: Option<&str>, title: &str, repo: &str) -> String {
+        match label {
+            Some(label) => format!("{} {}{} in {}", verb, label, title, repo),
+            None => format!("{} an issue in {}", verb, repo),
+        }
+    }
+
+    fn pull_request_label(&self, number: u64) -> String {
+        format!("pull request #{}", number)
+    }
+
+    fn pull_request(&self, verb: &str, label: Option<&str>, title: &str, repo: &str) -> String {
+        match label {
+            Some(label) => format!("{} {}{} in {}", verb, label, title, repo),
+            None => format!("{} a pull request in {}", verb, repo),
+        }
+    }
+
+    fn starred(&self, repo: &str) -> String {
+        format!("Starred {}", repo)
+    }
+
+    fn forked(&self, repo: &str, destination: Option<&str>) -> String {
+        match destination {
+            Some(destination) => format!("Forked {} to {}", repo, destination),
+            None => format!("Forked {}", repo),
+        }
+    }
+
+    fn created_ref(&self, ref_type: &str, name: Option<&str>, repo: &str) -> String {
+        match name {
+            Some(name) => format!("Created {} '{}' in {}", ref_type, name, repo),
+            // "branch", "tag" e "repository" começam com consoante, então o artigo é sempre "a"
+            None => format!("Created a {} in {}", ref_type, repo),
+        }
+    }
+
+    fn deleted_ref(&self, ref_type: &str, name: Option<&str>, repo: &str) -> String {
+        match name {
+            Some(name) => format!("Deleted {} '{}' in {}", ref_type, name, repo),
+            None => format!("Deleted a {} in {}", ref_type, repo),
+        }
+    }
+
+    fn release(&self, verb: &str, tag: Option<&str>, title: &str, repo: &str) -> String {
+        match tag {
+            Some(tag) => format!("{} release {}{} in {}", verb, tag, title, repo),
+            None => format!("{} a release in {}", verb, repo),
+        }
+    }
+
+    fn commented_on_issue(&self, repo: &str) -> String {
+        format!("Commented on an issue in {}", repo)
+    }
+
+    fn commented_on_pull_request(&self, repo: &str) -> String {
+        format!("Commented on a pull request in {}", repo)
+    }
+
+    fn commented_on_commit(&self, repo: &str) -> String {
+        format!("Commented on a commit in {}", repo)
+    }
+
+    fn reviewed(&self, state: &str, repo: &str) -> String {
+        format!("{} a pull request in {}", review_verb(state), repo)
+    }
+
+    fn member(&self, action: &str, login: Option<&str>, repo: &str) -> String {
+        match (action, login) {
+            ("added", Some(login)) => format!("Added '{}' as a collaborator to {}", login, repo),
+            ("added", None) => format!("Added a collaborator to {}", repo),
+            (_, Some(login)) => format!("{} collaborator '{}' in {}", self.action(action), login, repo),
+            (_, None) => format!("{} a collaborator in {}", self.action(action), repo),
+        }
+    }
+
+    fn made_public(&self, repo: &str) -> String {
+        format!("Made {} public", repo)
+    }
+
+    fn wiki(&self, pages_edited: usize, first_page: Option<&str>, created: bool, repo: &str) -> String {
+        match (pages_edited, first_page) {
+            (1, Some(page)) => {
+                let verb = if created { "Created" } else { "Edited" };
+                format!("{} wiki page '{}' in {}", verb, page, repo)
+            }
+            (1, None) => format!("Edited 1 wiki page in {}", repo),
+            (0, _) => format!("Edited the wiki in {}", repo),
+            (count, _) => format!("Edited {} wiki pages in {}", count, repo),
+        }
+    }
+
+    fn performed(&self, kind: &str, repo: &str) -> String {
+        format!("Performed {} in {}", kind, repo)
+    }
+
+    fn time_ago(&self, seconds: i64) -> String {
+        let (amount, unit) = match elapsed(seconds) {
+            Some(elapsed) => elapsed,
+            None => return "just now".to_string(),
+        };
+        let unit = match unit {
+            Unit::Minute => "minute",
+            Unit::Hour => "hour",
+            Unit::Day => "day",
+        };
+        format!("{} {}{} ago", amount, unit, plural(amount as u64))
+    }
+
+    fn user_header(&self, who: &str) -> String {
+        format!("Recent activity for {}:", who)
+    }
+
+    fn profile_counts(&self, followers: &str, follower_count: u64, repos: &str, repo_count: u64) -> String {
+        format!(
+            "{} follower{}, {} public repo{}",
+            followers,
+            plural(follower_count),
+            repos,
+            plural(repo_count)
+        )
+    }
+
+    fn repo_header(&self, full_name: &str) -> String {
+        format!("Recent activity in repository '{}':", full_name)
+    }
+
+    fn file_header(&self, source: &str) -> String {
+        format!("Events from '{}':", source)
+    }
+
+    fn found_events(&self, count: usize) -> String {
+        format!("Found {} event{}", count, plural(count as u64))
+    }
+
+    fn duplicates_hidden(&self, count: usize) -> String {
+        format!("{} duplicate event{} hidden", count, plural(count as u64))
+    }
+
+    fn bots_hidden(&self, count: usize) -> String {
+        format!("{} bot event{} hidden", count, plural(count as u64))
+    }
+
+    fn served_from_cache(&self, age: &str) -> String {
+        format!("cached, {} old", age)
+    }
+
+    fn no_user_events(&self, username: &str) -> String {
+        format!(
+            "No recent activity found for user '{}'\n\
+             This could mean:\n\
+             \x20 - The user has no public activity in the last 90 days\n\
+             \x20 - The user has made their activity private",
+            username
+        )
+    }
+
+    fn no_repo_events(&self, full_name: &str) -> String {
+        format!("No recent activity found in repository '{}'", full_name)
+    }
+
+    fn no_file_events(&self, source: &str) -> String {
+        format!("No events found in '{}'", source)
+    }
+
+    fn no_new_events(&self, target: &str, since: Option<&str>) -> String {
+        match since {
+            Some(since) => format!("No new activity for '{}' since {}", target, since),
+            None => format!("No new activity for '{}' since the last run", target),
+        }
+    }
+
+    fn starred_header(&self, username: &str) -> String {
+        format!("Repositories starred by '{}':", username)
+    }
+
+    fn found_repositories(&self, count: usize) -> String {
+        let noun = if count == 1 { "repository" } else { "repositories" };
+        format!("Found {} {}", count, noun)
+    }
+
+    fn no_starred(&self, username: &str) -> String {
+        format!("'{}' has not starred any repositories", username)
+    }
+
+    fn repos_header(&self, username: &str) -> String {
+        format!("Public repositories of '{}':", username)
+    }
+
+    fn no_repos(&self, username: &str) -> String {
+        format!("'{}' has no public repositories", username)
+    }
+
+    fn user_renamed(&self, old: &str, new: &str) -> String {
+        format!("Note: '{}' is now '{}'", old, new)
+    }
+
+    fn error_label(&self) -> String {
+        "Error".to_string()
+    }
+
+    fn network_error(&self, message: &str) -> String {
+        format!("Network error: {}", message)
+    }
+
+    fn io_error(&self, path: &str, cause: &str) -> String {
+        format!("Could not access '{}': {}", path, cause)
+    }
+
+    fn invalid_username(&self, username: &str) -> String {
+        format!("Invalid username: '{}'", username)
+    }
+
+    fn user_not_found(&self, username: &str) -> String {
+        format!("User '{}' was not found on GitHub. Check the spelling of the username", username)
+    }
+
+    fn api_error(&self, status: u16, message: &str) -> String {
+        match status {
+            0 => format!("GitHub API error: {}", message),
+            _ => format!("GitHub API error (status {}): {}", status, message),
+        }
+    }
+
+    fn see_documentation(&self, url: &str) -> String {
+        format!("\n  See: {}", url)
+    }
+
+    fn rate_limited(&self, reset_at: Option<&str>) -> String {
+        match reset_at {
+            Some(reset_at) => format!("GitHub API rate limit exceeded; it resets at {}", reset_at),
+            None => "GitHub API rate limit exceeded; try again later".to_string(),
+        }
+    }
+
+    fn secondary_rate_limit(&self, retry_after: u64) -> String {
+        format!(
+            "GitHub API secondary rate limit exceeded; wait {} second{} before trying again \
+             (or use --wait-on-limit)",
+            retry_after,
+            plural(retry_after)
+        )
+    }
+
+    fn parse_failed(&self, event_index: Option<usize>, message: &str) -> String {
+        match event_index {
+            Some(index) => format!("Failed to parse event #{}: {}", index, message),
+            None => format!("Failed to parse response: {}", message),
+        }
+    }
+
+    fn near(&self, snippet: &str) -> String {
+        format!(" near: {}", snippet)
+    }
+
+    fn invalid_argument(&self, message: &str) -> String {
+        format!("Invalid argument: {}", message)
+    }
+
+    fn invalid_config(&self, message: &str) -> String {
+        format!("Invalid configuration: {}", message)
+    }
+
+    fn targets_failed(&self, targets: &str) -> String {
+        format!("Could not show the activity of {}", targets)
+    }
+
+    fn compare_failed(&self, user: &str, cause: &str) -> String {
+        format!("Could not compare: fetching '{}' failed: {}", user, cause)
+    }
+
+    fn browser_error(&self, url: &str, reason: &str) -> String {
+        format!("Could not open {} in the browser: {}", url, reason)
+    }
+
+    fn webhook_error(&self, url: &str, reason: &str) -> String {
+        format!("Could not post the events to {}: {}", url, reason)
+    }
+
+    fn interrupted(&self, shown: usize) -> String {
+        format!("Interrupted — showing {} event{} fetched so far", shown, plural(shown as u64))
+    }
+
+    fn checks_failed(&self, failed: usize, total: usize) -> String {
+        let verb = if failed == 1 { "check failed" } else { "checks failed" };
+        format!("{} of {} {}", failed, total, verb)
+    }
+
+    fn no_events_found(&self) -> String {
+        "No recent events found".to_string()
+    }
+
+    fn rate_limit_footer(&self, remaining: Option<(u64, u64)>, resets_at: Option<&str>) -> String {
+        match (remaining, resets_at) {
+            (None, _) => "API rate limit: unknown".to_string(),
+            (Some((remaining, limit)), None) => format!("API rate limit: {}/{} remaining", remaining, limit),
+            (Some((remaining, limit)), Some(time)) => {
+                format!("API rate limit: {}/{} remaining, resets at {}", remaining, limit, time)
+            }
+        }
+    }
+}
+
+/// Mensagens em português do Brasil
+///
+/// Os termos que o GitHub em português mantém em inglês (issue, pull request,
+/// branch, tag, commit, fork, push) ficam em inglês aqui também
+#[derive(Debug, Clone, Copy)]
+pub struct Portuguese;
+
+impl Portuguese {
+    // Artigos definido e indefinido para o tipo de ref: "a branch"/"uma branch",
+    // "o repositório"/"um repositório"
+    fn ref_noun(ref_type: &str) -> (String, String) {
+        match ref_type {
+            "branch" | "tag" => (format!("a {}", ref_type), format!("uma {}", ref_type)),
+            "repository" => ("o repositório".to_string(), "um repositório".to_string()),
+            other => (format!("o {}", other), format!("um {}", other)),
+        }
+    }
+}
+
+impl Messages for Portuguese {
+    fn commits(&self, count: usize) -> String {
+        format!("{} commit{}", count, plural(count as u64))
+    }
+
+    fn force_push(&self) -> String {
+        "0 commits novos (force-push)".to_string()
+    }
+
+    fn pushed(&self, commits: &str, branch: Option<&str>, repo: &str) -> String {
+        match branch {
+            Some(branch) => format!("Enviou {} para {} em {}", commits, branch, repo),
+            None => format!("Enviou {} para {}", commits, repo),
+        }
+    }
+
+    fn across_pushes(&self, pushes: usize) -> String {
+        format!(" (em {} pushes)", pushes)
+    }
+
+    fn more_commits(&self, hidden: usize) -> String {
+        format!("…e mais {}", hidden)
+    }
+
+    // Ações que a API manda e que sabemos traduzir; as outras ficam em inglês
+    fn action(&self, action: &str) -> String {
+        let verb = match action {
+            "opened" => "Abriu",
+            "closed" => "Fechou",
+            "reopened" => "Reabriu",
+            "merged" => "Mesclou",
+            "published" => "Publicou",
+            "released" => "Lançou",
+            "drafted" => "Rascunhou",
+            "created" => "Criou",
+            "edited" => "Editou",
+            "deleted" => "Apagou",
+            "added" => "Adicionou",
+            "removed" => "Removeu",
+            "assigned" => "Atribuiu",
+            "labeled" => "Rotulou",
+            "transferred" => "Transferiu",
+            "locked" => "Bloqueou",
+            "unlocked" => "Desbloqueou",
+            "pinned" => "Fixou",
+            _ => return capitalize_first(action),
+        };
+        verb.to_string()
+    }
+
+    fn issue_label(&self, number: u64) -> String {
+        format!("issue #{}", number)
+    }
+
+    fn issue(&self, verb: &str, label: Option<&str>, title: &str, repo: &str) -> String {
+        match label {
+            Some(label) => format!("{} a {}{} em {}", verb, label, title, repo),
+            None => format!("{} uma issue em {}", verb, repo),
+        }
+    }
+
+    fn pull_request_label(&self, number: u64) -> String {
+        format!("pull request #{}", number)
+    }
+
+    fn pull_request(&self, verb: &str, label: Option<&str>, title: &str, repo: &str) -> String {
+        match label {
+            Some(label) => format!("{} o {}{} em {}", verb, label, title, repo),
+            None => format!("{} um pull request em {}", verb, repo),
+        }
+    }
+
+    fn starred(&self, repo: &str) -> String {
+        format!("Favoritou {}", repo)
+    }
+
+    fn forked(&self, repo: &str, destination: Option<&str>) -> String {
+        match destination {
+            Some(destination) => format!("Fez fork de {} para {}", repo, destination),
+            None => format!("Fez fork de {}", repo),
+        }
+    }
+
+    fn created_ref(&self, ref_type: &str, name: Option<&str>, repo: &str) -> String {
+        let (definite, indefinite) = Portuguese::ref_noun(ref_type);
+        match name {
+            Some(name) => format!("Criou {} '{}' em {}", definite, name, repo),
+            None => format!("Criou {} em {}", indefinite, repo),
+        }
+    }
+
+    fn deleted_ref(&self, ref_type: &str, name: Option<&str>, repo: &str) -> String {
+        let (definite, indefinite) = Portuguese::ref_noun(ref_type);
+        match name {
+            Some(name) => format!("Apagou {} '{}' em {}", definite, name, repo),
+            None => format!("Apagou {} em {}", indefinite, repo),
+        }
+    }
+
+    fn release(&self, verb: &str, tag: Option<&str>, title: &str, repo: &str) -> String {
+        match tag {
+            Some(tag) => format!("{} a release {}{} em {}", verb, tag, title, repo),
+            None => format!("{} uma release em {}", verb, repo),
+        }
+    }
+
+    fn commented_on_issue(&self, repo: &str) -> String {
+        format!("Comentou em uma issue em {}", repo)
+    }
+
+    fn commented_on_pull_request(&self, repo: &str) -> String {
+        format!("Comentou em um pull request em {}", repo)
+    }
+
+    fn commented_on_commit(&self, repo: &str) -> String {
+        format!("Comentou em um commit em {}", repo)
+    }
+
+    fn reviewed(&self, state: &str, repo: &str) -> String {
+        let verb = match state.to_ascii_lowercase().as_str() {
+            "approved" => "Aprovou",
+            "changes_requested" => "Pediu mudanças em",
+            "commented" => "Comentou em",
+            _ => "Revisou",
+        };
+        format!("{} um pull request em {}", verb, repo)
+    }
+
+    fn member(&self, action: &str, login: Option<&str>, repo: &str) -> String {
+        match (action, login) {
+            ("added", Some(login)) => format!("Adicionou '{}' como colaborador em {}", login, repo),
+            ("added", None) => format!("Adicionou um colaborador em {}", repo),
+            (_, Some(login)) => format!("{} o colaborador '{}' em {}", self.action(action), login, repo),
+            (_, None) => format!("{} um colaborador em {}", self.action(action), repo),
+        }
+    }
+
+    fn made_public(&self, repo: &str) -> String {
+        format!("Tornou {} público", repo)
+    }
+
+    fn wiki(&self, pages_edited: usize, first_page: Option<&str>, created: bool, repo: &str) -> String {
+        match (pages_edited, first_page) {
+            (1, Some(page)) => {
+                let verb = if created { "Criou" } else { "Editou" };
+                format!("{} a página '{}' da wiki em {}", verb, page, repo)
+            }
+            (1, None) => format!("Editou 1 página da wiki em {}", repo),
+            (0, _) => format!("Editou a wiki em {}", repo),
+            (count, _) => format!("Editou {} páginas da wiki em {}", count, repo),
+        }
+    }
+
+    fn performed(&self, kind: &str, repo: &str) -> String {
+        format!("Executou {} em {}", kind, repo)
+    }
+
+    fn time_ago(&self, seconds: i64) -> String {
+        let (amount, unit) = match elapsed(seconds) {
+            Some(elapsed) => elapsed,
+            None => return "agora mesmo".to_string(),
+        };
+        let unit = match unit {
+            Unit::Minute => "minuto",
+            Unit::Hour => "hora",
+            Unit::Day => "dia",
+        };
+        format!("há {} {}{}", amount, unit, plural(amount as u64))
+    }
+
+    fn user_header(&self, who: &str) -> String {
+        format!("Atividade recente de {}:", who)
+    }
+
+    // "1 seguidor"/"2 seguidores" e "1 repositório público"/"2 repositórios públicos":
+    // aqui o "s" não basta
+    fn profile_counts(&self, followers: &str, follower_count: u64, repos: &str, repo_count: u64) -> String {
+        let followers_word = if follower_count == 1 { "seguidor" } else { "seguidores" };
+        let repos_words = if repo_count == 1 { "repositório público" } else { "repositórios públicos" };
+        format!("{} {}, {} {}", followers, followers_word, repos, repos_words)
+    }
+
+    fn repo_header(&self, full_name: &str) -> String {
+        format!("Atividade recente no repositório '{}':", full_name)
+    }
+
+    fn file_header(&self, source: &str) -> String {
+        format!("Eventos de '{}':", source)
+    }
+
+    fn found_events(&self, count: usize) -> String {
+        let plural = plural(count as u64);
+        format!("{} evento{} encontrado{}", count, plural, plural)
+    }
+
+    fn duplicates_hidden(&self, count: usize) -> String {
+        let plural = plural(count as u64);
+        format!("{} evento{} duplicado{} oculto{}", count, plural, plural, plural)
+    }
+
+    fn bots_hidden(&self, count: usize) -> String {
+        let plural = plural(count as u64);
+        format!("{} evento{} de bots oculto{}", count, plural, plural)
+    }
+
+    fn served_from_cache(&self, age: &str) -> String {
+        format!("do cache, de {} atrás", age)
+    }
+
+    fn no_user_events(&self, username: &str) -> String {
+        format!(
+            "Nenhuma atividade recente encontrada para o usuário '{}'\n\
+             Isso pode significar que:\n\
+             \x20 - O usuário não teve atividade pública nos últimos 90 dias\n\
+             \x20 - O usuário deixou sua atividade privada",
+            username
+        )
+    }
+
+    fn no_repo_events(&self, full_name: &str) -> String {
+        format!("Nenhuma atividade recente encontrada no repositório '{}'", full_name)
+    }
+
+    fn no_file_events(&self, source: &str) -> String {
+        format!("Nenhum evento encontrado em '{}'", source)
+    }
+
+    fn no_new_events(&self, target: &str, since: Option<&str>) -> String {
+        match since {
+            Some(since) => format!("Nenhuma atividade nova de '{}' desde {}", target, since),
+            None => format!("Nenhuma atividade nova de '{}' desde a última execução", target),
+        }
+    }
+
+    fn starred_header(&self, username: &str) -> String {
+        format!("Repositórios favoritados por '{}':", username)
+    }
+
+    fn found_repositories(&self, count: usize) -> String {
+        let plural = plural(count as u64);
+        format!("{} repositório{} encontrado{}", count, plural, plural)
+    }
+
+    fn no_starred(&self, username: &str) -> String {
+        format!("'{}' ainda não favoritou nenhum repositório", username)
+    }
+
+    fn repos_header(&self, username: &str) -> String {
+        format!("Repositórios públicos de '{}':", username)
+    }
+
+    fn no_repos(&self, username: &str) -> String {
+        format!("'{}' não tem repositórios públicos", username)
+    }
+
+    fn user_renamed(&self, old: &str, new: &str) -> String {
+        format!("Observação: '{}' agora se chama '{}'", old, new)
+    }
+
+    fn error_label(&self) -> String {
+        "Erro".to_string()
+    }
+
+    fn network_error(&self, message: &str) -> String {
+        format!("Erro de rede: {}", message)
+    }
+
+    fn io_error(&self, path: &str, cause: &str) -> String {
+        format!("Não foi possível acessar '{}': {}", path, cause)
+    }
+
+    fn invalid_username(&self, username: &str) -> String {
+        format!("Nome de usuário inválido: '{}'", username)
+    }
+
+    fn user_not_found(&self, username: &str) -> String {
+        format!(
+            "O usuário '{}' não foi encontrado no GitHub. Confira a grafia do nome de usuário",
+            username
+        )
+    }
+
+    fn api_error(&self, status: u16, message: &str) -> String {
+        match status {
+            0 => format!("Erro da API do GitHub: {}", message),
+            _ => format!("Erro da API do GitHub (status {}): {}", status, message),
+        }
+    }
+
+    fn see_documentation(&self, url: &str) -> String {
+        format!("\n  Veja: {}", url)
+    }
+
+    fn rate_limited(&self, reset_at: Option<&str>) -> String {
+        match reset_at {
+            Some(reset_at) => format!("Limite de requisições da API do GitHub esgotado; ele renova em {}", reset_at),
+            None => "Limite de requisições da API do GitHub esgotado; tente novamente mais tarde".to_string(),
+        }
+    }
+
+    fn secondary_rate_limit(&self, retry_after: u64) -> String {
+        format!(
+            "Limite secundário de requisições da API do GitHub esgotado; espere {} segundo{} \
+             antes de tentar de novo (ou use --wait-on-limit)",
+            retry_after,
+            plural(retry_after)
+        )
+    }
+
+    fn parse_failed(&self, event_index: Option<usize>, message: &str) -> String {
+        match event_index {
+            Some(index) => format!("Falha ao interpretar o evento #{}: {}", index, message),
+            None => format!("Falha ao interpretar a resposta: {}", message),
+        }
+    }
+
+    fn near(&self, snippet: &str) -> String {
+        format!(" perto de: {}", snippet)
+    }
+
+    fn invalid_argument(&self, message: &str) -> String {
+        format!("Argumento inválido: {}", message)
+    }
+
+    fn invalid_config(&self, message: &str) -> String {
+        format!("Configuração inválida: {}", message)
+    }
+
+    fn targets_failed(&self, targets: &str) -> String {
+        format!("Não foi possível mostrar a atividade de {}", targets)
+    }
+
+    fn compare_failed(&self, user: &str, cause: &str) -> String {
+        format!("Não foi possível comparar: a busca de '{}' falhou: {}", user, cause)
+    }
+
+    fn browser_error(&self, url: &str, reason: &str) -> String {
+        format!("Não foi possível abrir {} no navegador: {}", url, reason)
+    }
+
+    fn webhook_error(&self, url: &str, reason: &str) -> String {
+        format!("Não foi possível enviar os eventos para {}: {}", url, reason)
+    }
+
+    fn interrupted(&self, shown: usize) -> String {
+        let plural = plural(shown as u64);
+        let article = if shown == 1 { "o" } else { "os" };
+        format!("Interrompido — mostrando {} {} evento{} buscado{} até agora", article, shown, plural, plural)
+    }
+
+    fn checks_failed(&self, failed: usize, total: usize) -> String {
+        let verb = if failed == 1 { "verificação falhou" } else { "verificações falharam" };
+        format!("{} de {} {}", failed, total, verb)
+    }
+
+    fn no_events_found(&self) -> String {
+        "Nenhum evento recente encontrado".to_string()
+    }
+
+    fn rate_limit_footer(&self, remaining: Option<(u64, u64)>, resets_at: Option<&str>) -> String {
+        match (remaining, resets_at) {
+            (None, _) => "Limite da API: desconhecido".to_string(),
+            (Some((remaining, limit)), None) => format!("Limite da API: {}/{} restantes", remaining, limit),
+            (Some((remaining, limit)), Some(time)) => {
+                format!("Limite da API: {}/{} restantes, renova às {}", remaining, limit, time)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lang() {
+        assert_eq!(Lang::parse("en"), Ok(Lang::En));
+        assert_eq!(Lang::parse("pt-BR"), Ok(Lang::PtBr));
+        assert_eq!(Lang::parse("pt_br"), Ok(Lang::PtBr));
+        assert_eq!(Lang::parse("pt"), Ok(Lang::PtBr));
+        assert!(Lang::parse("klingon").is_err());
+    }
+
+    #[test]
+    fn test_lang_from_env() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+        };
+
+        assert_eq!(Lang::from_env(env(&[("LANG", "pt_BR.UTF-8")])), Lang::PtBr);
+        assert_eq!(Lang::from_env(env(&[("LANG", "en_US.UTF-8")])), Lang::En);
+        assert_eq!(Lang::from_env(env(&[("LANG", "de_DE.UTF-8")])), Lang::En);
+        assert_eq!(Lang::from_env(env(&[])), Lang::En);
+        // LC_ALL vence LANG; vazia, é como se não existisse
+        assert_eq!(Lang::from_env(env(&[("LC_ALL", "C"), ("LANG", "pt_BR.UTF-8")])), Lang::En);
+        assert_eq!(Lang::from_env(env(&[("LC_ALL", ""), ("LANG", "pt_BR.UTF-8")])), Lang::PtBr);
+    }
+
+    #[test]
+    fn test_plurals_in_english() {
+        let en = Lang::En.messages();
+        assert_eq!(en.commits(1), "1 commit");
+        assert_eq!(en.commits(3), "3 commits");
+        assert_eq!(en.found_events(1), "Found 1 event");
+        assert_eq!(en.found_events(0), "Found 0 events");
+        assert_eq!(en.duplicates_hidden(1), "1 duplicate event hidden");
+        assert_eq!(en.bots_hidden(12), "12 bot events hidden");
+        assert_eq!(en.served_from_cache("2m"), "cached, 2m old");
+        assert_eq!(en.time_ago(3600), "1 hour ago");
+        assert_eq!(en.time_ago(3 * 86_400), "3 days ago");
+        assert_eq!(en.profile_counts("1", 1, "4", 4), "1 follower, 4 public repos");
+        assert_eq!(en.found_repositories(1), "Found 1 repository");
+        assert_eq!(en.found_repositories(2), "Found 2 repositories");
+        assert_eq!(en.wiki(3, Some("Home"), false, "a/b"), "Edited 3 wiki pages in a/b");
+        assert!(en.secondary_rate_limit(1).contains("wait 1 second before"));
+    }
+
+    #[test]
+    fn test_plurals_in_portuguese() {
+        let pt = Lang::PtBr.messages();
+        assert_eq!(pt.commits(1), "1 commit");
+        assert_eq!(pt.commits(3), "3 commits");
+        assert_eq!(pt.found_events(1), "1 evento encontrado");
+        assert_eq!(pt.found_events(3), "3 eventos encontrados");
+        assert_eq!(pt.found_repositories(1), "1 repositório encontrado");
+        assert_eq!(pt.found_repositories(5), "5 repositórios encontrados");
+        assert_eq!(pt.time_ago(30), "agora mesmo");
+        assert_eq!(pt.time_ago(60), "há 1 minuto");
+        assert_eq!(pt.time_ago(2 * 3600), "há 2 horas");
+        assert_eq!(pt.profile_counts("1", 1, "1", 1), "1 seguidor, 1 repositório público");
+        assert_eq!(pt.profile_counts("180k", 180_000, "4", 4), "180k seguidores, 4 repositórios públicos");
+        assert!(pt.secondary_rate_limit(30).contains("espere 30 segundos antes"));
+    }
+
+    // 0 leva plural nos dois idiomas ("0 events", "0 eventos"); só 1 fica no singular
+    #[test]
+    fn test_plurals_for_zero_one_and_two() {
+        let en = Lang::En.messages();
+        let pt = Lang::PtBr.messages();
+        let cases = [
+            (0, "Found 0 events", "0 eventos encontrados", "0 commits"),
+            (1, "Found 1 event", "1 evento encontrado", "1 commit"),
+            (2, "Found 2 events", "2 eventos encontrados", "2 commits"),
+        ];
+        for (count, english, portuguese, commits) in cases {
+            assert_eq!(en.found_events(count), english);
+            assert_eq!(pt.found_events(count), portuguese);
+            assert_eq!(en.commits(count), commits);
+            assert_eq!(pt.commits(count), commits);
+        }
+        assert_eq!(en.time_ago(60), "1 minute ago");
+        assert_eq!(en.time_ago(2 * 60), "2 minutes ago");
+        assert_eq!(pt.time_ago(86_400), "há 1 dia");
+        assert_eq!(pt.time_ago(2 * 86_400), "há 2 dias");
+    }
+
+    #[test]
+    fn test_portuguese_sentences() {
+        let pt = Lang::PtBr.messages();
+        assert_eq!(pt.pushed(&pt.commits(3), None, "user/repo"), "Enviou 3 commits para user/repo");
+        assert_eq!(pt.starred("torvalds/linux"), "Favoritou torvalds/linux");
+        assert_eq!(
+            pt.pull_request(&pt.action("merged"), Some(&pt.pull_request_label(7)), "", "a/b"),
+            "Mesclou o pull request #7 em a/b"
+        );
+        assert_eq!(pt.issue(&pt.action("opened"), None, "", "a/b"), "Abriu uma issue em a/b");
+        // O artigo acompanha o gênero do tipo de ref
+        assert_eq!(pt.created_ref("branch", Some("dev"), "a/b"), "Criou a branch 'dev' em a/b");
+        assert_eq!(pt.created_ref("repository", None, "a/b"), "Criou um repositório em a/b");
+        // Ações sem tradução conhecida ficam como a API mandou
+        assert_eq!(pt.action("milestoned"), "Milestoned");
+        assert_eq!(pt.invalid_username("a b"), "Nome de usuário inválido: 'a b'");
+        assert_eq!(pt.user_renamed("velho", "novo"), "Observação: 'velho' agora se chama 'novo'");
+        assert_eq!(pt.served_from_cache("2m"), "do cache, de 2m atrás");
+    }
+}
@@ -0,0 +1,192 @@
+//! Este módulo expande as listas de usernames da linha de comando
+//!
+//! `@team.txt` troca-se pelos usernames do arquivo e `-`, pelos da entrada padrão,
+//! um por linha. Linhas em branco e comentários (de `#` até o fim da linha) são
+//! ignorados:
+//!
+//! ```text
+//! # time de infraestrutura
+//! octocat
+//! torvalds   # lead
+//! ```
+//!
+//! Listas e nomes digitados podem ser misturados (`octocat @team.txt`): o resultado
+//! segue a ordem da linha de comando, sem repetições, e cada nome é validado antes
+//! de qualquer busca, com todos os inválidos listados de uma vez
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::Read;
+
+use github_activity::api::Target;
+use github_activity::error::ActivityError;
+
+// Um argumento que é uma lista: "-" ou "@caminho" ("@" sozinho é só um nome inválido)
+pub fn is_list(arg: &str) -> bool {
+    arg == "-" || (arg.starts_with('@') && arg.len() > 1)
+}
+
+// Como a lista aparece nas mensagens: o caminho, ou <stdin>
+fn list_name(arg: &str) -> &str {
+    if arg == "-" { "<stdin>" } else { &arg[1..] }
+}
+
+// Lê o texto de uma lista: a entrada padrão para "-", o arquivo para "@caminho"
+pub fn read_list(arg: &str) -> Result<String, ActivityError> {
+    if arg == "-" {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| ActivityError::io(list_name(arg), e))?;
+        return Ok(text);
+    }
+
+    fs::read_to_string(list_name(arg)).map_err(|e| ActivityError::io(list_name(arg), e))
+}
+
+// Os nomes de uma lista, na ordem do texto, sem comentários nem linhas vazias
+pub fn parse_list(text: &str) -> Vec<String> {
+    text.lines()
+        // CONCEITO: split + next
+        // split sempre produz ao menos um pedaço: o que vem antes do primeiro '#'
+        .filter_map(|line| line.split('#').next())
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// Remove repetições mantendo a primeira ocorrência (e a ordem)
+// Usernames não diferenciam maiúsculas: "Octocat" repete "octocat"
+pub fn dedup(names: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    // CONCEITO: HashSet::insert
+    // Devolve false quando o valor já estava no conjunto
+    names.into_iter().filter(|name| seen.insert(name.to_lowercase())).collect()
+}
+
+// Troca cada lista pelos seus nomes (lidos com `read`) e remove as repetições
+// Uma lista sem nenhum nome é erro: sem ela, o programa cairia no default_user
+pub fn expand(
+    args: &[String],
+    mut read: impl FnMut(&str) -> Result<String, ActivityError>,
+) -> Result<Vec<String>, ActivityError> {
+    let mut names = Vec::new();
+
+    for arg in args {
+        if !is_list(arg) {
+            names.push(arg.clone());
+            continue;
+        }
+
+        let listed = parse_list(&read(arg)?);
+        if listed.is_empty() {
+            return Err(ActivityError::InvalidArgument(format!("{} lists no usernames", list_name(arg))));
+        }
+        names.extend(listed);
+    }
+
+    Ok(dedup(names))
+}
+
+// Valida todos os nomes antes de buscar qualquer um, juntando os problemas em um só erro
+pub fn validate_all(names: &[String], skip_validation: bool) -> Result<(), ActivityError> {
+    let problems: Vec<String> = names
+        .iter()
+        .filter_map(|name| Target::parse(name).validate(skip_validation).err())
+        .map(|e| match e {
+            // A mensagem já começa pelo nome ("user_name: the character '_'...")
+            ActivityError::InvalidUsername(message) => message,
+            other => other.to_string(),
+        })
+        .collect();
+
+    match problems.len() {
+        0 => Ok(()),
+        1 => Err(ActivityError::InvalidArgument(format!("invalid entry: {}", problems[0]))),
+        count => Err(ActivityError::InvalidArgument(format!(
+            "{} invalid entries: {}",
+            count,
+            problems.join("; ")
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(list: &[&str]) -> Vec<String> {
+        list.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_is_list() {
+        assert!(is_list("-"));
+        assert!(is_list("@team.txt"));
+        assert!(!is_list("@"));
+        assert!(!is_list("octocat"));
+        assert!(!is_list("rust-lang/rust"));
+    }
+
+    #[test]
+    fn test_parse_list_skips_comments_and_blank_lines() {
+        let text = "# time de infraestrutura\n\noctocat\n  torvalds   # lead\r\n\t\n#fim\nrust-lang/rust\n";
+        assert_eq!(parse_list(text), strings(&["octocat", "torvalds", "rust-lang/rust"]));
+        assert!(parse_list("# só comentários\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_dedup_keeps_the_first_occurrence() {
+        let names = strings(&["octocat", "torvalds", "Octocat", "gvanrossum", "torvalds"]);
+        assert_eq!(dedup(names), strings(&["octocat", "torvalds", "gvanrossum"]));
+    }
+
+    #[test]
+    fn test_expand_mixes_names_and_lists() {
+        let mut read_from = Vec::new();
+        let read = |arg: &str| {
+            read_from.push(arg.to_string());
+            match arg {
+                "@team.txt" => Ok("torvalds\noctocat\n".to_string()),
+                "-" => Ok("gvanrossum\n".to_string()),
+                _ => unreachable!("not a list: {}", arg),
+            }
+        };
+
+        let names = expand(&strings(&["octocat", "@team.txt", "-", "rust-lang/rust"]), read).unwrap();
+        assert_eq!(names, strings(&["octocat", "torvalds", "gvanrossum", "rust-lang/rust"]));
+        assert_eq!(read_from, strings(&["@team.txt", "-"]));
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        let empty = expand(&strings(&["@empty.txt"]), |_| Ok("# ninguém\n".to_string())).unwrap_err();
+        assert_eq!(empty.to_string(), "Invalid argument: empty.txt lists no usernames");
+
+        let missing = expand(&strings(&["@missing.txt"]), |arg| {
+            Err(ActivityError::io(list_name(arg), std::io::ErrorKind::NotFound.into()))
+        });
+        assert!(matches!(missing, Err(ActivityError::IoError { ref path, .. }) if path == "missing.txt"));
+    }
+
+    #[test]
+    fn test_validate_all_reports_every_invalid_entry() {
+        assert!(validate_all(&strings(&["octocat", "rust-lang/rust"]), false).is_ok());
+
+        let error = validate_all(&strings(&["user_name", "octocat", "-bad", "a/b/c"]), false).unwrap_err();
+        let message = error.to_string();
+        assert!(message.starts_with("Invalid argument: 3 invalid entries: user_name: the character '_'"));
+        assert!(message.contains("; -bad: cannot start with a hyphen; a/b/c: "));
+
+        let error = validate_all(&strings(&["octocat", "bad--name"]), false).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid argument: invalid entry: bad--name: cannot contain consecutive hyphens"
+        );
+
+        // --skip-validation: só o que quebraria a URL
+        assert!(validate_all(&strings(&["user_name"]), true).is_ok());
+        assert!(validate_all(&strings(&["a b"]), true).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+//! Este módulo trata o Ctrl-C enquanto as páginas de eventos são buscadas
+//!
+//! Sem isso, o SIGINT mata o processo na hora: os eventos já buscados se perdem
+//! e o temporário de --output fica para trás (o Drop de AtomicFile não roda).
+//! Com um [`Catch`] ativo, o Ctrl-C só liga uma flag; quem busca as páginas a
+//! consulta antes de cada requisição, mostra o que já chegou e sai com o código 130
+//!
+//! Um segundo Ctrl-C volta a matar o processo: a requisição em andamento pode
+//! demorar (até o tempo limite), e quem insiste não quer esperar por ela
+//!
+//! Sem a crate `ctrlc`, o tratador é instalado com a função `signal` da libc,
+//! que a biblioteca padrão já liga ao programa. Fora do Unix, nada muda
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// CONCEITO: static com Atomic
+// O tratador de sinal roda "por cima" do programa, a qualquer momento: ele só pode
+// mexer em algo seguro nesse contexto, como um AtomicBool (sem locks, sem alocação)
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+// Houve um Ctrl-C desde que o Catch atual foi instalado?
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+// CONCEITO: Guard (RAII), como o RawMode de pager.rs
+// Enquanto o valor existe, o Ctrl-C só liga a flag; no Drop, ele volta a encerrar o programa
+pub struct Catch {
+    _private: (),
+}
+
+impl Catch {
+    pub fn install() -> Catch {
+        INTERRUPTED.store(false, Ordering::SeqCst);
+        #[cfg(unix)]
+        sys::catch();
+        Catch { _private: () }
+    }
+}
+
+impl Drop for Catch {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        sys::restore();
+    }
+}
+
+// CONCEITO: FFI (Foreign Function Interface)
+// `extern "C"` declara uma função da libc; chamá-la é `unsafe` porque o compilador
+// não consegue verificar o que acontece do outro lado
+#[cfg(unix)]
+mod sys {
+    use std::os::raw::c_int;
+    use std::sync::atomic::Ordering;
+
+    // O número do SIGINT e o tratador padrão (encerrar) são os mesmos em todo Unix
+    pub const SIGINT: c_int = 2;
+    const SIG_DFL: usize = 0;
+
+    extern "C" {
+        fn signal(signum: c_int, handler: usize) -> usize;
+    }
+
+    pub fn catch() {
+        // Um ponteiro de função vira o endereço que a libc espera
+        set_handler(on_sigint as extern "C" fn(c_int) as usize);
+    }
+
+    pub fn restore() {
+        set_handler(SIG_DFL);
+    }
+
+    fn set_handler(handler: usize) {
+        // SAFETY: `handler` é SIG_DFL ou on_sigint, que só faz operações
+        // permitidas dentro de um tratador de sinal
+        unsafe {
+            signal(SIGINT, handler);
+        }
+    }
+
+    // Liga a flag e devolve o Ctrl-C ao padrão: o próximo encerra o programa
+    extern "C" fn on_sigint(_signum: c_int) {
+        super::INTERRUPTED.store(true, Ordering::SeqCst);
+        set_handler(SIG_DFL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    extern "C" {
+        fn raise(signum: std::os::raw::c_int) -> std::os::raw::c_int;
+    }
+
+    // Um só teste: a flag é global, e testes em paralelo a disputariam
+    #[cfg(unix)]
+    #[test]
+    fn test_ctrl_c_sets_the_flag_instead_of_exiting() {
+        let catch = Catch::install();
+        assert!(!interrupted());
+
+        // SAFETY: o tratador instalado acima só liga a flag
+        unsafe {
+            raise(sys::SIGINT);
+        }
+        assert!(interrupted());
+
+        // Um novo Catch começa sem o Ctrl-C anterior
+        drop(catch);
+        let _catch = Catch::install();
+        assert!(!interrupted());
+    }
+}
@@ -0,0 +1,1288 @@
+//! Este módulo é um pequeno leitor de JSON, feito "na unha" como o resto do projeto
+//! Em vez de procurar trechos com find() e fatiar, um cursor percorre o texto
+//! UMA vez, reconhecendo cada pedaço (token): strings, números, literais,
+//! objetos e arrays - e monta uma árvore de valores
+//!
+//! A árvore é "emprestada": strings e números continuam apontando para o
+//! texto original (&'a str), sem cópias. Os escapes das strings (\n, \u00e9...)
+//! só são decodificados quando alguém pede o valor (decode_string)
+
+use std::fmt;
+
+use crate::error::ActivityError;
+
+// Tamanho (em caracteres) do trecho de texto mostrado em erros de parsing
+const SNIPPET_MAX_CHARS: usize = 80;
+
+// Profundidade máxima de objetos/arrays aninhados
+// O parser é recursivo: sem limite, um texto como "[[[[[[..." estouraria a pilha
+const MAX_DEPTH: usize = 128;
+
+// CONCEITO: Lifetimes ('a)
+// JsonValue<'a> guarda referências para o texto de entrada;
+// o compilador garante que a árvore não vive mais que o texto
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue<'a> {
+    Null,
+    Bool(bool),
+    // O número como aparece no texto ("42", "-1.5e3"); quem usa escolhe o tipo
+    Number(&'a str),
+    // Conteúdo entre as aspas, AINDA com os escapes
+    String(&'a str),
+    Array(Vec<JsonValue<'a>>),
+    // Pares (chave, valor) na ordem do texto
+    // Um Vec (e não um HashMap) porque objetos da API têm poucas chaves
+    Object(Vec<(&'a str, JsonValue<'a>)>),
+}
+
+impl<'a> JsonValue<'a> {
+    // Valor de uma chave, se este valor for um objeto
+    // Só olha as chaves DESTE objeto, nunca as de objetos aninhados
+    // As chaves são comparadas sem decodificar escapes (as da API são ASCII simples)
+    pub fn get(&self, key: &str) -> Option<&JsonValue<'a>> {
+        match self {
+            JsonValue::Object(entries) => entries
+                .iter()
+                .find(|(entry_key, _)| *entry_key == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn is_object(&self) -> bool {
+        matches!(self, JsonValue::Object(_))
+    }
+}
+
+// CONCEITO: Display para serializar
+// Reescreve o valor como JSON compacto (sem espaços): {"a":[1,null]}
+// Strings e números já guardam o texto original, com os escapes,
+// então basta recolocar as aspas - nada precisa ser escapado de novo
+impl fmt::Display for JsonValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(flag) => write!(f, "{}", flag),
+            JsonValue::Number(number) => write!(f, "{}", number),
+            JsonValue::String(raw) => write!(f, "\"{}\"", raw),
+            JsonValue::Array(items) => {
+                write!(f, "[")?;
+                for (position, item) in items.iter().enumerate() {
+                    if position > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            JsonValue::Object(entries) => {
+                write!(f, "{{")?;
+                for (position, (key, value)) in entries.iter().enumerate() {
+                    if position > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "\"{}\":{}", key, value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+// CONCEITO: Um tipo para ler, outro para escrever
+// JsonValue<'a> é o que LEMOS: empresta o texto, e as strings continuam escapadas
+// Json é o que ESCREVEMOS (--json, ndjson, webhooks, cache...): dono dos dados, com
+// as strings como o programa as conhece. Os escapes saem num lugar só, no Display,
+// em vez de cada formato concatenar aspas e barras por conta própria
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    // O número já no formato JSON ("42", "0.09"); vem de From<u64>... ou de Json::decimal
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    // Pares (chave, valor), escritos na ordem em que foram montados
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    // Um objeto a partir de pares com chaves fixas: Json::object([("a", Json::Null)])
+    pub fn object<'k>(entries: impl IntoIterator<Item = (&'k str, Json)>) -> Json {
+        Json::Object(entries.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+    }
+
+    // Um array com a conversão de cada item
+    pub fn array<T: Into<Json>>(items: impl IntoIterator<Item = T>) -> Json {
+        Json::Array(items.into_iter().map(Into::into).collect())
+    }
+
+    // Um número com `places` casas decimais ("0.09"); NaN e infinito (que o JSON não
+    // tem) viram null
+    pub fn decimal(value: f64, places: usize) -> Json {
+        if value.is_finite() {
+            Json::Number(format!("{:.*}", places, value))
+        } else {
+            Json::Null
+        }
+    }
+
+    // Acrescenta um par no fim de um objeto (em outro valor, não faz nada)
+    pub fn push(&mut self, key: &str, value: Json) {
+        if let Json::Object(entries) = self {
+            entries.push((key.to_string(), value));
+        }
+    }
+}
+
+// CONCEITO: From/Into
+// Com estas conversões, quem monta um objeto escreve Json::from(count) ou
+// `name.into()` sem se preocupar com aspas ou escapes
+impl From<bool> for Json {
+    fn from(flag: bool) -> Json {
+        Json::Bool(flag)
+    }
+}
+
+impl From<u64> for Json {
+    fn from(number: u64) -> Json {
+        Json::Number(number.to_string())
+    }
+}
+
+impl From<u32> for Json {
+    fn from(number: u32) -> Json {
+        Json::Number(number.to_string())
+    }
+}
+
+impl From<i32> for Json {
+    fn from(number: i32) -> Json {
+        Json::Number(number.to_string())
+    }
+}
+
+impl From<i64> for Json {
+    fn from(number: i64) -> Json {
+        Json::Number(number.to_string())
+    }
+}
+
+impl From<usize> for Json {
+    fn from(number: usize) -> Json {
+        Json::Number(number.to_string())
+    }
+}
+
+impl From<&str> for Json {
+    fn from(text: &str) -> Json {
+        Json::String(text.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(text: String) -> Json {
+        Json::String(text)
+    }
+}
+
+// None vira null: "campo ausente" fica explícito na saída
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Json {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+// JSON compacto, sem espaços: {"a":[1,null],"b":"x\n"}
+// Com {:#} (a forma "alternativa" do format!), indentado como --raw:
+// um valor por linha e 2 espaços por nível
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !f.alternate() {
+            return self.write_compact(f);
+        }
+        let mut compact = String::new();
+        self.write_compact(&mut compact)?;
+        // O texto compacto é sempre JSON válido; se não fosse, sairia como está
+        f.write_str(&pretty(&compact).unwrap_or(compact))
+    }
+}
+
+impl Json {
+    // CONCEITO: fmt::Write
+    // O mesmo código escreve num Formatter (Display) ou numa String
+    fn write_compact(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        match self {
+            Json::Null => out.write_str("null"),
+            Json::Bool(flag) => write!(out, "{}", flag),
+            Json::Number(number) => out.write_str(number),
+            Json::String(text) => write_escaped(out, text),
+            Json::Array(items) => {
+                out.write_char('[')?;
+                for (position, item) in items.iter().enumerate() {
+                    if position > 0 {
+                        out.write_char(',')?;
+                    }
+                    item.write_compact(out)?;
+                }
+                out.write_char(']')
+            }
+            Json::Object(entries) => {
+                out.write_char('{')?;
+                for (position, (key, value)) in entries.iter().enumerate() {
+                    if position > 0 {
+                        out.write_char(',')?;
+                    }
+                    write_escaped(out, key)?;
+                    out.write_char(':')?;
+                    value.write_compact(out)?;
+                }
+                out.write_char('}')
+            }
+        }
+    }
+}
+
+// Uma string como literal JSON, com aspas e escapes (o caminho inverso de decode_string)
+// Caracteres fora do BMP (emoji) vão como estão: o texto é UTF-8, e o JSON aceita
+// qualquer caractere dentro das aspas menos ", \ e os de controle
+fn write_escaped(f: &mut impl fmt::Write, text: &str) -> fmt::Result {
+    f.write_char('"')?;
+    // Trechos sem nada para escapar são escritos de uma vez
+    let mut start = 0;
+    for (position, c) in text.char_indices() {
+        if c != '"' && c != '\\' && (c as u32) >= 0x20 {
+            continue;
+        }
+        f.write_str(&text[start..position])?;
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            '\u{8}' => f.write_str("\\b")?,
+            '\u{c}' => f.write_str("\\f")?,
+            // Outros caracteres de controle precisam do formato \uXXXX
+            c => write!(f, "\\u{:04x}", c as u32)?,
+        }
+        start = position + c.len_utf8();
+    }
+    f.write_str(&text[start..])?;
+    f.write_char('"')
+}
+
+// Interpreta um texto com exatamente UM valor JSON (espaços nas pontas são permitidos)
+pub fn parse(text: &str) -> Result<JsonValue<'_>, ActivityError> {
+    let mut cursor = Cursor::new(text);
+    let value = cursor.parse_value(0)?;
+
+    cursor.skip_whitespace();
+    if !cursor.at_end() {
+        return Err(cursor.error("Unexpected data after the JSON value"));
+    }
+
+    Ok(value)
+}
+
+// Divide um array JSON no nível raiz, devolvendo o TEXTO de cada elemento
+// ("[{...}, {...}]" -> ["{...}", "{...}"])
+//
+// Os elementos não são interpretados aqui, só delimitados (respeitando strings e
+// aninhamento). Assim quem chama pode interpretar um por um e continuar
+// mesmo que um deles esteja mal formado
+pub fn split_array(text: &str) -> Result<Vec<&str>, ActivityError> {
+    let mut cursor = Cursor::new(text);
+    cursor.skip_whitespace();
+
+    if cursor.peek() != Some(b'[') {
+        return Err(ActivityError::parse("Expected JSON array"));
+    }
+    cursor.pos += 1;
+
+    let mut elements = Vec::new();
+    cursor.skip_whitespace();
+    if cursor.eat(b']') {
+        return cursor.expect_end().map(|_| elements);
+    }
+
+    loop {
+        cursor.skip_whitespace();
+        let start = cursor.pos;
+        cursor.skip_element(b']')?;
+        elements.push(text[start..cursor.pos].trim_end());
+
+        // Depois de um elemento: vírgula (tem mais) ou ] (acabou)
+        match cursor.next_byte() {
+            Some(b',') => continue,
+            Some(b']') => break,
+            Some(_) => return Err(cursor.error_before("Expected ',' or ']' in array")),
+            None => return Err(cursor.error("Unterminated array")),
+        }
+    }
+
+    cursor.expect_end()?;
+    Ok(elements)
+}
+
+/// Um elemento de um array no nível raiz: o texto dele e, se for um JSON válido, a árvore
+#[derive(Debug)]
+pub struct ArrayItem<'a> {
+    pub text: &'a str,
+    pub value: Option<JsonValue<'a>>,
+}
+
+// Como split_array, mas já interpretando cada elemento na MESMA passada pelo texto
+// (split_array + parse de cada pedaço percorreria cada elemento duas vezes)
+//
+// Um elemento mal formado não interrompe nada: ele só é delimitado, como em
+// split_array, e fica com value None para quem chama reinterpretá-lo e mostrar o erro
+// O array em si precisa estar bem formado, como em split_array
+pub fn parse_array_items(text: &str) -> Result<Vec<ArrayItem<'_>>, ActivityError> {
+    let mut cursor = Cursor::new(text);
+    cursor.skip_whitespace();
+
+    if cursor.peek() != Some(b'[') {
+        return Err(ActivityError::parse("Expected JSON array"));
+    }
+    cursor.pos += 1;
+
+    let mut items = Vec::new();
+    cursor.skip_whitespace();
+    if cursor.eat(b']') {
+        return cursor.expect_end().map(|_| items);
+    }
+
+    loop {
+        cursor.skip_whitespace();
+        let start = cursor.pos;
+        let value = cursor.parse_element(b']')?;
+        items.push(ArrayItem { text: text[start..cursor.pos].trim_end(), value });
+
+        match cursor.next_byte() {
+            Some(b',') => continue,
+            Some(b']') => break,
+            Some(_) => return Err(cursor.error_before("Expected ',' or ']' in array")),
+            None => return Err(cursor.error("Unterminated array")),
+        }
+    }
+
+    cursor.expect_end()?;
+    Ok(items)
+}
+
+// Como split_array, mas para um objeto no nível raiz: devolve (chave, TEXTO do valor)
+// ("{\"a\": [1], \"b\": {...}}" -> [("a", "[1]"), ("b", "{...}")])
+// As chaves vêm sem as aspas e sem decodificar escapes, como em JsonValue::get
+pub fn split_object(text: &str) -> Result<Vec<(&str, &str)>, ActivityError> {
+    let mut cursor = Cursor::new(text);
+    cursor.skip_whitespace();
+
+    if cursor.peek() != Some(b'{') {
+        return Err(ActivityError::parse("Expected JSON object"));
+    }
+    cursor.pos += 1;
+
+    let mut entries = Vec::new();
+    cursor.skip_whitespace();
+    if cursor.eat(b'}') {
+        return cursor.expect_end().map(|_| entries);
+    }
+
+    loop {
+        cursor.skip_whitespace();
+        if cursor.peek() != Some(b'"') {
+            return Err(cursor.error("Expected a string key in object"));
+        }
+        let key = cursor.parse_string()?;
+
+        cursor.skip_whitespace();
+        if !cursor.eat(b':') {
+            return Err(cursor.error("Expected ':' after object key"));
+        }
+
+        cursor.skip_whitespace();
+        let start = cursor.pos;
+        cursor.skip_element(b'}')?;
+        entries.push((key, text[start..cursor.pos].trim_end()));
+
+        match cursor.next_byte() {
+            Some(b',') => continue,
+            Some(b'}') => break,
+            Some(_) => return Err(cursor.error_before("Expected ',' or '}' in object")),
+            None => return Err(cursor.error("Unterminated object")),
+        }
+    }
+
+    cursor.expect_end()?;
+    Ok(entries)
+}
+
+// Reescreve um texto JSON com um valor por linha e 2 espaços por nível (--raw):
+//
+//   {
+//     "id": "1",
+//     "payload": {
+//       "commits": []
+//     }
+//   }
+//
+// Strings e números são copiados do texto como estão (os escapes continuam escapes,
+// "é" não vira "é"), então o resultado representa exatamente os mesmos dados
+// Objetos e arrays vazios ficam em uma linha ("{}", "[]")
+//
+// CONCEITO: Pilha explícita em vez de recursão
+// parse() é recursivo e por isso precisa de MAX_DEPTH; aqui os objetos/arrays abertos
+// ficam num Vec (o byte que fecha cada um), que cresce no heap: qualquer profundidade
+// cabe, e o texto é percorrido uma vez só
+pub fn pretty(text: &str) -> Result<String, ActivityError> {
+    let mut cursor = Cursor::new(text);
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut open: Vec<u8> = Vec::new();
+
+    loop {
+        // Aqui começa um valor: um escalar termina nesta volta; um objeto/array abre um nível
+        cursor.skip_whitespace();
+        match cursor.peek() {
+            Some(byte @ (b'{' | b'[')) => {
+                let close = if byte == b'{' { b'}' } else { b']' };
+                cursor.pos += 1;
+                cursor.skip_whitespace();
+                if cursor.eat(close) {
+                    out.push(byte as char);
+                    out.push(close as char);
+                } else {
+                    out.push(byte as char);
+                    open.push(close);
+                    new_line(&mut out, open.len());
+                    if close == b'}' {
+                        pretty_key(&mut cursor, &mut out)?;
+                    }
+                    continue;
+                }
+            }
+            Some(b'"') => {
+                let raw = cursor.parse_string()?;
+                // Um escape inválido ("\x") é erro aqui também, não só em quem decodifica
+                decode_string(raw)?;
+                out.push('"');
+                out.push_str(raw);
+                out.push('"');
+            }
+            Some(b'-' | b'0'..=b'9') => out.push_str(cursor.parse_number()?),
+            Some(b't') => out.push_str(pretty_literal(&mut cursor, "true")?),
+            Some(b'f') => out.push_str(pretty_literal(&mut cursor, "false")?),
+            Some(b'n') => out.push_str(pretty_literal(&mut cursor, "null")?),
+            Some(_) => return Err(cursor.error("Unexpected character")),
+            None => return Err(cursor.error("Unexpected end of input")),
+        }
+
+        // Um valor terminou: fecha os níveis que acabaram junto com ele, até a próxima ','
+        loop {
+            let close = match open.last() {
+                Some(&close) => close,
+                None => {
+                    cursor.expect_end()?;
+                    return Ok(out);
+                }
+            };
+            cursor.skip_whitespace();
+            match cursor.next_byte() {
+                Some(b',') => {
+                    out.push(',');
+                    new_line(&mut out, open.len());
+                    if close == b'}' {
+                        pretty_key(&mut cursor, &mut out)?;
+                    }
+                    break;
+                }
+                Some(byte) if byte == close => {
+                    open.pop();
+                    new_line(&mut out, open.len());
+                    out.push(close as char);
+                }
+                Some(_) if close == b'}' => return Err(cursor.error_before("Expected ',' or '}' in object")),
+                Some(_) => return Err(cursor.error_before("Expected ',' or ']' in array")),
+                None if close == b'}' => return Err(cursor.error("Unterminated object")),
+                None => return Err(cursor.error("Unterminated array")),
+            }
+        }
+    }
+}
+
+// Quebra a linha e indenta `depth` níveis de 2 espaços
+fn new_line(out: &mut String, depth: usize) {
+    out.push('\n');
+    for _ in 0..depth {
+        out.push_str("  ");
+    }
+}
+
+// A chave de um objeto e o ':', escritos como "key": (o valor vem na volta seguinte)
+fn pretty_key(cursor: &mut Cursor, out: &mut String) -> Result<(), ActivityError> {
+    cursor.skip_whitespace();
+    if cursor.peek() != Some(b'"') {
+        return Err(cursor.error("Expected a string key in object"));
+    }
+    let key = cursor.parse_string()?;
+    decode_string(key)?;
+
+    cursor.skip_whitespace();
+    if !cursor.eat(b':') {
+        return Err(cursor.error("Expected ':' after object key"));
+    }
+    out.push('"');
+    out.push_str(key);
+    out.push_str("\": ");
+    Ok(())
+}
+
+// true, false ou null, com as mesmas regras de parse() ("nullish" não é null)
+fn pretty_literal(cursor: &mut Cursor, literal: &'static str) -> Result<&'static str, ActivityError> {
+    cursor.parse_literal(literal, JsonValue::Null)?;
+    Ok(literal)
+}
+
+// Um trecho de até SNIPPET_MAX_CHARS caracteres em volta da posição `pos` (em bytes),
+// em uma linha, com "…" marcando onde o texto foi cortado
+// Ex: snippet_around(json, 120) -> "…\"type\":\"PushEvent\",\"repo\":{}…"
+//
+// CONCEITO: Fronteiras de caractere
+// Fatiar uma &str no meio de um caractere multi-byte (é, 🚀) causa panic
+// Por isso as pontas da janela vêm de char_indices(), que só devolve fronteiras válidas
+pub fn snippet_around(text: &str, pos: usize) -> String {
+    // A posição pode cair no meio de um caractere: recua até o início dele
+    let mut pos = pos.min(text.len());
+    while !text.is_char_boundary(pos) {
+        pos -= 1;
+    }
+
+    // Metade da janela antes da posição (ou desde o começo do texto)...
+    let start = text[..pos]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_MAX_CHARS / 2 - 1)
+        .map_or(0, |(i, _)| i);
+    // ...e o restante depois
+    let end = text[start..]
+        .char_indices()
+        .nth(SNIPPET_MAX_CHARS)
+        .map_or(text.len(), |(i, _)| start + i);
+
+    // split_whitespace + join troca quebras de linha e indentação por um espaço
+    let window = text[start..end].split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push('…');
+    }
+    snippet.push_str(&window);
+    if end < text.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+// Decodifica os escapes de uma string JSON (o conteúdo entre as aspas)
+// Suporta \" \\ \/ \b \f \n \r \t e \uXXXX, incluindo pares surrogate
+// (emoji como 🚀 chegam como "\ud83d\ude80")
+pub fn decode_string(raw: &str) -> Result<String, ActivityError> {
+    // Caminho rápido: sem barras não há nada para decodificar
+    if !raw.contains('\\') {
+        return Ok(raw.to_string());
+    }
+
+    // CONCEITO: String::with_capacity
+    // Reserva memória de antemão; o resultado nunca é maior que a entrada
+    let mut result = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+
+    // CONCEITO: while let
+    // Repete enquanto o padrão casar - aqui, enquanto houver caracteres
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            result.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            Some('"') => result.push('"'),
+            Some('\\') => result.push('\\'),
+            Some('/') => result.push('/'),
+            Some('b') => result.push('\u{0008}'),
+            Some('f') => result.push('\u{000C}'),
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some('u') => {
+                let high = read_hex4(&mut chars)?;
+                result.push(decode_utf16_escape(high, &mut chars)?);
+            }
+            Some(other) => {
+                return Err(ActivityError::parse(format!(
+                    "Invalid escape sequence '\\{}' in string",
+                    other
+                )));
+            }
+            None => {
+                return Err(ActivityError::parse(
+                    "Invalid escape sequence '\\' at end of string",
+                ));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+// Lê os 4 dígitos hexadecimais de um escape \uXXXX
+// CONCEITO: impl Iterator
+// Aceita qualquer iterator de char (aqui, o Chars da string sendo decodificada)
+// Os dígitos são somados um a um, sem montar uma String: a String só aparece no erro
+fn read_hex4(chars: &mut impl Iterator<Item = char>) -> Result<u16, ActivityError> {
+    let mut digits = ['\0'; 4];
+    let mut count = 0;
+    let mut valid = 0;
+    let mut value: u16 = 0;
+
+    for (slot, ch) in digits.iter_mut().zip(chars.take(4)) {
+        *slot = ch;
+        count += 1;
+        // to_digit(16) só aceita 0-9, a-f e A-F; cada dígito cabe em 4 bits
+        if let Some(digit) = ch.to_digit(16) {
+            value = value << 4 | digit as u16;
+            valid += 1;
+        }
+    }
+
+    if valid != 4 {
+        let digits: String = digits[..count].iter().collect();
+        return Err(ActivityError::parse(format!(
+            "Invalid escape sequence '\\u{}' in string",
+            digits
+        )));
+    }
+    Ok(value)
+}
+
+// Converte uma unidade UTF-16 (e, se for surrogate alto, a próxima) em char
+fn decode_utf16_escape(high: u16, chars: &mut std::str::Chars) -> Result<char, ActivityError> {
+    // Fora da faixa de surrogates: é um caractere comum do BMP
+    if !(0xD800..=0xDFFF).contains(&high) {
+        // unwrap_or é seguro: todo u16 fora dos surrogates é um char válido
+        return Ok(char::from_u32(u32::from(high)).unwrap_or('\u{FFFD}'));
+    }
+
+    // Surrogate baixo sozinho (sem o alto antes) é inválido
+    if high >= 0xDC00 {
+        return Err(ActivityError::parse(format!(
+            "Invalid escape sequence '\\u{:04x}' (unpaired low surrogate)",
+            high
+        )));
+    }
+
+    // Surrogate alto: o próximo escape PRECISA ser \uDC00..\uDFFF
+    // as_str() mostra o restante sem consumir, para checar o prefixo "\u"
+    if !chars.as_str().starts_with("\\u") {
+        return Err(ActivityError::parse(format!(
+            "Invalid escape sequence '\\u{:04x}' (unpaired high surrogate)",
+            high
+        )));
+    }
+    chars.next();
+    chars.next();
+
+    let low = read_hex4(chars)?;
+    if !(0xDC00..=0xDFFF).contains(&low) {
+        return Err(ActivityError::parse(format!(
+            "Invalid escape sequence '\\u{:04x}\\u{:04x}' (invalid surrogate pair)",
+            high, low
+        )));
+    }
+
+    // Fórmula do UTF-16 para juntar o par em um único code point
+    let code_point = 0x10000 + ((u32::from(high) - 0xD800) << 10) + (u32::from(low) - 0xDC00);
+    char::from_u32(code_point).ok_or_else(|| {
+        ActivityError::parse(format!("Invalid code point U+{:X} in string", code_point))
+    })
+}
+
+// CONCEITO: Cursor
+// Guarda o texto e a posição atual (em BYTES); cada método avança a posição
+// Todos os caracteres estruturais do JSON ({ } [ ] : , " e os literais) são ASCII,
+// então olhar byte a byte é seguro: um byte ASCII nunca aparece no meio de um
+// caractere UTF-8 multi-byte, e só fatiamos o texto em posições de bytes ASCII
+struct Cursor<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(text: &'a str) -> Self {
+        Cursor { text, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn next_byte(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.pos += 1;
+        Some(byte)
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.text.len()
+    }
+
+    // Consome o byte se for o esperado
+    fn eat(&mut self, expected: u8) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    // Só espaços podem sobrar depois do valor
+    fn expect_end(&mut self) -> Result<(), ActivityError> {
+        self.skip_whitespace();
+        if self.at_end() {
+            Ok(())
+        } else {
+            Err(self.error("Unexpected data after the JSON value"))
+        }
+    }
+
+    // Erro apontando a posição atual, com o trecho do texto em volta dela
+    fn error(&self, message: &str) -> ActivityError {
+        self.error_at(message, self.pos)
+    }
+
+    // Erro apontando o byte que acabamos de consumir
+    fn error_before(&self, message: &str) -> ActivityError {
+        self.error_at(message, self.pos.saturating_sub(1))
+    }
+
+    fn error_at(&self, message: &str, pos: usize) -> ActivityError {
+        ActivityError::parse(format!("{} at byte {}", message, pos))
+            .near(|| snippet_around(self.text, pos))
+    }
+
+    // Interpreta um valor qualquer a partir da posição atual
+    // `depth` conta quantos objetos/arrays já estão abertos (ver MAX_DEPTH)
+    fn parse_value(&mut self, depth: usize) -> Result<JsonValue<'a>, ActivityError> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some(b'{') => self.parse_object(depth + 1),
+            Some(b'[') => self.parse_array(depth + 1),
+            Some(b'"') => self.parse_string().map(JsonValue::String),
+            Some(b'-' | b'0'..=b'9') => self.parse_number().map(JsonValue::Number),
+            Some(b't') => self.parse_literal("true", JsonValue::Bool(true)),
+            Some(b'f') => self.parse_literal("false", JsonValue::Bool(false)),
+            Some(b'n') => self.parse_literal("null", JsonValue::Null),
+            Some(_) => Err(self.error("Unexpected character")),
+            None => Err(self.error("Unexpected end of input")),
+        }
+    }
+
+    fn parse_object(&mut self, depth: usize) -> Result<JsonValue<'a>, ActivityError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("JSON nested too deeply"));
+        }
+
+        self.pos += 1;  // {
+        let mut entries = Vec::new();
+
+        self.skip_whitespace();
+        if self.eat(b'}') {
+            return Ok(JsonValue::Object(entries));
+        }
+
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(self.error("Expected a string key in object"));
+            }
+            let key = self.parse_string()?;
+
+            // Espaços são permitidos dos dois lados do ':' ("key" : value)
+            self.skip_whitespace();
+            if !self.eat(b':') {
+                return Err(self.error("Expected ':' after object key"));
+            }
+
+            let value = self.parse_value(depth)?;
+            entries.push((key, value));
+
+            self.skip_whitespace();
+            match self.next_byte() {
+                Some(b',') => continue,
+                Some(b'}') => return Ok(JsonValue::Object(entries)),
+                Some(_) => return Err(self.error_before("Expected ',' or '}' in object")),
+                None => return Err(self.error("Unterminated object")),
+            }
+        }
+    }
+
+    fn parse_array(&mut self, depth: usize) -> Result<JsonValue<'a>, ActivityError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("JSON nested too deeply"));
+        }
+
+        self.pos += 1;  // [
+        let mut items = Vec::new();
+
+        self.skip_whitespace();
+        if self.eat(b']') {
+            return Ok(JsonValue::Array(items));
+        }
+
+        loop {
+            items.push(self.parse_value(depth)?);
+
+            self.skip_whitespace();
+            match self.next_byte() {
+                Some(b',') => continue,
+                Some(b']') => return Ok(JsonValue::Array(items)),
+                Some(_) => return Err(self.error_before("Expected ',' or ']' in array")),
+                None => return Err(self.error("Unterminated array")),
+            }
+        }
+    }
+
+    // Devolve o conteúdo entre as aspas, sem decodificar
+    // Para achar o fim basta saber que \ "protege" o caractere seguinte:
+    // em "C:\\" a barra final é ela mesma escapada e a aspa seguinte FECHA a string
+    // (a validade de cada escape é checada depois, em decode_string)
+    //
+    // CONCEITO: position() em vez de um byte por volta do loop
+    // Só " e \ interessam: o resto da string é pulado de uma vez. É o trecho mais
+    // executado do parser (toda chave e todo valor de texto passam por aqui)
+    fn parse_string(&mut self) -> Result<&'a str, ActivityError> {
+        let start = self.pos;
+        let bytes = self.text.as_bytes();
+        self.pos += 1;  // "
+
+        while let Some(offset) = bytes[self.pos..].iter().position(|&b| b == b'"' || b == b'\\') {
+            self.pos += offset + 1;
+            if bytes[self.pos - 1] == b'"' {
+                return Ok(&self.text[start + 1..self.pos - 1]);
+            }
+            // Pula o caractere escapado (se não for ASCII, é um escape
+            // inválido que decode_string vai reportar; só não pode ser a aspa final)
+            if self.pos >= bytes.len() {
+                break;
+            }
+            self.pos += 1;
+        }
+
+        self.pos = start;
+        Err(self.error("Unterminated string"))
+    }
+
+    // Número no formato JSON: -?(0|[1-9][0-9]*)(.[0-9]+)?([eE][+-]?[0-9]+)?
+    fn parse_number(&mut self) -> Result<&'a str, ActivityError> {
+        let start = self.pos;
+
+        self.eat(b'-');
+        if self.eat(b'0') {
+            // Zeros à esquerda ("007") não são permitidos
+        } else if !self.eat_digits() {
+            return Err(self.error("Invalid number"));
+        }
+
+        if self.eat(b'.') && !self.eat_digits() {
+            return Err(self.error("Invalid number: expected digits after '.'"));
+        }
+
+        if self.eat(b'e') || self.eat(b'E') {
+            if !self.eat(b'+') {
+                self.eat(b'-');
+            }
+            if !self.eat_digits() {
+                return Err(self.error("Invalid number: expected digits in exponent"));
+            }
+        }
+
+        Ok(&self.text[start..self.pos])
+    }
+
+    // Consome uma sequência de dígitos; false se não havia nenhum
+    fn eat_digits(&mut self) -> bool {
+        let start = self.pos;
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+        self.pos > start
+    }
+
+    fn parse_literal(
+        &mut self,
+        literal: &str,
+        value: JsonValue<'a>,
+    ) -> Result<JsonValue<'a>, ActivityError> {
+        if !self.text[self.pos..].starts_with(literal) {
+            return Err(self.error("Invalid literal"));
+        }
+        self.pos += literal.len();
+
+        // "nullish" não é null: o literal precisa terminar ali
+        if matches!(self.peek(), Some(b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_')) {
+            return Err(self.error("Invalid literal"));
+        }
+
+        Ok(value)
+    }
+
+    // Interpreta o elemento que começa aqui e termina na ',' ou no `close` seguinte,
+    // deixando o cursor nesse byte
+    // Se ele não for um valor JSON válido (ou tiver algo a mais antes da ','), volta
+    // e só o delimita: None. O erro é refeito por quem chama, a partir do texto dele
+    fn parse_element(&mut self, close: u8) -> Result<Option<JsonValue<'a>>, ActivityError> {
+        let start = self.pos;
+        if let Ok(value) = self.parse_value(0) {
+            self.skip_whitespace();
+            if matches!(self.peek(), Some(byte) if byte == b',' || byte == close) {
+                return Ok(Some(value));
+            }
+        }
+
+        self.pos = start;
+        self.skip_element(close)?;
+        Ok(None)
+    }
+
+    // Pula um elemento de array (ou valor de objeto) sem interpretá-lo,
+    // parando na ',' ou no `close` (']' ou '}') que o encerra
+    // Só acompanha strings e aninhamento - o conteúdo é validado depois, por parse()
+    fn skip_element(&mut self, close: u8) -> Result<(), ActivityError> {
+        let mut depth = 0usize;
+
+        while let Some(byte) = self.peek() {
+            match byte {
+                b'"' => {
+                    self.parse_string()?;
+                    continue;
+                }
+                b'{' | b'[' => depth += 1,
+                b'}' | b']' if depth > 0 => depth -= 1,
+                // Fim do elemento: a ',' ou o fechamento do array/objeto externo
+                b',' if depth == 0 => return Ok(()),
+                _ if byte == close && depth == 0 => return Ok(()),
+                _ => {}
+            }
+            self.pos += 1;
+        }
+
+        match close {
+            b'}' => Err(self.error("Unterminated object")),
+            _ => Err(self.error("Unterminated array")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scalars() {
+        assert_eq!(parse("null").unwrap(), JsonValue::Null);
+        assert_eq!(parse(" true ").unwrap(), JsonValue::Bool(true));
+        assert_eq!(parse("false").unwrap(), JsonValue::Bool(false));
+        assert_eq!(parse("-12.5e3").unwrap(), JsonValue::Number("-12.5e3"));
+        assert_eq!(parse(r#""a\"b""#).unwrap(), JsonValue::String(r#"a\"b"#));
+    }
+
+    #[test]
+    fn test_parse_nested_structures() {
+        let value = parse(r#"{"a": [1, {"b": null}], "c" : "d"}"#).unwrap();
+
+        assert_eq!(value.get("c"), Some(&JsonValue::String("d")));
+        assert_eq!(
+            value.get("a"),
+            Some(&JsonValue::Array(vec![
+                JsonValue::Number("1"),
+                JsonValue::Object(vec![("b", JsonValue::Null)]),
+            ]))
+        );
+        // get() não desce em objetos aninhados
+        assert_eq!(value.get("b"), None);
+    }
+
+    #[test]
+    fn test_display_writes_compact_json() {
+        let text = r#"{ "a" : [1, {"b": null}, true],
+                        "c": "quote \" and caf\u00e9" }"#;
+        let compact = parse(text).unwrap().to_string();
+        assert_eq!(compact, r#"{"a":[1,{"b":null},true],"c":"quote \" and caf\u00e9"}"#);
+
+        // O texto compacto é JSON válido e descreve o mesmo valor
+        assert_eq!(parse(&compact).unwrap(), parse(text).unwrap());
+        assert_eq!(parse("[]").unwrap().to_string(), "[]");
+    }
+
+    #[test]
+    fn test_keys_are_matched_exactly() {
+        // "ref" não pode casar com "ref_type"
+        let value = parse(r#"{"ref_type": "tag", "ref": "v1"}"#).unwrap();
+        assert_eq!(value.get("ref"), Some(&JsonValue::String("v1")));
+        assert_eq!(value.get("re"), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_json() {
+        for text in [
+            "",
+            "{",
+            "[1, 2",
+            r#"{"a" 1}"#,
+            r#"{"a": 1,}"#,
+            r#"{a: 1}"#,
+            "[1 2]",
+            "nul",
+            "nullish",
+            "01",
+            "1.",
+            "1e",
+            r#""unterminated"#,
+            "[] []",
+        ] {
+            assert!(parse(text).is_err(), "{:?} should be rejected", text);
+        }
+    }
+
+    #[test]
+    fn test_errors_report_byte_position() {
+        let error = parse(r#"{"a": 1 "b": 2}"#).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Failed to parse response: Expected ',' or '}' in object at byte 8 near: {"a": 1 "b": 2}"#
+        );
+    }
+
+    #[test]
+    fn test_snippet_around_is_a_window_on_one_line() {
+        let text = format!("{}\n  \"repo\": {{}}{}", "a".repeat(100), "b".repeat(100));
+        let snippet = snippet_around(&text, 100);
+
+        assert!(snippet.starts_with("…aaa"));
+        assert!(snippet.contains(r#"aaa "repo": {}bbb"#));
+        assert!(snippet.ends_with("bbb…"));
+        assert!(!snippet.contains('\n'));
+
+        // Texto curto: sem cortes
+        assert_eq!(snippet_around("{}", 1), "{}");
+    }
+
+    #[test]
+    fn test_snippet_around_never_splits_multibyte_characters() {
+        let text = "é🚀修".repeat(50);
+        // Todas as posições, inclusive as que caem no meio de um caractere
+        for pos in 0..=text.len() + 1 {
+            let snippet = snippet_around(&text, pos);
+            assert!(snippet.chars().filter(|c| *c != '…').count() <= SNIPPET_MAX_CHARS);
+        }
+    }
+
+    #[test]
+    fn test_deep_nesting_is_an_error_not_a_crash() {
+        let deep = "[".repeat(10_000);
+        assert!(parse(&deep).is_err());
+
+        let ok = format!("{}{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+        assert!(parse(&ok).is_ok());
+    }
+
+    #[test]
+    fn test_split_array() {
+        let text = r#" [ {"m": "a } b { c, ]"}, [1, 2] , "x" ] "#;
+        assert_eq!(
+            split_array(text).unwrap(),
+            vec![r#"{"m": "a } b { c, ]"}"#, "[1, 2]", r#""x""#]
+        );
+        assert!(split_array("[]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_split_array_errors() {
+        assert_eq!(
+            split_array(r#"{"message": "Not Found"}"#).unwrap_err().to_string(),
+            "Failed to parse response: Expected JSON array"
+        );
+        assert!(split_array("[1, 2").is_err());
+        assert!(split_array(r#"["open"#).is_err());
+        assert!(split_array("[1] x").is_err());
+    }
+
+    #[test]
+    fn test_parse_array_items_matches_split_array() {
+        let texts = [
+            r#" [ {"m": "a } b { c, ]"}, [1, 2] , "x" ] "#,
+            r#"[{"ok": 1}, {"bad": 1,}, {"x": 1} trailing, , "\"", {"e": "\q"}]"#,
+            "[]",
+        ];
+        for text in texts {
+            let items = parse_array_items(text).unwrap();
+            let item_texts: Vec<&str> = items.iter().map(|item| item.text).collect();
+            assert_eq!(item_texts, split_array(text).unwrap(), "{}", text);
+            // Cada árvore é a mesma que parse() monta do texto do elemento
+            for item in &items {
+                assert_eq!(item.value, parse(item.text).ok(), "{}", item.text);
+            }
+        }
+
+        assert!(parse_array_items(r#"{"message": "Not Found"}"#).is_err());
+        assert!(parse_array_items("[1, 2").is_err());
+        assert!(parse_array_items(r#"[{"a": "open"#).is_err());
+        assert!(parse_array_items("[1] x").is_err());
+    }
+
+    #[test]
+    fn test_split_object() {
+        let text = r#" { "meta": {"a": "} ,"}, "events" : [ {"x": 1}, 2 ] , "n": null } "#;
+        assert_eq!(
+            split_object(text).unwrap(),
+            vec![("meta", r#"{"a": "} ,"}"#), ("events", r#"[ {"x": 1}, 2 ]"#), ("n", "null")]
+        );
+        assert!(split_object("{}").unwrap().is_empty());
+
+        // O valor não é interpretado: um evento mal formado não impede a divisão
+        assert_eq!(
+            split_object(r#"{"events": [{"x": 1,}]}"#).unwrap(),
+            vec![("events", r#"[{"x": 1,}]"#)]
+        );
+    }
+
+    #[test]
+    fn test_split_object_errors() {
+        assert!(split_object("[1]").is_err());
+        assert!(split_object(r#"{"a" 1}"#).is_err());
+        assert!(split_object(r#"{"a": [1, 2"#).is_err());
+        assert!(split_object(r#"{"a": 1} x"#).is_err());
+    }
+
+    #[test]
+    fn test_strings_with_multibyte_characters() {
+        let value = parse(r#"{"é": "ü 🚀 \u00e9"}"#).unwrap();
+        match value.get("é") {
+            Some(JsonValue::String(raw)) => assert_eq!(decode_string(raw).unwrap(), "ü 🚀 é"),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_pretty_matches_golden_files() {
+        assert_eq!(
+            pretty(include_str!("../tests/fixtures/push_events.json")).unwrap() + "\n",
+            include_str!("../tests/fixtures/pretty/push_events.json")
+        );
+        assert_eq!(
+            pretty(include_str!("../tests/fixtures/starred.json")).unwrap() + "\n",
+            include_str!("../tests/fixtures/pretty/starred.json")
+        );
+    }
+
+    #[test]
+    fn test_pretty_layout() {
+        assert_eq!(pretty(" 42 ").unwrap(), "42");
+        assert_eq!(pretty("[]").unwrap(), "[]");
+        assert_eq!(pretty("{ }").unwrap(), "{}");
+        assert_eq!(
+            pretty(r#"{"a":[1,{"b":null}],"c":{},"d":[ ]}"#).unwrap(),
+            "{\n  \"a\": [\n    1,\n    {\n      \"b\": null\n    }\n  ],\n  \"c\": {},\n  \"d\": []\n}"
+        );
+    }
+
+    #[test]
+    fn test_pretty_keeps_strings_as_written() {
+        let text = r#"{"msg":"line\nbreak \"quoted\" C:\\ \u00e9 \ud83d\ude80 é","k\"ey":"\/"}"#;
+        let formatted = pretty(text).unwrap();
+        assert_eq!(
+            formatted,
+            "{\n  \"msg\": \"line\\nbreak \\\"quoted\\\" C:\\\\ \\u00e9 \\ud83d\\ude80 é\",\n  \"k\\\"ey\": \"\\/\"\n}"
+        );
+        // Os mesmos dados: o texto formatado é lido igual ao original
+        assert_eq!(parse(&formatted).unwrap(), parse(text).unwrap());
+    }
+
+    #[test]
+    fn test_pretty_has_no_depth_limit() {
+        // Bem além de MAX_DEPTH (a indentação cresce com o quadrado da profundidade,
+        // então o teste não vai muito mais longe)
+        let depth = 3_000;
+        let text = format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        let formatted = pretty(&text).unwrap();
+        assert_eq!(formatted.lines().count(), 2 * depth - 1);
+        assert!(parse(&text).is_err());
+    }
+
+    #[test]
+    fn test_pretty_rejects_malformed_json() {
+        for text in ["", "[1,]", "[1 2]", r#"{"a" 1}"#, r#"{"a":1,}"#, "{1:2}", "[", r#"{"a":"#, "[\"\\x\"]", "nullish", "[] []"] {
+            assert!(pretty(text).is_err(), "{:?}", text);
+        }
+        let err = pretty("[1,\n 2 3]").unwrap_err();
+        assert!(err.to_string().contains("Expected ',' or ']' in array at byte 7"), "{}", err);
+    }
+
+    #[test]
+    fn test_json_escapes_strings() {
+        let text = "a\"b\\c\nd\r\t\u{8}\u{c}\u{1}\u{1f}é";
+        assert_eq!(Json::from(text).to_string(), r#""a\"b\\c\nd\r\t\b\f\u0001\u001fé""#);
+        // O caminho de volta: o parser lê exatamente o texto original
+        let written = Json::from(text).to_string();
+        match parse(&written).unwrap() {
+            JsonValue::String(raw) => assert_eq!(decode_string(raw).unwrap(), text),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_json_keeps_characters_outside_the_bmp() {
+        let text = "🚀 deploy 👩‍💻 𝄞";
+        let written = Json::object([(text, text.into())]).to_string();
+        assert_eq!(written, format!("{{\"{0}\":\"{0}\"}}", text));
+        match parse(&written).unwrap().get(text) {
+            Some(JsonValue::String(raw)) => assert_eq!(decode_string(raw).unwrap(), text),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_json_values() {
+        let value = Json::object([
+            ("null", Json::Null),
+            ("flag", true.into()),
+            ("count", 3usize.into()),
+            ("negative", (-2i64).into()),
+            ("missing", Option::<&str>::None.into()),
+            ("present", Some("x").into()),
+            ("average", Json::decimal(0.0912, 2)),
+            ("nan", Json::decimal(f64::NAN, 2)),
+            ("list", Json::array([1u64, 2])),
+            ("empty", Json::array(Vec::<u64>::new())),
+        ]);
+        assert_eq!(
+            value.to_string(),
+            r#"{"null":null,"flag":true,"count":3,"negative":-2,"missing":null,"present":"x","average":0.09,"nan":null,"list":[1,2],"empty":[]}"#
+        );
+    }
+
+    #[test]
+    fn test_json_alternate_format_is_indented() {
+        let mut value = Json::object([("a", Json::array([1u64])), ("b", Json::object([]))]);
+        value.push("c", "\n".into());
+        assert_eq!(format!("{:#}", value), "{\n  \"a\": [\n    1\n  ],\n  \"b\": {},\n  \"c\": \"\\n\"\n}");
+    }
+}
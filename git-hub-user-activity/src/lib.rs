@@ -0,0 +1,68 @@
+//! Busca e descreve a atividade pública recente de usuários e repositórios do GitHub
+//!
+//! Esta biblioteca é o "miolo" do programa `git-hub-user-activity`: o binário
+//! (src/main.rs) só interpreta a linha de comando e chama as funções daqui.
+//! Qualquer outro programa pode fazer o mesmo:
+//!
+//! ```no_run
+//! use github_activity::{fetch_user_events, format_event};
+//!
+//! let outcome = fetch_user_events("torvalds")?;
+//! for event in &outcome.events {
+//!     println!("{}", format_event(event));
+//! }
+//! # Ok::<(), github_activity::ActivityError>(())
+//! ```
+//!
+//! Para interpretar um JSON obtido de outra forma (cache, arquivo, testes),
+//! use [`parse_events`] diretamente.
+
+// CONCEITO: Lints no nível do crate
+// #![...] (com !) vale para o crate inteiro
+// deny(missing_docs) transforma em ERRO qualquer item público sem comentário de documentação (///)
+#![deny(missing_docs)]
+
+// CONCEITO: pub mod
+// Na biblioteca, 'pub mod' decide o que os usuários do crate enxergam
+// json fica privado: é um detalhe de implementação do parser
+pub mod api;
+// CONCEITO: #[cfg(feature = "...")]
+// O módulo só é compilado com `--features async` (ver [features] no Cargo.toml)
+#[cfg(feature = "async")]
+pub mod async_api;
+pub mod cache;
+pub mod config;
+pub mod credentials;
+pub mod date;
+pub mod display;
+pub mod doctor;
+pub mod error;
+pub mod generator;
+// Só com `--features graphql`
+#[cfg(feature = "graphql")]
+pub mod graphql;
+pub mod history;
+pub mod i18n;
+mod json;
+pub mod logging;
+pub mod models;
+pub mod output;
+pub mod parser;
+pub mod recording;
+pub mod seen;
+pub mod stats;
+pub mod webhook;
+// Só com `--features sqlite`, que traz o rusqlite
+#[cfg(feature = "sqlite")]
+pub mod storage;
+
+// CONCEITO: Re-exports (pub use)
+// Os itens mais usados ficam disponíveis direto na raiz:
+// github_activity::parse_events em vez de github_activity::parser::parse_events
+pub use api::{fetch_repo_events, fetch_user_events, fetch_user_profile};
+#[cfg(feature = "async")]
+pub use async_api::fetch_user_events_async;
+pub use display::format_event;
+pub use error::ActivityError;
+pub use models::{EventCategory, EventKind, EventPayload, GitHubEvent, Repository, UserProfile};
+pub use parser::{parse_events, ParseOutcome};
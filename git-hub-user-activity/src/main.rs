@@ -1,21 +1,50 @@
 // Este é o ponto de entrada (entry point) da aplicação
 // A função main() é onde o programa começa a executar
 
+// CONCEITO: Biblioteca + binário no mesmo pacote
+// A lógica (buscar, interpretar e formatar eventos) mora na biblioteca, em src/lib.rs,
+// para que outros programas possam usá-la. Este binário é só uma "casca" em volta dela
+// e acessa a biblioteca pelo nome do crate, como qualquer dependência
+//
 // CONCEITO: Declaração de Módulos
-// 'mod' declara que estes módulos fazem parte do nosso crate (projeto)
-// O Rust procura por arquivos com esses nomes em src/
-mod api;      // Lê src/api.rs
-mod display;  // Lê src/display.rs
-mod error;    // Lê src/error.rs
-mod models;   // Lê src/models.rs
-mod parser;   // Lê src/parser.rs
+// 'mod' declara um módulo que faz parte DESTE crate (o binário)
+// A linha de comando só interessa ao executável, então cli fica aqui e não na biblioteca
+mod cli;         // Lê src/cli.rs
+mod pager;       // Lê src/pager.rs
+mod browser;     // Lê src/browser.rs
+mod completions; // Lê src/completions.rs
+mod discovery;   // Lê src/discovery.rs
+mod input;       // Lê src/input.rs
+mod interrupt;   // Lê src/interrupt.rs
+mod notify;      // Lê src/notify.rs
+mod progress;    // Lê src/progress.rs
+
+use github_activity::{
+    api, cache, config, credentials, date, display, doctor, error, generator, history, i18n, logging, models, output,
+    parser, recording, seen, stats, webhook,
+};
+#[cfg(feature = "async")]
+use github_activity::async_api;
+#[cfg(feature = "sqlite")]
+use github_activity::storage;
 
 // CONCEITO: use
 // Importa itens específicos para uso neste arquivo
 // Sem 'use', teríamos que escrever std::env::args() toda vez
+use std::collections::HashSet;
 use std::env;      // Para acessar argumentos da linha de comando
+use std::io::{IsTerminal, Read, Write};  // Traz read_to_string e writeln! para stdin/stdout
 use std::process;  // Para controlar o processo (exit codes)
 
+// Quantos usernames são buscados ao mesmo tempo quando --jobs não é informado
+// (poucos: cada um ainda gasta do mesmo limite de requisições)
+const DEFAULT_JOBS: usize = 4;
+
+// Sem --since, --backend graphql busca as contribuições do último ano
+// (a API GraphQL não aceita uma janela maior que essa)
+#[cfg(feature = "graphql")]
+const GRAPHQL_DEFAULT_DAYS: i64 = 365;
+
 // CONCEITO: fn main()
 // O ponto de entrada de todo programa Rust
 // Não retorna valor explicitamente (tipo de retorno é () - "unit")
@@ -26,43 +55,125 @@ fn main() {
     // args[0] é sempre o nome do executável
     // args[1..] são os argumentos passados pelo usuário
     let args: Vec<String> = env::args().collect();
+    let program = args.first().map(String::as_str).unwrap_or("github-activity");
+
+    // O parsing dos argumentos fica no módulo cli
+    // args[1..] pula o nome do executável
+    let command = match cli::parse_args(args.get(1..).unwrap_or(&[])) {
+        Ok(command) => command,
+        Err(e) => {
+            // Sem argumentos válidos, não há --lang: vale o idioma do ambiente
+            set_language(None);
+            // CONCEITO: eprintln!
+            // Similar ao println!, mas imprime em stderr (erro padrão)
+            // É uma convenção imprimir mensagens de erro em stderr
+            eprintln!("{}: {}\n", i18n::messages().error_label(), e);
+            eprint!("{}", cli::usage(program));
+
+            // CONCEITO: process::exit()
+            // Termina o programa com um código de saída
+            // 0 = sucesso, não-zero = erro
+            // Códigos de erro ajudam em scripts shell (ver error::EXIT_CODES)
+            process::exit(e.exit_code());
+        }
+    };
+
+    // CONCEITO: Match em enum
+    // Cada variante de Command é um "modo" do programa
+    let options = match command {
+        cli::Command::Help => {
+            print!("{}", cli::usage(program));
+            return;
+        }
+        cli::Command::Generate(options) => {
+            // Subcomando `generate`: imprime dados sintéticos e termina
+            print!("{}", generator::generate_events_json(&options));
+            return;
+        }
+        cli::Command::Completions(shell) => {
+            // O script completa o nome com que o programa foi chamado, sem o diretório
+            let name = std::path::Path::new(program).file_name().and_then(|name| name.to_str()).unwrap_or(program);
+            print!("{}", completions::script(shell, name));
+            return;
+        }
+        cli::Command::Activity(options) => options,
+    };
+
+    // O idioma é escolhido antes de tudo, para valer também nos erros de configuração
+    set_language(options.lang);
+
+    // -v / -vv: as mensagens de diagnóstico da biblioteca vão para stderr
+    // (sem isso a biblioteca não imprime nada; ver o módulo logging)
+    // Vem antes de resolver as opções para que a descoberta do username apareça no log
+    if options.verbose {
+        let level = if options.debug { logging::Level::Debug } else { logging::Level::Info };
+        logging::set_logger(level, |level, message| eprintln!("{}: {}", level, message));
+    }
 
-    // Validação: esperamos exatamente 1 argumento (além do nome do programa)
-    if args.len() != 2 {
-        // CONCEITO: eprintln!
-        // Similar ao println!, mas imprime em stderr (erro padrão)
-        // É uma convenção imprimir mensagens de erro em stderr
-        eprintln!("Usage: {} <username>", args[0]);
-        eprintln!("\nExamples:");
-        eprintln!("  {} torvalds", args[0]);
-        eprintln!("  {} github", args[0]);
-
-        // CONCEITO: process::exit()
-        // Termina o programa com um código de saída
-        // 0 = sucesso, não-zero = erro
-        // Códigos de erro ajudam em scripts shell
-        process::exit(1);
-    }
-
-    // CONCEITO: Indexação e Referências
-    // &args[1] cria uma referência ao segundo elemento
-    // Em Rust, indexar um Vec pode causar panic se o índice não existir
-    // Mas já validamos que args.len() == 2, então é seguro
-    let username = &args[1];
+    // Guardado antes de resolver: com --json até um erro de configuração sai como JSON
+    let json = options.json;
+
+    // Completa as opções com o ambiente e o arquivo de configuração
+    // (a linha de comando vence; ver o módulo config)
+    // Sem username, tenta descobrir um; só então falta o alvo (e o uso é impresso)
+    let options = load_config()
+        .and_then(|(environment, file)| options.resolve(environment, file))
+        .map(with_gh_token)
+        .map(with_discovered_user)
+        .and_then(cli::Options::check_target);
+    let options = match options {
+        Ok(options) => options,
+        Err(e) if json => {
+            println!("{}", display::format_error_json(&e));
+            process::exit(e.exit_code());
+        }
+        Err(e) => {
+            eprintln!("{}: {}\n", i18n::messages().error_label(), e);
+            // O uso só ajuda quando o problema está nos argumentos
+            if matches!(e, error::ActivityError::InvalidArgument(_)) {
+                eprint!("{}", cli::usage(program));
+            }
+            process::exit(e.exit_code());
+        }
+    };
+
+    // Com --api-url (ou GITHUB_API_URL) de um GitHub Enterprise, os links dos eventos
+    // (--hyperlinks, --open, webhooks) apontam para as páginas dele
+    if let Some(api_url) = &options.api_url {
+        models::set_web_base(api::web_base_url(api_url));
+    }
 
     // CONCEITO: Match com Result
     // run() retorna Result<(), error::ActivityError>
     // Devemos lidar com Ok e Err explicitamente
-    match run(username) {
+    match run(&options) {
         // Se sucesso, não fazemos nada
         // Ok(()) significa "sucesso sem valor de retorno"
         Ok(()) => {}
 
-        // Se erro, imprimimos a mensagem e saímos com código 1
+        // Se erro, imprimimos a mensagem e saímos com o código da categoria do erro
         // 'e' tem tipo ActivityError, que implementa Display
+        // Com --format ndjson quem lê a saída é um programa: o erro também vira JSON
+        // Com --json o erro ocupa o lugar do documento, em stdout; a exceção é o
+        // --doctor reprovado, cujo relatório já foi impresso
+        Err(e) if options.json && !matches!(e, error::ActivityError::ChecksFailed { .. }) => {
+            println!("{}", display::format_error_json(&e));
+            process::exit(e.exit_code());
+        }
         Err(e) => {
-            eprintln!("\nError: {}", e);
-            process::exit(1);
+            match options.format {
+                display::OutputFormat::Ndjson => eprintln!("{}", display::format_error_json(&e)),
+                // Ctrl-C não é uma falha: o aviso só diz que a listagem está incompleta
+                display::OutputFormat::Text | display::OutputFormat::Table | display::OutputFormat::Csv
+                    if matches!(e, error::ActivityError::Interrupted { .. }) =>
+                {
+                    eprintln!("\n{}", e)
+                }
+                display::OutputFormat::Text | display::OutputFormat::Table | display::OutputFormat::Csv => {
+                    eprintln!("\n{}: {}", i18n::messages().error_label(), e)
+                }
+            }
+            process::exit(e.exit_code());
         }
     }
 }
@@ -72,40 +183,839 @@ fn main() {
 // main() lida com argumentos e exit codes
 // run() contém a lógica de negócio e pode retornar Result
 //
-// &str é uma "string slice" - referência imutável a uma string
+// &cli::Options é uma referência às opções já interpretadas da linha de comando
 // Result<(), error::ActivityError> significa:
 //   - Ok(()) em caso de sucesso (sem valor)
 //   - Err(error::ActivityError) em caso de erro
-fn run(username: &str) -> Result<(), error::ActivityError> {
-    // Mensagem informativa
-    println!("Fetching recent activity for '{}'...", username);
+fn run(options: &cli::Options) -> Result<(), error::ActivityError> {
+    // --reset-seen só mexe no arquivo de estado; nada é buscado
+    if options.reset_seen {
+        for target in options.targets() {
+            reset_seen(&target.to_string());
+        }
+        return Ok(());
+    }
+
+    // --doctor só verifica o ambiente; nada é buscado
+    if options.doctor {
+        return run_doctor(options);
+    }
+
+    // --cache-info e --clear-cache só mexem no diretório de cache
+    if options.cache_info {
+        return run_cache_info();
+    }
+    if options.clear_cache {
+        let usernames: Vec<String> = options.targets().iter().map(|target| target.to_string()).collect();
+        return run_clear_cache(&usernames);
+    }
+
+    // O cliente guarda tudo que as requisições precisam (endereço, token, paginação...)
+    let client = github_client(options);
+
+    let result = match &options.output {
+        // --output: a saída inteira é montada na memória e só no fim vai para o arquivo,
+        // que aparece completo ou não aparece (ver output::AtomicFile)
+        // O temporário é criado ANTES da busca: um diretório sem permissão falha sem gastar requisições
+        Some(path) => {
+            let mut file = output::AtomicFile::create(path)?;
+            let mut rendered = Vec::new();
+            run_with_output(options, &client, &mut rendered)?;
+
+            file.write_all(&rendered).map_err(|e| error::ActivityError::io(path, e))?;
+            file.commit()
+        }
+        // --pager só faz sentido em um terminal; em um pipe ou arquivo a saída passa direto
+        // Interrompida com Ctrl-C, a listagem parcial também é paginada (com --output, não:
+        // o arquivo só é escrito quando a execução dá certo, e o temporário é apagado)
+        None if options.pager && std::io::stdout().is_terminal() => {
+            let mut rendered = Vec::new();
+            let result = run_with_output(options, &client, &mut rendered);
+            if matches!(result, Ok(()) | Err(error::ActivityError::Interrupted { .. })) {
+                let style = display::Style::from_environment(options.no_color);
+                pager::show(&String::from_utf8_lossy(&rendered), style)?;
+            }
+            result
+        }
+        None => {
+            let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+            run_with_output(options, &client, &mut out)?;
+
+            // CONCEITO: flush explícito
+            // O BufWriter também descarrega ao sair de escopo, mas aí um erro de escrita se perderia
+            out.flush()?;
+            Ok(())
+        }
+    };
+
+    // O rodapé só aparece depois de uma execução bem-sucedida, com o limite da última resposta
+    if result.is_ok() && show_rate_limit(options) {
+        rate_limit_footer(options, &client);
+    }
+    result
+}
+
+// As verificações de --doctor, em texto (PASS/WARN/FAIL) ou JSON, sempre no stdout
+// Uma falha vira erro depois do relatório: o código de saída diz que algo está errado
+fn run_doctor(options: &cli::Options) -> Result<(), error::ActivityError> {
+    let client = github_client(options);
+    // O arquivo de estado de --new fica no diretório de cache
+    let cache_dir = seen::state_path().and_then(|path| path.parent().map(|dir| dir.to_path_buf()));
+    let environment = doctor::Environment {
+        token_source: options.token_source.as_ref(),
+        cache_dir: cache_dir.as_deref(),
+        utc_offset: local_utc_offset(),
+    };
+
+    let results = doctor::run_checks(&client, &environment);
+    let mut out = std::io::stdout().lock();
+    if options.json {
+        writeln!(out, "{}", doctor::format_json(&results))?;
+    } else {
+        write!(out, "{}", doctor::render(&results))?;
+    }
+
+    if doctor::any_failed(&results) {
+        let failed = results.iter().filter(|result| result.status == doctor::Status::Fail).count();
+        return Err(error::ActivityError::ChecksFailed { failed, total: results.len() });
+    }
+    Ok(())
+}
+
+// O cache de respostas no lugar padrão (ver o módulo cache)
+fn response_cache() -> Result<cache::Cache, error::ActivityError> {
+    cache::Cache::default_location().ok_or_else(|| {
+        error::ActivityError::InvalidArgument(
+            "unknown cache directory: set XDG_CACHE_HOME or HOME to an absolute path".to_string(),
+        )
+    })
+}
+
+// --cache-info: o diretório, o total e uma linha por usuário, no stdout
+//
+// Cache directory: /home/ana/.cache/github-activity/responses
+// 2 cached users, 14.2 KB
+//   octocat      3.1 KB  5 minutes ago
+//   torvalds    11.1 KB  2 days ago
+fn run_cache_info() -> Result<(), error::ActivityError> {
+    let cache = response_cache()?;
+    let info = cache.info()?;
+    let now = date::Timestamp::now();
+
+    let mut out = std::io::stdout().lock();
+    writeln!(out, "Cache directory: {}", cache.dir().display())?;
+    let plural = if info.entries.len() == 1 { "" } else { "s" };
+    writeln!(out, "{} cached user{}, {}", info.entries.len(), plural, cache::format_size(info.total_size()))?;
+
+    let width = info.entries.iter().map(|entry| entry.username.len()).max().unwrap_or(0);
+    for entry in &info.entries {
+        let age = match entry.modified {
+            Some(modified) => display::humanize_duration(now.epoch_seconds() - modified.epoch_seconds()),
+            None => "unknown age".to_string(),
+        };
+        writeln!(out, "  {:<width$}  {:>9}  {}", entry.username, cache::format_size(entry.size), age, width = width)?;
+    }
+    for (path, error) in &info.problems {
+        eprintln!("warning: could not read {}: {}", path.display(), error);
+    }
+    Ok(())
+}
+
+// --clear-cache: apaga as entradas (todas, sem usernames) e diz o que saiu
+// Um arquivo que não pôde ser apagado não impede os outros, mas a execução termina com erro
+fn run_clear_cache(usernames: &[String]) -> Result<(), error::ActivityError> {
+    let cache = response_cache()?;
+    let mut report = cache.clear(usernames)?;
+
+    for entry in &report.removed {
+        println!("Removed the cached events of '{}' ({})", entry.username, cache::format_size(entry.size));
+    }
+    for username in &report.missing {
+        println!("No cached events for '{}'", username);
+    }
+    if usernames.is_empty() && report.removed.is_empty() && report.failed.is_empty() {
+        println!("The cache is already empty ({})", cache.dir().display());
+    }
+
+    // Os outros arquivos viram avisos; o primeiro é o erro (e o código de saída)
+    if report.failed.is_empty() {
+        return Ok(());
+    }
+    let (path, source) = report.failed.remove(0);
+    for (path, error) in &report.failed {
+        eprintln!("warning: could not delete {}: {}", path.display(), error);
+    }
+    Err(error::ActivityError::io(path.display().to_string(), source))
+}
+
+// "API rate limit: 54/60 remaining, resets at 14:32": em stderr, como as mensagens de
+// progresso, e pelas mesmas regras (--quiet e as saídas para máquinas o desligam)
+// --from-file e --history não fazem requisições: não há o que mostrar
+fn show_rate_limit(options: &cli::Options) -> bool {
+    options.verbosity().progress
+        && options.format != display::OutputFormat::Ndjson
+        && options.from_file.is_none()
+        && !options.history
+}
+
+fn rate_limit_footer(options: &cli::Options, client: &api::GitHubClient) {
+    let offset = if options.utc { 0 } else { local_utc_offset() };
+    let footer = display::format_rate_limit(client.rate_limit().as_ref(), offset, i18n::messages());
+
+    // Esmaecido, se o stderr for um terminal (as cores da listagem olham o stdout)
+    let no_color_env = env::var("NO_COLOR").ok();
+    let style = display::Style::detect(options.no_color, no_color_env.as_deref(), std::io::stderr().is_terminal());
+    eprintln!("{}", style.paint(&footer, display::Color::Dim));
+}
+
+// --raw: o corpo da resposta (ou o arquivo de --from-file) indentado, sem passar pelos eventos
+// Um corpo que não é JSON válido é um erro de parsing, com a posição e o trecho em volta
+fn run_raw(
+    options: &cli::Options,
+    client: &api::GitHubClient,
+    out: &mut impl Write,
+) -> Result<(), error::ActivityError> {
+    let body = match (&options.from_file, &options.target) {
+        (Some(path), _) => read_input(path)?,
+        (None, Some(target)) => {
+            log_token_source(options);
+            client.fetch_raw(target)?
+        }
+        // check_target já exige um alvo sem --from-file
+        (None, None) => return Ok(()),
+    };
+
+    writeln!(out, "{}", display::format_raw_json(&body)?)?;
+    Ok(())
+}
+
+// O resto de run(), já com o cliente e a saída prontos
+fn run_with_output(
+    options: &cli::Options,
+    client: &api::GitHubClient,
+    out: &mut impl Write,
+) -> Result<(), error::ActivityError> {
+    if options.raw {
+        return run_raw(options, client, out);
+    }
+    if options.starred || options.list_repos {
+        return run_repositories(options, client, out);
+    }
+
+    if !options.more_targets.is_empty() {
+        return run_many(options, client, out);
+    }
+
+    // Options::resolve garante um username (e não um repositório) junto com --compare
+    if let (Some(other), Some(cli::Target::User(username))) = (&options.compare, &options.target) {
+        return run_compare(options, client, out, username, other);
+    }
+
+    if options.format == display::OutputFormat::Ndjson {
+        return run_ndjson(options, client, out);
+    }
+
+    // mut: uma gravação pode informar o alvo quando a linha de comando não informou
+    let mut target = options.target.clone();
 
-    // CONCEITO: Chamada de função entre módulos
-    // api::fetch_user_events está em src/api.rs
+    // Nome da origem no modo --from-file, para cabeçalhos e mensagens
+    let source_name = options.from_file.as_deref().map(input_name).unwrap_or_default();
+
+    // Mensagem informativa (em stderr: o stdout fica só com os dados)
+    match (&options.from_file, &target) {
+        (Some(_), _) => status(options, &format!("Reading events from '{}'...", source_name)),
+        (None, Some(target)) if options.history => {
+            status(options, &format!("Reading the local history of '{}'...", target))
+        }
+        (None, Some(target)) if options.backend == cli::Backend::GraphQl => {
+            status(options, &format!("Fetching the contributions of '{}' from the GraphQL API...", target))
+        }
+        (None, Some(target)) => status(options, &format!("Fetching recent activity for '{}'...", target)),
+        (None, None) => {}
+    }
+
+    // CONCEITO: Chamada de método
+    // client.fetch_target_pages está em src/api.rs (impl GitHubClient)
     // O operador ? propaga erros:
     //   - Se Ok(events), desempacota e continua
     //   - Se Err(e), retorna Err(e) imediatamente
-    let events = api::fetch_user_events(username)?;
+    //
+    // --from-file/--replay pula a API: o conteúdo vai direto para o mesmo parser
+    let outcome = match &options.from_file {
+        Some(path) => {
+            let (info, outcome) = recording::parse_recording(&read_input(path)?)?;
+            if target.is_none() {
+                target = info
+                    .filter(|info| !info.target.is_empty())
+                    .map(|info| cli::Target::parse(&info.target));
+            }
+            outcome
+        }
+        None if options.history => read_history(options, target.as_ref())?,
+        // Sem a feature "graphql", cli.rs já recusou --backend graphql
+        #[cfg(feature = "graphql")]
+        None if options.backend == cli::Backend::GraphQl => {
+            log_token_source(options);
+            fetch_contributions(options, client, target.as_ref())?
+        }
+        None => {
+            log_token_source(options);
+
+            // Um Ctrl-C entre as páginas encerra a busca com o que já chegou (ver o módulo interrupt)
+            let (fetched, interrupted) = match &target {
+                Some(target) => {
+                    let _catch = interrupt::Catch::install();
+                    let mut progress: Box<dyn progress::Progress> = if show_progress(options) {
+                        Box::new(progress::Line::new(std::io::stderr()))
+                    } else {
+                        Box::new(progress::Hidden)
+                    };
+                    let fetched = client.event_pages(target, options.all)?.collect_while(|pages| {
+                        // A primeira página não aparece: antes dela não há total, e muitas buscas param nela
+                        if pages.pages_fetched() > 0 {
+                            progress.update(pages.pages_fetched() + 1, pages.total_pages(), pages.events_fetched());
+                        }
+                        !interrupt::interrupted()
+                    });
+                    // Apagada antes de qualquer saída, inclusive a de erro
+                    progress.clear();
+                    (fetched?, interrupt::interrupted())
+                }
+                // Options::resolve só deixa o alvo vazio junto com --from-file
+                None => unreachable!("a target is required without --from-file"),
+            };
+
+            // Um login renomeado aparece com o nome novo (ver follow_rename)
+            target = target.map(|target| follow_rename(options, client, target));
+
+            if interrupted {
+                // Uma gravação pela metade pareceria o histórico inteiro no --replay: nada é gravado
+                let shown = fetched.outcome.events.len();
+                if shown > 0 {
+                    show(options, client, out, &target, source_name, fetched.outcome)?;
+                }
+                return Err(error::ActivityError::Interrupted { shown });
+            }
+
+            // --record grava ANTES de exibir: mesmo que algo falhe depois, a resposta fica salva
+            if let (Some(path), Some(target)) = (&options.record, &target) {
+                write_recording(path, target, &fetched.bodies)?;
+            }
+            if let (true, Some(target)) = (options.log_history, &target) {
+                log_history(options, target, &fetched.bodies)?;
+            }
+            // Sem a feature "sqlite", cli.rs já recusou --export-sqlite
+            #[cfg(feature = "sqlite")]
+            if let (Some(path), Some(target)) = (&options.export_sqlite, &target) {
+                export_sqlite(options, path, target, &fetched.outcome.events, &fetched.bodies)?;
+            }
+            fetched.outcome
+        }
+    };
+
+    show(options, client, out, &target, source_name, outcome)
+}
+
+// --starred e --list-repos: uma lista de repositórios do usuário, no lugar dos eventos
+// (as estrelas, uma por linha; os repositórios dele, numa tabela)
+fn run_repositories(
+    options: &cli::Options,
+    client: &api::GitHubClient,
+    out: &mut impl Write,
+) -> Result<(), error::ActivityError> {
+    let username = match &options.target {
+        Some(cli::Target::User(username)) => username,
+        _ => unreachable!("cli.rs only accepts --starred and --list-repos with a single <username>"),
+    };
+
+    log_token_source(options);
+    let mut repositories = if options.starred {
+        status(options, &format!("Fetching the repositories '{}' starred...", username));
+        client.fetch_starred(username, options.all)?
+    } else {
+        status(options, &format!("Fetching the public repositories of '{}'...", username));
+        client.fetch_repos(username, options.all)?
+    };
+
+    // Filtro, ordem e --limit do lado de cá: a API só ordena por data
+    if options.no_forks {
+        repositories.retain(|repository| !repository.fork);
+    }
+    if let Some(order) = options.repo_sort {
+        stats::sort_repositories(&mut repositories, order);
+    }
+    if let Some(limit) = options.limit {
+        repositories.truncate(limit);
+    }
+
+    let verbosity = options.verbosity();
+    if repositories.is_empty() {
+        match options.starred {
+            _ if !verbosity.notices => {}
+            true => display::display_no_starred(out, username)?,
+            false => display::display_no_repos(out, username)?,
+        }
+        return Ok(());
+    }
+
+    if verbosity.header {
+        if options.starred {
+            display::display_starred_header(out, username, repositories.len())?;
+        } else {
+            display::display_repos_header(out, username, repositories.len())?;
+        }
+    }
+    if options.starred {
+        for repository in &repositories {
+            writeln!(out, "{}", display::format_starred_repository(repository))?;
+        }
+    } else {
+        display::display_repositories(out, &repositories)?;
+    }
+    if verbosity.header {
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+// A linha "Fetching page 3/10 … 90 events" (ver o módulo progress): só com --all, que busca
+// várias páginas, e só num terminal; --verbose já conta cada requisição no log
+fn show_progress(options: &cli::Options) -> bool {
+    options.all && options.verbosity().progress && !options.verbose && std::io::stderr().is_terminal()
+}
+
+// Mensagens de progresso ("Fetching..."): sempre em stderr, para que o stdout
+// (ou o arquivo de --output) tenha só a saída de dados; --quiet as desliga
+fn status(options: &cli::Options, message: &str) {
+    if options.verbosity().progress {
+        eprintln!("{}", message);
+    }
+}
+
+// --format ndjson: um objeto JSON por evento e por linha, escrito (e enviado com flush)
+// assim que cada página é interpretada, sem esperar o histórico inteiro
+// Um erro no meio do caminho interrompe a saída, mas as linhas já escritas continuam válidas
+// (`out` é um BufWriter: junta as linhas de uma página em poucas escritas, e flush() as envia)
+fn run_ndjson(
+    options: &cli::Options,
+    client: &api::GitHubClient,
+    out: &mut impl Write,
+) -> Result<(), error::ActivityError> {
+    // --limit conta as linhas escritas: atingido o limite, nenhuma página a mais é buscada
+    let mut remaining = options.limit.unwrap_or(usize::MAX);
+    // As estrelas e forks já escritos valem para as páginas seguintes também
+    let mut deduplicator = (!options.no_dedup).then(stats::Deduplicator::new);
+    let filter = actor_filter(options);
+    let selection = event_filter(options);
+
+    // Um arquivo (ou o histórico) já tem todos os eventos: é uma "página" só
+    let file_outcome = match &options.from_file {
+        Some(path) => Some(recording::parse_recording(&read_input(path)?)?.1),
+        None if options.history => Some(read_history(options, options.target.as_ref())?),
+        None => None,
+    };
+    if let Some(outcome) = file_outcome {
+        report_warnings(options, outcome.warnings)?;
+        let (mut events, _) = stats::remove_actors(outcome.events, &filter);
+        events.retain(|event| selection.keeps(event));
+        return write_ndjson(out, &events, &mut remaining, &mut deduplicator);
+    }
+
+    log_token_source(options);
+    let mut pages = match &options.target {
+        Some(target) => client.event_pages(target, options.all)?,
+        None => unreachable!("a target is required without --from-file"),
+    };
+
+    // As linhas já escritas ficam: um Ctrl-C só impede as próximas páginas
+    let _catch = interrupt::Catch::install();
+    let limit = remaining;
+    while remaining > 0 {
+        if interrupt::interrupted() {
+            return Err(error::ActivityError::Interrupted { shown: limit - remaining });
+        }
+        let events = match pages.next() {
+            Some(page) => page?,
+            None => break,
+        };
+        report_warnings(options, pages.take_warnings())?;
+        let (mut events, _) = stats::remove_actors(events, &filter);
+        events.retain(|event| selection.keeps(event));
+        write_ndjson(out, &events, &mut remaining, &mut deduplicator)?;
+    }
+
+    Ok(())
+}
+
+// Escreve até `remaining` eventos, um por linha, e os envia na hora
+// (sem os duplicados, que não contam para o --limit; None = --no-dedup)
+fn write_ndjson(
+    out: &mut impl Write,
+    events: &[models::GitHubEvent],
+    remaining: &mut usize,
+    deduplicator: &mut Option<stats::Deduplicator>,
+) -> Result<(), error::ActivityError> {
+    for event in events {
+        if *remaining == 0 {
+            break;
+        }
+        if let Some(deduplicator) = deduplicator {
+            if deduplicator.is_duplicate(event) {
+                continue;
+            }
+        }
+        writeln!(out, "{}", display::format_event_json(event))?;
+        *remaining -= 1;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+// Vários usernames: busca todos ao mesmo tempo (até --jobs) e mostra cada um
+// na ordem digitada. A falha de um não impede que os outros sejam exibidos
+fn run_many(
+    options: &cli::Options,
+    client: &api::GitHubClient,
+    out: &mut impl Write,
+) -> Result<(), error::ActivityError> {
+    let targets: Vec<cli::Target> = options.targets().into_iter().cloned().collect();
+
+    let names: Vec<String> = targets.iter().map(|target| format!("'{}'", target)).collect();
+    status(options, &format!("Fetching recent activity for {}...", names.join(", ")));
+    log_token_source(options);
+
+    let mut failed = Vec::new();
+    let mut first_error = None;
+
+    for (target, result) in targets.iter().zip(fetch_many(options, client, &targets)) {
+        let shown = match result {
+            Some(Ok(fetched)) => {
+                show(options, client, out, &Some(follow_rename(options, client, target.clone())), "", fetched.outcome)
+            }
+            Some(Err(e)) => Err(e),
+            None => {
+                eprintln!("\nSkipped '{}': the API rate limit was reached while fetching the others", target);
+                failed.push(target.to_string());
+                continue;
+            }
+        };
+
+        // Cada alvo aparece inteiro antes do erro do próximo (que vai para stderr)
+        let shown = shown.and_then(|()| Ok(out.flush()?));
+        if let Err(e) = shown {
+            eprintln!("\nError for '{}': {}", target, e);
+            failed.push(target.to_string());
+            // CONCEITO: Option::get_or_insert
+            // Guarda o valor só se ainda não houver um: aqui, o PRIMEIRO erro
+            first_error.get_or_insert(e);
+        }
+    }
+
+    match first_error {
+        Some(first) => Err(error::ActivityError::SomeTargetsFailed { failed, first: Box::new(first) }),
+        None => Ok(()),
+    }
+}
+
+// --compare: busca os dois usuários ao mesmo tempo (como vários usernames, via fetch_many)
+// e mostra os resumos lado a lado
+// Diferente de run_many, a falha de um aborta tudo: meia comparação não diz nada
+fn run_compare(
+    options: &cli::Options,
+    client: &api::GitHubClient,
+    out: &mut impl Write,
+    username: &str,
+    other: &str,
+) -> Result<(), error::ActivityError> {
+    let targets = vec![cli::Target::User(username.to_string()), cli::Target::User(other.to_string())];
+
+    status(options, &format!("Fetching recent activity for '{}' and '{}'...", username, other));
+    log_token_source(options);
+
+    let mut summaries = Vec::with_capacity(targets.len());
+    for (target, result) in targets.iter().zip(fetch_many(options, client, &targets)) {
+        let failed = |source| error::ActivityError::CompareFailed {
+            user: target.to_string(),
+            source: Box::new(source),
+        };
+
+        let outcome = match result {
+            Some(Ok(fetched)) => fetched.outcome,
+            Some(Err(e)) => return Err(failed(e)),
+            // Pulado porque o outro estourou o limite de requisições:
+            // o erro dele também está na lista, e é ele que será devolvido
+            None => continue,
+        };
+
+        // As mesmas regras de show() para eventos que o parser pulou
+        report_warnings(options, outcome.warnings).map_err(failed)?;
+
+        // Os dias no mesmo fuso do --summary (local, ou UTC com --utc)
+        let offset = if options.utc { 0 } else { local_utc_offset() };
+        summaries.push(models::ActivitySummary::from_events_in(&outcome.events, offset));
+    }
+
+    // CONCEITO: Slice patterns
+    // [left, right] só casa com um Vec de exatamente dois elementos
+    let comparison = match <[_; 2]>::try_from(summaries) {
+        Ok([left, right]) => left.compare(right),
+        Err(_) => unreachable!("a skipped user implies an error for the other"),
+    };
+
+    display::display_comparison(out, (username, other), &comparison, display::terminal_width())?;
+    if options.verbosity().header {
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+// Exibe os eventos de um alvo (ou de um arquivo, com target None)
+// `source_name` é o nome do arquivo de --from-file, usado quando não há alvo
+fn show(
+    options: &cli::Options,
+    client: &api::GitHubClient,
+    out: &mut impl Write,
+    target: &Option<cli::Target>,
+    source_name: &str,
+    outcome: parser::ParseOutcome,
+) -> Result<(), error::ActivityError> {
+    report_warnings(options, outcome.warnings)?;
+
+    let verbosity = options.verbosity();
+    let mut events = outcome.events;
+
+    // Marcador de "já visto": só dados da API contam (um arquivo pode ser antigo)
+    // `since` é o marcador anterior quando --new escondeu o que ele já cobria
+    let since = match (&options.from_file, target) {
+        (None, Some(target)) => update_seen(&target.to_string(), &mut events, options.new),
+        _ => None,
+    };
 
     // CONCEITO: Vec::is_empty()
     // Verifica se o vetor tem zero elementos
-    if events.is_empty() {
-        display::display_no_events(username);
+    // (Com --json mesmo uma lista vazia vira uma saída: um resumo com total 0, ou [];
+    // com --count-only, um "0")
+    if events.is_empty() && !options.json && !options.count_only {
+        match (since, target) {
+            _ if !verbosity.notices => {}
+            (Some(since), Some(target)) => display::display_no_new_events(out, &target.to_string(), since.created_at)?,
+            (None, Some(cli::Target::User(username))) => display::display_no_events(out, username)?,
+            (None, Some(target)) => display::display_no_repo_events(out, &target.to_string())?,
+            (_, None) => display::display_no_file_events(out, source_name)?,
+        }
+        // Sem eventos, --open N não tem o que abrir: open_event explica o porquê
+        if let Some(number) = options.open {
+            browser::open_event(&events, number, &mut browser::SystemOpener::new())?;
+        }
         // return explícito não é necessário, mas deixa o código mais claro
         return Ok(());
     }
 
+    // CONCEITO: matches!
+    // Retorna true se o valor casa com o padrão
+    // Em um repositório cada evento pode ter um autor diferente,
+    // então mostramos o ator em cada linha
+    // (o mesmo vale para um arquivo sem username: não sabemos de quem são os eventos)
+    let mut display_options = display::DisplayOptions {
+        show_actor: matches!(target, Some(cli::Target::Repo { .. }) | None),
+        // --no-time desliga o "(2 hours ago)"
+        // CONCEITO: bool::then
+        // true.then(|| x) == Some(x); false.then(|| x) == None
+        now: (!options.no_time).then(date::Timestamp::now),
+        // Um arquivo (--output) não é um terminal: nada de códigos de cor nele
+        style: display::Style::from_environment(options.no_color || options.output.is_some()),
+        // Links também só no terminal; alguns terminais (TERM_PROGRAM) os ligam sem o --hyperlinks
+        hyperlinks: options.output.is_none() && display::hyperlinks_from_environment(options.hyperlinks),
+        emoji: options.emoji,
+        numbered: options.numbered,
+        show_commits: options.commits,
+        verbose: options.verbose,
+        template: options.template.clone(),
+        ..Default::default()
+    };
+
+    // Bots (--no-bots, --ignore-actor) e estrelas e forks repetidos saem primeiro,
+    // antes do resumo e do --limit; o cabeçalho conta quantos foram escondidos
+    // --type e --action vêm antes: o que não foi pedido não conta como escondido
+    let selection = event_filter(options);
+    events.retain(|event| selection.keeps(event));
+    let mut hidden = display::HiddenEvents::default();
+    let filter = actor_filter(options);
+    if !filter.is_empty() {
+        (events, hidden.bots) = stats::remove_actors(events, &filter);
+    }
+    if !options.no_dedup {
+        (events, hidden.duplicates) = stats::dedup_stars_and_forks(events);
+    }
+
+    // --notify: avisa do que é novo, depois dos mesmos filtros da listagem
+    // Na primeira execução (sem marcador anterior) tudo seria "novo": nada é avisado
+    if let (true, Some(_), Some(target)) = (options.notify, &since, target) {
+        notify_new_events(&target.to_string(), &events);
+    }
+
+    // --post-webhook: os mesmos eventos vão para o painel da equipe
+    if let Some(url) = &options.post_webhook {
+        let name = target.as_ref().map_or_else(|| source_name.to_string(), ToString::to_string);
+        if let Err(e) = post_webhook(options, client, url, &name, &events) {
+            // Com --new, o marcador volta ao anterior: a próxima execução envia estes eventos de novo
+            if let (true, None, Some(target)) = (options.new, &options.from_file, target) {
+                restore_seen(&target.to_string(), since);
+            }
+            return Err(e);
+        }
+    }
+
+    // Junta pushes consecutivos ANTES das outras análises,
+    // para que índices e contagens se refiram às linhas realmente exibidas
+    if options.collapse {
+        events = stats::collapse_pushes(events);
+    }
+
+    if options.first_contributions {
+        let firsts = stats::first_contributions(&events);
+
+        if options.only {
+            // CONCEITO: into_iter() + filter
+            // into_iter() consome o Vec (toma posse dos eventos), então não há cópias
+            events = events
+                .into_iter()
+                .enumerate()
+                .filter(|(index, _)| firsts.contains(index))
+                .map(|(_, event)| event)
+                .collect();
+        } else {
+            display_options.first_activity = firsts;
+        }
+    }
+
+    // --limit corta DEPOIS dos filtros: são N eventos exibidos, não N buscados
+    if let Some(limit) = options.limit {
+        events.truncate(limit);
+    }
+
+    // --sort só reordena o que será exibido (os N mais recentes, com --limit)
+    if let Some(order) = options.sort {
+        events = sort_events(events, order, &mut display_options.first_activity);
+    }
+
+    // --count-only: só o total (ou um total por grupo), depois de todos os filtros
+    if options.count_only {
+        display::display_counts(out, &events, options.group_by)?;
+        return Ok(());
+    }
+
+    // --summary troca a listagem pelas estatísticas agregadas
+    if options.summary {
+        // As sequências de dias seguem o mesmo fuso do histograma (local, ou UTC com --utc)
+        let offset = if options.utc { 0 } else { local_utc_offset() };
+        let summary = models::ActivitySummary::from_events_in(&events, offset);
+        let daily = stats::daily_activity(&events, offset, date::Timestamp::now());
+
+        if options.json {
+            // CONCEITO: writeln! em um io::Write
+            // Diferente de println!, devolve um Result em vez de entrar em panic
+            writeln!(out, "{}", display::format_summary_json(&summary, &daily))?;
+        } else {
+            let title = target.as_ref().map(ToString::to_string);
+            let title = title.as_deref().unwrap_or(source_name);
+            display::display_summary(out, title, &summary, &daily)?;
+            if verbosity.header {
+                writeln!(out)?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    // --repos-only: um resumo por repositório, calculado dos mesmos eventos
+    if options.repos_only {
+        let repos = stats::repo_activity(&events);
+
+        if options.json {
+            writeln!(out, "{}", display::format_repo_activity_json(&repos))?;
+        } else {
+            let title = target.as_ref().map(ToString::to_string);
+            display::display_repo_activity(out, title.as_deref().unwrap_or(source_name), &repos)?;
+            if verbosity.header {
+                writeln!(out)?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    // --histogram: eventos por dia, no fuso local (ou em UTC, com --utc)
+    if options.histogram {
+        let offset = if options.utc { 0 } else { local_utc_offset() };
+        let days = stats::events_per_day(&events, offset);
+        let name = target.as_ref().map(ToString::to_string);
+        let title = format!("'{}' ({})", name.as_deref().unwrap_or(source_name), date::format_utc_offset(offset));
+
+        display::display_histogram(out, &title, &days, display::terminal_width())?;
+        if verbosity.header {
+            writeln!(out)?;
+        }
+        return Ok(());
+    }
+
+    // --by-week: uma linha por semana ISO, da mais recente (ou da mais antiga, com --sort oldest)
+    if options.by_week {
+        let offset = if options.utc { 0 } else { local_utc_offset() };
+        let mut weeks = stats::events_per_week(&events, offset);
+        if options.sort == Some(stats::SortOrder::Oldest) {
+            weeks.reverse();
+        }
+
+        if options.json {
+            writeln!(out, "{}", display::format_weeks_json(&weeks))?;
+        } else if options.format == display::OutputFormat::Csv {
+            write!(out, "{}", display::format_weeks_csv(&weeks))?;
+        } else {
+            let name = target.as_ref().map(ToString::to_string);
+            let title = format!("'{}' ({})", name.as_deref().unwrap_or(source_name), date::format_utc_offset(offset));
+            display::display_weeks(out, &title, &weeks)?;
+            if verbosity.header {
+                writeln!(out)?;
+            }
+        }
+        return Ok(());
+    }
+
+    // Com --template (ou --quiet) a saída é só as linhas: sem cabeçalho nem linha em branco no fim
+    let decorated = verbosity.header;
+
     // Mostra cabeçalho com contagem de eventos
-    display::display_header(username, events.len());
+    match target {
+        _ if !decorated => {}
+        Some(cli::Target::User(username)) => {
+            let profile = fetch_profile(options, client, username);
+            let cached_age = client.cached_age(username);
+            display::display_header(out, username, profile.as_ref(), events.len(), hidden, cached_age)?
+        }
+        Some(target) => display::display_repo_header(out, &target.to_string(), events.len(), hidden)?,
+        None => display::display_file_header(out, source_name, events.len(), hidden)?,
+    }
 
     // CONCEITO: Passagem por Referência
     // &events empresta (borrow) o vetor para display_events
     // A função pode ler mas não modificar ou tomar posse
     // Após a chamada, ainda podemos usar 'events' aqui
-    display::display_events(&events);
+    match (options.format, options.group_by) {
+        (display::OutputFormat::Table, _) => display::display_table(out, &events, &display_options, options.wide)?,
+        (_, Some(group_by)) => display::display_events_grouped(out, &events, &display_options, group_by)?,
+        (_, None) => display::display_events(out, &events, &display_options)?,
+    }
 
     // Linha em branco para melhor formatação
-    println!();
+    if decorated {
+        writeln!(out)?;
+    }
+
+    // --open N: o número é o mesmo que --numbered mostraria na listagem acima
+    if let Some(number) = options.open {
+        browser::open_event(&events, number, &mut browser::SystemOpener::new())?;
+    }
 
     // CONCEITO: Return implícito
     // A última expressão de uma função é retornada automaticamente
@@ -113,16 +1023,555 @@ fn run(username: &str) -> Result<(), error::ActivityError> {
     Ok(())
 }
 
+// Coloca os eventos na ordem de --sort; `marked` (os índices marcados por
+// --first-contributions) passa a apontar para as novas posições
+fn sort_events(
+    events: Vec<models::GitHubEvent>,
+    order: stats::SortOrder,
+    marked: &mut HashSet<usize>,
+) -> Vec<models::GitHubEvent> {
+    let order = stats::sorted_order(&events, order);
+    *marked = order
+        .iter()
+        .enumerate()
+        .filter(|(_, index)| marked.contains(index))
+        .map(|(position, _)| position)
+        .collect();
+
+    // CONCEITO: Option::take
+    // Tira cada evento do seu lugar (deixando None) sem clonar nada
+    let mut slots: Vec<Option<models::GitHubEvent>> = events.into_iter().map(Some).collect();
+    order.into_iter().filter_map(|index| slots[index].take()).collect()
+}
+
+// Os tipos, categorias e actions pedidos com --type, --category e --action
+fn event_filter(options: &cli::Options) -> stats::EventFilter {
+    stats::EventFilter {
+        kinds: options.types.clone(),
+        categories: options.categories.clone(),
+        actions: options.actions.clone(),
+    }
+}
+
+// Os autores escondidos por --no-bots e --ignore-actor
+// (resolve já juntou a lista ignored_actors da configuração, quando --no-bots a pede)
+fn actor_filter(options: &cli::Options) -> stats::ActorFilter {
+    stats::ActorFilter { bots: options.no_bots, logins: options.ignored_actors.clone() }
+}
+
+// Eventos que o parser não conseguiu interpretar:
+// --strict transforma o primeiro deles em erro; sem ele, avisamos em stderr e seguimos
+fn report_warnings(options: &cli::Options, warnings: Vec<parser::ParseWarning>) -> Result<(), error::ActivityError> {
+    // CONCEITO: if let com into_iter().next()
+    // Pega o primeiro elemento (tomando posse dele) se a lista não estiver vazia
+    if options.strict {
+        if let Some(warning) = warnings.into_iter().next() {
+            return Err(warning.into_error());
+        }
+    } else {
+        display::display_parse_warnings(&mut std::io::stderr().lock(), &warnings, options.verbose)?;
+    }
+    Ok(())
+}
+
+// O perfil do usuário para o cabeçalho: custa uma requisição a mais do limite,
+// então --no-profile o desliga (e --from-file, que não usa a rede, nem tenta)
+// Uma falha aqui não derruba a listagem: o cabeçalho volta ao formato simples
+// e o motivo só aparece com --verbose
+fn fetch_profile(
+    options: &cli::Options,
+    client: &api::GitHubClient,
+    username: &str,
+) -> Option<models::UserProfile> {
+    if options.no_profile || options.from_file.is_some() || options.history {
+        return None;
+    }
+
+    match client.fetch_user_profile(username) {
+        Ok(profile) => Some(profile),
+        Err(e) => {
+            logging::log(logging::Level::Info, || format!("could not fetch the profile of '{}': {}", username, e));
+            None
+        }
+    }
+}
+
+// Fuso local em segundos (ex: -10800 para UTC-03:00), para --histogram
+// Sem uma crate de datas, perguntamos ao comando `date` (POSIX); se ele não existir
+// ou responder algo inesperado, os dias são contados em UTC
+// É o deslocamento de AGORA: dias antes de uma mudança de horário de verão ficam uma hora fora
+fn local_utc_offset() -> i64 {
+    process::Command::new("date")
+        .arg("+%z")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .and_then(|text| date::parse_utc_offset(text.trim()))
+        .unwrap_or(0)
+}
+
+// O cliente da API, a partir das opções já resolvidas
+// O que a linha de comando não informou fica com o padrão do builder
+fn github_client(options: &cli::Options) -> api::GitHubClient {
+    let mut builder = api::GitHubClient::builder()
+        .compression(!options.no_compression)
+        .wait_on_limit(options.wait_on_limit)
+        .skip_validation(options.skip_validation);
+    if let Some(api_url) = &options.api_url {
+        builder = builder.base_url(api_url.as_str());
+    }
+    if let Some(token) = &options.token {
+        builder = builder.token(token.as_str());
+    }
+    if let Some(api_version) = &options.api_version {
+        builder = builder.api_version(api_version.as_str());
+    }
+    if let Some(per_page) = options.per_page {
+        builder = builder.per_page(per_page);
+    }
+    if let Some(max_events) = options.max_events {
+        builder = builder.max_events(max_events);
+    }
+    // Sem um diretório de cache conhecido, as buscas só não são revalidadas
+    // --no-cache desliga tudo: a revalidação e o --max-age
+    if let (false, Some(cache)) = (options.no_cache, cache::Cache::default_location()) {
+        builder = builder.cache(cache);
+        if let Some(max_age) = options.max_age {
+            builder = builder.max_age(std::time::Duration::from_secs(max_age));
+        }
+    }
+    builder.build()
+}
+
+// Diz de onde veio a credencial (-v), nunca o token
+fn log_token_source(options: &cli::Options) {
+    logging::log(logging::Level::Info, || match &options.token_source {
+        Some(source) => format!("authenticating with the token from {}", source),
+        None => "no token found, sending unauthenticated requests".to_string(),
+    });
+}
+
+// Busca os alvos em threads (o padrão, sem a feature "async")
+// None = pulado: depois de estourar o limite de requisições, os próximos falhariam também
+#[cfg(not(feature = "async"))]
+fn fetch_many(
+    options: &cli::Options,
+    client: &api::GitHubClient,
+    targets: &[cli::Target],
+) -> Vec<Option<Result<api::FetchedPages, error::ActivityError>>> {
+    let jobs = options.jobs.unwrap_or(DEFAULT_JOBS);
+    client.fetch_parallel(targets, options.all, jobs)
+}
+
+// Com a feature "async": as mesmas regras, com tarefas do tokio no lugar de threads
+// (ver async_api::fetch_concurrently)
+#[cfg(feature = "async")]
+fn fetch_many(
+    options: &cli::Options,
+    client: &api::GitHubClient,
+    targets: &[cli::Target],
+) -> Vec<Option<Result<api::FetchedPages, error::ActivityError>>> {
+    let all = options.all;
+
+    // Cada tarefa é dona dos seus dados (async move): elas podem rodar em outras threads
+    let jobs: Vec<_> = targets
+        .iter()
+        .map(|target| {
+            let (target, client) = (target.clone(), client.clone());
+            async move {
+                match &target {
+                    cli::Target::User(username) => client.fetch_user_pages_async(username, all).await,
+                    cli::Target::Repo { owner, repo } => client.fetch_repo_pages_async(owner, repo, all).await,
+                }
+            }
+        })
+        .collect();
+
+    match tokio::runtime::Runtime::new() {
+        Ok(runtime) => {
+            let max_in_flight = options.jobs.unwrap_or(DEFAULT_JOBS);
+            runtime.block_on(async_api::fetch_concurrently(jobs, max_in_flight))
+        }
+        // Sem runtime não há como buscar nada: o mesmo erro para cada alvo
+        Err(e) => targets
+            .iter()
+            .map(|_| {
+                Some(Err(error::ActivityError::NetworkError {
+                    msg: format!("could not start the async runtime: {}", e),
+                    source: None,
+                }))
+            })
+            .collect(),
+    }
+}
+
+// Configuração que não veio da linha de comando: (ambiente, arquivo)
+// Um arquivo inexistente é normal; um arquivo inválido é erro (melhor que ignorá-lo calado)
+// --lang vence; sem ele, LC_ALL/LC_MESSAGES/LANG (ver i18n::Lang::from_env)
+fn set_language(lang: Option<i18n::Lang>) {
+    let lang = lang.unwrap_or_else(|| i18n::Lang::from_env(|name| env::var(name).ok()));
+    i18n::set_lang(lang);
+}
+
+fn load_config() -> Result<(config::Config, config::Config), error::ActivityError> {
+    let environment = config::Config::from_env(|name| env::var(name).ok())?;
+    let file = match config::config_path() {
+        Some(path) => config::load(&path)?,
+        None => config::Config::default(),
+    };
+
+    Ok((environment, file))
+}
+
+// Sem nenhum token configurado, usa o do login do `gh` (a menos que --no-gh-token)
+// O token do gh é do github.com: com outro --api-url ele nunca é enviado
+fn with_gh_token(mut options: cli::Options) -> cli::Options {
+    if options.token.is_some() || options.no_gh_token || options.api_url.is_some() {
+        return options;
+    }
+
+    if let Some(path) = credentials::gh_hosts_path() {
+        if let Some(token) = credentials::load_gh_token(&path) {
+            options.token = Some(token);
+            options.token_source = Some(credentials::TokenSource::GhCli(path));
+        }
+    }
+    options
+}
+
+// Sem username (nem default_user) e fora de --from-file, descobre um pelo token
+// ou pelo git (ver o módulo discovery); sem nenhum, o alvo continua vazio
+fn with_discovered_user(mut options: cli::Options) -> cli::Options {
+    if options.target.is_some() || options.from_file.is_some() || options.maintenance() {
+        return options;
+    }
+
+    let client = github_client(&options);
+    // GET /user só faz sentido com um token
+    let mut lookups = discovery::SystemLookups::new(options.token.as_ref().map(|_| &client));
+    let valid = |name: &str| client.check_username(name).is_ok();
+    if let Some((username, source)) = discovery::discover_username(&mut lookups, valid) {
+        logging::log(logging::Level::Info, || format!("no username given, using '{}' from {}", username, source));
+        options.target = Some(cli::Target::User(username));
+    }
+    options
+}
+
+// Lê todo o conteúdo de --from-file; "-" significa a entrada padrão
+// Erros de leitura viram IoError com o caminho, para a mensagem dizer qual arquivo falhou
+fn read_input(path: &str) -> Result<String, error::ActivityError> {
+    if path == "-" {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| error::ActivityError::io(input_name(path), e))?;
+        return Ok(text);
+    }
+
+    std::fs::read_to_string(path).map_err(|e| error::ActivityError::io(path, e))
+}
+
+// Avança o marcador de `key` para o evento mais novo e, com `only_new`,
+// remove de `events` o que o marcador anterior já cobria (devolvendo esse marcador)
+//
+// O arquivo de estado é só uma conveniência: qualquer problema com ele vira um aviso
+// e a listagem continua (no pior caso, --new mostra eventos repetidos)
+fn update_seen(key: &str, events: &mut Vec<models::GitHubEvent>, only_new: bool) -> Option<seen::Marker> {
+    let path = match seen::state_path() {
+        Some(path) => path,
+        None => {
+            if only_new {
+                eprintln!("warning: no cache directory (HOME is not set), so --new shows every event");
+            }
+            return None;
+        }
+    };
+
+    let mut state = seen::load(&path).unwrap_or_else(|e| {
+        eprintln!("warning: ignoring the last-seen state: {}", e);
+        seen::SeenState::default()
+    });
+    let previous = state.get(key);
+
+    if let Some(newest) = seen::Marker::newest(events) {
+        state.update(key, newest);
+        if let Err(e) = seen::save(&path, &state) {
+            eprintln!("warning: could not save the last-seen state: {}", e);
+        }
+    }
+
+    // Sem marcador anterior (primeira execução), --new mostra tudo
+    let previous = previous.filter(|_| only_new)?;
+    events.retain(|event| previous.is_unseen(event));
+    Some(previous)
+}
+
+// Uma notificação que não pôde ser mostrada (sem servidor de notificações, por exemplo)
+// não atrapalha a listagem: só aparece com --verbose
+fn notify_new_events(target: &str, events: &[models::GitHubEvent]) {
+    use notify::Notifier;
+
+    let mut notifier = notify::SystemNotifier::new();
+    if let Err(e) = notify::notify_new_events(target, events, &mut notifier) {
+        logging::log(logging::Level::Info, || format!("could not show a notification with {}: {}", notifier.name(), e));
+    }
+}
+
+// Envia os eventos ao webhook (ou, com --dry-run, só mostra o corpo no stderr)
+fn post_webhook(
+    options: &cli::Options,
+    client: &api::GitHubClient,
+    url: &str,
+    source: &str,
+    events: &[models::GitHubEvent],
+) -> Result<(), error::ActivityError> {
+    let payload = if options.slack {
+        webhook::format_slack_payload(source, events)
+    } else {
+        webhook::format_payload(source, events)
+    };
+
+    if options.dry_run {
+        eprintln!("Would POST to {}:\n{}", logging::redact_url(url), payload);
+        return Ok(());
+    }
+    client.post_webhook(url, &payload)?;
+    status(options, &format!("Posted {} events to {}", events.len(), logging::redact_url(url)));
+    Ok(())
+}
+
+// Devolve o marcador de `key` ao que era antes desta execução (None: nenhum)
+fn restore_seen(key: &str, previous: Option<seen::Marker>) {
+    let path = match seen::state_path() {
+        Some(path) => path,
+        None => return,
+    };
+    let mut state = seen::load(&path).unwrap_or_default();
+    // update() não volta no tempo: o marcador novo sai antes
+    state.remove(key);
+    if let Some(previous) = previous {
+        state.update(key, previous);
+    }
+    if let Err(e) = seen::save(&path, &state) {
+        eprintln!("warning: could not save the last-seen state: {}", e);
+    }
+}
+
+// --reset-seen: esquece o marcador de `key`
+fn reset_seen(key: &str) {
+    // Um arquivo corrompido é simplesmente substituído
+    let path = seen::state_path();
+    let mut state = path.as_deref().and_then(|path| seen::load(path).ok()).unwrap_or_default();
+
+    let path = match path {
+        Some(path) if state.remove(key) => path,
+        _ => {
+            println!("No last-seen event stored for '{}'", key);
+            return;
+        }
+    };
+
+    match seen::save(&path, &state) {
+        Ok(()) => println!("Forgot the last-seen event for '{}'", key),
+        Err(e) => eprintln!("warning: could not save the last-seen state: {}", e),
+    }
+}
+
+// O arquivo de histórico do username (ver history.rs); só usernames chegam aqui (ver cli.rs)
+fn history_file(target: &cli::Target) -> Result<std::path::PathBuf, error::ActivityError> {
+    history::history_path(&target.to_string()).ok_or_else(|| {
+        error::ActivityError::InvalidConfig(
+            "no data directory for the history: set XDG_DATA_HOME or HOME to an absolute path".to_string(),
+        )
+    })
+}
+
+// --log-history: acrescenta ao histórico os eventos que ele ainda não tem
+fn log_history(options: &cli::Options, target: &cli::Target, bodies: &[String]) -> Result<(), error::ActivityError> {
+    let path = history_file(target)?;
+    let added = history::append(&path, bodies, date::Timestamp::now())?;
+    status(options, &format!("Added {} new events to '{}'", added, path.display()));
+    Ok(())
+}
+
+// Se a API redirecionou o login de `target` para outro (o usuário foi renomeado),
+// avisa em stderr e devolve o login novo, para a listagem sair com o nome atual
+fn follow_rename(options: &cli::Options, client: &api::GitHubClient, target: cli::Target) -> cli::Target {
+    let renamed = match &target {
+        cli::Target::User(username) => client.renamed_login(username).map(|login| (username.clone(), login)),
+        cli::Target::Repo { .. } => None,
+    };
+    match renamed {
+        Some((old, new)) => {
+            if options.verbosity().notices {
+                eprintln!("{}", i18n::messages().user_renamed(&old, &new));
+            }
+            cli::Target::User(new)
+        }
+        None => target,
+    }
+}
+
+// --history: os eventos do histórico local no lugar da API, filtrados por --since
+// Linhas ilegíveis são puladas com um aviso, como os eventos que o parser pula
+fn read_history(
+    options: &cli::Options,
+    target: Option<&cli::Target>,
+) -> Result<parser::ParseOutcome, error::ActivityError> {
+    let target = match target {
+        Some(target) => target,
+        // check_target exige um username sem --from-file
+        None => unreachable!("a target is required with --history"),
+    };
+    let path = history_file(target)?;
+    let history = history::read(&path, options.since)?;
+    if !history.corrupt_lines.is_empty() {
+        let lines: Vec<String> = history.corrupt_lines.iter().map(|line| line.to_string()).collect();
+        let plural = if lines.len() == 1 { "" } else { "s" };
+        eprintln!(
+            "warning: skipped {} unreadable line{} of '{}' (line{} {})",
+            lines.len(),
+            plural,
+            path.display(),
+            plural,
+            lines.join(", ")
+        );
+    }
+    Ok(parser::ParseOutcome { events: history.events, warnings: Vec::new() })
+}
+
+// --backend graphql: as contribuições de --since (ou do último ano) até agora, no lugar do feed
+#[cfg(feature = "graphql")]
+fn fetch_contributions(
+    options: &cli::Options,
+    client: &api::GitHubClient,
+    target: Option<&cli::Target>,
+) -> Result<parser::ParseOutcome, error::ActivityError> {
+    let username = match target {
+        Some(cli::Target::User(username)) => username,
+        // cli.rs só aceita --backend graphql com um username
+        _ => unreachable!("a username is required with --backend graphql"),
+    };
+    let to = date::Timestamp::now();
+    let from = options
+        .since
+        .unwrap_or_else(|| date::Timestamp::from_epoch_seconds(to.epoch_seconds() - GRAPHQL_DEFAULT_DAYS * 86_400));
+    client.fetch_contributions(username, from, to)
+}
+
+// Salva as respostas brutas em `path` com o cabeçalho de recording::RecordingInfo
+fn write_recording(path: &str, target: &cli::Target, bodies: &[String]) -> Result<(), error::ActivityError> {
+    let info = recording::RecordingInfo {
+        target: target.to_string(),
+        fetched_at: Some(date::Timestamp::now()),
+        pages: bodies.len(),
+    };
+    let text = recording::format_recording(&info, bodies)?;
+
+    std::fs::write(path, text).map_err(|e| error::ActivityError::io(path, e))
+}
+
+// Acrescenta os eventos ao banco de --export-sqlite (ver storage.rs) e conta os novos
+#[cfg(feature = "sqlite")]
+fn export_sqlite(
+    options: &cli::Options,
+    path: &str,
+    target: &cli::Target,
+    events: &[models::GitHubEvent],
+    bodies: &[String],
+) -> Result<(), error::ActivityError> {
+    let mut database = storage::Database::open(path)?;
+    let added = database.upsert(&target.to_string(), events, bodies)?;
+    status(options, &format!("Saved {} events to '{}' ({} new)", events.len(), path, added));
+    Ok(())
+}
+
+// Nome exibido para a origem de --from-file
+fn input_name(path: &str) -> &str {
+    if path == "-" { "<stdin>" } else { path }
+}
+
 // CONCEITO: Conditional Compilation
 // #[cfg(test)] significa "compile apenas em modo de teste"
 // Execute com: cargo test
 #[cfg(test)]
 mod tests {
-    use super::*;  // Importa tudo do módulo pai (main)
+    use super::*;
 
     // Aqui você poderia adicionar testes de integração
     // Por exemplo, testar a função run() com mocks
 
+    const FORK_EVENTS: &str = include_str!("../tests/fixtures/fork_events.json");
+
+    // O que show() escreve no stdout para um arquivo de eventos, com estes argumentos
+    // (--no-time e --no-color deixam a saída igual em qualquer dia e terminal)
+    fn capture(events_json: &str, extra: &[&str]) -> String {
+        let mut list = vec!["--from-file", "events.json", "--no-time", "--no-color"];
+        list.extend_from_slice(extra);
+        let list: Vec<String> = list.into_iter().map(String::from).collect();
+        let options = match cli::parse_args(&list).unwrap() {
+            cli::Command::Activity(options) => {
+                options.resolve(config::Config::default(), config::Config::default()).unwrap()
+            }
+            other => panic!("unexpected {:?}", other),
+        };
+
+        let client = api::GitHubClient::builder().build();
+        let outcome = parser::parse_events(events_json).unwrap();
+        let mut out = Vec::new();
+        show(&options, &client, &mut out, &None, "events.json", outcome).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_default_output_has_header_and_trailing_blank_line() {
+        assert_eq!(
+            capture(FORK_EVENTS, &[]),
+            "\nEvents from 'events.json':\n\
+             Found 2 events\n\
+             \n\
+             - octocat: Forked torvalds/linux to octocat/linux\n\
+             - octocat: Forked octocat/Hello-World\n\
+             \n"
+        );
+    }
+
+    #[test]
+    fn test_quiet_output_is_only_the_events() {
+        assert_eq!(
+            capture(FORK_EVENTS, &["--quiet"]),
+            "- octocat: Forked torvalds/linux to octocat/linux\n\
+             - octocat: Forked octocat/Hello-World\n"
+        );
+        // Sem eventos, nem o aviso aparece
+        assert_eq!(capture("[]", &["--quiet"]), "");
+        // Outras saídas perdem só as linhas em branco em volta
+        let summary = capture(FORK_EVENTS, &["--quiet", "--summary"]);
+        assert!(summary.starts_with("\nSummary for 'events.json':\n") && summary.ends_with("(1 event)\n"), "{:?}", summary);
+    }
+
+    #[test]
+    fn test_sort_keeps_the_first_activity_marks_on_their_events() {
+        assert_eq!(
+            capture(FORK_EVENTS, &["--quiet", "--sort", "oldest", "--first-contributions"]),
+            "- octocat: Forked octocat/Hello-World  ✦ first activity here in this window\n\
+             - octocat: Forked torvalds/linux to octocat/linux  ✦ first activity here in this window\n"
+        );
+        assert_eq!(
+            capture(FORK_EVENTS, &["--quiet", "--sort", "repo"]),
+            "- octocat: Forked octocat/Hello-World\n\
+             - octocat: Forked torvalds/linux to octocat/linux\n"
+        );
+    }
+
+    #[test]
+    fn test_json_output_is_only_the_json() {
+        let json = capture(FORK_EVENTS, &["--summary", "--json"]);
+        assert!(json.starts_with("{\"total_events\":2,") && json.ends_with("}\n"), "{:?}", json);
+        assert_eq!(json.lines().count(), 1);
+        assert_eq!(capture(FORK_EVENTS, &["--summary", "--json", "--quiet"]), json);
+    }
+
     #[test]
     fn test_example() {
         // Este é só um placeholder
@@ -1,108 +1,1268 @@
-// Este módulo define as estruturas de dados que representam eventos do GitHub
-// Modelar dados com tipos fortes é uma das maiores vantagens de Rust
+//! Este módulo define as estruturas de dados que representam eventos do GitHub
+//! Modelar dados com tipos fortes é uma das maiores vantagens de Rust
+
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::date::Timestamp;
+use crate::stats;
+
+/// Endereço das páginas do GitHub (não da API), usado nos links do terminal (--hyperlinks)
+pub const GITHUB_WEB_BASE: &str = "https://github.com";
+
+static WEB_BASE: OnceLock<String> = OnceLock::new();
+
+/// Escolhe o endereço das páginas usado nos links dos eventos (um GitHub Enterprise,
+/// ver api::web_base_url); só a primeira escolha vale (devolve false se já havia uma)
+pub fn set_web_base(base: String) -> bool {
+    WEB_BASE.set(base).is_ok()
+}
+
+/// O endereço escolhido com set_web_base(), ou GITHUB_WEB_BASE
+pub fn web_base() -> &'static str {
+    WEB_BASE.get().map_or(GITHUB_WEB_BASE, String::as_str)
+}
+
+/// Separa "owner/name" em (owner, name)
+///
+/// None quando falta uma das partes: um feed de repositório às vezes manda só o
+/// nome, e "https://github.com/linux" seria a página de um usuário, não a do repositório
+pub fn split_repo_name(repo_name: &str) -> Option<(&str, &str)> {
+    repo_name.split_once('/').filter(|(owner, name)| !owner.is_empty() && !name.is_empty())
+}
 
 // CONCEITO: Structs
 // Structs são tipos customizados que agrupam dados relacionados
 // Similar a classes em outras linguagens, mas sem métodos (por padrão)
-#[derive(Debug, Clone)]  // Deriva Debug (para imprimir) e Clone (para copiar)
+// CONCEITO: PartialEq, Eq e Hash
+// Permitem comparar eventos com == (e assert_eq! nos testes) e usá-los como chave de HashSet
+// Só dá para derivar Eq e Hash porque nenhum campo é f32/f64 (NaN != NaN quebraria Eq)
+/// Um evento da atividade pública: o que aconteceu, onde, quando e quem fez
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]  // Debug (imprimir), Clone (copiar), o resto (comparar)
 pub struct GitHubEvent {
     // CONCEITO: pub
     // 'pub' torna o campo público, acessível de fora do módulo
     // Sem 'pub', campos seriam privados por padrão
-    pub event_type: String,  // Tipo do evento (PushEvent, WatchEvent, etc.)
-    pub repo_name: String,   // Nome completo do repositório (ex: "torvalds/linux")
-    pub payload: EventPayload,  // Dados específicos do tipo de evento
+    /// Tipo do evento (campo "type": PushEvent, WatchEvent, etc.)
+    pub kind: EventKind,
+    /// Id do evento (campo "id", que a API manda como string: "42000000011")
+    /// Os ids crescem com o tempo: um id maior é um evento mais novo
+    pub id: Option<u64>,
+    /// Nome completo do repositório (ex: "torvalds/linux")
+    pub repo_name: String,
+    /// Dados específicos do tipo de evento
+    pub payload: EventPayload,
+    /// Quando o evento aconteceu (campo "created_at")
+    /// Option: um timestamp ausente ou mal formatado não descarta o evento
+    pub created_at: Option<Timestamp>,
+    /// Quem gerou o evento (campo "actor")
+    /// Option porque o campo pode faltar em dados antigos ou incompletos
+    pub actor: Option<Actor>,
+}
+
+/// Tipo de um evento, como vem no campo "type" da API
+/// Um enum (e não a String crua) deixa o compilador conferir cada comparação:
+/// um erro de digitação em EventKind::Psuh não compila, em "PsuhEvent" compilaria
+///
+/// Other guarda o nome de tipos que ainda não conhecemos, para que eventos
+/// novos do GitHub continuem aparecendo (compatibilidade com o futuro)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    /// PushEvent
+    Push,
+    /// IssuesEvent
+    Issues,
+    /// PullRequestEvent
+    PullRequest,
+    /// PullRequestReviewEvent
+    PullRequestReview,
+    /// WatchEvent (star)
+    Watch,
+    /// ForkEvent
+    Fork,
+    /// CreateEvent
+    Create,
+    /// DeleteEvent
+    Delete,
+    /// ReleaseEvent
+    Release,
+    /// IssueCommentEvent
+    IssueComment,
+    /// PullRequestReviewCommentEvent
+    PullRequestReviewComment,
+    /// CommitCommentEvent
+    CommitComment,
+    /// GollumEvent (wiki)
+    Gollum,
+    /// MemberEvent
+    Member,
+    /// PublicEvent
+    Public,
+    /// Qualquer outro tipo, com o nome original (ex: "SponsorshipEvent")
+    Other(String),
+}
+
+impl EventKind {
+    /// O nome usado pela API: "PushEvent", "WatchEvent"...
+    pub fn name(&self) -> &str {
+        match self {
+            EventKind::Push => "PushEvent",
+            EventKind::Issues => "IssuesEvent",
+            EventKind::PullRequest => "PullRequestEvent",
+            EventKind::PullRequestReview => "PullRequestReviewEvent",
+            EventKind::Watch => "WatchEvent",
+            EventKind::Fork => "ForkEvent",
+            EventKind::Create => "CreateEvent",
+            EventKind::Delete => "DeleteEvent",
+            EventKind::Release => "ReleaseEvent",
+            EventKind::IssueComment => "IssueCommentEvent",
+            EventKind::PullRequestReviewComment => "PullRequestReviewCommentEvent",
+            EventKind::CommitComment => "CommitCommentEvent",
+            EventKind::Gollum => "GollumEvent",
+            EventKind::Member => "MemberEvent",
+            EventKind::Public => "PublicEvent",
+            EventKind::Other(name) => name,
+        }
+    }
+
+    /// Método auxiliar para verificar se o payload requer dados do JSON
+    /// Retorna true se precisarmos fazer parsing adicional do payload
+    pub fn requires_payload_parsing(&self) -> bool {
+        matches!(
+            self,
+            EventKind::Push
+                | EventKind::Issues
+                | EventKind::PullRequest
+                | EventKind::PullRequestReview
+                | EventKind::Fork
+                | EventKind::Create
+                | EventKind::Delete
+                | EventKind::Release
+                | EventKind::Gollum
+                | EventKind::Member
+        )
+    }
+}
+
+// CONCEITO: FromStr
+// Implementar FromStr habilita "PushEvent".parse::<EventKind>()
+// O erro é Infallible (um tipo sem valores): qualquer nome vira pelo menos Other
+impl FromStr for EventKind {
+    type Err = Infallible;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Ok(match name {
+            "PushEvent" => EventKind::Push,
+            "IssuesEvent" => EventKind::Issues,
+            "PullRequestEvent" => EventKind::PullRequest,
+            "PullRequestReviewEvent" => EventKind::PullRequestReview,
+            "WatchEvent" => EventKind::Watch,
+            "ForkEvent" => EventKind::Fork,
+            "CreateEvent" => EventKind::Create,
+            "DeleteEvent" => EventKind::Delete,
+            "ReleaseEvent" => EventKind::Release,
+            "IssueCommentEvent" => EventKind::IssueComment,
+            "PullRequestReviewCommentEvent" => EventKind::PullRequestReviewComment,
+            "CommitCommentEvent" => EventKind::CommitComment,
+            "GollumEvent" => EventKind::Gollum,
+            "MemberEvent" => EventKind::Member,
+            "PublicEvent" => EventKind::Public,
+            other => EventKind::Other(other.to_string()),
+        })
+    }
+}
+
+// Display escreve o nome da API, o caminho inverso de FromStr
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Grupo grosso de tipos de evento, para filtrar (--category) e colorir sem
+/// listar cada variante de EventPayload de novo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventCategory {
+    /// Pushes e criação/remoção de branches e tags
+    Code,
+    /// Issues e comentários em issues
+    Issues,
+    /// Pull requests, revisões e comentários em código (de PR ou de commit)
+    Review,
+    /// Stars, forks e colaboradores adicionados
+    Social,
+    /// Releases
+    Release,
+    /// Wiki, repositório tornado público e tipos desconhecidos
+    Other,
+}
+
+impl EventCategory {
+    /// Todas as categorias, na ordem em que o --help as lista
+    pub const ALL: [EventCategory; 6] = [
+        EventCategory::Code,
+        EventCategory::Issues,
+        EventCategory::Review,
+        EventCategory::Social,
+        EventCategory::Release,
+        EventCategory::Other,
+    ];
+
+    /// O nome aceito por --category: "code", "issues"...
+    pub fn name(self) -> &'static str {
+        match self {
+            EventCategory::Code => "code",
+            EventCategory::Issues => "issues",
+            EventCategory::Review => "review",
+            EventCategory::Social => "social",
+            EventCategory::Release => "release",
+            EventCategory::Other => "other",
+        }
+    }
+
+    /// O inverso de name(); None para um nome que não é de categoria
+    pub fn from_name(name: &str) -> Option<EventCategory> {
+        EventCategory::ALL.into_iter().find(|category| category.name() == name)
+    }
+}
+
+impl fmt::Display for EventCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// O usuário que gerou um evento
+/// Em modo repositório (e em feeds de organização) cada evento pode ter um ator diferente
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Actor {
+    /// Nome de usuário, ex: "torvalds"
+    pub login: String,
+    /// Id numérico: continua o mesmo se o usuário mudar de login
+    pub id: u64,
+}
+
+/// Um commit de um push: o suficiente para mostrar com --commits
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CommitSummary {
+    /// Hash do commit; ainda não exibido, mas identifica o commit para quem usa os eventos
+    pub sha: String,
+    /// Mensagem completa (título + corpo)
+    pub message: String,
+}
+
+impl CommitSummary {
+    /// Primeira linha da mensagem: o "título" do commit
+    pub fn title(&self) -> &str {
+        self.message.lines().next().unwrap_or("")
+    }
+}
+
+/// O perfil público de um usuário (endpoint /users/{username}), para o cabeçalho
+/// Só os campos que o cabeçalho mostra; os textos opcionais são null para muitos usuários
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserProfile {
+    /// Nome de usuário, com as maiúsculas do cadastro ("Torvalds" vira "torvalds")
+    pub login: String,
+    /// Nome de exibição, ex: "Linus Torvalds"
+    pub name: Option<String>,
+    /// Quantos seguidores
+    pub followers: u64,
+    /// Quantos repositórios públicos
+    pub public_repos: u64,
+    /// Empresa, como o usuário escreveu ("@linuxfoundation", "Linux Foundation"...)
+    pub company: Option<String>,
+    /// Localização, texto livre
+    pub location: Option<String>,
+}
+
+/// Um repositório das listas da API (/users/{username}/starred e /users/{username}/repos)
+/// Descrição e linguagem são null para muitos repositórios
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repository {
+    /// "owner/repo"
+    pub full_name: String,
+    /// Só o nome, sem o dono: "repo"
+    pub name: String,
+    /// A descrição curta do repositório
+    pub description: Option<String>,
+    /// Quantas estrelas
+    pub stargazers_count: u64,
+    /// A linguagem principal, segundo o GitHub ("Rust", "C"...)
+    pub language: Option<String>,
+    /// É um fork de outro repositório?
+    pub fork: bool,
+    /// A última mudança no repositório (None se faltar ou vier num formato estranho)
+    pub updated_at: Option<Timestamp>,
 }
 
 // CONCEITO: Enums com Dados
 // Diferente de enums em C/Java, enums em Rust podem carregar dados
 // Cada variante pode ter dados diferentes ou nenhum dado
-#[derive(Debug, Clone)]
+/// Os dados específicos de cada tipo de evento (campo "payload" da API)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum EventPayload {
     // Variante com campo nomeado
-    // Usada quando alguém faz push de commits
+    /// Usada quando alguém faz push de commits
     Push {
-        commit_count: usize,  // usize é um inteiro sem sinal do tamanho do ponteiro (32/64 bits)
+        /// Quantos commits o push enviou ("size")
+        // usize é um inteiro sem sinal do tamanho do ponteiro (32/64 bits)
+        commit_count: usize,
+        /// Quantos desses commits são novos ("distinct_size"); menor que commit_count
+        /// quando o push reenviou commits que já existiam (ex: force-push)
+        distinct_count: usize,
+        /// Quantos pushes este evento representa: 1 vindo da API,
+        /// mais que 1 quando o --collapse junta pushes consecutivos
+        push_count: usize,
+        /// Branch que recebeu o push, já sem o prefixo "refs/heads/" (ex: "main")
+        branch: Option<String>,
+        /// Commits listados no payload (a API manda no máximo 20 por push,
+        /// então pode haver menos itens que commit_count)
+        commits: Vec<CommitSummary>,
     },
 
-    // Evento de issue (aberta, fechada, etc.)
+    /// Evento de issue (aberta, fechada, etc.)
     IssuesEvent {
-        action: String,  // "opened", "closed", "reopened"
+        /// "opened", "closed", "reopened"
+        action: String,
+        /// #42 (payload.issue.number)
+        number: Option<u64>,
+        /// "Fix crash on startup" (payload.issue.title)
+        title: Option<String>,
     },
 
-    // Evento de pull request
+    /// Evento de pull request
     PullRequestEvent {
-        action: String,  // "opened", "closed", "merged"
+        /// "opened", "closed", "reopened"...
+        action: String,
+        /// #1347 (payload.pull_request.number)
+        number: Option<u64>,
+        /// Título do PR (payload.pull_request.title)
+        title: Option<String>,
+        /// A API informa um merge como action "closed" + pull_request.merged = true
+        merged: bool,
     },
 
     // Variante sem dados
-    // WatchEvent significa que alguém deu "star" no repositório
+    /// WatchEvent significa que alguém deu "star" no repositório
     WatchEvent,
 
-    // Evento de fork (alguém copiou o repositório)
-    ForkEvent,
+    /// Evento de fork (alguém copiou o repositório)
+    ForkEvent {
+        /// Destino do fork: payload.forkee.full_name, ex: "myuser/linux"
+        forkee: Option<String>,
+    },
 
-    // Criação de branch ou tag
+    /// Criação de branch ou tag
     CreateEvent {
-        ref_type: String,  // "branch" ou "tag"
+        /// "branch", "tag" ou "repository"
+        ref_type: String,
+        /// "feature-x", "v1.2.0"; None ao criar um repositório
+        ref_name: Option<String>,
     },
 
-    // Deleção de branch ou tag
+    /// Deleção de branch ou tag
     DeleteEvent {
-        ref_type: String,  // "branch" ou "tag"
+        /// "branch" ou "tag"
+        ref_type: String,
+        /// Nome da branch ou tag removida
+        ref_name: Option<String>,
     },
 
-    // Publicação de uma release
+    /// Publicação de uma release
     ReleaseEvent {
-        action: String,  // "published", "created", "edited"
+        /// "published", "created", "edited"
+        action: String,
+        /// payload.release.tag_name, ex: "v1.4.0"
+        tag: Option<String>,
+        /// payload.release.name, ex: "Spring cleaning" (pode ser vazio)
+        name: Option<String>,
+        /// payload.release.draft: rascunho ainda não publicado
+        draft: bool,
     },
 
-    // Comentários em issues
+    /// Comentários em issues
     IssueCommentEvent,
 
-    // Comentários em pull requests (code review)
+    /// Comentários em pull requests (code review)
     PullRequestReviewCommentEvent,
 
-    // Comentários em commits
+    /// Comentários em commits
     CommitCommentEvent,
 
-    // Tipo desconhecido - usado quando encontramos um evento que não mapeamos
-    // É uma boa prática ter um caso "catch-all" para dados externos
-    Unknown,
+    /// Revisão de um pull request (PullRequestReviewEvent)
+    PullRequestReview {
+        /// "created" (a revisão foi enviada); ainda não exibido, o estado já diz o que a revisão fez
+        action: String,
+        /// payload.review.state: "approved", "changes_requested", "commented"
+        state: String,
+    },
+
+    /// Colaborador adicionado ao repositório (MemberEvent)
+    Member {
+        /// "added" (a API antiga também mandava "removed", "edited")
+        action: String,
+        /// payload.member.login
+        member_login: Option<String>,
+    },
+
+    /// O repositório se tornou público (PublicEvent)
+    Public,
+
+    /// Edição da wiki (GollumEvent - Gollum é o motor de wiki do GitHub)
+    /// Um evento pode mexer em várias páginas de uma vez
+    Gollum {
+        /// Quantas páginas o evento tocou
+        pages_edited: usize,
+        /// "page_name" da primeira página, ex: "Home"
+        first_page: Option<String>,
+        /// "created" ou "edited"
+        first_page_action: Option<String>,
+    },
+
+    /// Tipo desconhecido - usado quando encontramos um evento que não mapeamos
+    /// É uma boa prática ter um caso "catch-all" para dados externos
+    /// Guardamos o payload como texto (JSON compacto, cortado em cerca de 2 KB)
+    /// para que o --verbose mostre o que não soubemos interpretar
+    Unknown {
+        /// None se o evento não tinha payload
+        raw_payload: Option<String>,
+    },
 }
 
 // CONCEITO: Implementação de métodos
 // O bloco 'impl' adiciona métodos (funções associadas) a um tipo
 impl GitHubEvent {
-    // Método construtor - convenção em Rust é usar 'new'
-    // 'pub' torna o método público
-    pub fn new(event_type: String, repo_name: String, payload: EventPayload) -> Self {
+    /// Método construtor - convenção em Rust é usar 'new'
+    /// 'pub' torna o método público
+    pub fn new(
+        kind: EventKind,
+        repo_name: String,
+        payload: EventPayload,
+        created_at: Option<Timestamp>,
+    ) -> Self {
         // 'Self' é um alias para o tipo que estamos implementando (GitHubEvent)
         // Em Rust, a última expressão de uma função é automaticamente retornada
         // (não precisa de 'return' explícito)
         GitHubEvent {
-            event_type,  // Sintaxe curta: event_type: event_type
+            kind,        // Sintaxe curta: kind: kind
+            id: None,
             repo_name,   // O Rust permite omitir o valor se o nome do campo == nome da variável
             payload,
+            created_at,
+            actor: None,
+        }
+    }
+
+    /// Define o id do evento, no mesmo estilo de with_actor
+    pub fn with_id(mut self, id: Option<u64>) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Define o ator do evento, no estilo "builder"
+    // CONCEITO: self por valor
+    // O método toma posse do evento, modifica e devolve - permite encadear chamadas:
+    // GitHubEvent::new(...).with_actor(...)
+    pub fn with_actor(mut self, actor: Option<Actor>) -> Self {
+        self.actor = actor;
+        self
+    }
+
+    /// O evento é de um tipo que o parser não conhece?
+    pub fn is_unknown(&self) -> bool {
+        matches!(self.payload, EventPayload::Unknown { .. })
+    }
+
+    /// (owner, name) do repositório do evento; ver split_repo_name
+    pub fn owner_and_name(&self) -> Option<(&str, &str)> {
+        split_repo_name(&self.repo_name)
+    }
+
+    /// Página do repositório no GitHub: "https://github.com/rust-lang/rust"
+    /// (em outro host com set_web_base)
+    pub fn repo_url(&self) -> String {
+        format!("{}/{}", web_base(), self.repo_name)
+    }
+
+    /// Página da coisa de que o evento fala, quando ela tem uma própria:
+    /// a issue (".../issues/42"), o pull request (".../pull/1347"), a release
+    /// (".../releases/tag/v1.0"), o fork criado, o primeiro commit de um push
+    /// (".../commit/<sha>") ou a branch ou tag criada (".../tree/feature-x")
+    /// None nos demais eventos e quando o payload não trouxe o número (ou a tag...)
+    pub fn item_url(&self) -> Option<String> {
+        match &self.payload {
+            EventPayload::Push { commits, .. } => commits
+                .first()
+                .filter(|commit| !commit.sha.is_empty())
+                .map(|commit| format!("{}/commit/{}", self.repo_url(), encode_path_segment(&commit.sha))),
+            EventPayload::CreateEvent { ref_type, ref_name: Some(ref_name) } if ref_type != "repository" => {
+                Some(format!("{}/tree/{}", self.repo_url(), encode_path_segment(ref_name)))
+            }
+            EventPayload::IssuesEvent { number: Some(number), .. } => {
+                Some(format!("{}/issues/{}", self.repo_url(), number))
+            }
+            EventPayload::PullRequestEvent { number: Some(number), .. } => {
+                Some(format!("{}/pull/{}", self.repo_url(), number))
+            }
+            EventPayload::ReleaseEvent { tag: Some(tag), .. } => {
+                Some(format!("{}/releases/tag/{}", self.repo_url(), encode_path_segment(tag)))
+            }
+            EventPayload::ForkEvent { forkee: Some(forkee) } => Some(format!("{}/{}", web_base(), forkee)),
+            _ => None,
         }
     }
+
+    /// A página que melhor mostra o evento (--open, webhooks): a de item_url() ou,
+    /// sem uma, a do repositório
+    /// None quando o nome do repositório não tem owner e name (ver split_repo_name):
+    /// nenhum link montado com ele levaria ao lugar certo
+    pub fn html_url(&self) -> Option<String> {
+        self.owner_and_name()?;
+        Some(self.item_url().unwrap_or_else(|| self.repo_url()))
+    }
+}
+
+// Uma tag pode ter caracteres com significado numa URL ("#", "?", "%"...):
+// tudo o que não for letra, dígito, "-", ".", "_", "~" ou "/" vira %XX (byte a byte, em UTF-8)
+// A "/" fica: "releases/tag/app/v1" é o endereço que o próprio GitHub usa
+fn encode_path_segment(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
 }
 
 impl EventPayload {
-    // Método auxiliar para verificar se o payload requer dados do JSON
-    // Retorna true se precisarmos fazer parsing adicional do payload
-    pub fn requires_payload_parsing(event_type: &str) -> bool {
-        // CONCEITO: &str vs String
-        // &str é uma "string slice" - uma referência imutável a uma string
-        // É mais eficiente que String quando não precisamos possuir os dados
+    /// A categoria do evento (ver EventCategory)
+    // Sem braço "_": uma variante nova em EventPayload sem categoria não compila
+    pub fn category(&self) -> EventCategory {
+        match self {
+            EventPayload::Push { .. } | EventPayload::CreateEvent { .. } | EventPayload::DeleteEvent { .. } => {
+                EventCategory::Code
+            }
+            EventPayload::IssuesEvent { .. } | EventPayload::IssueCommentEvent => EventCategory::Issues,
+            EventPayload::PullRequestEvent { .. }
+            | EventPayload::PullRequestReview { .. }
+            | EventPayload::PullRequestReviewCommentEvent
+            | EventPayload::CommitCommentEvent => EventCategory::Review,
+            EventPayload::WatchEvent | EventPayload::ForkEvent { .. } | EventPayload::Member { .. } => {
+                EventCategory::Social
+            }
+            EventPayload::ReleaseEvent { .. } => EventCategory::Release,
+            EventPayload::Gollum { .. } | EventPayload::Public | EventPayload::Unknown { .. } => EventCategory::Other,
+        }
+    }
+
+    /// O evento é um comentário (em issue, em pull request ou em commit)?
+    pub fn is_comment(&self) -> bool {
         matches!(
-            event_type,
-            "PushEvent"
-                | "IssuesEvent"
-                | "PullRequestEvent"
-                | "CreateEvent"
-                | "DeleteEvent"
-                | "ReleaseEvent"
+            self,
+            EventPayload::IssueCommentEvent
+                | EventPayload::PullRequestReviewCommentEvent
+                | EventPayload::CommitCommentEvent
+        )
+    }
+
+    /// O evento mudou o código de uma branch? Vale para pushes e PRs mergeados;
+    /// um PR aberto (ou fechado sem merge) só propõe a mudança
+    pub fn is_code_change(&self) -> bool {
+        matches!(self, EventPayload::Push { .. } | EventPayload::PullRequestEvent { merged: true, .. })
+    }
+
+    /// A action do payload como a API manda ("opened", "closed", "published"...),
+    /// ou None para os tipos que não têm uma (push, star, fork...)
+    ///
+    /// Um PR mergeado continua "closed", como na API (o merge fica em `merged`);
+    /// na wiki vale a action da primeira página ("created" ou "edited")
+    pub fn action(&self) -> Option<&str> {
+        match self {
+            EventPayload::IssuesEvent { action, .. }
+            | EventPayload::PullRequestEvent { action, .. }
+            | EventPayload::ReleaseEvent { action, .. }
+            | EventPayload::PullRequestReview { action, .. }
+            | EventPayload::Member { action, .. } => Some(action),
+            EventPayload::Gollum { first_page_action, .. } => first_page_action.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Rótulos (singular, plural) usados na contagem por tipo do resumo
+    /// kind só é usado para eventos desconhecidos, que mostram o tipo original
+    pub fn summary_labels(&self, kind: &EventKind) -> (String, String) {
+        // Macro local para evitar repetir .to_string() em cada braço
+        macro_rules! labels {
+            ($singular:expr, $plural:expr) => {
+                ($singular.to_string(), $plural.to_string())
+            };
+        }
+
+        match self {
+            EventPayload::Push { .. } => labels!("push", "pushes"),
+            EventPayload::IssuesEvent { action, .. } => {
+                labels!(format!("issue {}", action), format!("issues {}", action))
+            }
+            // Um PR "closed" com merge aparece como "PRs merged"
+            EventPayload::PullRequestEvent { merged: true, .. } => {
+                labels!("PR merged", "PRs merged")
+            }
+            EventPayload::PullRequestEvent { action, .. } => {
+                labels!(format!("PR {}", action), format!("PRs {}", action))
+            }
+            EventPayload::WatchEvent => labels!("star", "stars"),
+            EventPayload::ForkEvent { .. } => labels!("fork", "forks"),
+            EventPayload::CreateEvent { ref_type, .. } => labels!(
+                format!("{} created", ref_type),
+                format!("{} created", plural_ref_type(ref_type))
+            ),
+            EventPayload::DeleteEvent { ref_type, .. } => labels!(
+                format!("{} deleted", ref_type),
+                format!("{} deleted", plural_ref_type(ref_type))
+            ),
+            EventPayload::ReleaseEvent { action, .. } => {
+                labels!(format!("release {}", action), format!("releases {}", action))
+            }
+            EventPayload::IssueCommentEvent => labels!("issue comment", "issue comments"),
+            EventPayload::PullRequestReviewCommentEvent => {
+                labels!("review comment", "review comments")
+            }
+            EventPayload::CommitCommentEvent => labels!("commit comment", "commit comments"),
+            EventPayload::Gollum { .. } => labels!("wiki edit", "wiki edits"),
+            EventPayload::PullRequestReview { .. } => labels!("PR review", "PR reviews"),
+            EventPayload::Member { action, .. } => labels!(
+                format!("collaborator {}", action),
+                format!("collaborators {}", action)
+            ),
+            EventPayload::Public => labels!("repo made public", "repos made public"),
+            EventPayload::Unknown { .. } => labels!(kind.name(), kind.name()),
+        }
+    }
+}
+
+/// Quantos repositórios o resumo (--summary) lista como "mais ativos"
+pub const SUMMARY_TOP_REPOS: usize = 5;
+
+/// Uma linha da contagem por tipo: "12 pushes", "3 PRs opened"
+/// Guardamos singular e plural porque a regra não é só "adicionar s"
+/// ("PR opened" -> "PRs opened", "branch created" -> "branches created")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeCount {
+    /// Rótulo para um evento: "PR opened"
+    pub singular: String,
+    /// Rótulo para vários eventos: "PRs opened"
+    pub plural: String,
+    /// Quantos eventos deste tipo
+    pub count: usize,
+}
+
+impl TypeCount {
+    /// Rótulo concordando com a contagem
+    pub fn label(&self) -> &str {
+        if self.count == 1 {
+            &self.singular
+        } else {
+            &self.plural
+        }
+    }
+}
+
+/// Estatísticas agregadas de uma lista de eventos (modo --summary)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivitySummary {
+    /// Quantos eventos foram resumidos
+    pub total_events: usize,
+    /// Contagem por tipo, da maior para a menor
+    pub by_type: Vec<TypeCount>,
+    /// Até SUMMARY_TOP_REPOS repositórios com mais eventos: (nome, eventos)
+    pub top_repos: Vec<(String, usize)>,
+    /// Início do período coberto; None quando nenhum evento tem horário
+    pub first_event: Option<Timestamp>,
+    /// Fim do período coberto (o evento mais recente)
+    pub last_event: Option<Timestamp>,
+    /// Eventos de tipos que o parser não conhece (EventPayload::Unknown)
+    pub unrecognized_events: usize,
+    /// Os nomes distintos desses tipos, na ordem em que apareceram
+    pub unrecognized_types: Vec<String>,
+    /// Todos os repositórios com eventos (não só os mais ativos), em ordem alfabética
+    pub repos: Vec<String>,
+    /// O dia ("2024-06-01", no fuso de from_events_in) com mais eventos e quantos foram;
+    /// empates ficam com o mais antigo
+    pub busiest_day: Option<(String, usize)>,
+}
+
+/// Uma linha da comparação por tipo (--compare): "pushes", 12 de um lado, 3 do outro
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeComparison {
+    /// Rótulo no plural, como as chaves do resumo em JSON: "pushes", "PRs opened"
+    pub label: String,
+    /// Contagem do primeiro usuário
+    pub left: usize,
+    /// Contagem do segundo usuário
+    pub right: usize,
+}
+
+/// Dois resumos lado a lado (--compare), com o que dá para calcular só tendo os dois
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummaryComparison {
+    /// Resumo do primeiro usuário
+    pub left: ActivitySummary,
+    /// Resumo do segundo usuário
+    pub right: ActivitySummary,
+    /// Os tipos de qualquer um dos dois (0 de quem não teve), do maior total para o menor
+    pub by_type: Vec<TypeComparison>,
+    /// Repositórios em que os dois tiveram atividade, em ordem alfabética
+    pub shared_repos: Vec<String>,
+}
+
+impl ActivitySummary {
+    /// Calcula o resumo sem nenhum efeito colateral (sem rede, sem impressão),
+    /// o que permite testá-lo com um vetor de eventos montado à mão
+    ///
+    /// Empates na contagem mantêm a ordem em que o tipo/repositório apareceu
+    /// primeiro na lista (ou seja, o mais recente vem antes)
+    ///
+    /// Os dias (busiest_day) são contados em UTC; ver from_events_in para outro fuso
+    pub fn from_events(events: &[GitHubEvent]) -> Self {
+        ActivitySummary::from_events_in(events, 0)
+    }
+
+    /// Como from_events, com os dias contados no fuso `offset_seconds` (o de --utc ou o
+    /// local), como em stats::daily_activity: --summary e --compare concordam no dia
+    pub fn from_events_in(events: &[GitHubEvent], offset_seconds: i64) -> Self {
+        let mut by_type: Vec<TypeCount> = Vec::new();
+        let mut repos: Vec<(String, usize)> = Vec::new();
+        let mut unrecognized_types: Vec<String> = Vec::new();
+
+        for event in events {
+            let kind_name = event.kind.name();
+            if event.is_unknown() && !unrecognized_types.iter().any(|name| name == kind_name) {
+                unrecognized_types.push(kind_name.to_string());
+            }
+
+            let (singular, plural) = event.payload.summary_labels(&event.kind);
+
+            // Um push agregado pelo --collapse conta como todos os pushes que representa
+            let amount = match event.payload {
+                EventPayload::Push { push_count, .. } => push_count,
+                _ => 1,
+            };
+
+            // Busca linear, como em display::group_events: poucas chaves e ordem preservada
+            match by_type.iter_mut().find(|entry| entry.plural == plural) {
+                Some(entry) => entry.count += amount,
+                None => by_type.push(TypeCount { singular, plural, count: amount }),
+            }
+
+            match repos.iter_mut().find(|(name, _)| *name == event.repo_name) {
+                Some((_, count)) => *count += 1,
+                None => repos.push((event.repo_name.clone(), 1)),
+            }
+        }
+
+        // CONCEITO: Ordenação estável
+        // sort_by_key (diferente de sort_unstable_by_key) mantém a ordem original dos empates
+        // std::cmp::Reverse inverte a comparação: maiores primeiro
+        by_type.sort_by_key(|entry| std::cmp::Reverse(entry.count));
+        let mut all_repos: Vec<String> = repos.iter().map(|(name, _)| name.clone()).collect();
+        all_repos.sort();
+
+        repos.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        repos.truncate(SUMMARY_TOP_REPOS);
+
+        // CONCEITO: filter_map + min/max
+        // Ignora eventos sem horário; min() de um iterator vazio é None
+        let timestamps = || events.iter().filter_map(|event| event.created_at);
+
+        let busiest_day = stats::busiest_day(&stats::events_per_day(events, offset_seconds));
+
+        ActivitySummary {
+            total_events: events.len(),
+            by_type,
+            top_repos: repos,
+            first_event: timestamps().min(),
+            last_event: timestamps().max(),
+            unrecognized_events: events.iter().filter(|event| event.is_unknown()).count(),
+            unrecognized_types,
+            repos: all_repos,
+            busiest_day,
+        }
+    }
+
+    /// Compara este resumo (o primeiro usuário) com `other` (o segundo)
+    ///
+    /// Empates no total de um tipo mantêm a ordem em que ele aparece nos resumos,
+    /// primeiro os tipos deste, depois os que só o outro tem
+    pub fn compare(self, other: ActivitySummary) -> SummaryComparison {
+        let mut by_type: Vec<TypeComparison> = self
+            .by_type
+            .iter()
+            .map(|entry| TypeComparison { label: entry.plural.clone(), left: entry.count, right: 0 })
+            .collect();
+
+        for entry in &other.by_type {
+            match by_type.iter_mut().find(|row| row.label == entry.plural) {
+                Some(row) => row.right = entry.count,
+                None => by_type.push(TypeComparison { label: entry.plural.clone(), left: 0, right: entry.count }),
+            }
+        }
+        by_type.sort_by_key(|row| std::cmp::Reverse(row.left + row.right));
+
+        // Os dois já estão em ordem alfabética, então o resultado também fica
+        let shared_repos = self
+            .repos
+            .iter()
+            .filter(|repo| other.repos.contains(repo))
+            .cloned()
+            .collect();
+
+        SummaryComparison { left: self, right: other, by_type, shared_repos }
+    }
+}
+
+// Plural dos ref_type que o GitHub usa em CreateEvent/DeleteEvent
+fn plural_ref_type(ref_type: &str) -> String {
+    match ref_type {
+        "branch" => "branches".to_string(),
+        "repository" => "repositories".to_string(),
+        other => format!("{}s", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(repo: &str, payload: EventPayload, created_at: Option<i64>) -> GitHubEvent {
+        GitHubEvent::new(
+            EventKind::Other("SomeEvent".to_string()),
+            repo.to_string(),
+            payload,
+            created_at.map(Timestamp::from_epoch_seconds),
         )
     }
+
+    fn push() -> EventPayload {
+        EventPayload::Push { commit_count: 1, distinct_count: 1, push_count: 1, branch: None, commits: Vec::new() }
+    }
+
+    fn pr(action: &str) -> EventPayload {
+        EventPayload::PullRequestEvent {
+            action: action.to_string(),
+            number: None,
+            title: None,
+            merged: false,
+        }
+    }
+
+    // Resumo como ("rótulo", contagem) para comparar em uma linha
+    fn type_counts(summary: &ActivitySummary) -> Vec<(&str, usize)> {
+        summary.by_type.iter().map(|entry| (entry.label(), entry.count)).collect()
+    }
+
+    #[test]
+    fn test_summary_counts_by_type_and_repo() {
+        let events = vec![
+            event("a/one", EventPayload::WatchEvent, Some(500)),
+            event("a/two", push(), Some(400)),
+            event("a/two", push(), Some(300)),
+            event("a/one", pr("opened"), Some(200)),
+            event("a/three", push(), Some(100)),
+        ];
+
+        let summary = ActivitySummary::from_events(&events);
+
+        assert_eq!(summary.total_events, 5);
+        assert_eq!(type_counts(&summary), vec![("pushes", 3), ("star", 1), ("PR opened", 1)]);
+        assert_eq!(
+            summary.top_repos,
+            vec![("a/one".to_string(), 2), ("a/two".to_string(), 2), ("a/three".to_string(), 1)]
+        );
+        assert_eq!(summary.first_event, Some(Timestamp::from_epoch_seconds(100)));
+        assert_eq!(summary.last_event, Some(Timestamp::from_epoch_seconds(500)));
+    }
+
+    #[test]
+    fn test_summary_keeps_top_five_repos() {
+        // r0 tem 7 eventos, r1 tem 6, ..., r6 tem 1
+        let events: Vec<GitHubEvent> = (0..7)
+            .flat_map(|repo| (0..7 - repo).map(move |_| event(&format!("o/r{}", repo), push(), None)))
+            .collect();
+
+        let summary = ActivitySummary::from_events(&events);
+        let names: Vec<&str> = summary.top_repos.iter().map(|(name, _)| name.as_str()).collect();
+
+        assert_eq!(names, vec!["o/r0", "o/r1", "o/r2", "o/r3", "o/r4"]);
+        assert_eq!(summary.first_event, None);
+    }
+
+    #[test]
+    fn test_summary_counts_collapsed_pushes() {
+        let events = vec![event("a/x", EventPayload::Push { commit_count: 9, distinct_count: 9, push_count: 4, branch: None, commits: Vec::new() }, None)];
+        let summary = ActivitySummary::from_events(&events);
+
+        assert_eq!(summary.total_events, 1);
+        assert_eq!(type_counts(&summary), vec![("pushes", 4)]);
+    }
+
+    #[test]
+    fn test_payload_action() {
+        let issue = EventPayload::IssuesEvent { action: "opened".to_string(), number: None, title: None };
+        assert_eq!(issue.action(), Some("opened"));
+        // O merge não muda a action
+        assert_eq!(pr("closed").action(), Some("closed"));
+        let wiki = EventPayload::Gollum { pages_edited: 1, first_page: None, first_page_action: Some("edited".to_string()) };
+        assert_eq!(wiki.action(), Some("edited"));
+
+        assert_eq!(push().action(), None);
+        assert_eq!(EventPayload::WatchEvent.action(), None);
+        assert_eq!(EventPayload::Gollum { pages_edited: 0, first_page: None, first_page_action: None }.action(), None);
+    }
+
+    // Uma variante de cada, com o que category(), is_comment(), is_code_change() e
+    // action() devem responder
+    fn every_payload() -> Vec<(EventPayload, EventCategory, bool, bool, Option<&'static str>)> {
+        let text = |value: &str| value.to_string();
+        vec![
+            (push(), EventCategory::Code, false, true, None),
+            (EventPayload::CreateEvent { ref_type: text("branch"), ref_name: Some(text("x")) }, EventCategory::Code, false, false, None),
+            (EventPayload::DeleteEvent { ref_type: text("tag"), ref_name: None }, EventCategory::Code, false, false, None),
+            (
+                EventPayload::IssuesEvent { action: text("closed"), number: Some(1), title: None },
+                EventCategory::Issues,
+                false,
+                false,
+                Some("closed"),
+            ),
+            (EventPayload::IssueCommentEvent, EventCategory::Issues, true, false, None),
+            (pr("opened"), EventCategory::Review, false, false, Some("opened")),
+            (
+                EventPayload::PullRequestEvent { action: text("closed"), number: None, title: None, merged: true },
+                EventCategory::Review,
+                false,
+                true,
+                Some("closed"),
+            ),
+            (
+                EventPayload::PullRequestReview { action: text("created"), state: text("approved") },
+                EventCategory::Review,
+                false,
+                false,
+                Some("created"),
+            ),
+            (EventPayload::PullRequestReviewCommentEvent, EventCategory::Review, true, false, None),
+            (EventPayload::CommitCommentEvent, EventCategory::Review, true, false, None),
+            (EventPayload::WatchEvent, EventCategory::Social, false, false, None),
+            (EventPayload::ForkEvent { forkee: None }, EventCategory::Social, false, false, None),
+            (
+                EventPayload::Member { action: text("added"), member_login: None },
+                EventCategory::Social,
+                false,
+                false,
+                Some("added"),
+            ),
+            (
+                EventPayload::ReleaseEvent { action: text("published"), tag: None, name: None, draft: false },
+                EventCategory::Release,
+                false,
+                false,
+                Some("published"),
+            ),
+            (
+                EventPayload::Gollum { pages_edited: 1, first_page: None, first_page_action: Some(text("created")) },
+                EventCategory::Other,
+                false,
+                false,
+                Some("created"),
+            ),
+            (EventPayload::Public, EventCategory::Other, false, false, None),
+            (EventPayload::Unknown { raw_payload: None }, EventCategory::Other, false, false, None),
+        ]
+    }
+
+    // O número da variante; sem braço "_", uma variante nova obriga a mexer aqui
+    // (e o teste abaixo, a colocá-la em every_payload)
+    fn variant(payload: &EventPayload) -> usize {
+        match payload {
+            EventPayload::Push { .. } => 0,
+            EventPayload::IssuesEvent { .. } => 1,
+            EventPayload::PullRequestEvent { .. } => 2,
+            EventPayload::WatchEvent => 3,
+            EventPayload::ForkEvent { .. } => 4,
+            EventPayload::CreateEvent { .. } => 5,
+            EventPayload::DeleteEvent { .. } => 6,
+            EventPayload::ReleaseEvent { .. } => 7,
+            EventPayload::IssueCommentEvent => 8,
+            EventPayload::PullRequestReviewCommentEvent => 9,
+            EventPayload::CommitCommentEvent => 10,
+            EventPayload::PullRequestReview { .. } => 11,
+            EventPayload::Member { .. } => 12,
+            EventPayload::Public => 13,
+            EventPayload::Gollum { .. } => 14,
+            EventPayload::Unknown { .. } => 15,
+        }
+    }
+
+    #[test]
+    fn test_every_payload_variant_is_covered() {
+        let mut seen: Vec<usize> = every_payload().iter().map(|(payload, ..)| variant(payload)).collect();
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen, (0..=15).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_payload_predicates_for_each_variant() {
+        for (payload, category, comment, code_change, action) in every_payload() {
+            assert_eq!(payload.category(), category, "{:?}", payload);
+            assert_eq!(payload.is_comment(), comment, "{:?}", payload);
+            assert_eq!(payload.is_code_change(), code_change, "{:?}", payload);
+            assert_eq!(payload.action(), action, "{:?}", payload);
+        }
+    }
+
+    #[test]
+    fn test_every_category_has_an_event_and_a_name() {
+        for category in EventCategory::ALL {
+            assert!(every_payload().iter().any(|(_, expected, ..)| *expected == category), "{}", category);
+            assert_eq!(EventCategory::from_name(category.name()), Some(category));
+        }
+        assert_eq!(EventCategory::from_name("Code"), None);
+        assert_eq!(EventCategory::from_name("pushes"), None);
+    }
+
+    #[test]
+    fn test_summary_labels_pluralize() {
+        let create = EventPayload::CreateEvent { ref_type: "branch".to_string(), ref_name: None };
+        assert_eq!(
+            create.summary_labels(&EventKind::Create),
+            ("branch created".to_string(), "branches created".to_string())
+        );
+        let merged = EventPayload::PullRequestEvent {
+            action: "closed".to_string(),
+            number: None,
+            title: None,
+            merged: true,
+        };
+        assert_eq!(merged.summary_labels(&EventKind::PullRequest).1, "PRs merged");
+        let sponsorship = EventKind::Other("SponsorshipEvent".to_string());
+        assert_eq!(
+            EventPayload::Unknown { raw_payload: None }.summary_labels(&sponsorship),
+            ("SponsorshipEvent".to_string(), "SponsorshipEvent".to_string())
+        );
+    }
+
+    #[test]
+    fn test_summary_lists_unrecognized_types() {
+        let unknown = |name: &str| GitHubEvent {
+            kind: EventKind::Other(name.to_string()),
+            ..event("a/b", EventPayload::Unknown { raw_payload: None }, None)
+        };
+        let events = vec![
+            unknown("SponsorshipEvent"),
+            event("a/b", push(), None),
+            unknown("DiscussionEvent"),
+            unknown("SponsorshipEvent"),
+        ];
+
+        assert!(events[0].is_unknown());
+        assert!(!events[1].is_unknown());
+
+        let summary = ActivitySummary::from_events(&events);
+        assert_eq!(summary.unrecognized_events, 3);
+        assert_eq!(summary.unrecognized_types, vec!["SponsorshipEvent", "DiscussionEvent"]);
+    }
+
+    #[test]
+    fn test_event_kind_round_trips_through_its_name() {
+        for name in ["PushEvent", "PullRequestReviewEvent", "GollumEvent", "SponsorshipEvent"] {
+            let kind: EventKind = name.parse().unwrap();
+            assert_eq!(kind.to_string(), name);
+        }
+
+        assert_eq!("WatchEvent".parse(), Ok(EventKind::Watch));
+        // Tipos novos não são erro: viram Other, com o nome original
+        assert_eq!("DiscussionEvent".parse(), Ok(EventKind::Other("DiscussionEvent".to_string())));
+        // A comparação é exata, como no JSON da API
+        assert_eq!("pushevent".parse(), Ok(EventKind::Other("pushevent".to_string())));
+    }
+
+    #[test]
+    fn test_summary_of_empty_list() {
+        let summary = ActivitySummary::from_events(&[]);
+        assert_eq!(summary.total_events, 0);
+        assert!(summary.by_type.is_empty());
+        assert!(summary.top_repos.is_empty());
+        assert_eq!(summary.last_event, None);
+    }
+
+    #[test]
+    fn test_summary_busiest_day_and_all_repos() {
+        const DAY: i64 = 86_400;
+        let events = vec![
+            event("c/three", push(), Some(2 * DAY + 10)),
+            event("a/one", push(), Some(DAY + 20)),
+            event("b/two", push(), Some(DAY + 10)),
+            event("a/one", push(), Some(20)),
+            event("a/one", push(), Some(10)),
+            event("d/four", push(), None),
+        ];
+
+        let summary = ActivitySummary::from_events(&events);
+
+        // 1970-01-01 e 1970-01-02 empatam com 2: fica o mais antigo
+        assert_eq!(summary.busiest_day, Some(("1970-01-01".to_string(), 2)));
+        assert_eq!(summary.repos, vec!["a/one", "b/two", "c/three", "d/four"]);
+        assert_eq!(ActivitySummary::from_events(&[]).busiest_day, None);
+
+        // Uma hora a oeste de UTC, os dois primeiros caem em 31/12; o dia é o mesmo
+        // que o das sequências (stats::daily_activity) no mesmo fuso
+        let west = ActivitySummary::from_events_in(&events, -3600);
+        assert_eq!(west.busiest_day, Some(("1969-12-31".to_string(), 2)));
+        let daily = stats::daily_activity(&events, -3600, Timestamp::from_epoch_seconds(3 * DAY));
+        assert_eq!(west.busiest_day, daily.busiest_day);
+    }
+
+    #[test]
+    fn test_compare_summaries() {
+        let left = ActivitySummary::from_events(&[
+            event("a/one", push(), None),
+            event("a/one", push(), None),
+            event("a/two", EventPayload::WatchEvent, None),
+        ]);
+        let right = ActivitySummary::from_events(&[
+            event("a/two", pr("opened"), None),
+            event("a/three", EventPayload::WatchEvent, None),
+            event("a/three", EventPayload::WatchEvent, None),
+        ]);
+
+        let comparison = left.compare(right);
+
+        let rows: Vec<(&str, usize, usize)> = comparison
+            .by_type
+            .iter()
+            .map(|row| (row.label.as_str(), row.left, row.right))
+            .collect();
+        // stars (1 + 2) vem antes de pushes (2 + 0); PRs opened só existe à direita
+        assert_eq!(rows, vec![("stars", 1, 2), ("pushes", 2, 0), ("PRs opened", 0, 1)]);
+        assert_eq!(comparison.shared_repos, vec!["a/two"]);
+        assert_eq!(comparison.left.total_events, 3);
+    }
+
+    #[test]
+    fn test_repo_url() {
+        let star = event("rust-lang/rust", EventPayload::WatchEvent, None);
+        assert_eq!(star.repo_url(), "https://github.com/rust-lang/rust");
+    }
+
+    #[test]
+    fn test_item_url_for_each_payload() {
+        let item_url = |payload| event("user/repo", payload, None).item_url();
+
+        let issue = |number| EventPayload::IssuesEvent { action: "opened".to_string(), number, title: None };
+        assert_eq!(item_url(issue(Some(42))).as_deref(), Some("https://github.com/user/repo/issues/42"));
+        assert_eq!(item_url(issue(None)), None);
+
+        let pull = |number| EventPayload::PullRequestEvent {
+            action: "closed".to_string(),
+            number,
+            title: None,
+            merged: true,
+        };
+        assert_eq!(item_url(pull(Some(1347))).as_deref(), Some("https://github.com/user/repo/pull/1347"));
+        assert_eq!(item_url(pull(None)), None);
+
+        let release = |tag: Option<&str>| EventPayload::ReleaseEvent {
+            action: "published".to_string(),
+            tag: tag.map(str::to_string),
+            name: None,
+            draft: false,
+        };
+        assert_eq!(item_url(release(Some("v1.0"))).as_deref(), Some("https://github.com/user/repo/releases/tag/v1.0"));
+        assert_eq!(
+            item_url(release(Some("v1.0+build #2"))).as_deref(),
+            Some("https://github.com/user/repo/releases/tag/v1.0%2Bbuild%20%232")
+        );
+        assert_eq!(item_url(release(None)), None);
+
+        let fork = |forkee: Option<&str>| EventPayload::ForkEvent { forkee: forkee.map(str::to_string) };
+        assert_eq!(item_url(fork(Some("me/repo"))).as_deref(), Some("https://github.com/me/repo"));
+        assert_eq!(item_url(fork(None)), None);
+
+        // O primeiro commit do push; sem commits listados, nenhum
+        let pushed = |shas: &[&str]| EventPayload::Push {
+            commit_count: shas.len(),
+            distinct_count: shas.len(),
+            push_count: 1,
+            branch: Some("main".to_string()),
+            commits: shas.iter().map(|sha| CommitSummary { sha: sha.to_string(), message: "x".to_string() }).collect(),
+        };
+        assert_eq!(item_url(pushed(&["abc123", "def456"])).as_deref(), Some("https://github.com/user/repo/commit/abc123"));
+        assert_eq!(item_url(pushed(&[""])), None);
+
+        let created = |ref_type: &str, ref_name: Option<&str>| EventPayload::CreateEvent {
+            ref_type: ref_type.to_string(),
+            ref_name: ref_name.map(str::to_string),
+        };
+        assert_eq!(
+            item_url(created("branch", Some("feature/x y"))).as_deref(),
+            Some("https://github.com/user/repo/tree/feature/x%20y")
+        );
+        assert_eq!(item_url(created("tag", Some("v1.0"))).as_deref(), Some("https://github.com/user/repo/tree/v1.0"));
+        assert_eq!(item_url(created("repository", None)), None);
+
+        // Os outros eventos não apontam para uma página própria: só o repositório
+        let without_page = vec![
+            push(),
+            EventPayload::WatchEvent,
+            EventPayload::DeleteEvent { ref_type: "tag".to_string(), ref_name: Some("v1".to_string()) },
+            EventPayload::IssueCommentEvent,
+            EventPayload::PullRequestReviewCommentEvent,
+            EventPayload::CommitCommentEvent,
+            EventPayload::PullRequestReview { action: "created".to_string(), state: "approved".to_string() },
+            EventPayload::Member { action: "added".to_string(), member_login: Some("octocat".to_string()) },
+            EventPayload::Public,
+            EventPayload::Gollum { pages_edited: 1, first_page: Some("Home".to_string()), first_page_action: None },
+            EventPayload::Unknown { raw_payload: None },
+        ];
+        for payload in without_page {
+            assert_eq!(item_url(payload.clone()), None, "{:?}", payload);
+        }
+    }
+
+    #[test]
+    fn test_html_url_falls_back_to_the_repo() {
+        let push = event("user/repo", push(), None);
+        assert_eq!(push.html_url().as_deref(), Some("https://github.com/user/repo"));
+
+        let issue = event(
+            "user/repo",
+            EventPayload::IssuesEvent { action: "opened".to_string(), number: Some(7), title: None },
+            None,
+        );
+        assert_eq!(issue.html_url().as_deref(), Some("https://github.com/user/repo/issues/7"));
+
+        // Sem owner, nem a página da issue seria a certa
+        let orphan = GitHubEvent { repo_name: "repo".to_string(), ..issue };
+        assert_eq!(orphan.html_url(), None);
+    }
+
+    #[test]
+    fn test_split_repo_name() {
+        assert_eq!(split_repo_name("rust-lang/rust"), Some(("rust-lang", "rust")));
+        assert_eq!(split_repo_name("a/b/c"), Some(("a", "b/c")));
+        assert_eq!(split_repo_name("linux"), None);
+        assert_eq!(split_repo_name("/linux"), None);
+        assert_eq!(split_repo_name("torvalds/"), None);
+        assert_eq!(split_repo_name(""), None);
+
+        let named = |repo: &str| event(repo, push(), None);
+        assert_eq!(named("torvalds/linux").owner_and_name(), Some(("torvalds", "linux")));
+        assert_eq!(named("linux").owner_and_name(), None);
+    }
 }
@@ -0,0 +1,289 @@
+//! Este módulo avisa, com uma notificação na área de trabalho, que há eventos
+//! novos (--new --notify)
+//!
+//! Cada sistema tem seu jeito de mostrar uma notificação: `notify-send` no Linux
+//! (e outros Unix com um servidor de notificações) e `osascript` no macOS. Nos
+//! outros, o aviso é o sino do terminal (o caractere BEL no stderr)
+//!
+//! ```text
+//! torvalds: 3 new events
+//! 2 pushes, 1 PR merged
+//! in torvalds/linux and 1 more repository
+//! ```
+//!
+//! Quem mostra a notificação é um [`Notifier`]: os testes usam um que só anota as mensagens
+
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use github_activity::models::{ActivitySummary, GitHubEvent};
+use github_activity::stats;
+
+// Limites do texto, em caracteres: as notificações cortam (ou quebram) textos longos
+// de formas diferentes em cada sistema, então o corte é feito aqui
+const TITLE_MAX_CHARS: usize = 80;
+const LINE_MAX_CHARS: usize = 120;
+// Quantos tipos de evento e quantos repositórios aparecem antes do "and N more"
+const MAX_TYPES: usize = 3;
+const MAX_REPOS: usize = 2;
+
+// CONCEITO: Traits como ponto de extensão
+// Como em browser.rs: notify_new_events() só monta a mensagem; o programa passa um
+// SystemNotifier e os testes, um que guarda o que seria mostrado
+pub trait Notifier {
+    // O nome do programa, para as mensagens de --verbose
+    fn name(&self) -> &str;
+    fn notify(&mut self, title: &str, body: &str) -> io::Result<()>;
+}
+
+// A notificação do sistema: o programa de cada plataforma
+pub enum SystemNotifier {
+    NotifySend,
+    Osascript,
+    // Sem um programa de notificações: só o sino do terminal
+    Bell,
+}
+
+impl SystemNotifier {
+    pub fn new() -> SystemNotifier {
+        if cfg!(target_os = "macos") {
+            SystemNotifier::Osascript
+        } else if cfg!(all(unix, not(target_os = "android"))) {
+            SystemNotifier::NotifySend
+        } else {
+            SystemNotifier::Bell
+        }
+    }
+}
+
+impl Notifier for SystemNotifier {
+    fn name(&self) -> &str {
+        match self {
+            SystemNotifier::NotifySend => "notify-send",
+            SystemNotifier::Osascript => "osascript",
+            SystemNotifier::Bell => "the terminal bell",
+        }
+    }
+
+    fn notify(&mut self, title: &str, body: &str) -> io::Result<()> {
+        let mut command = match self {
+            SystemNotifier::NotifySend => {
+                let mut command = Command::new("notify-send");
+                command.args(["--app-name=github-activity", title, body]);
+                command
+            }
+            // O texto vai como argumento do script (argv), e não dentro dele:
+            // assim aspas num nome de repositório não precisam de escape
+            SystemNotifier::Osascript => {
+                let mut command = Command::new("osascript");
+                command.args([
+                    "-e",
+                    "on run argv",
+                    "-e",
+                    "display notification (item 2 of argv) with title (item 1 of argv)",
+                    "-e",
+                    "end run",
+                    title,
+                    body,
+                ]);
+                command
+            }
+            SystemNotifier::Bell => {
+                let mut stderr = io::stderr();
+                stderr.write_all(b"\x07")?;
+                return stderr.flush();
+            }
+        };
+
+        // Diferente do navegador, esperamos: os dois programas terminam logo,
+        // e só assim uma falha (sem servidor de notificações, por exemplo) aparece
+        let status = command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!("exited with {}", status)))
+        }
+    }
+}
+
+// Mostra uma notificação resumindo `events` (os eventos novos de `target`)
+// Sem eventos não há o que avisar; o erro do Notifier volta para quem chamou
+pub fn notify_new_events(target: &str, events: &[GitHubEvent], notifier: &mut impl Notifier) -> io::Result<()> {
+    match new_events_message(target, events) {
+        Some((title, body)) => notifier.notify(&title, &body),
+        None => Ok(()),
+    }
+}
+
+// O título ("torvalds: 3 new events") e o corpo (tipos e repositórios) da notificação
+fn new_events_message(target: &str, events: &[GitHubEvent]) -> Option<(String, String)> {
+    if events.is_empty() {
+        return None;
+    }
+
+    let plural = if events.len() == 1 { "" } else { "s" };
+    let title = format!("{}: {} new event{}", target, events.len(), plural);
+
+    // "2 pushes, 1 PR merged and 2 more types"
+    let summary = ActivitySummary::from_events(events);
+    let types: Vec<String> =
+        summary.by_type.iter().take(MAX_TYPES).map(|entry| format!("{} {}", entry.count, entry.label())).collect();
+    let types = with_more(types, summary.by_type.len(), "type");
+
+    // "in torvalds/linux and 1 more repository" (os com mais eventos primeiro)
+    let repos = stats::repo_activity(events);
+    let names: Vec<String> = repos.iter().take(MAX_REPOS).map(|repo| repo.repo_name.clone()).collect();
+    let more = repos.len().saturating_sub(MAX_REPOS);
+    let in_repos = match more {
+        0 => format!("in {}", names.join(", ")),
+        1 => format!("in {} and 1 more repository", names.join(", ")),
+        more => format!("in {} and {} more repositories", names.join(", "), more),
+    };
+
+    // Cada linha é limpa separadamente: a quebra entre elas é a única que fica
+    let body = format!("{}\n{}", clean(&types, LINE_MAX_CHARS), clean(&in_repos, LINE_MAX_CHARS));
+    Some((clean(&title, TITLE_MAX_CHARS), body))
+}
+
+// Junta os itens com ", " e acrescenta "and N more <noun>s" se `total` passar deles
+fn with_more(items: Vec<String>, total: usize, noun: &str) -> String {
+    match total.saturating_sub(items.len()) {
+        0 => items.join(", "),
+        1 => format!("{} and 1 more {}", items.join(", "), noun),
+        more => format!("{} and {} more {}s", items.join(", "), more, noun),
+    }
+}
+
+// CONCEITO: Dados de fora são texto não confiável
+// Nomes de repositórios e tipos de evento vêm da API (ou de um arquivo); um caractere
+// de controle neles poderia quebrar a notificação (ou, no terminal, mudar as cores).
+// Cada um vira um espaço, e o texto longo demais é cortado com "…"
+fn clean(text: &str, max_chars: usize) -> String {
+    let text: String = text.chars().map(|c| if c.is_control() { ' ' } else { c }).collect();
+    if text.chars().count() <= max_chars {
+        return text;
+    }
+    let short: String = text.chars().take(max_chars - 1).collect();
+    format!("{}…", short.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use github_activity::models::{EventKind, EventPayload};
+
+    // Guarda as notificações em vez de mostrá-las; com `failing`, finge que o programa falhou
+    #[derive(Default)]
+    struct FakeNotifier {
+        shown: Vec<(String, String)>,
+        failing: bool,
+    }
+
+    impl Notifier for FakeNotifier {
+        fn name(&self) -> &str {
+            "fake-notify"
+        }
+
+        fn notify(&mut self, title: &str, body: &str) -> io::Result<()> {
+            if self.failing {
+                return Err(io::ErrorKind::NotFound.into());
+            }
+            self.shown.push((title.to_string(), body.to_string()));
+            Ok(())
+        }
+    }
+
+    fn push(repo: &str) -> GitHubEvent {
+        GitHubEvent::new(
+            EventKind::Push,
+            repo.to_string(),
+            EventPayload::Push { commit_count: 1, distinct_count: 1, push_count: 1, branch: None, commits: Vec::new() },
+            None,
+        )
+    }
+
+    fn star(repo: &str) -> GitHubEvent {
+        GitHubEvent::new(EventKind::Watch, repo.to_string(), EventPayload::WatchEvent, None)
+    }
+
+    fn other(kind: &str, repo: &str) -> GitHubEvent {
+        GitHubEvent::new(
+            EventKind::Other(kind.to_string()),
+            repo.to_string(),
+            EventPayload::Unknown { raw_payload: None },
+            None,
+        )
+    }
+
+    #[test]
+    fn test_summarizes_the_new_events() {
+        let mut notifier = FakeNotifier::default();
+        let events = vec![push("torvalds/linux"), push("torvalds/linux"), star("torvalds/subsurface")];
+
+        notify_new_events("torvalds", &events, &mut notifier).unwrap();
+        assert_eq!(
+            notifier.shown,
+            vec![(
+                "torvalds: 3 new events".to_string(),
+                "2 pushes, 1 star\nin torvalds/linux, torvalds/subsurface".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_long_lists_end_in_and_more() {
+        let (title, body) = new_events_message(
+            "octocat",
+            &[
+                push("a/one"),
+                push("a/one"),
+                star("a/two"),
+                other("SponsorshipEvent", "a/three"),
+                other("MemberEvent", "a/four"),
+                other("PublicEvent", "a/five"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(title, "octocat: 6 new events");
+        let lines: Vec<&str> = body.lines().collect();
+        assert!(lines[0].starts_with("2 pushes, ") && lines[0].ends_with(" and 2 more types"), "{}", lines[0]);
+        assert_eq!(lines[1], "in a/one, a/five and 3 more repositories");
+
+        let (title, _) = new_events_message("octocat", &[star("a/b")]).unwrap();
+        assert_eq!(title, "octocat: 1 new event");
+    }
+
+    #[test]
+    fn test_control_characters_never_reach_the_notification() {
+        let (title, body) =
+            new_events_message("evil\x1b[31m", &[other("Odd\u{7}Event", "a/\x1b]8;;x\x07b\nc")]).unwrap();
+        assert!(!title.chars().any(char::is_control), "{:?}", title);
+        // A quebra entre as duas linhas é a única
+        assert_eq!(body.matches('\n').count(), 1, "{:?}", body);
+        assert!(!body.replace('\n', "").chars().any(char::is_control), "{:?}", body);
+    }
+
+    #[test]
+    fn test_long_text_is_truncated() {
+        let repo = format!("a/{}", "x".repeat(300));
+        let (title, body) = new_events_message(&"u".repeat(200), &[star(&repo)]).unwrap();
+        assert_eq!(title.chars().count(), TITLE_MAX_CHARS);
+        assert!(title.ends_with('…'));
+        assert!(body.lines().all(|line| line.chars().count() <= LINE_MAX_CHARS), "{:?}", body);
+        assert_eq!(clean("short", 10), "short");
+    }
+
+    #[test]
+    fn test_nothing_new_means_no_notification() {
+        let mut notifier = FakeNotifier::default();
+        notify_new_events("torvalds", &[], &mut notifier).unwrap();
+        assert!(notifier.shown.is_empty());
+    }
+
+    #[test]
+    fn test_failures_are_returned() {
+        let mut notifier = FakeNotifier { failing: true, ..Default::default() };
+        let error = notify_new_events("torvalds", &[star("a/b")], &mut notifier).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+}
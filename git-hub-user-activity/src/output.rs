@@ -0,0 +1,154 @@
+//! Este módulo grava o arquivo de --output sem nunca deixar um relatório pela metade
+//!
+//! A escrita vai para um arquivo temporário no MESMO diretório do destino, e só no fim
+//! ele é renomeado para o nome final. Um rename dentro de um sistema de arquivos é
+//! atômico: quem abrir o arquivo vê o relatório antigo inteiro ou o novo inteiro,
+//! nunca um pedaço. Se algo falhar antes, o temporário é apagado e o antigo fica intacto
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::error::ActivityError;
+
+/// Um arquivo que só aparece no caminho final quando está completo
+///
+/// Escreva nele como em qualquer [`Write`] e chame [`AtomicFile::commit`] no fim.
+/// Descartado sem commit (por um erro no meio do caminho), ele apaga o temporário
+#[derive(Debug)]
+pub struct AtomicFile {
+    path: PathBuf,
+    temp_path: PathBuf,
+    // Option: commit() precisa tirar o arquivo daqui para fechá-lo antes do rename
+    writer: Option<BufWriter<File>>,
+}
+
+impl AtomicFile {
+    /// Cria o temporário ao lado de `path` (".report.txt.tmp-<pid>")
+    /// Um diretório inexistente ou sem permissão de escrita falha já aqui
+    pub fn create(path: impl AsRef<Path>) -> Result<AtomicFile, ActivityError> {
+        let path = path.as_ref().to_path_buf();
+        let temp_path = temp_path_for(&path);
+
+        let file = File::create(&temp_path).map_err(|e| io_error(&path, e))?;
+        Ok(AtomicFile { path, temp_path, writer: Some(BufWriter::new(file)) })
+    }
+
+    /// Termina a escrita e põe o arquivo no lugar, substituindo o anterior (se havia um)
+    pub fn commit(mut self) -> Result<(), ActivityError> {
+        let writer = match self.writer.take() {
+            Some(writer) => writer,
+            None => unreachable!("commit() consumes the AtomicFile"),
+        };
+
+        // into_inner() descarrega o buffer; sync_all() garante que os dados chegaram
+        // ao disco ANTES do rename, senão uma queda de energia poderia deixar um arquivo vazio
+        let file = writer.into_inner().map_err(|e| io_error(&self.path, e.into_error()))?;
+        file.sync_all().map_err(|e| io_error(&self.path, e))?;
+        drop(file);
+
+        fs::rename(&self.temp_path, &self.path).map_err(|e| io_error(&self.path, e))
+    }
+
+    fn writer(&mut self) -> &mut BufWriter<File> {
+        match self.writer.as_mut() {
+            Some(writer) => writer,
+            None => unreachable!("commit() consumes the AtomicFile"),
+        }
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer().flush()
+    }
+}
+
+// CONCEITO: Drop
+// drop() roda quando o valor sai de escopo, inclusive quando um ? retorna mais cedo
+// Depois de um commit bem-sucedido o temporário já não existe, e remove_file só falha calado
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        // Fecha o arquivo antes de apagá-lo (no Windows, um arquivo aberto não pode ser apagado)
+        self.writer.take();
+        let _ = fs::remove_file(&self.temp_path);
+    }
+}
+
+// O temporário fica no mesmo diretório do destino: um rename entre
+// sistemas de arquivos diferentes não seria atômico (nem sempre é possível)
+// O ponto na frente esconde o arquivo; o pid evita colisão entre execuções simultâneas
+fn temp_path_for(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    path.with_file_name(format!(".{}.tmp-{}", name, std::process::id()))
+}
+
+// Os erros citam o caminho que o usuário digitou, nunca o do temporário
+fn io_error(path: &Path, error: io::Error) -> ActivityError {
+    ActivityError::io(path.display().to_string(), error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("github-activity-output-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_commit_replaces_an_existing_file() {
+        let dir = test_dir("overwrite");
+        let path = dir.join("report.txt");
+        fs::write(&path, "old report, much longer than the new one\n").unwrap();
+
+        let mut file = AtomicFile::create(&path).unwrap();
+        file.write_all(b"new report\n").unwrap();
+        // Até o commit, o relatório antigo continua lá
+        assert!(fs::read_to_string(&path).unwrap().starts_with("old report"));
+        file.commit().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new report\n");
+        // Nenhum temporário sobrando
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_dropping_without_commit_keeps_the_old_file() {
+        let dir = test_dir("abort");
+        let path = dir.join("report.txt");
+        fs::write(&path, "old report\n").unwrap();
+
+        let mut file = AtomicFile::create(&path).unwrap();
+        file.write_all(b"half of a new rep").unwrap();
+        drop(file);
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old report\n");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unwritable_directory_names_the_path() {
+        // Um "diretório" que na verdade é um arquivo: ninguém escreve dentro dele,
+        // nem o root (que ignoraria permissões de um diretório de verdade)
+        let dir = test_dir("unwritable");
+        let not_a_dir = dir.join("file");
+        fs::write(&not_a_dir, "").unwrap();
+        let path = not_a_dir.join("report.txt");
+
+        let error = AtomicFile::create(&path).unwrap_err();
+
+        assert!(matches!(error, ActivityError::IoError { .. }), "{:?}", error);
+        assert!(error.to_string().contains(&path.display().to_string()), "{}", error);
+        assert!(!error.to_string().contains(".tmp-"), "{}", error);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
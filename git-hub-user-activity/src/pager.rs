@@ -0,0 +1,276 @@
+//! Este módulo mostra uma saída longa uma tela por vez (--pager), como o `more`
+//!
+//! A saída inteira é montada antes (na memória) e depois entregue aqui.
+//! Entre uma tela e outra aparece um aviso e o programa espera UMA tecla:
+//! espaço mostra a próxima tela, Enter mais uma linha, q (ou Ctrl-C) encerra
+//!
+//! Para ler uma tecla sem esperar o Enter, o terminal precisa sair do modo
+//! "canônico" (que junta a linha inteira antes de entregá-la). Sem crates como
+//! termios/crossterm, pedimos isso ao comando `stty` (POSIX), como já fazemos
+//! com o `date` para o fuso local. Em outras plataformas, as teclas são lidas
+//! linha a linha (cada resposta termina com Enter)
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+
+use github_activity::display::Style;
+
+pub const PROMPT: &str = "-- More (q to quit, space for next page) --";
+
+// Altura usada quando não dá para perguntar ao terminal
+const DEFAULT_TERMINAL_HEIGHT: usize = 24;
+
+// O que cada tecla pede
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    NextPage,
+    NextLine,
+    Quit,
+}
+
+// Mostra `text` em `out`, `height` linhas por vez (a última linha da tela é o aviso)
+// As teclas vêm de `next_key`; quando elas acabam (None), o resto é descartado, como com q
+//
+// Uma linha mais larga que o terminal ocupa mais de uma linha na tela:
+// nesse caso a tela rola um pouco além do ideal, o que o `more` também faz
+pub fn page(
+    out: &mut impl Write,
+    text: &str,
+    height: usize,
+    style: Style,
+    mut next_key: impl FnMut() -> Option<Key>,
+) -> io::Result<()> {
+    // Uma linha fica para o aviso
+    let screen = height.saturating_sub(1).max(1);
+    let lines: Vec<&str> = text.lines().collect();
+
+    let mut shown = lines.len().min(screen);
+    for line in &lines[..shown] {
+        writeln!(out, "{}", line)?;
+    }
+
+    while shown < lines.len() {
+        write!(out, "{}", prompt(style))?;
+        out.flush()?;
+
+        let more = match next_key() {
+            Some(Key::NextPage) => screen,
+            Some(Key::NextLine) => 1,
+            Some(Key::Quit) | None => 0,
+        };
+        // Apaga o aviso: a próxima linha (ou o prompt do shell) ocupa o lugar dele
+        write!(out, "\r{}\r", " ".repeat(PROMPT.len()))?;
+        if more == 0 {
+            break;
+        }
+
+        let end = (shown + more).min(lines.len());
+        for line in &lines[shown..end] {
+            writeln!(out, "{}", line)?;
+        }
+        shown = end;
+    }
+
+    out.flush()
+}
+
+// O aviso em vídeo reverso, a menos que as cores estejam desligadas (--no-color, NO_COLOR)
+fn prompt(style: Style) -> String {
+    match style {
+        Style::Ansi => format!("\x1b[7m{}\x1b[0m", PROMPT),
+        Style::Plain => PROMPT.to_string(),
+    }
+}
+
+// Interpreta uma tecla; None = ignorar e esperar outra
+pub fn key_for(byte: u8) -> Option<Key> {
+    match byte {
+        b' ' | b'f' => Some(Key::NextPage),
+        b'\n' | b'\r' | b'j' => Some(Key::NextLine),
+        // 0x03 é o Ctrl-C: sem o sinal (stty -isig), ele chega como um byte qualquer
+        b'q' | b'Q' | 0x03 => Some(Key::Quit),
+        _ => None,
+    }
+}
+
+// Mostra `text` no terminal, paginando se ele não couber em uma tela
+pub fn show(text: &str, style: Style) -> io::Result<()> {
+    let height = terminal_height();
+    let mut stdout = io::stdout().lock();
+
+    // Cabe inteiro: nada de aviso nem de mexer no terminal
+    if text.lines().count() < height {
+        return stdout.write_all(text.as_bytes());
+    }
+
+    // As teclas vêm do terminal, não do stdin: com `--from-file -`, o stdin é um pipe
+    #[cfg(unix)]
+    {
+        let mut tty = File::open("/dev/tty")?;
+        let _raw = RawMode::enable(&tty);
+        page(&mut stdout, text, height, style, || read_key(&mut tty))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let mut stdin = io::stdin().lock();
+        page(&mut stdout, text, height, style, || read_line_key(&mut stdin))
+    }
+}
+
+// Sem o modo tecla a tecla, cada resposta é uma linha: "q" + Enter sai,
+// só Enter mostra mais uma linha e qualquer outra coisa, a próxima tela
+#[cfg(not(unix))]
+fn read_line_key(input: &mut impl io::BufRead) -> Option<Key> {
+    let mut line = String::new();
+    match input.read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.bytes().next().and_then(key_for).unwrap_or(Key::NextPage)),
+    }
+}
+
+// Lê bytes até um deles ser uma tecla conhecida; None no fim da entrada ou em erro
+#[cfg_attr(not(unix), allow(dead_code))]
+fn read_key(input: &mut impl Read) -> Option<Key> {
+    let mut byte = [0u8];
+    loop {
+        match input.read(&mut byte) {
+            Ok(0) | Err(_) => return None,
+            Ok(_) => {
+                if let Some(key) = key_for(byte[0]) {
+                    return Some(key);
+                }
+            }
+        }
+    }
+}
+
+// Linhas do terminal: `stty size` responde "linhas colunas"; sem ele, LINES; sem nada, 24
+fn terminal_height() -> usize {
+    let from_stty = File::open("/dev/tty").ok().and_then(|tty| {
+        let output = Command::new("stty").arg("size").stdin(tty).stderr(Stdio::null()).output().ok()?;
+        let text = String::from_utf8(output.stdout).ok()?;
+        text.split_whitespace().next()?.parse().ok()
+    });
+
+    from_stty
+        .or_else(|| std::env::var("LINES").ok()?.trim().parse().ok())
+        .filter(|&lines: &usize| lines > 1)
+        .unwrap_or(DEFAULT_TERMINAL_HEIGHT)
+}
+
+// CONCEITO: Guard (RAII)
+// Enquanto o valor existe, o terminal fica em modo "tecla a tecla";
+// no Drop (fim do escopo, retorno com ?, ou panic) a configuração anterior volta
+// Ctrl-C não mata o processo no meio do caminho: com -isig ele vira a tecla 0x03 (sair),
+// então o Drop sempre roda
+#[cfg(unix)]
+struct RawMode {
+    // A configuração anterior, no formato de `stty -g` (só o stty precisa entendê-la)
+    saved: String,
+    tty: File,
+}
+
+#[cfg(unix)]
+impl RawMode {
+    // None quando o stty não existe ou falha: a paginação segue, mas cada tecla espera o Enter
+    fn enable(tty: &File) -> Option<RawMode> {
+        let saved = stty(tty, &["-g"])?;
+        stty(tty, &["-icanon", "-echo", "-isig", "min", "1"])?;
+        Some(RawMode { saved: saved.trim().to_string(), tty: tty.try_clone().ok()? })
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = stty(&self.tty, &[self.saved.as_str()]);
+    }
+}
+
+// Roda o stty sobre o terminal `tty` (ele age sobre o seu stdin) e devolve o que ele imprimiu
+#[cfg(unix)]
+fn stty(tty: &File, args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(tty.try_clone().ok()?)
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    String::from_utf8(output.stdout).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(count: usize) -> String {
+        (1..=count).map(|n| format!("line {}\n", n)).collect()
+    }
+
+    // Pagina com as teclas dadas e devolve o que foi escrito
+    fn paged(text: &str, height: usize, keys: &[Key]) -> String {
+        let mut keys = keys.iter().copied();
+        let mut out = Vec::new();
+        page(&mut out, text, height, Style::Plain, || keys.next()).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn erase() -> String {
+        format!("\r{}\r", " ".repeat(PROMPT.len()))
+    }
+
+    #[test]
+    fn test_short_text_has_no_prompt() {
+        assert_eq!(paged(&lines(3), 4, &[]), lines(3));
+    }
+
+    #[test]
+    fn test_space_shows_the_next_screen() {
+        // Telas de 2 linhas (a terceira é o aviso)
+        let text = paged(&lines(5), 3, &[Key::NextPage, Key::NextPage]);
+        assert_eq!(
+            text,
+            format!(
+                "line 1\nline 2\n{p}{e}line 3\nline 4\n{p}{e}line 5\n",
+                p = PROMPT,
+                e = erase()
+            )
+        );
+    }
+
+    #[test]
+    fn test_enter_shows_one_more_line_and_quit_stops() {
+        let text = paged(&lines(5), 3, &[Key::NextLine, Key::Quit]);
+        assert_eq!(
+            text,
+            format!("line 1\nline 2\n{p}{e}line 3\n{p}{e}", p = PROMPT, e = erase())
+        );
+    }
+
+    #[test]
+    fn test_end_of_input_stops_paging() {
+        assert!(!paged(&lines(10), 3, &[]).contains("line 3"));
+    }
+
+    #[test]
+    fn test_colored_prompt_is_reversed() {
+        assert_eq!(prompt(Style::Ansi), format!("\x1b[7m{}\x1b[0m", PROMPT));
+        assert_eq!(prompt(Style::Plain), PROMPT);
+    }
+
+    #[test]
+    fn test_keys() {
+        assert_eq!(key_for(b' '), Some(Key::NextPage));
+        assert_eq!(key_for(b'\n'), Some(Key::NextLine));
+        assert_eq!(key_for(b'q'), Some(Key::Quit));
+        assert_eq!(key_for(0x03), Some(Key::Quit));
+        assert_eq!(key_for(b'x'), None);
+
+        // Teclas desconhecidas são puladas; o fim da entrada é None
+        assert_eq!(read_key(&mut &b"xy q"[..]), Some(Key::NextPage));
+        assert_eq!(read_key(&mut &b"xyz"[..]), None);
+    }
+}
@@ -1,338 +1,1717 @@
-// Este módulo implementa parsing manual de JSON
-// É uma abordagem educativa para entender manipulação de strings em Rust
-// Em produção, normalmente usaríamos 'serde_json', mas fazer manualmente
-// ensina muito sobre borrowing, string slicing, e error handling
+//! Este módulo transforma o JSON da API em eventos (GitHubEvent)
+//! A leitura do JSON em si fica em json.rs; aqui só interpretamos os campos
+//! Em produção, normalmente usaríamos 'serde_json', mas fazer manualmente
+//! ensina muito sobre borrowing, lifetimes, e error handling
 
+use std::time::Instant;
+
+use crate::api::RateLimitInfo;
+use crate::date::Timestamp;
 use crate::error::ActivityError;
-use crate::models::{EventPayload, GitHubEvent};
+use crate::json::{self, JsonValue};
+use crate::logging::{self, Level};
+use crate::models::{Actor, CommitSummary, EventKind, EventPayload, GitHubEvent, Repository, UserProfile};
+
+/// Um evento que não conseguimos interpretar e foi pulado
+#[derive(Debug)]
+pub struct ParseWarning {
+    /// Posição do evento no array (0 = o mais recente)
+    pub index: usize,
+    /// Por que o parsing falhou (com o evento e o trecho do JSON)
+    pub error: ActivityError,
+}
+
+impl ParseWarning {
+    /// Transforma o aviso em erro (modo --strict)
+    /// O ParseError já diz qual evento falhou e mostra o trecho do JSON
+    pub fn into_error(self) -> ActivityError {
+        self.error
+    }
+
+    // O mesmo aviso, com o índice deslocado por `offset` eventos de páginas anteriores
+    pub(crate) fn shifted(self, offset: usize) -> ParseWarning {
+        let index = self.index + offset;
+        ParseWarning { index, error: self.error.in_event(index) }
+    }
+}
+
+/// Resultado do parsing: os eventos que deram certo + avisos sobre os que foram pulados
+/// Um evento estranho não derruba a listagem inteira, mas também não some em silêncio
+#[derive(Debug, Default)]
+pub struct ParseOutcome {
+    /// Eventos interpretados, na ordem da API (do mais novo para o mais antigo)
+    pub events: Vec<GitHubEvent>,
+    /// Eventos pulados e o motivo
+    pub warnings: Vec<ParseWarning>,
+}
+
+impl ParseOutcome {
+    /// Junta o resultado de outra página ao final deste
+    /// Os índices dos avisos são deslocados para continuarem únicos na lista completa
+    pub fn append(&mut self, page: ParseOutcome) {
+        let offset = self.events.len() + self.warnings.len();
+
+        self.events.extend(page.events);
+        self.warnings.extend(page.warnings.into_iter().map(|warning| warning.shifted(offset)));
+    }
+}
 
 // CONCEITO: Result<T, E>
 // Result é um enum que representa sucesso (Ok) ou falha (Err)
 // É como o sistema de tipos força você a lidar com erros explicitamente
-pub fn parse_events(json_text: &str) -> Result<Vec<GitHubEvent>, ActivityError> {
+//
+// Só falha se o TEXTO todo não for um array; eventos individuais inválidos
+// (inclusive com JSON mal formado) viram avisos em ParseOutcome::warnings
+/// Interpreta o array JSON devolvido pelos endpoints de eventos da API
+pub fn parse_events(json_text: &str) -> Result<ParseOutcome, ActivityError> {
     // CONCEITO: Vec<T>
     // Vec é um vetor dinâmico (como ArrayList em Java ou list em Python)
     // Cresce conforme necessário
-    let mut events = Vec::new();
+    let mut outcome = ParseOutcome::default();
+    let started = Instant::now();
 
-    // Encontra o início e fim do array JSON
-    // trim() remove espaços em branco nas pontas
-    let trimmed = json_text.trim();
+    let trimmed = response_body(json_text)?;
 
-    // Validação básica: deve começar com [ e terminar com ]
-    if !trimmed.starts_with('[') || !trimmed.ends_with(']') {
-        return Err(ActivityError::ParseError(
-            "Expected JSON array".to_string(),
-        ));
-    }
-
-    // Remove os colchetes [ e ]
-    let content = &trimmed[1..trimmed.len() - 1].trim();
-
-    // Se o array está vazio, retorna vetor vazio
-    if content.is_empty() {
-        return Ok(events);
+    // Um objeto no lugar do array costuma ser um erro da API que passou
+    // com status de sucesso ({"message": "Not Found"}): mostramos a mensagem do GitHub
+    if !trimmed.starts_with('[') {
+        if let Some((message, documentation_url)) = api_message(trimmed) {
+            return Err(ActivityError::ApiError { status: 0, message, documentation_url });
+        }
+        return Err(unexpected_response(trimmed));
     }
 
-    // Divide o JSON em objetos individuais
-    // Esta é a parte mais complexa do parsing manual
-    let objects = split_json_objects(content)?;
+    // Divide o array em elementos, já com a árvore de cada um (ver json::parse_array_items)
+    let items = json::parse_array_items(trimmed)?;
 
     // CONCEITO: Iterators
     // for..in é sintaxe açucarada para iterators
-    // Cada 'obj' é uma referência &str para um objeto JSON
-    for obj in objects {
-        // Tenta parsear cada objeto como um evento
-        // O operador ? propaga erros automaticamente
-        // Se parse_event retornar Err, toda a função retorna esse erro
-        match parse_event(obj) {
-            Ok(event) => events.push(event),  // push adiciona ao final do Vec
-            Err(_) => continue,  // Ignora eventos que não conseguimos parsear
+    // Cada 'item' traz o texto de um elemento do array e, se ele era JSON válido, a árvore
+    for (index, item) in items.into_iter().enumerate() {
+        // Tenta interpretar cada elemento como um evento
+        // Um erro aqui não interrompe o parsing: o evento é pulado e registrado
+        // Um elemento sem árvore passa por parse_event, que refaz o erro de JSON com a posição
+        let parsed = match &item.value {
+            Some(value) => event_from_value(value, item.text),
+            None => parse_event(item.text),
+        };
+        match parsed {
+            Ok(event) => outcome.events.push(event),  // push adiciona ao final do Vec
+            Err(error) => outcome.warnings.push(ParseWarning {
+                index,
+                error: error.in_event(index),
+            }),
         }
     }
 
-    Ok(events)  // Sucesso! Retorna o vetor de eventos
+    logging::log(Level::Info, || {
+        format!(
+            "parsed {} events ({} skipped) in {:.1?}",
+            outcome.events.len(),
+            outcome.warnings.len(),
+            started.elapsed()
+        )
+    });
+
+    Ok(outcome)  // Sucesso! Retorna os eventos (e os avisos)
+}
+
+// Quanto do corpo mostrar quando a resposta não é um array (para depuração)
+const RESPONSE_PREVIEW_MAX_CHARS: usize = 100;
+
+/// O ParseError de uma resposta sem corpo (ou só com espaços): um 200 vazio costuma
+/// vir de um proxy, não do GitHub
+pub fn empty_response() -> ActivityError {
+    ActivityError::parse("empty response from server")
+}
+
+// O corpo de uma resposta pronto para o leitor de JSON, o mesmo para todos os endpoints
+// Um proxy (ou um editor de texto, num --from-file) pode acrescentar o BOM do UTF-8
+// (U+FEFF) no começo; ele não é espaço em branco para o trim(), então sai à parte.
+// Sem nada além disso, é empty_response() em vez de um erro de JSON sem sentido
+pub(crate) fn response_body(json_text: &str) -> Result<&str, ActivityError> {
+    let trimmed = json_text.strip_prefix('\u{feff}').unwrap_or(json_text).trim();
+    if trimmed.is_empty() {
+        return Err(empty_response());
+    }
+    Ok(trimmed)
+}
+
+// ParseError para uma resposta que não é um array, com o começo do texto recebido
+fn unexpected_response(trimmed: &str) -> ActivityError {
+    ActivityError::parse(format!(
+        "Expected JSON array, got: {}",
+        truncate_chars(trimmed, RESPONSE_PREVIEW_MAX_CHARS)
+    ))
 }
 
-// Função auxiliar para dividir objetos JSON em um array
-// Esta é uma versão simplificada que funciona para o caso específico da API do GitHub
-fn split_json_objects(content: &str) -> Result<Vec<&str>, ActivityError> {
-    let mut objects = Vec::new();
-    let mut depth = 0;  // Rastreia nível de aninhamento de { }
-    let mut start = 0;
+// Tamanho máximo de um corpo de erro que não é JSON (ex: página HTML de um proxy)
+const RAW_ERROR_MAX_CHARS: usize = 200;
 
-    // CONCEITO: chars() e enumerate()
-    // chars() retorna um iterator sobre os caracteres Unicode
-    // enumerate() adiciona o índice (posição) a cada elemento
-    for (i, ch) in content.chars().enumerate() {
-        match ch {
-            '{' => {
-                if depth == 0 {
-                    start = i;  // Marca início de um objeto
-                }
-                depth += 1;
-            }
-            '}' => {
-                depth -= 1;
-                if depth == 0 {
-                    // Fim de um objeto no nível raiz
-                    // CONCEITO: String slicing [start..end]
-                    // Cria uma fatia (slice) da string original
-                    // É uma referência, não cópia - muito eficiente!
-                    let obj = &content[start..=i];
-                    objects.push(obj.trim());
-                }
-            }
-            _ => {}  // Ignora outros caracteres
+/// Interpreta o corpo de uma resposta de erro da API
+/// O GitHub responde {"message": "...", "documentation_url": "..."}
+/// Retorna (mensagem, url da documentação)
+///
+/// Se o corpo não for um objeto JSON com "message" (ex: HTML de um proxy em um 502),
+/// a mensagem é o próprio texto, cortado em RAW_ERROR_MAX_CHARS caracteres
+pub fn parse_error_body(body: &str) -> (String, Option<String>) {
+    let trimmed = body.trim();
+
+    if let Some(parsed) = api_message(trimmed) {
+        return parsed;
+    }
+
+    if trimmed.is_empty() {
+        return ("Unknown error".to_string(), None);
+    }
+
+    (truncate_chars(trimmed, RAW_ERROR_MAX_CHARS), None)
+}
+
+// Lê {"message": "...", "documentation_url": "..."}
+// None se o texto não for um objeto JSON com "message"
+// JSON inválido ou escapes inválidos contam como "não é o JSON esperado" (.ok())
+fn api_message(text: &str) -> Option<(String, Option<String>)> {
+    let value = json::parse(text).ok()?;
+    let message = extract_string_value(&value, "message").ok().flatten()?;
+    let documentation_url = extract_string_value(&value, "documentation_url").ok().flatten();
+    Some((message, documentation_url))
+}
+
+/// Interpreta o perfil devolvido por /users/{username}
+/// Só o login é obrigatório: contagens ausentes viram 0, textos null viram None
+pub fn parse_user_profile(json_text: &str) -> Result<UserProfile, ActivityError> {
+    let user = json::parse(response_body(json_text)?)?;
+    if !user.is_object() {
+        return Err(ActivityError::parse("Expected a JSON object with the user profile"));
+    }
+
+    let login = extract_string_value(&user, "login")?
+        .ok_or_else(|| ActivityError::parse("Missing 'login' field"))?;
+
+    Ok(UserProfile {
+        login,
+        name: extract_string_value(&user, "name")?,
+        followers: extract_number_value(&user, "followers").unwrap_or(0),
+        public_repos: extract_number_value(&user, "public_repos").unwrap_or(0),
+        company: extract_string_value(&user, "company")?,
+        location: extract_string_value(&user, "location")?,
+    })
+}
+
+/// Interpreta um array de repositórios, como o de /users/{username}/starred
+///
+/// Outro formato de documento, os mesmos extratores dos eventos: só `full_name` é
+/// obrigatório; descrição, linguagem e data null viram None, estrelas ausentes viram 0
+/// e um "fork" ausente, false. Sem "name", o nome sai do `full_name`.
+/// Diferente dos eventos, um repositório que não dá para ler é um erro: a lista
+/// é pequena e simples, e um item estranho quer dizer que a resposta não é a esperada
+pub fn parse_repositories(json_text: &str) -> Result<Vec<Repository>, ActivityError> {
+    let trimmed = response_body(json_text)?;
+    if !trimmed.starts_with('[') {
+        if let Some((message, documentation_url)) = api_message(trimmed) {
+            return Err(ActivityError::ApiError { status: 0, message, documentation_url });
         }
+        return Err(unexpected_response(trimmed));
     }
 
-    Ok(objects)
+    let list = json::parse(trimmed)?;
+    let items: &[JsonValue] = match &list {
+        JsonValue::Array(items) => items,
+        _ => &[],
+    };
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| read_repository(item).map_err(|error| in_repository(error, index)))
+        .collect()
+}
+
+// Acrescenta a posição do repositório à mensagem de um ParseError
+// (o event_index dos eventos diria "event #N", que aqui seria mentira)
+fn in_repository(error: ActivityError, index: usize) -> ActivityError {
+    match error {
+        ActivityError::ParseError { message, event_index, snippet, source } => ActivityError::ParseError {
+            message: format!("repository #{}: {}", index, message),
+            event_index,
+            snippet,
+            source,
+        },
+        other => other,
+    }
+}
+
+fn read_repository(repository: &JsonValue) -> Result<Repository, ActivityError> {
+    if !repository.is_object() {
+        return Err(ActivityError::parse("Expected a JSON object"));
+    }
+
+    let full_name = extract_string_value(repository, "full_name")?
+        .ok_or_else(|| ActivityError::parse("Missing 'full_name' field"))?;
+    let name = match extract_string_value(repository, "name")? {
+        Some(name) => name,
+        None => full_name.rsplit('/').next().unwrap_or(&full_name).to_string(),
+    };
+    Ok(Repository {
+        name,
+        description: extract_string_value(repository, "description")?,
+        stargazers_count: extract_number_value(repository, "stargazers_count").unwrap_or(0),
+        language: extract_string_value(repository, "language")?,
+        fork: extract_bool_value(repository, "fork").unwrap_or(false),
+        updated_at: extract_string_value(repository, "updated_at")?.and_then(|text| Timestamp::parse_rfc3339(&text)),
+        full_name,
+    })
+}
+
+/// Interpreta a resposta de `GET /rate_limit`: o objeto "rate", a cota principal
+///
+/// ```text
+/// {"resources": {...}, "rate": {"limit": 60, "remaining": 54, "reset": 1714584720, ...}}
+/// ```
+pub fn parse_rate_limit(json_text: &str) -> Result<RateLimitInfo, ActivityError> {
+    let body = json::parse(response_body(json_text)?)?;
+    let rate = body
+        .get("rate")
+        .filter(|rate| rate.is_object())
+        .ok_or_else(|| ActivityError::parse("Expected a 'rate' object in the rate limit response"))?;
+
+    let number = |key: &str| {
+        extract_number_value::<u64>(rate, key).ok_or_else(|| ActivityError::parse(format!("Missing '{}' field", key)))
+    };
+    Ok(RateLimitInfo {
+        limit: number("limit")?,
+        remaining: number("remaining")?,
+        reset_at: extract_number_value::<i64>(rate, "reset").map(Timestamp::from_epoch_seconds),
+    })
+}
+
+// Quanto do payload de um evento desconhecido guardar (cerca de 2 KB)
+// Só serve para depuração, e alguns payloads trazem textos enormes (ex: corpo de issue)
+const RAW_PAYLOAD_MAX_CHARS: usize = 2048;
+
+// Corta o texto em `max` caracteres, indicando o corte com "..."
+// CONCEITO: chars().take(n)
+// Corta por caracteres, não por bytes: nunca divide um caractere multi-byte
+fn truncate_chars(text: &str, max: usize) -> String {
+    let mut truncated: String = text.chars().take(max).collect();
+    if truncated.len() < text.len() {
+        truncated.push_str("...");
+    }
+    truncated
 }
 
 // Parseia um único objeto JSON representando um evento
-fn parse_event(json_obj: &str) -> Result<GitHubEvent, ActivityError> {
+// Um erro sem posição (ex: campo faltando) mostra o começo do evento
+// Também usada por history.rs, onde cada linha do arquivo é um evento
+pub(crate) fn parse_event(json_obj: &str) -> Result<GitHubEvent, ActivityError> {
+    // Monta a árvore de valores do evento (ver json.rs)
+    // JSON mal formado vira ParseError com a posição do problema
+    json::parse(json_obj)
+        .map_err(|error| error.near(|| json::snippet_around(json_obj, 0)))
+        .and_then(|event| event_from_value(&event, json_obj))
+}
+
+// O evento de uma árvore já montada; `json_obj` é o texto dela, para o trecho dos erros
+fn event_from_value(event: &JsonValue, json_obj: &str) -> Result<GitHubEvent, ActivityError> {
+    read_event(event).map_err(|error| error.near(|| json::snippet_around(json_obj, 0)))
+}
+
+fn read_event(event: &JsonValue) -> Result<GitHubEvent, ActivityError> {
+    if !event.is_object() {
+        return Err(ActivityError::parse("Expected a JSON object"));
+    }
+
     // Extrai campos obrigatórios
     // CONCEITO: Option<T>
     // Option representa um valor que pode existir (Some) ou não (None)
     // É como null em outras linguagens, mas type-safe
-    let event_type = extract_string_value(json_obj, "type")
-        .ok_or_else(|| ActivityError::ParseError("Missing 'type' field".to_string()))?;
+    let type_name = extract_string_value(event, "type")?
+        .ok_or_else(|| ActivityError::parse("Missing 'type' field".to_string()))?;
+
+    // CONCEITO: str::parse com erro Infallible
+    // Usa o FromStr de EventKind, que nunca falha: tipos desconhecidos viram EventKind::Other
+    // `match never {}` convence o compilador disso (Infallible não tem valores para casar)
+    let kind: EventKind = type_name.parse().unwrap_or_else(|never| match never {});
 
     // repo.name está aninhado: {"repo": {"name": "..."}}
     // Primeiro extraímos o objeto "repo"
-    let repo_obj = extract_nested_object(json_obj, "repo")
-        .ok_or_else(|| ActivityError::ParseError("Missing 'repo' field".to_string()))?;
+    let repo_obj = extract_nested_object(event, "repo")
+        .ok_or_else(|| ActivityError::parse("Missing 'repo' field".to_string()))?;
 
     // Depois extraímos "name" de dentro dele
-    let repo_name = extract_string_value(repo_obj, "name")
-        .ok_or_else(|| ActivityError::ParseError("Missing 'repo.name' field".to_string()))?;
+    let repo_name = extract_string_value(repo_obj, "name")?
+        .ok_or_else(|| ActivityError::parse("Missing 'repo.name' field".to_string()))?;
+
+    // O ator é opcional: sem ele o evento ainda pode ser exibido
+    let actor = match extract_nested_object(event, "actor") {
+        Some(actor_obj) => parse_actor(actor_obj)?,
+        None => None,
+    };
+
+    // Timestamp também é opcional: se faltar ou vier num formato estranho,
+    // guardamos None e o evento continua sendo exibido
+    // CONCEITO: Option::and_then
+    // Encadeia operações que retornam Option: se alguma der None, o resultado é None
+    let created_at = extract_string_value(event, "created_at")?
+        .and_then(|text| Timestamp::parse_rfc3339(&text));
 
     // Parseia o payload específico do tipo de evento
-    let payload = parse_payload(json_obj, &event_type)?;
+    let payload = parse_payload(event, &kind)?;
+
+    Ok(GitHubEvent::new(kind, repo_name, payload, created_at)
+        .with_id(parse_event_id(event))
+        .with_actor(actor))
+}
+
+// Os eventos com id de uma página bruta, como árvores JSON, para quem guarda o evento
+// como a API mandou (storage.rs e history.rs)
+// Eventos ilegíveis ou sem id ficam de fora: parse_events já avisa deles
+pub(crate) fn raw_events(body: &str) -> Vec<(u64, JsonValue<'_>)> {
+    json::parse_array_items(body.trim())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|item| item.value)
+        .filter_map(|event| parse_event_id(&event).map(|id| (id, event)))
+        .collect()
+}
+
+// O id do evento vem como string ("42000000011"), mas um número também é aceito
+// Um id ausente ou estranho não descarta o evento: só fica sem id
+fn parse_event_id(event: &JsonValue) -> Option<u64> {
+    match event.get("id")? {
+        JsonValue::String(raw) => raw.parse().ok(),
+        JsonValue::Number(number) => number.parse().ok(),
+        _ => None,
+    }
+}
+
+// Parseia {"id": 1024025, "login": "torvalds", "avatar_url": "..."}
+// Um ator sem login ou sem id é tratado como ausente
+fn parse_actor(actor_obj: &JsonValue) -> Result<Option<Actor>, ActivityError> {
+    let login = extract_string_value(actor_obj, "login")?;
+    let id = extract_number_value(actor_obj, "id");
 
-    Ok(GitHubEvent::new(event_type, repo_name, payload))
+    // CONCEITO: zip em Option
+    // Some(a).zip(Some(b)) == Some((a, b)); se qualquer um for None, o resultado é None
+    Ok(login.zip(id).map(|(login, id)| Actor { login, id }))
 }
 
 // Parseia o campo "payload" baseado no tipo de evento
-fn parse_payload(json_obj: &str, event_type: &str) -> Result<EventPayload, ActivityError> {
-    // CONCEITO: Pattern matching com strings
-    // match em &str compara o conteúdo da string
-    match event_type {
-        "PushEvent" => {
-            // Extrai o objeto payload
-            let payload_obj = extract_nested_object(json_obj, "payload")
-                .unwrap_or("");  // unwrap_or retorna valor padrão se None
-
-            // NOTA: O endpoint /users/{username}/events não inclui a lista de commits
-            // Em produção, usaríamos size se disponível no payload
-            // Para fins educacionais, vamos extrair size ou usar 1 como padrão
+fn parse_payload(event: &JsonValue, kind: &EventKind) -> Result<EventPayload, ActivityError> {
+    // Sem payload, os campos são lidos de um objeto vazio (todos "ausentes")
+    let empty = JsonValue::Object(Vec::new());
+    let payload_obj = extract_nested_object(event, "payload").unwrap_or(&empty);
+
+    // CONCEITO: Pattern matching em enum
+    // O compilador confere os nomes das variantes (um "PsuhEvent" seria um erro de compilação)
+    match kind {
+        EventKind::Push => {
+            // "ref": "refs/heads/feature-x" -> "feature-x"
+            let branch = extract_string_value(payload_obj, "ref")?.map(|git_ref| {
+                // CONCEITO: strip_prefix + unwrap_or
+                // Se o prefixo não existir (ex: "refs/tags/v1"), mantemos o ref completo
+                git_ref.strip_prefix("refs/heads/").unwrap_or(&git_ref).to_string()
+            });
+
+            // "commits": [{"sha": "...", "message": "..."}, ...]
+            // Commits sem mensagem são ignorados
+            let listed: Vec<&JsonValue> = extract_array_objects(payload_obj, "commits").collect();
+            let mut commits = Vec::new();
+            for commit in listed.iter().copied() {
+                if let Some(message) = extract_string_value(commit, "message")? {
+                    let sha = extract_string_value(commit, "sha")?.unwrap_or_default();
+                    commits.push(CommitSummary { sha, message });
+                }
+            }
+
+            // NOTA: Quantos commits o push enviou vem de "size"; sem ele, do tamanho da
+            // lista "commits" (que a API corta em 20, então pode contar menos); sem nenhum
+            // dos dois, 1, porque todo push envia pelo menos um commit
             let commit_count = extract_number_value(payload_obj, "size")
-                .unwrap_or(1);  // Padrão: assume 1 commit
+                .or_else(|| (!listed.is_empty()).then_some(listed.len()))
+                .unwrap_or(1);
+            // Sem "distinct_size", consideramos todos os commits novos
+            let distinct_count = extract_number_value(payload_obj, "distinct_size")
+                .unwrap_or(commit_count);
 
-            Ok(EventPayload::Push { commit_count })
+            // Cada evento da API é exatamente um push
+            Ok(EventPayload::Push { commit_count, distinct_count, push_count: 1, branch, commits })
         }
-        "IssuesEvent" => {
-            let payload_obj = extract_nested_object(json_obj, "payload").unwrap_or("");
-            let action = extract_string_value(payload_obj, "action")
+        EventKind::Issues => {
+            let action = extract_string_value(payload_obj, "action")?
                 .unwrap_or_else(|| "unknown".to_string());
 
-            Ok(EventPayload::IssuesEvent { action })
+            // Número e título ficam no objeto aninhado "issue"
+            let (number, title) = issue_reference(payload_obj, "issue")?;
+
+            Ok(EventPayload::IssuesEvent { action, number, title })
         }
-        "PullRequestEvent" => {
-            let payload_obj = extract_nested_object(json_obj, "payload").unwrap_or("");
-            let action = extract_string_value(payload_obj, "action")
+        EventKind::PullRequest => {
+            let action = extract_string_value(payload_obj, "action")?
                 .unwrap_or_else(|| "unknown".to_string());
 
-            Ok(EventPayload::PullRequestEvent { action })
+            let (number, title) = issue_reference(payload_obj, "pull_request")?;
+
+            // "merged": true só aparece dentro de pull_request
+            let merged = extract_nested_object(payload_obj, "pull_request")
+                .and_then(|pull_request| extract_bool_value(pull_request, "merged"))
+                .unwrap_or(false);
+
+            Ok(EventPayload::PullRequestEvent { action, number, title, merged })
+        }
+        EventKind::Watch => Ok(EventPayload::WatchEvent),
+        EventKind::Fork => {
+            // O repositório novo vem em "forkee": {"full_name": "myuser/linux", ...}
+            let forkee = match extract_nested_object(payload_obj, "forkee") {
+                Some(forkee) => extract_string_value(forkee, "full_name")?,
+                None => None,
+            };
+
+            Ok(EventPayload::ForkEvent { forkee })
         }
-        "WatchEvent" => Ok(EventPayload::WatchEvent),
-        "ForkEvent" => Ok(EventPayload::ForkEvent),
-        "CreateEvent" => {
-            let payload_obj = extract_nested_object(json_obj, "payload").unwrap_or("");
-            let ref_type = extract_string_value(payload_obj, "ref_type")
+        EventKind::Create => {
+            let ref_type = extract_string_value(payload_obj, "ref_type")?
                 .unwrap_or_else(|| "unknown".to_string());
+            // "ref" é o nome curto ("feature-x", "v1.2.0")
+            // null é esperado ao criar um repositório; ausente só em dados incompletos
+            let ref_name = match extract_string_field(payload_obj, "ref")? {
+                JsonField::Value(name) => Some(name),
+                JsonField::Null | JsonField::Missing => None,
+            };
 
-            Ok(EventPayload::CreateEvent { ref_type })
+            Ok(EventPayload::CreateEvent { ref_type, ref_name })
         }
-        "DeleteEvent" => {
-            let payload_obj = extract_nested_object(json_obj, "payload").unwrap_or("");
-            let ref_type = extract_string_value(payload_obj, "ref_type")
+        EventKind::Delete => {
+            let ref_type = extract_string_value(payload_obj, "ref_type")?
                 .unwrap_or_else(|| "unknown".to_string());
+            let ref_name = extract_string_value(payload_obj, "ref")?;
 
-            Ok(EventPayload::DeleteEvent { ref_type })
+            Ok(EventPayload::DeleteEvent { ref_type, ref_name })
         }
-        "ReleaseEvent" => {
-            let payload_obj = extract_nested_object(json_obj, "payload").unwrap_or("");
-            let action = extract_string_value(payload_obj, "action")
+        EventKind::Release => {
+            let action = extract_string_value(payload_obj, "action")?
                 .unwrap_or_else(|| "published".to_string());
 
-            Ok(EventPayload::ReleaseEvent { action })
+            // Os detalhes ficam em "release": {"tag_name": "v1.4.0", "name": "...", "draft": false}
+            let (tag, name, draft) = match extract_nested_object(payload_obj, "release") {
+                Some(release) => (
+                    extract_string_value(release, "tag_name")?,
+                    extract_string_value(release, "name")?,
+                    extract_bool_value(release, "draft").unwrap_or(false),
+                ),
+                None => (None, None, false),
+            };
+
+            Ok(EventPayload::ReleaseEvent { action, tag, name, draft })
+        }
+        EventKind::IssueComment => Ok(EventPayload::IssueCommentEvent),
+        EventKind::PullRequestReviewComment => Ok(EventPayload::PullRequestReviewCommentEvent),
+        EventKind::CommitComment => Ok(EventPayload::CommitCommentEvent),
+        EventKind::PullRequestReview => {
+            let action = extract_string_value(payload_obj, "action")?
+                .unwrap_or_else(|| "created".to_string());
+            // O estado fica na revisão: {"review": {"state": "approved", ...}}
+            let state = match extract_nested_object(payload_obj, "review") {
+                Some(review) => extract_string_value(review, "state")?,
+                None => None,
+            };
+
+            Ok(EventPayload::PullRequestReview {
+                action,
+                state: state.unwrap_or_else(|| "unknown".to_string()),
+            })
+        }
+        EventKind::Member => {
+            let action = extract_string_value(payload_obj, "action")?
+                .unwrap_or_else(|| "added".to_string());
+            // O colaborador fica em "member": {"login": "octocat", ...}
+            let member_login = match extract_nested_object(payload_obj, "member") {
+                Some(member) => extract_string_value(member, "login")?,
+                None => None,
+            };
+
+            Ok(EventPayload::Member { action, member_login })
         }
-        "IssueCommentEvent" => Ok(EventPayload::IssueCommentEvent),
-        "PullRequestReviewCommentEvent" => Ok(EventPayload::PullRequestReviewCommentEvent),
-        "CommitCommentEvent" => Ok(EventPayload::CommitCommentEvent),
-        _ => Ok(EventPayload::Unknown),  // Tipos não mapeados
+        // O payload de PublicEvent é sempre vazio
+        EventKind::Public => Ok(EventPayload::Public),
+        EventKind::Gollum => {
+            // "pages": [{"page_name": "Home", "action": "created", ...}, ...]
+            // CONCEITO: Iterator::next
+            // Um iterator só avança quando pedimos: pegamos a primeira página
+            // e depois contamos as restantes com o mesmo iterator
+            let mut pages = extract_array_objects(payload_obj, "pages");
+            let (first_page, first_page_action, pages_edited) = match pages.next() {
+                Some(page) => (
+                    extract_string_value(page, "page_name")?,
+                    extract_string_value(page, "action")?,
+                    1 + pages.count(),
+                ),
+                None => (None, None, 0),
+            };
+
+            Ok(EventPayload::Gollum { pages_edited, first_page, first_page_action })
+        }
+        // Tipos não mapeados: guardamos o payload como texto para o --verbose
+        EventKind::Other(_) => Ok(EventPayload::Unknown {
+            raw_payload: extract_nested_object(event, "payload")
+                .map(|payload| truncate_chars(&payload.to_string(), RAW_PAYLOAD_MAX_CHARS)),
+        }),
+    }
+}
+
+// Lê "number" e "title" de um objeto aninhado do payload ("issue" ou "pull_request")
+// Qualquer um dos dois pode faltar; format_event cai na frase genérica
+fn issue_reference(
+    payload_obj: &JsonValue,
+    key: &str,
+) -> Result<(Option<u64>, Option<String>), ActivityError> {
+    match extract_nested_object(payload_obj, key) {
+        Some(object) => Ok((
+            extract_number_value(object, "number"),
+            extract_string_value(object, "title")?,
+        )),
+        None => Ok((None, None)),
     }
 }
 
 // FUNÇÕES AUXILIARES DE PARSING
-// Estas funções fazem o trabalho pesado de extrair valores do JSON
+// Estas funções leem campos da árvore montada por json::parse
+// Todas olham só as chaves do NÍVEL RAIZ do objeto recebido: pedir "type" ao evento
+// {"actor": {"type": "User"}, "type": "PushEvent"} devolve "PushEvent"
 
-// Extrai um valor numérico de um campo JSON
-// Exemplo: "size": 3 -> Some(3)
-fn extract_number_value(json: &str, key: &str) -> Option<usize> {
-    let pattern = format!("\"{}\":", key);
-    let start_pos = json.find(&pattern)?;
-
-    let after_colon = &json[start_pos + pattern.len()..].trim_start();
-
-    // Encontra onde o número termina (próximo caractere não-numérico)
-    let mut end_pos = 0;
-    for ch in after_colon.chars() {
-        if ch.is_numeric() {
-            end_pos += ch.len_utf8();
-        } else {
-            break;
+// CONCEITO: Enum genérico
+// O resultado de ler um campo distingue três situações que um Option juntaria:
+//   "ref": "main"  -> Value("main")
+//   "ref": null    -> Null  (o campo existe, mas está vazio de propósito)
+//   (sem "ref")    -> Missing
+// Assim quem chama decide o padrão certo para cada caso
+// Um valor de outro tipo (ex: número onde esperávamos string) conta como Missing
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum JsonField<T> {
+    Missing,
+    Null,
+    Value(T),
+}
+
+impl<T> JsonField<T> {
+    /// Descarta a diferença entre Null e Missing
+    pub fn value(self) -> Option<T> {
+        match self {
+            JsonField::Value(value) => Some(value),
+            JsonField::Null | JsonField::Missing => None,
         }
     }
+}
+
+// Lê um campo convertendo o valor com `read`
+// Cuida dos casos comuns a todos os extratores: chave ausente e null
+// CONCEITO: Lifetimes
+// 'v é o tempo de vida da árvore; `read` pode devolver referências para dentro dela
+fn extract_field<'v, 'a, T>(
+    json: &'v JsonValue<'a>,
+    key: &str,
+    read: impl Fn(&'v JsonValue<'a>) -> Option<T>,
+) -> JsonField<T> {
+    match json.get(key) {
+        None => JsonField::Missing,
+        Some(JsonValue::Null) => JsonField::Null,
+        Some(value) => read(value).map_or(JsonField::Missing, JsonField::Value),
+    }
+}
+
+// Extrai um valor numérico de um campo JSON
+// Exemplo: "size": 3 -> Value(3)
+// Genérico no tipo do número: usize para contagens, u64 para ids
+fn extract_number_field<T: std::str::FromStr>(json: &JsonValue, key: &str) -> JsonField<T> {
+    // Só dígitos: rejeita sinais, frações e expoentes (ids e contagens não os têm)
+    extract_field(json, key, |value| match value {
+        JsonValue::Number(text) if text.bytes().all(|b| b.is_ascii_digit()) => text.parse().ok(),
+        _ => None,
+    })
+}
+
+// Versão sem a distinção entre null e ausente
+pub(crate) fn extract_number_value<T: std::str::FromStr>(json: &JsonValue, key: &str) -> Option<T> {
+    extract_number_field(json, key).value()
+}
+
+// Extrai um valor booleano de um campo JSON
+// Exemplo: "merged": true -> Value(true)
+fn extract_bool_field(json: &JsonValue, key: &str) -> JsonField<bool> {
+    extract_field(json, key, |value| match value {
+        JsonValue::Bool(flag) => Some(*flag),
+        _ => None,
+    })
+}
+
+fn extract_bool_value(json: &JsonValue, key: &str) -> Option<bool> {
+    extract_bool_field(json, key).value()
+}
+
+// Extrai um valor string de um campo JSON, já decodificando os escapes
+// Exemplo: "name": "torvalds/linux" -> Ok(Value("torvalds/linux"))
+// Exemplo: "title": "Café" -> Ok(Value("Café"))
+//
+// Retorna:
+//   - Ok(Value(valor)) se a chave existe e o valor é uma string
+//   - Ok(Null) se o valor é null
+//   - Ok(Missing) se a chave não existe ou o valor não é uma string
+//   - Err(ParseError) se a string tem um escape inválido
+fn extract_string_field(json: &JsonValue, key: &str) -> Result<JsonField<String>, ActivityError> {
+    let raw = extract_field(json, key, |value| match value {
+        JsonValue::String(raw) => Some(*raw),
+        _ => None,
+    });
 
-    if end_pos == 0 {
-        return None;
+    match raw {
+        JsonField::Value(raw) => json::decode_string(raw).map(JsonField::Value),
+        JsonField::Null => Ok(JsonField::Null),
+        JsonField::Missing => Ok(JsonField::Missing),
     }
+}
+
+// Versão sem a distinção entre null e ausente:
+// Ok(Some(valor)) para strings, Ok(None) para null/ausente/outro tipo
+pub(crate) fn extract_string_value(json: &JsonValue, key: &str) -> Result<Option<String>, ActivityError> {
+    extract_string_field(json, key).map(JsonField::value)
+}
+
+// Extrai um objeto aninhado
+// Exemplo: "repo": {...} -> Some(&{...})
+// O resultado é uma referência para dentro da árvore, não uma cópia
+pub(crate) fn extract_nested_object<'v, 'a>(json: &'v JsonValue<'a>, key: &str) -> Option<&'v JsonValue<'a>> {
+    json.get(key).filter(|value| value.is_object())
+}
 
-    // Parse a string para número
-    after_colon[..end_pos].parse().ok()
+// Percorre os objetos de um array JSON
+// Exemplo: "commits": [{...}, {...}] -> os dois objetos, em ordem
+// Chave ausente (ou que não é um array) dá um iterator vazio; itens que não
+// são objetos são pulados
+// CONCEITO: impl Iterator no retorno
+// Quem chama pode usar for, count(), map()... sem que um Vec seja criado
+pub(crate) fn extract_array_objects<'v, 'a>(
+    json: &'v JsonValue<'a>,
+    key: &str,
+) -> impl Iterator<Item = &'v JsonValue<'a>> {
+    let items: &[JsonValue] = match json.get(key) {
+        Some(JsonValue::Array(items)) => items,
+        _ => &[],
+    };
+    items.iter().filter(|item| item.is_object())
 }
 
-// Extrai um valor string de um campo JSON
-// Exemplo: "name": "torvalds/linux" -> Some("torvalds/linux")
-fn extract_string_value(json: &str, key: &str) -> Option<String> {
-    // Monta o padrão de busca: "key":
-    // CONCEITO: format! macro
-    // Similar ao println!, mas retorna uma String ao invés de imprimir
-    let pattern = format!("\"{}\":", key);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // CONCEITO: find() retorna Option<usize>
-    // Some(posição) se encontrar, None se não encontrar
-    let start_pos = json.find(&pattern)?;
+    // Monta a árvore de um objeto de teste
+    fn obj(json: &str) -> JsonValue<'_> {
+        json::parse(json).unwrap()
+    }
 
-    // Pula para depois do ":"
-    let after_colon = &json[start_pos + pattern.len()..].trim_start();
+    #[test]
+    fn test_extractors_only_match_top_level_keys() {
+        let json = r#"{"payload": {"name": "nested", "list": [{"name": "in array"}]},
+            "note": "name", "name": "top"}"#;
 
-    // Verifica se o valor é uma string (começa com ")
-    if !after_colon.starts_with('"') {
-        return None;
+        assert_eq!(extract_string_value(&obj(json), "name").unwrap().as_deref(), Some("top"));
+        // Um valor igual ao nome da chave ("note": "name") não é uma chave
+        assert_eq!(extract_string_value(&obj(r#"{"note": "name"}"#), "name").unwrap(), None);
+        // Chaves dentro de strings também não contam
+        assert_eq!(extract_string_value(&obj(r#"{"text": "\"name\": 1"}"#), "name").unwrap(), None);
     }
 
-    // Encontra o fim da string (próximo " que não é escapado)
-    let value_start = 1;  // Pula o primeiro "
-    let mut end_pos = value_start;
-    let chars: Vec<char> = after_colon.chars().collect();
+    #[test]
+    fn test_parse_event_ignores_nested_keys_that_come_first() {
+        // "type", "name" e "action" aparecem aninhados ANTES dos campos verdadeiros
+        let json = r#"{
+            "actor": {"id": 1, "login": "octocat", "type": "User"},
+            "payload": {
+                "pull_request": {"action": "wrong", "number": 5, "head": {"repo": {"name": "fork/x"}}},
+                "action": "opened"
+            },
+            "type": "PullRequestEvent",
+            "repo": {"owner": {"name": "ignored"}, "name": "right/repo"}
+        }"#;
 
-    // CONCEITO: Loop while com condições
-    while end_pos < chars.len() {
-        if chars[end_pos] == '"' && chars[end_pos - 1] != '\\' {
-            // Encontrou o " final não-escapado
-            break;
+        let event = parse_event(json).unwrap();
+        assert_eq!(event.kind, EventKind::PullRequest);
+        assert_eq!(event.repo_name, "right/repo");
+        match event.payload {
+            EventPayload::PullRequestEvent { action, number, .. } => {
+                assert_eq!(action, "opened");
+                assert_eq!(number, Some(5));
+            }
+            other => panic!("unexpected {:?}", other),
         }
-        end_pos += 1;
     }
 
-    // Extrai a substring
-    let value: String = chars[value_start..end_pos].iter().collect();
-    Some(value)
-}
+    #[test]
+    fn test_extract_bool_value() {
+        assert_eq!(extract_bool_value(&obj(r#"{"merged": true}"#), "merged"), Some(true));
+        assert_eq!(extract_bool_value(&obj(r#"{"merged":false,"x":1}"#), "merged"), Some(false));
+        assert_eq!(extract_bool_value(&obj(r#"{"merged": null}"#), "merged"), None);
+        assert_eq!(extract_bool_value(&obj(r#"{"merged": "true"}"#), "merged"), None);
+        assert_eq!(extract_bool_value(&obj(r#"{"other": true}"#), "merged"), None);
+    }
 
-// Extrai um objeto aninhado
-// Exemplo: "repo": {...} -> Some("{...}")
-// CONCEITO: Lifetimes
-// 'a indica que a string retornada vive tanto quanto a string json de entrada
-// Isso é necessário porque retornamos uma fatia (slice) de json
-fn extract_nested_object<'a>(json: &'a str, key: &str) -> Option<&'a str> {
-    let pattern = format!("\"{}\":", key);
-    let start_pos = json.find(&pattern)?;
-
-    let after_colon = &json[start_pos + pattern.len()..].trim_start();
-
-    // Objeto deve começar com {
-    if !after_colon.starts_with('{') {
-        return None;
-    }
-
-    // Encontra o { correspondente rastreando profundidade
-    let mut depth = 0;
-    let mut end_pos = 0;
-
-    for (i, ch) in after_colon.chars().enumerate() {
-        match ch {
-            '{' => depth += 1,
-            '}' => {
-                depth -= 1;
-                if depth == 0 {
-                    end_pos = i + 1;
-                    break;
-                }
+    #[test]
+    fn test_extract_bool_field_distinguishes_null() {
+        let field = |json| extract_bool_field(&obj(json), "draft");
+        assert_eq!(field(r#"{"draft": true}"#), JsonField::Value(true));
+        assert_eq!(field(r#"{"draft":false}"#), JsonField::Value(false));
+        assert_eq!(field(r#"{"draft": null}"#), JsonField::Null);
+        assert_eq!(field(r#"{"public": true}"#), JsonField::Missing);
+    }
+
+    #[test]
+    fn test_extract_number_field_distinguishes_null() {
+        let field = |json| extract_number_field::<u64>(&obj(json), "size");
+        assert_eq!(field(r#"{"size": 3}"#), JsonField::Value(3));
+        assert_eq!(field(r#"{"size":0,"x":1}"#), JsonField::Value(0));
+        assert_eq!(field(r#"{"size": null}"#), JsonField::Null);
+        assert_eq!(field(r#"{"size": "3"}"#), JsonField::Missing);
+        assert_eq!(field(r#"{"size": -1}"#), JsonField::Missing);
+        assert_eq!(field(r#"{}"#), JsonField::Missing);
+    }
+
+    #[test]
+    fn test_extract_string_field_distinguishes_null() {
+        let field = |json| extract_string_field(&obj(json), "ref").unwrap();
+        assert_eq!(field(r#"{"ref": "main"}"#), JsonField::Value("main".to_string()));
+        assert_eq!(field(r#"{"ref": ""}"#), JsonField::Value(String::new()));
+        assert_eq!(field(r#"{"ref": null}"#), JsonField::Null);
+        assert_eq!(field(r#"{"ref":null}"#), JsonField::Null);
+        assert_eq!(field(r#"{"ref": 12}"#), JsonField::Missing);
+        assert_eq!(field(r#"{"ref_type": "tag"}"#), JsonField::Missing);
+
+        // A versão Option junta null e ausente
+        assert_eq!(extract_string_value(&obj(r#"{"ref": null}"#), "ref").unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_pull_request_merge_states() {
+        let parse_pr = |action: &str, merged: &str| {
+            let json = format!(
+                r#"{{"type": "PullRequestEvent", "repo": {{"name": "a/b"}},
+                    "payload": {{"action": "{}", "pull_request": {{"number": 1, "merged": {}}}}}}}"#,
+                action, merged
+            );
+            match parse_event(&json).unwrap().payload {
+                EventPayload::PullRequestEvent { action, merged, .. } => (action, merged),
+                other => panic!("unexpected {:?}", other),
+            }
+        };
+
+        assert_eq!(parse_pr("opened", "false"), ("opened".to_string(), false));
+        assert_eq!(parse_pr("closed", "false"), ("closed".to_string(), false));
+        assert_eq!(parse_pr("closed", "true"), ("closed".to_string(), true));
+    }
+
+    #[test]
+    fn test_parse_issue_and_pr_references() {
+        let issue = r#"{"type": "IssuesEvent", "repo": {"name": "a/b"},
+            "payload": {"action": "opened", "issue": {"number": 42, "title": "Fix crash on startup"}}}"#;
+        assert_eq!(
+            parse_event(issue).unwrap().payload,
+            EventPayload::IssuesEvent {
+                action: "opened".to_string(),
+                number: Some(42),
+                title: Some("Fix crash on startup".to_string()),
             }
-            _ => {}
+        );
+
+        let pr = r#"{"type": "PullRequestEvent", "repo": {"name": "a/b"},
+            "payload": {"action": "closed", "number": 7, "pull_request": {"number": 7, "title": "Add \"x\""}}}"#;
+        assert_eq!(
+            parse_event(pr).unwrap().payload,
+            EventPayload::PullRequestEvent {
+                action: "closed".to_string(),
+                number: Some(7),
+                title: Some("Add \"x\"".to_string()),
+                merged: false,
+            }
+        );
+
+        // Sem o objeto aninhado: só a ação
+        let bare = r#"{"type": "IssuesEvent", "repo": {"name": "a/b"}, "payload": {"action": "closed"}}"#;
+        assert_eq!(
+            parse_event(bare).unwrap().payload,
+            EventPayload::IssuesEvent { action: "closed".to_string(), number: None, title: None }
+        );
+    }
+
+    #[test]
+    fn test_parse_push_branch() {
+        let json = r#"{"type": "PushEvent", "repo": {"name": "a/b"},
+            "payload": {"size": 3, "ref": "refs/heads/feature-x"}}"#;
+
+        match parse_event(json).unwrap().payload {
+            EventPayload::Push { commit_count, distinct_count, branch, .. } => {
+                assert_eq!(commit_count, 3);
+                // Sem "distinct_size": todos os commits contam como novos
+                assert_eq!(distinct_count, 3);
+                assert_eq!(branch.as_deref(), Some("feature-x"));
+            }
+            other => panic!("unexpected {:?}", other),
         }
     }
 
-    if end_pos == 0 {
-        return None;
+    #[test]
+    fn test_parse_create_and_delete_refs() {
+        let tag = r#"{"type": "CreateEvent", "repo": {"name": "a/b"},
+            "payload": {"ref": "v1.2.0", "ref_type": "tag"}}"#;
+        assert_eq!(
+            parse_event(tag).unwrap().payload,
+            EventPayload::CreateEvent { ref_type: "tag".to_string(), ref_name: Some("v1.2.0".to_string()) }
+        );
+
+        let branch = r#"{"type": "DeleteEvent", "repo": {"name": "a/b"},
+            "payload": {"ref": "old-branch", "ref_type": "branch"}}"#;
+        assert_eq!(
+            parse_event(branch).unwrap().payload,
+            EventPayload::DeleteEvent {
+                ref_type: "branch".to_string(),
+                ref_name: Some("old-branch".to_string()),
+            }
+        );
+
+        // Repositório novo: "ref" é null
+        let repository = r#"{"type": "CreateEvent", "repo": {"name": "a/b"},
+            "payload": {"ref": null, "ref_type": "repository"}}"#;
+        assert_eq!(
+            parse_event(repository).unwrap().payload,
+            EventPayload::CreateEvent { ref_type: "repository".to_string(), ref_name: None }
+        );
     }
 
-    Some(&after_colon[..end_pos])
-}
+    // Evento GollumEvent com as páginas dadas como (page_name, action)
+    fn gollum(pages: &[(&str, &str)]) -> EventPayload {
+        let pages: Vec<String> = pages
+            .iter()
+            .map(|(name, action)| {
+                format!(
+                    r#"{{"page_name": "{}", "title": "{}", "summary": null, "action": "{}", "sha": "0123abc", "html_url": "https://github.com/a/b/wiki/{}"}}"#,
+                    name, name, action, name
+                )
+            })
+            .collect();
+        let json = format!(
+            r#"{{"type": "GollumEvent", "repo": {{"name": "a/b"}}, "payload": {{"pages": [{}]}}}}"#,
+            pages.join(", ")
+        );
+        parse_event(&json).unwrap().payload
+    }
+
+    #[test]
+    fn test_parse_gollum_pages() {
+        let summary = |payload| match payload {
+            EventPayload::Gollum { pages_edited, first_page, first_page_action } => {
+                (pages_edited, first_page, first_page_action)
+            }
+            other => panic!("unexpected {:?}", other),
+        };
+        let some = |text: &str| Some(text.to_string());
 
-// Extrai o tamanho de um array JSON
-// Exemplo: "commits": [{...}, {...}] -> Some(2)
-fn extract_array_length(json: &str, key: &str) -> Option<usize> {
-    let pattern = format!("\"{}\":", key);
-    let start_pos = json.find(&pattern)?;
+        assert_eq!(summary(gollum(&[("Home", "created")])), (1, some("Home"), some("created")));
+        assert_eq!(summary(gollum(&[("Setup", "edited")])), (1, some("Setup"), some("edited")));
+        assert_eq!(
+            summary(gollum(&[("Home", "edited"), ("FAQ", "created"), ("Setup", "edited")])),
+            (3, some("Home"), some("edited"))
+        );
+        assert_eq!(summary(gollum(&[])), (0, None, None));
+    }
+
+    #[test]
+    fn test_parse_pull_request_review_state() {
+        let json = r#"{"type": "PullRequestReviewEvent", "repo": {"name": "a/b"},
+            "payload": {"action": "created",
+                "review": {"id": 1, "user": {"login": "x", "type": "User"}, "state": "changes_requested"},
+                "pull_request": {"number": 3, "state": "open"}}}"#;
+        assert_eq!(
+            parse_event(json).unwrap().payload,
+            EventPayload::PullRequestReview {
+                action: "created".to_string(),
+                state: "changes_requested".to_string(),
+            }
+        );
+
+        // Sem a revisão no payload, o estado é desconhecido
+        let bare = r#"{"type": "PullRequestReviewEvent", "repo": {"name": "a/b"}, "payload": {}}"#;
+        assert_eq!(
+            parse_event(bare).unwrap().payload,
+            EventPayload::PullRequestReview { action: "created".to_string(), state: "unknown".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_unknown_event_keeps_raw_payload() {
+        let json = r#"{"type": "SponsorshipEvent", "repo": {"name": "a/b"},
+            "payload": {"action": "created", "tier": {"name": "Café ☕", "monthly_price_in_cents": 500}}}"#;
+        let raw = r#"{"action":"created","tier":{"name":"Café ☕","monthly_price_in_cents":500}}"#;
+        assert_eq!(
+            parse_event(json).unwrap().payload,
+            EventPayload::Unknown { raw_payload: Some(raw.to_string()) }
+        );
+
+        // Sem payload não há o que guardar
+        let bare = r#"{"type": "SponsorshipEvent", "repo": {"name": "a/b"}}"#;
+        assert_eq!(parse_event(bare).unwrap().payload, EventPayload::Unknown { raw_payload: None });
+
+        // Payloads enormes são cortados
+        let huge = format!(
+            r#"{{"type": "NewEvent", "repo": {{"name": "a/b"}}, "payload": {{"body": "{}"}}}}"#,
+            "é".repeat(5000)
+        );
+        match parse_event(&huge).unwrap().payload {
+            EventPayload::Unknown { raw_payload: Some(raw) } => {
+                assert_eq!(raw.chars().count(), RAW_PAYLOAD_MAX_CHARS + "...".len());
+                assert!(raw.ends_with("..."));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_push_without_ref() {
+        let json = r#"{"type": "PushEvent", "repo": {"name": "a/b"}, "payload": {"size": 1}}"#;
+        match parse_event(json).unwrap().payload {
+            EventPayload::Push { branch, .. } => assert_eq!(branch, None),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_event_actor() {
+        let json = r#"{"type": "WatchEvent", "actor": {"id": 1024025, "login": "torvalds",
+            "avatar_url": "https://avatars.githubusercontent.com/u/1024025?"},
+            "repo": {"id": 2325298, "name": "torvalds/linux"}}"#;
+
+        let actor = parse_event(json).unwrap().actor.unwrap();
+        assert_eq!(actor.login, "torvalds");
+        assert_eq!(actor.id, 1_024_025);
+
+        // Sem id (ou sem login) o ator é considerado ausente
+        let partial = r#"{"type": "WatchEvent", "actor": {"login": "x"}, "repo": {"name": "a/b"}}"#;
+        assert!(parse_event(partial).unwrap().actor.is_none());
+    }
+
+    #[test]
+    fn test_parse_event_id() {
+        let with_id = |id: &str| {
+            let json = format!(r#"{{"id": {}, "type": "WatchEvent", "repo": {{"name": "a/b"}}}}"#, id);
+            parse_event(&json).unwrap().id
+        };
+
+        assert_eq!(with_id(r#""38941057321""#), Some(38_941_057_321));
+        assert_eq!(with_id("38941057321"), Some(38_941_057_321));
+        // Um id estranho não descarta o evento
+        assert_eq!(with_id(r#""abc""#), None);
+        assert_eq!(with_id("null"), None);
+        assert_eq!(parse_event(r#"{"type": "WatchEvent", "repo": {"name": "a/b"}}"#).unwrap().id, None);
+    }
+
+    #[test]
+    fn test_parse_events_reports_skipped_events() {
+        let json = r#"[
+            {"type": "WatchEvent", "repo": {"name": "a/one"}},
+            {"repo": {"name": "a/missing-type"}},
+            {"type": "ForkEvent", "repo": {"name": "a/two"}},
+            {"type": "WatchEvent"}
+        ]"#;
+
+        let outcome = parse_events(json).unwrap();
+
+        assert_eq!(outcome.events.len(), 2);
+        let indices: Vec<usize> = outcome.warnings.iter().map(|warning| warning.index).collect();
+        assert_eq!(indices, vec![1, 3]);
+        assert_eq!(
+            outcome.warnings[0].error.to_string(),
+            r#"Failed to parse event #1: Missing 'type' field near: {"repo": {"name": "a/missing-type"}}"#
+        );
+    }
+
+    #[test]
+    fn test_parse_warning_into_error_names_the_event() {
+        let outcome = parse_events(r#"[{"type": "WatchEvent", "repo": {"name": "a/b"}}, {"type": "WatchEvent"}]"#)
+            .unwrap();
+        let error = outcome.warnings.into_iter().next().unwrap().into_error();
+
+        assert_eq!(
+            error.to_string(),
+            r#"Failed to parse event #1: Missing 'repo' field near: {"type": "WatchEvent"}"#
+        );
+    }
+
+    #[test]
+    fn test_parse_outcome_append_offsets_indices() {
+        let mut all = parse_events(r#"[{"type": "WatchEvent", "repo": {"name": "a/b"}}, {}]"#).unwrap();
+        all.append(parse_events(r#"[{}]"#).unwrap());
+
+        let indices: Vec<usize> = all.warnings.iter().map(|warning| warning.index).collect();
+        assert_eq!(indices, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_parse_error_snippet_points_at_the_problem() {
+        // Erro de sintaxe: o trecho fica em volta da posição do problema, não no começo
+        let json = format!(r#"{{"type": "WatchEvent", "padding": "{}", "repo": {{"name" "a/b"}}}}"#, "x".repeat(100));
+        match parse_event(&json) {
+            Err(ActivityError::ParseError { snippet, .. }) => {
+                assert!(snippet.starts_with('…'), "{}", snippet);
+                assert!(snippet.contains(r#"{"name" "a/b"}"#), "{}", snippet);
+            }
+            other => panic!("expected ParseError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_error_body_json() {
+        let body = r#"{"message":"Not Found","documentation_url":"https://docs.github.com/rest"}"#;
+        assert_eq!(
+            parse_error_body(body),
+            ("Not Found".to_string(), Some("https://docs.github.com/rest".to_string()))
+        );
+        assert_eq!(parse_error_body(r#"{"message":"Bad credentials"}"#).1, None);
+    }
+
+    #[test]
+    fn test_parse_error_body_fallbacks() {
+        assert_eq!(parse_error_body("Bad Gateway\n"), ("Bad Gateway".to_string(), None));
+        assert_eq!(parse_error_body("  "), ("Unknown error".to_string(), None));
+        // Objeto JSON sem "message": mostramos o texto cru
+        assert_eq!(parse_error_body(r#"{"error":"x"}"#).0, r#"{"error":"x"}"#);
 
-    let after_colon = &json[start_pos + pattern.len()..].trim_start();
+        let long = "é".repeat(250);
+        let (message, _) = parse_error_body(&long);
+        assert_eq!(message, format!("{}...", "é".repeat(200)));
+    }
+
+    #[test]
+    fn test_extract_string_value_plain() {
+        let json = r#"{"name": "torvalds/linux"}"#;
+        assert_eq!(extract_string_value(&obj(json), "name").unwrap(), Some("torvalds/linux".to_string()));
+        assert_eq!(extract_string_value(&obj(json), "missing").unwrap(), None);
+    }
+
+    #[test]
+    fn test_extract_string_value_decodes_escapes() {
+        let json = r#"{"msg": "say \"hi\"\n\tC:\\temp\/x"}"#;
+        assert_eq!(
+            extract_string_value(&obj(json), "msg").unwrap(),
+            Some("say \"hi\"\n\tC:\\temp/x".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_string_value_escaped_backslash_before_quote() {
+        // A string termina em uma barra escapada; a aspa seguinte fecha a string
+        let json = r#"{"path": "C:\\", "next": "x"}"#;
+        assert_eq!(extract_string_value(&obj(json), "path").unwrap(), Some("C:\\".to_string()));
+    }
+
+    #[test]
+    fn test_extract_string_value_accented_name() {
+        let json = r#"{"name": "Jos\u00e9 Ara\u00fajo"}"#;
+        assert_eq!(extract_string_value(&obj(json), "name").unwrap(), Some("José Araújo".to_string()));
+    }
+
+    #[test]
+    fn test_extract_string_value_surrogate_pair_emoji() {
+        let json = r#"{"message": "Ship it \ud83d\ude80"}"#;
+        assert_eq!(extract_string_value(&obj(json), "message").unwrap(), Some("Ship it 🚀".to_string()));
+    }
+
+    #[test]
+    fn test_extract_string_value_raw_unicode() {
+        let json = r#"{"message": "修复 🦀"}"#;
+        assert_eq!(extract_string_value(&obj(json), "message").unwrap(), Some("修复 🦀".to_string()));
+    }
+
+    #[test]
+    fn test_extract_string_value_invalid_escapes() {
+        for json in [
+            r#"{"s": "bad \x escape"}"#,
+            r#"{"s": "short \u12"}"#,
+            r#"{"s": "lone \ud83d high"}"#,
+            r#"{"s": "lone \ude80 low"}"#,
+            r#"{"s": "bad pair \ud83d\u0041"}"#,
+        ] {
+            match extract_string_value(&obj(json), "s") {
+                Err(ActivityError::ParseError { message, .. }) => assert!(message.contains("\\"), "{}", message),
+                other => panic!("expected ParseError for {}, got {:?}", json, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_extract_string_value_reports_offending_sequence() {
+        let err = extract_string_value(&obj(r#"{"s": "a\qb"}"#), "s").unwrap_err();
+        assert!(err.to_string().contains("\\q"));
+    }
 
-    // Array deve começar com [
-    if !after_colon.starts_with('[') {
-        return None;
+    #[test]
+    fn test_parse_event_created_at() {
+        let event = parse_event(
+            r#"{"type":"WatchEvent","repo":{"name":"a/b"},"payload":{},"created_at":"2024-05-01T12:00:00Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(event.created_at, Timestamp::parse_rfc3339("2024-05-01T12:00:00Z"));
     }
 
-    // Conta objetos separados por vírgula no nível raiz do array
-    let mut depth = 0;
-    let mut count = 0;
-    let mut in_array = false;
+    #[test]
+    fn test_parse_event_missing_or_malformed_created_at_keeps_event() {
+        let missing = parse_event(r#"{"type":"WatchEvent","repo":{"name":"a/b"}}"#).unwrap();
+        assert_eq!(missing.created_at, None);
 
-    for ch in after_colon.chars() {
-        match ch {
-            '[' => {
-                depth += 1;
-                in_array = true;
+        let malformed =
+            parse_event(r#"{"type":"WatchEvent","repo":{"name":"a/b"},"created_at":"last tuesday"}"#)
+                .unwrap();
+        assert_eq!(malformed.created_at, None);
+        assert_eq!(malformed.repo_name, "a/b");
+    }
+
+    #[test]
+    fn test_parse_events_ignores_braces_inside_strings() {
+        let json = r#"[{"type": "WatchEvent", "repo": {"name": "a/b"}, "m": "a } b { c, ]", "é": "ü"},
+            {"type": "ForkEvent", "repo": {"name": "a/🚀"}}]"#;
+        let outcome = parse_events(json).unwrap();
+
+        assert!(outcome.warnings.is_empty());
+        assert_eq!(outcome.events.len(), 2);
+        assert_eq!(outcome.events[1].repo_name, "a/🚀");
+    }
+
+    #[test]
+    fn test_parse_events_malformed_event_is_a_warning() {
+        // O segundo evento não é JSON válido, mas o array ainda é delimitável
+        let json = r#"[{"type": "WatchEvent", "repo": {"name": "a/b"}},
+            {"type": "WatchEvent", "repo": {"name": }}]"#;
+        let outcome = parse_events(json).unwrap();
+
+        assert_eq!(outcome.events.len(), 1);
+        assert_eq!(outcome.warnings[0].index, 1);
+        assert!(outcome.warnings[0].error.to_string().contains("at byte"));
+    }
+
+    #[test]
+    fn test_parse_events_rejects_non_arrays() {
+        for text in ["", "{}", "[{}", "[{}] trailing"] {
+            assert!(parse_events(text).is_err(), "{:?} should be rejected", text);
+        }
+    }
+
+    #[test]
+    fn test_parse_events_surfaces_api_message_from_object() {
+        let body = r#"{"message": "Not Found", "documentation_url": "https://docs.github.com/rest"}"#;
+        match parse_events(body) {
+            Err(ActivityError::ApiError { status, message, documentation_url }) => {
+                assert_eq!(status, 0);
+                assert_eq!(message, "Not Found");
+                assert_eq!(documentation_url.as_deref(), Some("https://docs.github.com/rest"));
             }
-            ']' => {
-                depth -= 1;
-                if depth == 0 {
-                    // Fim do array
-                    // Se encontramos pelo menos um caractere não-whitespace, conta como 1 item
-                    // Arrays vazios [] têm count = 0
-                    break;
+            other => panic!("expected ApiError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_events_non_array_error_includes_preview() {
+        let message = |text: &str| parse_events(text).unwrap_err().to_string();
+
+        assert_eq!(
+            message("<html>Bad Gateway</html>"),
+            "Failed to parse response: Expected JSON array, got: <html>Bad Gateway</html>"
+        );
+        // Objeto sem "message": também é só uma resposta inesperada
+        assert!(message(r#"{"error": "x"}"#).ends_with(r#"got: {"error": "x"}"#));
+
+        let long = "x".repeat(500);
+        assert!(message(&long).ends_with(&format!("got: {}...", "x".repeat(100))));
+    }
+
+    #[test]
+    fn test_parse_events_empty_and_whitespace_bodies() {
+        for body in ["", "  \n", "\r\n\t", "\u{feff}", "\u{feff} \n"] {
+            match parse_events(body) {
+                Err(error @ ActivityError::ParseError { .. }) => {
+                    assert_eq!(error.to_string(), "Failed to parse response: empty response from server", "{:?}", body)
                 }
+                other => panic!("expected ParseError for {:?}, got {:?}", body, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_events_skips_a_bom_and_surrounding_whitespace() {
+        let expected = parse_events(USER_EVENTS).unwrap().events;
+        assert!(!expected.is_empty());
+
+        for body in [
+            format!("\u{feff}{}", USER_EVENTS),
+            format!("\u{feff}\n  {}\n\n", USER_EVENTS),
+            format!("\r\n\t{}\r\n", USER_EVENTS),
+        ] {
+            assert_eq!(parse_events(&body).unwrap().events, expected);
+        }
+        assert!(parse_events("\u{feff}[]\n").unwrap().events.is_empty());
+
+        // Só o BOM do começo é ignorado: no meio do texto ele continua sendo um erro
+        assert!(parse_events(" \u{feff}[]").is_err());
+    }
+
+    #[test]
+    fn test_profile_and_repositories_share_the_bom_and_empty_body_handling() {
+        let empty = "Failed to parse response: empty response from server";
+        for body in ["", " \r\n", "\u{feff}", "\u{feff}\n"] {
+            assert_eq!(parse_user_profile(body).unwrap_err().to_string(), empty, "{:?}", body);
+            assert_eq!(parse_repositories(body).unwrap_err().to_string(), empty, "{:?}", body);
+            assert_eq!(parse_rate_limit(body).unwrap_err().to_string(), empty, "{:?}", body);
+        }
+
+        let profile = parse_user_profile("\u{feff}{\"login\": \"torvalds\", \"followers\": 3}\n").unwrap();
+        assert_eq!((profile.login.as_str(), profile.followers), ("torvalds", 3));
+
+        let repositories = parse_repositories("\u{feff}\n[{\"full_name\": \"torvalds/linux\"}]").unwrap();
+        assert_eq!(repositories.len(), 1);
+        assert_eq!(repositories[0].full_name, "torvalds/linux");
+
+        let rate = parse_rate_limit("\u{feff}{\"rate\": {\"limit\": 60, \"remaining\": 54, \"reset\": 1714584720}}").unwrap();
+        assert_eq!(rate.remaining, 54);
+    }
+
+    #[test]
+    fn test_parse_event_tolerates_whitespace_around_colons() {
+        let json = "{ \"type\" :\n \"WatchEvent\" , \"repo\"\t: { \"name\" : \"a/b\" } }";
+        assert_eq!(parse_event(json).unwrap().repo_name, "a/b");
+    }
+
+    // Respostas no formato da API do GitHub, em tests/fixtures/
+    // CONCEITO: include_str!
+    // Embute o conteúdo do arquivo no binário de teste, em tempo de compilação
+    const USER_EVENTS: &str = include_str!("../tests/fixtures/user_events.json");
+    const REPO_EVENTS: &str = include_str!("../tests/fixtures/repo_events.json");
+    const EMPTY_EVENTS: &str = include_str!("../tests/fixtures/empty_events.json");
+    const PUSH_EVENTS: &str = include_str!("../tests/fixtures/push_events.json");
+    const MEMBER_PUBLIC_EVENTS: &str = include_str!("../tests/fixtures/member_public_events.json");
+    const FORK_EVENTS: &str = include_str!("../tests/fixtures/fork_events.json");
+    const RELEASE_EVENTS: &str = include_str!("../tests/fixtures/release_events.json");
+
+    // Evento esperado com o ator octocat (id 583231), o autor de todos os eventos das fixtures
+    fn octocat_event(
+        id: u64,
+        kind: EventKind,
+        repo: &str,
+        payload: EventPayload,
+        created_at: &str,
+    ) -> GitHubEvent {
+        GitHubEvent::new(
+            kind,
+            repo.to_string(),
+            payload,
+            Timestamp::parse_rfc3339(created_at),
+        )
+        .with_id(Some(id))
+        .with_actor(Some(Actor { login: "octocat".to_string(), id: 583231 }))
+    }
+
+    #[test]
+    fn test_fixture_user_events() {
+        let outcome = parse_events(USER_EVENTS).unwrap();
+        assert!(outcome.warnings.is_empty(), "{:?}", outcome.warnings);
+
+        let commit = |sha: &str, message: &str| CommitSummary {
+            sha: sha.to_string(),
+            message: message.to_string(),
+        };
+
+        // CONCEITO: Igualdade estrutural
+        // Com PartialEq derivado, um único assert_eq! compara todos os campos de todos os eventos
+        let expected = vec![
+            octocat_event(
+                38_941_057_321,
+                EventKind::Push,
+                "octocat/Hello-World",
+                EventPayload::Push {
+                    commit_count: 2,
+                    distinct_count: 2,
+                    push_count: 1,
+                    branch: Some("feature/json-parser".to_string()),
+                    commits: vec![
+                        commit(
+                            "553c2077f0edc3d5dc5d17262f6aa498e69d6f8e",
+                            "Handle \"key\" : value {with braces} and [brackets], ref: main",
+                        ),
+                        commit(
+                            "7fd1a60b01f91b314f59955a4e4d4e80d8edf11d",
+                            "Corrige acentuação 🚀\n\nPath: C:\\temp\\",
+                        ),
+                    ],
+                },
+                "2024-05-01T12:00:00Z",
+            ),
+            octocat_event(
+                38_941_057_322,
+                EventKind::PullRequest,
+                "octocat/Hello-World",
+                EventPayload::PullRequestEvent {
+                    action: "closed".to_string(),
+                    number: Some(1347),
+                    title: Some("Amazing new feature: {json} é \"fun\"".to_string()),
+                    merged: true,
+                },
+                "2024-04-30T09:15:42Z",
+            ),
+            octocat_event(
+                38_941_057_323,
+                EventKind::Issues,
+                "octocat/Spoon-Knife",
+                EventPayload::IssuesEvent {
+                    action: "opened".to_string(),
+                    number: Some(42),
+                    title: Some("Found a bug".to_string()),
+                },
+                "2024-04-29T18:03:11Z",
+            ),
+            octocat_event(
+                38_941_057_324,
+                EventKind::Create,
+                "octocat/linguist",
+                EventPayload::CreateEvent {
+                    ref_type: "tag".to_string(),
+                    ref_name: Some("v1.2.0".to_string()),
+                },
+                "2024-04-28T07:45:00Z",
+            ),
+            octocat_event(
+                38_941_057_325,
+                EventKind::Create,
+                "octocat/new-repo",
+                EventPayload::CreateEvent { ref_type: "repository".to_string(), ref_name: None },
+                "2024-04-27T21:30:05Z",
+            ),
+            octocat_event(
+                38_941_057_326,
+                EventKind::Watch,
+                "torvalds/linux",
+                EventPayload::WatchEvent,
+                "2024-04-26T14:00:00Z",
+            ),
+            octocat_event(
+                38_941_057_327,
+                EventKind::Other("SponsorshipEvent".to_string()),
+                "octocat/Hello-World",
+                EventPayload::Unknown { raw_payload: Some("{}".to_string()) },
+                "2024-04-25T10:10:10Z",
+            ),
+        ];
+
+        assert_eq!(outcome.events, expected);
+        assert_eq!(outcome.events[1].payload.summary_labels(&EventKind::PullRequest).1, "PRs merged");
+        assert!(outcome.events[6].is_unknown());
+    }
+
+    #[test]
+    fn test_fixture_repo_events() {
+        let outcome = parse_events(REPO_EVENTS).unwrap();
+        assert!(outcome.warnings.is_empty(), "{:?}", outcome.warnings);
+
+        let logins: Vec<&str> = outcome
+            .events
+            .iter()
+            .filter_map(|event| event.actor.as_ref().map(|actor| actor.login.as_str()))
+            .collect();
+        assert_eq!(logins, vec!["torvalds", "gregkh", "contributor-2"]);
+        assert!(outcome.events.iter().all(|event| event.repo_name == "torvalds/linux"));
+        match &outcome.events[0].payload {
+            EventPayload::DeleteEvent { ref_type, ref_name } => {
+                assert_eq!(ref_type, "branch");
+                assert_eq!(ref_name.as_deref(), Some("old-branch"));
             }
-            '{' => {
-                depth += 1;
-                if depth == 2 {
-                    // Profundidade 2 significa um objeto dentro do array
-                    count += 1;
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_fixture_push_events_distinct_counts() {
+        let outcome = parse_events(PUSH_EVENTS).unwrap();
+        assert!(outcome.warnings.is_empty(), "{:?}", outcome.warnings);
+
+        // (total, novos) de cada push: normal, force-push e push vazio
+        let counts: Vec<(usize, usize)> = outcome
+            .events
+            .iter()
+            .map(|event| match event.payload {
+                EventPayload::Push { commit_count, distinct_count, .. } => (commit_count, distinct_count),
+                ref other => panic!("unexpected {:?}", other),
+            })
+            .collect();
+        assert_eq!(counts, vec![(3, 3), (2, 0), (0, 0)]);
+    }
+
+    #[test]
+    fn test_push_commit_count_falls_back_to_the_commit_list_then_one() {
+        let count = |payload: &str| {
+            let json = format!(r#"{{"type": "PushEvent", "repo": {{"name": "a/b"}}, "payload": {}}}"#, payload);
+            match parse_event(&json).unwrap().payload {
+                EventPayload::Push { commit_count, .. } => commit_count,
+                other => panic!("unexpected {:?}", other),
+            }
+        };
+        let two_commits = r#"[{"sha": "1", "message": "a"}, {"sha": "2", "message": "b"}]"#;
+
+        assert_eq!(count(&format!(r#"{{"size": 5, "commits": {}}}"#, two_commits)), 5);
+        assert_eq!(count(&format!(r#"{{"commits": {}}}"#, two_commits)), 2);
+        assert_eq!(count(r#"{"commits": []}"#), 1);
+        assert_eq!(count("{}"), 1);
+    }
+
+    #[test]
+    fn test_fixture_member_and_public_events() {
+        let outcome = parse_events(MEMBER_PUBLIC_EVENTS).unwrap();
+        assert!(outcome.warnings.is_empty(), "{:?}", outcome.warnings);
+
+        assert_eq!(
+            outcome.events,
+            vec![
+                octocat_event(
+                    42_000_000_001,
+                    EventKind::Member,
+                    "octocat/Hello-World",
+                    EventPayload::Member {
+                        action: "added".to_string(),
+                        member_login: Some("hubot".to_string()),
+                    },
+                    "2024-08-10T09:00:00Z",
+                ),
+                octocat_event(
+                    42_000_000_002,
+                    EventKind::Public,
+                    "octocat/secret-project",
+                    EventPayload::Public,
+                    "2024-08-09T17:30:00Z",
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fixture_fork_events() {
+        let outcome = parse_events(FORK_EVENTS).unwrap();
+        assert!(outcome.warnings.is_empty(), "{:?}", outcome.warnings);
+
+        // Com o objeto forkee, sabemos para onde o fork foi; sem ele, não
+        assert_eq!(
+            outcome.events,
+            vec![
+                octocat_event(
+                    42_000_000_011,
+                    EventKind::Fork,
+                    "torvalds/linux",
+                    EventPayload::ForkEvent { forkee: Some("octocat/linux".to_string()) },
+                    "2024-08-12T14:05:00Z",
+                ),
+                octocat_event(
+                    42_000_000_012,
+                    EventKind::Fork,
+                    "octocat/Hello-World",
+                    EventPayload::ForkEvent { forkee: None },
+                    "2024-08-11T08:20:00Z",
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fixture_release_events() {
+        let outcome = parse_events(RELEASE_EVENTS).unwrap();
+        assert!(outcome.warnings.is_empty(), "{:?}", outcome.warnings);
+
+        let releases: Vec<(&str, Option<&str>, Option<&str>, bool)> = outcome
+            .events
+            .iter()
+            .map(|event| match &event.payload {
+                EventPayload::ReleaseEvent { action, tag, name, draft } => {
+                    (action.as_str(), tag.as_deref(), name.as_deref(), *draft)
                 }
+                other => panic!("unexpected {:?}", other),
+            })
+            .collect();
+
+        assert_eq!(
+            releases,
+            vec![
+                ("published", Some("v1.4.0"), Some("Spring cleaning"), false),
+                ("edited", Some("v1.3.2"), Some("v1.3.2"), false),
+                ("created", Some("v2.0.0-rc.1"), Some(""), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fixture_empty_events() {
+        let outcome = parse_events(EMPTY_EVENTS).unwrap();
+        assert!(outcome.events.is_empty());
+        assert!(outcome.warnings.is_empty());
+    }
+
+    // Textos multi-byte de vários tamanhos (2, 3 e 4 bytes por caractere)
+    const MULTIBYTE: [&str; 4] = ["é", "修复", "🦀🚀", r"a\u00e9\ud83d\ude80"];
+
+    #[test]
+    fn test_multibyte_text_in_every_field() {
+        for text in MULTIBYTE {
+            let json = format!(
+                r#"[{{"id": "{t}", "type": "PullRequestEvent",
+                    "actor": {{"id": 1, "login": "{t}", "display_login": "{t}"}},
+                    "repo": {{"id": 2, "name": "{t}/{t}"}},
+                    "payload": {{"action": "{t}", "pull_request": {{"number": 3, "title": "{t}",
+                        "body": "{t} {{ }} [ ]", "head": {{"ref": "{t}"}}}}}},
+                    "created_at": "{t}"}},
+                  {{"type": "PushEvent", "repo": {{"name": "{t}"}},
+                    "payload": {{"size": 1, "ref": "refs/heads/{t}",
+                        "commits": [{{"message": "{t}", "author": {{"name": "{t}"}}}}]}}}}]"#,
+                t = text
+            );
+
+            let outcome = parse_events(&json).unwrap();
+            assert!(outcome.warnings.is_empty(), "{:?}", outcome.warnings);
+
+            let decoded = json::decode_string(text).unwrap();
+            assert_eq!(outcome.events[0].repo_name, format!("{0}/{0}", decoded));
+            assert_eq!(outcome.events[1].repo_name, decoded);
+            match &outcome.events[0].payload {
+                EventPayload::PullRequestEvent { title, .. } => {
+                    assert_eq!(title.as_deref(), Some(decoded.as_str()))
+                }
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_events_never_panics_on_truncated_input() {
+        // Corta a resposta em cada fronteira de caractere: o resultado pode ser
+        // Ok ou Err, mas nunca um panic
+        let text = REPO_EVENTS.replace("gregkh", "grégkh🦀");
+        for (end, _) in text.char_indices() {
+            let _ = parse_events(&text[..end]);
+        }
+    }
+
+    #[test]
+    fn test_parse_events_never_panics_on_random_input() {
+        use crate::generator::Rng;
+
+        // Pedaços que exercitam o tokenizer: estrutura, escapes e multi-byte
+        let pieces = [
+            "[", "]", "{", "}", ",", ":", "\"", "\\", "\\u", "d83d", "null", "true", "-", "0",
+            "1e", ".", " ", "\n", "é", "修", "🦀", r#""type""#, r#""repo""#, r#""name""#,
+            r#""payload""#, r#""PushEvent""#,
+        ];
+        let mut rng = Rng::new(792);
+
+        for _ in 0..2_000 {
+            let length = rng.below(40);
+            let mut text = String::from("[");
+            for _ in 0..length {
+                let piece = rng.pick(&pieces);
+                text.push_str(piece);
             }
-            '}' => depth -= 1,
-            _ => {}
+            // Só não pode entrar em panic
+            let _ = parse_events(&text);
+            let _ = parse_error_body(&text);
         }
     }
 
-    if !in_array {
-        return None;
+    #[test]
+    fn test_parse_user_profile() {
+        let json = r#"{"login": "torvalds", "id": 1024025, "name": "Linus Torvalds",
+            "company": "Linux Foundation", "location": "Portland, OR", "bio": null,
+            "public_repos": 4, "followers": 180000, "following": 0}"#;
+
+        let profile = parse_user_profile(json).unwrap();
+        assert_eq!(profile.login, "torvalds");
+        assert_eq!(profile.name.as_deref(), Some("Linus Torvalds"));
+        assert_eq!(profile.followers, 180_000);
+        assert_eq!(profile.public_repos, 4);
+        assert_eq!(profile.company.as_deref(), Some("Linux Foundation"));
+        assert_eq!(profile.location.as_deref(), Some("Portland, OR"));
+    }
+
+    #[test]
+    fn test_parse_user_profile_optional_fields() {
+        // name/company/location null e contagens ausentes não são erro
+        let profile = parse_user_profile(r#"{"login": "ghost", "name": null, "company": null}"#).unwrap();
+        assert_eq!(profile.name, None);
+        assert_eq!(profile.company, None);
+        assert_eq!(profile.followers, 0);
+
+        // Sem login não há o que mostrar
+        assert!(parse_user_profile(r#"{"name": "Nobody"}"#).is_err());
+        assert!(parse_user_profile("[]").is_err());
     }
 
-    Some(count)
+    #[test]
+    fn test_parse_repositories() {
+        let json = r#"[
+            {"id": 2325298, "name": "linux", "full_name": "torvalds/linux",
+             "owner": {"login": "torvalds", "full_name": "wrong"},
+             "description": "Linux kernel source tree", "stargazers_count": 180123, "language": "C",
+             "fork": false, "updated_at": "2024-05-20T09:12:44Z"},
+            {"full_name": "octocat/empty", "description": null, "language": null, "fork": true, "updated_at": null}
+        ]"#;
+
+        let repositories = parse_repositories(json).unwrap();
+        assert_eq!(
+            repositories[0],
+            Repository {
+                full_name: "torvalds/linux".to_string(),
+                name: "linux".to_string(),
+                description: Some("Linux kernel source tree".to_string()),
+                stargazers_count: 180_123,
+                language: Some("C".to_string()),
+                fork: false,
+                updated_at: Timestamp::parse_rfc3339("2024-05-20T09:12:44Z"),
+            }
+        );
+        assert_eq!(repositories[1].name, "empty");
+        assert_eq!(repositories[1].description, None);
+        assert_eq!(repositories[1].stargazers_count, 0);
+        assert!(repositories[1].fork);
+        assert_eq!(repositories[1].updated_at, None);
+        assert!(parse_repositories("[]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_repositories_errors() {
+        let error = parse_repositories(r#"[{"full_name": "a/b"}, {"name": "c"}]"#).unwrap_err();
+        assert!(error.to_string().contains("repository #1: Missing"), "{}", error);
+        assert!(error.to_string().contains("full_name"), "{}", error);
+        assert!(parse_repositories("[1]").is_err());
+
+        // O erro da API que chega com status de sucesso mostra a mensagem do GitHub
+        match parse_repositories(r#"{"message": "Not Found"}"#) {
+            Err(ActivityError::ApiError { message, .. }) => assert_eq!(message, "Not Found"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_rate_limit() {
+        let json = r#"{"resources": {"core": {"limit": 5000, "remaining": 4999, "reset": 1714584000},
+            "search": {"limit": 10, "remaining": 10, "reset": 1714580460}},
+            "rate": {"limit": 60, "remaining": 54, "reset": 1714584720, "used": 6, "resource": "core"}}"#;
+
+        assert_eq!(
+            parse_rate_limit(json).unwrap(),
+            RateLimitInfo {
+                limit: 60,
+                remaining: 54,
+                reset_at: Some(Timestamp::from_epoch_seconds(1714584720)),
+            }
+        );
+
+        assert!(parse_rate_limit(r#"{"resources": {}}"#).is_err());
+        assert!(parse_rate_limit(r#"{"rate": {"limit": 60}}"#).is_err());
+    }
 }
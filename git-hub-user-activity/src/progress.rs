@@ -0,0 +1,141 @@
+//! Este módulo mostra o andamento de uma busca de várias páginas (--all)
+//!
+//! Uma linha só, reescrita a cada página com um retorno de carro (`\r`):
+//!
+//! ```text
+//! Fetching page 3/10 … 90 events
+//! ```
+//!
+//! O total vem do `rel="last"` do Link header da API; sem ele, um "spinner"
+//! (`| / - \`) gira no lugar. A linha é apagada antes da saída de verdade,
+//! e só aparece quando o stderr é um terminal (ver main::show_progress)
+//!
+//! Quem desenha é um [`Progress`]: o programa escolhe entre a linha no stderr e
+//! nada ([`Hidden`]); os testes desenham a linha num `Vec<u8>`
+
+use std::io::Write;
+
+// Os quadros do spinner, um por atualização
+const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+
+// CONCEITO: Traits como ponto de extensão (como o Opener de browser.rs)
+// Quem busca as páginas só avisa o andamento; o programa decide se e como mostrar
+pub trait Progress {
+    // A página `page` vai ser buscada; `total` é None quando a API não informa
+    fn update(&mut self, page: usize, total: Option<usize>, events: usize);
+    // Apaga o que foi mostrado, antes da saída de verdade
+    fn clear(&mut self);
+}
+
+// Sem progresso: --quiet, stderr redirecionado ou uma página só
+pub struct Hidden;
+
+impl Progress for Hidden {
+    fn update(&mut self, _page: usize, _total: Option<usize>, _events: usize) {}
+    fn clear(&mut self) {}
+}
+
+// A linha de progresso em `out` (o stderr, no programa)
+pub struct Line<W: Write> {
+    out: W,
+    // Largura da última linha escrita: a próxima cobre o que sobrar dela
+    width: usize,
+    frame: usize,
+}
+
+impl<W: Write> Line<W> {
+    pub fn new(out: W) -> Line<W> {
+        Line { out, width: 0, frame: 0 }
+    }
+
+    // Reescreve a linha, completando com espaços se a anterior era mais larga
+    fn write(&mut self, text: &str) {
+        // chars(), não len(): "…" ocupa uma coluna, mas três bytes
+        let width = text.chars().count();
+        let padding = " ".repeat(self.width.saturating_sub(width));
+        // Falhar ao escrever o progresso não deve interromper a busca
+        let _ = write!(self.out, "\r{}{}", text, padding);
+        let _ = self.out.flush();
+        self.width = width;
+    }
+}
+
+impl<W: Write> Progress for Line<W> {
+    fn update(&mut self, page: usize, total: Option<usize>, events: usize) {
+        let text = match total {
+            Some(total) => format!("Fetching page {}/{} … {} events", page, total, events),
+            None => {
+                let spinner = SPINNER[self.frame % SPINNER.len()];
+                self.frame += 1;
+                format!("{} Fetching page {} … {} events", spinner, page, events)
+            }
+        };
+        self.write(&text);
+    }
+
+    fn clear(&mut self) {
+        if self.width == 0 {
+            return;
+        }
+        let _ = write!(self.out, "\r{}\r", " ".repeat(self.width));
+        let _ = self.out.flush();
+        self.width = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn written(line: &Line<Vec<u8>>) -> String {
+        String::from_utf8(line.out.clone()).unwrap()
+    }
+
+    #[test]
+    fn test_line_with_a_known_total() {
+        let mut line = Line::new(Vec::new());
+        line.update(2, Some(10), 30);
+        line.update(3, Some(10), 60);
+        assert_eq!(
+            written(&line),
+            "\rFetching page 2/10 … 30 events\rFetching page 3/10 … 60 events"
+        );
+    }
+
+    #[test]
+    fn test_spinner_without_a_total() {
+        let mut line = Line::new(Vec::new());
+        for page in 2..7 {
+            line.update(page, None, 0);
+        }
+        let frames: Vec<char> = written(&line).split('\r').skip(1).map(|text| text.chars().next().unwrap()).collect();
+        assert_eq!(frames, vec!['|', '/', '-', '\\', '|']);
+    }
+
+    #[test]
+    fn test_shorter_line_covers_the_previous_one() {
+        let mut line = Line::new(Vec::new());
+        line.update(9, Some(10), 1000);
+        line.update(10, Some(10), 10);
+        // A linha anterior tinha um caractere a mais ("9/10 … 1000" contra "10/10 … 10")
+        assert!(written(&line).ends_with("\rFetching page 10/10 … 10 events "));
+    }
+
+    #[test]
+    fn test_clear_erases_the_line() {
+        let mut line = Line::new(Vec::new());
+        line.clear();
+        assert_eq!(written(&line), "");
+
+        line.update(2, Some(3), 5);
+        line.clear();
+        let text = written(&line);
+        let width = "Fetching page 2/3 … 5 events".chars().count();
+        assert!(text.ends_with(&format!("\r{}\r", " ".repeat(width))));
+
+        // Um segundo clear não escreve nada
+        let before = written(&line);
+        line.clear();
+        assert_eq!(written(&line), before);
+    }
+}
@@ -0,0 +1,200 @@
+//! Este módulo grava e reproduz respostas da API (--record / --replay)
+//! Uma gravação é um objeto JSON com um pequeno cabeçalho e os eventos brutos:
+//!
+//! ```text
+//! {"recording":{"target":"torvalds","fetched_at":"2024-05-01T12:00:00Z","pages":2},
+//! "events":[ ...os eventos de todas as páginas, como o GitHub mandou... ]}
+//! ```
+//!
+//! Assim uma fixture nova sai de uma conta real sem editar JSON à mão,
+//! e o arquivo continua sendo JSON válido para ferramentas como `jq`
+
+use crate::date::Timestamp;
+use crate::error::ActivityError;
+use crate::json::{self, Json, JsonValue};
+use crate::parser::{self, ParseOutcome};
+
+/// O cabeçalho de uma gravação: de onde e quando os eventos vieram
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordingInfo {
+    /// Username ou "owner/repo", como foi digitado
+    pub target: String,
+    /// Quando a busca foi feita (None se o cabeçalho não disser ou for inválido)
+    pub fetched_at: Option<Timestamp>,
+    /// Quantas páginas da API foram juntadas
+    pub pages: usize,
+}
+
+/// Monta o texto de uma gravação a partir dos corpos de cada página
+///
+/// Os eventos de todas as páginas viram UM array, com o texto de cada evento
+/// copiado sem alterações; falha se algum corpo não for um array JSON
+pub fn format_recording(info: &RecordingInfo, bodies: &[String]) -> Result<String, ActivityError> {
+    // CONCEITO: collect em Result
+    // Um Iterator de Result<Vec<_>, E> vira Result<Vec<Vec<_>>, E>:
+    // o primeiro Err interrompe tudo
+    let pages = bodies
+        .iter()
+        .map(|body| json::split_array(body))
+        .collect::<Result<Vec<_>, _>>()?;
+    let events: Vec<&str> = pages.into_iter().flatten().collect();
+
+    let header = Json::object([
+        ("target", info.target.as_str().into()),
+        ("fetched_at", info.fetched_at.map(|timestamp| timestamp.to_string()).into()),
+        ("pages", info.pages.into()),
+    ]);
+
+    // Um evento por linha: o arquivo fica legível e diffs entre gravações ficam pequenos
+    let events = if events.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n]", events.join(",\n"))
+    };
+
+    // Os eventos entram como texto: são os objetos da API, copiados sem passar por Json
+    Ok(format!("{{\"recording\":{},\n\"events\":{}}}\n", header, events))
+}
+
+/// Interpreta uma gravação OU um array de eventos comum
+///
+/// Um array (a resposta da API salva direto, como em --from-file) devolve None
+/// no lugar do cabeçalho. Os eventos passam pelo mesmo parse_events da API,
+/// então um evento inválido continua sendo só um aviso
+pub fn parse_recording(text: &str) -> Result<(Option<RecordingInfo>, ParseOutcome), ActivityError> {
+    // Só um objeto com "events" é uma gravação; qualquer outra coisa
+    // (inclusive {"message": "Not Found"}) segue para parse_events e seus erros
+    let entries = if text.trim_start().starts_with('{') {
+        json::split_object(text).ok()
+    } else {
+        None
+    };
+    let events = entries
+        .as_ref()
+        .and_then(|entries| entries.iter().find(|(key, _)| *key == "events"));
+
+    let events = match events {
+        Some((_, events)) => *events,
+        None => return Ok((None, parser::parse_events(text)?)),
+    };
+
+    // O cabeçalho é opcional: sem ele (ou com ele estragado) ainda dá para ler os eventos
+    let info = entries
+        .iter()
+        .flatten()
+        .find(|(key, _)| *key == "recording")
+        .and_then(|(_, header)| json::parse(header).ok())
+        .map(|header| read_info(&header));
+
+    Ok((info, parser::parse_events(events)?))
+}
+
+// Lê os campos do cabeçalho; campos ausentes ficam com valores neutros
+fn read_info(header: &JsonValue) -> RecordingInfo {
+    let text = |key: &str| match header.get(key) {
+        Some(JsonValue::String(raw)) => json::decode_string(raw).ok(),
+        _ => None,
+    };
+    let pages = match header.get("pages") {
+        Some(JsonValue::Number(number)) => number.parse().unwrap_or(0),
+        _ => 0,
+    };
+
+    RecordingInfo {
+        target: text("target").unwrap_or_default(),
+        fetched_at: text("fetched_at").and_then(|value| Timestamp::parse_rfc3339(&value)),
+        pages,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER_EVENTS: &str = include_str!("../tests/fixtures/user_events.json");
+    const RECORDED_EVENTS: &str = include_str!("../tests/fixtures/recorded_events.json");
+
+    fn info(pages: usize) -> RecordingInfo {
+        RecordingInfo {
+            target: "octocat".to_string(),
+            fetched_at: Timestamp::parse_rfc3339("2024-05-01T12:00:00Z"),
+            pages,
+        }
+    }
+
+    #[test]
+    fn test_round_trip_keeps_events_and_header() {
+        let recorded = format_recording(&info(1), &[USER_EVENTS.to_string()]).unwrap();
+        let (header, outcome) = parse_recording(&recorded).unwrap();
+
+        assert_eq!(header, Some(info(1)));
+        assert_eq!(outcome.events, parser::parse_events(USER_EVENTS).unwrap().events);
+    }
+
+    #[test]
+    fn test_pages_are_joined_into_one_array() {
+        let pages = ["[{\"id\":1}, {\"id\":2}]".to_string(), "[]".to_string(), "[ {\"id\":3} ]".to_string()];
+        let recorded = format_recording(&info(3), &pages).unwrap();
+
+        assert_eq!(
+            recorded,
+            "{\"recording\":{\"target\":\"octocat\",\"fetched_at\":\"2024-05-01T12:00:00Z\",\"pages\":3},\n\
+             \"events\":[\n{\"id\":1},\n{\"id\":2},\n{\"id\":3}\n]}\n"
+        );
+    }
+
+    #[test]
+    fn test_empty_recording() {
+        let recorded = format_recording(&info(1), &["[]".to_string()]).unwrap();
+        let (header, outcome) = parse_recording(&recorded).unwrap();
+
+        assert_eq!(header.map(|header| header.pages), Some(1));
+        assert!(outcome.events.is_empty() && outcome.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_non_array_page_is_an_error() {
+        assert!(format_recording(&info(1), &["{\"message\":\"Not Found\"}".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_plain_array_has_no_header() {
+        let (header, outcome) = parse_recording(USER_EVENTS).unwrap();
+        assert_eq!(header, None);
+        assert_eq!(outcome.events.len(), 7);
+    }
+
+    #[test]
+    fn test_recorded_fixture() {
+        let (header, outcome) = parse_recording(RECORDED_EVENTS).unwrap();
+
+        assert_eq!(
+            header,
+            Some(RecordingInfo {
+                target: "octocat/Hello-World".to_string(),
+                fetched_at: Timestamp::parse_rfc3339("2024-05-02T09:30:00Z"),
+                pages: 2,
+            })
+        );
+        assert_eq!(outcome.events.len(), 2);
+        assert!(outcome.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_missing_or_broken_header_still_reads_events() {
+        let (header, outcome) = parse_recording(r#"{"events": []}"#).unwrap();
+        assert_eq!(header, None);
+        assert!(outcome.events.is_empty());
+
+        let (header, _) = parse_recording(r#"{"recording": {"pages": "two"}, "events": []}"#).unwrap();
+        assert_eq!(header, Some(RecordingInfo { target: String::new(), fetched_at: None, pages: 0 }));
+    }
+
+    #[test]
+    fn test_api_error_object_is_not_a_recording() {
+        assert!(matches!(
+            parse_recording(r#"{"message": "Not Found"}"#),
+            Err(ActivityError::ApiError { status: 0, .. })
+        ));
+    }
+}
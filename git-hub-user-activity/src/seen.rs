@@ -0,0 +1,302 @@
+//! Este módulo guarda, entre execuções, o último evento visto de cada alvo (--new)
+//! O estado fica em um arquivo de texto pequeno no diretório de cache do usuário,
+//! uma linha por username (ou owner/repo):
+//!
+//! ```text
+//! torvalds 38941057321 2024-05-01T12:00:00Z
+//! rust-lang/rust 38941057399 -
+//! ```
+//!
+//! O arquivo é só uma conveniência: quem chama trata falhas de leitura e escrita
+//! como avisos, nunca como erro fatal
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::date::Timestamp;
+use crate::error::ActivityError;
+use crate::models::GitHubEvent;
+
+// Subdiretório e nome do arquivo dentro do diretório de cache
+const CACHE_SUBDIR: &str = "github-activity";
+const STATE_FILE: &str = "last-seen";
+
+/// O evento mais novo já mostrado para um alvo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Marker {
+    /// Id do evento (os ids crescem com o tempo)
+    pub id: u64,
+    /// Quando o evento aconteceu, se a API informou
+    pub created_at: Option<Timestamp>,
+}
+
+impl Marker {
+    /// O marcador do evento mais novo da lista (o de maior id)
+    /// None se nenhum evento tiver id
+    pub fn newest(events: &[GitHubEvent]) -> Option<Marker> {
+        events
+            .iter()
+            .filter_map(|event| event.id.map(|id| Marker { id, created_at: event.created_at }))
+            .max_by_key(|marker| marker.id)
+    }
+
+    /// O evento ainda não foi visto?
+    /// Sem id, comparamos pelo horário; sem nenhum dos dois, o evento é mostrado
+    /// (esconder algo que talvez seja novo seria pior que repetir um evento)
+    pub fn is_unseen(&self, event: &GitHubEvent) -> bool {
+        match (event.id, event.created_at, self.created_at) {
+            (Some(id), _, _) => id > self.id,
+            (None, Some(created_at), Some(seen_at)) => created_at > seen_at,
+            _ => true,
+        }
+    }
+}
+
+/// Os marcadores de todos os alvos, como estão no arquivo de estado
+// CONCEITO: BTreeMap
+// Um mapa ordenado pela chave: o arquivo é sempre escrito na mesma ordem,
+// então salvar o mesmo estado duas vezes produz o mesmo texto
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeenState {
+    markers: BTreeMap<String, Marker>,
+}
+
+impl SeenState {
+    /// Interpreta o texto do arquivo; linhas em branco e começando com # são ignoradas
+    /// Qualquer linha inválida torna o arquivo inteiro inválido (ParseError com o número da linha)
+    pub fn parse(text: &str) -> Result<SeenState, ActivityError> {
+        let mut state = SeenState::default();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || ActivityError::parse(format!("invalid line {}: '{}'", number + 1, line));
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (target, id, created_at) = match fields[..] {
+                [target, id, created_at] => (target, id, created_at),
+                _ => return Err(invalid()),
+            };
+            let id = id.parse().map_err(|_| invalid())?;
+            let created_at = match created_at {
+                "-" => None,
+                text => Some(Timestamp::parse_rfc3339(text).ok_or_else(invalid)?),
+            };
+
+            state.markers.insert(target.to_string(), Marker { id, created_at });
+        }
+
+        Ok(state)
+    }
+
+    /// O texto do arquivo, uma linha por alvo
+    pub fn to_text(&self) -> String {
+        self.markers
+            .iter()
+            .map(|(target, marker)| {
+                let created_at = marker.created_at.map(|at| at.to_string());
+                format!("{} {} {}\n", target, marker.id, created_at.as_deref().unwrap_or("-"))
+            })
+            .collect()
+    }
+
+    /// O marcador guardado para um alvo
+    pub fn get(&self, target: &str) -> Option<Marker> {
+        self.markers.get(target).copied()
+    }
+
+    /// Guarda um marcador, a menos que o atual já seja mais novo
+    /// (uma resposta atrasada de um cache não faz o marcador "voltar no tempo")
+    pub fn update(&mut self, target: &str, marker: Marker) {
+        if !matches!(self.get(target), Some(current) if current.id >= marker.id) {
+            self.markers.insert(target.to_string(), marker);
+        }
+    }
+
+    /// Esquece o marcador de um alvo; devolve se havia um
+    pub fn remove(&mut self, target: &str) -> bool {
+        self.markers.remove(target).is_some()
+    }
+}
+
+/// Caminho do arquivo de estado, ou None se não há diretório de cache conhecido
+pub fn state_path() -> Option<PathBuf> {
+    app_cache_dir().map(|dir| dir.join(STATE_FILE))
+}
+
+// O diretório do programa dentro do cache do usuário ("~/.cache/github-activity" no Linux),
+// onde ficam o arquivo de estado e as respostas de cache.rs
+pub(crate) fn app_cache_dir() -> Option<PathBuf> {
+    cache_dir(|name| std::env::var_os(name).map(PathBuf::from)).map(|dir| dir.join(CACHE_SUBDIR))
+}
+
+// Diretório de cache do usuário, seguindo a convenção de cada sistema
+// Recebe a leitura de variáveis de ambiente como parâmetro para poder ser testado
+// Caminhos relativos são ignorados, como pede a especificação XDG
+fn cache_dir(var: impl Fn(&str) -> Option<PathBuf>) -> Option<PathBuf> {
+    let absolute = |name: &str| var(name).filter(|path| path.is_absolute());
+
+    if let Some(dir) = absolute("XDG_CACHE_HOME") {
+        return Some(dir);
+    }
+
+    if cfg!(windows) {
+        absolute("LOCALAPPDATA")
+    } else if cfg!(target_os = "macos") {
+        absolute("HOME").map(|home| home.join("Library").join("Caches"))
+    } else {
+        absolute("HOME").map(|home| home.join(".cache"))
+    }
+}
+
+/// Lê o arquivo de estado; um arquivo que ainda não existe é um estado vazio
+pub fn load(path: &Path) -> Result<SeenState, ActivityError> {
+    match fs::read_to_string(path) {
+        Ok(text) => SeenState::parse(&text).map_err(|error| match error {
+            ActivityError::ParseError { message, .. } => {
+                ActivityError::parse(format!("{}: {}", path.display(), message))
+            }
+            other => other,
+        }),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(SeenState::default()),
+        Err(error) => Err(ActivityError::io(path.display().to_string(), error)),
+    }
+}
+
+/// Grava o arquivo de estado, criando o diretório se preciso
+pub fn save(path: &Path, state: &SeenState) -> Result<(), ActivityError> {
+    let io_error = |error| ActivityError::io(path.display().to_string(), error);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    fs::write(path, state.to_text()).map_err(io_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EventKind, EventPayload};
+
+    fn event(id: Option<u64>, created_at: &str) -> GitHubEvent {
+        GitHubEvent::new(
+            EventKind::Watch,
+            "a/b".to_string(),
+            EventPayload::WatchEvent,
+            Timestamp::parse_rfc3339(created_at),
+        )
+        .with_id(id)
+    }
+
+    fn marker(id: u64, created_at: &str) -> Marker {
+        Marker { id, created_at: Timestamp::parse_rfc3339(created_at) }
+    }
+
+    #[test]
+    fn test_newest_marker() {
+        let events = vec![
+            event(Some(20), "2024-05-02T00:00:00Z"),
+            event(None, "2024-05-03T00:00:00Z"),
+            event(Some(10), "2024-05-01T00:00:00Z"),
+        ];
+        assert_eq!(Marker::newest(&events), Some(marker(20, "2024-05-02T00:00:00Z")));
+        assert_eq!(Marker::newest(&[event(None, "2024-05-01T00:00:00Z")]), None);
+    }
+
+    #[test]
+    fn test_is_unseen() {
+        let seen = marker(20, "2024-05-02T00:00:00Z");
+
+        assert!(seen.is_unseen(&event(Some(21), "2024-05-01T00:00:00Z")));
+        assert!(!seen.is_unseen(&event(Some(20), "2024-05-02T00:00:00Z")));
+        assert!(!seen.is_unseen(&event(Some(5), "2024-05-09T00:00:00Z")));
+
+        // Sem id: decide o horário; sem horário também, o evento aparece
+        assert!(seen.is_unseen(&event(None, "2024-05-03T00:00:00Z")));
+        assert!(!seen.is_unseen(&event(None, "2024-05-01T00:00:00Z")));
+        assert!(seen.is_unseen(&event(None, "not a date")));
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let mut state = SeenState::default();
+        state.update("torvalds", marker(38_941_057_321, "2024-05-01T12:00:00Z"));
+        state.update("rust-lang/rust", Marker { id: 7, created_at: None });
+
+        let text = state.to_text();
+        assert_eq!(text, "rust-lang/rust 7 -\ntorvalds 38941057321 2024-05-01T12:00:00Z\n");
+        assert_eq!(SeenState::parse(&text).unwrap(), state);
+    }
+
+    #[test]
+    fn test_update_never_goes_back() {
+        let mut state = SeenState::default();
+        state.update("torvalds", marker(20, "2024-05-02T00:00:00Z"));
+        state.update("torvalds", marker(10, "2024-05-01T00:00:00Z"));
+        assert_eq!(state.get("torvalds").map(|marker| marker.id), Some(20));
+
+        assert!(state.remove("torvalds"));
+        assert!(!state.remove("torvalds"));
+        assert_eq!(state.get("torvalds"), None);
+    }
+
+    #[test]
+    fn test_parse_ignores_comments_and_rejects_garbage() {
+        let state = SeenState::parse("# comment\n\n  torvalds 1 -  \n").unwrap();
+        assert_eq!(state.get("torvalds"), Some(Marker { id: 1, created_at: None }));
+
+        for text in ["torvalds", "torvalds x -", "torvalds 1 yesterday", "a 1 - extra"] {
+            let error = SeenState::parse(text).unwrap_err();
+            assert!(error.to_string().contains("invalid line 1"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_cache_dir_per_platform() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter().find(|(key, _)| *key == name).map(|(_, value)| PathBuf::from(value))
+            }
+        };
+
+        assert_eq!(
+            cache_dir(env(&[("XDG_CACHE_HOME", "/tmp/cache"), ("HOME", "/home/me")])),
+            Some(PathBuf::from("/tmp/cache"))
+        );
+        // XDG_CACHE_HOME relativo é ignorado
+        if cfg!(all(unix, not(target_os = "macos"))) {
+            assert_eq!(
+                cache_dir(env(&[("XDG_CACHE_HOME", "cache"), ("HOME", "/home/me")])),
+                Some(PathBuf::from("/home/me/.cache"))
+            );
+            assert_eq!(cache_dir(env(&[])), None);
+        }
+    }
+
+    #[test]
+    fn test_load_and_save() {
+        let dir = std::env::temp_dir().join(format!("github-activity-seen-{}", std::process::id()));
+        let path = dir.join("nested").join(STATE_FILE);
+
+        // Arquivo inexistente = estado vazio
+        assert_eq!(load(&path).unwrap(), SeenState::default());
+
+        let mut state = SeenState::default();
+        state.update("torvalds", marker(42, "2024-05-01T12:00:00Z"));
+        save(&path, &state).unwrap();
+        assert_eq!(load(&path).unwrap(), state);
+
+        // Um arquivo corrompido vira ParseError com o caminho
+        fs::write(&path, "garbage\n").unwrap();
+        let error = load(&path).unwrap_err();
+        assert!(matches!(error, ActivityError::ParseError { .. }));
+        assert!(error.to_string().contains(STATE_FILE), "{}", error);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,1117 @@
+//! Este módulo reúne análises sobre uma lista de eventos já buscada
+//! São funções puras: recebem &[GitHubEvent] e devolvem dados, sem imprimir nada,
+//! o que as torna fáceis de testar
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::date::{IsoWeek, Timestamp};
+use crate::models::{EventCategory, EventKind, EventPayload, GitHubEvent, Repository};
+
+/// Detecta a primeira atividade de cada repositório dentro da janela buscada
+/// Retorna os ÍNDICES (em `events`) do evento mais antigo de cada repositório
+///
+// ATENÇÃO: a API só devolve eventos recentes, então "primeira" significa
+// "primeira nesta janela" - a pessoa pode ter contribuído antes disso
+pub fn first_contributions(events: &[GitHubEvent]) -> HashSet<usize> {
+    // CONCEITO: HashSet
+    // Conjunto sem repetições; insert() retorna false se o valor já existia
+    let mut seen_repos: HashSet<&str> = HashSet::new();
+    let mut firsts = HashSet::new();
+
+    for index in chronological_order(events) {
+        if seen_repos.insert(&events[index].repo_name) {
+            firsts.insert(index);
+        }
+    }
+
+    firsts
+}
+
+/// Atividade em um repositório, para a listagem de --repos-only
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoActivity {
+    /// Nome completo do repositório (ex: "torvalds/linux")
+    pub repo_name: String,
+    /// Quantos eventos aconteceram nele
+    pub events: usize,
+    /// Tipo do evento mais recente nele
+    pub latest_kind: EventKind,
+}
+
+/// Os repositórios distintos de `events`, com a contagem de eventos e o tipo do mais recente
+///
+/// Ordenados pela contagem, da maior para a menor; empates ficam em ordem alfabética,
+/// para que os mesmos eventos produzam sempre a mesma saída
+pub fn repo_activity(events: &[GitHubEvent]) -> Vec<RepoActivity> {
+    // CONCEITO: BTreeMap
+    // Um mapa que mantém as chaves ordenadas: percorrê-lo já dá os repositórios em ordem alfabética
+    let mut repos: BTreeMap<&str, (usize, &EventKind)> = BTreeMap::new();
+
+    // Do mais antigo para o mais novo: o último tipo gravado é o do evento mais recente
+    for index in chronological_order(events) {
+        let event = &events[index];
+        let entry = repos.entry(event.repo_name.as_str()).or_insert((0, &event.kind));
+        entry.0 += 1;
+        entry.1 = &event.kind;
+    }
+
+    let mut activity: Vec<RepoActivity> = repos
+        .into_iter()
+        .map(|(repo_name, (events, latest_kind))| RepoActivity {
+            repo_name: repo_name.to_string(),
+            events,
+            latest_kind: latest_kind.clone(),
+        })
+        .collect();
+
+    // sort_by_key é estável: quem empata continua na ordem alfabética do BTreeMap
+    activity.sort_by_key(|repo| std::cmp::Reverse(repo.events));
+    activity
+}
+
+/// Eventos por dia do calendário, do primeiro ao último dia com eventos (para --histogram)
+///
+/// `offset_seconds` é o fuso em que os dias são contados (0 = UTC, -10800 = UTC-03:00):
+/// um evento às 01:00 UTC ainda é "ontem" em São Paulo
+/// Dias sem eventos no meio do período aparecem com 0, para que as lacunas fiquem visíveis
+/// Eventos sem horário ficam de fora
+pub fn events_per_day(events: &[GitHubEvent], offset_seconds: i64) -> Vec<(String, usize)> {
+    // Dias desde a época, já no fuso pedido -> quantidade de eventos
+    let mut counts: BTreeMap<i64, usize> = BTreeMap::new();
+    for created_at in events.iter().filter_map(|event| event.created_at) {
+        let day = (created_at.epoch_seconds() + offset_seconds).div_euclid(86_400);
+        *counts.entry(day).or_insert(0) += 1;
+    }
+
+    // CONCEITO: first_key_value / last_key_value
+    // O BTreeMap está ordenado: o menor e o maior dia saem direto, sem percorrer tudo
+    let (first, last) = match (counts.first_key_value(), counts.last_key_value()) {
+        (Some((&first, _)), Some((&last, _))) => (first, last),
+        _ => return Vec::new(),
+    };
+
+    (first..=last)
+        .map(|day| {
+            let date = Timestamp::from_epoch_seconds(day * 86_400).date_string();
+            (date, counts.get(&day).copied().unwrap_or(0))
+        })
+        .collect()
+}
+
+/// Sequências de dias com atividade e o ritmo diário, para o --summary
+/// Os dias são contados num fuso, como em events_per_day
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DailyActivity {
+    /// Dias seguidos com atividade terminando hoje (0 se hoje ainda não teve eventos)
+    pub current_streak: usize,
+    /// A maior sequência de dias seguidos com atividade na janela buscada
+    pub longest_streak: usize,
+    /// O dia com mais eventos ("2024-05-01", quantos); no empate, o mais antigo
+    pub busiest_day: Option<(String, usize)>,
+    /// Eventos com horário (os outros não caem em dia nenhum)
+    pub events: usize,
+    /// Dias do primeiro ao último evento, inclusive, com ou sem eventos
+    pub days: usize,
+}
+
+impl DailyActivity {
+    /// Média de eventos por dia da janela; None sem eventos com horário
+    pub fn average_per_day(&self) -> Option<f64> {
+        (self.days > 0).then(|| self.events as f64 / self.days as f64)
+    }
+}
+
+/// Calcula as sequências de `events` com os dias no fuso `offset_seconds`
+/// `now` decide qual é o dia de hoje (um parâmetro, e não o relógio, para os testes)
+pub fn daily_activity(events: &[GitHubEvent], offset_seconds: i64, now: Timestamp) -> DailyActivity {
+    let days = events_per_day(events, offset_seconds);
+    let today = Timestamp::from_epoch_seconds(now.epoch_seconds() + offset_seconds).date_string();
+
+    DailyActivity {
+        current_streak: current_streak(&days, &today),
+        longest_streak: longest_streak(&days),
+        busiest_day: busiest_day(&days),
+        events: days.iter().map(|(_, count)| count).sum(),
+        days: days.len(),
+    }
+}
+
+/// Dias seguidos com eventos no fim de `days` (de events_per_day), se o último for `today`
+///
+/// events_per_day termina no último dia com eventos: se ele não é hoje, a sequência já acabou
+pub fn current_streak(days: &[(String, usize)], today: &str) -> usize {
+    match days.last() {
+        Some((last, _)) if last == today => days.iter().rev().take_while(|(_, count)| *count > 0).count(),
+        _ => 0,
+    }
+}
+
+/// A maior sequência de dias seguidos com eventos em `days` (de events_per_day)
+/// As lacunas já vêm na lista, com 0: cada 0 recomeça a contagem
+pub fn longest_streak(days: &[(String, usize)]) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for (_, count) in days {
+        current = if *count > 0 { current + 1 } else { 0 };
+        longest = longest.max(current);
+    }
+    longest
+}
+
+/// O dia com mais eventos em `days`; no empate fica o mais antigo
+pub fn busiest_day(days: &[(String, usize)]) -> Option<(String, usize)> {
+    let mut busiest: Option<&(String, usize)> = None;
+    for day in days {
+        // > (e não >=): no empate fica o dia que veio antes
+        if day.1 > 0 && busiest.is_none_or(|best| day.1 > best.1) {
+            busiest = Some(day);
+        }
+    }
+    busiest.cloned()
+}
+
+/// Os eventos de uma semana ISO, por categoria (uma linha de --by-week)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeekActivity {
+    /// A semana ("2024-W19")
+    pub week: IsoWeek,
+    /// Todos os eventos da semana
+    pub total: usize,
+    /// PushEvent
+    pub pushes: usize,
+    /// Pull requests, revisões e comentários de revisão
+    pub pull_requests: usize,
+    /// Issues e comentários em issues
+    pub issues: usize,
+    /// Todo o resto (stars, forks, branches...)
+    pub other: usize,
+}
+
+/// Eventos por semana ISO, da mais recente para a mais antiga (para --by-week)
+///
+/// As semanas são contadas no fuso `offset_seconds`, como os dias de events_per_day,
+/// e semanas sem eventos no meio do período aparecem zeradas
+/// Eventos sem horário ficam de fora
+pub fn events_per_week(events: &[GitHubEvent], offset_seconds: i64) -> Vec<WeekActivity> {
+    // A segunda-feira de cada semana (em dias desde a época) -> a linha da semana
+    let mut weeks: BTreeMap<i64, WeekActivity> = BTreeMap::new();
+    for event in events {
+        let week = match event.created_at {
+            Some(created_at) => created_at.iso_week(offset_seconds),
+            None => continue,
+        };
+        let row = weeks.entry(week.monday()).or_insert_with(|| empty_week(week));
+        row.total += 1;
+        match event.kind {
+            EventKind::Push => row.pushes += 1,
+            EventKind::PullRequest | EventKind::PullRequestReview | EventKind::PullRequestReviewComment => {
+                row.pull_requests += 1
+            }
+            EventKind::Issues | EventKind::IssueComment => row.issues += 1,
+            _ => row.other += 1,
+        }
+    }
+
+    let (first, last) = match (weeks.first_key_value(), weeks.last_key_value()) {
+        (Some((&first, _)), Some((&last, _))) => (first, last),
+        _ => return Vec::new(),
+    };
+
+    // Uma segunda-feira por semana, de 7 em 7 dias, da última para a primeira
+    (0..=(last - first) / 7)
+        .map(|back| last - 7 * back)
+        .map(|monday| weeks.remove(&monday).unwrap_or_else(|| empty_week(IsoWeek::from_days(monday))))
+        .collect()
+}
+
+fn empty_week(week: IsoWeek) -> WeekActivity {
+    WeekActivity { week, total: 0, pushes: 0, pull_requests: 0, issues: 0, other: 0 }
+}
+
+/// Índices dos eventos do mais antigo para o mais novo
+///
+/// Se TODOS os eventos têm created_at, ordenamos pela data
+/// Caso contrário usamos a ordem da API (do mais novo para o mais antigo) invertida,
+/// já que misturar eventos com e sem data não teria uma ordem confiável
+pub fn chronological_order(events: &[GitHubEvent]) -> Vec<usize> {
+    // CONCEITO: rev()
+    // Inverte a direção de um iterator (aqui: do mais antigo para o mais novo)
+    let mut order: Vec<usize> = (0..events.len()).rev().collect();
+
+    if events.iter().all(|event| event.created_at.is_some()) {
+        // CONCEITO: sort_by_key é estável
+        // Eventos com a mesma data mantêm a ordem relativa (a da API invertida)
+        order.sort_by_key(|&index| events[index].created_at);
+    }
+
+    order
+}
+
+/// Ordem da listagem (--sort)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Do mais novo para o mais antigo (a ordem da API)
+    Newest,
+    /// Do mais antigo para o mais novo
+    Oldest,
+    /// Por repositório, em ordem alfabética (sem diferenciar maiúsculas)
+    Repo,
+    /// Por tipo de evento (o nome da API), em ordem alfabética
+    Type,
+}
+
+/// Índices dos eventos na ordem pedida
+///
+/// Todas as ordenações são estáveis: eventos com a mesma chave (mesmo repositório,
+/// mesmo tipo, mesma data) mantêm a ordem original, a cronológica da API
+/// Newest e Oldest usam as datas quando TODOS os eventos têm uma, como
+/// [`chronological_order`]; senão, a ordem da API e o seu inverso
+pub fn sorted_order(events: &[GitHubEvent], order: SortOrder) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..events.len()).collect();
+
+    match order {
+        SortOrder::Oldest => return chronological_order(events),
+        SortOrder::Newest => {
+            if events.iter().all(|event| event.created_at.is_some()) {
+                // CONCEITO: std::cmp::Reverse
+                // Inverte a comparação: a data maior (mais nova) vem primeiro
+                indices.sort_by_key(|&index| std::cmp::Reverse(events[index].created_at));
+            }
+        }
+        // Sem diferenciar maiúsculas, como os nomes do GitHub: "linguist" antes de "Spoon-Knife"
+        // (sort_by_cached_key também é estável, e calcula cada chave uma vez só)
+        SortOrder::Repo => indices.sort_by_cached_key(|&index| events[index].repo_name.to_lowercase()),
+        SortOrder::Type => indices.sort_by(|&a, &b| events[a].kind.name().cmp(events[b].kind.name())),
+    }
+
+    indices
+}
+
+/// Ordem da lista de repositórios de --list-repos (--sort)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoSort {
+    /// Mais estrelas primeiro
+    Stars,
+    /// Atualizado mais recentemente primeiro (a ordem que a API já manda)
+    Updated,
+    /// Pelo nome, em ordem alfabética (sem diferenciar maiúsculas)
+    Name,
+}
+
+/// Ordena `repositories` como pedido, no próprio slice
+///
+/// Como [`sorted_order`], as ordenações são estáveis: empates mantêm a ordem da API.
+/// Repositórios sem data de atualização vão para o fim com `Updated`
+pub fn sort_repositories(repositories: &mut [Repository], order: RepoSort) {
+    match order {
+        RepoSort::Stars => repositories.sort_by_key(|repository| std::cmp::Reverse(repository.stargazers_count)),
+        // Option: None < Some, então o Reverse deixa os None por último
+        RepoSort::Updated => repositories.sort_by_key(|repository| std::cmp::Reverse(repository.updated_at)),
+        RepoSort::Name => repositories.sort_by_cached_key(|repository| repository.name.to_lowercase()),
+    }
+}
+
+/// Junta pushes CONSECUTIVOS no mesmo repositório (e na mesma branch) em um único evento,
+/// somando commits e pushes ("Pushed 17 commits to user/repo (across 10 pushes)")
+///
+/// Só eventos vizinhos são juntados: se houve atividade em outro repositório no meio,
+/// a listagem continua contando a história na ordem em que aconteceu
+/// O evento resultante fica com os dados (e o horário) do push mais recente da sequência
+pub fn collapse_pushes(events: Vec<GitHubEvent>) -> Vec<GitHubEvent> {
+    let mut collapsed: Vec<GitHubEvent> = Vec::with_capacity(events.len());
+
+    for event in events {
+        // CONCEITO: last_mut()
+        // Referência mutável ao último elemento (None se o Vec estiver vazio)
+        if let Some(previous) = collapsed.last_mut() {
+            // CONCEITO: Match em tupla de referências
+            // Só casa se AMBOS forem Push; o primeiro é &mut para podermos somar
+            if let (
+                EventPayload::Push { commit_count, distinct_count, push_count, branch, commits },
+                EventPayload::Push {
+                    commit_count: more_commits,
+                    distinct_count: more_distinct,
+                    push_count: more_pushes,
+                    branch: other_branch,
+                    commits: older_commits,
+                },
+            ) = (&mut previous.payload, &event.payload)
+            {
+                // Mesma branch também: pushes em branches diferentes contam histórias diferentes
+                // (previous.repo_name e previous.payload são campos distintos,
+                // então o borrow checker aceita ler um enquanto o outro está emprestado)
+                if previous.repo_name == event.repo_name && branch == other_branch {
+                    *commit_count += more_commits;
+                    *distinct_count += more_distinct;
+                    *push_count += more_pushes;
+                    // O evento atual é MAIS ANTIGO (a lista vem do mais recente para o
+                    // mais antigo): seus commits entram antes, mantendo a ordem cronológica
+                    // CONCEITO: splice
+                    // Substitui um intervalo do Vec (aqui, o vazio 0..0) pelos itens dados
+                    commits.splice(0..0, older_commits.iter().cloned());
+                    continue;
+                }
+            }
+        }
+
+        collapsed.push(event);
+    }
+
+    collapsed
+}
+
+/// Os eventos pedidos por --type, --category e --action; uma lista vazia não restringe nada
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    /// Tipos aceitos (--type PullRequestEvent)
+    pub kinds: Vec<EventKind>,
+    /// Categorias aceitas (--category code,social)
+    pub categories: Vec<EventCategory>,
+    /// Actions aceitas, como a API as manda (--action opened)
+    pub actions: Vec<String>,
+}
+
+impl EventFilter {
+    /// true se o filtro aceita todos os eventos
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty() && self.categories.is_empty() && self.actions.is_empty()
+    }
+
+    /// true se `event` passa por todos os critérios
+    ///
+    /// Com --action, eventos sem action (push, star, fork...) NÃO passam:
+    /// quem pede "opened" quer só o que foi aberto
+    pub fn keeps(&self, event: &GitHubEvent) -> bool {
+        let kind_matches = self.kinds.is_empty() || self.kinds.contains(&event.kind);
+        let category_matches = self.categories.is_empty() || self.categories.contains(&event.payload.category());
+        let action_matches = self.actions.is_empty()
+            || matches!(event.payload.action(), Some(action) if self.actions.iter().any(|wanted| wanted == action));
+        kind_matches && category_matches && action_matches
+    }
+}
+
+/// Quem esconder da listagem pelo autor do evento (--no-bots, --ignore-actor)
+///
+/// Feeds de organizações e repositórios costumam ser dominados por dependabot,
+/// renovate e github-actions; o GitHub marca essas contas com "[bot]" no login
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActorFilter {
+    /// Esconde os logins terminados em "[bot]" (dependabot[bot], github-actions[bot]...)
+    pub bots: bool,
+    /// Logins sempre escondidos, sem diferenciar maiúsculas (como os logins do GitHub)
+    pub logins: Vec<String>,
+}
+
+impl ActorFilter {
+    /// true se o filtro não esconde ninguém
+    pub fn is_empty(&self) -> bool {
+        !self.bots && self.logins.is_empty()
+    }
+
+    /// true se o autor de `event` deve ser escondido; eventos sem autor nunca são
+    pub fn hides(&self, event: &GitHubEvent) -> bool {
+        match &event.actor {
+            Some(actor) => {
+                (self.bots && actor.login.ends_with("[bot]"))
+                    || self.logins.iter().any(|login| login.eq_ignore_ascii_case(&actor.login))
+            }
+            None => false,
+        }
+    }
+}
+
+/// Tira os eventos dos autores que `filter` esconde, sem mudar a ordem dos outros;
+/// devolve os eventos restantes e quantos saíram
+pub fn remove_actors(events: Vec<GitHubEvent>, filter: &ActorFilter) -> (Vec<GitHubEvent>, usize) {
+    let total = events.len();
+    let events: Vec<GitHubEvent> = events.into_iter().filter(|event| !filter.hides(event)).collect();
+    let hidden = total - events.len();
+    (events, hidden)
+}
+
+/// Esconde estrelas e forks repetidos do mesmo repositório
+///
+/// O feed pode trazer a mesma estrela várias vezes (tirar e dar a estrela de novo),
+/// o que só enche a listagem. Guarda os pares (tipo, repositório) já vistos:
+/// o primeiro a passar fica, os seguintes são duplicados
+/// O estado atravessa chamadas, então também serve para eventos que chegam
+/// página a página (--format ndjson), onde a ordem da API (mais novo primeiro) decide
+#[derive(Debug, Default)]
+pub struct Deduplicator {
+    seen: HashSet<(EventKind, String)>,
+}
+
+impl Deduplicator {
+    /// Um deduplicador que ainda não viu nenhum evento
+    pub fn new() -> Deduplicator {
+        Deduplicator::default()
+    }
+
+    /// true se `event` repete uma estrela ou um fork já visto
+    /// Os outros tipos de evento nunca são duplicados: dois pushes são dois pushes
+    pub fn is_duplicate(&mut self, event: &GitHubEvent) -> bool {
+        match event.kind {
+            // CONCEITO: HashSet::insert
+            // Retorna false se o valor JÁ estava no conjunto
+            EventKind::Watch | EventKind::Fork => !self.seen.insert((event.kind.clone(), event.repo_name.clone())),
+            _ => false,
+        }
+    }
+}
+
+/// Mantém só a estrela (e o fork) mais recente de cada repositório, sem mudar a ordem
+/// dos que ficam; devolve os eventos restantes e quantos foram escondidos
+///
+/// "Mais recente" segue a mesma regra de [`chronological_order`]: pela data quando
+/// todos os eventos têm uma, senão pela ordem da API
+pub fn dedup_stars_and_forks(events: Vec<GitHubEvent>) -> (Vec<GitHubEvent>, usize) {
+    let mut deduplicator = Deduplicator::new();
+    // Do mais novo para o mais antigo: o primeiro de cada repositório é o que fica
+    let kept: HashSet<usize> = chronological_order(&events)
+        .into_iter()
+        .rev()
+        .filter(|&index| !deduplicator.is_duplicate(&events[index]))
+        .collect();
+
+    let hidden = events.len() - kept.len();
+    let events = events
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| kept.contains(index))
+        .map(|(_, event)| event)
+        .collect();
+
+    (events, hidden)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::Timestamp;
+    use crate::models::{CommitSummary, EventKind, EventPayload};
+
+    fn star(repo: &str) -> GitHubEvent {
+        GitHubEvent::new(
+            EventKind::Watch,
+            repo.to_string(),
+            EventPayload::WatchEvent,
+            None,
+        )
+    }
+
+    fn star_at(repo: &str, epoch_seconds: i64) -> GitHubEvent {
+        GitHubEvent {
+            created_at: Some(Timestamp::from_epoch_seconds(epoch_seconds)),
+            ..star(repo)
+        }
+    }
+
+    #[test]
+    fn test_first_contributions_single_repo() {
+        // Três eventos no mesmo repositório: só o mais antigo (último) é o primeiro
+        let events = vec![star("a/x"), star("a/x"), star("a/x")];
+        assert_eq!(first_contributions(&events), HashSet::from([2]));
+    }
+
+    #[test]
+    fn test_first_contributions_interleaved_repos() {
+        // Mais novo -> mais antigo: x, y, x, z, y
+        let events = vec![star("a/x"), star("a/y"), star("a/x"), star("a/z"), star("a/y")];
+        assert_eq!(first_contributions(&events), HashSet::from([2, 3, 4]));
+    }
+
+    #[test]
+    fn test_first_contributions_without_timestamps_uses_fetch_order() {
+        // Sem datas, a ordem da API (mais novo primeiro) decide
+        let events = vec![star("a/new"), star("a/old")];
+        assert_eq!(first_contributions(&events), HashSet::from([0, 1]));
+    }
+
+    #[test]
+    fn test_first_contributions_uses_timestamps_over_fetch_order() {
+        // A ordem da lista está "errada", mas as datas dizem quem veio primeiro
+        let events = vec![star_at("a/x", 100), star_at("a/x", 300), star_at("a/x", 200)];
+        assert_eq!(first_contributions(&events), HashSet::from([0]));
+    }
+
+    #[test]
+    fn test_chronological_order_mixed_timestamps_falls_back() {
+        let events = vec![star_at("a/x", 100), star("a/x"), star_at("a/x", 300)];
+        assert_eq!(chronological_order(&events), vec![2, 1, 0]);
+    }
+
+    fn fork(repo: &str) -> GitHubEvent {
+        GitHubEvent::new(EventKind::Fork, repo.to_string(), EventPayload::ForkEvent { forkee: None }, None)
+    }
+
+    // Resume cada evento como "tipo repo" para comparar listas
+    fn kinds(events: &[GitHubEvent]) -> Vec<String> {
+        events.iter().map(|event| format!("{} {}", event.kind.name(), event.repo_name)).collect()
+    }
+
+    #[test]
+    fn test_dedup_interleaved_duplicates() {
+        // Mais novo -> mais antigo: x, y, x, fork x, y, x
+        let events = vec![star("a/x"), star("a/y"), star("a/x"), fork("a/x"), star("a/y"), star("a/x")];
+        let (events, hidden) = dedup_stars_and_forks(events);
+
+        // Fica a estrela mais recente de cada um; o fork de a/x é outro tipo e também fica
+        assert_eq!(kinds(&events), vec!["WatchEvent a/x", "WatchEvent a/y", "ForkEvent a/x"]);
+        assert_eq!(hidden, 3);
+    }
+
+    #[test]
+    fn test_dedup_leaves_other_event_types_alone() {
+        let events = vec![push("a/x", 1), star("a/x"), push("a/x", 2), star("a/x"), fork("a/y"), fork("a/y")];
+        let (events, hidden) = dedup_stars_and_forks(events);
+
+        assert_eq!(shape(&events), vec![("a/x", 1, 1), ("a/x", 0, 0), ("a/x", 2, 1), ("a/y", 0, 0)]);
+        assert_eq!(hidden, 2);
+        assert_eq!(dedup_stars_and_forks(Vec::new()).1, 0);
+    }
+
+    #[test]
+    fn test_dedup_keeps_the_most_recent_by_timestamp() {
+        // A ordem da lista está "errada", mas a data diz qual estrela é a mais recente
+        let (events, hidden) = dedup_stars_and_forks(vec![star_at("a/x", 100), star_at("a/x", 300), star_at("a/x", 200)]);
+
+        assert_eq!(hidden, 2);
+        assert_eq!(events[0].created_at, Some(Timestamp::from_epoch_seconds(300)));
+    }
+
+    #[test]
+    fn test_dedup_happens_before_limit() {
+        // --limit 3 conta eventos DEPOIS da deduplicação: 3 repositórios diferentes,
+        // e não a mesma estrela três vezes
+        let events = vec![star("a/x"), star("a/x"), star("a/x"), star("a/y"), star("a/z"), star("a/w")];
+        let (mut events, hidden) = dedup_stars_and_forks(events);
+        events.truncate(3);
+
+        assert_eq!(kinds(&events), vec!["WatchEvent a/x", "WatchEvent a/y", "WatchEvent a/z"]);
+        assert_eq!(hidden, 2);
+    }
+
+    fn issue(repo: &str, action: &str) -> GitHubEvent {
+        GitHubEvent::new(
+            EventKind::Issues,
+            repo.to_string(),
+            EventPayload::IssuesEvent { action: action.to_string(), number: None, title: None },
+            None,
+        )
+    }
+
+    fn pull_request(repo: &str, action: &str) -> GitHubEvent {
+        GitHubEvent::new(
+            EventKind::PullRequest,
+            repo.to_string(),
+            EventPayload::PullRequestEvent { action: action.to_string(), number: None, title: None, merged: false },
+            None,
+        )
+    }
+
+    fn mixed() -> Vec<GitHubEvent> {
+        vec![
+            issue("a/x", "opened"),
+            pull_request("a/x", "closed"),
+            star("a/x"),
+            pull_request("a/y", "opened"),
+            issue("a/y", "closed"),
+            push("a/x", 1),
+        ]
+    }
+
+    fn kept(filter: &EventFilter) -> Vec<String> {
+        let events: Vec<GitHubEvent> = mixed().into_iter().filter(|event| filter.keeps(event)).collect();
+        events
+            .iter()
+            .map(|event| format!("{} {}", event.kind.name(), event.payload.action().unwrap_or("-")))
+            .collect()
+    }
+
+    #[test]
+    fn test_event_filter_by_type() {
+        let filter = EventFilter { kinds: vec![EventKind::PullRequest, EventKind::Watch], ..EventFilter::default() };
+        assert_eq!(kept(&filter), vec!["PullRequestEvent closed", "WatchEvent -", "PullRequestEvent opened"]);
+    }
+
+    #[test]
+    fn test_event_filter_by_action() {
+        // Eventos sem action (a estrela e o push) ficam de fora
+        let filter = EventFilter { actions: vec!["opened".to_string()], ..EventFilter::default() };
+        assert_eq!(kept(&filter), vec!["IssuesEvent opened", "PullRequestEvent opened"]);
+    }
+
+    #[test]
+    fn test_event_filter_by_type_and_action() {
+        let filter = EventFilter {
+            kinds: vec![EventKind::PullRequest],
+            actions: vec!["closed".to_string()],
+            ..EventFilter::default()
+        };
+        assert_eq!(kept(&filter), vec!["PullRequestEvent closed"]);
+
+        assert!(EventFilter::default().is_empty());
+        assert_eq!(kept(&EventFilter::default()).len(), mixed().len());
+    }
+
+    #[test]
+    fn test_event_filter_by_category() {
+        let filter = EventFilter { categories: vec![EventCategory::Code, EventCategory::Social], ..EventFilter::default() };
+        assert_eq!(kept(&filter), vec!["WatchEvent -", "PushEvent -"]);
+
+        // Com --action junto, os dois critérios valem
+        let filter = EventFilter {
+            categories: vec![EventCategory::Issues, EventCategory::Review],
+            actions: vec!["closed".to_string()],
+            ..EventFilter::default()
+        };
+        assert_eq!(kept(&filter), vec!["PullRequestEvent closed", "IssuesEvent closed"]);
+        assert!(!filter.is_empty());
+    }
+
+    // Um feed de repositório com pessoas, bots "[bot]" e contas de automação comuns
+    const BOT_EVENTS: &str = include_str!("../tests/fixtures/bot_events.json");
+
+    fn actors(events: &[GitHubEvent]) -> Vec<&str> {
+        events.iter().filter_map(|event| event.actor.as_ref()).map(|actor| actor.login.as_str()).collect()
+    }
+
+    #[test]
+    fn test_remove_bot_actors() {
+        let events = crate::parser::parse_events(BOT_EVENTS).unwrap().events;
+        let filter = ActorFilter { bots: true, logins: Vec::new() };
+
+        let (events, hidden) = remove_actors(events, &filter);
+        assert_eq!(actors(&events), vec!["octocat", "hubot", "ci-runner", "octocat"]);
+        assert_eq!(hidden, 4);
+    }
+
+    #[test]
+    fn test_remove_ignored_actors() {
+        let events = crate::parser::parse_events(BOT_EVENTS).unwrap().events;
+
+        // Os logins da lista saem mesmo sem "[bot]", sem diferenciar maiúsculas
+        let filter = ActorFilter { bots: true, logins: vec!["HUBOT".to_string(), "ci-runner".to_string()] };
+        let (kept, hidden) = remove_actors(events.clone(), &filter);
+        assert_eq!(actors(&kept), vec!["octocat", "octocat"]);
+        assert_eq!(hidden, 6);
+
+        // Só a lista, sem --no-bots: os "[bot]" ficam
+        let filter = ActorFilter { bots: false, logins: vec!["dependabot[bot]".to_string()] };
+        assert_eq!(remove_actors(events.clone(), &filter).1, 2);
+
+        // Filtro vazio não esconde nada; evento sem autor nunca é escondido
+        assert!(ActorFilter::default().is_empty());
+        assert_eq!(remove_actors(events, &ActorFilter::default()).1, 0);
+        assert!(!ActorFilter { bots: true, logins: Vec::new() }.hides(&star("a/x")));
+    }
+
+    #[test]
+    fn test_deduplicator_across_pages() {
+        let mut deduplicator = Deduplicator::new();
+        let first_page = [star("a/x"), fork("a/y")];
+        let second_page = [star("a/x"), fork("a/y"), push("a/x", 1), push("a/x", 1)];
+
+        assert!(first_page.iter().all(|event| !deduplicator.is_duplicate(event)));
+        let duplicates: Vec<bool> = second_page.iter().map(|event| deduplicator.is_duplicate(event)).collect();
+        assert_eq!(duplicates, vec![true, true, false, false]);
+    }
+
+    // Eventos de vários tipos e repositórios, do mais novo para o mais antigo
+    const USER_EVENTS: &str = include_str!("../tests/fixtures/user_events.json");
+
+    fn sorted(events: &[GitHubEvent], order: SortOrder) -> Vec<String> {
+        sorted_order(events, order)
+            .into_iter()
+            .map(|index| format!("{} {}", events[index].kind.name(), events[index].repo_name))
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_by_time() {
+        let events = crate::parser::parse_events(USER_EVENTS).unwrap().events;
+
+        assert_eq!(sorted_order(&events, SortOrder::Newest), (0..events.len()).collect::<Vec<_>>());
+        assert_eq!(sorted_order(&events, SortOrder::Oldest), (0..events.len()).rev().collect::<Vec<_>>());
+
+        // Fora de ordem, as datas decidem; sem todas as datas, vale a ordem da lista
+        let shuffled = vec![star_at("a/x", 200), star_at("a/y", 300), star_at("a/z", 100)];
+        assert_eq!(sorted_order(&shuffled, SortOrder::Newest), vec![1, 0, 2]);
+        assert_eq!(sorted_order(&shuffled, SortOrder::Oldest), vec![2, 0, 1]);
+        let undated = vec![star_at("a/x", 200), star("a/y"), star_at("a/z", 300)];
+        assert_eq!(sorted_order(&undated, SortOrder::Newest), vec![0, 1, 2]);
+        assert_eq!(sorted_order(&undated, SortOrder::Oldest), vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn test_sort_by_repo_is_stable() {
+        let events = crate::parser::parse_events(USER_EVENTS).unwrap().events;
+
+        // Os três eventos de Hello-World continuam do mais novo para o mais antigo
+        assert_eq!(
+            sorted(&events, SortOrder::Repo),
+            vec![
+                "PushEvent octocat/Hello-World",
+                "PullRequestEvent octocat/Hello-World",
+                "SponsorshipEvent octocat/Hello-World",
+                "CreateEvent octocat/linguist",
+                "CreateEvent octocat/new-repo",
+                "IssuesEvent octocat/Spoon-Knife",
+                "WatchEvent torvalds/linux",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_type_is_stable() {
+        let events = crate::parser::parse_events(USER_EVENTS).unwrap().events;
+
+        // Os dois CreateEvent mantêm a ordem original (linguist é o mais novo)
+        assert_eq!(
+            sorted(&events, SortOrder::Type),
+            vec![
+                "CreateEvent octocat/linguist",
+                "CreateEvent octocat/new-repo",
+                "IssuesEvent octocat/Spoon-Knife",
+                "PullRequestEvent octocat/Hello-World",
+                "PushEvent octocat/Hello-World",
+                "SponsorshipEvent octocat/Hello-World",
+                "WatchEvent torvalds/linux",
+            ]
+        );
+        assert!(sorted_order(&[], SortOrder::Type).is_empty());
+    }
+
+    fn repository(name: &str, stars: u64, updated_at: Option<&str>) -> Repository {
+        Repository {
+            full_name: format!("me/{}", name),
+            name: name.to_string(),
+            description: None,
+            stargazers_count: stars,
+            language: None,
+            fork: false,
+            updated_at: updated_at.and_then(Timestamp::parse_date),
+        }
+    }
+
+    #[test]
+    fn test_sort_repositories() {
+        let mut repositories = vec![
+            repository("zeta", 5, Some("2024-01-01")),
+            repository("Alpha", 50, None),
+            repository("beta", 5, Some("2024-06-01")),
+        ];
+        let names = |repositories: &[Repository]| -> Vec<String> {
+            repositories.iter().map(|repository| repository.name.clone()).collect()
+        };
+
+        sort_repositories(&mut repositories, RepoSort::Stars);
+        // Empate em 5 estrelas: zeta continua antes de beta
+        assert_eq!(names(&repositories), vec!["Alpha", "zeta", "beta"]);
+        sort_repositories(&mut repositories, RepoSort::Updated);
+        assert_eq!(names(&repositories), vec!["beta", "zeta", "Alpha"]);
+        sort_repositories(&mut repositories, RepoSort::Name);
+        assert_eq!(names(&repositories), vec!["Alpha", "beta", "zeta"]);
+    }
+
+    fn push(repo: &str, commits: usize) -> GitHubEvent {
+        GitHubEvent::new(
+            EventKind::Push,
+            repo.to_string(),
+            EventPayload::Push { commit_count: commits, distinct_count: commits, push_count: 1, branch: None, commits: Vec::new() },
+            None,
+        )
+    }
+
+    // Resume cada evento como (repo, commits, pushes); não-pushes viram (repo, 0, 0)
+    fn shape(events: &[GitHubEvent]) -> Vec<(&str, usize, usize)> {
+        events
+            .iter()
+            .map(|event| match event.payload {
+                EventPayload::Push { commit_count, push_count, .. } => {
+                    (event.repo_name.as_str(), commit_count, push_count)
+                }
+                _ => (event.repo_name.as_str(), 0, 0),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_collapse_run_at_start() {
+        let events = vec![push("a/x", 1), push("a/x", 2), push("a/x", 3), star("a/y")];
+        assert_eq!(shape(&collapse_pushes(events)), vec![("a/x", 6, 3), ("a/y", 0, 0)]);
+    }
+
+    #[test]
+    fn test_collapse_run_in_middle() {
+        let events = vec![star("a/y"), push("a/x", 1), push("a/x", 1), star("a/z")];
+        assert_eq!(
+            shape(&collapse_pushes(events)),
+            vec![("a/y", 0, 0), ("a/x", 2, 2), ("a/z", 0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_collapse_run_at_end() {
+        let events = vec![star("a/y"), push("a/x", 4), push("a/x", 5)];
+        assert_eq!(shape(&collapse_pushes(events)), vec![("a/y", 0, 0), ("a/x", 9, 2)]);
+    }
+
+    #[test]
+    fn test_collapse_only_adjacent_same_repo_pushes() {
+        // Pushes intercalados com outro repositório (ou outro tipo) não são juntados
+        let events = vec![
+            push("a/x", 1),
+            push("a/y", 1),
+            push("a/x", 1),
+            star("a/x"),
+            push("a/x", 1),
+        ];
+        assert_eq!(
+            shape(&collapse_pushes(events)),
+            vec![("a/x", 1, 1), ("a/y", 1, 1), ("a/x", 1, 1), ("a/x", 0, 0), ("a/x", 1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_collapse_requires_same_branch() {
+        let on_branch = |name: &str| GitHubEvent {
+            payload: EventPayload::Push {
+                commit_count: 1,
+                distinct_count: 1,
+                push_count: 1,
+                branch: Some(name.to_string()),
+                commits: Vec::new(),
+            },
+            ..push("a/x", 1)
+        };
+
+        let events = vec![on_branch("main"), on_branch("main"), on_branch("dev")];
+        assert_eq!(shape(&collapse_pushes(events)), vec![("a/x", 2, 2), ("a/x", 1, 1)]);
+    }
+
+    #[test]
+    fn test_collapse_keeps_newest_timestamp() {
+        let newest = GitHubEvent {
+            created_at: Some(Timestamp::from_epoch_seconds(200)),
+            ..push("a/x", 1)
+        };
+        let older = GitHubEvent {
+            created_at: Some(Timestamp::from_epoch_seconds(100)),
+            ..push("a/x", 1)
+        };
+        let collapsed = collapse_pushes(vec![newest, older]);
+        assert_eq!(collapsed[0].created_at, Some(Timestamp::from_epoch_seconds(200)));
+    }
+
+    #[test]
+    fn test_collapse_keeps_commits_in_chronological_order() {
+        let with_commits = |messages: &[&str]| GitHubEvent {
+            payload: EventPayload::Push {
+                commit_count: messages.len(),
+                distinct_count: messages.len(),
+                push_count: 1,
+                branch: None,
+                commits: messages
+                    .iter()
+                    .map(|message| CommitSummary { sha: String::new(), message: message.to_string() })
+                    .collect(),
+            },
+            ..push("a/x", 0)
+        };
+
+        // Mais recente primeiro, como vem da API
+        let events = vec![with_commits(&["c", "d"]), with_commits(&["a", "b"])];
+        // Um único push com os quatro commits, do mais antigo para o mais novo
+        let mut expected = with_commits(&["a", "b", "c", "d"]);
+        if let EventPayload::Push { push_count, .. } = &mut expected.payload {
+            *push_count = 2;
+        }
+        assert_eq!(collapse_pushes(events), vec![expected]);
+    }
+
+    #[test]
+    fn test_repo_activity_counts_and_orders_repos() {
+        // Da API: do mais novo para o mais antigo
+        let events = vec![
+            push("b/two", 1),
+            star("c/three"),
+            star("b/two"),
+            push("a/one", 2),
+            star("a/one"),
+            push("d/four", 1),
+        ];
+
+        let repos = repo_activity(&events);
+        let shape: Vec<(&str, usize, &str)> = repos
+            .iter()
+            .map(|repo| (repo.repo_name.as_str(), repo.events, repo.latest_kind.name()))
+            .collect();
+        assert_eq!(
+            shape,
+            vec![
+                // Empate em 2: ordem alfabética
+                ("a/one", 2, "PushEvent"),
+                ("b/two", 2, "PushEvent"),
+                ("c/three", 1, "WatchEvent"),
+                ("d/four", 1, "PushEvent"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repo_activity_latest_follows_timestamps() {
+        // Com horários, "mais recente" é o de created_at, não a posição na lista
+        let mut newer = push("a/one", 1);
+        newer.created_at = Some(Timestamp::from_epoch_seconds(200));
+        let events = vec![star_at("a/one", 100), newer];
+
+        assert_eq!(repo_activity(&events)[0].latest_kind, EventKind::Push);
+        assert!(repo_activity(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_events_per_day_fills_gaps() {
+        const DAY: i64 = 86_400;
+        // 2024-06-01T00:00:00Z
+        let start = 1_717_200_000;
+        let events = vec![
+            star_at("a/one", start + 3 * DAY + 100),
+            star_at("a/one", start + DAY + 200),
+            star_at("a/one", start + DAY + 100),
+            star_at("a/one", start + 100),
+            star("a/one"),
+        ];
+
+        assert_eq!(
+            events_per_day(&events, 0),
+            vec![
+                ("2024-06-01".to_string(), 1),
+                ("2024-06-02".to_string(), 2),
+                // A lacuna continua na lista, com 0
+                ("2024-06-03".to_string(), 0),
+                ("2024-06-04".to_string(), 1),
+            ]
+        );
+        assert!(events_per_day(&[star("a/one")], 0).is_empty());
+    }
+
+    #[test]
+    fn test_events_per_day_uses_the_offset() {
+        // 2024-06-01T01:00:00Z: ainda 31 de maio em UTC-03:00, já 1º de junho em UTC+01:00
+        let events = vec![star_at("a/one", 1_717_203_600)];
+
+        assert_eq!(events_per_day(&events, -3 * 3600), vec![("2024-05-31".to_string(), 1)]);
+        assert_eq!(events_per_day(&events, 3600), vec![("2024-06-01".to_string(), 1)]);
+    }
+
+    // Os dias como events_per_day devolve: (data, eventos)
+    fn days(counts: &[usize]) -> Vec<(String, usize)> {
+        counts.iter().enumerate().map(|(index, count)| (format!("2024-06-{:02}", index + 1), *count)).collect()
+    }
+
+    // (eventos por dia a partir de 2024-06-01, hoje, sequência atual, maior sequência, dia mais movimentado)
+    type StreakCase<'a> = (&'a [usize], &'a str, usize, usize, Option<(&'a str, usize)>);
+
+    #[test]
+    fn test_streaks_table() {
+        let cases: &[StreakCase] = &[
+            (&[], "2024-06-01", 0, 0, None),
+            (&[3], "2024-06-01", 1, 1, Some(("2024-06-01", 3))),
+            // O último dia com eventos foi ontem: a sequência de hoje é 0
+            (&[3], "2024-06-02", 0, 1, Some(("2024-06-01", 3))),
+            (&[1, 2, 0, 1, 1, 1], "2024-06-06", 3, 3, Some(("2024-06-02", 2))),
+            (&[1, 1, 1, 0, 4], "2024-06-05", 1, 3, Some(("2024-06-05", 4))),
+            // Empate no dia mais movimentado: fica o mais antigo
+            (&[2, 0, 2], "2024-06-03", 1, 1, Some(("2024-06-01", 2))),
+        ];
+
+        for (counts, today, current, longest, busiest) in cases {
+            let days = days(counts);
+            assert_eq!(current_streak(&days, today), *current, "{:?} on {}", counts, today);
+            assert_eq!(longest_streak(&days), *longest, "{:?}", counts);
+            let expected = busiest.map(|(day, count)| (day.to_string(), count));
+            assert_eq!(busiest_day(&days), expected, "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn test_daily_activity_counts_days_in_the_offset() {
+        const DAY: i64 = 86_400;
+        // 2024-06-01T00:00:00Z
+        let start = 1_717_200_000;
+        // 01:00 UTC de cada dia: em UTC-03:00, ainda é o dia anterior
+        let events = vec![
+            star_at("a/one", start + 2 * DAY + 3600),
+            star_at("a/one", start + DAY + 3600),
+            star_at("a/one", start + 3600),
+            star("a/one"),
+        ];
+        let now = Timestamp::from_epoch_seconds(start + 2 * DAY + 12 * 3600);
+
+        let utc = daily_activity(&events, 0, now);
+        assert_eq!((utc.current_streak, utc.longest_streak), (3, 3));
+        assert_eq!(utc.busiest_day, Some(("2024-06-01".to_string(), 1)));
+        assert_eq!((utc.events, utc.days), (3, 3));
+        assert_eq!(utc.average_per_day(), Some(1.0));
+
+        // Em UTC-03:00 os eventos caem de 31/05 a 02/06, e "hoje" (09:00 local) é 03/06
+        let local = daily_activity(&events, -3 * 3600, now);
+        assert_eq!((local.current_streak, local.longest_streak), (0, 3));
+        assert_eq!(local.busiest_day, Some(("2024-05-31".to_string(), 1)));
+
+        assert_eq!(daily_activity(&[], 0, now), DailyActivity::default());
+        assert_eq!(DailyActivity::default().average_per_day(), None);
+    }
+
+    #[test]
+    fn test_events_per_week() {
+        // 2024-05-06 (segunda-feira, semana 19), ao meio-dia UTC
+        let monday = 1_714_996_800;
+        const DAY: i64 = 86_400;
+        let event = |kind: EventKind, epoch_seconds: i64| GitHubEvent { kind, ..star_at("a/one", epoch_seconds) };
+        let events = vec![
+            event(EventKind::Push, monday + 14 * DAY),
+            event(EventKind::PullRequestReview, monday + 14 * DAY),
+            event(EventKind::IssueComment, monday + 6 * DAY),
+            event(EventKind::PullRequest, monday),
+            event(EventKind::Push, monday),
+            event(EventKind::Watch, monday),
+            star("a/one"),
+        ];
+
+        let weeks = events_per_week(&events, 0);
+        let rows: Vec<(String, usize, usize, usize, usize, usize)> = weeks
+            .iter()
+            .map(|w| (w.week.to_string(), w.total, w.pushes, w.pull_requests, w.issues, w.other))
+            .collect();
+        // Da mais recente para a mais antiga, com a semana 20 (vazia) no meio
+        assert_eq!(
+            rows,
+            vec![
+                ("2024-W21".to_string(), 2, 1, 1, 0, 0),
+                ("2024-W20".to_string(), 0, 0, 0, 0, 0),
+                ("2024-W19".to_string(), 4, 1, 1, 1, 1),
+            ]
+        );
+
+        // Em UTC+14:00 o domingo ao meio-dia já é segunda-feira: o comentário muda de semana
+        let ahead = events_per_week(&events, 14 * 3600);
+        assert_eq!((ahead[1].week.to_string(), ahead[1].issues), ("2024-W20".to_string(), 1));
+
+        assert!(events_per_week(&[star("a/one")], 0).is_empty());
+    }
+
+    #[test]
+    fn test_events_per_week_across_the_year_boundary() {
+        // 2020-12-31 (2020-W53) e 2021-01-11 (2021-W02): a semana 1 de 2021 fica entre elas
+        let events = vec![star_at("a/one", 1_610_366_400), star_at("a/one", 1_609_416_000)];
+        let weeks: Vec<String> = events_per_week(&events, 0).iter().map(|w| w.week.to_string()).collect();
+        assert_eq!(weeks, vec!["2021-W02", "2021-W01", "2020-W53"]);
+    }
+
+    #[test]
+    fn test_first_contributions_empty() {
+        assert!(first_contributions(&[]).is_empty());
+    }
+}
@@ -0,0 +1,255 @@
+//! Este módulo guarda eventos num banco SQLite (--export-sqlite, feature "sqlite")
+//!
+//! Cada execução acrescenta os eventos buscados à tabela `events`, com o id do
+//! evento como chave: rodar de novo atualiza as linhas que já existem em vez de
+//! duplicá-las, e o banco vira um histórico que cresce a cada busca
+//!
+//! ```text
+//! id | username | type | repo | action | commit_count | created_at | raw_payload
+//! ```
+//!
+//! `raw_payload` é o campo "payload" como a API mandou (em JSON compacto), para
+//! análises que precisem do que o programa não interpreta
+//!
+//! A versão do esquema fica no `PRAGMA user_version` do próprio arquivo: um banco
+//! novo (versão 0) recebe todas as [`MIGRATIONS`]; um banco antigo, só as que faltam
+
+use std::collections::HashMap;
+use std::io;
+use std::time::Duration;
+
+use rusqlite::{params, Connection, ErrorCode};
+
+use crate::error::ActivityError;
+use crate::json::JsonValue;
+use crate::models::{EventPayload, GitHubEvent};
+use crate::parser;
+
+/// Os passos do esquema, em ordem: o banco na versão N já recebeu os N primeiros
+/// Uma mudança no esquema é um passo novo no fim da lista, nunca uma edição de um antigo
+pub const MIGRATIONS: &[&str] = &["CREATE TABLE events (
+        id           INTEGER PRIMARY KEY,
+        username     TEXT NOT NULL,
+        type         TEXT NOT NULL,
+        repo         TEXT NOT NULL,
+        action       TEXT,
+        commit_count INTEGER,
+        created_at   TEXT,
+        raw_payload  TEXT
+    );
+    CREATE INDEX events_username_created_at ON events (username, created_at);"];
+
+/// A versão do esquema que este programa escreve
+pub const SCHEMA_VERSION: usize = MIGRATIONS.len();
+
+// Quanto esperar por outro programa que esteja escrevendo no banco
+// antes de desistir com "database is locked"
+const BUSY_TIMEOUT: Duration = Duration::from_secs(2);
+
+// CONCEITO: Encapsulamento
+// Database esconde a Connection do rusqlite: quem usa o módulo só vê
+// abrir (com o esquema em dia) e gravar eventos, com erros do próprio programa
+/// Um banco de eventos aberto, com o esquema na versão [`SCHEMA_VERSION`]
+pub struct Database {
+    connection: Connection,
+    // Para as mensagens de erro: qual arquivo falhou
+    path: String,
+}
+
+impl Database {
+    /// Abre (ou cria) o banco em `path` e aplica as migrações que faltarem
+    pub fn open(path: &str) -> Result<Database, ActivityError> {
+        let connection = Connection::open(path).map_err(|e| database_error(path, e))?;
+        let mut database = Database { connection, path: path.to_string() };
+        database.connection.busy_timeout(BUSY_TIMEOUT).map_err(|e| database_error(path, e))?;
+        database.migrate()?;
+        Ok(database)
+    }
+
+    /// A versão do esquema gravada no arquivo (`PRAGMA user_version`)
+    pub fn schema_version(&self) -> Result<usize, ActivityError> {
+        self.connection
+            .query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))
+            .map(|version| version as usize)
+            .map_err(|e| database_error(&self.path, e))
+    }
+
+    // Aplica, numa transação só, os passos que o arquivo ainda não tem
+    fn migrate(&mut self) -> Result<(), ActivityError> {
+        let version = self.schema_version()?;
+        if version > SCHEMA_VERSION {
+            return Err(ActivityError::io(
+                self.path.as_str(),
+                io::Error::other(format!(
+                    "the database has schema version {}, newer than the {} this program knows",
+                    version, SCHEMA_VERSION
+                )),
+            ));
+        }
+        if version == SCHEMA_VERSION {
+            return Ok(());
+        }
+
+        let path = self.path.as_str();
+        let transaction = self.connection.transaction().map_err(|e| database_error(path, e))?;
+        for step in &MIGRATIONS[version..] {
+            transaction.execute_batch(step).map_err(|e| database_error(path, e))?;
+        }
+        // PRAGMA não aceita parâmetros (?): o número entra no texto
+        transaction
+            .execute_batch(&format!("PRAGMA user_version = {}", SCHEMA_VERSION))
+            .map_err(|e| database_error(path, e))?;
+        transaction.commit().map_err(|e| database_error(path, e))
+    }
+
+    /// Grava `events` (inserindo os novos e atualizando os que já existem)
+    /// e devolve quantos eram novos
+    ///
+    /// `username` vai para os eventos sem ator; `bodies` são as páginas brutas da API,
+    /// de onde sai o `raw_payload`. Eventos sem id ficam de fora: não há como
+    /// reconhecê-los na próxima execução
+    pub fn upsert(&mut self, username: &str, events: &[GitHubEvent], bodies: &[String]) -> Result<usize, ActivityError> {
+        let payloads = raw_payloads(bodies);
+        let path = self.path.as_str();
+        let before = count_rows(&self.connection, path)?;
+
+        let transaction = self.connection.transaction().map_err(|e| database_error(path, e))?;
+        {
+            let mut statement = transaction
+                .prepare(
+                    "INSERT INTO events (id, username, type, repo, action, commit_count, created_at, raw_payload)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                     ON CONFLICT (id) DO UPDATE SET
+                         username = excluded.username,
+                         type = excluded.type,
+                         repo = excluded.repo,
+                         action = excluded.action,
+                         commit_count = excluded.commit_count,
+                         created_at = excluded.created_at,
+                         raw_payload = excluded.raw_payload",
+                )
+                .map_err(|e| database_error(path, e))?;
+
+            for event in events {
+                // O SQLite guarda inteiros com sinal: um id acima de i64::MAX não caberia
+                let (id, key) = match event.id.and_then(|id| i64::try_from(id).ok().map(|key| (id, key))) {
+                    Some(ids) => ids,
+                    None => continue,
+                };
+                let login = event.actor.as_ref().map_or(username, |actor| actor.login.as_str());
+                let commit_count = match &event.payload {
+                    EventPayload::Push { commit_count, .. } => Some(*commit_count as i64),
+                    _ => None,
+                };
+                statement
+                    .execute(params![
+                        key,
+                        login,
+                        event.kind.name(),
+                        event.repo_name,
+                        event.payload.action(),
+                        commit_count,
+                        event.created_at.map(|created_at| created_at.to_string()),
+                        payloads.get(&id),
+                    ])
+                    .map_err(|e| database_error(path, e))?;
+            }
+        }
+        transaction.commit().map_err(|e| database_error(path, e))?;
+
+        Ok(count_rows(&self.connection, path)? - before)
+    }
+
+    /// Quantos eventos o banco guarda
+    pub fn count(&self) -> Result<usize, ActivityError> {
+        count_rows(&self.connection, &self.path)
+    }
+}
+
+fn count_rows(connection: &Connection, path: &str) -> Result<usize, ActivityError> {
+    connection
+        .query_row("SELECT COUNT(*) FROM events", [], |row| row.get::<_, i64>(0))
+        .map(|count| count as usize)
+        .map_err(|e| database_error(path, e))
+}
+
+// O "payload" de cada evento das páginas, pelo id do evento
+// Uma página ou um evento que não dá para ler fica sem payload (o parser já avisou dele)
+fn raw_payloads(bodies: &[String]) -> HashMap<u64, String> {
+    let mut payloads = HashMap::new();
+    for body in bodies {
+        for (id, event) in parser::raw_events(body) {
+            if let Some(payload) = event.get("payload").filter(|payload| **payload != JsonValue::Null) {
+                payloads.insert(id, payload.to_string());
+            }
+        }
+    }
+    payloads
+}
+
+// CONCEITO: Traduzir erros de uma biblioteca
+// O rusqlite tem seus próprios códigos; os dois casos comuns (outro programa usando o
+// banco, arquivo sem permissão) viram frases claras, e o resto mantém a mensagem do SQLite.
+// Tudo vira IoError: é um arquivo que não pôde ser escrito (mesmo código de saída de --output)
+fn database_error(path: &str, error: rusqlite::Error) -> ActivityError {
+    let source = match error.sqlite_error_code() {
+        Some(ErrorCode::DatabaseBusy) | Some(ErrorCode::DatabaseLocked) => io::Error::new(
+            io::ErrorKind::WouldBlock,
+            "the database is locked by another program; try again when it finishes",
+        ),
+        Some(ErrorCode::ReadOnly) | Some(ErrorCode::CannotOpen) | Some(ErrorCode::PermissionDenied) => {
+            io::Error::new(io::ErrorKind::PermissionDenied, "the file (or its directory) is not writable")
+        }
+        Some(ErrorCode::NotADatabase) => io::Error::new(io::ErrorKind::InvalidData, "not a SQLite database"),
+        _ => io::Error::other(error.to_string()),
+    };
+    ActivityError::io(path, source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn database() -> Database {
+        Database::open(":memory:").unwrap()
+    }
+
+    #[test]
+    fn test_new_database_gets_the_current_schema() {
+        assert_eq!(database().schema_version().unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_raw_payloads_by_id() {
+        let bodies = vec![
+            r#"[{"id": "7", "type": "WatchEvent", "payload": {"action": "started"}},
+                {"id": "8", "type": "PublicEvent"}]"#
+                .to_string(),
+            "not json".to_string(),
+        ];
+        let payloads = raw_payloads(&bodies);
+        assert_eq!(payloads.get(&7).map(String::as_str), Some(r#"{"action":"started"}"#));
+        assert_eq!(payloads.len(), 1);
+    }
+
+    #[test]
+    fn test_newer_schema_is_refused() {
+        let mut database = database();
+        database.connection.execute_batch("PRAGMA user_version = 99").unwrap();
+        let error = database.migrate().unwrap_err();
+        assert!(error.to_string().contains("schema version 99"), "{}", error);
+    }
+
+    #[test]
+    fn test_events_without_id_are_skipped() {
+        let mut database = database();
+        let event = GitHubEvent::new(
+            crate::models::EventKind::Watch,
+            "a/b".to_string(),
+            EventPayload::WatchEvent,
+            None,
+        );
+        assert_eq!(database.upsert("octocat", &[event], &[]).unwrap(), 0);
+        assert_eq!(database.count().unwrap(), 0);
+    }
+}
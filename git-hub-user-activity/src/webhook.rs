@@ -0,0 +1,122 @@
+//! Este módulo monta o corpo que --post-webhook envia: os eventos (novos, com --new)
+//! de um alvo, para painéis de equipe
+//!
+//! Há dois formatos:
+//!
+//! - [`format_payload`]: um objeto JSON com os eventos completos, no mesmo formato
+//!   de `--format ndjson`, para quem vai processar os dados
+//! - [`format_slack_payload`] (--slack): `{"text": "..."}`, o corpo que os webhooks de
+//!   entrada do Slack (e de ferramentas compatíveis, como Mattermost) aceitam, com uma
+//!   linha por evento no "mrkdwn" do Slack
+//!
+//! ```text
+//! {"source":"torvalds","count":2,"events":[{"id":1,"type":"PushEvent",...},...]}
+//! {"text":"*torvalds*: 2 new events\n• <https://github.com/a/b|Pushed 2 commits to a/b>\n..."}
+//! ```
+//!
+//! O envio em si fica em [`crate::api::GitHubClient::post_webhook`]
+
+use crate::display::format_event;
+use crate::json::Json;
+use crate::models::GitHubEvent;
+
+/// Quantos eventos entram na mensagem do Slack; o resto vira "…and N more"
+/// (o Slack corta mensagens longas, e um painel não precisa de 300 linhas)
+pub const SLACK_MAX_EVENTS: usize = 20;
+
+/// O corpo genérico: `{"source":...,"count":N,"events":[...]}`, com todos os eventos
+pub fn format_payload(source: &str, events: &[GitHubEvent]) -> String {
+    Json::object([
+        ("source", source.into()),
+        ("count", events.len().into()),
+        // Cada evento como em --format ndjson (display::format_event_json)
+        ("events", Json::array(events)),
+    ])
+    .to_string()
+}
+
+/// O corpo do Slack: `{"text":"..."}`, com um título e um item (com link) por evento
+pub fn format_slack_payload(source: &str, events: &[GitHubEvent]) -> String {
+    json_object_text(&slack_text(source, events))
+}
+
+fn json_object_text(text: &str) -> String {
+    Json::object([("text", text.into())]).to_string()
+}
+
+// "*torvalds*: 2 new events" e uma linha "• <url|descrição>" por evento
+fn slack_text(source: &str, events: &[GitHubEvent]) -> String {
+    let plural = if events.len() == 1 { "" } else { "s" };
+    let mut lines = vec![format!("*{}*: {} new event{}", slack_escape(source), events.len(), plural)];
+
+    for event in events.iter().take(SLACK_MAX_EVENTS) {
+        // Dentro de <url|texto>, o link termina no primeiro '>': a URL (que leva o nome
+        // do repositório) passa pelo mesmo escape que o texto
+        // Sem um link que leve ao lugar certo, só a descrição
+        match event.html_url() {
+            Some(url) => lines.push(format!("• <{}|{}>", slack_escape(&url), slack_escape(&format_event(event)))),
+            None => lines.push(format!("• {}", slack_escape(&format_event(event)))),
+        }
+    }
+    if events.len() > SLACK_MAX_EVENTS {
+        lines.push(format!("…and {} more", events.len() - SLACK_MAX_EVENTS));
+    }
+    lines.join("\n")
+}
+
+// CONCEITO: Escapar para o destino
+// No mrkdwn do Slack, &, < e > têm significado (entidades e links); o resto passa como está.
+// Caracteres de controle (de nomes de repositório, por exemplo) viram espaços: a quebra
+// de linha entre os eventos é a única que a mensagem deve ter
+fn slack_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            c if c.is_control() => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EventKind, EventPayload};
+
+    fn star(repo: &str) -> GitHubEvent {
+        GitHubEvent::new(EventKind::Watch, repo.to_string(), EventPayload::WatchEvent, None)
+    }
+
+    #[test]
+    fn test_format_payload() {
+        let payload = format_payload("torvalds", &[star("a/b")]);
+        assert!(payload.starts_with(r#"{"source":"torvalds","count":1,"events":[{"id":null,"type":"WatchEvent""#));
+        assert!(payload.ends_with("}]}"), "{}", payload);
+        assert_eq!(format_payload("a/b", &[]), r#"{"source":"a/b","count":0,"events":[]}"#);
+    }
+
+    #[test]
+    fn test_format_slack_payload() {
+        assert_eq!(
+            format_slack_payload("torvalds", &[star("a/b")]),
+            r#"{"text":"*torvalds*: 1 new event\n• <https://github.com/a/b|Starred a/b>"}"#
+        );
+    }
+
+    #[test]
+    fn test_slack_text_escapes_and_truncates() {
+        let events: Vec<GitHubEvent> = (0..SLACK_MAX_EVENTS + 3).map(|_| star("a/<b>&\x1b[31m")).collect();
+        let text = slack_text("x", &events);
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines.len(), 1 + SLACK_MAX_EVENTS + 1);
+        // A descrição já vem de format_event sem a sequência de escape
+        assert!(lines[1].ends_with("|Starred a/&lt;b&gt;&amp;>"), "{}", lines[1]);
+        assert_eq!(lines[SLACK_MAX_EVENTS + 1], "…and 3 more");
+        assert!(!text.replace('\n', "").chars().any(char::is_control));
+    }
+}
@@ -0,0 +1,153 @@
+// Testes do cliente assíncrono (GitHubClient::*_async), só com a feature "async":
+//   cargo test --features async
+// As mesmas regras do cliente síncrono, contra o mesmo servidor falso de tests/common
+#![cfg(feature = "async")]
+
+mod common;
+
+use std::fs;
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use common::{MockServer, Response, USER_EVENTS};
+use github_activity::api::{GitHubClient, Target};
+use github_activity::cache::Cache;
+use github_activity::ActivityError;
+
+fn block_on<T>(future: impl Future<Output = T>) -> T {
+    tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap().block_on(future)
+}
+
+fn moved(location: &str) -> Response {
+    Response::with_status("301 Moved Permanently", "").header("Location", location)
+}
+
+#[test]
+fn a_renamed_user_is_followed_and_reported() {
+    let server = MockServer::start(Duration::ZERO, |request| {
+        if request.path.starts_with("/users/old-name/") {
+            moved(&request.path.replace("old-name", "new-name"))
+        } else {
+            Response::ok(USER_EVENTS)
+        }
+    });
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    let pages = block_on(client.fetch_user_pages_async("old-name", false)).unwrap();
+
+    assert!(!pages.outcome.events.is_empty());
+    let paths: Vec<String> = server.requests().into_iter().map(|request| request.path).collect();
+    assert_eq!(paths, vec!["/users/old-name/events", "/users/new-name/events"]);
+    assert_eq!(client.renamed_login("old-name").as_deref(), Some("new-name"));
+}
+
+#[test]
+fn redirects_to_another_host_are_rejected() {
+    let other = MockServer::events();
+    let location = format!("{}/users/new-name/events", other.url);
+    let server = MockServer::start(Duration::ZERO, move |_| moved(&location));
+    let client = GitHubClient::builder().base_url(server.url.as_str()).token("ghp_secret").build();
+
+    let error = block_on(client.fetch_user_pages_async("old-name", false)).unwrap_err();
+
+    assert!(matches!(error, ActivityError::NetworkError { .. }), "{:?}", error);
+    assert!(error.to_string().contains("another host"), "{}", error);
+    // O token nunca chega ao outro servidor
+    assert!(other.requests().is_empty());
+    assert_eq!(client.renamed_login("old-name"), None);
+}
+
+#[test]
+fn redirect_loops_are_rejected() {
+    let server = MockServer::start(Duration::ZERO, |request| {
+        if request.path.starts_with("/users/a/") {
+            moved("/users/b/events")
+        } else {
+            moved("/users/a/events")
+        }
+    });
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    let error = block_on(client.fetch_user_pages_async("a", false)).unwrap_err();
+
+    assert!(error.to_string().contains("redirect loop"), "{}", error);
+    assert_eq!(server.requests().len(), 2);
+}
+
+// Um diretório só deste teste (os testes rodam em paralelo)
+fn cache_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("github-activity-it-async-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+const ETAG: &str = "W/\"v1\"";
+
+#[test]
+fn the_cache_is_revalidated_and_a_fresh_entry_needs_no_request() {
+    let server = MockServer::start(Duration::ZERO, |request| {
+        if request.header("if-none-match") == Some(ETAG) {
+            Response::with_status("304 Not Modified", "")
+        } else {
+            Response::ok(USER_EVENTS).header("ETag", ETAG)
+        }
+    });
+    let dir = cache_dir("cache");
+    let builder = GitHubClient::builder().base_url(server.url.as_str()).cache(Cache::new(&dir));
+
+    // Sem --max-age: a segunda busca vai com If-None-Match e o 304 usa o corpo guardado
+    let client = builder.clone().build();
+    let first = block_on(client.fetch_user_pages_async("octocat", false)).unwrap();
+    let second = block_on(client.fetch_user_pages_async("octocat", false)).unwrap();
+    assert!(!first.outcome.events.is_empty());
+    assert_eq!(first.outcome.events, second.outcome.events);
+    assert_eq!(second.bodies, vec![USER_EVENTS.to_string()]);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].header("if-none-match"), Some(ETAG));
+
+    // Com --max-age: nenhuma requisição
+    let client = builder.max_age(Duration::from_secs(300)).build();
+    let third = block_on(client.fetch_user_pages_async("octocat", false)).unwrap();
+    assert_eq!(third.outcome.events, first.outcome.events);
+    assert_eq!(server.requests().len(), 2);
+    assert!(client.cached_age("octocat").is_some());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn an_empty_success_is_an_empty_response() {
+    let server = MockServer::start(Duration::ZERO, |_| Response::ok(""));
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    let error = block_on(client.fetch_user_pages_async("octocat", false)).unwrap_err();
+
+    assert!(matches!(error, ActivityError::ParseError { .. }), "{:?}", error);
+    assert_eq!(error.to_string(), "Failed to parse response: empty response from server");
+}
+
+#[test]
+fn pages_stop_where_the_sync_client_stops() {
+    // A fixture tem 7 itens: com per_page 7, toda página vem cheia
+    for (max_pages, max_events) in [(3, None), (5, Some(10)), (2, Some(100))] {
+        let fetch = |concurrent: bool| {
+            let server = MockServer::events();
+            let mut builder = GitHubClient::builder().base_url(server.url.as_str()).per_page(7).max_pages(max_pages);
+            if let Some(max_events) = max_events {
+                builder = builder.max_events(max_events);
+            }
+            let client = builder.build();
+            let pages = if concurrent {
+                block_on(client.fetch_user_pages_async("octocat", true)).unwrap()
+            } else {
+                client.fetch_target_pages(&Target::parse("octocat"), true).unwrap()
+            };
+            let paths: Vec<String> = server.requests().into_iter().map(|request| request.path).collect();
+            let warnings: Vec<usize> = pages.outcome.warnings.iter().map(|warning| warning.index).collect();
+            (paths, pages.outcome.events, warnings, pages.bodies.len())
+        };
+
+        assert_eq!(fetch(true), fetch(false), "max_pages {}, max_events {:?}", max_pages, max_events);
+    }
+}
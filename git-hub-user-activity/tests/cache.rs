@@ -0,0 +1,150 @@
+// O cache de respostas contra o servidor falso de tests/common: a segunda busca vai com
+// If-None-Match, e um 304 devolve os mesmos eventos com o corpo guardado; com --max-age,
+// uma entrada nova o bastante nem chega a virar requisição
+
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use common::{MockServer, Response, USER_EVENTS};
+use github_activity::api::{GitHubClient, Target};
+use github_activity::cache::Cache;
+use github_activity::date::Timestamp;
+
+const ETAG: &str = "W/\"v1\"";
+
+// Um diretório só deste teste (os testes rodam em paralelo)
+fn cache_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("github-activity-it-cache-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+// Responde 304 a quem manda o ETag atual; aos outros, os eventos com o ETag
+fn etag_server() -> MockServer {
+    MockServer::start(Duration::ZERO, |request| {
+        if request.header("if-none-match") == Some(ETAG) {
+            Response::with_status("304 Not Modified", "")
+        } else {
+            Response::ok(USER_EVENTS).header("ETag", ETAG)
+        }
+    })
+}
+
+#[test]
+fn an_unchanged_page_comes_from_the_cache() {
+    let server = etag_server();
+    let dir = cache_dir("hit");
+    let client = GitHubClient::builder().base_url(server.url.as_str()).cache(Cache::new(&dir)).build();
+
+    let first = client.fetch_user_events("octocat").unwrap();
+    let second = client.fetch_user_events("Octocat").unwrap();
+
+    assert!(!first.events.is_empty());
+    assert_eq!(first.events, second.events);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].header("if-none-match"), None);
+    assert_eq!(requests[1].header("if-none-match"), Some(ETAG));
+
+    let info = Cache::new(&dir).info().unwrap();
+    assert_eq!(info.entries.len(), 1);
+    assert_eq!(info.entries[0].username, "octocat");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn without_a_cache_nothing_is_conditional() {
+    let server = etag_server();
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    client.fetch_user_events("octocat").unwrap();
+    client.fetch_user_events("octocat").unwrap();
+
+    assert!(server.requests().iter().all(|request| request.header("if-none-match").is_none()));
+}
+
+#[test]
+fn the_first_page_of_all_is_cached_too() {
+    let server = etag_server();
+    let dir = cache_dir("pages");
+    let client = GitHubClient::builder()
+        .base_url(server.url.as_str())
+        .per_page(100)
+        .max_pages(1)
+        .cache(Cache::new(&dir))
+        .build();
+
+    // Com all, a primeira página vai com page=1 e ainda é guardada
+    client.fetch_target_pages(&Target::parse("octocat"), true).unwrap();
+    client.fetch_target_pages(&Target::parse("octocat"), true).unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[1].header("if-none-match"), Some(ETAG));
+    assert!(Cache::new(&dir).load("octocat").is_some());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn a_fresh_entry_needs_no_request() {
+    let server = etag_server();
+    let dir = cache_dir("fresh");
+    let client = GitHubClient::builder()
+        .base_url(server.url.as_str())
+        .cache(Cache::new(&dir))
+        .max_age(Duration::from_secs(300))
+        .build();
+
+    let first = client.fetch_user_events("octocat").unwrap();
+    assert_eq!(client.cached_age("octocat"), None);
+    let second = client.fetch_user_events("octocat").unwrap();
+
+    assert_eq!(first.events, second.events);
+    assert_eq!(server.requests().len(), 1);
+    assert!(client.cached_age("OctoCat").is_some_and(|age| age < Duration::from_secs(300)));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn max_age_zero_always_revalidates() {
+    let server = etag_server();
+    let dir = cache_dir("zero");
+    let client = GitHubClient::builder()
+        .base_url(server.url.as_str())
+        .cache(Cache::new(&dir))
+        .max_age(Duration::ZERO)
+        .build();
+
+    client.fetch_user_events("octocat").unwrap();
+    client.fetch_user_events("octocat").unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].header("if-none-match"), Some(ETAG));
+    assert_eq!(client.cached_age("octocat"), None);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn an_entry_from_the_future_is_revalidated() {
+    let server = etag_server();
+    let dir = cache_dir("future");
+    let cache = Cache::new(&dir);
+    let client = GitHubClient::builder()
+        .base_url(server.url.as_str())
+        .cache(cache.clone())
+        .max_age(Duration::from_secs(300))
+        .build();
+
+    client.fetch_user_events("octocat").unwrap();
+    // O relógio voltou uma hora depois da busca: a idade da entrada é desconhecida
+    let mut entry = cache.load("octocat").unwrap();
+    entry.fetched_at = Some(Timestamp::from_epoch_seconds(Timestamp::now().epoch_seconds() + 3600));
+    cache.store("octocat", &entry).unwrap();
+    client.fetch_user_events("octocat").unwrap();
+
+    assert_eq!(server.requests().len(), 2);
+    fs::remove_dir_all(&dir).unwrap();
+}
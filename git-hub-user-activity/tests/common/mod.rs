@@ -0,0 +1,144 @@
+// Servidor HTTP falso, local, compartilhado pelos testes de integração
+// Cada teste decide a resposta a partir da requisição, e depois pode conferir
+// o que chegou de fato pela rede (caminho e headers)
+//
+// Nada sai da máquina: o servidor escuta em 127.0.0.1, numa porta escolhida pelo sistema
+
+// Cada arquivo em tests/ é uma crate separada e nem todos usam todas as funções
+#![allow(dead_code)]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+pub const USER_EVENTS: &str = include_str!("../fixtures/user_events.json");
+
+/// Uma requisição recebida pelo servidor
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    /// O corpo (de um POST), lido pelo content-length
+    pub body: String,
+}
+
+impl Request {
+    /// Valor de um header (o nome não diferencia maiúsculas)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A resposta que o servidor devolve
+pub struct Response {
+    pub status: &'static str,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn ok(body: impl Into<Vec<u8>>) -> Self {
+        Response::with_status("200 OK", body)
+    }
+
+    pub fn with_status(status: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        Response { status, headers: Vec::new(), body: body.into() }
+    }
+
+    pub fn header(mut self, name: &'static str, value: &str) -> Self {
+        self.headers.push((name, value.to_string()));
+        self
+    }
+}
+
+type Handler = dyn Fn(&Request) -> Response + Send + Sync;
+
+/// Servidor em execução: o endereço base (para GitHubClient::builder().base_url) e as requisições recebidas
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+    /// Sobe o servidor; cada conexão é atendida em uma thread própria
+    /// e espera `delay` antes de responder (para medir paralelismo)
+    pub fn start(delay: Duration, handler: impl Fn(&Request) -> Response + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let received = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let received = Arc::clone(&received);
+                let handler = Arc::clone(&handler);
+                thread::spawn(move || respond(stream, delay, &*handler, &received));
+            }
+        });
+
+        MockServer { url: format!("http://{}", address), requests }
+    }
+
+    /// Servidor sem atraso que responde sempre com a fixture de eventos
+    pub fn events() -> Self {
+        MockServer::start(Duration::ZERO, |_| Response::ok(USER_EVENTS))
+    }
+
+    /// As requisições recebidas até agora, na ordem de chegada
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn respond(mut stream: TcpStream, delay: Duration, handler: &Handler, received: &Mutex<Vec<Request>>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+    // "GET /users/alice/events HTTP/1.1" -> "GET" e "/users/alice/events"
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("").to_string();
+    let path = parts.next().unwrap_or("").to_string();
+
+    let mut headers = Vec::new();
+    let mut line = String::new();
+    while reader.read_line(&mut line).unwrap() > 2 {
+        if let Some((name, value)) = line.trim_end().split_once(':') {
+            headers.push((name.to_string(), value.trim().to_string()));
+        }
+        line.clear();
+    }
+
+    let length = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).unwrap();
+
+    let request = Request { method, path, headers, body: String::from_utf8_lossy(&body).into_owned() };
+    received.lock().unwrap().push(request.clone());
+
+    thread::sleep(delay);
+
+    let response = handler(&request);
+    let mut head = format!(
+        "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n",
+        response.status,
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(&response.body);
+}
@@ -0,0 +1,61 @@
+// Testes das respostas comprimidas com gzip, contra o servidor falso de tests/common
+// A fixture .json.gz é a mesma user_events.json, comprimida de antemão
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MockServer, Response, USER_EVENTS};
+use github_activity::api::{GitHubClient, Target};
+use github_activity::ActivityError;
+
+const USER_EVENTS_GZ: &[u8] = include_bytes!("fixtures/user_events.json.gz");
+
+fn client(base_url: &str, compression: bool) -> GitHubClient {
+    GitHubClient::builder().base_url(base_url).compression(compression).build()
+}
+
+// Só comprime quando o cliente pediu, como a API de verdade
+fn gzip_server() -> MockServer {
+    MockServer::start(Duration::ZERO, |request| {
+        if request.header("accept-encoding") == Some("gzip") {
+            Response::ok(USER_EVENTS_GZ).header("content-encoding", "gzip")
+        } else {
+            Response::ok(USER_EVENTS)
+        }
+    })
+}
+
+#[test]
+fn gzip_responses_are_decompressed() {
+    let server = gzip_server();
+
+    let pages = client(&server.url, true).fetch_target_pages(&Target::parse("alice"), false).unwrap();
+
+    assert_eq!(pages.outcome.events.len(), 7);
+    // O corpo guardado (para --record) é o JSON já descomprimido
+    assert_eq!(pages.bodies, vec![USER_EVENTS.to_string()]);
+    assert_eq!(server.requests()[0].header("accept-encoding"), Some("gzip"));
+}
+
+#[test]
+fn no_compression_does_not_ask_for_gzip() {
+    let server = gzip_server();
+
+    let pages = client(&server.url, false).fetch_target_pages(&Target::parse("alice"), false).unwrap();
+
+    assert_eq!(pages.outcome.events.len(), 7);
+    assert_eq!(server.requests()[0].header("accept-encoding"), None);
+}
+
+#[test]
+fn corrupt_gzip_is_a_parse_error() {
+    let server = MockServer::start(Duration::ZERO, |_| {
+        Response::ok(&USER_EVENTS_GZ[..40]).header("content-encoding", "gzip")
+    });
+
+    let error = client(&server.url, true).fetch_target_pages(&Target::parse("alice"), false).unwrap_err();
+
+    assert!(matches!(error, ActivityError::ParseError { .. }));
+    assert!(error.to_string().contains("content-encoding"), "{}", error);
+}
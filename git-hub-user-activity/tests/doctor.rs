@@ -0,0 +1,137 @@
+// As verificações de --doctor (doctor::run_checks) contra o servidor falso de tests/common
+// O servidor imita os três endpoints usados: a raiz, GET /user e GET /rate_limit
+
+mod common;
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use common::{MockServer, Response};
+use github_activity::api::GitHubClient;
+use github_activity::credentials::TokenSource;
+use github_activity::doctor::{self, CheckResult, Environment, Status};
+
+const GOOD_TOKEN: &str = "ghp_good";
+
+fn api_server(remaining: u64) -> MockServer {
+    MockServer::start(Duration::ZERO, move |request| match request.path.as_str() {
+        "/" => Response::ok(r#"{"current_user_url": "https://api.github.com/user"}"#),
+        "/user" if request.header("authorization") == Some(&format!("Bearer {}", GOOD_TOKEN)) => {
+            Response::ok(r#"{"login": "octocat", "public_repos": 8, "followers": 9000}"#)
+        }
+        "/user" => Response::with_status("401 Unauthorized", r#"{"message": "Bad credentials"}"#),
+        "/rate_limit" => Response::ok(format!(
+            r#"{{"resources": {{}}, "rate": {{"limit": 5000, "remaining": {}, "reset": 1714584720}}}}"#,
+            remaining
+        )),
+        _ => Response::with_status("404 Not Found", r#"{"message": "Not Found"}"#),
+    })
+}
+
+fn client(url: &str, token: Option<&str>) -> GitHubClient {
+    let mut builder = GitHubClient::builder().base_url(url).timeout(Duration::from_secs(2));
+    if let Some(token) = token {
+        builder = builder.token(token);
+    }
+    builder.build()
+}
+
+// Um diretório de cache só deste teste, apagado no fim
+struct CacheDir(PathBuf);
+
+impl CacheDir {
+    fn new(name: &str) -> CacheDir {
+        CacheDir(std::env::temp_dir().join(format!("github-activity-{}-{}", name, std::process::id())))
+    }
+}
+
+impl Drop for CacheDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+fn statuses(results: &[CheckResult]) -> Vec<(&str, Status)> {
+    results.iter().map(|result| (result.name, result.status)).collect()
+}
+
+#[test]
+fn everything_passes_with_a_good_token() {
+    let server = api_server(4999);
+    let cache = CacheDir::new("doctor-pass");
+    let environment =
+        Environment { token_source: Some(&TokenSource::Environment), cache_dir: Some(&cache.0), utc_offset: 0 };
+
+    let results = doctor::run_checks(&client(&server.url, Some(GOOD_TOKEN)), &environment);
+
+    assert_eq!(
+        statuses(&results),
+        vec![
+            ("API reachable", Status::Pass),
+            ("Token", Status::Pass),
+            ("Authentication", Status::Pass),
+            ("Rate limit", Status::Pass),
+            ("Cache directory", Status::Pass),
+        ]
+    );
+    assert!(results[0].detail.starts_with(&format!("{} answered with HTTP 200 in ", server.url)));
+    assert_eq!(results[2].detail, "authenticated as octocat");
+    assert_eq!(results[3].detail, "4999/5000 requests left, resets at 17:32");
+    assert!(!doctor::any_failed(&results));
+}
+
+#[test]
+fn a_rejected_token_and_an_exhausted_limit_fail() {
+    let server = api_server(0);
+    let cache = CacheDir::new("doctor-fail");
+    let environment =
+        Environment { token_source: Some(&TokenSource::CommandLine), cache_dir: Some(&cache.0), utc_offset: 0 };
+
+    let results = doctor::run_checks(&client(&server.url, Some("ghp_revoked")), &environment);
+
+    assert_eq!(results[2].status, Status::Fail);
+    assert!(results[2].detail.contains("Bad credentials"), "{}", results[2].detail);
+    assert_eq!(results[3].status, Status::Fail);
+    assert!(doctor::any_failed(&results));
+}
+
+#[test]
+fn without_a_token_it_only_warns() {
+    let server = api_server(60);
+    let cache = CacheDir::new("doctor-anonymous");
+    let environment = Environment { cache_dir: Some(&cache.0), ..Environment::default() };
+
+    let results = doctor::run_checks(&client(&server.url, None), &environment);
+
+    assert_eq!(results[1].status, Status::Warn);
+    assert_eq!(results[2], doctor::check_authentication(&client(&server.url, None)));
+    assert_eq!(results[2].status, Status::Warn);
+    assert!(!doctor::any_failed(&results));
+    // Sem token, GET /user nem é tentado
+    assert!(server.requests().iter().all(|request| request.path != "/user"));
+}
+
+#[test]
+fn an_unreachable_api_skips_the_api_checks() {
+    // Uma porta que acabou de ser liberada: a conexão é recusada na hora
+    let url = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    };
+    let cache = CacheDir::new("doctor-offline");
+    let environment = Environment { cache_dir: Some(&cache.0), ..Environment::default() };
+
+    let results = doctor::run_checks(&client(&url, Some(GOOD_TOKEN)), &environment);
+
+    assert_eq!(
+        statuses(&results),
+        vec![
+            ("API reachable", Status::Fail),
+            ("Token", Status::Warn),
+            ("Authentication", Status::Fail),
+            ("Rate limit", Status::Fail),
+            ("Cache directory", Status::Pass),
+        ]
+    );
+    assert_eq!(results[3].detail, "not checked: the API is not reachable");
+}
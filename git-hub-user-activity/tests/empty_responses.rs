@@ -0,0 +1,46 @@
+// Corpos que um proxy pode devolver com status 200 no lugar do array de eventos:
+// vazio (Content-Length: 0), só espaços, ou o array precedido do BOM do UTF-8
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MockServer, Response, USER_EVENTS};
+use github_activity::api::GitHubClient;
+use github_activity::{parse_events, ActivityError, GitHubEvent};
+
+fn fetch(body: &'static str) -> Result<Vec<GitHubEvent>, ActivityError> {
+    let server = MockServer::start(Duration::ZERO, move |_| Response::ok(body));
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+    client.fetch_user_events("octocat").map(|outcome| outcome.events)
+}
+
+fn assert_empty_response(result: Result<Vec<GitHubEvent>, ActivityError>) {
+    match result {
+        Err(error @ ActivityError::ParseError { .. }) => {
+            assert_eq!(error.to_string(), "Failed to parse response: empty response from server")
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn an_empty_success_is_an_empty_response() {
+    // O servidor falso manda Content-Length: 0: o cliente nem chega ao parser
+    assert_empty_response(fetch(""));
+}
+
+#[test]
+fn a_whitespace_body_is_an_empty_response() {
+    assert_empty_response(fetch(" \r\n\t\n"));
+}
+
+#[test]
+fn a_bom_before_the_array_is_skipped() {
+    let server = MockServer::start(Duration::ZERO, |_| Response::ok(format!("\u{feff}{}\n\n", USER_EVENTS)));
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    let events = client.fetch_user_events("octocat").unwrap().events;
+
+    assert_eq!(events, parse_events(USER_EVENTS).unwrap().events);
+}
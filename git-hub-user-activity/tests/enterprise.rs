@@ -0,0 +1,30 @@
+// GitHub Enterprise: a API fica em um caminho do servidor (https://host/api/v3)
+// O servidor falso de tests/common faz o papel do servidor da empresa
+
+mod common;
+
+use common::MockServer;
+use github_activity::api::{GitHubClient, Target};
+
+#[test]
+fn enterprise_base_keeps_its_path_prefix() {
+    let server = MockServer::events();
+
+    // Com e sem a barra final, como alguém digitaria no arquivo de configuração
+    for base_url in [format!("{}/api/v3", server.url), format!("{}/api/v3/", server.url)] {
+        let client = GitHubClient::builder().base_url(base_url).build();
+        client.fetch_target_pages(&Target::parse("octocat"), false).unwrap();
+        client.fetch_target_pages(&Target::parse("octocat/Hello-World"), false).unwrap();
+    }
+
+    let paths: Vec<String> = server.requests().into_iter().map(|request| request.path).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "/api/v3/users/octocat/events",
+            "/api/v3/repos/octocat/Hello-World/events",
+            "/api/v3/users/octocat/events",
+            "/api/v3/repos/octocat/Hello-World/events",
+        ]
+    );
+}
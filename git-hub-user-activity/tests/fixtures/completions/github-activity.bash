@@ -0,0 +1,131 @@
+# bash completion for github-activity
+# Generated by `github-activity --completions bash`; load it with:
+#   source <(github-activity --completions bash)
+
+_github_activity() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}"
+
+    case "$prev" in
+        --backend)
+            COMPREPLY=($(compgen -W "rest graphql" -- "$cur"))
+            return
+            ;;
+        --category)
+            COMPREPLY=($(compgen -W "code issues review social release other" -- "$cur"))
+            return
+            ;;
+        --sort)
+            COMPREPLY=($(compgen -W "newest oldest repo type stars updated name" -- "$cur"))
+            return
+            ;;
+        --group-by)
+            COMPREPLY=($(compgen -W "repo type" -- "$cur"))
+            return
+            ;;
+        --format)
+            COMPREPLY=($(compgen -W "text table ndjson csv" -- "$cur"))
+            return
+            ;;
+        --lang)
+            COMPREPLY=($(compgen -W "en pt-BR" -- "$cur"))
+            return
+            ;;
+        --from-file|--record|--export-sqlite|--replay|--output)
+            COMPREPLY=($(compgen -f -- "$cur"))
+            return
+            ;;
+        --since|--type|--action|--ignore-actor|--post-webhook|--limit|--compare|--template|--open|--per-page|--max-events|--jobs|--token|--max-age|--api-url|--events|--seed|--kinds)
+            return
+            ;;
+    esac
+
+    local flags=(
+        --all
+        --from-file
+        --record
+        --export-sqlite
+        --log-history
+        --history
+        --since
+        --backend
+        --starred
+        --list-repos
+        --no-forks
+        --replay
+        --output
+        --collapse
+        --quiet
+        --count-only
+        --type
+        --category
+        --action
+        --no-bots
+        --ignore-actor
+        --no-dedup
+        --commits
+        --new
+        --notify
+        --post-webhook
+        --slack
+        --dry-run
+        --reset-seen
+        --limit
+        --first-contributions
+        --only
+        --sort
+        --group-by
+        --summary
+        --histogram
+        --by-week
+        --utc
+        --compare
+        --repos-only
+        --json
+        --raw
+        --template
+        --format
+        --wide
+        --pager
+        --no-color
+        --hyperlinks
+        --numbered
+        --open
+        --lang
+        --emoji
+        --no-time
+        --no-profile
+        --per-page
+        --max-events
+        --jobs
+        --token
+        --no-gh-token
+        --wait-on-limit
+        --skip-validation
+        --no-compression
+        --no-cache
+        --max-age
+        --api-url
+        --verbose
+        -vv
+        --strict
+        --doctor
+        --cache-info
+        --clear-cache
+        --help
+    )
+    local generate_flags=(
+        --events
+        --seed
+        --kinds
+    )
+
+    if [[ $COMP_CWORD -gt 1 && "${COMP_WORDS[1]}" == generate ]]; then
+        COMPREPLY=($(compgen -W "${generate_flags[*]}" -- "$cur"))
+    elif [[ "$cur" == -* ]]; then
+        COMPREPLY=($(compgen -W "${flags[*]}" -- "$cur"))
+    elif [[ $COMP_CWORD -eq 1 ]]; then
+        COMPREPLY=($(compgen -W "generate" -- "$cur"))
+    fi
+}
+
+complete -F _github_activity github-activity
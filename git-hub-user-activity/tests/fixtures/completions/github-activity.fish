@@ -0,0 +1,83 @@
+# fish completion for github-activity
+# Generated by `github-activity --completions fish`; save it as
+# ~/.config/fish/completions/github-activity.fish
+
+complete -c github-activity -f
+complete -c github-activity -n __fish_use_subcommand -a generate -d 'Synthesize a realistic events JSON array'
+
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l all -d 'Fetch the full history GitHub keeps (up to 300 events, 3 requests)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l from-file -r -F -d 'Read an events JSON array from PATH (\'-\' for stdin) instead of the API'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l record -r -F -d 'Also save the raw API responses to PATH, with a small metadata header'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l export-sqlite -r -F -d 'Also add the fetched events to the SQLite database PATH (needs the \'sqlite\' feature)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l log-history -d 'Also append the fetched events to the local history of <username>'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l history -d 'Show the local history of <username> (see --log-history) instead of fetching'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l since -x -d 'With --history or --backend graphql, only events from DATE on (2024-05-01 or an RFC 3339 time)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l backend -x -a 'rest graphql' -d 'Fetch the events feed (rest, default) or the GraphQL contributions (needs \'graphql\' and a token)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l starred -d 'List the repositories <username> starred instead of the events'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l list-repos -d 'List the public repositories of <username> instead of the events (most recently updated first)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l no-forks -d 'With --list-repos, leave out the repositories that are forks'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l replay -r -F -d 'Show a file saved by --record (same as --from-file)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l output -r -F -d 'Write the output to PATH instead of the terminal (only if the run succeeds)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l collapse -d 'Merge consecutive pushes to the same repository into one line'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l quiet -d 'Print only the events (or the summary, JSON...): no progress line, header or trailing blank line'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l count-only -d 'Print only the number of events after the filters (per group with --group-by: KEY<TAB>COUNT)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l type -x -d 'Only events of these types, comma-separated API names (PushEvent,PullRequestEvent)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l category -x -a 'code issues review social release other' -d 'Only events of these categories, comma-separated (code,social); see below'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l action -x -d 'Only events whose payload action is one of these (opened,closed); events without one are left out'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l no-bots -d 'Hide events by bots ([bot] logins and the ignored_actors config key)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l ignore-actor -x -d 'Hide events by LOGIN (repeatable)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l no-dedup -d 'Keep repeated stars and forks of the same repository (by default only the latest is shown)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l commits -d 'List the commit messages of each push (up to 5 per push)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l new -d 'Show only events newer than the last run for this user or repository'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l notify -d 'With --new, also show a desktop notification summarizing the new events'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l post-webhook -x -d 'Also POST the events (only the new ones with --new) to URL as JSON'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l slack -d 'With --post-webhook, send a Slack message ({"text": ...}) instead of the events'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l dry-run -d 'With --post-webhook, print the body to stderr instead of sending it'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l reset-seen -d 'Forget the last-seen event for this user or repository and exit'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l limit -x -d 'Show at most N events'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l first-contributions -d 'Mark events in repos with no older activity in this window'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l only -d 'With --first-contributions, show only the marked events'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l sort -x -a 'newest oldest repo type stars updated name' -d 'Order of the listed events (default: newest first; repo and type keep the time order within each) or, with stars|updated|name, of --list-repos'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l group-by -x -a 'repo type' -d 'Group events under a heading per repository or per event type'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l summary -d 'Print totals per event type and the most active repositories'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l histogram -d 'Chart the number of events per day, in local time'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l by-week -d 'Table of events per ISO week (pushes, PRs, issues, other), newest week first'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l utc -d 'With --histogram, --by-week or --summary, count days in UTC instead of local time'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l compare -x -d 'Compare with another user: totals, types, shared repos, busiest day'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l repos-only -d 'List each repository touched, with its event count and latest event type'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l json -d 'With --summary, --repos-only, --by-week or --doctor, print the result as JSON'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l raw -d 'Print the response body as the API sent it (or the --from-file file), indented'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l template -x -d 'Print each event as TEXT instead of the usual line (see below)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l format -x -a 'text table ndjson csv' -d 'List events as text (default), table (aligned columns) or ndjson (see below); csv is for --by-week'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l wide -d 'With --format table, don\'t shorten long repository names and details'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l pager -d 'In a terminal, show long output one screen at a time (space, Enter, q)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l no-color -d 'Disable colors (also disabled by NO_COLOR or when not a terminal)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l hyperlinks -d 'In a terminal, make repositories, issues and PRs clickable links'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l numbered -d 'Number the events (1, 2, 3...) instead of starting each line with \'-\''
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l open -x -d 'After listing, open event number N on GitHub in the default browser'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l lang -x -a 'en pt-BR' -d 'Language of the output (default: from LANG, else en)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l emoji -d 'Start each event with an icon for its type (📦 push, ⭐ star, 🔀 PR...)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l no-time -d 'Don\'t show relative timestamps like "(2 hours ago)"'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l no-profile -d 'Don\'t fetch the user\'s name and followers for the header (saves a request)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l per-page -x -d 'Ask the API for N events per page, 1 to 100 (with --all, default: 100)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l max-events -x -d 'With --all, stop fetching pages once N events were fetched'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l jobs -x -d 'With several usernames, fetch up to N at the same time (default: 4)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l token -x -d 'GitHub access token, for a higher rate limit (also GITHUB_TOKEN)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l no-gh-token -d 'Don\'t fall back to the token of the gh CLI login'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l wait-on-limit -d 'On a secondary rate limit, wait as asked (up to 60s) and retry once'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l skip-validation -d 'Don\'t check usernames against github.com\'s rules (for Enterprise instances with other rules)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l no-compression -d 'Don\'t ask the API for gzip-compressed responses'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l no-cache -d 'Don\'t read or update the response cache (always download the events again)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l max-age -x -d 'Use cached events younger than SECONDS without asking the API (0: always revalidate)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l api-url -x -d 'API base URL, e.g. for GitHub Enterprise (default: https://api.github.com)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l verbose -d 'Log requests and parsing to stderr, and explain skipped or unknown events (-v)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -o vv -d 'Like --verbose, plus request and response headers (the token is redacted)'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l strict -d 'Fail instead of skipping events that could not be parsed'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l doctor -d 'Check the connection, the token, the rate limit and the cache directory, then exit'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l cache-info -d 'Show where the response cache is, the cached users, their size and age, then exit'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l clear-cache -d 'Delete the cached responses (only of the given usernames, if any), then exit'
+complete -c github-activity -n 'not __fish_seen_subcommand_from generate' -l help -d 'Show this help and exit'
+
+complete -c github-activity -n '__fish_seen_subcommand_from generate' -l events -x -d 'Number of events to generate (default: 30)'
+complete -c github-activity -n '__fish_seen_subcommand_from generate' -l seed -x -d 'Seed for the deterministic generator (default: 42)'
+complete -c github-activity -n '__fish_seen_subcommand_from generate' -l kinds -x -d 'Comma-separated event kinds, e.g. push,issue,star (default: all)'
@@ -0,0 +1,96 @@
+// Entradas que o alvo de fuzzing (fuzz/fuzz_targets/parse_events.rs) exercita, fixadas
+// aqui para rodar em todo `cargo test`, sem o nightly nem o cargo-fuzz
+//
+// Cada caso é um texto que já derrubou (ou poderia derrubar) um parser que fatia o
+// texto e conta profundidade à mão: fechamentos sem abertura, strings e escapes
+// cortados, caracteres multi-byte perto dos trechos de erro, aninhamento fundo.
+// Um crash novo do fuzzer, minimizado (cargo fuzz tmin), entra em CASES
+
+use std::fs;
+use std::path::Path;
+
+use github_activity::parse_events;
+
+const CASES: &[&str] = &[
+    // Fechamentos sem abertura: a contagem de profundidade não pode ficar negativa
+    "]}",
+    "[]}",
+    "[]]",
+    "[}]",
+    "[]}]",
+    "[{]}",
+    "[{}]}]",
+    "[[]]]]]",
+    "[{\"a\":]}]",
+    // Strings e escapes cortados no fim do texto
+    "[\"",
+    "[\"\\",
+    "[\"\\u",
+    "[\"\\ud83d",
+    "[{\"type\":\"\\ud83d\\u\"}]",
+    "[{\"type\":\"\\udc00\"}]",
+    "[{\"type\":\"\\ud83d\\ud83d\"}]",
+    // Multi-byte logo antes e depois do ponto do erro (o trecho não pode cortar um caractere)
+    "[{\"type\":\"é🦀\",\"repo\":{\"name\":\"修\"} 🦀]",
+    "[🦀]",
+    "[{\"🦀\":1,}]",
+    "\u{feff}[]",
+    // Números e literais pela metade
+    "[-]",
+    "[1e]",
+    "[0.]",
+    "[tru]",
+    "[nul",
+    // Uma vírgula sobrando ou faltando
+    "[,]",
+    "[{},]",
+    "[{}{}]",
+    "[,,,,]",
+];
+
+#[test]
+fn known_inputs_do_not_panic() {
+    for case in CASES {
+        let _ = parse_events(case);
+    }
+}
+
+#[test]
+fn unbalanced_closers_are_errors() {
+    assert!(parse_events("]}").is_err());
+    assert!(parse_events("[]}").is_err());
+    assert!(parse_events("[]]").is_err());
+    // Dentro do array, um elemento mal formado só vira aviso
+    let outcome = parse_events("[{]}, {}]").unwrap_or_default();
+    assert!(outcome.events.is_empty());
+}
+
+#[test]
+fn deep_nesting_is_an_error_not_a_stack_overflow() {
+    for depth in [200, 10_000] {
+        let event = format!("[{{\"type\":\"PushEvent\",\"payload\":{}{}}}]", "[".repeat(depth), "]".repeat(depth));
+        let outcome = parse_events(&event).unwrap();
+        assert_eq!(outcome.events.len(), 0);
+        assert_eq!(outcome.warnings.len(), 1);
+        assert!(parse_events(&"[".repeat(depth)).is_err());
+    }
+}
+
+// A semente do fuzzer são respostas de verdade: todas precisam continuar sendo lidas
+#[test]
+fn the_fuzz_corpus_parses() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz/corpus/parse_events");
+    let mut seen = 0;
+    for entry in fs::read_dir(&corpus).unwrap() {
+        let path = entry.unwrap().path();
+        let text = fs::read_to_string(&path).unwrap();
+        let result = parse_events(&text);
+        if path.file_name().unwrap() == "not_found.json" {
+            assert!(result.is_err());
+        } else {
+            assert!(result.unwrap().warnings.is_empty(), "{}", path.display());
+        }
+        seen += 1;
+    }
+    assert!(seen >= 5);
+}
@@ -0,0 +1,84 @@
+// Testes de --backend graphql (GitHubClient::fetch_contributions), só com a feature "graphql":
+//   cargo test --features graphql
+// O servidor falso (de tests/common) faz o papel do endpoint /graphql
+#![cfg(feature = "graphql")]
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MockServer, Response};
+use github_activity::api::GitHubClient;
+use github_activity::date::Timestamp;
+use github_activity::error::ActivityError;
+use github_activity::models::EventPayload;
+
+const CONTRIBUTIONS: &str = r#"{"data": {"user": {"contributionsCollection": {
+    "commitContributionsByRepository": [{"repository": {"nameWithOwner": "octocat/hello"},
+        "contributions": {"nodes": [{"commitCount": 4, "occurredAt": "2024-05-02T00:00:00Z"}]}}],
+    "pullRequestContributions": {"nodes": [{"occurredAt": "2024-05-03T08:00:00Z",
+        "pullRequest": {"number": 9, "title": "Add tests", "repository": {"nameWithOwner": "octocat/hello"}}}]}
+}}}}"#;
+
+fn window() -> (Timestamp, Timestamp) {
+    (Timestamp::parse_date("2024-01-01").unwrap(), Timestamp::parse_date("2024-06-01").unwrap())
+}
+
+fn client(server: &MockServer) -> GitHubClient {
+    GitHubClient::builder().base_url(server.url.as_str()).token("ghp_test").build()
+}
+
+#[test]
+fn posts_the_query_with_the_token() {
+    let server = MockServer::start(Duration::ZERO, |_| Response::ok(CONTRIBUTIONS));
+    let (from, to) = window();
+
+    let outcome = client(&server).fetch_contributions("octocat", from, to).unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/graphql");
+    assert_eq!(requests[0].header("authorization"), Some("Bearer ghp_test"));
+    assert_eq!(requests[0].header("content-type"), Some("application/json"));
+    assert!(requests[0].body.contains(r#""login":"octocat","from":"2024-01-01T00:00:00Z""#), "{}", requests[0].body);
+
+    // O PR (mais novo) primeiro, com o título que o feed às vezes não traz
+    assert_eq!(outcome.events.len(), 2);
+    assert!(matches!(
+        &outcome.events[0].payload,
+        EventPayload::PullRequestEvent { number: Some(9), title: Some(title), .. } if title == "Add tests"
+    ));
+    assert!(matches!(outcome.events[1].payload, EventPayload::Push { commit_count: 4, .. }));
+}
+
+#[test]
+fn errors_in_a_200_response_become_api_errors() {
+    let server = MockServer::start(Duration::ZERO, |_| {
+        Response::ok(r#"{"data": {"user": null}, "errors": [{"type": "NOT_FOUND", "message": "Could not resolve"}]}"#)
+    });
+    let (from, to) = window();
+    let error = client(&server).fetch_contributions("nobody", from, to).unwrap_err();
+    assert!(matches!(&error, ActivityError::UserNotFound(name) if name == "nobody"), "{:?}", error);
+
+    let server = MockServer::start(Duration::ZERO, |_| {
+        Response::ok(r#"{"errors": [{"message": "must not exceed 1 year"}]}"#)
+    });
+    let error = client(&server).fetch_contributions("octocat", from, to).unwrap_err();
+    match error {
+        ActivityError::ApiError { status: 0, message, .. } => assert_eq!(message, "must not exceed 1 year"),
+        other => panic!("expected ApiError, got {:?}", other),
+    }
+}
+
+#[test]
+fn a_token_is_required() {
+    let server = MockServer::start(Duration::ZERO, |_| Response::ok(CONTRIBUTIONS));
+    let (from, to) = window();
+
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+    let error = client.fetch_contributions("octocat", from, to).unwrap_err();
+
+    assert!(error.to_string().contains("token"), "{}", error);
+    assert!(server.requests().is_empty());
+}
@@ -0,0 +1,60 @@
+// --list-repos contra o servidor falso de tests/common: os campos que --starred não usa
+// (fork, updated_at, linguagem e descrição null) e o ?sort=updated junto da paginação
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MockServer, Response};
+use github_activity::api::GitHubClient;
+use github_activity::display::display_repositories;
+use github_activity::stats::{sort_repositories, RepoSort};
+
+const USER_REPOS: &str = include_str!("fixtures/user_repos.json");
+
+fn fixture_server() -> MockServer {
+    MockServer::start(Duration::ZERO, |_| Response::ok(USER_REPOS))
+}
+
+fn paths(server: &MockServer) -> Vec<String> {
+    server.requests().into_iter().map(|request| request.path).collect()
+}
+
+#[test]
+fn asks_for_the_most_recently_updated_first() {
+    let server = fixture_server();
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+    client.fetch_repos("torvalds", false).unwrap();
+
+    let client = GitHubClient::builder().base_url(server.url.as_str()).per_page(10).build();
+    client.fetch_repos("torvalds", true).unwrap();
+
+    assert_eq!(
+        paths(&server),
+        vec!["/users/torvalds/repos?sort=updated", "/users/torvalds/repos?sort=updated&per_page=10&page=1"]
+    );
+}
+
+#[test]
+fn renders_forks_and_missing_fields() {
+    let server = fixture_server();
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+    let mut repositories = client.fetch_repos("torvalds", false).unwrap();
+
+    assert!(repositories[1].fork);
+    assert_eq!(repositories[2].language, None);
+    sort_repositories(&mut repositories, RepoSort::Stars);
+
+    let mut out = Vec::new();
+    display_repositories(&mut out, &repositories).unwrap();
+    let table = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines[0], "  NAME                     STARS  LANGUAGE  UPDATED     DESCRIPTION");
+    assert_eq!(lines[1], "  linux                  171,234  C         2024-05-20  Linux kernel source tree");
+    assert_eq!(lines[2], "  uemacs                   1,490  C         2023-01-15  Random version of microemacs with my private modificatons");
+    assert_eq!(lines[3], "  subsurface-for-dirk      1,312  -         2023-11-02");
+    assert_eq!(
+        lines[4],
+        "  libdc-for-dirk (fork)      285  C         2024-04-02  Only use for syncing with Dirk, don't use for anything else"
+    );
+}
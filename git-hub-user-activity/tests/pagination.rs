@@ -0,0 +1,210 @@
+// URLs pedidas com --per-page, --all e --max-events, contra o servidor falso de tests/common
+// O servidor imita a paginação da API: um histórico de HISTORY eventos,
+// entregue em páginas do tamanho pedido (30 se a URL não disser)
+
+mod common;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::{MockServer, Response};
+use github_activity::api::{GitHubClient, Target};
+use github_activity::generator::{generate_events_json, GenerateOptions};
+use github_activity::ActivityError;
+
+const HISTORY: usize = 250;
+
+fn paginated_server() -> MockServer {
+    MockServer::start(Duration::ZERO, |request| {
+        let param = |name: &str| {
+            let query = request.path.split_once('?').map(|(_, query)| query).unwrap_or("");
+            query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+                .and_then(|value| value.parse::<usize>().ok())
+        };
+        let per_page = param("per_page").unwrap_or(30);
+        let page = param("page").unwrap_or(1);
+
+        let events = HISTORY.saturating_sub((page - 1) * per_page).min(per_page);
+        Response::ok(generate_events_json(&GenerateOptions { events, ..GenerateOptions::default() }))
+    })
+}
+
+fn client(server: &MockServer, per_page: Option<usize>, max_events: Option<usize>) -> GitHubClient {
+    let mut builder = GitHubClient::builder().base_url(server.url.as_str());
+    if let Some(per_page) = per_page {
+        builder = builder.per_page(per_page);
+    }
+    if let Some(max_events) = max_events {
+        builder = builder.max_events(max_events);
+    }
+    builder.build()
+}
+
+// Busca "alice" e devolve quantos eventos vieram e os caminhos pedidos
+fn fetch(all: bool, per_page: Option<usize>, max_events: Option<usize>) -> (usize, Vec<String>) {
+    let server = paginated_server();
+
+    let pages = client(&server, per_page, max_events).fetch_target_pages(&Target::parse("alice"), all).unwrap();
+    let paths = server.requests().into_iter().map(|request| request.path).collect();
+    (pages.outcome.events.len(), paths)
+}
+
+#[test]
+fn one_page_without_all() {
+    assert_eq!(fetch(false, None, None), (30, vec!["/users/alice/events".to_string()]));
+    assert_eq!(fetch(false, Some(10), None), (10, vec!["/users/alice/events?per_page=10".to_string()]));
+}
+
+#[test]
+fn all_pages_follow_per_page() {
+    let (count, paths) = fetch(true, None, None);
+    assert_eq!(count, HISTORY);
+    assert_eq!(
+        paths,
+        vec![
+            "/users/alice/events?per_page=100&page=1",
+            "/users/alice/events?per_page=100&page=2",
+            "/users/alice/events?per_page=100&page=3",
+        ]
+    );
+
+    // Páginas menores precisam de mais requisições para o mesmo histórico
+    // (a quinta vem cheia, então só a sexta, vazia, mostra que acabou)
+    let (count, paths) = fetch(true, Some(50), None);
+    assert_eq!(count, HISTORY);
+    assert_eq!(paths.len(), 6);
+    assert_eq!(paths[5], "/users/alice/events?per_page=50&page=6");
+}
+
+#[test]
+fn max_events_stops_pagination_early() {
+    let (count, paths) = fetch(true, Some(50), Some(120));
+    assert_eq!(count, 120);
+    // A terceira página já passa de 120: a quarta nunca é pedida
+    assert_eq!(paths.last().unwrap(), "/users/alice/events?per_page=50&page=3");
+    assert_eq!(paths.len(), 3);
+
+    // Sem --all, só corta a única página
+    assert_eq!(fetch(false, None, Some(5)).0, 5);
+}
+
+#[test]
+fn invalid_paging_fails_before_any_request() {
+    let server = paginated_server();
+    for (per_page, max_events) in [(Some(0), None), (Some(101), None), (None, Some(0))] {
+        let client = client(&server, per_page, max_events);
+        let error = client.fetch_target_pages(&Target::parse("alice"), true).unwrap_err();
+        assert!(matches!(error, ActivityError::InvalidArgument(_)), "{:?}", error);
+    }
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn event_pages_fetch_one_page_per_step() {
+    let server = paginated_server();
+    let client = client(&server, None, None);
+    let mut pages = client.event_pages(&Target::parse("alice"), true).unwrap();
+
+    // Nada é pedido antes do primeiro next()
+    assert!(server.requests().is_empty());
+    assert_eq!(pages.next().unwrap().unwrap().len(), 100);
+    assert_eq!(server.requests().len(), 1);
+
+    // As outras páginas, e o fim depois da incompleta
+    let rest: Vec<usize> = pages.map(|page| page.unwrap().len()).collect();
+    assert_eq!(rest, vec![100, 50]);
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn event_pages_stop_after_an_error() {
+    // A segunda página falha: a primeira já foi entregue, e o iterador termina no erro
+    let server = MockServer::start(Duration::ZERO, |request| {
+        if request.path.ends_with("page=1") {
+            Response::ok(generate_events_json(&GenerateOptions { events: 100, ..GenerateOptions::default() }))
+        } else {
+            Response::with_status("500 Internal Server Error", r#"{"message": "Server Error"}"#)
+        }
+    });
+    let client = client(&server, None, None);
+    let mut pages = client.event_pages(&Target::parse("alice"), true).unwrap();
+
+    assert_eq!(pages.next().unwrap().unwrap().len(), 100);
+    assert!(matches!(pages.next(), Some(Err(ActivityError::ApiError { status: 500, .. }))));
+    assert!(pages.next().is_none());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn collect_while_keeps_the_pages_fetched_before_stopping() {
+    // Simula um Ctrl-C enquanto a segunda página é servida: a flag só é vista
+    // antes da próxima requisição, e as duas páginas já buscadas são devolvidas
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&interrupted);
+    let server = MockServer::start(Duration::ZERO, move |request| {
+        if request.path.ends_with("page=2") {
+            flag.store(true, Ordering::SeqCst);
+        }
+        Response::ok(generate_events_json(&GenerateOptions { events: 100, ..GenerateOptions::default() }))
+    });
+    let client = client(&server, None, None);
+
+    let pages = client
+        .event_pages(&Target::parse("alice"), true)
+        .unwrap()
+        .collect_while(|_| !interrupted.load(Ordering::SeqCst))
+        .unwrap();
+
+    assert_eq!(pages.outcome.events.len(), 200);
+    assert_eq!(pages.bodies.len(), 2);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn total_pages_comes_from_the_link_header() {
+    // Como a API: rel="last" em todas as páginas, menos na última
+    let server = MockServer::start(Duration::ZERO, |request| {
+        let last = request.path.ends_with("page=3");
+        let body = generate_events_json(&GenerateOptions {
+            events: if last { 50 } else { 100 },
+            ..GenerateOptions::default()
+        });
+        if last {
+            return Response::ok(body);
+        }
+        Response::ok(body).header("Link", "<http://localhost/users/alice/events?per_page=100&page=3>; rel=\"last\"")
+    });
+
+    let mut seen = Vec::new();
+    let pages = client(&server, None, None)
+        .event_pages(&Target::parse("alice"), true)
+        .unwrap()
+        .collect_while(|pages| {
+            seen.push((pages.pages_fetched(), pages.total_pages(), pages.events_fetched()));
+            true
+        })
+        .unwrap();
+
+    assert_eq!(pages.outcome.events.len(), 250);
+    // Antes da primeira página não há total; a última (sem Link) mantém o que já se sabia
+    assert_eq!(seen, vec![(0, None, 0), (1, Some(3), 100), (2, Some(3), 200)]);
+
+    // --max-events corta o total também
+    let mut totals = Vec::new();
+    client(&server, None, Some(150))
+        .event_pages(&Target::parse("alice"), true)
+        .unwrap()
+        .collect_while(|pages| {
+            totals.push(pages.total_pages());
+            true
+        })
+        .unwrap();
+    assert_eq!(totals, vec![None, Some(2)]);
+
+    // Sem --all, uma página só
+    let client = client(&server, None, None);
+    assert_eq!(client.event_pages(&Target::parse("alice"), false).unwrap().total_pages(), Some(1));
+}
@@ -0,0 +1,86 @@
+// Testes de GitHubClient::fetch_parallel contra o servidor falso de tests/common
+// O servidor responde depois de um atraso artificial,
+// então dá para medir se as buscas realmente acontecem ao mesmo tempo
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{MockServer, Response, USER_EVENTS};
+use github_activity::api::{GitHubClient, Target};
+use github_activity::ActivityError;
+
+const DELAY: Duration = Duration::from_millis(300);
+
+// /users/ghost/... não existe; /users/limited/... estourou o limite; o resto recebe a fixture
+fn mock_server() -> String {
+    let server = MockServer::start(DELAY, |request| {
+        if request.path.starts_with("/users/ghost/") {
+            Response::with_status("404 Not Found", r#"{"message": "Not Found"}"#)
+        } else if request.path.starts_with("/users/limited/") {
+            Response::with_status("403 Forbidden", r#"{"message": "API rate limit exceeded"}"#)
+                .header("x-ratelimit-remaining", "0")
+        } else {
+            Response::ok(USER_EVENTS)
+        }
+    });
+    server.url
+}
+
+fn client(base_url: String) -> GitHubClient {
+    GitHubClient::builder().base_url(base_url).build()
+}
+
+fn users(names: &[&str]) -> Vec<Target> {
+    names.iter().map(|name| Target::parse(name)).collect()
+}
+
+#[test]
+fn three_users_in_parallel_take_about_one_delay() {
+    let client = client(mock_server());
+
+    let started = Instant::now();
+    let results = client.fetch_parallel(&users(&["alice", "bob", "carol"]), false, 3);
+    let elapsed = started.elapsed();
+
+    // Em sequência seriam 3 atrasos; em paralelo, pouco mais que 1
+    assert!(elapsed < DELAY * 2, "took {:?}", elapsed);
+    assert_eq!(results.len(), 3);
+    for result in results {
+        assert_eq!(result.unwrap().unwrap().outcome.events.len(), 7);
+    }
+}
+
+#[test]
+fn one_job_fetches_in_sequence() {
+    let client = client(mock_server());
+
+    let started = Instant::now();
+    let results = client.fetch_parallel(&users(&["alice", "bob"]), false, 1);
+
+    assert!(started.elapsed() >= DELAY * 2);
+    assert!(results.iter().all(|result| matches!(result, Some(Ok(_)))));
+}
+
+#[test]
+fn a_failing_user_does_not_stop_the_others() {
+    let client = client(mock_server());
+
+    let results = client.fetch_parallel(&users(&["alice", "ghost", "octocat/Hello-World"]), false, 3);
+
+    assert!(matches!(results[0], Some(Ok(_))));
+    assert!(matches!(&results[1], Some(Err(ActivityError::UserNotFound(name))) if name == "ghost"));
+    assert!(matches!(results[2], Some(Ok(_))));
+}
+
+#[test]
+fn rate_limit_skips_the_users_not_started_yet() {
+    let client = client(mock_server());
+
+    // Com uma thread só a ordem é garantida: alice, limited e só então carol
+    let results = client.fetch_parallel(&users(&["alice", "limited", "carol"]), false, 1);
+
+    assert!(matches!(results[0], Some(Ok(_))));
+    assert!(matches!(results[1], Some(Err(ActivityError::RateLimited { .. }))));
+    assert!(results[2].is_none());
+}
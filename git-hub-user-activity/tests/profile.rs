@@ -0,0 +1,49 @@
+// Testes de GitHubClient::fetch_user_profile contra o servidor falso de tests/common
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MockServer, Response};
+use github_activity::api::GitHubClient;
+use github_activity::ActivityError;
+
+const TORVALDS: &str = r#"{"login": "torvalds", "id": 1024025, "name": "Linus Torvalds",
+    "company": "Linux Foundation", "blog": "", "location": "Portland, OR", "email": null,
+    "public_repos": 4, "public_gists": 0, "followers": 180432, "following": 0}"#;
+
+fn profile_server() -> MockServer {
+    MockServer::start(Duration::ZERO, |request| {
+        if request.path == "/users/torvalds" {
+            Response::ok(TORVALDS)
+        } else {
+            Response::with_status("404 Not Found", r#"{"message": "Not Found"}"#)
+        }
+    })
+}
+
+#[test]
+fn profile_comes_from_the_user_endpoint() {
+    let server = profile_server();
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    let profile = client.fetch_user_profile("torvalds").unwrap();
+
+    assert_eq!(profile.name.as_deref(), Some("Linus Torvalds"));
+    assert_eq!(profile.followers, 180_432);
+    assert_eq!(profile.public_repos, 4);
+    // Uma requisição só, com os mesmos headers dos eventos
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].header("accept"), Some("application/vnd.github+json"));
+}
+
+#[test]
+fn unknown_user_is_user_not_found() {
+    let server = profile_server();
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    let error = client.fetch_user_profile("ghost").unwrap_err();
+
+    assert!(matches!(error, ActivityError::UserNotFound(name) if name == "ghost"));
+}
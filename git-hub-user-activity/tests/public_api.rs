@@ -0,0 +1,64 @@
+// Testes de integração: ficam FORA de src/ e usam o crate como um usuário externo usaria
+// CONCEITO: tests/
+// Cada arquivo em tests/ é compilado como um crate separado, que só enxerga
+// os itens públicos da biblioteca - se algo precisar de `crate::`, não é API pública
+//
+// Nenhum teste aqui acessa a rede: os eventos vêm das fixtures em tests/fixtures/
+
+use github_activity::api::fetch_user_events;
+use github_activity::{format_event, parse_events, ActivityError, EventKind, EventPayload, ParseOutcome};
+
+const USER_EVENTS: &str = include_str!("fixtures/user_events.json");
+const RELEASE_EVENTS: &str = include_str!("fixtures/release_events.json");
+
+#[test]
+fn fetch_functions_are_reachable_from_outside() {
+    // Só confere que o caminho e a assinatura são públicos; chamar exigiria rede
+    let fetch: fn(&str) -> Result<ParseOutcome, ActivityError> = fetch_user_events;
+    let fetch_repo: fn(&str, &str) -> Result<ParseOutcome, ActivityError> =
+        github_activity::fetch_repo_events;
+    let _ = (fetch, fetch_repo);
+}
+
+#[test]
+fn parse_and_format_a_fixture() {
+    let outcome = parse_events(USER_EVENTS).unwrap();
+    assert!(outcome.warnings.is_empty());
+
+    let lines: Vec<String> = outcome.events.iter().map(format_event).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "Pushed 2 commits to feature/json-parser in octocat/Hello-World",
+            "Merged pull request #1347 'Amazing new feature: {json} é \"fun\"' in octocat/Hello-World",
+            "Opened issue #42 'Found a bug' in octocat/Spoon-Knife",
+            "Created tag 'v1.2.0' in octocat/linguist",
+            "Created a repository in octocat/new-repo",
+            "Starred torvalds/linux",
+            "Performed SponsorshipEvent in octocat/Hello-World",
+        ]
+    );
+}
+
+#[test]
+fn events_can_be_matched_by_kind_and_payload() {
+    let outcome = parse_events(RELEASE_EVENTS).unwrap();
+
+    let drafts = outcome
+        .events
+        .iter()
+        .filter(|event| event.kind == EventKind::Release)
+        .filter(|event| matches!(event.payload, EventPayload::ReleaseEvent { draft: true, .. }))
+        .count();
+    assert_eq!(drafts, 1);
+}
+
+#[test]
+fn invalid_input_is_a_public_error() {
+    match parse_events("not json") {
+        Err(ActivityError::ParseError { message, .. }) => {
+            assert!(message.starts_with("Expected JSON array"), "{}", message);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
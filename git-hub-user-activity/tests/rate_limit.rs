@@ -0,0 +1,64 @@
+// O limite de requisições da última resposta (GitHubClient::rate_limit), para o rodapé
+// "API rate limit: 54/60 remaining", contra o servidor falso de tests/common
+
+mod common;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use common::{MockServer, Response, USER_EVENTS};
+use github_activity::api::{GitHubClient, RateLimitInfo, Target};
+use github_activity::date::Timestamp;
+
+// Cada resposta gasta uma requisição da cota, como a API
+fn counting_server() -> MockServer {
+    let remaining = AtomicU64::new(60);
+    MockServer::start(Duration::ZERO, move |_| {
+        let left = remaining.fetch_sub(1, Ordering::SeqCst) - 1;
+        Response::ok(USER_EVENTS)
+            .header("x-ratelimit-limit", "60")
+            .header("x-ratelimit-remaining", &left.to_string())
+            .header("x-ratelimit-reset", "1714584720")
+    })
+}
+
+#[test]
+fn the_last_response_wins() {
+    let server = counting_server();
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+    assert_eq!(client.rate_limit(), None);
+
+    client.fetch_target_pages(&Target::parse("alice"), false).unwrap();
+    // O corpo não é um perfil, mas a resposta chegou: o limite dela conta
+    client.fetch_user_profile("alice").unwrap_err();
+
+    assert_eq!(
+        client.rate_limit(),
+        Some(RateLimitInfo {
+            limit: 60,
+            remaining: 58,
+            reset_at: Some(Timestamp::from_epoch_seconds(1714584720)),
+        })
+    );
+}
+
+#[test]
+fn clones_share_what_they_learned() {
+    // fetch_parallel busca com clones do cliente em outras threads
+    let server = counting_server();
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    let clone = client.clone();
+    clone.fetch_target_pages(&Target::parse("alice"), false).unwrap();
+
+    assert_eq!(client.rate_limit().map(|info| info.remaining), Some(59));
+}
+
+#[test]
+fn responses_without_the_headers_leave_it_unknown() {
+    let server = MockServer::events();
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    client.fetch_target_pages(&Target::parse("alice"), false).unwrap();
+    assert_eq!(client.rate_limit(), None);
+}
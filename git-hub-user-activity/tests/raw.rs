@@ -0,0 +1,43 @@
+// GitHubClient::fetch_raw (--raw) contra o servidor falso de tests/common: o corpo volta
+// exatamente como o servidor mandou, mesmo quando não seria um array de eventos válido
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MockServer, Response, USER_EVENTS};
+use github_activity::api::{GitHubClient, Target};
+use github_activity::ActivityError;
+
+#[test]
+fn the_body_is_returned_untouched() {
+    let server = MockServer::events();
+    let client = GitHubClient::builder().base_url(server.url.as_str()).per_page(5).build();
+
+    let body = client.fetch_raw(&Target::parse("octocat")).unwrap();
+
+    assert_eq!(body, USER_EVENTS);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/users/octocat/events?per_page=5");
+}
+
+#[test]
+fn a_body_the_parser_would_reject_still_comes_back() {
+    let server = MockServer::start(Duration::ZERO, |_| Response::ok(r#"{"not": "events"}"#));
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    assert_eq!(client.fetch_raw(&Target::parse("rust-lang/rust")).unwrap(), r#"{"not": "events"}"#);
+    assert!(client.fetch_repo_events("rust-lang", "rust").is_err());
+}
+
+#[test]
+fn a_missing_user_is_still_an_error() {
+    let server = MockServer::start(Duration::ZERO, |_| Response::with_status("404 Not Found", r#"{"message": "Not Found"}"#));
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    match client.fetch_raw(&Target::parse("ghost-user")) {
+        Err(ActivityError::UserNotFound(username)) => assert_eq!(username, "ghost-user"),
+        other => panic!("unexpected {:?}", other),
+    }
+}
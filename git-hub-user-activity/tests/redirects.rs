@@ -0,0 +1,87 @@
+// Redirecionamentos (301/302) contra o servidor falso de tests/common: um login renomeado
+// é seguido e anotado; um laço ou um outro host viram NetworkError
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MockServer, Response, USER_EVENTS};
+use github_activity::api::GitHubClient;
+use github_activity::ActivityError;
+
+fn moved(location: &str) -> Response {
+    Response::with_status("301 Moved Permanently", "").header("Location", location)
+}
+
+#[test]
+fn a_renamed_user_is_followed_and_reported() {
+    let server = MockServer::start(Duration::ZERO, |request| {
+        if request.path.starts_with("/users/old-name/") {
+            // O caminho, sem o host: a query (?per_page...) vai junto
+            moved(&request.path.replace("old-name", "new-name"))
+        } else {
+            Response::ok(USER_EVENTS)
+        }
+    });
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    let outcome = client.fetch_user_events("old-name").unwrap();
+
+    assert!(!outcome.events.is_empty());
+    let paths: Vec<String> = server.requests().into_iter().map(|request| request.path).collect();
+    assert_eq!(paths, vec!["/users/old-name/events", "/users/new-name/events"]);
+    assert_eq!(client.renamed_login("old-name").as_deref(), Some("new-name"));
+    assert_eq!(client.renamed_login("OLD-NAME").as_deref(), Some("new-name"));
+    assert_eq!(client.renamed_login("someone-else"), None);
+}
+
+#[test]
+fn a_redirect_to_the_same_login_is_not_a_rename() {
+    let server = MockServer::start(Duration::ZERO, |request| {
+        if request.path == "/users/octocat/events" {
+            Response::with_status("302 Found", "").header("Location", "/users/Octocat/events?page=1")
+        } else {
+            Response::ok(USER_EVENTS)
+        }
+    });
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    client.fetch_user_events("octocat").unwrap();
+    assert_eq!(server.requests().len(), 2);
+    assert_eq!(client.renamed_login("octocat"), None);
+}
+
+#[test]
+fn redirect_loops_are_rejected() {
+    let server = MockServer::start(Duration::ZERO, |request| {
+        if request.path.starts_with("/users/a/") {
+            moved("/users/b/events")
+        } else {
+            moved("/users/a/events")
+        }
+    });
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    let error = client.fetch_user_events("a").unwrap_err();
+
+    assert!(matches!(error, ActivityError::NetworkError { .. }), "{:?}", error);
+    assert!(error.to_string().contains("redirect loop"), "{}", error);
+    // a -> b -> a: o terceiro pedido não acontece
+    assert_eq!(server.requests().len(), 2);
+    assert_eq!(client.renamed_login("a"), None);
+}
+
+#[test]
+fn redirects_to_another_host_are_rejected() {
+    let other = MockServer::events();
+    let location = format!("{}/users/new-name/events", other.url);
+    let server = MockServer::start(Duration::ZERO, move |_| moved(&location));
+    let client = GitHubClient::builder().base_url(server.url.as_str()).token("ghp_secret").build();
+
+    let error = client.fetch_user_events("old-name").unwrap_err();
+
+    assert!(matches!(error, ActivityError::NetworkError { .. }), "{:?}", error);
+    assert!(error.to_string().contains("another host"), "{}", error);
+    // O token nunca chega ao outro servidor
+    assert!(other.requests().is_empty());
+}
@@ -0,0 +1,45 @@
+// Confere os headers que chegam de fato ao servidor (falso, de tests/common)
+// em cada endpoint, e não só o que request_headers monta
+
+mod common;
+
+use common::MockServer;
+use github_activity::api::{GitHubClient, GitHubClientBuilder, Target};
+
+fn fetch(server: &MockServer, target: &str, builder: GitHubClientBuilder) {
+    let client = builder.base_url(server.url.as_str()).build();
+    client.fetch_target_pages(&Target::parse(target), false).unwrap();
+}
+
+#[test]
+fn every_endpoint_sends_the_github_headers() {
+    let server = MockServer::events();
+
+    fetch(&server, "alice", GitHubClient::builder());
+    fetch(&server, "octocat/Hello-World", GitHubClient::builder());
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    for request in &requests {
+        assert_eq!(request.header("user-agent"), Some("github-activity-cli/1.0"), "{}", request.path);
+        assert_eq!(request.header("accept"), Some("application/vnd.github+json"), "{}", request.path);
+        assert_eq!(request.header("x-github-api-version"), Some("2022-11-28"), "{}", request.path);
+        // Sem token configurado, nada de Authorization
+        assert_eq!(request.header("authorization"), None, "{}", request.path);
+    }
+}
+
+#[test]
+fn token_and_api_version_come_from_the_options() {
+    let server = MockServer::events();
+
+    fetch(
+        &server,
+        "alice",
+        GitHubClient::builder().token("gho_secret").api_version("2026-03-10"),
+    );
+
+    let request = &server.requests()[0];
+    assert_eq!(request.header("authorization"), Some("Bearer gho_secret"));
+    assert_eq!(request.header("x-github-api-version"), Some("2026-03-10"));
+}
@@ -0,0 +1,69 @@
+// Limite secundário (403 + Retry-After) contra o servidor falso de tests/common
+// O servidor barra a PRIMEIRA requisição e deixa as seguintes passarem
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use common::{MockServer, Response, USER_EVENTS};
+use github_activity::api::{GitHubClient, Target};
+use github_activity::ActivityError;
+
+const LIMIT_MESSAGE: &str =
+    r#"{"message": "You have exceeded a secondary rate limit. Please wait a few minutes before you try again."}"#;
+
+fn limited_once_server() -> MockServer {
+    let served = AtomicUsize::new(0);
+    MockServer::start(Duration::ZERO, move |_| {
+        if served.fetch_add(1, Ordering::SeqCst) == 0 {
+            Response::with_status("403 Forbidden", LIMIT_MESSAGE)
+                .header("retry-after", "1")
+                .header("x-ratelimit-remaining", "4990")
+        } else {
+            Response::ok(USER_EVENTS)
+        }
+    })
+}
+
+#[test]
+fn wait_on_limit_waits_and_retries_once() {
+    let server = limited_once_server();
+    let client = GitHubClient::builder().base_url(server.url.as_str()).wait_on_limit(true).build();
+
+    let started = Instant::now();
+    let pages = client.fetch_target_pages(&Target::parse("alice"), false).unwrap();
+
+    assert_eq!(pages.outcome.events.len(), 7);
+    assert_eq!(server.requests().len(), 2);
+    // Esperou o Retry-After antes da segunda tentativa
+    assert!(started.elapsed() >= Duration::from_secs(1), "took {:?}", started.elapsed());
+}
+
+#[test]
+fn without_wait_on_limit_it_fails_fast() {
+    let server = limited_once_server();
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    let started = Instant::now();
+    let error = client.fetch_target_pages(&Target::parse("alice"), false).unwrap_err();
+
+    assert!(matches!(error, ActivityError::SecondaryRateLimit { retry_after: 1 }), "{:?}", error);
+    assert!(error.to_string().contains("wait 1 second"), "{}", error);
+    assert_eq!(server.requests().len(), 1);
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn a_second_limit_is_not_retried_again() {
+    // Barra todas: com --wait-on-limit são duas tentativas, e o erro volta
+    let server = MockServer::start(Duration::ZERO, |_| {
+        Response::with_status("403 Forbidden", LIMIT_MESSAGE).header("retry-after", "1")
+    });
+    let client = GitHubClient::builder().base_url(server.url.as_str()).wait_on_limit(true).build();
+
+    let error = client.fetch_target_pages(&Target::parse("alice"), false).unwrap_err();
+
+    assert!(matches!(error, ActivityError::SecondaryRateLimit { .. }));
+    assert_eq!(server.requests().len(), 2);
+}
@@ -0,0 +1,135 @@
+// Testes de storage::Database (--export-sqlite), só com a feature "sqlite":
+//   cargo test --features sqlite
+// O banco é um arquivo de verdade num diretório temporário, lido de volta com o rusqlite
+#![cfg(feature = "sqlite")]
+
+use std::path::PathBuf;
+use std::{fs, process};
+
+use github_activity::parse_events;
+use github_activity::storage::{Database, SCHEMA_VERSION};
+
+const USER_EVENTS: &str = include_str!("fixtures/user_events.json");
+
+// Um diretório só deste teste (os testes rodam em paralelo)
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("github-activity-sqlite-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn export(path: &str) -> usize {
+    let outcome = parse_events(USER_EVENTS).unwrap();
+    let mut database = Database::open(path).unwrap();
+    database.upsert("octocat", &outcome.events, &[USER_EVENTS.to_string()]).unwrap()
+}
+
+#[test]
+fn exporting_twice_does_not_duplicate_rows() {
+    let dir = temp_dir("twice");
+    let path = dir.join("activity.db");
+    let path = path.to_str().unwrap();
+    let events = parse_events(USER_EVENTS).unwrap().events.len();
+
+    assert_eq!(export(path), events);
+    // A segunda vez não acrescenta nada: as mesmas linhas são atualizadas
+    assert_eq!(export(path), 0);
+
+    let database = Database::open(path).unwrap();
+    assert_eq!(database.count().unwrap(), events);
+    assert_eq!(database.schema_version().unwrap(), SCHEMA_VERSION);
+
+    let connection = rusqlite::Connection::open(path).unwrap();
+    let distinct: i64 = connection.query_row("SELECT COUNT(DISTINCT id) FROM events", [], |row| row.get(0)).unwrap();
+    assert_eq!(distinct as usize, events);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn columns_hold_the_event_fields() {
+    let dir = temp_dir("columns");
+    let path = dir.join("activity.db");
+    let path = path.to_str().unwrap();
+    export(path);
+
+    let connection = rusqlite::Connection::open(path).unwrap();
+    let row = connection
+        .query_row(
+            "SELECT username, type, repo, action, commit_count, created_at, raw_payload FROM events WHERE id = ?1",
+            [38941057321_i64],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<i64>>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                    row.get::<_, String>(6)?,
+                ))
+            },
+        )
+        .unwrap();
+    let (username, kind, repo, action, commit_count, created_at, raw_payload) = row;
+
+    assert_eq!(username, "octocat");
+    assert_eq!(kind, "PushEvent");
+    assert_eq!(repo, "octocat/Hello-World");
+    assert_eq!(action, None);
+    assert_eq!(commit_count, Some(2));
+    assert!(created_at.unwrap().ends_with('Z'));
+    // O payload inteiro, compacto, inclusive o que o programa não interpreta
+    assert!(raw_payload.starts_with(r#"{"repository_id":1296269,"push_id":18339571562,"size":2"#), "{}", raw_payload);
+
+    // Um evento com action: a coluna vem do payload
+    let actions: Vec<String> = connection
+        .prepare("SELECT action FROM events WHERE action IS NOT NULL ORDER BY id")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert!(!actions.is_empty());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn unwritable_and_foreign_files_are_clear_errors() {
+    let dir = temp_dir("errors");
+
+    // Um diretório que não existe: o SQLite não cria diretórios
+    let missing = dir.join("missing").join("activity.db");
+    let error = Database::open(missing.to_str().unwrap()).err().unwrap();
+    assert!(error.to_string().contains("not writable"), "{}", error);
+
+    let text = dir.join("notes.txt");
+    fs::write(&text, "these are not the events you are looking for, not at all").unwrap();
+    let error = Database::open(text.to_str().unwrap()).err().unwrap();
+    assert!(error.to_string().contains("not a SQLite database"), "{}", error);
+    assert_eq!(error.exit_code(), github_activity::error::EXIT_IO);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn a_locked_database_is_reported() {
+    let dir = temp_dir("locked");
+    let path = dir.join("activity.db");
+    let path = path.to_str().unwrap();
+    export(path);
+
+    // Outra conexão segura a trava de escrita até o fim do teste
+    let other = rusqlite::Connection::open(path).unwrap();
+    other.execute_batch("BEGIN EXCLUSIVE").unwrap();
+
+    // Depois de esperar o BUSY_TIMEOUT, já ao ler a versão do esquema
+    let outcome = parse_events(USER_EVENTS).unwrap();
+    let error = Database::open(path)
+        .and_then(|mut database| database.upsert("octocat", &outcome.events, &[]))
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("locked by another program"), "{}", error);
+
+    other.execute_batch("ROLLBACK").unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,97 @@
+// --starred contra o servidor falso de tests/common: outro formato de resposta
+// (um array de repositórios) com a mesma paginação dos eventos
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MockServer, Response};
+use github_activity::api::GitHubClient;
+use github_activity::display::format_starred_repository;
+use github_activity::ActivityError;
+
+const STARRED: &str = include_str!("fixtures/starred.json");
+
+// Um usuário com `total` estrelas, entregues em páginas do tamanho pedido (30 sem per_page)
+fn paginated_server(total: usize) -> MockServer {
+    MockServer::start(Duration::ZERO, move |request| {
+        let param = |name: &str| {
+            let query = request.path.split_once('?').map(|(_, query)| query).unwrap_or("");
+            query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+                .and_then(|value| value.parse::<usize>().ok())
+        };
+        let per_page = param("per_page").unwrap_or(30);
+        let first = (param("page").unwrap_or(1) - 1) * per_page;
+
+        let items: Vec<String> = (first..total.min(first + per_page))
+            .map(|n| format!(r#"{{"full_name": "owner/repo-{}", "stargazers_count": {}}}"#, n, n))
+            .collect();
+        Response::ok(format!("[{}]", items.join(",")))
+    })
+}
+
+fn paths(server: &MockServer) -> Vec<String> {
+    server.requests().into_iter().map(|request| request.path).collect()
+}
+
+#[test]
+fn lists_the_starred_repositories() {
+    let server = MockServer::start(Duration::ZERO, |_| Response::ok(STARRED));
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    let repositories = client.fetch_starred("octocat", false).unwrap();
+    let lines: Vec<String> = repositories.iter().map(format_starred_repository).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "- torvalds/linux (★ 171,234) — Linux kernel source tree",
+            "- rust-lang/rust (★ 93,456) — Empowering everyone to build reliable and efficient software.",
+            "- octocat/Hello-World (★ 2,612)",
+        ]
+    );
+    assert_eq!(repositories[1].language.as_deref(), Some("Rust"));
+    assert_eq!(paths(&server), vec!["/users/octocat/starred"]);
+}
+
+#[test]
+fn all_pages_until_a_short_one() {
+    let server = paginated_server(25);
+    let client = GitHubClient::builder().base_url(server.url.as_str()).per_page(10).build();
+
+    assert_eq!(client.fetch_starred("alice", true).unwrap().len(), 25);
+    assert_eq!(
+        paths(&server),
+        vec![
+            "/users/alice/starred?per_page=10&page=1",
+            "/users/alice/starred?per_page=10&page=2",
+            "/users/alice/starred?per_page=10&page=3",
+        ]
+    );
+}
+
+#[test]
+fn max_events_and_max_pages_stop_the_pages() {
+    let server = paginated_server(1000);
+    let client = GitHubClient::builder().base_url(server.url.as_str()).per_page(10).max_events(15).build();
+    let repositories = client.fetch_starred("alice", true).unwrap();
+    assert_eq!(repositories.len(), 15);
+    assert_eq!(repositories[14].full_name, "owner/repo-14");
+    assert_eq!(paths(&server).len(), 2);
+
+    let server = paginated_server(1000);
+    let client = GitHubClient::builder().base_url(server.url.as_str()).per_page(10).max_pages(3).build();
+    assert_eq!(client.fetch_starred("alice", true).unwrap().len(), 30);
+    assert_eq!(paths(&server).len(), 3);
+}
+
+#[test]
+fn unknown_user_is_user_not_found() {
+    let server = MockServer::start(Duration::ZERO, |_| {
+        Response::with_status("404 Not Found", r#"{"message": "Not Found"}"#)
+    });
+    let client = GitHubClient::builder().base_url(server.url.as_str()).build();
+
+    assert!(matches!(client.fetch_starred("ghost", false), Err(ActivityError::UserNotFound(user)) if user == "ghost"));
+}
@@ -0,0 +1,85 @@
+// Confere o que --post-webhook manda de fato ao servidor (falso, de tests/common):
+// método, headers, corpo, as novas tentativas e o que o erro deixa aparecer da URL
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use common::{MockServer, Response};
+use github_activity::api::GitHubClient;
+use github_activity::error::{ActivityError, EXIT_WEBHOOK};
+use github_activity::models::{EventKind, EventPayload, GitHubEvent};
+use github_activity::webhook;
+
+fn star(repo: &str) -> GitHubEvent {
+    GitHubEvent::new(EventKind::Watch, repo.to_string(), EventPayload::WatchEvent, None)
+}
+
+fn ok_server() -> MockServer {
+    MockServer::start(Duration::ZERO, |_| Response::ok("ok"))
+}
+
+#[test]
+fn posts_json_without_the_github_token() {
+    let server = ok_server();
+    let client = GitHubClient::builder().token("gho_secret").build();
+    let payload = webhook::format_payload("octocat", &[star("a/b")]);
+
+    client.post_webhook(&format!("{}/hooks/abc", server.url), &payload).unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.path, "/hooks/abc");
+    assert_eq!(request.header("content-type"), Some("application/json"));
+    // O token é do GitHub: um webhook de terceiros nunca o recebe
+    assert_eq!(request.header("authorization"), None);
+    assert!(request.body.starts_with(r#"{"source":"octocat","count":1,"events":[{"#), "{}", request.body);
+}
+
+#[test]
+fn slack_payload_is_a_text_object() {
+    let server = ok_server();
+    let payload = webhook::format_slack_payload("octocat", &[star("a/b")]);
+
+    GitHubClient::builder().build().post_webhook(&server.url, &payload).unwrap();
+
+    assert_eq!(
+        server.requests()[0].body,
+        r#"{"text":"*octocat*: 1 new event\n• <https://github.com/a/b|Starred a/b>"}"#
+    );
+}
+
+#[test]
+fn server_errors_are_retried() {
+    let calls = AtomicUsize::new(0);
+    let server = MockServer::start(Duration::ZERO, move |_| {
+        if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+            Response::with_status("500 Internal Server Error", "try later")
+        } else {
+            Response::ok("ok")
+        }
+    });
+
+    GitHubClient::builder().build().post_webhook(&server.url, "{}").unwrap();
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn client_errors_fail_without_showing_the_path() {
+    let server = MockServer::start(Duration::ZERO, |_| Response::with_status("400 Bad Request", "invalid_payload"));
+    let url = format!("{}/services/T000/B000/secret-token", server.url);
+
+    let error = GitHubClient::builder().build().post_webhook(&url, "{}").unwrap_err();
+
+    // Um 4xx é definitivo: sem novas tentativas
+    assert_eq!(server.requests().len(), 1);
+    assert!(matches!(error, ActivityError::WebhookError { .. }), "{:?}", error);
+    assert_eq!(error.exit_code(), EXIT_WEBHOOK);
+    let message = error.to_string();
+    assert!(message.contains("127.0.0.1"), "{}", message);
+    assert!(message.contains("HTTP 400: invalid_payload"), "{}", message);
+    assert!(!message.contains("secret-token"), "{}", message);
+}